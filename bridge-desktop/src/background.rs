@@ -3,8 +3,8 @@
 use async_trait::async_trait;
 use bridge_traits::{
     background::{
-        BackgroundExecutor, LifecycleChangeStream, LifecycleObserver, LifecycleState,
-        TaskConstraints, TaskId, TaskStatus,
+        BackgroundExecutor, LifecycleChangeStream, LifecycleObserver, LifecycleState, PeriodicTask,
+        ScheduleHandle, SpawnedTask, TaskConstraints, TaskHandle, TaskId, TaskPriority, TaskStatus,
     },
     error::{BridgeError, Result},
     network::{NetworkInfo, NetworkMonitor, NetworkStatus, NetworkType},
@@ -12,21 +12,34 @@ use bridge_traits::{
 };
 use core_async::sync::{oneshot, RwLock};
 use core_async::task::JoinHandle;
-use core_async::time::sleep;
+use core_async::time::{sleep, Instant};
 use futures_util::{future::BoxFuture, FutureExt};
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 use std::time::Duration;
+use tokio::time::MissedTickBehavior;
 use tracing::{debug, warn};
 
 type TaskHandler = Arc<dyn Fn() -> BoxFuture<'static, Result<()>> + Send + Sync>;
 
+/// Number of task handlers that may run at once by default
+pub const DEFAULT_MAX_CONCURRENT_TASKS: usize = 4;
+
 /// Tokio-based background executor for desktop.
+///
+/// At most [`DEFAULT_MAX_CONCURRENT_TASKS`] handlers run at once (see
+/// [`with_max_concurrent_tasks`](Self::with_max_concurrent_tasks)). Runs that
+/// find every slot taken wait in a queue ordered by
+/// [`TaskConstraints::priority`], and runs whose
+/// [`TaskConstraints::deadline`] passes before they start are dropped.
 pub struct TokioBackgroundExecutor {
     tasks: Arc<RwLock<HashMap<TaskId, TaskInfo>>>,
     handlers: Arc<RwLock<HashMap<String, TaskHandler>>>,
     network_monitor: Option<Arc<dyn NetworkMonitor>>,
     clock: Arc<dyn Clock>,
+    run_queue: Arc<RunQueue>,
+    lifecycle_observer: Option<Arc<dyn LifecycleObserver>>,
 }
 
 struct TaskInfo {
@@ -60,9 +73,23 @@ impl TokioBackgroundExecutor {
             handlers: Arc::new(RwLock::new(HashMap::new())),
             network_monitor: monitor,
             clock,
+            run_queue: Arc::new(RunQueue::new(DEFAULT_MAX_CONCURRENT_TASKS)),
+            lifecycle_observer: None,
         }
     }
 
+    /// Pause periodic schedules while the app is in the background.
+    pub fn with_lifecycle_observer(mut self, observer: Arc<dyn LifecycleObserver>) -> Self {
+        self.lifecycle_observer = Some(observer);
+        self
+    }
+
+    /// Limit how many task handlers may run at once (at least one).
+    pub fn with_max_concurrent_tasks(mut self, limit: usize) -> Self {
+        self.run_queue = Arc::new(RunQueue::new(limit.max(1)));
+        self
+    }
+
     fn now_millis(clock: &dyn Clock) -> i64 {
         clock.unix_timestamp_millis()
     }
@@ -155,6 +182,29 @@ impl TokioBackgroundExecutor {
         }
     }
 
+    async fn mark_cancelled(tasks: &RwLock<HashMap<TaskId, TaskInfo>>, id: &TaskId) {
+        let mut tasks = tasks.write().await;
+        if let Some(info) = tasks.get_mut(id) {
+            info.status = TaskStatus::Cancelled;
+            info.next_run = None;
+        }
+    }
+
+    /// Drop a run that is about to start after its deadline
+    async fn drop_if_expired(
+        tasks: &RwLock<HashMap<TaskId, TaskInfo>>,
+        id: &TaskId,
+        constraints: &TaskConstraints,
+    ) -> bool {
+        if !constraints.is_expired(Instant::now()) {
+            return false;
+        }
+        warn!(task_id = %id.0, "Task missed its deadline; dropping it");
+        Self::mark_cancelled(tasks, id).await;
+        true
+    }
+
+    #[allow(clippy::too_many_arguments)]
     async fn run_recurring_task(
         tasks: Arc<RwLock<HashMap<TaskId, TaskInfo>>>,
         id: TaskId,
@@ -164,6 +214,7 @@ impl TokioBackgroundExecutor {
         mut cancel_rx: oneshot::Receiver<()>,
         monitor: Option<Arc<dyn NetworkMonitor>>,
         clock: Arc<dyn Clock>,
+        run_queue: Arc<RunQueue>,
     ) {
         let mut ticker = tokio::time::interval(period);
         let period_millis = Self::duration_to_millis(period);
@@ -187,6 +238,17 @@ impl TokioBackgroundExecutor {
                         continue;
                     }
 
+                    let permit = tokio::select! {
+                        _ = &mut cancel_rx => {
+                            Self::mark_cancelled(&tasks, &id).await;
+                            break;
+                        }
+                        permit = run_queue.acquire(constraints.priority) => permit,
+                    };
+                    if Self::drop_if_expired(&tasks, &id, &constraints).await {
+                        break;
+                    }
+
                     {
                         let mut tasks = tasks.write().await;
                         if let Some(info) = tasks.get_mut(&id) {
@@ -195,6 +257,7 @@ impl TokioBackgroundExecutor {
                     }
 
                     let result = handler().await;
+                    drop(permit);
 
                     let mut tasks = tasks.write().await;
                     if let Some(info) = tasks.get_mut(&id) {
@@ -214,6 +277,7 @@ impl TokioBackgroundExecutor {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn run_one_time_task(
         tasks: Arc<RwLock<HashMap<TaskId, TaskInfo>>>,
         id: TaskId,
@@ -223,6 +287,7 @@ impl TokioBackgroundExecutor {
         mut cancel_rx: oneshot::Receiver<()>,
         monitor: Option<Arc<dyn NetworkMonitor>>,
         clock: Arc<dyn Clock>,
+        run_queue: Arc<RunQueue>,
     ) {
         let delay_sleep = sleep(delay);
         tokio::pin!(delay_sleep);
@@ -258,6 +323,17 @@ impl TokioBackgroundExecutor {
             }
         }
 
+        let permit = tokio::select! {
+            _ = &mut cancel_rx => {
+                Self::mark_cancelled(&tasks, &id).await;
+                return;
+            }
+            permit = run_queue.acquire(constraints.priority) => permit,
+        };
+        if Self::drop_if_expired(&tasks, &id, &constraints).await {
+            return;
+        }
+
         {
             let mut tasks = tasks.write().await;
             if let Some(info) = tasks.get_mut(&id) {
@@ -266,6 +342,7 @@ impl TokioBackgroundExecutor {
         }
 
         let result = handler().await;
+        drop(permit);
 
         let mut tasks = tasks.write().await;
         if let Some(info) = tasks.get_mut(&id) {
@@ -327,6 +404,7 @@ impl BackgroundExecutor for TokioBackgroundExecutor {
         let task_id_clone = TaskId::new(task_id);
         let constraints_clone = constraints.clone();
         let clock = Arc::clone(&self.clock);
+        let run_queue = Arc::clone(&self.run_queue);
 
         let handle = tokio::spawn(async move {
             TokioBackgroundExecutor::run_recurring_task(
@@ -338,6 +416,7 @@ impl BackgroundExecutor for TokioBackgroundExecutor {
                 cancel_rx,
                 monitor,
                 clock,
+                run_queue,
             )
             .await;
         });
@@ -387,6 +466,7 @@ impl BackgroundExecutor for TokioBackgroundExecutor {
         let task_id_clone = TaskId::new(task_id);
         let constraints_clone = constraints.clone();
         let clock = Arc::clone(&self.clock);
+        let run_queue = Arc::clone(&self.run_queue);
 
         let handle = tokio::spawn(async move {
             TokioBackgroundExecutor::run_one_time_task(
@@ -398,6 +478,7 @@ impl BackgroundExecutor for TokioBackgroundExecutor {
                 cancel_rx,
                 monitor,
                 clock,
+                run_queue,
             )
             .await;
         });
@@ -463,9 +544,257 @@ impl BackgroundExecutor for TokioBackgroundExecutor {
             )))
         }
     }
+
+    /// Run on a Tokio interval that skips runs while the app is backgrounded
+    /// or the network constraints aren't met, and shares the priority queue
+    /// with scheduled tasks.
+    fn schedule_periodic(
+        &self,
+        interval: Duration,
+        constraints: TaskConstraints,
+        task: PeriodicTask,
+    ) -> ScheduleHandle {
+        let lifecycle = self.lifecycle_observer.clone();
+        let monitor = self.network_monitor.clone();
+        let run_queue = Arc::clone(&self.run_queue);
+
+        ScheduleHandle::new(self.spawn(SpawnedTask::new(move |token| async move {
+            let mut gate = LifecycleGate::new(lifecycle).await;
+            let mut ticker =
+                tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+            // A run longer than the interval pushes the next one back rather
+            // than queueing a burst of catch-up runs
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+            loop {
+                ticker.tick().await;
+                if gate.wait_for_foreground().await {
+                    // Count the next interval from the resumed run
+                    ticker.reset();
+                }
+                if !Self::constraints_satisfied(monitor.clone(), &constraints).await {
+                    debug!("Constraints not satisfied; skipping periodic run");
+                    continue;
+                }
+
+                let _permit = run_queue.acquire(constraints.priority).await;
+                if constraints.is_expired(Instant::now()) {
+                    warn!("Periodic task passed its deadline; stopping the schedule");
+                    break;
+                }
+                task.run(token.child_token()).await;
+            }
+        })))
+    }
+
+    /// Spawn on Tokio; cancelling aborts the task instead of waiting for it
+    /// to reach a suspension point that observes the token.
+    fn spawn(&self, task: SpawnedTask) -> TaskHandle {
+        let (handle, run) = task.prepare();
+        let abort = tokio::spawn(run).abort_handle();
+        handle.with_abort(move || abort.abort())
+    }
+}
+
+/// Lifecycle state as seen by one periodic schedule
+struct LifecycleGate {
+    state: LifecycleState,
+    changes: Option<Box<dyn LifecycleChangeStream>>,
+}
+
+impl LifecycleGate {
+    async fn new(observer: Option<Arc<dyn LifecycleObserver>>) -> Self {
+        let Some(observer) = observer else {
+            return Self {
+                state: LifecycleState::Foreground,
+                changes: None,
+            };
+        };
+
+        // Subscribe first so no change between the two calls is missed
+        let changes = match observer.subscribe_changes().await {
+            Ok(changes) => Some(changes),
+            Err(err) => {
+                warn!(error = %err, "Lifecycle changes unavailable; periodic runs won't pause");
+                None
+            }
+        };
+        let state = observer
+            .get_state()
+            .await
+            .unwrap_or(LifecycleState::Foreground);
+        Self { state, changes }
+    }
+
+    /// Catch up on lifecycle changes, then wait while the app is backgrounded
+    ///
+    /// Returns whether it had to wait.
+    async fn wait_for_foreground(&mut self) -> bool {
+        let Some(changes) = self.changes.as_mut() else {
+            return false;
+        };
+
+        while let Some(Some(state)) = changes.next().now_or_never() {
+            self.state = state;
+        }
+        let mut waited = false;
+        while self.state != LifecycleState::Foreground {
+            waited = true;
+            match changes.next().await {
+                Some(state) => self.state = state,
+                None => {
+                    self.changes = None;
+                    break;
+                }
+            }
+        }
+        waited
+    }
+}
+
+/// Slots for running task handlers, handed out by priority
+///
+/// When every slot is taken, runs wait ordered by priority and then by
+/// arrival, so a high-priority run overtakes queued low-priority ones.
+/// Handlers that already started are never interrupted.
+struct RunQueue {
+    state: Mutex<RunQueueState>,
+}
+
+struct RunQueueState {
+    limit: usize,
+    running: usize,
+    next_seq: u64,
+    waiting: BinaryHeap<Waiter>,
+}
+
+struct Waiter {
+    priority: TaskPriority,
+    seq: u64,
+    wake: oneshot::Sender<()>,
+}
+
+impl PartialEq for Waiter {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Waiter {}
+
+impl PartialOrd for Waiter {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Waiter {
+    /// Max-heap order: higher priority first, then earlier arrival
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+impl RunQueue {
+    fn new(limit: usize) -> Self {
+        Self {
+            state: Mutex::new(RunQueueState {
+                limit,
+                running: 0,
+                next_seq: 0,
+                waiting: BinaryHeap::new(),
+            }),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, RunQueueState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Wait for a free slot
+    async fn acquire(self: &Arc<Self>, priority: TaskPriority) -> RunPermit {
+        let wake = {
+            let mut state = self.lock();
+            if state.running < state.limit && state.waiting.is_empty() {
+                state.running += 1;
+                return RunPermit {
+                    queue: Arc::clone(self),
+                };
+            }
+            let (wake, woken) = oneshot::channel();
+            let seq = state.next_seq;
+            state.next_seq += 1;
+            state.waiting.push(Waiter {
+                priority,
+                seq,
+                wake,
+            });
+            woken
+        };
+
+        let mut pending = PendingSlot {
+            queue: Arc::clone(self),
+            woken: Some(wake),
+        };
+        if let Some(woken) = pending.woken.as_mut() {
+            let _ = woken.await;
+        }
+        pending.woken = None;
+        RunPermit {
+            queue: Arc::clone(self),
+        }
+    }
+
+    /// Hand a finished run's slot to the next waiter, or free it
+    fn release(&self) {
+        let mut state = self.lock();
+        while let Some(waiter) = state.waiting.pop() {
+            // Fails if the waiter gave up, e.g. because its task was cancelled
+            if waiter.wake.send(()).is_ok() {
+                return;
+            }
+        }
+        state.running -= 1;
+    }
+}
+
+/// A slot held by a running handler, released on drop
+struct RunPermit {
+    queue: Arc<RunQueue>,
+}
+
+impl Drop for RunPermit {
+    fn drop(&mut self) {
+        self.queue.release();
+    }
+}
+
+/// A run waiting for a slot
+///
+/// If the wait is abandoned after the slot was already handed over, the slot
+/// is passed on instead of leaking.
+struct PendingSlot {
+    queue: Arc<RunQueue>,
+    woken: Option<oneshot::Receiver<()>>,
+}
+
+impl Drop for PendingSlot {
+    fn drop(&mut self) {
+        if let Some(mut woken) = self.woken.take() {
+            woken.close();
+            if woken.try_recv().is_ok() {
+                self.queue.release();
+            }
+        }
+    }
 }
 
 /// Desktop lifecycle observer (no-op implementation).
+///
+/// Desktop platforms give us no memory-pressure or battery-saver signal to
+/// hook into, so system events come from the never-emitting default.
 pub struct DesktopLifecycleObserver;
 
 impl DesktopLifecycleObserver {
@@ -508,6 +837,7 @@ mod tests {
     use super::*;
     use bridge_traits::error::BridgeError;
     use bridge_traits::network::NetworkChangeStream;
+    use core_async::sync::Notify;
     use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
     #[core_async::test]
@@ -645,6 +975,241 @@ mod tests {
         executor.cancel_task(&task_id).await.unwrap();
     }
 
+    /// Executor with one slot, taken by a "blocker" task until `release` fires
+    async fn saturated_executor(release: Arc<Notify>) -> TokioBackgroundExecutor {
+        let executor = TokioBackgroundExecutor::new().with_max_concurrent_tasks(1);
+        executor
+            .register_task_handler("blocker", move || {
+                let release = Arc::clone(&release);
+                async move {
+                    release.notified().await;
+                    Ok(())
+                }
+            })
+            .await
+            .unwrap();
+        executor
+            .schedule_once("blocker", Duration::ZERO, TaskConstraints::default())
+            .await
+            .unwrap();
+        sleep(Duration::from_millis(30)).await;
+        executor
+    }
+
+    #[core_async::test]
+    async fn test_high_priority_overtakes_queued_low_priority() {
+        let release = Arc::new(Notify::new());
+        let executor = saturated_executor(Arc::clone(&release)).await;
+
+        let order = Arc::new(Mutex::new(Vec::new()));
+        for (task, priority) in [("low", TaskPriority::Low), ("high", TaskPriority::High)] {
+            let order_clone = Arc::clone(&order);
+            executor
+                .register_task_handler(task, move || {
+                    let order = Arc::clone(&order_clone);
+                    async move {
+                        order.lock().unwrap().push(task);
+                        Ok(())
+                    }
+                })
+                .await
+                .unwrap();
+            executor
+                .schedule_once(
+                    task,
+                    Duration::ZERO,
+                    TaskConstraints {
+                        priority,
+                        ..Default::default()
+                    },
+                )
+                .await
+                .unwrap();
+            // Make sure "low" is queued first
+            sleep(Duration::from_millis(20)).await;
+        }
+        assert!(order.lock().unwrap().is_empty());
+
+        release.notify_one();
+        sleep(Duration::from_millis(50)).await;
+
+        assert_eq!(*order.lock().unwrap(), vec!["high", "low"]);
+    }
+
+    #[core_async::test]
+    async fn test_expired_deadline_task_never_runs() {
+        let release = Arc::new(Notify::new());
+        let executor = saturated_executor(Arc::clone(&release)).await;
+
+        let ran = Arc::new(AtomicBool::new(false));
+        let ran_clone = Arc::clone(&ran);
+        executor
+            .register_task_handler("late", move || {
+                let ran = Arc::clone(&ran_clone);
+                async move {
+                    ran.store(true, Ordering::SeqCst);
+                    Ok(())
+                }
+            })
+            .await
+            .unwrap();
+        let task_id = executor
+            .schedule_once(
+                "late",
+                Duration::ZERO,
+                TaskConstraints {
+                    deadline: Some(Instant::now() + Duration::from_millis(20)),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        // The deadline passes while the task waits for the busy slot
+        sleep(Duration::from_millis(50)).await;
+        release.notify_one();
+        sleep(Duration::from_millis(50)).await;
+
+        assert!(!ran.load(Ordering::SeqCst));
+        assert_eq!(
+            executor.get_task_status(&task_id).await.unwrap(),
+            TaskStatus::Cancelled
+        );
+    }
+
+    #[core_async::test]
+    async fn test_cancel_spawned_task_stops_it() {
+        let executor = TokioBackgroundExecutor::new();
+        let ticks = Arc::new(AtomicUsize::new(0));
+        let ticks_clone = Arc::clone(&ticks);
+
+        // Never checks its token
+        let handle = executor.spawn(SpawnedTask::new(move |_token| async move {
+            loop {
+                ticks_clone.fetch_add(1, Ordering::SeqCst);
+                sleep(Duration::from_millis(5)).await;
+            }
+        }));
+
+        sleep(Duration::from_millis(30)).await;
+        assert!(ticks.load(Ordering::SeqCst) > 0);
+        assert!(!handle.is_finished());
+
+        handle.cancel();
+        sleep(Duration::from_millis(10)).await;
+        let stopped_at = ticks.load(Ordering::SeqCst);
+        sleep(Duration::from_millis(30)).await;
+
+        assert!(handle.is_finished());
+        assert_eq!(ticks.load(Ordering::SeqCst), stopped_at);
+    }
+
+    /// Periodic task counting its runs, each taking `duration`
+    fn counting_task(
+        runs: Arc<AtomicUsize>,
+        active: Arc<AtomicUsize>,
+        max_active: Arc<AtomicUsize>,
+        duration: Duration,
+    ) -> PeriodicTask {
+        PeriodicTask::new(move |_token| {
+            let runs = Arc::clone(&runs);
+            let active = Arc::clone(&active);
+            let max_active = Arc::clone(&max_active);
+            async move {
+                let now_active = active.fetch_add(1, Ordering::SeqCst) + 1;
+                max_active.fetch_max(now_active, Ordering::SeqCst);
+                sleep(duration).await;
+                active.fetch_sub(1, Ordering::SeqCst);
+                runs.fetch_add(1, Ordering::SeqCst);
+            }
+        })
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_periodic_task_runs_once_per_interval() {
+        let executor = TokioBackgroundExecutor::new();
+        let runs = Arc::new(AtomicUsize::new(0));
+        let schedule = executor.schedule_periodic(
+            Duration::from_secs(10),
+            TaskConstraints::default(),
+            counting_task(
+                Arc::clone(&runs),
+                Arc::new(AtomicUsize::new(0)),
+                Arc::new(AtomicUsize::new(0)),
+                Duration::ZERO,
+            ),
+        );
+
+        sleep(Duration::from_secs(65)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 6);
+
+        // Dropping the handle stops the schedule
+        drop(schedule);
+        sleep(Duration::from_secs(60)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 6);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_periodic_runs_never_overlap() {
+        let executor = TokioBackgroundExecutor::new();
+        let runs = Arc::new(AtomicUsize::new(0));
+        let max_active = Arc::new(AtomicUsize::new(0));
+        let _schedule = executor.schedule_periodic(
+            Duration::from_secs(10),
+            TaskConstraints::default(),
+            counting_task(
+                Arc::clone(&runs),
+                Arc::new(AtomicUsize::new(0)),
+                Arc::clone(&max_active),
+                Duration::from_secs(25),
+            ),
+        );
+
+        sleep(Duration::from_secs(100)).await;
+        assert_eq!(max_active.load(Ordering::SeqCst), 1);
+        // Runs start at 10s, 35s, 60s and 85s, each pushed back by the
+        // previous one, so three have finished
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_periodic_task_pauses_in_background() {
+        let (state_tx, _) = tokio::sync::watch::channel(LifecycleState::Foreground);
+        let state_tx = Arc::new(state_tx);
+        let executor = TokioBackgroundExecutor::new().with_lifecycle_observer(Arc::new(
+            TestLifecycleObserver {
+                state: Arc::clone(&state_tx),
+            },
+        ));
+        let runs = Arc::new(AtomicUsize::new(0));
+        let _schedule = executor.schedule_periodic(
+            Duration::from_secs(10),
+            TaskConstraints::default(),
+            counting_task(
+                Arc::clone(&runs),
+                Arc::new(AtomicUsize::new(0)),
+                Arc::new(AtomicUsize::new(0)),
+                Duration::ZERO,
+            ),
+        );
+
+        sleep(Duration::from_secs(25)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+
+        state_tx.send_replace(LifecycleState::Background);
+        sleep(Duration::from_secs(60)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+
+        // The run held back by the pause starts as soon as the app returns
+        state_tx.send_replace(LifecycleState::Foreground);
+        sleep(Duration::from_secs(1)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+
+        // ...and the interval restarts from there
+        sleep(Duration::from_secs(10)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 4);
+    }
+
     #[core_async::test]
     async fn test_lifecycle_observer() {
         let observer = DesktopLifecycleObserver::new();
@@ -654,6 +1219,32 @@ mod tests {
         );
     }
 
+    struct TestLifecycleObserver {
+        state: Arc<tokio::sync::watch::Sender<LifecycleState>>,
+    }
+
+    #[async_trait]
+    impl LifecycleObserver for TestLifecycleObserver {
+        async fn get_state(&self) -> Result<LifecycleState> {
+            Ok(*self.state.borrow())
+        }
+
+        async fn subscribe_changes(&self) -> Result<Box<dyn LifecycleChangeStream>> {
+            Ok(Box::new(TestLifecycleChanges(self.state.subscribe())))
+        }
+    }
+
+    struct TestLifecycleChanges(tokio::sync::watch::Receiver<LifecycleState>);
+
+    #[async_trait]
+    impl LifecycleChangeStream for TestLifecycleChanges {
+        async fn next(&mut self) -> Option<LifecycleState> {
+            self.0.changed().await.ok()?;
+            let state = *self.0.borrow_and_update();
+            Some(state)
+        }
+    }
+
     #[derive(Clone)]
     struct TestNetworkMonitor {
         connected: Arc<AtomicBool>,
@@ -674,6 +1265,8 @@ mod tests {
                     network_type: Some(NetworkType::WiFi),
                     is_metered: false,
                     is_expensive: false,
+                    is_captive_portal: None,
+                    is_vpn: None,
                 })
             } else {
                 Ok(NetworkInfo {
@@ -681,6 +1274,8 @@ mod tests {
                     network_type: None,
                     is_metered: false,
                     is_expensive: false,
+                    is_captive_portal: None,
+                    is_vpn: None,
                 })
             }
         }
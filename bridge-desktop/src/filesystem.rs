@@ -6,20 +6,86 @@ use bridge_traits::{
     storage::{FileMetadata, FileSystemAccess},
 };
 use bytes::Bytes;
-use core_async::io::AsyncWriteExt;
+use core_async::io::{AsyncSeekExt, AsyncWriteExt};
 use std::path::{Path, PathBuf};
 use tokio::fs;
 use tracing::debug;
 
+/// How `TokioFileSystem` treats path case and symbolic links
+///
+/// # Case normalization
+///
+/// macOS and Windows default to case-insensitive filesystems while Linux is
+/// case-sensitive, so `Song.mp3` and `song.mp3` are one file on some hosts
+/// and two on others. With `normalize_case` enabled, every path below the
+/// cache or data directory is lowercased before it reaches the OS, giving
+/// the same collisions everywhere. Paths outside those directories, and the
+/// directories themselves, are passed through untouched.
+///
+/// Moving a cache directory between filesystems is the edge case to watch:
+/// a cache written without normalization on a case-sensitive filesystem may
+/// hold names that differ only by case, and copying it onto a
+/// case-insensitive one silently keeps just one of them. Caches written with
+/// normalization enabled contain only lowercase names and survive the move.
+/// Switching the policy on for an existing cache leaves mixed-case files
+/// unreachable until they are rewritten.
+///
+/// # Symlinks
+///
+/// With `follow_symlinks` disabled, `exists` and `metadata` refuse to look
+/// through a symbolic link and return `BridgeError::OperationFailed`
+/// instead, so a link planted in the cache cannot point reads elsewhere.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PathPolicy {
+    /// Lowercase paths below the managed directories
+    pub normalize_case: bool,
+    /// Follow symbolic links in `exists` and `metadata`
+    pub follow_symlinks: bool,
+}
+
+impl PathPolicy {
+    /// Case-insensitive policy that rejects symlinks
+    pub fn strict() -> Self {
+        Self {
+            normalize_case: true,
+            follow_symlinks: false,
+        }
+    }
+
+    /// Set whether paths below the managed directories are lowercased
+    pub fn with_normalize_case(mut self, normalize_case: bool) -> Self {
+        self.normalize_case = normalize_case;
+        self
+    }
+
+    /// Set whether symlinks are followed in `exists` and `metadata`
+    pub fn with_follow_symlinks(mut self, follow_symlinks: bool) -> Self {
+        self.follow_symlinks = follow_symlinks;
+        self
+    }
+}
+
+impl Default for PathPolicy {
+    /// Host filesystem semantics: paths are used as given and symlinks are followed
+    fn default() -> Self {
+        Self {
+            normalize_case: false,
+            follow_symlinks: true,
+        }
+    }
+}
+
 /// Tokio-based file system implementation
 ///
 /// Provides async file I/O operations using:
 /// - `tokio::fs` for async operations
 /// - Standard library paths
 /// - Platform-specific app directories
+/// - A configurable [`PathPolicy`] for case and symlink handling
 pub struct TokioFileSystem {
     cache_dir: PathBuf,
     data_dir: PathBuf,
+    path_policy: PathPolicy,
 }
 
 impl TokioFileSystem {
@@ -41,6 +107,7 @@ impl TokioFileSystem {
         Self {
             cache_dir,
             data_dir,
+            path_policy: PathPolicy::default(),
         }
     }
 
@@ -49,6 +116,48 @@ impl TokioFileSystem {
         Self {
             cache_dir,
             data_dir,
+            path_policy: PathPolicy::default(),
+        }
+    }
+
+    /// Set the case and symlink policy
+    pub fn with_path_policy(mut self, path_policy: PathPolicy) -> Self {
+        self.path_policy = path_policy;
+        self
+    }
+
+    /// Current case and symlink policy
+    pub fn path_policy(&self) -> PathPolicy {
+        self.path_policy
+    }
+
+    /// Apply the path policy to a caller-supplied path
+    fn resolve(&self, path: &Path) -> PathBuf {
+        if !self.path_policy.normalize_case {
+            return path.to_path_buf();
+        }
+
+        for base in [&self.cache_dir, &self.data_dir] {
+            if let Ok(relative) = path.strip_prefix(base) {
+                let lowered = relative.to_string_lossy().to_lowercase();
+                return base.join(lowered);
+            }
+        }
+
+        path.to_path_buf()
+    }
+
+    /// Fail if `path` is a symlink and the policy rejects them
+    ///
+    /// Returns `Ok(None)` when nothing exists at `path`.
+    async fn check_symlink(&self, path: &Path) -> Result<Option<std::fs::Metadata>> {
+        match fs::symlink_metadata(path).await {
+            Ok(metadata) if metadata.file_type().is_symlink() => Err(BridgeError::OperationFailed(
+                format!("Refusing to follow symlink: {:?}", path),
+            )),
+            Ok(metadata) => Ok(Some(metadata)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(Self::map_io_error(e)),
         }
     }
 
@@ -56,6 +165,18 @@ impl TokioFileSystem {
     fn map_io_error(e: std::io::Error) -> BridgeError {
         BridgeError::Io(e)
     }
+
+    /// Temporary sibling used by `write_file_atomic`
+    ///
+    /// Kept in the same directory so the final rename never crosses a
+    /// filesystem boundary.
+    fn atomic_temp_path(path: &Path) -> PathBuf {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        path.with_file_name(format!(".{}.{}.tmp", name, std::process::id()))
+    }
 }
 
 impl Default for TokioFileSystem {
@@ -89,11 +210,22 @@ impl FileSystemAccess for TokioFileSystem {
     }
 
     async fn exists(&self, path: &Path) -> Result<bool> {
+        let path = &self.resolve(path);
+        if !self.path_policy.follow_symlinks {
+            return Ok(self.check_symlink(path).await?.is_some());
+        }
         Ok(fs::try_exists(path).await.map_err(Self::map_io_error)?)
     }
 
     async fn metadata(&self, path: &Path) -> Result<FileMetadata> {
-        let metadata = fs::metadata(path).await.map_err(Self::map_io_error)?;
+        let path = &self.resolve(path);
+        let metadata = if self.path_policy.follow_symlinks {
+            fs::metadata(path).await.map_err(Self::map_io_error)?
+        } else {
+            self.check_symlink(path).await?.ok_or_else(|| {
+                Self::map_io_error(std::io::Error::from(std::io::ErrorKind::NotFound))
+            })?
+        };
 
         Ok(FileMetadata {
             size: metadata.len(),
@@ -112,18 +244,21 @@ impl FileSystemAccess for TokioFileSystem {
     }
 
     async fn create_dir_all(&self, path: &Path) -> Result<()> {
+        let path = &self.resolve(path);
         fs::create_dir_all(path).await.map_err(Self::map_io_error)?;
         debug!(path = ?path, "Created directory");
         Ok(())
     }
 
     async fn read_file(&self, path: &Path) -> Result<Bytes> {
+        let path = &self.resolve(path);
         let data = fs::read(path).await.map_err(Self::map_io_error)?;
         debug!(path = ?path, size = data.len(), "Read file");
         Ok(Bytes::from(data))
     }
 
     async fn write_file(&self, path: &Path, data: Bytes) -> Result<()> {
+        let path = &self.resolve(path);
         // Ensure parent directory exists
         if let Some(parent) = path.parent() {
             self.create_dir_all(parent).await?;
@@ -136,7 +271,34 @@ impl FileSystemAccess for TokioFileSystem {
         Ok(())
     }
 
+    async fn write_file_atomic(&self, path: &Path, data: Bytes) -> Result<()> {
+        let path = &self.resolve(path);
+        // Ensure parent directory exists
+        if let Some(parent) = path.parent() {
+            self.create_dir_all(parent).await?;
+        }
+
+        let temp_path = Self::atomic_temp_path(path);
+        let written = async {
+            let mut file = fs::File::create(&temp_path).await?;
+            file.write_all(data.as_ref()).await?;
+            file.sync_all().await?;
+            drop(file);
+            fs::rename(&temp_path, path).await
+        }
+        .await;
+
+        if let Err(e) = written {
+            let _ = fs::remove_file(&temp_path).await;
+            return Err(Self::map_io_error(e));
+        }
+
+        debug!(path = ?path, size = data.len(), "Atomically wrote file");
+        Ok(())
+    }
+
     async fn append_file(&self, path: &Path, data: Bytes) -> Result<()> {
+        let path = &self.resolve(path);
         // Ensure parent directory exists
         if let Some(parent) = path.parent() {
             self.create_dir_all(parent).await?;
@@ -158,19 +320,49 @@ impl FileSystemAccess for TokioFileSystem {
         Ok(())
     }
 
+    async fn write_at(&self, path: &Path, offset: u64, data: Bytes) -> Result<()> {
+        let path = &self.resolve(path);
+        // Ensure parent directory exists
+        if let Some(parent) = path.parent() {
+            self.create_dir_all(parent).await?;
+        }
+
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path)
+            .await
+            .map_err(Self::map_io_error)?;
+
+        file.seek(std::io::SeekFrom::Start(offset))
+            .await
+            .map_err(Self::map_io_error)?;
+        file.write_all(data.as_ref())
+            .await
+            .map_err(Self::map_io_error)?;
+        file.flush().await.map_err(Self::map_io_error)?;
+
+        debug!(path = ?path, offset, size = data.len(), "Wrote file region");
+        Ok(())
+    }
+
     async fn delete_file(&self, path: &Path) -> Result<()> {
+        let path = &self.resolve(path);
         fs::remove_file(path).await.map_err(Self::map_io_error)?;
         debug!(path = ?path, "Deleted file");
         Ok(())
     }
 
     async fn delete_dir_all(&self, path: &Path) -> Result<()> {
+        let path = &self.resolve(path);
         fs::remove_dir_all(path).await.map_err(Self::map_io_error)?;
         debug!(path = ?path, "Deleted directory");
         Ok(())
     }
 
     async fn list_directory(&self, path: &Path) -> Result<Vec<PathBuf>> {
+        let path = &self.resolve(path);
         let mut entries = Vec::new();
         let mut read_dir = fs::read_dir(path).await.map_err(Self::map_io_error)?;
 
@@ -186,6 +378,7 @@ impl FileSystemAccess for TokioFileSystem {
         &self,
         path: &Path,
     ) -> Result<Box<dyn tokio::io::AsyncRead + Send + Unpin>> {
+        let path = &self.resolve(path);
         let file = fs::File::open(path).await.map_err(Self::map_io_error)?;
         debug!(path = ?path, "Opened file for reading");
         Ok(Box::new(file))
@@ -195,6 +388,7 @@ impl FileSystemAccess for TokioFileSystem {
         &self,
         path: &Path,
     ) -> Result<Box<dyn tokio::io::AsyncWrite + Send + Unpin>> {
+        let path = &self.resolve(path);
         // Ensure parent directory exists
         if let Some(parent) = path.parent() {
             self.create_dir_all(parent).await?;
@@ -205,6 +399,27 @@ impl FileSystemAccess for TokioFileSystem {
         Ok(Box::new(file))
     }
 
+    async fn available_space(&self, path: &Path) -> Result<u64> {
+        let path = &self.resolve(path);
+        // statvfs needs an existing path; the target file or its directory
+        // may not have been created yet
+        let mut probe = path.to_path_buf();
+        while !fs::try_exists(&probe).await.map_err(Self::map_io_error)? {
+            if !probe.pop() {
+                probe = std::env::current_dir().map_err(Self::map_io_error)?;
+                break;
+            }
+        }
+
+        let available = tokio::task::spawn_blocking(move || fs2::available_space(&probe))
+            .await
+            .map_err(|e| BridgeError::OperationFailed(format!("Space query panicked: {}", e)))?
+            .map_err(Self::map_io_error)?;
+
+        debug!(path = ?path, available, "Queried available space");
+        Ok(available)
+    }
+
     async fn directory_size(&self, path: &Path) -> Result<u64> {
         let mut total = 0u64;
         let entries = self.list_directory(path).await?;
@@ -263,4 +478,201 @@ mod tests {
         // Clean up
         fs.delete_file(&test_file).await.unwrap();
     }
+
+    #[core_async::test]
+    async fn test_write_at_preserves_other_regions() {
+        let fs = TokioFileSystem::new();
+        let test_file = env::temp_dir().join(format!("test-write-at-{}.bin", std::process::id()));
+        let _ = fs.delete_file(&test_file).await;
+
+        // Out-of-order writes, the first one past the end of a new file
+        fs.write_at(&test_file, 6, Bytes::from("World!"))
+            .await
+            .unwrap();
+        fs.write_at(&test_file, 0, Bytes::from("Hello,"))
+            .await
+            .unwrap();
+        assert_eq!(fs.read_file(&test_file).await.unwrap(), "Hello,World!");
+
+        fs.write_at(&test_file, 5, Bytes::from(" ")).await.unwrap();
+        assert_eq!(fs.read_file(&test_file).await.unwrap(), "Hello World!");
+
+        fs.delete_file(&test_file).await.unwrap();
+    }
+
+    #[core_async::test]
+    async fn test_write_file_atomic_replaces_contents() {
+        let fs = TokioFileSystem::new();
+        let test_file = env::temp_dir().join(format!("test-atomic-{}.txt", std::process::id()));
+        let _ = fs.delete_file(&test_file).await;
+
+        fs.write_file_atomic(&test_file, Bytes::from("first"))
+            .await
+            .unwrap();
+        fs.write_file_atomic(&test_file, Bytes::from("second"))
+            .await
+            .unwrap();
+
+        assert_eq!(fs.read_file(&test_file).await.unwrap(), "second");
+        assert!(!fs
+            .exists(&TokioFileSystem::atomic_temp_path(&test_file))
+            .await
+            .unwrap());
+
+        fs.delete_file(&test_file).await.unwrap();
+    }
+
+    #[core_async::test]
+    async fn test_interrupted_atomic_write_leaves_final_path_absent() {
+        let fs = TokioFileSystem::new();
+        let test_file =
+            env::temp_dir().join(format!("test-atomic-crash-{}.txt", std::process::id()));
+        let temp_file = TokioFileSystem::atomic_temp_path(&test_file);
+        let _ = fs.delete_file(&test_file).await;
+
+        // Simulate a crash after the temp file is written but before the rename
+        fs.write_file(&temp_file, Bytes::from("partial"))
+            .await
+            .unwrap();
+
+        assert!(!fs.exists(&test_file).await.unwrap());
+
+        // A later atomic write is unaffected by the leftover temp file
+        fs.write_file_atomic(&test_file, Bytes::from("complete"))
+            .await
+            .unwrap();
+        assert_eq!(fs.read_file(&test_file).await.unwrap(), "complete");
+        assert!(!fs.exists(&temp_file).await.unwrap());
+
+        fs.delete_file(&test_file).await.unwrap();
+    }
+
+    #[core_async::test]
+    async fn test_available_space_for_missing_path() {
+        let fs = TokioFileSystem::new();
+        let missing = env::temp_dir().join("does-not-exist").join("track.cache");
+
+        // Falls back to the nearest existing ancestor
+        let available = fs.available_space(&missing).await.unwrap();
+        assert!(available > 0);
+    }
+
+    #[core_async::test]
+    async fn test_directory_size_nested() {
+        let fs = TokioFileSystem::new();
+        let root = env::temp_dir().join(format!("test-dir-size-{}", std::process::id()));
+        let _ = fs.delete_dir_all(&root).await;
+
+        fs.write_file(&root.join("a.bin"), Bytes::from(vec![0u8; 10]))
+            .await
+            .unwrap();
+        fs.write_file(
+            &root.join("nested").join("b.bin"),
+            Bytes::from(vec![0u8; 20]),
+        )
+        .await
+        .unwrap();
+        fs.write_file(
+            &root.join("nested").join("deeper").join("c.bin"),
+            Bytes::from(vec![0u8; 30]),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(fs.directory_size(&root).await.unwrap(), 60);
+
+        fs.delete_dir_all(&root).await.unwrap();
+    }
+
+    fn policy_test_dirs(label: &str) -> (PathBuf, PathBuf) {
+        let root =
+            env::temp_dir().join(format!("test-path-policy-{}-{}", label, std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        (root.join("cache"), root.join("data"))
+    }
+
+    #[core_async::test]
+    async fn test_normalized_case_collides_on_every_host() {
+        let (cache, data) = policy_test_dirs("normalized");
+        let fs = TokioFileSystem::with_directories(cache.clone(), data)
+            .with_path_policy(PathPolicy::default().with_normalize_case(true));
+        let dir = fs.get_cache_directory().await.unwrap();
+
+        fs.write_file(&dir.join("Song.mp3"), Bytes::from("upper"))
+            .await
+            .unwrap();
+        fs.write_file(&dir.join("song.mp3"), Bytes::from("lower"))
+            .await
+            .unwrap();
+
+        assert!(fs.exists(&dir.join("SONG.MP3")).await.unwrap());
+        assert_eq!(fs.read_file(&dir.join("Song.mp3")).await.unwrap(), "lower");
+        assert_eq!(
+            fs.list_directory(&dir).await.unwrap(),
+            vec![dir.join("song.mp3")]
+        );
+
+        let _ = std::fs::remove_dir_all(cache.parent().unwrap());
+    }
+
+    #[core_async::test]
+    async fn test_default_policy_follows_host_case_rules() {
+        let (cache, data) = policy_test_dirs("host");
+        let fs = TokioFileSystem::with_directories(cache.clone(), data);
+        let dir = fs.get_cache_directory().await.unwrap();
+
+        fs.write_file(&dir.join("Song.mp3"), Bytes::from("upper"))
+            .await
+            .unwrap();
+        let case_insensitive_host = std::fs::metadata(dir.join("SONG.MP3")).is_ok();
+        fs.write_file(&dir.join("song.mp3"), Bytes::from("lower"))
+            .await
+            .unwrap();
+
+        let entries = fs.list_directory(&dir).await.unwrap();
+        let upper = fs.read_file(&dir.join("Song.mp3")).await.unwrap();
+        if case_insensitive_host {
+            assert_eq!(entries.len(), 1);
+            assert_eq!(upper, "lower");
+        } else {
+            assert_eq!(entries.len(), 2);
+            assert_eq!(upper, "upper");
+        }
+
+        let _ = std::fs::remove_dir_all(cache.parent().unwrap());
+    }
+
+    #[cfg(unix)]
+    #[core_async::test]
+    async fn test_symlinks_rejected_when_policy_forbids() {
+        let (cache, data) = policy_test_dirs("symlink");
+        let following = TokioFileSystem::with_directories(cache.clone(), data.clone());
+        let dir = following.get_cache_directory().await.unwrap();
+
+        let target = dir.join("target.bin");
+        let link = dir.join("link.bin");
+        following
+            .write_file(&target, Bytes::from("data"))
+            .await
+            .unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        assert!(following.exists(&link).await.unwrap());
+        assert_eq!(following.metadata(&link).await.unwrap().size, 4);
+
+        let rejecting = TokioFileSystem::with_directories(cache.clone(), data)
+            .with_path_policy(PathPolicy::default().with_follow_symlinks(false));
+        assert!(matches!(
+            rejecting.exists(&link).await,
+            Err(BridgeError::OperationFailed(_))
+        ));
+        assert!(matches!(
+            rejecting.metadata(&link).await,
+            Err(BridgeError::OperationFailed(_))
+        ));
+        assert!(rejecting.exists(&target).await.unwrap());
+        assert!(!rejecting.exists(&dir.join("missing.bin")).await.unwrap());
+
+        let _ = std::fs::remove_dir_all(cache.parent().unwrap());
+    }
 }
@@ -4,11 +4,15 @@ use async_trait::async_trait;
 use bridge_traits::{
     error::{BridgeError, Result},
     http::{HttpClient, HttpMethod, HttpRequest, HttpResponse, RetryPolicy},
+    platform::DynAsyncRead,
+    time::SystemClock,
 };
 use core_async::time::sleep;
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT_ENCODING, CONTENT_LENGTH};
 use reqwest::Client;
 use std::collections::HashMap;
 use std::time::Duration;
+use tokio_util::io::ReaderStream;
 use tracing::{debug, warn};
 
 /// Reqwest-based HTTP client implementation
@@ -18,10 +22,148 @@ use tracing::{debug, warn};
 /// - Automatic retry with exponential backoff
 /// - TLS support by default
 /// - Async streaming
+/// - gzip, deflate and brotli response decompression (see
+///   [`ReqwestHttpClientBuilder`])
 pub struct ReqwestHttpClient {
     client: Client,
 }
 
+/// Connection pool settings for [`ReqwestHttpClient`]
+///
+/// The defaults match a plain [`ReqwestHttpClient::new`]. Clients issuing
+/// many parallel requests to one host, such as sync downloads, should raise
+/// `max_idle_per_host` to their concurrency so every worker reuses a warm
+/// connection instead of paying for a new TCP and TLS handshake.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolConfig {
+    /// Idle connections kept open per host (default: 10)
+    pub max_idle_per_host: usize,
+    /// How long an idle connection is kept before closing (default: 90 seconds)
+    pub idle_timeout: Duration,
+    /// Speak HTTP/2 without negotiating it first (default: false)
+    ///
+    /// Only enable this for hosts known to support HTTP/2; HTTP/1-only
+    /// servers will reject every request.
+    pub http2_prior_knowledge: bool,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            max_idle_per_host: 10,
+            idle_timeout: Duration::from_secs(90),
+            http2_prior_knowledge: false,
+        }
+    }
+}
+
+/// Builder for [`ReqwestHttpClient`]
+///
+/// Compression is negotiated and decoded transparently by default. Byte-range
+/// downloads never ask for compression, so `Content-Range` offsets always
+/// refer to the raw file; turn both options off for a client that must
+/// never see encoded bodies either.
+#[derive(Debug, Clone)]
+pub struct ReqwestHttpClientBuilder {
+    timeout: Duration,
+    connect_timeout: Duration,
+    accept_compression: bool,
+    decompress_response: bool,
+    pool: PoolConfig,
+}
+
+impl Default for ReqwestHttpClientBuilder {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(30),
+            connect_timeout: Duration::from_secs(10),
+            accept_compression: true,
+            decompress_response: true,
+            pool: PoolConfig::default(),
+        }
+    }
+}
+
+impl ReqwestHttpClientBuilder {
+    /// Total timeout for each request (default: 30 seconds)
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Timeout for establishing a connection (default: 10 seconds)
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Whether to send `Accept-Encoding: gzip, deflate, br` (default: true)
+    ///
+    /// When disabled, requests ask for `identity` so servers send bodies
+    /// uncompressed.
+    pub fn accept_compression(mut self, accept: bool) -> Self {
+        self.accept_compression = accept;
+        self
+    }
+
+    /// Whether to decompress encoded response bodies (default: true)
+    ///
+    /// When disabled, bodies are returned exactly as sent and
+    /// [`HttpResponse::content_encoding`] reports their encoding.
+    pub fn decompress_response(mut self, decompress: bool) -> Self {
+        self.decompress_response = decompress;
+        self
+    }
+
+    /// Connection pool settings (default: [`PoolConfig::default`])
+    pub fn pool(mut self, pool: PoolConfig) -> Self {
+        self.pool = pool;
+        self
+    }
+
+    /// Build the client
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the TLS backend fails to initialize
+    pub fn build(self) -> Result<ReqwestHttpClient> {
+        // reqwest only advertises the encodings it decodes, so the header is
+        // set by hand whenever the two options disagree
+        let mut headers = HeaderMap::new();
+        match (self.accept_compression, self.decompress_response) {
+            (true, false) => {
+                headers.insert(
+                    ACCEPT_ENCODING,
+                    HeaderValue::from_static("gzip, deflate, br"),
+                );
+            }
+            (false, true) => {
+                headers.insert(ACCEPT_ENCODING, HeaderValue::from_static("identity"));
+            }
+            _ => {}
+        }
+
+        let mut client = Client::builder()
+            .timeout(self.timeout)
+            .connect_timeout(self.connect_timeout)
+            .pool_max_idle_per_host(self.pool.max_idle_per_host)
+            .pool_idle_timeout(self.pool.idle_timeout)
+            .user_agent("music-platform-core/0.1.0")
+            .default_headers(headers)
+            .gzip(self.decompress_response)
+            .deflate(self.decompress_response)
+            .brotli(self.decompress_response);
+        if self.pool.http2_prior_knowledge {
+            client = client.http2_prior_knowledge();
+        }
+        let client = client.build().map_err(|e| {
+            BridgeError::OperationFailed(format!("Failed to build HTTP client: {}", e))
+        })?;
+
+        Ok(ReqwestHttpClient { client })
+    }
+}
+
 impl ReqwestHttpClient {
     /// Create a new HTTP client with default configuration
     pub fn new() -> Self {
@@ -30,15 +172,23 @@ impl ReqwestHttpClient {
 
     /// Create a new HTTP client with custom timeout
     pub fn with_timeout(timeout: Duration) -> Self {
-        let client = Client::builder()
+        Self::builder()
             .timeout(timeout)
-            .connect_timeout(Duration::from_secs(10))
-            .pool_max_idle_per_host(10)
-            .user_agent("music-platform-core/0.1.0")
             .build()
-            .expect("Failed to build HTTP client");
+            .expect("Failed to build HTTP client")
+    }
 
-        Self { client }
+    /// Create a new HTTP client with custom connection pool settings
+    pub fn with_pool_config(pool: PoolConfig) -> Self {
+        Self::builder()
+            .pool(pool)
+            .build()
+            .expect("Failed to build HTTP client")
+    }
+
+    /// Start configuring a new HTTP client
+    pub fn builder() -> ReqwestHttpClientBuilder {
+        ReqwestHttpClientBuilder::default()
     }
 
     /// Create a new HTTP client with custom configuration
@@ -63,6 +213,18 @@ impl ReqwestHttpClient {
         let method = Self::convert_method(request.method);
         let mut req = self.client.request(method, &request.url);
 
+        // Byte ranges must index the raw file, so never negotiate an
+        // encoding for them (this overrides any default Accept-Encoding)
+        let has_header = |name: &str| {
+            request
+                .headers
+                .keys()
+                .any(|key| key.eq_ignore_ascii_case(name))
+        };
+        if has_header("range") && !has_header("accept-encoding") {
+            req = req.header(ACCEPT_ENCODING, "identity");
+        }
+
         // Add headers
         for (key, value) in request.headers {
             req = req.header(key, value);
@@ -81,19 +243,46 @@ impl ReqwestHttpClient {
         req
     }
 
+    /// Read a reqwest response into a bridge response
+    async fn convert_response(response: reqwest::Response) -> Result<HttpResponse> {
+        let status = response.status().as_u16();
+        let headers: HashMap<String, String> = response
+            .headers()
+            .iter()
+            .filter_map(|(k, v)| v.to_str().ok().map(|s| (k.to_string(), s.to_string())))
+            .collect();
+
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| BridgeError::OperationFailed(e.to_string()))?;
+
+        Ok(HttpResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+
     /// Execute request with retry logic
+    ///
+    /// Connection failures, timeouts, 429 and 5xx responses are retried with
+    /// the policy's backoff, up to [`HttpRequest::max_attempts`] attempts.
     async fn execute_with_retry_internal(
         &self,
         request: HttpRequest,
         policy: RetryPolicy,
     ) -> Result<HttpResponse> {
+        let max_attempts = request.max_attempts(&policy).max(1);
         let mut attempt = 0;
-        let mut last_error = None;
+        let mut last_status = None;
+        let mut last_message = String::new();
+        let mut backoff = policy.backoff_from_clock(&SystemClock);
 
-        while attempt < policy.max_attempts {
+        while attempt < max_attempts {
             debug!(
                 attempt = attempt + 1,
-                max_attempts = policy.max_attempts,
+                max_attempts = max_attempts,
                 url = %request.url,
                 "Executing HTTP request"
             );
@@ -111,30 +300,11 @@ impl ReqwestHttpClient {
                             attempt = attempt + 1,
                             "HTTP request failed with retryable status"
                         );
-                        last_error = Some(BridgeError::OperationFailed(format!(
-                            "HTTP {} error",
-                            status
-                        )));
+                        last_status = Some(status);
+                        last_message = format!("HTTP {} error", status);
                     } else {
                         // Success or non-retryable error
-                        let headers: HashMap<String, String> = response
-                            .headers()
-                            .iter()
-                            .filter_map(|(k, v)| {
-                                v.to_str().ok().map(|s| (k.to_string(), s.to_string()))
-                            })
-                            .collect();
-
-                        let body = response
-                            .bytes()
-                            .await
-                            .map_err(|e| BridgeError::OperationFailed(e.to_string()))?;
-
-                        return Ok(HttpResponse {
-                            status,
-                            headers,
-                            body,
-                        });
+                        return Self::convert_response(response).await;
                     }
                 }
                 Err(e) => {
@@ -144,31 +314,22 @@ impl ReqwestHttpClient {
                         "HTTP request failed"
                     );
 
-                    if e.is_timeout() {
-                        last_error = Some(BridgeError::OperationFailed(
-                            "Request timed out".to_string(),
-                        ));
+                    last_status = None;
+                    last_message = if e.is_timeout() {
+                        "Request timed out".to_string()
                     } else if e.is_connect() {
-                        last_error = Some(BridgeError::OperationFailed(format!(
-                            "Connection failed: {}",
-                            e
-                        )));
+                        format!("Connection failed: {}", e)
                     } else {
-                        last_error = Some(BridgeError::OperationFailed(e.to_string()));
-                    }
+                        e.to_string()
+                    };
                 }
             }
 
             attempt += 1;
 
-            // If we're going to retry, wait with exponential backoff
-            if attempt < policy.max_attempts {
-                let delay = if policy.use_exponential_backoff {
-                    let exponential_delay = policy.base_delay * 2u32.pow(attempt - 1);
-                    exponential_delay.min(policy.max_delay)
-                } else {
-                    policy.base_delay
-                };
+            // If we're going to retry, wait according to the backoff policy
+            if attempt < max_attempts {
+                let delay = backoff.next_delay();
 
                 debug!(delay_ms = delay.as_millis(), "Retrying after delay");
                 sleep(delay).await;
@@ -176,9 +337,11 @@ impl ReqwestHttpClient {
         }
 
         // All retries exhausted
-        Err(last_error.unwrap_or_else(|| {
-            BridgeError::OperationFailed("All retry attempts exhausted".to_string())
-        }))
+        Err(BridgeError::RetriesExhausted {
+            attempts: attempt,
+            last_status,
+            message: last_message,
+        })
     }
 }
 
@@ -230,6 +393,40 @@ impl HttpClient for ReqwestHttpClient {
         Ok(Box::new(reader))
     }
 
+    /// Stream `body` as the request body without buffering it
+    ///
+    /// The body can only be read once, so the request is sent a single time
+    /// regardless of the retry policy.
+    async fn upload_stream(
+        &self,
+        request: HttpRequest,
+        body: Box<DynAsyncRead>,
+    ) -> Result<HttpResponse> {
+        debug!(
+            url = %request.url,
+            content_length = ?request.content_length,
+            "Uploading HTTP request body"
+        );
+
+        let content_length = request.content_length;
+        let mut req_builder = self
+            .build_request(request)
+            .body(reqwest::Body::wrap_stream(ReaderStream::new(body)));
+        if let Some(length) = content_length {
+            req_builder = req_builder.header(CONTENT_LENGTH, length);
+        }
+
+        let response = req_builder.send().await.map_err(|e| {
+            if e.is_timeout() {
+                BridgeError::OperationFailed("Request timed out".to_string())
+            } else {
+                BridgeError::OperationFailed(format!("Upload failed: {}", e))
+            }
+        })?;
+
+        Self::convert_response(response).await
+    }
+
     async fn is_connected(&self) -> bool {
         self.client
             .head("https://www.google.com")
@@ -243,6 +440,336 @@ impl HttpClient for ReqwestHttpClient {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use bridge_traits::http::JitterMode;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+    use std::time::Instant;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio::task::JoinHandle;
+
+    const JSON: &str = r#"{"track":"Song","artist":"Artist"}"#;
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    /// Serve one gzip-encoded JSON response, returning the URL and a handle
+    /// resolving to the raw request head
+    async fn serve_gzip_once() -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/metadata.json", listener.local_addr().unwrap());
+
+        let handle = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let request = read_request_head(&mut socket).await;
+
+            let body = gzip(JSON.as_bytes());
+            let head = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+                 Content-Encoding: gzip\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            );
+            socket.write_all(head.as_bytes()).await.unwrap();
+            socket.write_all(&body).await.unwrap();
+            socket.shutdown().await.unwrap();
+            String::from_utf8(request).unwrap().to_ascii_lowercase()
+        });
+
+        (url, handle)
+    }
+
+    async fn read_request_head(socket: &mut TcpStream) -> Vec<u8> {
+        let mut request = Vec::new();
+        let mut buffer = [0u8; 1024];
+        while !request.ends_with(b"\r\n\r\n") {
+            let read = socket.read(&mut buffer).await.unwrap();
+            assert!(read > 0, "connection closed mid-request");
+            request.extend_from_slice(&buffer[..read]);
+        }
+        request
+    }
+
+    /// Answer one connection per entry of `statuses` with an empty body
+    async fn serve_statuses(statuses: Vec<u16>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/files", listener.local_addr().unwrap());
+
+        tokio::spawn(async move {
+            for status in statuses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                read_request_head(&mut socket).await;
+                let head = format!(
+                    "HTTP/1.1 {} Status\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                );
+                socket.write_all(head.as_bytes()).await.unwrap();
+                socket.shutdown().await.unwrap();
+            }
+        });
+
+        url
+    }
+
+    /// Accept one upload and answer `201 Created`, returning the URL and a
+    /// handle resolving to the raw request (head and body)
+    async fn serve_upload_once() -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/playlist.m3u", listener.local_addr().unwrap());
+
+        let handle = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+
+            // The body may arrive in the same read as the head
+            let mut request = Vec::new();
+            let mut buffer = [0u8; 1024];
+            let body_start = loop {
+                if let Some(end) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+                    break end + 4;
+                }
+                let read = socket.read(&mut buffer).await.unwrap();
+                assert!(read > 0, "connection closed mid-request");
+                request.extend_from_slice(&buffer[..read]);
+            };
+            let head = String::from_utf8_lossy(&request[..body_start]).to_ascii_lowercase();
+            let content_length = head.lines().find_map(|line| {
+                line.strip_prefix("content-length: ")
+                    .map(|value| value.trim().parse::<usize>().unwrap())
+            });
+
+            loop {
+                let body = &request[body_start..];
+                let complete = match content_length {
+                    Some(length) => body.len() >= length,
+                    None => body.ends_with(b"0\r\n\r\n"),
+                };
+                if complete {
+                    break;
+                }
+                let read = socket.read(&mut buffer).await.unwrap();
+                assert!(read > 0, "connection closed mid-body");
+                request.extend_from_slice(&buffer[..read]);
+            }
+
+            let response = "HTTP/1.1 201 Created\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+            socket.write_all(response.as_bytes()).await.unwrap();
+            socket.shutdown().await.unwrap();
+            String::from_utf8(request).unwrap()
+        });
+
+        (url, handle)
+    }
+
+    const PLAYLIST: &[u8] = b"#EXTM3U\n#EXTINF:180,Artist - Song\nsong.flac\n";
+
+    fn fixed_backoff() -> RetryPolicy {
+        RetryPolicy {
+            base_delay: Duration::from_millis(50),
+            jitter: JitterMode::None,
+            ..Default::default()
+        }
+    }
+
+    fn get(url: String) -> HttpRequest {
+        HttpRequest {
+            method: HttpMethod::Get,
+            url,
+            headers: HashMap::new(),
+            body: None,
+            timeout: None,
+            max_retries: None,
+            content_length: None,
+        }
+    }
+
+    #[core_async::test]
+    async fn test_decompresses_gzip_response_by_default() {
+        let (url, server) = serve_gzip_once().await;
+        let client = ReqwestHttpClient::new();
+
+        let response = client.execute(get(url)).await.unwrap();
+        let request = server.await.unwrap();
+
+        assert!(request.contains("accept-encoding: gzip"), "{}", request);
+        assert_eq!(response.text().unwrap(), JSON);
+        assert_eq!(response.content_encoding(), None);
+    }
+
+    #[core_async::test]
+    async fn test_returns_raw_body_when_decompression_disabled() {
+        let (url, server) = serve_gzip_once().await;
+        let client = ReqwestHttpClient::builder()
+            .decompress_response(false)
+            .build()
+            .unwrap();
+
+        let response = client.execute(get(url)).await.unwrap();
+        let request = server.await.unwrap();
+
+        // Compression is still requested, but the body is left encoded
+        assert!(request.contains("accept-encoding: gzip"), "{}", request);
+        assert_eq!(response.content_encoding(), Some("gzip"));
+        assert_eq!(response.body.as_ref(), gzip(JSON.as_bytes()).as_slice());
+    }
+
+    #[core_async::test]
+    async fn test_requests_identity_when_compression_not_accepted() {
+        let (url, server) = serve_gzip_once().await;
+        let client = ReqwestHttpClient::builder()
+            .accept_compression(false)
+            .build()
+            .unwrap();
+
+        // A server that compresses anyway is still decoded
+        let response = client.execute(get(url)).await.unwrap();
+        let request = server.await.unwrap();
+
+        assert!(request.contains("accept-encoding: identity"), "{}", request);
+        assert!(!request.contains("gzip"), "{}", request);
+        assert_eq!(response.text().unwrap(), JSON);
+    }
+
+    #[core_async::test]
+    async fn test_range_requests_never_accept_compression() {
+        let (url, server) = serve_gzip_once().await;
+        let client = ReqwestHttpClient::builder()
+            .decompress_response(false)
+            .build()
+            .unwrap();
+
+        let mut request = get(url);
+        request
+            .headers
+            .insert("Range".to_string(), "bytes=0-1023".to_string());
+        client.execute(request).await.unwrap();
+        let request = server.await.unwrap();
+
+        assert!(request.contains("accept-encoding: identity"), "{}", request);
+        assert!(!request.contains("gzip"), "{}", request);
+    }
+
+    #[core_async::test]
+    async fn test_succeeds_after_two_service_unavailable_responses() {
+        let url = serve_statuses(vec![503, 503, 200]).await;
+        let client = ReqwestHttpClient::new();
+
+        let started = Instant::now();
+        let response = client
+            .execute_with_retry(get(url), fixed_backoff())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status, 200);
+        // Backoff doubles: 50ms before the second attempt, 100ms before the third
+        assert!(started.elapsed() >= Duration::from_millis(150));
+    }
+
+    #[core_async::test]
+    async fn test_exhausted_retries_report_attempts_and_last_status() {
+        let url = serve_statuses(vec![503, 429]).await;
+        let client = ReqwestHttpClient::new();
+
+        let started = Instant::now();
+        let error = client
+            .execute_with_retry(get(url).max_retries(1), fixed_backoff())
+            .await
+            .unwrap_err();
+
+        assert!(started.elapsed() >= Duration::from_millis(50));
+        match error {
+            BridgeError::RetriesExhausted {
+                attempts,
+                last_status,
+                ..
+            } => {
+                assert_eq!(attempts, 2);
+                assert_eq!(last_status, Some(429));
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[core_async::test]
+    async fn test_request_timeout_applies_per_attempt() {
+        // Accept connections but never answer them
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hung", listener.local_addr().unwrap());
+        let _server = tokio::spawn(async move {
+            let mut sockets = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                sockets.push(socket);
+            }
+        });
+        let client = ReqwestHttpClient::new();
+
+        let started = Instant::now();
+        let request = get(url).timeout(Duration::from_millis(100)).max_retries(1);
+        let error = client
+            .execute_with_retry(request, fixed_backoff())
+            .await
+            .unwrap_err();
+
+        assert!(started.elapsed() < Duration::from_secs(5));
+        match error {
+            BridgeError::RetriesExhausted {
+                attempts,
+                last_status,
+                ..
+            } => {
+                assert_eq!(attempts, 2);
+                assert_eq!(last_status, None);
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[core_async::test]
+    async fn test_upload_stream_with_content_length() {
+        let (url, server) = serve_upload_once().await;
+        let client = ReqwestHttpClient::new();
+
+        let request = HttpRequest::new(HttpMethod::Put, url).content_length(PLAYLIST.len() as u64);
+        let response = client
+            .upload_stream(request, Box::new(PLAYLIST))
+            .await
+            .unwrap();
+        let request = server.await.unwrap();
+
+        assert_eq!(response.status, 201);
+        assert!(request.starts_with("PUT /playlist.m3u"), "{}", request);
+        let lowercase = request.to_ascii_lowercase();
+        assert!(
+            lowercase.contains(&format!("content-length: {}", PLAYLIST.len())),
+            "{}",
+            request
+        );
+        assert!(!lowercase.contains("transfer-encoding"), "{}", request);
+        assert!(request.ends_with(std::str::from_utf8(PLAYLIST).unwrap()));
+    }
+
+    #[core_async::test]
+    async fn test_upload_stream_without_length_is_chunked() {
+        let (url, server) = serve_upload_once().await;
+        let client = ReqwestHttpClient::new();
+
+        let request = HttpRequest::new(HttpMethod::Put, url);
+        let response = client
+            .upload_stream(request, Box::new(PLAYLIST))
+            .await
+            .unwrap();
+        let request = server.await.unwrap().to_ascii_lowercase();
+
+        assert_eq!(response.status, 201);
+        assert!(
+            request.contains("transfer-encoding: chunked"),
+            "{}",
+            request
+        );
+        assert!(request.contains("song.flac"), "{}", request);
+    }
 
     #[core_async::test]
     async fn test_http_client_creation() {
@@ -38,15 +38,18 @@ mod background;
 mod filesystem;
 mod http;
 mod network;
+mod network_watch;
 mod settings;
 
 #[cfg(feature = "secure-store")]
 mod secure_store;
 
-pub use background::{DesktopLifecycleObserver, TokioBackgroundExecutor};
-pub use filesystem::TokioFileSystem;
-pub use http::ReqwestHttpClient;
-pub use network::DesktopNetworkMonitor;
+pub use background::{
+    DesktopLifecycleObserver, TokioBackgroundExecutor, DEFAULT_MAX_CONCURRENT_TASKS,
+};
+pub use filesystem::{PathPolicy, TokioFileSystem};
+pub use http::{PoolConfig, ReqwestHttpClient, ReqwestHttpClientBuilder};
+pub use network::{DesktopNetworkMonitor, DEFAULT_CAPTIVE_PORTAL_PROBE_URL};
 pub use settings::SqliteSettingsStore;
 
 #[cfg(feature = "secure-store")]
@@ -1,34 +1,96 @@
 //! Network Monitoring Implementation
 
+use crate::network_watch::NetworkWatcher;
 use async_trait::async_trait;
 use bridge_traits::{
     error::Result,
     network::{NetworkChangeStream, NetworkInfo, NetworkMonitor, NetworkStatus, NetworkType},
 };
 use core_async::sync::Mutex;
+use reqwest::{redirect::Policy, Client, StatusCode};
 use std::sync::Arc;
-use tracing::debug;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+/// Endpoint that answers `204 No Content` when traffic reaches the internet
+pub const DEFAULT_CAPTIVE_PORTAL_PROBE_URL: &str =
+    "http://connectivitycheck.gstatic.com/generate_204";
+
+/// How long `get_network_info` serves the last check before checking again
+const INFO_TTL: Duration = Duration::from_secs(10);
+
+/// How long a captive portal probe result is reused while the network stays
+/// connected and no change notification arrives
+const CAPTIVE_PORTAL_TTL: Duration = Duration::from_secs(60);
+
+/// Interface name prefixes used by common VPN clients
+const VPN_INTERFACE_PREFIXES: &[&str] = &[
+    "tun",
+    "tap",
+    "wg",
+    "ppp",
+    "ipsec",
+    "utun",
+    "tailscale",
+    "nordlynx",
+    "proton",
+];
 
 /// Desktop network monitor implementation
 ///
 /// Provides basic network connectivity detection:
 /// - Connection status check
 /// - Simple connectivity testing
+/// - Captive portal probe against an endpoint that answers `204 No Content`
+/// - VPN detection from network interface names (Linux only)
+/// - Change subscriptions driven by the platform's network change
+///   notifications (netlink, routing sockets, IP Helper), polling elsewhere
 ///
-/// Note: Platform-specific implementations (Linux netlink, macOS SystemConfiguration,
-/// Windows WinAPI) would be more robust but require additional dependencies.
+/// Checks are cached for 10 seconds, and the captive portal probe result for
+/// a minute, so frequent `is_connected` calls don't each hit the network.
+/// Clones share the cache and the probe settings.
+#[derive(Clone)]
 pub struct DesktopNetworkMonitor {
-    cached_info: Arc<Mutex<Option<NetworkInfo>>>,
+    cached_info: Arc<Mutex<Option<CachedInfo>>>,
+    probe_client: Option<Client>,
+    probe_url: String,
+}
+
+/// The last network check and when it ran
+#[derive(Clone)]
+struct CachedInfo {
+    info: NetworkInfo,
+    checked_at: Instant,
+    /// When `info.is_captive_portal` was probed, if it was
+    probed_at: Option<Instant>,
 }
 
 impl DesktopNetworkMonitor {
     /// Create a new network monitor
     pub fn new() -> Self {
+        // Portals answer the probe with a redirect to their login page, so
+        // redirects must not be followed
+        let probe_client = Client::builder()
+            .redirect(Policy::none())
+            .timeout(Duration::from_secs(3))
+            .build()
+            .ok();
+
         Self {
             cached_info: Arc::new(Mutex::new(None)),
+            probe_client,
+            probe_url: DEFAULT_CAPTIVE_PORTAL_PROBE_URL.to_string(),
         }
     }
 
+    /// Use a different captive portal probe endpoint
+    ///
+    /// The endpoint must answer `204 No Content` with an empty body.
+    pub fn with_captive_portal_probe_url(mut self, url: impl Into<String>) -> Self {
+        self.probe_url = url.into();
+        self
+    }
+
     /// Check network connectivity by attempting a simple HTTP request
     async fn check_connectivity(&self) -> NetworkStatus {
         // Try to connect to a reliable endpoint
@@ -43,22 +105,14 @@ impl DesktopNetworkMonitor {
             Err(_) => NetworkStatus::Disconnected,
         }
     }
-}
-
-impl Default for DesktopNetworkMonitor {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-#[async_trait]
-impl NetworkMonitor for DesktopNetworkMonitor {
-    async fn get_network_info(&self) -> Result<NetworkInfo> {
-        // Check if we have cached info
-        let mut cached = self.cached_info.lock().await;
 
-        // For desktop, we do simple connectivity checks
+    /// Check the network now, ignoring the cached info
+    ///
+    /// With `reprobe` set the captive portal is probed even if the last probe
+    /// is recent, which is what a change notification calls for.
+    async fn refresh(&self, reprobe: bool) -> NetworkInfo {
         let status = self.check_connectivity().await;
+        let (is_captive_portal, probed_at) = self.captive_portal_state(status, reprobe).await;
 
         let info = NetworkInfo {
             status,
@@ -72,12 +126,115 @@ impl NetworkMonitor for DesktopNetworkMonitor {
             is_metered: false,
             // Desktop connections are typically not expensive
             is_expensive: false,
+            is_captive_portal,
+            is_vpn: detect_vpn(),
         };
 
-        *cached = Some(info.clone());
+        *self.cached_info.lock().await = Some(CachedInfo {
+            info: info.clone(),
+            checked_at: Instant::now(),
+            probed_at,
+        });
         debug!(status = ?status, "Network info updated");
 
-        Ok(info)
+        info
+    }
+
+    /// Captive portal state for a network with `status`, and when it was probed
+    ///
+    /// A recent probe is reused while the network stayed connected, unless
+    /// `reprobe` is set.
+    async fn captive_portal_state(
+        &self,
+        status: NetworkStatus,
+        reprobe: bool,
+    ) -> (Option<bool>, Option<Instant>) {
+        if status != NetworkStatus::Connected {
+            return (None, None);
+        }
+
+        if !reprobe {
+            let cached = self.cached_info.lock().await.clone();
+            if let Some(CachedInfo {
+                info,
+                probed_at: Some(probed_at),
+                ..
+            }) = cached
+            {
+                if info.status == NetworkStatus::Connected
+                    && probed_at.elapsed() < CAPTIVE_PORTAL_TTL
+                {
+                    return (info.is_captive_portal, Some(probed_at));
+                }
+            }
+        }
+
+        (self.probe_captive_portal().await, Some(Instant::now()))
+    }
+
+    /// Whether a captive portal intercepts the probe, or `None` if the probe
+    /// couldn't be sent
+    async fn probe_captive_portal(&self) -> Option<bool> {
+        let client = self.probe_client.as_ref()?;
+        match client.get(&self.probe_url).send().await {
+            Ok(response) if response.status() == StatusCode::NO_CONTENT => Some(false),
+            Ok(response) => {
+                debug!(status = %response.status(), "Captive portal probe intercepted");
+                Some(true)
+            }
+            Err(e) => {
+                debug!(error = %e, "Captive portal probe failed");
+                None
+            }
+        }
+    }
+}
+
+/// Whether any VPN interface is up
+#[cfg(target_os = "linux")]
+fn detect_vpn() -> Option<bool> {
+    const IFF_UP: u32 = 0x1;
+
+    let entries = std::fs::read_dir("/sys/class/net").ok()?;
+    Some(entries.flatten().any(|entry| {
+        let name = entry.file_name();
+        let is_down = std::fs::read_to_string(entry.path().join("flags"))
+            .ok()
+            .and_then(|flags| u32::from_str_radix(flags.trim().trim_start_matches("0x"), 16).ok())
+            .is_some_and(|flags| flags & IFF_UP == 0);
+        !is_down && is_vpn_interface(&name.to_string_lossy())
+    }))
+}
+
+/// Interface enumeration needs platform APIs outside Linux
+#[cfg(not(target_os = "linux"))]
+fn detect_vpn() -> Option<bool> {
+    None
+}
+
+fn is_vpn_interface(name: &str) -> bool {
+    VPN_INTERFACE_PREFIXES
+        .iter()
+        .any(|prefix| name.starts_with(prefix))
+}
+
+impl Default for DesktopNetworkMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl NetworkMonitor for DesktopNetworkMonitor {
+    async fn get_network_info(&self) -> Result<NetworkInfo> {
+        // The lock is released before any network check runs
+        if let Some(cached) = self.cached_info.lock().await.as_ref() {
+            if cached.checked_at.elapsed() < INFO_TTL {
+                return Ok(cached.info.clone());
+            }
+        }
+
+        Ok(self.refresh(false).await)
     }
 
     async fn is_connected(&self) -> bool {
@@ -101,33 +258,77 @@ impl NetworkMonitor for DesktopNetworkMonitor {
         false
     }
 
+    async fn estimated_downlink_mbps(&self) -> Result<Option<f64>> {
+        // Measuring throughput would need a test download; report no estimate
+        Ok(None)
+    }
+
     async fn subscribe_changes(&self) -> Result<Box<dyn NetworkChangeStream>> {
-        // Simple implementation: poll periodically
-        // A production implementation would use platform-specific APIs to watch for changes
-        Ok(Box::new(DesktopNetworkChangeStream {
-            monitor: Self::new(),
-            last_status: None,
-        }))
+        Ok(Box::new(self.change_stream()))
     }
 }
 
-/// Network change stream that polls for changes
+impl DesktopNetworkMonitor {
+    /// Change stream sharing this monitor's probe settings and cache
+    fn change_stream(&self) -> DesktopNetworkChangeStream {
+        DesktopNetworkChangeStream {
+            monitor: self.clone(),
+            watcher: NetworkWatcher::new(),
+            last: None,
+        }
+    }
+}
+
+/// How often the network is re-checked without change notifications
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Network change stream driven by the platform's change notifications
+///
+/// The first call reports the current network. After that, every
+/// notification re-checks the network, which is reported if it transitioned.
+/// Without a notification API, or once listening fails, the network is
+/// polled every 5 seconds instead, through the monitor's cache.
 struct DesktopNetworkChangeStream {
     monitor: DesktopNetworkMonitor,
-    last_status: Option<NetworkStatus>,
+    watcher: Option<NetworkWatcher>,
+    last: Option<NetworkInfo>,
 }
 
 #[async_trait]
 impl NetworkChangeStream for DesktopNetworkChangeStream {
     async fn next(&mut self) -> Option<NetworkInfo> {
-        // Poll every 5 seconds for changes
+        // The current network is reported without waiting for a change
+        let mut waiting = self.last.is_some();
         loop {
-            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+            // A notification means the cached info is out of date
+            let mut notified = false;
+            if waiting {
+                match &mut self.watcher {
+                    Some(watcher) => match watcher.changed().await {
+                        Ok(()) => notified = true,
+                        Err(e) => {
+                            warn!(error = %e, "Network change notifications failed, polling instead");
+                            self.watcher = None;
+                        }
+                    },
+                    None => tokio::time::sleep(POLL_INTERVAL).await,
+                }
+            }
+            waiting = true;
 
-            if let Ok(info) = self.monitor.get_network_info().await {
-                // Only return if status changed
-                if self.last_status.as_ref() != Some(&info.status) {
-                    self.last_status = Some(info.status);
+            let info = if notified {
+                Ok(self.monitor.refresh(true).await)
+            } else {
+                self.monitor.get_network_info().await
+            };
+            if let Ok(info) = info {
+                // Only return on status, type, metering or captive portal transitions
+                let changed = match &self.last {
+                    Some(last) => info.is_transition_from(last),
+                    None => true,
+                };
+                if changed {
+                    self.last = Some(info.clone());
                     return Some(info);
                 }
             }
@@ -138,6 +339,7 @@ impl NetworkChangeStream for DesktopNetworkChangeStream {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
     #[core_async::test]
     async fn test_network_monitor_creation() {
@@ -163,4 +365,98 @@ mod tests {
         // Just verify it doesn't panic
         let _ = monitor.is_connected().await;
     }
+
+    /// Answer one probe with `status`, returning the probe URL
+    async fn serve_probe(status: &'static str) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/generate_204", listener.local_addr().unwrap());
+
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buffer = [0u8; 1024];
+            let _ = socket.read(&mut buffer).await.unwrap();
+            let head = format!(
+                "HTTP/1.1 {status}\r\nLocation: http://portal.example/login\r\n\
+                 Content-Length: 0\r\nConnection: close\r\n\r\n"
+            );
+            socket.write_all(head.as_bytes()).await.unwrap();
+            socket.shutdown().await.unwrap();
+        });
+
+        url
+    }
+
+    #[core_async::test]
+    async fn test_captive_portal_probe() {
+        let open = DesktopNetworkMonitor::new()
+            .with_captive_portal_probe_url(serve_probe("204 No Content").await);
+        assert_eq!(open.probe_captive_portal().await, Some(false));
+
+        let portal = DesktopNetworkMonitor::new()
+            .with_captive_portal_probe_url(serve_probe("302 Found").await);
+        assert_eq!(portal.probe_captive_portal().await, Some(true));
+
+        let unreachable =
+            DesktopNetworkMonitor::new().with_captive_portal_probe_url("http://127.0.0.1:9/");
+        assert_eq!(unreachable.probe_captive_portal().await, None);
+    }
+
+    #[core_async::test]
+    async fn test_captive_portal_probe_is_cached() {
+        // The probe endpoint only answers once
+        let monitor = DesktopNetworkMonitor::new()
+            .with_captive_portal_probe_url(serve_probe("302 Found").await);
+
+        let (portal, probed_at) = monitor
+            .captive_portal_state(NetworkStatus::Connected, false)
+            .await;
+        assert_eq!(portal, Some(true));
+        *monitor.cached_info.lock().await = Some(CachedInfo {
+            info: NetworkInfo {
+                status: NetworkStatus::Connected,
+                network_type: Some(NetworkType::Other),
+                is_metered: false,
+                is_expensive: false,
+                is_captive_portal: portal,
+                is_vpn: None,
+            },
+            checked_at: Instant::now(),
+            probed_at,
+        });
+
+        // A recent probe is reused, and a forced one reaches the network again
+        let (portal, _) = monitor
+            .captive_portal_state(NetworkStatus::Connected, false)
+            .await;
+        assert_eq!(portal, Some(true));
+        let (portal, _) = monitor
+            .captive_portal_state(NetworkStatus::Connected, true)
+            .await;
+        assert_eq!(portal, None);
+    }
+
+    #[core_async::test]
+    async fn test_change_stream_keeps_probe_url() {
+        let monitor =
+            DesktopNetworkMonitor::new().with_captive_portal_probe_url("http://127.0.0.1:9/");
+        let stream = monitor.change_stream();
+        assert_eq!(stream.monitor.probe_url, "http://127.0.0.1:9/");
+        assert!(Arc::ptr_eq(
+            &stream.monitor.cached_info,
+            &monitor.cached_info
+        ));
+    }
+
+    #[test]
+    fn test_vpn_interface_names() {
+        for name in ["tun0", "wg0", "utun3", "tailscale0", "ppp0"] {
+            assert!(is_vpn_interface(name), "{name} should be a VPN interface");
+        }
+        for name in ["lo", "eth0", "wlan0", "enp3s0", "docker0"] {
+            assert!(
+                !is_vpn_interface(name),
+                "{name} should not be a VPN interface"
+            );
+        }
+    }
 }
@@ -0,0 +1,337 @@
+//! Platform Network Change Notifications
+//!
+//! Wakes the desktop network change stream when the OS reports that network
+//! interfaces, addresses or routes changed:
+//! - Linux: `NETLINK_ROUTE` socket subscribed to link, address and route groups
+//! - macOS and the BSDs: `PF_ROUTE` routing socket
+//! - Windows: `NotifyIpInterfaceChange` and `NotifyUnicastIpAddressChange`
+//!
+//! `NetworkWatcher::new` returns `None` where no notification API exists, and
+//! the caller falls back to polling.
+
+use std::io;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// How long to let a burst of notifications settle before re-checking
+///
+/// Bringing an interface up reports the link, then each address and route.
+const SETTLE_DELAY: Duration = Duration::from_millis(500);
+
+/// Listens for the platform's network change notifications
+pub(crate) struct NetworkWatcher {
+    inner: imp::Watcher,
+}
+
+impl NetworkWatcher {
+    /// Start listening, or `None` if the platform can't notify about changes
+    pub(crate) fn new() -> Option<Self> {
+        match imp::Watcher::new() {
+            Ok(inner) => Some(Self { inner }),
+            Err(e) if e.kind() == io::ErrorKind::Unsupported => {
+                debug!("No network change notifications on this platform");
+                None
+            }
+            Err(e) => {
+                warn!(error = %e, "Failed to watch for network changes");
+                None
+            }
+        }
+    }
+
+    /// Wait until the platform reports a change and the burst has settled
+    pub(crate) async fn changed(&mut self) -> io::Result<()> {
+        self.inner.wait().await?;
+        tokio::time::sleep(SETTLE_DELAY).await;
+        self.inner.drain();
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+mod imp {
+    use std::io;
+    use std::os::fd::{AsRawFd, OwnedFd};
+    use tokio::io::unix::AsyncFd;
+
+    /// Socket the kernel writes a message to on every change
+    pub(super) struct Watcher {
+        socket: AsyncFd<OwnedFd>,
+    }
+
+    impl Watcher {
+        pub(super) fn new() -> io::Result<Self> {
+            Ok(Self {
+                socket: AsyncFd::new(open_socket()?)?,
+            })
+        }
+
+        pub(super) async fn wait(&mut self) -> io::Result<()> {
+            // Only the arrival of a message matters, not its content
+            let mut buffer = [0u8; 4096];
+            loop {
+                let mut guard = self.socket.readable().await?;
+                match guard.try_io(|socket| recv(socket.get_ref(), &mut buffer)) {
+                    Ok(Ok(_)) => return Ok(()),
+                    // The kernel dropped messages because they arrived too fast
+                    Ok(Err(e)) if e.raw_os_error() == Some(libc::ENOBUFS) => return Ok(()),
+                    Ok(Err(e)) => return Err(e),
+                    Err(_would_block) => continue,
+                }
+            }
+        }
+
+        pub(super) fn drain(&mut self) {
+            let mut buffer = [0u8; 4096];
+            while recv(self.socket.get_ref(), &mut buffer).is_ok() {}
+        }
+    }
+
+    fn recv(socket: &OwnedFd, buffer: &mut [u8]) -> io::Result<usize> {
+        // SAFETY: the buffer is valid for writes of its whole length
+        let read = unsafe {
+            libc::recv(
+                socket.as_raw_fd(),
+                buffer.as_mut_ptr().cast(),
+                buffer.len(),
+                libc::MSG_DONTWAIT,
+            )
+        };
+        if read < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(read as usize)
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn open_socket() -> io::Result<OwnedFd> {
+        use std::os::fd::FromRawFd;
+
+        // SAFETY: socket(2) has no memory arguments
+        let fd = unsafe {
+            libc::socket(
+                libc::AF_NETLINK,
+                libc::SOCK_RAW | libc::SOCK_CLOEXEC | libc::SOCK_NONBLOCK,
+                libc::NETLINK_ROUTE,
+            )
+        };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: the descriptor was just opened and nothing else owns it
+        let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+
+        // SAFETY: sockaddr_nl is plain data, valid when zeroed
+        let mut address: libc::sockaddr_nl = unsafe { std::mem::zeroed() };
+        address.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+        address.nl_groups = (libc::RTMGRP_LINK
+            | libc::RTMGRP_IPV4_IFADDR
+            | libc::RTMGRP_IPV6_IFADDR
+            | libc::RTMGRP_IPV4_ROUTE
+            | libc::RTMGRP_IPV6_ROUTE) as u32;
+        // SAFETY: the address points to a sockaddr_nl of the given length
+        let bound = unsafe {
+            libc::bind(
+                socket.as_raw_fd(),
+                (&address as *const libc::sockaddr_nl).cast(),
+                std::mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+            )
+        };
+        if bound < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(socket)
+    }
+
+    #[cfg(any(
+        target_os = "macos",
+        target_os = "ios",
+        target_os = "freebsd",
+        target_os = "dragonfly",
+        target_os = "openbsd",
+        target_os = "netbsd"
+    ))]
+    fn open_socket() -> io::Result<OwnedFd> {
+        use std::os::fd::FromRawFd;
+
+        // SAFETY: socket(2) has no memory arguments
+        let fd = unsafe { libc::socket(libc::PF_ROUTE, libc::SOCK_RAW, libc::AF_UNSPEC) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: the descriptor was just opened and nothing else owns it
+        let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+
+        // SAFETY: fcntl(2) on a descriptor we own, with integer arguments
+        let configured = unsafe {
+            libc::fcntl(socket.as_raw_fd(), libc::F_SETFD, libc::FD_CLOEXEC) >= 0
+                && libc::fcntl(socket.as_raw_fd(), libc::F_SETFL, libc::O_NONBLOCK) >= 0
+        };
+        if !configured {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(socket)
+    }
+
+    #[cfg(not(any(
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "ios",
+        target_os = "freebsd",
+        target_os = "dragonfly",
+        target_os = "openbsd",
+        target_os = "netbsd"
+    )))]
+    fn open_socket() -> io::Result<OwnedFd> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use futures_util::FutureExt;
+    use std::ffi::c_void;
+    use std::io;
+    use std::sync::Arc;
+    use tokio::sync::Notify;
+    use windows_sys::Win32::Foundation::{HANDLE, NO_ERROR};
+    use windows_sys::Win32::NetworkManagement::IpHelper::{
+        CancelMibChangeNotify2, NotifyIpInterfaceChange, NotifyUnicastIpAddressChange,
+        MIB_IPINTERFACE_ROW, MIB_NOTIFICATION_TYPE, MIB_UNICASTIPADDRESS_ROW,
+    };
+    use windows_sys::Win32::Networking::WinSock::AF_UNSPEC;
+
+    /// IP Helper registrations whose callbacks wake the stream
+    pub(super) struct Watcher {
+        notify: Arc<Notify>,
+        handles: Vec<HANDLE>,
+    }
+
+    // SAFETY: the handles are only passed to CancelMibChangeNotify2, which
+    // may be called from any thread
+    unsafe impl Send for Watcher {}
+
+    impl Watcher {
+        pub(super) fn new() -> io::Result<Self> {
+            let notify = Arc::new(Notify::new());
+            let context = Arc::as_ptr(&notify).cast::<c_void>();
+            // Dropping the watcher cancels whatever was registered so far
+            let mut watcher = Self {
+                notify,
+                handles: Vec::new(),
+            };
+
+            let mut handle: HANDLE = std::ptr::null_mut();
+            // SAFETY: the context outlives the registration, which Drop cancels
+            let status = unsafe {
+                NotifyIpInterfaceChange(
+                    AF_UNSPEC,
+                    Some(on_interface_change),
+                    context,
+                    false,
+                    &mut handle,
+                )
+            };
+            if status != NO_ERROR {
+                return Err(io::Error::from_raw_os_error(status as i32));
+            }
+            watcher.handles.push(handle);
+
+            let mut handle: HANDLE = std::ptr::null_mut();
+            // SAFETY: as above
+            let status = unsafe {
+                NotifyUnicastIpAddressChange(
+                    AF_UNSPEC,
+                    Some(on_address_change),
+                    context,
+                    false,
+                    &mut handle,
+                )
+            };
+            if status != NO_ERROR {
+                return Err(io::Error::from_raw_os_error(status as i32));
+            }
+            watcher.handles.push(handle);
+
+            Ok(watcher)
+        }
+
+        pub(super) async fn wait(&mut self) -> io::Result<()> {
+            self.notify.notified().await;
+            Ok(())
+        }
+
+        pub(super) fn drain(&mut self) {
+            // A notification that arrived while settling is already covered
+            let _ = self.notify.notified().now_or_never();
+        }
+    }
+
+    impl Drop for Watcher {
+        fn drop(&mut self) {
+            for handle in self.handles.drain(..) {
+                // SAFETY: the handle came from a successful registration;
+                // cancelling waits for running callbacks to return
+                unsafe {
+                    CancelMibChangeNotify2(handle);
+                }
+            }
+        }
+    }
+
+    unsafe extern "system" fn on_interface_change(
+        context: *const c_void,
+        _row: *const MIB_IPINTERFACE_ROW,
+        _notification_type: MIB_NOTIFICATION_TYPE,
+    ) {
+        // SAFETY: the context is the watcher's Notify, alive while registered
+        let notify = unsafe { &*context.cast::<Notify>() };
+        notify.notify_one();
+    }
+
+    unsafe extern "system" fn on_address_change(
+        context: *const c_void,
+        _row: *const MIB_UNICASTIPADDRESS_ROW,
+        _notification_type: MIB_NOTIFICATION_TYPE,
+    ) {
+        // SAFETY: the context is the watcher's Notify, alive while registered
+        let notify = unsafe { &*context.cast::<Notify>() };
+        notify.notify_one();
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+mod imp {
+    use std::io;
+
+    /// No notification API to listen to
+    pub(super) enum Watcher {}
+
+    impl Watcher {
+        pub(super) fn new() -> io::Result<Self> {
+            Err(io::ErrorKind::Unsupported.into())
+        }
+
+        pub(super) async fn wait(&mut self) -> io::Result<()> {
+            match *self {}
+        }
+
+        pub(super) fn drain(&mut self) {
+            match *self {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(any(target_os = "linux", target_os = "macos", windows))]
+    #[core_async::test]
+    async fn test_watcher_listens_on_supported_platforms() {
+        assert!(NetworkWatcher::new().is_some());
+    }
+}
@@ -3,10 +3,13 @@
 use async_trait::async_trait;
 use bridge_traits::{
     error::{BridgeError, Result},
-    storage::{SettingsStore, SettingsTransaction},
+    storage::{SettingWatchStream, SettingsStore, SettingsTransaction, SettingsWatchers},
+    time::{Clock, SystemClock},
 };
 use sqlx::{sqlite::SqlitePool, Row};
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 use tracing::{debug, error};
 
 /// SQLite-backed settings store implementation
@@ -15,8 +18,14 @@ use tracing::{debug, error};
 /// - Type-safe value storage
 /// - Transactional updates
 /// - Async operations
+/// - Optional per-value expiry, checked lazily on read
+/// - Change notifications for writes made through this store
 pub struct SqliteSettingsStore {
     pool: SqlitePool,
+    /// Decides when values stored with a TTL expire
+    clock: Arc<dyn Clock>,
+    /// Streams handed out by `watch`, shared with transactions
+    watchers: Arc<SettingsWatchers>,
 }
 
 impl SqliteSettingsStore {
@@ -36,25 +45,11 @@ impl SqliteSettingsStore {
         let pool = SqlitePool::connect(&db_url)
             .await
             .map_err(|e| BridgeError::OperationFailed(format!("Failed to connect to DB: {}", e)))?;
-
-        // Create settings table
-        sqlx::query(
-            r#"
-            CREATE TABLE IF NOT EXISTS settings (
-                key TEXT PRIMARY KEY,
-                value TEXT NOT NULL,
-                value_type TEXT NOT NULL,
-                updated_at INTEGER NOT NULL
-            )
-            "#,
-        )
-        .execute(&pool)
-        .await
-        .map_err(|e| BridgeError::OperationFailed(format!("Failed to create table: {}", e)))?;
+        Self::init_schema(&pool).await?;
 
         debug!(path = ?db_path, "Initialized settings store");
 
-        Ok(Self { pool })
+        Ok(Self::from_pool(pool))
     }
 
     /// Create an in-memory settings store (for testing)
@@ -63,22 +58,61 @@ impl SqliteSettingsStore {
             .await
             .map_err(|e| BridgeError::OperationFailed(format!("Failed to connect to DB: {}", e)))?;
 
-        // Create settings table
+        Self::init_schema(&pool).await?;
+
+        Ok(Self::from_pool(pool))
+    }
+
+    /// Use a custom clock to decide when values expire (for deterministic tests)
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn from_pool(pool: SqlitePool) -> Self {
+        Self {
+            pool,
+            clock: Arc::new(SystemClock),
+            watchers: Arc::new(SettingsWatchers::new()),
+        }
+    }
+
+    /// Create the settings table, adding columns missing from older databases
+    async fn init_schema(pool: &SqlitePool) -> Result<()> {
+        // `expires_at` is a Unix timestamp in milliseconds, NULL for permanent values
         sqlx::query(
             r#"
             CREATE TABLE IF NOT EXISTS settings (
                 key TEXT PRIMARY KEY,
                 value TEXT NOT NULL,
                 value_type TEXT NOT NULL,
-                updated_at INTEGER NOT NULL
+                updated_at INTEGER NOT NULL,
+                expires_at INTEGER
             )
             "#,
         )
-        .execute(&pool)
+        .execute(pool)
         .await
         .map_err(|e| BridgeError::OperationFailed(format!("Failed to create table: {}", e)))?;
 
-        Ok(Self { pool })
+        let has_expires_at =
+            sqlx::query("SELECT 1 FROM pragma_table_info('settings') WHERE name = 'expires_at'")
+                .fetch_optional(pool)
+                .await
+                .map_err(|e| {
+                    BridgeError::OperationFailed(format!("Failed to inspect table: {}", e))
+                })?
+                .is_some();
+        if !has_expires_at {
+            sqlx::query("ALTER TABLE settings ADD COLUMN expires_at INTEGER")
+                .execute(pool)
+                .await
+                .map_err(|e| {
+                    BridgeError::OperationFailed(format!("Failed to migrate table: {}", e))
+                })?;
+        }
+
+        Ok(())
     }
 
     /// Get the current Unix timestamp
@@ -89,37 +123,60 @@ impl SqliteSettingsStore {
             .as_secs() as i64
     }
 
+    /// Current time in milliseconds, as used for `expires_at`
+    fn now_millis(&self) -> i64 {
+        self.clock.unix_timestamp_millis()
+    }
+
     /// Set a value with type information
     async fn set_value(&self, key: &str, value: &str, value_type: &str) -> Result<()> {
+        self.set_value_expiring(key, value, value_type, None).await
+    }
+
+    /// Set a value with type information and an optional expiry timestamp
+    async fn set_value_expiring(
+        &self,
+        key: &str,
+        value: &str,
+        value_type: &str,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
         sqlx::query(
             r#"
-            INSERT INTO settings (key, value, value_type, updated_at)
-            VALUES (?, ?, ?, ?)
+            INSERT INTO settings (key, value, value_type, updated_at, expires_at)
+            VALUES (?, ?, ?, ?, ?)
             ON CONFLICT(key) DO UPDATE SET
                 value = excluded.value,
                 value_type = excluded.value_type,
-                updated_at = excluded.updated_at
+                updated_at = excluded.updated_at,
+                expires_at = excluded.expires_at
             "#,
         )
         .bind(key)
         .bind(value)
         .bind(value_type)
         .bind(Self::now())
+        .bind(expires_at)
         .execute(&self.pool)
         .await
         .map_err(|e| BridgeError::OperationFailed(format!("Failed to set setting: {}", e)))?;
 
         debug!(key = key, value_type = value_type, "Stored setting");
+        self.watchers.notify(key, Some(value));
         Ok(())
     }
 
     /// Get a value and verify its type
     async fn get_value(&self, key: &str, expected_type: &str) -> Result<Option<String>> {
-        let row = sqlx::query("SELECT value, value_type FROM settings WHERE key = ?")
-            .bind(key)
-            .fetch_optional(&self.pool)
-            .await
-            .map_err(|e| BridgeError::OperationFailed(format!("Failed to get setting: {}", e)))?;
+        let row = sqlx::query(
+            "SELECT value, value_type FROM settings \
+             WHERE key = ? AND (expires_at IS NULL OR expires_at > ?)",
+        )
+        .bind(key)
+        .bind(self.now_millis())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| BridgeError::OperationFailed(format!("Failed to get setting: {}", e)))?;
 
         match row {
             Some(row) => {
@@ -157,6 +214,31 @@ impl SettingsStore for SqliteSettingsStore {
         self.get_value(key, "string").await
     }
 
+    async fn set_string_with_ttl(&self, key: &str, value: &str, ttl: Duration) -> Result<()> {
+        let ttl_ms = i64::try_from(ttl.as_millis()).unwrap_or(i64::MAX);
+        let expires_at = self.now_millis().saturating_add(ttl_ms);
+        self.set_value_expiring(key, value, "string", Some(expires_at))
+            .await
+    }
+
+    async fn purge_expired(&self) -> Result<usize> {
+        let rows = sqlx::query("DELETE FROM settings WHERE expires_at <= ? RETURNING key")
+            .bind(self.now_millis())
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| {
+                BridgeError::OperationFailed(format!("Failed to purge settings: {}", e))
+            })?;
+
+        for row in &rows {
+            self.watchers.notify(row.get(0), None);
+        }
+
+        let purged = rows.len();
+        debug!(purged = purged, "Purged expired settings");
+        Ok(purged)
+    }
+
     async fn set_bool(&self, key: &str, value: bool) -> Result<()> {
         self.set_value(key, &value.to_string(), "bool").await
     }
@@ -206,24 +288,31 @@ impl SettingsStore for SqliteSettingsStore {
             })?;
 
         debug!(key = key, "Deleted setting");
+        self.watchers.notify(key, None);
         Ok(())
     }
 
     async fn has_key(&self, key: &str) -> Result<bool> {
-        let row = sqlx::query("SELECT 1 FROM settings WHERE key = ?")
-            .bind(key)
-            .fetch_optional(&self.pool)
-            .await
-            .map_err(|e| BridgeError::OperationFailed(format!("Failed to check key: {}", e)))?;
+        let row = sqlx::query(
+            "SELECT 1 FROM settings WHERE key = ? AND (expires_at IS NULL OR expires_at > ?)",
+        )
+        .bind(key)
+        .bind(self.now_millis())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| BridgeError::OperationFailed(format!("Failed to check key: {}", e)))?;
 
         Ok(row.is_some())
     }
 
     async fn list_keys(&self) -> Result<Vec<String>> {
-        let rows = sqlx::query("SELECT key FROM settings ORDER BY key")
-            .fetch_all(&self.pool)
-            .await
-            .map_err(|e| BridgeError::OperationFailed(format!("Failed to list keys: {}", e)))?;
+        let rows = sqlx::query(
+            "SELECT key FROM settings WHERE expires_at IS NULL OR expires_at > ? ORDER BY key",
+        )
+        .bind(self.now_millis())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| BridgeError::OperationFailed(format!("Failed to list keys: {}", e)))?;
 
         let keys = rows.into_iter().map(|row| row.get(0)).collect();
         Ok(keys)
@@ -238,6 +327,7 @@ impl SettingsStore for SqliteSettingsStore {
             })?;
 
         debug!("Cleared all settings");
+        self.watchers.notify_cleared();
         Ok(())
     }
 
@@ -246,39 +336,102 @@ impl SettingsStore for SqliteSettingsStore {
             BridgeError::OperationFailed(format!("Failed to begin transaction: {}", e))
         })?;
 
-        Ok(Box::new(SqliteSettingsTransaction { tx: Some(tx) }))
+        Ok(Box::new(SqliteSettingsTransaction {
+            tx: Some(tx),
+            watchers: self.watchers.clone(),
+            written: Vec::new(),
+        }))
+    }
+
+    async fn watch(&self, key: &str) -> Result<SettingWatchStream> {
+        let row = sqlx::query(
+            "SELECT value FROM settings WHERE key = ? AND (expires_at IS NULL OR expires_at > ?)",
+        )
+        .bind(key)
+        .bind(self.now_millis())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| BridgeError::OperationFailed(format!("Failed to get setting: {}", e)))?;
+
+        let current = row.map(|row| row.get(0));
+        Ok(self.watchers.subscribe(key, current))
     }
 }
 
 /// SQLite settings transaction
 struct SqliteSettingsTransaction {
     tx: Option<sqlx::Transaction<'static, sqlx::Sqlite>>,
+    watchers: Arc<SettingsWatchers>,
+    /// Changes to publish once the transaction commits, `None` for deletes
+    written: Vec<(String, Option<String>)>,
 }
 
-#[async_trait]
-impl SettingsTransaction for SqliteSettingsTransaction {
-    async fn set_string(&mut self, key: &str, value: &str) -> Result<()> {
-        let tx = self.tx.as_mut().ok_or_else(|| {
+impl SqliteSettingsTransaction {
+    fn tx(&mut self) -> Result<&mut sqlx::Transaction<'static, sqlx::Sqlite>> {
+        self.tx.as_mut().ok_or_else(|| {
             BridgeError::OperationFailed("Transaction already committed".to_string())
-        })?;
+        })
+    }
+
+    /// Set a value with type information within the transaction
+    async fn set_value(&mut self, key: &str, value: &str, value_type: &str) -> Result<()> {
+        let tx = self.tx()?;
 
         sqlx::query(
             r#"
-            INSERT INTO settings (key, value, value_type, updated_at)
-            VALUES (?, ?, 'string', ?)
+            INSERT INTO settings (key, value, value_type, updated_at, expires_at)
+            VALUES (?, ?, ?, ?, NULL)
             ON CONFLICT(key) DO UPDATE SET
                 value = excluded.value,
                 value_type = excluded.value_type,
-                updated_at = excluded.updated_at
+                updated_at = excluded.updated_at,
+                expires_at = excluded.expires_at
             "#,
         )
         .bind(key)
         .bind(value)
+        .bind(value_type)
         .bind(SqliteSettingsStore::now())
         .execute(&mut **tx)
         .await
         .map_err(|e| BridgeError::OperationFailed(format!("Failed to set setting: {}", e)))?;
 
+        self.written
+            .push((key.to_string(), Some(value.to_string())));
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SettingsTransaction for SqliteSettingsTransaction {
+    async fn set_string(&mut self, key: &str, value: &str) -> Result<()> {
+        self.set_value(key, value, "string").await
+    }
+
+    async fn set_bool(&mut self, key: &str, value: bool) -> Result<()> {
+        self.set_value(key, &value.to_string(), "bool").await
+    }
+
+    async fn set_i64(&mut self, key: &str, value: i64) -> Result<()> {
+        self.set_value(key, &value.to_string(), "i64").await
+    }
+
+    async fn set_f64(&mut self, key: &str, value: f64) -> Result<()> {
+        self.set_value(key, &value.to_string(), "f64").await
+    }
+
+    async fn delete(&mut self, key: &str) -> Result<()> {
+        let tx = self.tx()?;
+
+        sqlx::query("DELETE FROM settings WHERE key = ?")
+            .bind(key)
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| {
+                BridgeError::OperationFailed(format!("Failed to delete setting: {}", e))
+            })?;
+
+        self.written.push((key.to_string(), None));
         Ok(())
     }
 
@@ -291,6 +444,10 @@ impl SettingsTransaction for SqliteSettingsTransaction {
             .await
             .map_err(|e| BridgeError::OperationFailed(format!("Failed to commit: {}", e)))?;
 
+        for (key, value) in &self.written {
+            self.watchers.notify(key, value.as_deref());
+        }
+
         debug!("Committed transaction");
         Ok(())
     }
@@ -312,6 +469,7 @@ impl SettingsTransaction for SqliteSettingsTransaction {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use bridge_traits::test_util::ManualClock;
 
     #[core_async::test]
     async fn test_settings_store_creation() {
@@ -359,4 +517,122 @@ mod tests {
         let keys = store.list_keys().await.unwrap();
         assert_eq!(keys, vec!["key1", "key2"]);
     }
+
+    #[core_async::test]
+    async fn test_string_with_ttl_expires() {
+        let clock = Arc::new(ManualClock::default());
+        let store = SqliteSettingsStore::in_memory()
+            .await
+            .unwrap()
+            .with_clock(clock.clone());
+
+        store
+            .set_string_with_ttl("last_network_check", "online", Duration::from_secs(60))
+            .await
+            .unwrap();
+        store.set_string("theme", "dark").await.unwrap();
+
+        clock.advance(Duration::from_secs(59));
+        assert_eq!(
+            store.get_string("last_network_check").await.unwrap(),
+            Some("online".to_string())
+        );
+
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(store.get_string("last_network_check").await.unwrap(), None);
+        assert!(!store.has_key("last_network_check").await.unwrap());
+        assert_eq!(store.list_keys().await.unwrap(), vec!["theme"]);
+
+        assert_eq!(store.purge_expired().await.unwrap(), 1);
+        assert_eq!(store.purge_expired().await.unwrap(), 0);
+        assert_eq!(
+            store.get_string("theme").await.unwrap(),
+            Some("dark".to_string())
+        );
+    }
+
+    #[core_async::test]
+    async fn test_plain_set_clears_ttl() {
+        let clock = Arc::new(ManualClock::default());
+        let store = SqliteSettingsStore::in_memory()
+            .await
+            .unwrap()
+            .with_clock(clock.clone());
+
+        store
+            .set_string_with_ttl("rate_limit_reset", "soon", Duration::from_secs(1))
+            .await
+            .unwrap();
+        store.set_string("rate_limit_reset", "never").await.unwrap();
+
+        clock.advance(Duration::from_secs(10));
+        assert_eq!(
+            store.get_string("rate_limit_reset").await.unwrap(),
+            Some("never".to_string())
+        );
+    }
+
+    #[core_async::test]
+    async fn test_watch_yields_written_values() {
+        use futures_util::StreamExt;
+
+        let store = SqliteSettingsStore::in_memory().await.unwrap();
+        store.set_bool("wifi_only", false).await.unwrap();
+
+        let mut changes = store.watch("wifi_only").await.unwrap();
+        assert_eq!(changes.next().await, Some(Some("false".to_string())));
+
+        // A write elsewhere, e.g. from background sync
+        store.set_bool("wifi_only", true).await.unwrap();
+        assert_eq!(changes.next().await, Some(Some("true".to_string())));
+
+        store.delete("wifi_only").await.unwrap();
+        assert_eq!(changes.next().await, Some(None));
+
+        let mut tx = store.begin_transaction().await.unwrap();
+        tx.set_string("wifi_only", "committed").await.unwrap();
+        tx.commit().await.unwrap();
+        assert_eq!(changes.next().await, Some(Some("committed".to_string())));
+    }
+
+    #[core_async::test]
+    async fn test_rolled_back_transaction_leaves_keys_unchanged() {
+        let store = SqliteSettingsStore::in_memory().await.unwrap();
+        store.set_bool("wifi_only", true).await.unwrap();
+        store.set_i64("cache_size_mb", 512).await.unwrap();
+        store.set_string("theme", "dark").await.unwrap();
+
+        let mut tx = store.begin_transaction().await.unwrap();
+        tx.set_bool("wifi_only", false).await.unwrap();
+        tx.set_i64("cache_size_mb", 1024).await.unwrap();
+        tx.set_f64("volume", 0.5).await.unwrap();
+        tx.delete("theme").await.unwrap();
+        tx.rollback().await.unwrap();
+
+        assert_eq!(store.get_bool("wifi_only").await.unwrap(), Some(true));
+        assert_eq!(store.get_i64("cache_size_mb").await.unwrap(), Some(512));
+        assert_eq!(store.get_f64("volume").await.unwrap(), None);
+        assert_eq!(
+            store.get_string("theme").await.unwrap(),
+            Some("dark".to_string())
+        );
+    }
+
+    #[core_async::test]
+    async fn test_committed_transaction_keeps_value_types() {
+        let store = SqliteSettingsStore::in_memory().await.unwrap();
+        store.set_string("theme", "dark").await.unwrap();
+
+        let mut tx = store.begin_transaction().await.unwrap();
+        tx.set_bool("wifi_only", false).await.unwrap();
+        tx.set_i64("cache_size_mb", 1024).await.unwrap();
+        tx.set_f64("volume", 0.5).await.unwrap();
+        tx.delete("theme").await.unwrap();
+        tx.commit().await.unwrap();
+
+        assert_eq!(store.get_bool("wifi_only").await.unwrap(), Some(false));
+        assert_eq!(store.get_i64("cache_size_mb").await.unwrap(), Some(1024));
+        assert_eq!(store.get_f64("volume").await.unwrap(), Some(0.5));
+        assert!(!store.has_key("theme").await.unwrap());
+    }
 }
@@ -0,0 +1,97 @@
+//! Integration tests for `ReqwestHttpClient` connection pooling
+//!
+//! A local HTTP/1.1 server counts accepted connections, so the tests can
+//! tell whether sequential requests reuse a pooled connection.
+
+use bridge_desktop::{PoolConfig, ReqwestHttpClient};
+use bridge_traits::http::{HttpClient, HttpMethod, HttpRequest};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Keep-alive server answering every request with `ok`
+struct CountingServer {
+    url: String,
+    connections: Arc<AtomicUsize>,
+}
+
+impl CountingServer {
+    async fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/metadata", listener.local_addr().unwrap());
+        let connections = Arc::new(AtomicUsize::new(0));
+
+        let accepted = connections.clone();
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                accepted.fetch_add(1, Ordering::SeqCst);
+                tokio::spawn(serve(socket));
+            }
+        });
+
+        Self { url, connections }
+    }
+
+    fn connections(&self) -> usize {
+        self.connections.load(Ordering::SeqCst)
+    }
+}
+
+/// Answer requests on one connection until the client closes it
+async fn serve(mut socket: TcpStream) {
+    let mut request = Vec::new();
+    let mut buffer = [0u8; 1024];
+    loop {
+        match socket.read(&mut buffer).await {
+            Ok(0) | Err(_) => return,
+            Ok(read) => request.extend_from_slice(&buffer[..read]),
+        }
+        // Requests are bodiless GETs, so the head ends the request
+        if request.ends_with(b"\r\n\r\n") {
+            request.clear();
+            let response = b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok";
+            if socket.write_all(response).await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+async fn fetch_sequentially(client: &ReqwestHttpClient, url: &str, count: usize) {
+    for _ in 0..count {
+        let response = client
+            .execute(HttpRequest::new(HttpMethod::Get, url))
+            .await
+            .unwrap();
+        assert_eq!(response.text().unwrap(), "ok");
+    }
+}
+
+#[core_async::test]
+async fn test_sequential_requests_reuse_one_connection() {
+    let server = CountingServer::start().await;
+    let client = ReqwestHttpClient::with_pool_config(PoolConfig {
+        max_idle_per_host: 4,
+        idle_timeout: Duration::from_secs(30),
+        ..Default::default()
+    });
+
+    fetch_sequentially(&client, &server.url, 50).await;
+
+    assert_eq!(server.connections(), 1);
+}
+
+#[core_async::test]
+async fn test_disabled_pool_opens_a_connection_per_request() {
+    let server = CountingServer::start().await;
+    let client = ReqwestHttpClient::with_pool_config(PoolConfig {
+        max_idle_per_host: 0,
+        ..Default::default()
+    });
+
+    fetch_sequentially(&client, &server.url, 5).await;
+
+    assert_eq!(server.connections(), 5);
+}
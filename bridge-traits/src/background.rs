@@ -2,8 +2,19 @@
 //!
 //! Provides platform-aware background task scheduling.
 
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 
+use core_async::sync::CancellationToken;
+use core_async::time::Instant;
+#[cfg(not(target_arch = "wasm32"))]
+use futures::future::BoxFuture;
+#[cfg(target_arch = "wasm32")]
+use futures::future::LocalBoxFuture;
+use futures::FutureExt;
+
 use crate::{
     error::Result,
     platform::{PlatformSend, PlatformSendSync},
@@ -20,6 +31,10 @@ pub struct TaskConstraints {
     pub requires_charging: bool,
     /// Require device to be idle
     pub requires_idle: bool,
+    /// Order among runs waiting for a free slot; higher runs first
+    pub priority: TaskPriority,
+    /// Drop a run that hasn't started by this instant
+    pub deadline: Option<Instant>,
 }
 
 impl Default for TaskConstraints {
@@ -29,14 +44,24 @@ impl Default for TaskConstraints {
             requires_network: true,
             requires_charging: false,
             requires_idle: false,
+            priority: TaskPriority::Normal,
+            deadline: None,
         }
     }
 }
 
+impl TaskConstraints {
+    /// Whether the deadline has passed at `now`
+    pub fn is_expired(&self, now: Instant) -> bool {
+        self.deadline.is_some_and(|deadline| now >= deadline)
+    }
+}
+
 /// Task scheduling priority
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
 pub enum TaskPriority {
     Low,
+    #[default]
     Normal,
     High,
 }
@@ -51,6 +76,183 @@ impl TaskId {
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+type TaskFuture = BoxFuture<'static, ()>;
+#[cfg(target_arch = "wasm32")]
+type TaskFuture = LocalBoxFuture<'static, ()>;
+
+#[cfg(not(target_arch = "wasm32"))]
+type TaskFn = Box<dyn FnOnce(CancellationToken) -> TaskFuture + Send>;
+#[cfg(target_arch = "wasm32")]
+type TaskFn = Box<dyn FnOnce(CancellationToken) -> TaskFuture>;
+
+/// Work handed to [`BackgroundExecutor::spawn`]
+///
+/// The closure receives a token that is cancelled along with the task's
+/// [`TaskHandle`]; long-running work should check it between steps.
+pub struct SpawnedTask(TaskFn);
+
+impl SpawnedTask {
+    /// Wrap a closure producing the task's future from its token
+    pub fn new<F, Fut>(task: F) -> Self
+    where
+        F: FnOnce(CancellationToken) -> Fut + PlatformSend + 'static,
+        Fut: Future<Output = ()> + PlatformSend + 'static,
+    {
+        #[cfg(not(target_arch = "wasm32"))]
+        let task: TaskFn = Box::new(move |token| task(token).boxed());
+        #[cfg(target_arch = "wasm32")]
+        let task: TaskFn = Box::new(move |token| task(token).boxed_local());
+        Self(task)
+    }
+
+    /// Start the work, returning its handle and the future to drive
+    ///
+    /// The future stops at its next suspension point once the handle is
+    /// cancelled, even if the work never checks its token.
+    pub fn prepare(self) -> (TaskHandle, TaskFuture) {
+        let token = CancellationToken::new();
+        let finished = Arc::new(AtomicBool::new(false));
+        let handle = TaskHandle {
+            token: token.clone(),
+            finished: Arc::clone(&finished),
+            abort: None,
+        };
+
+        let work = (self.0)(token.clone());
+        let run = async move {
+            // Also marks aborted tasks finished, as their future is dropped
+            let _finished = FinishedGuard(finished);
+            let cancelled = token.cancelled();
+            futures::pin_mut!(cancelled);
+            futures::future::select(work, cancelled).await;
+        };
+        #[cfg(not(target_arch = "wasm32"))]
+        let run = run.boxed();
+        #[cfg(target_arch = "wasm32")]
+        let run = run.boxed_local();
+
+        (handle, run)
+    }
+}
+
+struct FinishedGuard(Arc<AtomicBool>);
+
+impl Drop for FinishedGuard {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Handle to a task started with [`BackgroundExecutor::spawn`]
+///
+/// Dropping the handle leaves the task running.
+#[derive(Clone)]
+pub struct TaskHandle {
+    token: CancellationToken,
+    finished: Arc<AtomicBool>,
+    abort: Option<Arc<dyn Fn() + Send + Sync>>,
+}
+
+impl TaskHandle {
+    /// Also run `abort` on [`cancel`](Self::cancel), for executors that can
+    /// stop a task without its cooperation
+    pub fn with_abort(mut self, abort: impl Fn() + Send + Sync + 'static) -> Self {
+        self.abort = Some(Arc::new(abort));
+        self
+    }
+
+    /// Cancel the task
+    ///
+    /// Its token is cancelled and the task stops at its next suspension
+    /// point. Cancelling a finished task does nothing.
+    pub fn cancel(&self) {
+        self.token.cancel();
+        if let Some(abort) = &self.abort {
+            abort();
+        }
+    }
+
+    /// Whether the task was cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    /// Whether the task has stopped, by completing or being cancelled
+    pub fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::SeqCst)
+    }
+}
+
+impl std::fmt::Debug for TaskHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TaskHandle")
+            .field("cancelled", &self.is_cancelled())
+            .field("finished", &self.is_finished())
+            .finish()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+type PeriodicFn = Box<dyn Fn(CancellationToken) -> TaskFuture + Send + Sync>;
+#[cfg(target_arch = "wasm32")]
+type PeriodicFn = Box<dyn Fn(CancellationToken) -> TaskFuture>;
+
+/// Work run repeatedly by [`BackgroundExecutor::schedule_periodic`]
+///
+/// Each run receives a token that is cancelled when the schedule stops.
+pub struct PeriodicTask(PeriodicFn);
+
+impl PeriodicTask {
+    /// Wrap a closure producing the future of one run
+    pub fn new<F, Fut>(task: F) -> Self
+    where
+        F: Fn(CancellationToken) -> Fut + PlatformSendSync + 'static,
+        Fut: Future<Output = ()> + PlatformSend + 'static,
+    {
+        #[cfg(not(target_arch = "wasm32"))]
+        let task: PeriodicFn = Box::new(move |token| task(token).boxed());
+        #[cfg(target_arch = "wasm32")]
+        let task: PeriodicFn = Box::new(move |token| task(token).boxed_local());
+        Self(task)
+    }
+
+    /// Start one run
+    pub fn run(&self, token: CancellationToken) -> TaskFuture {
+        (self.0)(token)
+    }
+}
+
+/// Handle to a schedule from [`BackgroundExecutor::schedule_periodic`]
+///
+/// Dropping the handle stops the schedule and cancels a run in progress.
+#[must_use = "dropping the handle stops the schedule"]
+#[derive(Debug)]
+pub struct ScheduleHandle(TaskHandle);
+
+impl ScheduleHandle {
+    /// Wrap the handle of the task driving the schedule
+    pub fn new(handle: TaskHandle) -> Self {
+        Self(handle)
+    }
+
+    /// Stop the schedule
+    pub fn cancel(&self) {
+        self.0.cancel();
+    }
+
+    /// Whether the schedule has stopped
+    pub fn is_finished(&self) -> bool {
+        self.0.is_finished()
+    }
+}
+
+impl Drop for ScheduleHandle {
+    fn drop(&mut self) {
+        self.0.cancel();
+    }
+}
+
 /// Task execution status
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TaskStatus {
@@ -153,6 +355,41 @@ pub trait BackgroundExecutor: PlatformSendSync {
     /// Returns `None` if the information is not available or if the task
     /// will execute immediately.
     async fn next_execution_time(&self, task_id: &TaskId) -> Result<Option<Duration>>;
+
+    /// Start `task` now, returning a handle to cancel it
+    ///
+    /// The default implementation spawns the task on the current async
+    /// runtime and relies on cooperative cancellation through its token.
+    fn spawn(&self, task: SpawnedTask) -> TaskHandle {
+        let (handle, run) = task.prepare();
+        core_async::task::spawn(run);
+        handle
+    }
+
+    /// Run `task` every `interval` until the returned handle is dropped
+    ///
+    /// Runs never overlap: one that takes longer than `interval` delays the
+    /// next. The schedule stops once `constraints.deadline` passes.
+    ///
+    /// The default implementation sleeps between runs and ignores the other
+    /// constraints; executors that know about network and lifecycle state
+    /// override it.
+    fn schedule_periodic(
+        &self,
+        interval: Duration,
+        constraints: TaskConstraints,
+        task: PeriodicTask,
+    ) -> ScheduleHandle {
+        ScheduleHandle::new(self.spawn(SpawnedTask::new(move |token| async move {
+            loop {
+                core_async::time::sleep(interval).await;
+                if constraints.is_expired(Instant::now()) {
+                    break;
+                }
+                task.run(token.child_token()).await;
+            }
+        })))
+    }
 }
 
 /// Lifecycle state
@@ -166,12 +403,22 @@ pub enum LifecycleState {
     Suspended,
 }
 
+/// System condition reported alongside lifecycle transitions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SystemEvent {
+    /// The OS is running low on memory; caches and buffers should shrink
+    MemoryWarning,
+    /// Low-power (battery saver) mode was turned on (`true`) or off
+    LowPowerMode(bool),
+}
+
 /// Lifecycle observer trait
 ///
 /// Notifies the core about app lifecycle transitions so it can:
 /// - Pause expensive operations when backgrounded
 /// - Release resources before suspension
 /// - Resume operations when foregrounded
+/// - Shrink buffers under memory pressure or in low-power mode
 ///
 /// # Platform Support
 ///
@@ -206,6 +453,13 @@ pub trait LifecycleObserver: PlatformSendSync {
 
     /// Subscribe to lifecycle state changes
     async fn subscribe_changes(&self) -> Result<Box<dyn LifecycleChangeStream>>;
+
+    /// Subscribe to memory-pressure and low-power events
+    ///
+    /// Platforms without these signals use the default, which never emits.
+    async fn subscribe_system_events(&self) -> Result<Box<dyn SystemEventStream>> {
+        Ok(Box::new(NoSystemEvents))
+    }
 }
 
 /// Stream of lifecycle state changes
@@ -218,6 +472,28 @@ pub trait LifecycleChangeStream: PlatformSend {
     async fn next(&mut self) -> Option<LifecycleState>;
 }
 
+/// Stream of system events
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait SystemEventStream: PlatformSend {
+    /// Get the next system event
+    ///
+    /// Returns `None` when the stream is closed.
+    async fn next(&mut self) -> Option<SystemEvent>;
+}
+
+/// System event stream for platforms without the signals (never emits)
+struct NoSystemEvents;
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl SystemEventStream for NoSystemEvents {
+    async fn next(&mut self) -> Option<SystemEvent> {
+        std::future::pending::<()>().await;
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -232,6 +508,33 @@ mod tests {
         assert!(constraints.requires_wifi);
         assert!(constraints.requires_network);
         assert!(!constraints.requires_charging);
+        assert_eq!(constraints.priority, TaskPriority::Normal);
+        assert!(!constraints.is_expired(Instant::now()));
+    }
+
+    #[test]
+    fn test_task_constraints_deadline() {
+        let now = Instant::now();
+        let constraints = TaskConstraints {
+            deadline: Some(now),
+            ..Default::default()
+        };
+
+        assert!(constraints.is_expired(now));
+        assert!(!constraints.is_expired(now.checked_sub(Duration::from_millis(1)).unwrap()));
+    }
+
+    #[core_async::test]
+    async fn test_cancelled_task_stops_cooperatively() {
+        let (handle, run) = SpawnedTask::new(|_token| std::future::pending::<()>()).prepare();
+        let task = core_async::task::spawn(run);
+        assert!(!handle.is_finished());
+
+        handle.cancel();
+        task.await.unwrap();
+
+        assert!(handle.is_cancelled());
+        assert!(handle.is_finished());
     }
 
     #[test]
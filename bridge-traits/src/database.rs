@@ -31,7 +31,10 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
-use crate::{error::Result, platform::PlatformSendSync};
+use crate::{
+    error::{BridgeError, Result},
+    platform::PlatformSendSync,
+};
 
 // =============================================================================
 // Configuration
@@ -57,6 +60,14 @@ pub struct DatabaseConfig {
 
     /// Statement cache capacity
     pub cache_capacity: usize,
+
+    /// Tokenizer used by the full-text search indexes
+    pub fts_tokenizer: FtsTokenizer,
+
+    /// Key for an encrypted (SQLCipher) database
+    ///
+    /// Only native builds with SQLCipher support can open encrypted databases.
+    pub encryption_key: Option<DatabaseKey>,
 }
 
 impl DatabaseConfig {
@@ -72,9 +83,26 @@ impl DatabaseConfig {
             acquire_timeout_secs: 30,
             enable_cache: true,
             cache_capacity: 100,
+            fts_tokenizer: FtsTokenizer::default(),
+            encryption_key: None,
         }
     }
 
+    /// Set the full-text search tokenizer
+    ///
+    /// Changing the tokenizer of an existing database rebuilds its search
+    /// indexes on the next initialization.
+    pub fn with_fts_tokenizer(mut self, tokenizer: FtsTokenizer) -> Self {
+        self.fts_tokenizer = tokenizer;
+        self
+    }
+
+    /// Encrypt the database with `key`
+    pub fn with_encryption_key(mut self, key: DatabaseKey) -> Self {
+        self.encryption_key = Some(key);
+        self
+    }
+
     /// Create a configuration for an in-memory database
     pub fn in_memory() -> Self {
         Self {
@@ -84,6 +112,8 @@ impl DatabaseConfig {
             acquire_timeout_secs: 30,
             enable_cache: true,
             cache_capacity: 100,
+            fts_tokenizer: FtsTokenizer::default(),
+            encryption_key: None,
         }
     }
 }
@@ -94,6 +124,83 @@ impl Default for DatabaseConfig {
     }
 }
 
+/// Key of an encrypted database
+///
+/// `Debug` never prints the key material.
+#[derive(Clone, PartialEq, Eq)]
+pub enum DatabaseKey {
+    /// Passphrase the key is derived from with PBKDF2-HMAC-SHA512, salted
+    /// per database
+    Passphrase(String),
+    /// 256-bit key used as is, skipping key derivation
+    Raw([u8; 32]),
+}
+
+impl DatabaseKey {
+    /// Value of the SQLCipher `key` pragma for this key, quoted for SQL
+    pub fn pragma_value(&self) -> String {
+        match self {
+            DatabaseKey::Passphrase(passphrase) => {
+                format!("'{}'", passphrase.replace('\'', "''"))
+            }
+            DatabaseKey::Raw(bytes) => {
+                let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+                format!("\"x'{}'\"", hex)
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for DatabaseKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DatabaseKey::Passphrase(_) => f.write_str("DatabaseKey::Passphrase(..)"),
+            DatabaseKey::Raw(_) => f.write_str("DatabaseKey::Raw(..)"),
+        }
+    }
+}
+
+/// SQLite FTS5 tokenizer used for the library search indexes
+///
+/// The tokenizer is fixed when an FTS table is created, so switching it means
+/// rebuilding the index from the source tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum FtsTokenizer {
+    /// Word tokenizer that folds case and strips diacritics ("beyonce" matches
+    /// "Beyoncé"). Text without word separators, such as CJK, becomes a single
+    /// token and only matches as a whole.
+    Unicode61,
+    /// Substring matching on three-character sequences, with diacritics
+    /// stripped. Matches partial words and CJK text, but query terms shorter
+    /// than three characters match nothing.
+    #[default]
+    Trigram,
+    /// English stemming on top of `Unicode61` ("singing" matches "sing")
+    Porter,
+}
+
+impl FtsTokenizer {
+    /// Value of the FTS5 `tokenize` option for this tokenizer
+    pub fn tokenize_option(&self) -> &'static str {
+        match self {
+            FtsTokenizer::Unicode61 => "unicode61 remove_diacritics 2",
+            FtsTokenizer::Trigram => "trigram remove_diacritics 1",
+            FtsTokenizer::Porter => "porter unicode61 remove_diacritics 2",
+        }
+    }
+
+    /// Find the tokenizer whose `tokenize` option is `option`
+    pub fn from_tokenize_option(option: &str) -> Option<Self> {
+        [
+            FtsTokenizer::Unicode61,
+            FtsTokenizer::Trigram,
+            FtsTokenizer::Porter,
+        ]
+        .into_iter()
+        .find(|tokenizer| tokenizer.tokenize_option() == option.trim())
+    }
+}
+
 // =============================================================================
 // Query Result Types
 // =============================================================================
@@ -343,6 +450,112 @@ pub trait DatabaseAdapter: PlatformSendSync {
     async fn get_statistics(&self) -> Result<DatabaseStatistics>;
 }
 
+// Blanket implementation for Arc<dyn DatabaseAdapter> so a shared adapter can
+// be handed to code that wraps adapters in `Rc` (repositories on WASM)
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl DatabaseAdapter for std::sync::Arc<dyn DatabaseAdapter> {
+    async fn initialize(&mut self) -> Result<()> {
+        std::sync::Arc::get_mut(self)
+            .ok_or_else(|| {
+                BridgeError::OperationFailed("Cannot initialize a shared adapter".to_string())
+            })?
+            .initialize()
+            .await
+    }
+
+    async fn health_check(&self) -> Result<()> {
+        (**self).health_check().await
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        std::sync::Arc::get_mut(self)
+            .ok_or_else(|| {
+                BridgeError::OperationFailed("Cannot close a shared adapter".to_string())
+            })?
+            .close()
+            .await
+    }
+
+    async fn query(&self, query: &str, params: &[QueryValue]) -> Result<Vec<QueryRow>> {
+        (**self).query(query, params).await
+    }
+
+    async fn execute(&self, statement: &str, params: &[QueryValue]) -> Result<u64> {
+        (**self).execute(statement, params).await
+    }
+
+    async fn query_one_optional(
+        &self,
+        query: &str,
+        params: &[QueryValue],
+    ) -> Result<Option<QueryRow>> {
+        (**self).query_one_optional(query, params).await
+    }
+
+    async fn query_one(&self, query: &str, params: &[QueryValue]) -> Result<QueryRow> {
+        (**self).query_one(query, params).await
+    }
+
+    async fn begin_transaction(&self) -> Result<TransactionId> {
+        (**self).begin_transaction().await
+    }
+
+    async fn commit_transaction(&self, transaction_id: TransactionId) -> Result<()> {
+        (**self).commit_transaction(transaction_id).await
+    }
+
+    async fn rollback_transaction(&self, transaction_id: TransactionId) -> Result<()> {
+        (**self).rollback_transaction(transaction_id).await
+    }
+
+    async fn query_in_transaction(
+        &self,
+        transaction_id: TransactionId,
+        query: &str,
+        params: &[QueryValue],
+    ) -> Result<Vec<QueryRow>> {
+        (**self)
+            .query_in_transaction(transaction_id, query, params)
+            .await
+    }
+
+    async fn execute_in_transaction(
+        &self,
+        transaction_id: TransactionId,
+        statement: &str,
+        params: &[QueryValue],
+    ) -> Result<u64> {
+        (**self)
+            .execute_in_transaction(transaction_id, statement, params)
+            .await
+    }
+
+    async fn execute_batch(&self, statements: &[(&str, &[QueryValue])]) -> Result<Vec<u64>> {
+        (**self).execute_batch(statements).await
+    }
+
+    async fn get_schema_version(&self) -> Result<i64> {
+        (**self).get_schema_version().await
+    }
+
+    async fn apply_migration(&self, version: i64, up_sql: &str) -> Result<()> {
+        (**self).apply_migration(version, up_sql).await
+    }
+
+    async fn is_migration_applied(&self, version: i64) -> Result<bool> {
+        (**self).is_migration_applied(version).await
+    }
+
+    async fn last_insert_rowid(&self) -> Result<i64> {
+        (**self).last_insert_rowid().await
+    }
+
+    async fn get_statistics(&self) -> Result<DatabaseStatistics> {
+        (**self).get_statistics().await
+    }
+}
+
 // =============================================================================
 // Supporting Types
 // =============================================================================
@@ -456,6 +669,34 @@ mod tests {
         assert_eq!(config.cache_capacity, 100);
     }
 
+    #[test]
+    fn test_fts_tokenizer_option_round_trip() {
+        for tokenizer in [
+            FtsTokenizer::Unicode61,
+            FtsTokenizer::Trigram,
+            FtsTokenizer::Porter,
+        ] {
+            assert_eq!(
+                FtsTokenizer::from_tokenize_option(tokenizer.tokenize_option()),
+                Some(tokenizer)
+            );
+        }
+        assert_eq!(FtsTokenizer::from_tokenize_option("ascii"), None);
+
+        let config = DatabaseConfig::in_memory().with_fts_tokenizer(FtsTokenizer::Porter);
+        assert_eq!(config.fts_tokenizer, FtsTokenizer::Porter);
+    }
+
+    #[test]
+    fn test_database_key_pragma_value() {
+        let passphrase = DatabaseKey::Passphrase("it's secret".to_string());
+        assert_eq!(passphrase.pragma_value(), "'it''s secret'");
+        assert_eq!(format!("{:?}", passphrase), "DatabaseKey::Passphrase(..)");
+
+        let raw = DatabaseKey::Raw([0xab; 32]);
+        assert_eq!(raw.pragma_value(), format!("\"x'{}'\"", "ab".repeat(32)));
+    }
+
     #[test]
     fn test_database_config_from_path() {
         let config = DatabaseConfig::new("test.db");
@@ -11,6 +11,21 @@ pub enum BridgeError {
     #[error("Database error: {0}")]
     DatabaseError(String),
 
+    /// Stored data could not be decoded into the requested type
+    #[error("Deserialization error: {0}")]
+    Deserialization(String),
+
+    /// Every attempt of a retried request failed
+    ///
+    /// `last_status` is the status of the final response, or `None` when the
+    /// final attempt failed before a response arrived (timeout, connection).
+    #[error("Request failed after {attempts} attempts (last status: {}): {message}", last_status.map_or_else(|| "none".to_string(), |s| s.to_string()))]
+    RetriesExhausted {
+        attempts: u32,
+        last_status: Option<u16>,
+        message: String,
+    },
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 }
@@ -7,9 +7,12 @@ use serde::{de::DeserializeOwned, Serialize};
 use std::collections::HashMap;
 use std::time::Duration;
 
+use core_async::io::AsyncReadExt;
+
 use crate::{
     error::{BridgeError, Result},
     platform::{DynAsyncRead, PlatformSendSync},
+    time::Clock,
 };
 
 /// HTTP method types
@@ -31,6 +34,14 @@ pub struct HttpRequest {
     pub headers: HashMap<String, String>,
     pub body: Option<Bytes>,
     pub timeout: Option<Duration>,
+    /// Retries allowed after the first attempt, overriding the client's
+    /// [`RetryPolicy::max_attempts`]
+    pub max_retries: Option<u32>,
+    /// Length of the body passed to [`HttpClient::upload_stream`]
+    ///
+    /// Sent as `Content-Length` when known; `None` streams the body with
+    /// chunked transfer encoding.
+    pub content_length: Option<u64>,
 }
 
 impl HttpRequest {
@@ -41,6 +52,8 @@ impl HttpRequest {
             headers: HashMap::new(),
             body: None,
             timeout: None,
+            max_retries: None,
+            content_length: None,
         }
     }
 
@@ -72,6 +85,26 @@ impl HttpRequest {
         self.timeout = Some(duration);
         self
     }
+
+    /// Allow at most `retries` retries after the first attempt
+    ///
+    /// `max_retries(0)` sends the request exactly once.
+    pub fn max_retries(mut self, retries: u32) -> Self {
+        self.max_retries = Some(retries);
+        self
+    }
+
+    /// Declare the length of a streamed upload body
+    pub fn content_length(mut self, length: u64) -> Self {
+        self.content_length = Some(length);
+        self
+    }
+
+    /// Total attempts allowed for this request under `policy`
+    pub fn max_attempts(&self, policy: &RetryPolicy) -> u32 {
+        self.max_retries
+            .map_or(policy.max_attempts, |retries| retries.saturating_add(1))
+    }
 }
 
 /// HTTP response
@@ -110,6 +143,39 @@ impl HttpResponse {
     pub fn is_server_error(&self) -> bool {
         (500..600).contains(&self.status)
     }
+
+    /// Get a header value, matching the name case-insensitively
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Encoding of `body` according to the `Content-Encoding` header
+    ///
+    /// `None` means the body is not encoded: either the server sent it as-is
+    /// or the client already decompressed it.
+    pub fn content_encoding(&self) -> Option<&str> {
+        self.header("content-encoding")
+            .filter(|encoding| !encoding.eq_ignore_ascii_case("identity"))
+    }
+}
+
+/// How retry delays are randomised
+///
+/// Without jitter, clients that fail together retry together and collide
+/// again; both jitter modes spread retries out while keeping the delays
+/// bounded by [`RetryPolicy::max_delay`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JitterMode {
+    /// No randomisation: the delay is the exponential (or base) delay
+    None,
+    /// Uniform in `[0, min(max_delay, base_delay * 2^retry)]`
+    #[default]
+    Full,
+    /// Uniform in `[base_delay, previous_delay * 3]`, capped at `max_delay`
+    Decorrelated,
 }
 
 /// Retry policy configuration
@@ -123,6 +189,8 @@ pub struct RetryPolicy {
     pub max_delay: Duration,
     /// Whether to use exponential backoff
     pub use_exponential_backoff: bool,
+    /// How delays are randomised
+    pub jitter: JitterMode,
 }
 
 impl Default for RetryPolicy {
@@ -132,10 +200,80 @@ impl Default for RetryPolicy {
             base_delay: Duration::from_millis(100),
             max_delay: Duration::from_secs(30),
             use_exponential_backoff: true,
+            jitter: JitterMode::Full,
         }
     }
 }
 
+impl RetryPolicy {
+    /// Delay schedule for one request
+    ///
+    /// `seed` drives the jitter; the same seed always yields the same delays.
+    /// See [`backoff_from_clock`](Self::backoff_from_clock) to seed from a [`Clock`].
+    pub fn backoff(&self, seed: u64) -> Backoff {
+        Backoff {
+            policy: self.clone(),
+            retry: 0,
+            previous: self.base_delay,
+            rng_state: seed,
+        }
+    }
+
+    /// Delay schedule seeded from the current time of `clock`
+    pub fn backoff_from_clock(&self, clock: &dyn Clock) -> Backoff {
+        let seed = clock.now().timestamp_nanos_opt().unwrap_or_default();
+        self.backoff(seed as u64)
+    }
+}
+
+/// Sequence of retry delays produced by [`RetryPolicy::backoff`]
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    policy: RetryPolicy,
+    retry: u32,
+    previous: Duration,
+    rng_state: u64,
+}
+
+impl Backoff {
+    /// Delay to wait before the next retry
+    pub fn next_delay(&mut self) -> Duration {
+        let policy = &self.policy;
+        let ceiling = if policy.use_exponential_backoff {
+            policy
+                .base_delay
+                .saturating_mul(2u32.saturating_pow(self.retry))
+        } else {
+            policy.base_delay
+        }
+        .min(policy.max_delay);
+
+        let delay = match policy.jitter {
+            JitterMode::None => ceiling,
+            JitterMode::Full => ceiling.mul_f64(self.next_unit()),
+            JitterMode::Decorrelated => {
+                let upper = self.previous.saturating_mul(3).min(policy.max_delay);
+                let lower = policy.base_delay.min(upper);
+                lower + (upper - lower).mul_f64(self.next_unit())
+            }
+        };
+
+        self.retry = self.retry.saturating_add(1);
+        self.previous = delay;
+        delay
+    }
+
+    /// Next pseudo-random value in `[0, 1)` (SplitMix64)
+    fn next_unit(&mut self) -> f64 {
+        self.rng_state = self.rng_state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.rng_state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
 /// Async HTTP client trait
 ///
 /// This trait abstracts HTTP operations to allow platform-specific implementations.
@@ -189,6 +327,22 @@ pub trait HttpClient: PlatformSendSync {
     /// This is useful for large files that should not be loaded entirely into memory.
     async fn download_stream(&self, url: String) -> Result<Box<DynAsyncRead>>;
 
+    /// Send `request` with a body read from `body`, e.g. a `PUT` upload
+    ///
+    /// Any `request.body` is replaced by the contents of `body`. The default
+    /// implementation buffers the whole body and calls [`execute`](Self::execute);
+    /// implementations that can stream should override it.
+    async fn upload_stream(
+        &self,
+        mut request: HttpRequest,
+        mut body: Box<DynAsyncRead>,
+    ) -> Result<HttpResponse> {
+        let mut buffer = Vec::new();
+        body.read_to_end(&mut buffer).await?;
+        request.body = Some(Bytes::from(buffer));
+        self.execute(request).await
+    }
+
     /// Check network connectivity
     async fn is_connected(&self) -> bool {
         // Default implementation: try a simple HEAD request
@@ -196,6 +350,46 @@ pub trait HttpClient: PlatformSendSync {
     }
 }
 
+/// Hook for adding or modifying headers on outgoing provider requests
+///
+/// Connectors call the signer right before each HTTP request is sent,
+/// including retries, after their own headers (OAuth `Authorization`, `Accept`)
+/// have been set. This is where deployment-specific headers go, e.g. API
+/// gateway keys or organisation headers required by a corporate proxy.
+///
+/// Closures of the form `Fn(&mut HttpRequest) -> Result<()>` implement this
+/// trait.
+///
+/// # Example
+///
+/// ```ignore
+/// use bridge_traits::http::{HttpRequest, RequestSigner};
+/// use std::sync::Arc;
+///
+/// let signer: Arc<dyn RequestSigner> = Arc::new(|request: &mut HttpRequest| {
+///     request.headers.insert("X-Org-Id".to_string(), "acme".to_string());
+///     Ok(())
+/// });
+/// let connector = GoogleDriveConnector::new(http_client, token).with_request_signer(signer);
+/// ```
+pub trait RequestSigner: PlatformSendSync {
+    /// Add or modify headers on `request`
+    ///
+    /// # Errors
+    ///
+    /// Returning an error aborts the request and is reported to the caller.
+    fn sign(&self, request: &mut HttpRequest) -> Result<()>;
+}
+
+impl<F> RequestSigner for F
+where
+    F: Fn(&mut HttpRequest) -> Result<()> + PlatformSendSync,
+{
+    fn sign(&self, request: &mut HttpRequest) -> Result<()> {
+        self(request)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -212,6 +406,98 @@ mod tests {
         assert!(request.headers.contains_key("Authorization"));
     }
 
+    #[test]
+    fn test_max_retries_overrides_policy_attempts() {
+        let policy = RetryPolicy::default();
+        let request = HttpRequest::new(HttpMethod::Get, "https://example.com");
+        assert_eq!(request.max_attempts(&policy), policy.max_attempts);
+
+        assert_eq!(request.clone().max_retries(0).max_attempts(&policy), 1);
+        assert_eq!(request.max_retries(5).max_attempts(&policy), 6);
+    }
+
+    /// Responds with the request body it received
+    struct EchoClient;
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl HttpClient for EchoClient {
+        async fn execute(&self, request: HttpRequest) -> Result<HttpResponse> {
+            Ok(HttpResponse {
+                status: 201,
+                headers: HashMap::new(),
+                body: request.body.unwrap_or_default(),
+            })
+        }
+
+        async fn download_stream(&self, _url: String) -> Result<Box<DynAsyncRead>> {
+            Err(BridgeError::NotAvailable("download_stream".to_string()))
+        }
+    }
+
+    #[core_async::test]
+    async fn test_default_upload_stream_buffers_body() {
+        let request = HttpRequest::new(HttpMethod::Put, "https://example.com/playlist.m3u")
+            .body(Bytes::from_static(b"replaced"));
+        let body: &'static [u8] = b"#EXTM3U\nsong.flac\n";
+
+        let response = EchoClient
+            .upload_stream(request, Box::new(body))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status, 201);
+        assert_eq!(response.body.as_ref(), body);
+    }
+
+    #[test]
+    fn test_backoff_without_jitter_is_exponential_and_capped() {
+        let policy = RetryPolicy {
+            max_delay: Duration::from_millis(500),
+            jitter: JitterMode::None,
+            ..Default::default()
+        };
+        let mut backoff = policy.backoff(42);
+
+        let delays: Vec<_> = (0..5).map(|_| backoff.next_delay()).collect();
+        assert_eq!(delays, [100, 200, 400, 500, 500].map(Duration::from_millis));
+    }
+
+    #[test]
+    fn test_backoff_jitter_stays_within_bounds_and_varies() {
+        for jitter in [JitterMode::Full, JitterMode::Decorrelated] {
+            let policy = RetryPolicy {
+                max_attempts: 20,
+                max_delay: Duration::from_secs(2),
+                jitter,
+                ..Default::default()
+            };
+            let mut backoff = policy.backoff(7);
+
+            let delays: Vec<_> = (0..20).map(|_| backoff.next_delay()).collect();
+            for delay in &delays {
+                assert!(*delay <= policy.max_delay, "{:?}: {:?}", jitter, delay);
+            }
+            assert!(
+                delays.windows(2).any(|pair| pair[0] != pair[1]),
+                "{:?} produced identical delays",
+                jitter
+            );
+        }
+    }
+
+    #[test]
+    fn test_backoff_is_deterministic_per_seed() {
+        let policy = RetryPolicy::default();
+        let schedule = |seed| {
+            let mut backoff = policy.backoff(seed);
+            (0..5).map(|_| backoff.next_delay()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(schedule(1), schedule(1));
+        assert_ne!(schedule(1), schedule(2));
+    }
+
     #[test]
     fn test_http_response_status_checks() {
         let response = HttpResponse {
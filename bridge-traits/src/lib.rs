@@ -21,7 +21,7 @@
 //! ### Platform Integration
 //! - [`NetworkMonitor`](network::NetworkMonitor) - Connectivity and metered network detection
 //! - [`BackgroundExecutor`](background::BackgroundExecutor) - Task scheduling respecting platform constraints
-//! - [`LifecycleObserver`](background::LifecycleObserver) - App foreground/background transitions
+//! - [`LifecycleObserver`](background::LifecycleObserver) - App lifecycle, memory pressure and low-power mode
 //!
 //! ### Media & Playback
 //! - [`PlaybackAdapter`](playback::PlaybackAdapter) - Host audio engine integration (play, pause, seek, volume)
@@ -30,6 +30,7 @@
 //! ### Utilities
 //! - [`Clock`](time::Clock) - Time source for deterministic testing
 //! - [`LoggerSink`](time::LoggerSink) - Forward structured logs to host logging
+//! - `test_util` (feature `test-util`) - Record/replay HTTP clients, an in-memory secure store and a manual clock for tests
 //!
 //! ## Platform Requirements
 //!
@@ -112,22 +113,36 @@ pub mod network;
 pub mod platform;
 pub mod playback;
 pub mod storage;
+#[cfg(feature = "test-util")]
+pub mod test_util;
 pub mod time;
 
 pub use error::BridgeError;
 pub use platform::{DynAsyncRead, DynAsyncWrite, PlatformSend, PlatformSendSync};
 
 // Re-export commonly used types
-pub use background::{BackgroundExecutor, LifecycleObserver, LifecycleState, TaskConstraints};
+pub use background::{
+    BackgroundExecutor, LifecycleObserver, LifecycleState, PeriodicTask, ScheduleHandle,
+    SpawnedTask, SystemEvent, SystemEventStream, TaskConstraints, TaskHandle, TaskPriority,
+};
 pub use database::{
-    DatabaseAdapter, DatabaseConfig, DatabaseStatistics, QueryRow, QueryValue, TransactionId,
+    DatabaseAdapter, DatabaseConfig, DatabaseKey, DatabaseStatistics, FtsTokenizer, QueryRow,
+    QueryValue, TransactionId,
+};
+pub use http::{
+    Backoff, HttpClient, HttpMethod, HttpRequest, HttpResponse, JitterMode, RequestSigner,
+    RetryPolicy,
+};
+pub use network::{
+    network_transitions, NetworkInfo, NetworkInfoStream, NetworkMonitor, NetworkStatus, NetworkType,
 };
-pub use http::{HttpClient, HttpMethod, HttpRequest, HttpResponse};
-pub use network::{NetworkInfo, NetworkMonitor, NetworkStatus, NetworkType};
 pub use playback::{
     AudioCodec, AudioDecoder, AudioFormat, AudioFrameChunk, AudioSource, PlaybackAdapter,
     PlaybackMetadata, PlaybackOptions, PlaybackRequest, PlaybackResult, PlaybackSessionId,
     PlaybackState, ProbeResult,
 };
-pub use storage::{FileSystemAccess, RemoteFile, SecureStore, SettingsStore, StorageProvider};
+pub use storage::{
+    FileSystemAccess, NamespacedSecureStore, RemoteFile, SecureStore, SettingWatchStream,
+    SettingsStore, SettingsStoreExt, SettingsWatchers, StorageProvider,
+};
 pub use time::{Clock, LogEntry, LogLevel, LoggerSink, SystemClock};
@@ -2,6 +2,8 @@
 //!
 //! Provides network connectivity and status information.
 
+use futures::{future, stream, StreamExt};
+
 use crate::{
     error::Result,
     platform::{PlatformSend, PlatformSendSync},
@@ -32,7 +34,7 @@ pub enum NetworkStatus {
 }
 
 /// Network information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct NetworkInfo {
     pub status: NetworkStatus,
     pub network_type: Option<NetworkType>,
@@ -40,6 +42,22 @@ pub struct NetworkInfo {
     pub is_metered: bool,
     /// Whether the connection is considered expensive by the OS
     pub is_expensive: bool,
+    /// Whether traffic is intercepted by a captive portal (hotel or airport
+    /// login page), or `None` if it wasn't checked
+    pub is_captive_portal: Option<bool>,
+    /// Whether traffic goes through a VPN, or `None` if it can't be detected
+    pub is_vpn: Option<bool>,
+}
+
+impl NetworkInfo {
+    /// Whether the status, connection type, metering or captive portal state
+    /// differs from `previous`
+    pub fn is_transition_from(&self, previous: &NetworkInfo) -> bool {
+        self.status != previous.status
+            || self.network_type != previous.network_type
+            || self.is_metered != previous.is_metered
+            || self.is_captive_portal != previous.is_captive_portal
+    }
 }
 
 /// Network monitor trait
@@ -106,11 +124,62 @@ pub trait NetworkMonitor: PlatformSendSync {
         )
     }
 
+    /// Estimated downlink throughput in megabits per second
+    ///
+    /// Estimates are coarse (the Web's Network Information API rounds to
+    /// 25 kbps and caps at 10 Mbps) and only meant for scaling work down on
+    /// slow links. Returns `None` when the platform gives no estimate.
+    async fn estimated_downlink_mbps(&self) -> Result<Option<f64>> {
+        Ok(None)
+    }
+
     /// Subscribe to network status changes
     ///
     /// Returns a stream of network info updates. Implementations should
     /// emit an event whenever network status changes.
     async fn subscribe_changes(&self) -> Result<Box<dyn NetworkChangeStream>>;
+
+    /// Stream connectivity transitions
+    ///
+    /// Yields the current network info first, then a new item whenever the
+    /// status, connection type, metering or captive portal state changes (see
+    /// [`NetworkInfo::is_transition_from`]). Updates that change nothing else
+    /// are dropped.
+    ///
+    /// The default implementation filters [`subscribe_changes`](Self::subscribe_changes).
+    async fn subscribe(&self) -> Result<NetworkInfoStream> {
+        let current = self.get_network_info().await?;
+        let changes = self.subscribe_changes().await?;
+        Ok(network_transitions(current, changes))
+    }
+}
+
+/// Stream of network info returned by [`NetworkMonitor::subscribe`]
+#[cfg(not(target_arch = "wasm32"))]
+pub type NetworkInfoStream = stream::BoxStream<'static, NetworkInfo>;
+
+/// Stream of network info returned by [`NetworkMonitor::subscribe`]
+#[cfg(target_arch = "wasm32")]
+pub type NetworkInfoStream = stream::LocalBoxStream<'static, NetworkInfo>;
+
+/// Yield `current`, then every update from `changes` that is a transition
+pub fn network_transitions(
+    current: NetworkInfo,
+    changes: Box<dyn NetworkChangeStream>,
+) -> NetworkInfoStream {
+    let transitions = stream::unfold(
+        (changes, current.clone()),
+        |(mut changes, last)| async move {
+            while let Some(info) = changes.next().await {
+                if info.is_transition_from(&last) {
+                    return Some((info.clone(), (changes, info)));
+                }
+            }
+            None
+        },
+    );
+
+    Box::pin(stream::once(future::ready(current)).chain(transitions))
 }
 
 /// Stream of network status changes
@@ -134,10 +203,59 @@ mod tests {
             network_type: Some(NetworkType::WiFi),
             is_metered: false,
             is_expensive: false,
+            is_captive_portal: None,
+            is_vpn: None,
         };
 
         assert_eq!(info.status, NetworkStatus::Connected);
         assert_eq!(info.network_type, Some(NetworkType::WiFi));
         assert!(!info.is_metered);
     }
+
+    fn info(network_type: NetworkType, is_metered: bool) -> NetworkInfo {
+        NetworkInfo {
+            status: NetworkStatus::Connected,
+            network_type: Some(network_type),
+            is_metered,
+            is_expensive: false,
+            is_captive_portal: None,
+            is_vpn: None,
+        }
+    }
+
+    struct ScriptedChanges(std::vec::IntoIter<NetworkInfo>);
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl NetworkChangeStream for ScriptedChanges {
+        async fn next(&mut self) -> Option<NetworkInfo> {
+            self.0.next()
+        }
+    }
+
+    #[core_async::test]
+    async fn test_network_transitions_skip_repeated_updates() {
+        let wifi = info(NetworkType::WiFi, false);
+        let cellular = info(NetworkType::Cellular, true);
+        let expensive_wifi = NetworkInfo {
+            is_expensive: true,
+            ..wifi.clone()
+        };
+
+        let changes = ScriptedChanges(
+            vec![
+                wifi.clone(),
+                expensive_wifi,
+                cellular.clone(),
+                cellular.clone(),
+                wifi.clone(),
+            ]
+            .into_iter(),
+        );
+
+        let seen: Vec<_> = network_transitions(wifi.clone(), Box::new(changes))
+            .collect()
+            .await;
+        assert_eq!(seen, vec![wifi.clone(), cellular, wifi]);
+    }
 }
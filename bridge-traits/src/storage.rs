@@ -4,10 +4,16 @@
 //! and key-value settings storage.
 
 use bytes::Bytes;
+use core_async::sync::watch;
+use futures::{future, stream, StreamExt};
+use serde::{de::DeserializeOwned, Serialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
 
 use crate::{
-    error::Result,
+    error::{BridgeError, Result},
     platform::{DynAsyncRead, DynAsyncWrite, PlatformSend, PlatformSendSync},
 };
 
@@ -70,9 +76,42 @@ pub trait FileSystemAccess: PlatformSendSync {
     /// Write data to a file, creating it if it doesn't exist
     async fn write_file(&self, path: &Path, data: Bytes) -> Result<()>;
 
+    /// Write data to a file so that readers see either the old or the new
+    /// contents, never a partial write
+    ///
+    /// Implementations write to a temporary sibling and only replace `path`
+    /// once the data is fully stored. The default implementation falls back
+    /// to `write_file`, which gives no such guarantee.
+    async fn write_file_atomic(&self, path: &Path, data: Bytes) -> Result<()> {
+        self.write_file(path, data).await
+    }
+
     /// Append data to an existing file or create it
     async fn append_file(&self, path: &Path, data: Bytes) -> Result<()>;
 
+    /// Write data at a byte offset, creating the file if it doesn't exist
+    ///
+    /// Existing content outside the written region is preserved; writing past
+    /// the end extends the file, zero-filling any gap. The default
+    /// implementation rewrites the whole file, so platforms with positioned
+    /// I/O should override it.
+    async fn write_at(&self, path: &Path, offset: u64, data: Bytes) -> Result<()> {
+        let mut contents = if self.exists(path).await? {
+            self.read_file(path).await?.to_vec()
+        } else {
+            Vec::new()
+        };
+
+        let start = offset as usize;
+        let end = start + data.len();
+        if contents.len() < end {
+            contents.resize(end, 0);
+        }
+        contents[start..end].copy_from_slice(&data);
+
+        self.write_file(path, Bytes::from(contents)).await
+    }
+
     /// Delete a file
     async fn delete_file(&self, path: &Path) -> Result<()>;
 
@@ -90,6 +129,18 @@ pub trait FileSystemAccess: PlatformSendSync {
     /// Open a file for streaming writes
     async fn open_write_stream(&self, path: &Path) -> Result<Box<DynAsyncWrite>>;
 
+    /// Bytes available for writing on the volume containing `path`
+    ///
+    /// Used for pre-flight checks before large writes such as offline cache
+    /// downloads. Platforms without a capacity API return
+    /// `BridgeError::NotAvailable`, which callers should treat as "unknown"
+    /// rather than "full".
+    async fn available_space(&self, _path: &Path) -> Result<u64> {
+        Err(BridgeError::NotAvailable(
+            "available_space is not supported on this platform".to_string(),
+        ))
+    }
+
     /// Calculate total size of a directory recursively
     async fn directory_size(&self, path: &Path) -> Result<u64> {
         let mut total = 0u64;
@@ -176,6 +227,31 @@ pub trait SecureStore: PlatformSendSync {
         Ok(self.get_secret(key).await?.is_some())
     }
 
+    /// Store several secrets at once
+    ///
+    /// The default implementation calls `set_secret` for each entry in order
+    /// and stops at the first failure, so earlier entries stay written.
+    /// Stores backed by transactional storage should override this to apply
+    /// all entries or none.
+    async fn set_secrets(&self, entries: &[(&str, &[u8])]) -> Result<()> {
+        for (key, value) in entries {
+            self.set_secret(key, value).await?;
+        }
+        Ok(())
+    }
+
+    /// Retrieve several secrets at once
+    ///
+    /// Returns one entry per key, in the same order, with `None` for keys
+    /// that don't exist.
+    async fn get_secrets(&self, keys: &[&str]) -> Result<Vec<Option<Vec<u8>>>> {
+        let mut values = Vec::with_capacity(keys.len());
+        for key in keys {
+            values.push(self.get_secret(key).await?);
+        }
+        Ok(values)
+    }
+
     /// List all secret keys (without values)
     ///
     /// Useful for debugging or migration scenarios.
@@ -187,6 +263,122 @@ pub trait SecureStore: PlatformSendSync {
     async fn clear_all(&self) -> Result<()>;
 }
 
+/// Separator between a namespace and the key it scopes
+const NAMESPACE_SEPARATOR: char = '/';
+
+/// Secure store decorator that scopes every key to a namespace
+///
+/// Keys are stored in the wrapped store as `<namespace>/<key>`. `list_keys`
+/// only reports keys inside the namespace (with the prefix stripped) and
+/// `clear_all` only deletes those, so several profiles or providers can share
+/// one keyring without seeing or clearing each other's secrets.
+///
+/// Batch operations are forwarded to the wrapped store, so they are exactly
+/// as atomic as its own `set_secrets`.
+///
+/// # Example
+///
+/// ```ignore
+/// let google = NamespacedSecureStore::new(keyring.clone(), "profile-a");
+/// let onedrive = NamespacedSecureStore::new(keyring, "profile-b");
+///
+/// google.set_secret("token", b"...").await?;
+/// assert!(onedrive.get_secret("token").await?.is_none());
+/// ```
+pub struct NamespacedSecureStore {
+    inner: Arc<dyn SecureStore>,
+    prefix: String,
+}
+
+impl NamespacedSecureStore {
+    /// Wrap `inner`, scoping all keys to `namespace`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `namespace` is empty or contains `/`, either of which would
+    /// let one namespace read keys belonging to another.
+    pub fn new(inner: Arc<dyn SecureStore>, namespace: impl Into<String>) -> Self {
+        let namespace = namespace.into();
+        assert!(
+            !namespace.is_empty() && !namespace.contains(NAMESPACE_SEPARATOR),
+            "invalid secure store namespace: {:?}",
+            namespace
+        );
+
+        Self {
+            inner,
+            prefix: format!("{}{}", namespace, NAMESPACE_SEPARATOR),
+        }
+    }
+
+    /// Namespace all keys are scoped to
+    pub fn namespace(&self) -> &str {
+        self.prefix.trim_end_matches(NAMESPACE_SEPARATOR)
+    }
+
+    fn scoped_key(&self, key: &str) -> String {
+        format!("{}{}", self.prefix, key)
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl SecureStore for NamespacedSecureStore {
+    async fn set_secret(&self, key: &str, value: &[u8]) -> Result<()> {
+        self.inner.set_secret(&self.scoped_key(key), value).await
+    }
+
+    async fn get_secret(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        self.inner.get_secret(&self.scoped_key(key)).await
+    }
+
+    async fn delete_secret(&self, key: &str) -> Result<()> {
+        self.inner.delete_secret(&self.scoped_key(key)).await
+    }
+
+    async fn has_secret(&self, key: &str) -> Result<bool> {
+        self.inner.has_secret(&self.scoped_key(key)).await
+    }
+
+    async fn set_secrets(&self, entries: &[(&str, &[u8])]) -> Result<()> {
+        let keys: Vec<String> = entries
+            .iter()
+            .map(|(key, _)| self.scoped_key(key))
+            .collect();
+        let scoped: Vec<(&str, &[u8])> = keys
+            .iter()
+            .zip(entries)
+            .map(|(key, (_, value))| (key.as_str(), *value))
+            .collect();
+        self.inner.set_secrets(&scoped).await
+    }
+
+    async fn get_secrets(&self, keys: &[&str]) -> Result<Vec<Option<Vec<u8>>>> {
+        let scoped: Vec<String> = keys.iter().map(|key| self.scoped_key(key)).collect();
+        let scoped: Vec<&str> = scoped.iter().map(String::as_str).collect();
+        self.inner.get_secrets(&scoped).await
+    }
+
+    async fn list_keys(&self) -> Result<Vec<String>> {
+        Ok(self
+            .inner
+            .list_keys()
+            .await?
+            .into_iter()
+            .filter_map(|key| key.strip_prefix(&self.prefix).map(str::to_string))
+            .collect())
+    }
+
+    async fn clear_all(&self) -> Result<()> {
+        for key in self.inner.list_keys().await? {
+            if key.starts_with(&self.prefix) {
+                self.inner.delete_secret(&key).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Key-value settings storage trait
 ///
 /// Abstracts platform-specific preferences/settings storage:
@@ -213,8 +405,31 @@ pub trait SettingsStore: PlatformSendSync {
     async fn set_string(&self, key: &str, value: &str) -> Result<()>;
 
     /// Retrieve a string value
+    ///
+    /// Returns `None` for values stored with a TTL that has run out.
     async fn get_string(&self, key: &str) -> Result<Option<String>>;
 
+    /// Store a string value that expires after `ttl`
+    ///
+    /// Once expired, the key reads as missing. Expired entries are dropped
+    /// lazily; call [`purge_expired`](Self::purge_expired) to reclaim space.
+    /// Storing the key again without a TTL makes it permanent.
+    ///
+    /// The default implementation returns `NotAvailable`.
+    async fn set_string_with_ttl(&self, _key: &str, _value: &str, _ttl: Duration) -> Result<()> {
+        Err(BridgeError::NotAvailable(
+            "Settings expiry is not supported by this store".to_string(),
+        ))
+    }
+
+    /// Delete every expired entry, returning how many were removed
+    ///
+    /// The default implementation does nothing, which is correct for stores
+    /// without expiry support.
+    async fn purge_expired(&self) -> Result<usize> {
+        Ok(0)
+    }
+
     /// Store a boolean value
     async fn set_bool(&self, key: &str, value: bool) -> Result<()>;
 
@@ -250,15 +465,175 @@ pub trait SettingsStore: PlatformSendSync {
     /// Changes are committed when the transaction is dropped successfully,
     /// or rolled back if an error occurs.
     async fn begin_transaction(&self) -> Result<Box<dyn SettingsTransaction>>;
+
+    /// Watch a setting for changes
+    ///
+    /// The stream yields the current value (as stored, whatever its type)
+    /// first, then every value written through this store, with `None` once
+    /// the key is deleted. Only writes made through this process are
+    /// observed: changes from another process or another browser tab, and
+    /// values lapsing through a TTL, show up only when polled.
+    ///
+    /// The default implementation yields the current value and then stays
+    /// pending.
+    async fn watch(&self, key: &str) -> Result<SettingWatchStream> {
+        let current = self.get_string(key).await?;
+        Ok(Box::pin(
+            stream::once(future::ready(current)).chain(stream::pending()),
+        ))
+    }
 }
 
+/// Stream of values returned by [`SettingsStore::watch`]
+#[cfg(not(target_arch = "wasm32"))]
+pub type SettingWatchStream = stream::BoxStream<'static, Option<String>>;
+
+/// Stream of values returned by [`SettingsStore::watch`]
+#[cfg(target_arch = "wasm32")]
+pub type SettingWatchStream = stream::LocalBoxStream<'static, Option<String>>;
+
+/// Per-key channels backing [`SettingsStore::watch`]
+///
+/// A store keeps one of these, hands out streams from
+/// [`subscribe`](Self::subscribe) and calls [`notify`](Self::notify) after
+/// each successful write. Keys nobody watches cost nothing, and a key's
+/// channel is dropped on the first write after its last stream goes away.
+#[derive(Default)]
+pub struct SettingsWatchers {
+    senders: Mutex<HashMap<String, watch::Sender<Option<String>>>>,
+}
+
+impl SettingsWatchers {
+    /// Create an empty set of watchers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stream `key`, starting from its `current` value
+    pub fn subscribe(&self, key: &str, current: Option<String>) -> SettingWatchStream {
+        let mut senders = self.senders.lock().unwrap_or_else(PoisonError::into_inner);
+        let sender = senders
+            .entry(key.to_string())
+            .or_insert_with(|| watch::channel(current.clone()).0);
+
+        let mut receiver = sender.subscribe();
+        // Other watchers may hold a value that went stale without a write,
+        // e.g. through a TTL; bring them up to date as well
+        if *sender.borrow() != current {
+            let _ = sender.send(current.clone());
+        }
+        receiver.borrow_and_update();
+
+        let updates = stream::unfold(receiver, |mut receiver| async move {
+            receiver.changed().await.ok()?;
+            let value = receiver.borrow_and_update().clone();
+            Some((value, receiver))
+        });
+        Box::pin(stream::once(future::ready(current)).chain(updates))
+    }
+
+    /// Publish the new value of `key`, `None` if it was removed
+    pub fn notify(&self, key: &str, value: Option<&str>) {
+        let mut senders = self.senders.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(sender) = senders.get(key) {
+            if sender.send(value.map(str::to_string)).is_err() {
+                senders.remove(key);
+            }
+        }
+    }
+
+    /// Publish the removal of every watched key
+    pub fn notify_cleared(&self) {
+        let mut senders = self.senders.lock().unwrap_or_else(PoisonError::into_inner);
+        senders.retain(|_, sender| sender.send(None).is_ok());
+    }
+}
+
+/// Typed JSON helpers for [`SettingsStore`]
+///
+/// Kept out of `SettingsStore` so that trait stays object-safe; the blanket
+/// impl makes these available on every store, including
+/// `dyn SettingsStore`. Values are stored as JSON through `set_string`.
+///
+/// # Example
+///
+/// ```ignore
+/// use bridge_traits::storage::SettingsStoreExt;
+///
+/// store.set_json("equalizer", &EqualizerPreset { bass: 3, treble: -1 }).await?;
+/// let preset: Option<EqualizerPreset> = store.get_json("equalizer").await?;
+/// ```
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait SettingsStoreExt: SettingsStore {
+    /// Store `value` serialized as JSON
+    async fn set_json<T>(&self, key: &str, value: &T) -> Result<()>
+    where
+        T: Serialize + Sync + ?Sized,
+    {
+        let json = serde_json::to_string(value).map_err(|e| {
+            BridgeError::OperationFailed(format!("Failed to serialize setting {}: {}", key, e))
+        })?;
+        self.set_string(key, &json).await
+    }
+
+    /// Retrieve a value stored with [`set_json`](Self::set_json)
+    ///
+    /// Returns `BridgeError::Deserialization` if the stored string is not
+    /// valid JSON for `T`.
+    async fn get_json<T>(&self, key: &str) -> Result<Option<T>>
+    where
+        T: DeserializeOwned + Send,
+    {
+        match self.get_string(key).await? {
+            Some(json) => serde_json::from_str(&json)
+                .map(Some)
+                .map_err(|e| BridgeError::Deserialization(format!("Setting {}: {}", key, e))),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<S: SettingsStore + ?Sized> SettingsStoreExt for S {}
+
 /// Transaction for atomic settings updates
+///
+/// Nothing written through a transaction is visible until
+/// [`commit`](Self::commit); [`rollback`](Self::rollback) leaves every
+/// touched key as it was.
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
 pub trait SettingsTransaction: PlatformSend {
     /// Set a value within the transaction
     async fn set_string(&mut self, key: &str, value: &str) -> Result<()>;
 
+    /// Set a boolean value within the transaction
+    ///
+    /// The default implementation stores `"true"` or `"false"` through
+    /// `set_string`; stores that track value types should override it.
+    async fn set_bool(&mut self, key: &str, value: bool) -> Result<()> {
+        self.set_string(key, &value.to_string()).await
+    }
+
+    /// Set an integer value within the transaction
+    ///
+    /// The default implementation stores the decimal string through
+    /// `set_string`.
+    async fn set_i64(&mut self, key: &str, value: i64) -> Result<()> {
+        self.set_string(key, &value.to_string()).await
+    }
+
+    /// Set a floating-point value within the transaction
+    ///
+    /// The default implementation stores the decimal string through
+    /// `set_string`.
+    async fn set_f64(&mut self, key: &str, value: f64) -> Result<()> {
+        self.set_string(key, &value.to_string()).await
+    }
+
+    /// Delete a setting within the transaction
+    async fn delete(&mut self, key: &str) -> Result<()>;
+
     /// Commit the transaction
     async fn commit(self: Box<Self>) -> Result<()>;
 
@@ -403,6 +778,16 @@ pub trait StorageProvider: PlatformSendSync {
     /// ```
     async fn get_metadata(&self, file_id: &str) -> Result<RemoteFile>;
 
+    /// List the direct children of a folder, including non-media files
+    ///
+    /// Used to find companion files such as `cover.jpg` next to a track.
+    /// Providers without folder listing return `BridgeError::NotAvailable`.
+    async fn list_folder(&self, _folder_id: &str) -> Result<Vec<RemoteFile>> {
+        Err(BridgeError::NotAvailable(
+            "list_folder is not supported by this provider".to_string(),
+        ))
+    }
+
     /// Download file contents as a byte stream
     ///
     /// Returns a streaming reader for efficient downloading of large files.
@@ -438,6 +823,14 @@ pub trait StorageProvider: PlatformSendSync {
     /// ```
     async fn download(&self, file_id: &str, range: Option<&str>) -> Result<Bytes>;
 
+    /// Whether `download` honours byte ranges
+    ///
+    /// Callers only split a file into ranged requests when this returns `true`.
+    /// Defaults to `false`.
+    fn supports_range(&self) -> bool {
+        false
+    }
+
     /// Get incremental changes since a previous sync
     ///
     /// Enables efficient incremental synchronization by fetching only files that
@@ -527,10 +920,18 @@ impl FileSystemAccess for std::sync::Arc<dyn FileSystemAccess> {
         (**self).write_file(path, data).await
     }
 
+    async fn write_file_atomic(&self, path: &Path, data: Bytes) -> Result<()> {
+        (**self).write_file_atomic(path, data).await
+    }
+
     async fn append_file(&self, path: &Path, data: Bytes) -> Result<()> {
         (**self).append_file(path, data).await
     }
 
+    async fn write_at(&self, path: &Path, offset: u64, data: Bytes) -> Result<()> {
+        (**self).write_at(path, offset, data).await
+    }
+
     async fn delete_file(&self, path: &Path) -> Result<()> {
         (**self).delete_file(path).await
     }
@@ -550,6 +951,10 @@ impl FileSystemAccess for std::sync::Arc<dyn FileSystemAccess> {
     async fn open_write_stream(&self, path: &Path) -> Result<Box<DynAsyncWrite>> {
         (**self).open_write_stream(path).await
     }
+
+    async fn available_space(&self, path: &Path) -> Result<u64> {
+        (**self).available_space(path).await
+    }
 }
 
 #[cfg(test)]
@@ -592,4 +997,247 @@ mod tests {
         assert!(!file.is_folder);
         assert_eq!(file.size, Some(5242880));
     }
+
+    /// Store that stages a batch and commits it only if every entry is valid
+    #[derive(Default)]
+    struct TransactionalStore {
+        secrets: std::sync::Mutex<std::collections::HashMap<String, Vec<u8>>>,
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl SecureStore for TransactionalStore {
+        async fn set_secret(&self, key: &str, value: &[u8]) -> Result<()> {
+            self.set_secrets(&[(key, value)]).await
+        }
+
+        async fn get_secret(&self, key: &str) -> Result<Option<Vec<u8>>> {
+            Ok(self.secrets.lock().unwrap().get(key).cloned())
+        }
+
+        async fn delete_secret(&self, key: &str) -> Result<()> {
+            self.secrets.lock().unwrap().remove(key);
+            Ok(())
+        }
+
+        async fn list_keys(&self) -> Result<Vec<String>> {
+            Ok(self.secrets.lock().unwrap().keys().cloned().collect())
+        }
+
+        async fn clear_all(&self) -> Result<()> {
+            self.secrets.lock().unwrap().clear();
+            Ok(())
+        }
+
+        async fn set_secrets(&self, entries: &[(&str, &[u8])]) -> Result<()> {
+            let mut staged = self.secrets.lock().unwrap().clone();
+            for (key, value) in entries {
+                if value.is_empty() {
+                    return Err(BridgeError::OperationFailed(format!(
+                        "Empty secret for {}",
+                        key
+                    )));
+                }
+                staged.insert(key.to_string(), value.to_vec());
+            }
+            *self.secrets.lock().unwrap() = staged;
+            Ok(())
+        }
+    }
+
+    #[core_async::test]
+    async fn test_get_secrets_preserves_order() {
+        let store = TransactionalStore::default();
+        store.set_secret("b", b"2").await.unwrap();
+        store.set_secret("a", b"1").await.unwrap();
+
+        let values = store.get_secrets(&["a", "missing", "b"]).await.unwrap();
+        assert_eq!(values, vec![Some(b"1".to_vec()), None, Some(b"2".to_vec())]);
+    }
+
+    #[core_async::test]
+    async fn test_failed_batch_leaves_no_partial_state() {
+        let store = TransactionalStore::default();
+        store.set_secret("access", b"old-access").await.unwrap();
+
+        let result = store
+            .set_secrets(&[
+                ("access", b"new-access".as_slice()),
+                ("refresh", b"new-refresh".as_slice()),
+                ("expiry", b"".as_slice()),
+            ])
+            .await;
+
+        assert!(result.is_err());
+        let values = store
+            .get_secrets(&["access", "refresh", "expiry"])
+            .await
+            .unwrap();
+        assert_eq!(values, vec![Some(b"old-access".to_vec()), None, None]);
+    }
+
+    #[core_async::test]
+    async fn test_namespaces_are_isolated() {
+        let shared: Arc<dyn SecureStore> = Arc::new(TransactionalStore::default());
+        let first = NamespacedSecureStore::new(shared.clone(), "profile-a");
+        let second = NamespacedSecureStore::new(shared.clone(), "profile-b");
+
+        first.set_secret("token", b"a").await.unwrap();
+        second.set_secret("token", b"b").await.unwrap();
+        second.set_secret("extra", b"x").await.unwrap();
+
+        assert_eq!(
+            first.get_secret("token").await.unwrap(),
+            Some(b"a".to_vec())
+        );
+        assert_eq!(
+            second.get_secret("token").await.unwrap(),
+            Some(b"b".to_vec())
+        );
+        assert!(!first.has_secret("extra").await.unwrap());
+        assert_eq!(first.list_keys().await.unwrap(), vec!["token".to_string()]);
+
+        let mut keys = second.list_keys().await.unwrap();
+        keys.sort();
+        assert_eq!(keys, vec!["extra".to_string(), "token".to_string()]);
+    }
+
+    #[core_async::test]
+    async fn test_namespaced_clear_all_is_scoped() {
+        let shared: Arc<dyn SecureStore> = Arc::new(TransactionalStore::default());
+        shared.set_secret("global", b"g").await.unwrap();
+        let first = NamespacedSecureStore::new(shared.clone(), "profile-a");
+        let second = NamespacedSecureStore::new(shared.clone(), "profile-b");
+
+        first
+            .set_secrets(&[("access", b"1".as_slice()), ("refresh", b"2".as_slice())])
+            .await
+            .unwrap();
+        second.set_secret("access", b"3").await.unwrap();
+
+        first.clear_all().await.unwrap();
+
+        assert!(first.list_keys().await.unwrap().is_empty());
+        assert_eq!(
+            second.get_secret("access").await.unwrap(),
+            Some(b"3".to_vec())
+        );
+        assert_eq!(
+            shared.get_secret("global").await.unwrap(),
+            Some(b"g".to_vec())
+        );
+    }
+
+    /// Settings store keeping only strings, enough for the JSON helpers
+    #[derive(Default)]
+    struct StringSettingsStore {
+        values: std::sync::Mutex<std::collections::HashMap<String, String>>,
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl SettingsStore for StringSettingsStore {
+        async fn set_string(&self, key: &str, value: &str) -> Result<()> {
+            self.values
+                .lock()
+                .unwrap()
+                .insert(key.to_string(), value.to_string());
+            Ok(())
+        }
+
+        async fn get_string(&self, key: &str) -> Result<Option<String>> {
+            Ok(self.values.lock().unwrap().get(key).cloned())
+        }
+
+        async fn set_bool(&self, key: &str, value: bool) -> Result<()> {
+            self.set_string(key, &value.to_string()).await
+        }
+
+        async fn get_bool(&self, key: &str) -> Result<Option<bool>> {
+            Ok(self.get_string(key).await?.and_then(|v| v.parse().ok()))
+        }
+
+        async fn set_i64(&self, key: &str, value: i64) -> Result<()> {
+            self.set_string(key, &value.to_string()).await
+        }
+
+        async fn get_i64(&self, key: &str) -> Result<Option<i64>> {
+            Ok(self.get_string(key).await?.and_then(|v| v.parse().ok()))
+        }
+
+        async fn set_f64(&self, key: &str, value: f64) -> Result<()> {
+            self.set_string(key, &value.to_string()).await
+        }
+
+        async fn get_f64(&self, key: &str) -> Result<Option<f64>> {
+            Ok(self.get_string(key).await?.and_then(|v| v.parse().ok()))
+        }
+
+        async fn delete(&self, key: &str) -> Result<()> {
+            self.values.lock().unwrap().remove(key);
+            Ok(())
+        }
+
+        async fn has_key(&self, key: &str) -> Result<bool> {
+            Ok(self.values.lock().unwrap().contains_key(key))
+        }
+
+        async fn list_keys(&self) -> Result<Vec<String>> {
+            Ok(self.values.lock().unwrap().keys().cloned().collect())
+        }
+
+        async fn clear_all(&self) -> Result<()> {
+            self.values.lock().unwrap().clear();
+            Ok(())
+        }
+
+        async fn begin_transaction(&self) -> Result<Box<dyn SettingsTransaction>> {
+            Err(BridgeError::NotAvailable("transactions".to_string()))
+        }
+    }
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct EqualizerPreset {
+        name: String,
+        bands: Vec<i8>,
+        enabled: bool,
+    }
+
+    #[core_async::test]
+    async fn test_json_settings_round_trip() {
+        let store = StringSettingsStore::default();
+        let preset = EqualizerPreset {
+            name: "Bass Boost".to_string(),
+            bands: vec![6, 4, 0, -2],
+            enabled: true,
+        };
+
+        store.set_json("equalizer", &preset).await.unwrap();
+
+        let loaded: Option<EqualizerPreset> = store.get_json("equalizer").await.unwrap();
+        assert_eq!(loaded, Some(preset));
+        let missing: Option<EqualizerPreset> = store.get_json("missing").await.unwrap();
+        assert!(missing.is_none());
+    }
+
+    #[core_async::test]
+    async fn test_corrupt_json_setting_is_deserialization_error() {
+        let store: Arc<dyn SettingsStore> = Arc::new(StringSettingsStore::default());
+        store.set_string("equalizer", "{not json").await.unwrap();
+
+        let result = store.get_json::<EqualizerPreset>("equalizer").await;
+        assert!(matches!(result, Err(BridgeError::Deserialization(_))));
+
+        // Valid JSON of the wrong shape is rejected the same way
+        store.set_string("equalizer", "[1, 2, 3]").await.unwrap();
+        let result = store.get_json::<EqualizerPreset>("equalizer").await;
+        assert!(matches!(result, Err(BridgeError::Deserialization(_))));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid secure store namespace")]
+    fn test_namespace_rejects_separator() {
+        let shared: Arc<dyn SecureStore> = Arc::new(TransactionalStore::default());
+        let _ = NamespacedSecureStore::new(shared, "profile/a");
+    }
 }
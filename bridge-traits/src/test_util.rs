@@ -0,0 +1,1033 @@
+//! Test Utilities
+//!
+//! Helpers for testing code that talks to HTTP APIs without live
+//! credentials, or that needs a secure store or a controllable clock.
+//! Enabled by the `test-util` feature.
+//!
+//! - [`MockHttpClient`] serves canned responses registered by the test,
+//!   records every request and can simulate network errors and latency.
+//! - [`RecordingHttpClient`] wraps a real [`HttpClient`], forwards every
+//!   request and keeps the responses so they can be saved as a fixture file.
+//! - [`ReplayHttpClient`] serves those responses back by matching the request
+//!   method and URL, and fails requests that were never recorded.
+//! - [`InMemorySecureStore`] keeps secrets in a hashmap and can simulate
+//!   write failures.
+//! - [`ManualClock`] only moves when the test advances it, and can adjust
+//!   wall-clock time without moving monotonic time.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use bridge_traits::test_util::{MockHttpClient, MockResponse};
+//!
+//! let http = Arc::new(MockHttpClient::new());
+//! http.stub(HttpMethod::Get, "https://api.example.com/files", MockResponse::new(503))
+//!     .stub(HttpMethod::Get, "https://api.example.com/files", MockResponse::ok(r#"{"files": []}"#));
+//!
+//! let connector = GoogleDriveConnector::new(http.clone(), "token".to_string());
+//! connector.list_media(None).await?;
+//! assert_eq!(http.requests().len(), 2);
+//! ```
+//!
+//! Recording and replaying a real API:
+//!
+//! ```ignore
+//! use bridge_traits::test_util::{RecordingHttpClient, ReplayHttpClient};
+//!
+//! // Once, against the real API
+//! let recorder = Arc::new(RecordingHttpClient::new(real_client));
+//! let connector = GoogleDriveConnector::new(recorder.clone(), token);
+//! connector.list_media(None).await?;
+//! recorder.save("tests/fixtures/drive_list_media.json")?;
+//!
+//! // In tests
+//! let replay = ReplayHttpClient::from_file("tests/fixtures/drive_list_media.json")?;
+//! let connector = GoogleDriveConnector::new(Arc::new(replay), "token".to_string());
+//! ```
+
+use bytes::Bytes;
+use chrono::{DateTime, TimeZone, Utc};
+use core_async::time::Instant;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::{
+    error::{BridgeError, Result},
+    http::{HttpClient, HttpMethod, HttpRequest, HttpResponse},
+    platform::DynAsyncRead,
+    storage::SecureStore,
+    time::Clock,
+};
+
+/// Method name used to match recorded requests
+fn method_name(method: HttpMethod) -> &'static str {
+    match method {
+        HttpMethod::Get => "GET",
+        HttpMethod::Post => "POST",
+        HttpMethod::Put => "PUT",
+        HttpMethod::Patch => "PATCH",
+        HttpMethod::Delete => "DELETE",
+        HttpMethod::Head => "HEAD",
+    }
+}
+
+/// How a fixture body is stored in the fixture file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BodyEncoding {
+    /// Body is stored as-is (valid UTF-8)
+    #[default]
+    Utf8,
+    /// Body is stored as lowercase hex (binary content)
+    Hex,
+}
+
+/// One recorded request/response pair
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HttpFixture {
+    /// Request method (`GET`, `POST`, ...)
+    pub method: String,
+    /// Full request URL, including the query string
+    pub url: String,
+    /// Response status code
+    pub status: u16,
+    /// Response headers
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// Response body, encoded according to `body_encoding`
+    #[serde(default)]
+    pub body: String,
+    /// Encoding of `body`
+    #[serde(default)]
+    pub body_encoding: BodyEncoding,
+}
+
+impl HttpFixture {
+    /// Create a fixture for a response with a UTF-8 body
+    pub fn new(
+        method: HttpMethod,
+        url: impl Into<String>,
+        status: u16,
+        body: impl Into<String>,
+    ) -> Self {
+        Self {
+            method: method_name(method).to_string(),
+            url: url.into(),
+            status,
+            headers: HashMap::new(),
+            body: body.into(),
+            body_encoding: BodyEncoding::Utf8,
+        }
+    }
+
+    /// Build a fixture from a request and the response it received
+    pub fn from_exchange(request: &HttpRequest, response: &HttpResponse) -> Self {
+        let (body, body_encoding) = match std::str::from_utf8(&response.body) {
+            Ok(text) => (text.to_string(), BodyEncoding::Utf8),
+            Err(_) => (encode_hex(&response.body), BodyEncoding::Hex),
+        };
+
+        Self {
+            method: method_name(request.method).to_string(),
+            url: request.url.clone(),
+            status: response.status,
+            headers: response.headers.clone(),
+            body,
+            body_encoding,
+        }
+    }
+
+    /// Whether this fixture was recorded for `method` and `url`
+    pub fn matches(&self, method: HttpMethod, url: &str) -> bool {
+        self.method.eq_ignore_ascii_case(method_name(method)) && self.url == url
+    }
+
+    /// Decoded response body
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a hex-encoded body is malformed
+    pub fn body_bytes(&self) -> Result<Bytes> {
+        match self.body_encoding {
+            BodyEncoding::Utf8 => Ok(Bytes::from(self.body.clone())),
+            BodyEncoding::Hex => decode_hex(&self.body).map(Bytes::from),
+        }
+    }
+
+    /// Rebuild the recorded response
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a hex-encoded body is malformed
+    pub fn to_response(&self) -> Result<HttpResponse> {
+        Ok(HttpResponse {
+            status: self.status,
+            headers: self.headers.clone(),
+            body: self.body_bytes()?,
+        })
+    }
+}
+
+fn encode_hex(data: &[u8]) -> String {
+    data.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn decode_hex(text: &str) -> Result<Vec<u8>> {
+    let invalid = || BridgeError::OperationFailed("Invalid hex body in HTTP fixture".to_string());
+    if !text.len().is_multiple_of(2) {
+        return Err(invalid());
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| {
+            text.get(i..i + 2)
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .ok_or_else(invalid)
+        })
+        .collect()
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    // Fixtures stay consistent even if a test panicked while holding the lock
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Canned outcome served by [`MockHttpClient`]
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    outcome: MockOutcome,
+    delay: Option<Duration>,
+}
+
+#[derive(Debug, Clone)]
+enum MockOutcome {
+    Response {
+        status: u16,
+        headers: HashMap<String, String>,
+        body: Bytes,
+    },
+    NetworkError(String),
+}
+
+impl MockResponse {
+    /// Response with `status` and an empty body
+    pub fn new(status: u16) -> Self {
+        Self {
+            outcome: MockOutcome::Response {
+                status,
+                headers: HashMap::new(),
+                body: Bytes::new(),
+            },
+            delay: None,
+        }
+    }
+
+    /// `200 OK` response with `body`
+    pub fn ok(body: impl Into<Bytes>) -> Self {
+        Self::new(200).with_body(body)
+    }
+
+    /// Request fails before any response arrives, as if the network dropped
+    pub fn network_error(message: impl Into<String>) -> Self {
+        Self {
+            outcome: MockOutcome::NetworkError(message.into()),
+            delay: None,
+        }
+    }
+
+    /// Set the response body
+    pub fn with_body(mut self, body: impl Into<Bytes>) -> Self {
+        if let MockOutcome::Response { body: current, .. } = &mut self.outcome {
+            *current = body.into();
+        }
+        self
+    }
+
+    /// Add a response header
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        if let MockOutcome::Response { headers, .. } = &mut self.outcome {
+            headers.insert(name.into(), value.into());
+        }
+        self
+    }
+
+    /// Wait `delay` before answering
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
+    async fn serve(&self) -> Result<HttpResponse> {
+        if let Some(delay) = self.delay {
+            core_async::time::sleep(delay).await;
+        }
+
+        match &self.outcome {
+            MockOutcome::Response {
+                status,
+                headers,
+                body,
+            } => Ok(HttpResponse {
+                status: *status,
+                headers: headers.clone(),
+                body: body.clone(),
+            }),
+            MockOutcome::NetworkError(message) => Err(BridgeError::OperationFailed(format!(
+                "Network error: {}",
+                message
+            ))),
+        }
+    }
+}
+
+/// Responses registered for one method and URL
+struct MockStub {
+    method: HttpMethod,
+    url: String,
+    responses: Vec<MockResponse>,
+    served: usize,
+}
+
+/// Configurable in-memory [`HttpClient`] for tests
+///
+/// Responses are registered per method and URL with [`stub`](Self::stub).
+/// Registering several responses for the same request serves them in order
+/// and repeats the last one once the others are used up, which makes retry
+/// paths easy to exercise. Every request is recorded and available from
+/// [`requests`](Self::requests).
+///
+/// # Panics
+///
+/// A request without a registered response panics, naming the request and
+/// the registered stubs, so a missing stub fails the test loudly instead of
+/// surfacing as an unrelated error.
+#[derive(Default)]
+pub struct MockHttpClient {
+    stubs: Mutex<Vec<MockStub>>,
+    requests: Mutex<Vec<HttpRequest>>,
+}
+
+impl MockHttpClient {
+    /// Client with no registered responses
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `response` for requests with `method` and `url`
+    pub fn stub(
+        &self,
+        method: HttpMethod,
+        url: impl Into<String>,
+        response: MockResponse,
+    ) -> &Self {
+        let url = url.into();
+        let mut stubs = lock(&self.stubs);
+        match stubs
+            .iter_mut()
+            .find(|stub| stub.method == method && stub.url == url)
+        {
+            Some(stub) => stub.responses.push(response),
+            None => stubs.push(MockStub {
+                method,
+                url,
+                responses: vec![response],
+                served: 0,
+            }),
+        }
+        self
+    }
+
+    /// Requests received so far, in order
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        lock(&self.requests).clone()
+    }
+
+    /// Take the next response for a request, panicking if none is registered
+    fn next_response(&self, method: HttpMethod, url: &str) -> MockResponse {
+        let mut stubs = lock(&self.stubs);
+        let registered: Vec<String> = stubs
+            .iter()
+            .map(|stub| format!("{} {}", method_name(stub.method), stub.url))
+            .collect();
+
+        let Some(stub) = stubs
+            .iter_mut()
+            .find(|stub| stub.method == method && stub.url == url)
+        else {
+            panic!(
+                "MockHttpClient: no stub registered for {} {} (registered: [{}])",
+                method_name(method),
+                url,
+                registered.join(", ")
+            );
+        };
+
+        let index = stub.served.min(stub.responses.len() - 1);
+        stub.served += 1;
+        stub.responses[index].clone()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl HttpClient for MockHttpClient {
+    async fn execute(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let response = self.next_response(request.method, &request.url);
+        lock(&self.requests).push(request);
+        response.serve().await
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn download_stream(&self, url: String) -> Result<Box<DynAsyncRead>> {
+        let response = self.next_response(HttpMethod::Get, &url);
+        lock(&self.requests).push(HttpRequest::new(HttpMethod::Get, url));
+        let response = response.serve().await?;
+        Ok(Box::new(std::io::Cursor::new(response.body.to_vec())))
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    async fn download_stream(&self, _url: String) -> Result<Box<DynAsyncRead>> {
+        Err(BridgeError::NotAvailable(
+            "Streaming mock responses is not supported on WASM".to_string(),
+        ))
+    }
+}
+
+/// HTTP client that forwards to another client and records every exchange
+///
+/// Only [`execute`](HttpClient::execute) calls are recorded; streamed
+/// downloads are forwarded without recording.
+pub struct RecordingHttpClient {
+    inner: Arc<dyn HttpClient>,
+    fixtures: Mutex<Vec<HttpFixture>>,
+}
+
+impl RecordingHttpClient {
+    /// Record requests sent through `inner`
+    pub fn new(inner: Arc<dyn HttpClient>) -> Self {
+        Self {
+            inner,
+            fixtures: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Exchanges recorded so far, in request order
+    pub fn fixtures(&self) -> Vec<HttpFixture> {
+        lock(&self.fixtures).clone()
+    }
+
+    /// Recorded exchanges as pretty-printed JSON
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(&self.fixtures()).map_err(|e| {
+            BridgeError::OperationFailed(format!("Failed to serialize HTTP fixtures: {}", e))
+        })
+    }
+
+    /// Write the recorded exchanges to a fixture file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization or writing the file fails
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl HttpClient for RecordingHttpClient {
+    async fn execute(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let recorded_request = request.clone();
+        let response = self.inner.execute(request).await?;
+        lock(&self.fixtures).push(HttpFixture::from_exchange(&recorded_request, &response));
+        Ok(response)
+    }
+
+    async fn download_stream(&self, url: String) -> Result<Box<DynAsyncRead>> {
+        self.inner.download_stream(url).await
+    }
+}
+
+/// HTTP client that serves recorded fixtures
+///
+/// Requests are matched on method and URL. When several fixtures match, they
+/// are served in recorded order and the last one is repeated once the others
+/// are used up, so recorded pagination and retries replay faithfully.
+/// Requests without a matching fixture fail with
+/// [`BridgeError::OperationFailed`].
+pub struct ReplayHttpClient {
+    fixtures: Vec<HttpFixture>,
+    served: Mutex<Vec<bool>>,
+}
+
+impl ReplayHttpClient {
+    /// Serve the given fixtures
+    pub fn new(fixtures: Vec<HttpFixture>) -> Self {
+        let served = Mutex::new(vec![false; fixtures.len()]);
+        Self { fixtures, served }
+    }
+
+    /// Load fixtures from JSON produced by [`RecordingHttpClient::to_json`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is not a list of fixtures
+    pub fn from_json(json: &str) -> Result<Self> {
+        let fixtures = serde_json::from_str(json).map_err(|e| {
+            BridgeError::OperationFailed(format!("Failed to parse HTTP fixtures: {}", e))
+        })?;
+        Ok(Self::new(fixtures))
+    }
+
+    /// Load fixtures from a file written by [`RecordingHttpClient::save`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or parsed
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// Find the fixture to serve for a request and mark it as used
+    fn next_fixture(&self, method: HttpMethod, url: &str) -> Result<&HttpFixture> {
+        let mut served = lock(&self.served);
+        let matching: Vec<usize> = self
+            .fixtures
+            .iter()
+            .enumerate()
+            .filter(|(_, fixture)| fixture.matches(method, url))
+            .map(|(index, _)| index)
+            .collect();
+
+        let index = matching
+            .iter()
+            .copied()
+            .find(|&index| !served[index])
+            .or_else(|| matching.last().copied())
+            .ok_or_else(|| {
+                BridgeError::OperationFailed(format!(
+                    "No recorded response for {} {}",
+                    method_name(method),
+                    url
+                ))
+            })?;
+
+        served[index] = true;
+        Ok(&self.fixtures[index])
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl HttpClient for ReplayHttpClient {
+    async fn execute(&self, request: HttpRequest) -> Result<HttpResponse> {
+        self.next_fixture(request.method, &request.url)?
+            .to_response()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn download_stream(&self, url: String) -> Result<Box<DynAsyncRead>> {
+        let body = self.next_fixture(HttpMethod::Get, &url)?.body_bytes()?;
+        Ok(Box::new(std::io::Cursor::new(body.to_vec())))
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    async fn download_stream(&self, _url: String) -> Result<Box<DynAsyncRead>> {
+        Err(BridgeError::NotAvailable(
+            "Streaming replay is not supported on WASM".to_string(),
+        ))
+    }
+}
+
+/// Secure store that keeps secrets in memory
+///
+/// Implements the whole [`SecureStore`] trait, so tests don't need to
+/// hand-roll a hashmap store. Nothing is persisted, which also makes it a fit
+/// for guest sessions where credentials must not outlive the process.
+///
+/// [`fail_next_set`](Self::fail_next_set) makes the next write fail, to
+/// exercise error paths. `set_secrets` applies all entries or none.
+#[derive(Default)]
+pub struct InMemorySecureStore {
+    secrets: Mutex<HashMap<String, Vec<u8>>>,
+    fail_next_set: AtomicBool,
+}
+
+impl InMemorySecureStore {
+    /// Empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Make the next `set_secret` or `set_secrets` call fail
+    ///
+    /// The failed write leaves the store unchanged; later writes succeed.
+    pub fn fail_next_set(&self) {
+        self.fail_next_set.store(true, Ordering::SeqCst);
+    }
+
+    fn check_injected_failure(&self) -> Result<()> {
+        if self.fail_next_set.swap(false, Ordering::SeqCst) {
+            return Err(BridgeError::OperationFailed(
+                "Injected secure store failure".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl SecureStore for InMemorySecureStore {
+    async fn set_secret(&self, key: &str, value: &[u8]) -> Result<()> {
+        self.check_injected_failure()?;
+        lock(&self.secrets).insert(key.to_string(), value.to_vec());
+        Ok(())
+    }
+
+    async fn get_secret(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        Ok(lock(&self.secrets).get(key).cloned())
+    }
+
+    async fn delete_secret(&self, key: &str) -> Result<()> {
+        lock(&self.secrets).remove(key);
+        Ok(())
+    }
+
+    async fn set_secrets(&self, entries: &[(&str, &[u8])]) -> Result<()> {
+        self.check_injected_failure()?;
+        let mut secrets = lock(&self.secrets);
+        for (key, value) in entries {
+            secrets.insert(key.to_string(), value.to_vec());
+        }
+        Ok(())
+    }
+
+    async fn list_keys(&self) -> Result<Vec<String>> {
+        Ok(lock(&self.secrets).keys().cloned().collect())
+    }
+
+    async fn clear_all(&self) -> Result<()> {
+        lock(&self.secrets).clear();
+        Ok(())
+    }
+}
+
+/// Clock that only moves when the test advances it
+///
+/// [`advance`](Self::advance) moves wall-clock and monotonic time together.
+/// [`set_wall`](Self::set_wall) jumps wall-clock time in either direction,
+/// like an NTP correction, and leaves monotonic time alone.
+pub struct ManualClock {
+    wall: Mutex<DateTime<Utc>>,
+    origin: Instant,
+    elapsed: Mutex<Duration>,
+}
+
+impl ManualClock {
+    /// Clock starting at `start`
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self {
+            wall: Mutex::new(start),
+            origin: Instant::now(),
+            elapsed: Mutex::new(Duration::ZERO),
+        }
+    }
+
+    /// Move wall-clock and monotonic time forward by `by`
+    pub fn advance(&self, by: Duration) {
+        *lock(&self.wall) += chrono::Duration::from_std(by).expect("advance out of range");
+        *lock(&self.elapsed) += by;
+    }
+
+    /// Set wall-clock time without touching monotonic time
+    pub fn set_wall(&self, now: DateTime<Utc>) {
+        *lock(&self.wall) = now;
+    }
+}
+
+impl Default for ManualClock {
+    /// Clock starting at 2024-01-01 00:00:00 UTC
+    fn default() -> Self {
+        Self::new(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap())
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime<Utc> {
+        *lock(&self.wall)
+    }
+
+    fn now_monotonic(&self) -> Instant {
+        self.origin + *lock(&self.elapsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockall::mock;
+
+    mock! {
+        Inner {}
+
+        #[async_trait::async_trait]
+        impl HttpClient for Inner {
+            async fn execute(&self, request: HttpRequest) -> Result<HttpResponse>;
+            async fn download_stream(&self, url: String) -> Result<Box<DynAsyncRead>>;
+        }
+    }
+
+    fn get(url: &str) -> HttpRequest {
+        HttpRequest::new(HttpMethod::Get, url)
+    }
+
+    #[core_async::test]
+    async fn test_mock_matches_method_and_url() {
+        let client = MockHttpClient::new();
+        client
+            .stub(
+                HttpMethod::Get,
+                "https://api.example.com/a",
+                MockResponse::ok("listing").with_header("etag", "v1"),
+            )
+            .stub(
+                HttpMethod::Put,
+                "https://api.example.com/a",
+                MockResponse::new(201),
+            );
+
+        let response = client
+            .execute(get("https://api.example.com/a"))
+            .await
+            .unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(&response.body[..], b"listing");
+        assert_eq!(response.header("ETag"), Some("v1"));
+
+        let upload = HttpRequest::new(HttpMethod::Put, "https://api.example.com/a")
+            .body(Bytes::from_static(b"#EXTM3U"));
+        let response = client.execute(upload).await.unwrap();
+        assert_eq!(response.status, 201);
+
+        let requests = client.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].method, HttpMethod::Get);
+        assert_eq!(requests[1].body.as_deref(), Some(&b"#EXTM3U"[..]));
+    }
+
+    #[core_async::test]
+    async fn test_mock_serves_sequential_responses_and_errors() {
+        let url = "https://api.example.com/flaky";
+        let client = MockHttpClient::new();
+        client
+            .stub(HttpMethod::Get, url, MockResponse::network_error("reset"))
+            .stub(HttpMethod::Get, url, MockResponse::new(503))
+            .stub(HttpMethod::Get, url, MockResponse::ok("done"));
+
+        let error = client.execute(get(url)).await.unwrap_err();
+        assert!(error.to_string().contains("reset"), "{}", error);
+        assert_eq!(client.execute(get(url)).await.unwrap().status, 503);
+        assert_eq!(client.execute(get(url)).await.unwrap().status, 200);
+        // The last response repeats
+        assert_eq!(client.execute(get(url)).await.unwrap().status, 200);
+        assert_eq!(client.requests().len(), 4);
+    }
+
+    #[core_async::test]
+    async fn test_mock_delays_response() {
+        let client = MockHttpClient::new();
+        client.stub(
+            HttpMethod::Get,
+            "https://api.example.com/slow",
+            MockResponse::new(204).with_delay(Duration::from_millis(50)),
+        );
+
+        let started = core_async::time::Instant::now();
+        let response = client
+            .execute(get("https://api.example.com/slow"))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status, 204);
+        assert!(started.elapsed() >= Duration::from_millis(50));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[core_async::test]
+    async fn test_mock_download_stream_serves_body() {
+        use core_async::io::AsyncReadExt;
+
+        let client = MockHttpClient::new();
+        client.stub(
+            HttpMethod::Get,
+            "https://api.example.com/song.flac",
+            MockResponse::ok(&b"fLaC"[..]),
+        );
+
+        let mut reader = client
+            .download_stream("https://api.example.com/song.flac".to_string())
+            .await
+            .unwrap();
+        let mut body = Vec::new();
+        reader.read_to_end(&mut body).await.unwrap();
+
+        assert_eq!(body, b"fLaC");
+        assert_eq!(client.requests().len(), 1);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "MockHttpClient: no stub registered for DELETE https://api.example.com/a \
+                    (registered: [GET https://api.example.com/a])"
+    )]
+    fn test_mock_panics_without_matching_stub() {
+        let client = MockHttpClient::new();
+        client.stub(
+            HttpMethod::Get,
+            "https://api.example.com/a",
+            MockResponse::new(200),
+        );
+
+        client.next_response(HttpMethod::Delete, "https://api.example.com/a");
+    }
+
+    #[core_async::test]
+    async fn test_replay_returns_recorded_response() {
+        let client = ReplayHttpClient::new(vec![
+            HttpFixture::new(HttpMethod::Get, "https://api.example.com/a", 200, "first"),
+            HttpFixture::new(
+                HttpMethod::Post,
+                "https://api.example.com/a",
+                201,
+                "created",
+            ),
+        ]);
+
+        let response = client
+            .execute(get("https://api.example.com/a"))
+            .await
+            .unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(&response.body[..], b"first");
+
+        let response = client
+            .execute(HttpRequest::new(
+                HttpMethod::Post,
+                "https://api.example.com/a",
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status, 201);
+        assert_eq!(&response.body[..], b"created");
+    }
+
+    #[core_async::test]
+    async fn test_replay_errors_on_unmatched_request() {
+        let client = ReplayHttpClient::new(vec![HttpFixture::new(
+            HttpMethod::Get,
+            "https://api.example.com/a",
+            200,
+            "",
+        )]);
+
+        let result = client.execute(get("https://api.example.com/b")).await;
+        assert!(matches!(result, Err(BridgeError::OperationFailed(_))));
+
+        let result = client
+            .execute(HttpRequest::new(
+                HttpMethod::Delete,
+                "https://api.example.com/a",
+            ))
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[core_async::test]
+    async fn test_replay_serves_repeated_requests_in_order() {
+        let client = ReplayHttpClient::new(vec![
+            HttpFixture::new(HttpMethod::Get, "https://api.example.com/a", 503, ""),
+            HttpFixture::new(HttpMethod::Get, "https://api.example.com/a", 200, "ok"),
+        ]);
+
+        let statuses = [
+            client
+                .execute(get("https://api.example.com/a"))
+                .await
+                .unwrap()
+                .status,
+            client
+                .execute(get("https://api.example.com/a"))
+                .await
+                .unwrap()
+                .status,
+            client
+                .execute(get("https://api.example.com/a"))
+                .await
+                .unwrap()
+                .status,
+        ];
+        assert_eq!(statuses, [503, 200, 200]);
+    }
+
+    #[core_async::test]
+    async fn test_recording_round_trips_through_json() {
+        let mut inner = MockInner::new();
+        inner.expect_execute().returning(|request| {
+            let body = if request.url.ends_with("binary") {
+                Bytes::from_static(&[0xff, 0x00, 0x10])
+            } else {
+                Bytes::from_static(br#"{"files": []}"#)
+            };
+            Ok(HttpResponse {
+                status: 200,
+                headers: HashMap::from([("content-type".to_string(), "x".to_string())]),
+                body,
+            })
+        });
+
+        let recorder = RecordingHttpClient::new(Arc::new(inner));
+        recorder
+            .execute(get("https://api.example.com/list"))
+            .await
+            .unwrap();
+        recorder
+            .execute(get("https://api.example.com/binary"))
+            .await
+            .unwrap();
+
+        let fixtures = recorder.fixtures();
+        assert_eq!(fixtures.len(), 2);
+        assert_eq!(fixtures[1].body_encoding, BodyEncoding::Hex);
+
+        let replay = ReplayHttpClient::from_json(&recorder.to_json().unwrap()).unwrap();
+        let response = replay
+            .execute(get("https://api.example.com/binary"))
+            .await
+            .unwrap();
+        assert_eq!(&response.body[..], &[0xff, 0x00, 0x10]);
+        assert_eq!(response.headers.get("content-type"), Some(&"x".to_string()));
+
+        let response = replay
+            .execute(get("https://api.example.com/list"))
+            .await
+            .unwrap();
+        assert_eq!(&response.body[..], br#"{"files": []}"#);
+    }
+
+    #[core_async::test]
+    async fn test_in_memory_store_set_get_delete() {
+        let store = InMemorySecureStore::new();
+
+        store.set_secret("token", b"secret").await.unwrap();
+        assert_eq!(
+            store.get_secret("token").await.unwrap(),
+            Some(b"secret".to_vec())
+        );
+        assert!(store.has_secret("token").await.unwrap());
+
+        store.delete_secret("token").await.unwrap();
+        assert_eq!(store.get_secret("token").await.unwrap(), None);
+        // Deleting a missing key is not an error
+        store.delete_secret("token").await.unwrap();
+    }
+
+    #[core_async::test]
+    async fn test_in_memory_store_list_and_clear() {
+        let store = InMemorySecureStore::new();
+        store
+            .set_secrets(&[("a", b"1".as_slice()), ("b", b"2".as_slice())])
+            .await
+            .unwrap();
+
+        let mut keys = store.list_keys().await.unwrap();
+        keys.sort();
+        assert_eq!(keys, vec!["a", "b"]);
+
+        store.clear_all().await.unwrap();
+        assert!(store.list_keys().await.unwrap().is_empty());
+    }
+
+    #[core_async::test]
+    async fn test_in_memory_store_injected_failure() {
+        let store = InMemorySecureStore::new();
+        store.set_secret("kept", b"old").await.unwrap();
+
+        store.fail_next_set();
+        assert!(store.set_secret("kept", b"new").await.is_err());
+        assert_eq!(
+            store.get_secret("kept").await.unwrap(),
+            Some(b"old".to_vec())
+        );
+
+        // Batches fail as a whole
+        store.fail_next_set();
+        assert!(store
+            .set_secrets(&[("kept", b"new".as_slice()), ("other", b"x".as_slice())])
+            .await
+            .is_err());
+        assert_eq!(store.list_keys().await.unwrap(), vec!["kept"]);
+
+        // Only the next write fails
+        store.set_secret("kept", b"new").await.unwrap();
+        assert_eq!(
+            store.get_secret("kept").await.unwrap(),
+            Some(b"new".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_in_memory_store_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<InMemorySecureStore>();
+    }
+
+    #[test]
+    fn test_manual_clock_advances_wall_and_monotonic_time() {
+        let clock = ManualClock::default();
+        let wall = clock.now();
+        let start = clock.now_monotonic();
+
+        assert_eq!(clock.now_monotonic(), start);
+        clock.advance(Duration::from_micros(250));
+        assert_eq!(clock.now_monotonic() - start, Duration::from_micros(250));
+        assert_eq!(clock.now() - wall, chrono::Duration::microseconds(250));
+    }
+
+    #[test]
+    fn test_monotonic_time_ignores_wall_clock_adjustments() {
+        let clock = ManualClock::default();
+        let mut last = clock.now_monotonic();
+
+        // NTP steps the wall clock back an hour, then forward a day
+        let wall = clock.now();
+        for adjusted in [
+            wall - chrono::Duration::hours(1),
+            wall + chrono::Duration::days(1),
+        ] {
+            clock.set_wall(adjusted);
+            assert_eq!(clock.now(), adjusted);
+            assert_eq!(clock.now_monotonic(), last);
+
+            clock.advance(Duration::from_millis(10));
+            let now = clock.now_monotonic();
+            assert!(now > last);
+            last = now;
+        }
+        assert_eq!(
+            clock.now(),
+            wall + chrono::Duration::days(1) + chrono::Duration::milliseconds(10)
+        );
+    }
+}
@@ -3,6 +3,7 @@
 //! Provides injectable time source and logging sink for testing and platform integration.
 
 use chrono::{DateTime, Utc};
+use core_async::time::Instant;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -13,6 +14,9 @@ use crate::{error::Result, platform::PlatformSendSync};
 /// Abstracts system time to enable deterministic testing and support
 /// for host-specified timezones.
 ///
+/// `now` is wall-clock time and can jump when the system clock is adjusted
+/// (e.g. by NTP). Use `now_monotonic` to schedule or measure intervals.
+///
 /// # Example
 ///
 /// ```ignore
@@ -36,6 +40,15 @@ pub trait Clock: PlatformSendSync {
     fn unix_timestamp_millis(&self) -> i64 {
         self.now().timestamp_millis()
     }
+
+    /// Get a monotonic, high-resolution timestamp
+    ///
+    /// Never goes backwards and is unaffected by wall-clock adjustments, so
+    /// it suits playback scheduling and benchmarks. Only differences between
+    /// two readings are meaningful.
+    fn now_monotonic(&self) -> Instant {
+        Instant::now()
+    }
 }
 
 /// System clock implementation using actual system time
@@ -202,6 +215,9 @@ mod tests {
 
         assert!(timestamp > 0);
         assert!(now.timestamp() == timestamp);
+
+        let earlier = clock.now_monotonic();
+        assert!(clock.now_monotonic() >= earlier);
     }
 
     #[test]
@@ -0,0 +1,66 @@
+//! Cancellation of long-running operations started from JavaScript
+//!
+//! Binding methods that can run for a long time (searches, syncs, enrichment)
+//! accept an optional `AbortSignal`. The signal is mapped onto a
+//! `core_async::sync::CancellationToken`, so Rust code observes cancellation the
+//! same way it does natively:
+//!
+//! ```javascript
+//! const controller = new AbortController();
+//! const pending = query.search('beatles', controller.signal);
+//! controller.abort();
+//! await pending; // rejects with { kind: 'Cancelled' }
+//! ```
+
+use crate::js_error::{JsError, JsErrorKind};
+use core_async::sync::CancellationToken;
+use futures::future::{select, Either};
+use std::future::Future;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::AbortSignal;
+
+/// Create a cancellation token that fires when `signal` aborts
+///
+/// The token is already cancelled if the signal was aborted before the call.
+pub fn token_from_abort_signal(signal: &AbortSignal) -> CancellationToken {
+    let token = CancellationToken::new();
+    if signal.aborted() {
+        token.cancel();
+        return token;
+    }
+
+    let on_abort_token = token.clone();
+    let on_abort = Closure::once_into_js(move || on_abort_token.cancel());
+    let _ = signal.add_event_listener_with_callback("abort", on_abort.unchecked_ref());
+    token
+}
+
+/// Error value used to reject operations that were cancelled
+pub fn cancelled_error() -> JsValue {
+    JsError::new(JsErrorKind::Cancelled, "Operation cancelled").into()
+}
+
+/// Run `future` until it completes or `token` is cancelled
+///
+/// On cancellation the future is dropped, so any work it has not started yet
+/// never runs, and the call resolves to a `Cancelled` error.
+pub async fn run_cancellable<F, T>(token: Option<CancellationToken>, future: F) -> Result<T, JsValue>
+where
+    F: Future<Output = Result<T, JsValue>>,
+{
+    let Some(token) = token else {
+        return future.await;
+    };
+    if token.is_cancelled() {
+        return Err(cancelled_error());
+    }
+
+    let future = Box::pin(future);
+    let cancelled = Box::pin(token.cancelled());
+    let outcome = select(future, cancelled).await;
+    match outcome {
+        Either::Left((result, _)) => result,
+        Either::Right(((), _)) => Err(cancelled_error()),
+    }
+}
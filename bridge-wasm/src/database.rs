@@ -11,7 +11,8 @@
 //! The JavaScript environment must expose a global `bridgeWasmDb` namespace
 //! with the following async functions (returning `Promise`):
 //!
-//! - `init(config) -> handle`
+//! - `init(config) -> handle` (`config.fts_tokenize` is the FTS5 `tokenize`
+//!   option the host should use when creating the search indexes)
 //! - `close(handle)`
 //! - `initialize(handle)`
 //! - `healthCheck(handle)`
@@ -32,6 +33,15 @@
 //! The bridge serializes Rust data using `serde_wasm_bindgen`, so the JavaScript
 //! implementation must understand the serialized structures defined by
 //! `bridge-traits`.
+//!
+//! # Concurrent Tabs and Workers
+//!
+//! Several adapters (one per tab or worker) may open the same database. Writes
+//! follow a single-writer contract enforced by [`DatabaseWriteLock`]:
+//! initialization, `execute`, `executeBatch`, migrations and whole transactions
+//! (from `beginTransaction` to `commitTransaction`/`rollbackTransaction`) run
+//! while holding the database's write lock, so writes from different contexts
+//! never interleave.
 
 use bridge_traits::database::{
     DatabaseAdapter, DatabaseConfig, DatabaseStatistics, QueryRow, QueryValue, TransactionId,
@@ -41,10 +51,13 @@ use js_sys::Promise;
 use serde::{Deserialize, Serialize};
 use serde_json;
 use serde_wasm_bindgen::{from_value, to_value};
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::io;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::JsFuture;
 
+use crate::db_lock::{DatabaseWriteLock, WriteLockGuard};
 use crate::error::{WasmError, WasmResult};
 
 /// WASM implementation of the [`DatabaseAdapter`] trait.
@@ -55,14 +68,40 @@ pub struct WasmDbAdapter {
     handle: JsValue,
     #[allow(dead_code)]
     config: DatabaseConfig,
+    write_lock: DatabaseWriteLock,
+    /// Write locks held by open transactions, released on commit or rollback
+    transaction_locks: RefCell<HashMap<u64, WriteLockGuard>>,
 }
 
 impl WasmDbAdapter {
     /// Create a new adapter instance.
+    ///
+    /// Opening is serialized with other adapters over the same database so a
+    /// second tab never loads a copy that another context is still writing.
     pub async fn new(config: DatabaseConfig) -> WasmResult<Self> {
+        let write_lock = DatabaseWriteLock::new(&config.database_url);
         let js_config = JsAdapterConfig::from(&config);
-        let handle = call_js_promise(init_database(&to_js_value(&js_config)?)).await?;
-        Ok(Self { handle, config })
+        let handle = {
+            let _guard = write_lock.acquire().await?;
+            call_js_promise(init_database(&to_js_value(&js_config)?)).await?
+        };
+        Ok(Self {
+            handle,
+            config,
+            write_lock,
+            transaction_locks: RefCell::new(HashMap::new()),
+        })
+    }
+
+    /// Acquire the write lock for a single write outside a transaction
+    ///
+    /// Returns `None` while this adapter has a transaction open: it already
+    /// holds the lock, and the lock is not re-entrant.
+    async fn write_guard(&self) -> WasmResult<Option<WriteLockGuard>> {
+        if !self.transaction_locks.borrow().is_empty() {
+            return Ok(None);
+        }
+        self.write_lock.acquire().await.map(Some)
     }
 
     fn params_to_js(params: &[QueryValue]) -> WasmResult<JsValue> {
@@ -84,6 +123,7 @@ impl WasmDbAdapter {
 #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
 impl DatabaseAdapter for WasmDbAdapter {
     async fn initialize(&mut self) -> BridgeResult<()> {
+        let _guard = self.write_guard().await?;
         call_bridge_promise(db_initialize(&self.handle)).await?;
         Ok(())
     }
@@ -95,6 +135,7 @@ impl DatabaseAdapter for WasmDbAdapter {
 
     async fn close(&mut self) -> BridgeResult<()> {
         call_bridge_promise(db_close(&self.handle)).await?;
+        self.transaction_locks.borrow_mut().clear();
         Ok(())
     }
 
@@ -106,6 +147,7 @@ impl DatabaseAdapter for WasmDbAdapter {
     }
 
     async fn execute(&self, statement: &str, params: &[QueryValue]) -> BridgeResult<u64> {
+        let _guard = self.write_guard().await?;
         let affected = call_bridge_promise(db_execute(
             &self.handle,
             statement,
@@ -131,17 +173,29 @@ impl DatabaseAdapter for WasmDbAdapter {
     }
 
     async fn begin_transaction(&self) -> BridgeResult<TransactionId> {
+        let guard = self.write_guard().await?;
         let id = call_bridge_promise(db_begin_transaction(&self.handle)).await?;
-        Ok(TransactionId(js_value_to_u64(id)?))
+        let id = js_value_to_u64(id)?;
+        if let Some(guard) = guard {
+            self.transaction_locks.borrow_mut().insert(id, guard);
+        }
+        Ok(TransactionId(id))
     }
 
     async fn commit_transaction(&self, transaction_id: TransactionId) -> BridgeResult<()> {
-        call_bridge_promise(db_commit_transaction(&self.handle, transaction_id.0)).await?;
+        let result =
+            call_bridge_promise(db_commit_transaction(&self.handle, transaction_id.0)).await;
+        // Release even if the commit failed so other contexts are not blocked forever
+        self.transaction_locks.borrow_mut().remove(&transaction_id.0);
+        result?;
         Ok(())
     }
 
     async fn rollback_transaction(&self, transaction_id: TransactionId) -> BridgeResult<()> {
-        call_bridge_promise(db_rollback_transaction(&self.handle, transaction_id.0)).await?;
+        let result =
+            call_bridge_promise(db_rollback_transaction(&self.handle, transaction_id.0)).await;
+        self.transaction_locks.borrow_mut().remove(&transaction_id.0);
+        result?;
         Ok(())
     }
 
@@ -186,6 +240,7 @@ impl DatabaseAdapter for WasmDbAdapter {
             })
             .collect();
         let js_value = to_js_value(&serialized)?;
+        let _guard = self.write_guard().await?;
         let counts = call_bridge_promise(db_execute_batch(&self.handle, &js_value)).await?;
         from_value(counts).map_err(|e| BridgeError::from(serde_to_wasm_error(e)))
     }
@@ -196,6 +251,7 @@ impl DatabaseAdapter for WasmDbAdapter {
     }
 
     async fn apply_migration(&self, version: i64, up_sql: &str) -> BridgeResult<()> {
+        let _guard = self.write_guard().await?;
         call_bridge_promise(db_apply_migration(&self.handle, version, up_sql)).await?;
         Ok(())
     }
@@ -225,6 +281,7 @@ struct JsAdapterConfig<'a> {
     acquire_timeout_secs: u64,
     enable_cache: bool,
     cache_capacity: usize,
+    fts_tokenize: &'static str,
 }
 
 impl<'a> From<&'a DatabaseConfig> for JsAdapterConfig<'a> {
@@ -236,6 +293,7 @@ impl<'a> From<&'a DatabaseConfig> for JsAdapterConfig<'a> {
             acquire_timeout_secs: value.acquire_timeout_secs,
             enable_cache: value.enable_cache,
             cache_capacity: value.cache_capacity,
+            fts_tokenize: value.fts_tokenizer.tokenize_option(),
         }
     }
 }
@@ -0,0 +1,169 @@
+//! Cross-context write coordination for the WASM database
+//!
+//! Every tab or worker that calls `JsLibrary.create` gets its own
+//! [`WasmDbAdapter`](crate::WasmDbAdapter) over the same IndexedDB-backed
+//! database. The JS runtime holds an in-memory copy of the database and
+//! persists it back after writes, so two contexts writing at the same time
+//! overwrite each other's changes or persist a half-applied transaction.
+//!
+//! The database therefore has a **single-writer** contract: at most one context
+//! may be writing at any moment. [`DatabaseWriteLock`] enforces it with the
+//! [Web Locks API](https://developer.mozilla.org/en-US/docs/Web/API/Web_Locks_API),
+//! which is shared by every tab, dedicated worker and shared worker of the same
+//! origin. Where `navigator.locks` is missing, the lock degrades to an
+//! in-process mutex that only serializes writers inside the current context.
+//!
+//! Reads are not locked. A reader may observe the state from before a
+//! concurrent write commits, but never a partially persisted database.
+
+use crate::error::{WasmError, WasmResult};
+use futures::future::{select, Either};
+use futures::lock::{Mutex, OwnedMutexGuard};
+use js_sys::{Function, Object, Promise, Reflect};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::Arc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+
+/// Prefix for Web Lock names so they don't collide with application locks
+const LOCK_NAME_PREFIX: &str = "mpc-db-write:";
+
+thread_local! {
+    static LOCAL_LOCKS: RefCell<HashMap<String, Arc<Mutex<()>>>> = RefCell::new(HashMap::new());
+}
+
+/// Exclusive write lock for one database, shared across tabs and workers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatabaseWriteLock {
+    name: String,
+}
+
+impl DatabaseWriteLock {
+    /// Create the write lock for the database at `database_url`
+    ///
+    /// Locks created for the same URL contend with each other, in this context
+    /// and in every other context of the same origin.
+    pub fn new(database_url: &str) -> Self {
+        Self {
+            name: format!("{}{}", LOCK_NAME_PREFIX, database_url),
+        }
+    }
+
+    /// Name of the underlying Web Lock
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Wait until this context is the only writer
+    ///
+    /// The lock is held until the returned guard is dropped.
+    pub async fn acquire(&self) -> WasmResult<WriteLockGuard> {
+        // Queue on the in-process mutex first so each context has at most one
+        // pending Web Lock request per database.
+        let local = local_mutex(&self.name).lock_owned().await;
+        let release = match lock_manager() {
+            Some(locks) => Some(request_web_lock(&locks, &self.name).await?),
+            None => None,
+        };
+
+        Ok(WriteLockGuard {
+            release,
+            _local: local,
+        })
+    }
+}
+
+/// Held write lock; releases it on drop
+pub struct WriteLockGuard {
+    release: Option<Function>,
+    _local: OwnedMutexGuard<()>,
+}
+
+impl Drop for WriteLockGuard {
+    fn drop(&mut self) {
+        if let Some(release) = self.release.take() {
+            let _ = release.call0(&JsValue::NULL);
+        }
+    }
+}
+
+impl std::fmt::Debug for WriteLockGuard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WriteLockGuard")
+            .field("web_lock", &self.release.is_some())
+            .finish()
+    }
+}
+
+fn local_mutex(name: &str) -> Arc<Mutex<()>> {
+    LOCAL_LOCKS.with(|locks| {
+        locks
+            .borrow_mut()
+            .entry(name.to_string())
+            .or_insert_with(|| Arc::new(Mutex::new(())))
+            .clone()
+    })
+}
+
+/// `navigator.locks` of the current global scope (window or worker), if supported
+fn lock_manager() -> Option<JsValue> {
+    let navigator = Reflect::get(&js_sys::global(), &JsValue::from_str("navigator")).ok()?;
+    if navigator.is_undefined() || navigator.is_null() {
+        return None;
+    }
+    let locks = Reflect::get(&navigator, &JsValue::from_str("locks")).ok()?;
+    if locks.is_undefined() || locks.is_null() {
+        return None;
+    }
+    Some(locks)
+}
+
+/// Request an exclusive Web Lock and return the function that releases it
+///
+/// A Web Lock is held until the promise returned from the request callback
+/// settles, so the callback hands back a promise that only resolves once the
+/// release function is called.
+async fn request_web_lock(locks: &JsValue, name: &str) -> WasmResult<Function> {
+    let (held, release) = deferred();
+    let (granted, grant) = deferred();
+
+    let callback = Closure::once_into_js(move |_lock: JsValue| -> JsValue {
+        let _ = grant.call0(&JsValue::NULL);
+        held.into()
+    });
+
+    let options = Object::new();
+    Reflect::set(
+        &options,
+        &JsValue::from_str("mode"),
+        &JsValue::from_str("exclusive"),
+    )?;
+    let request: Function = Reflect::get(locks, &JsValue::from_str("request"))?
+        .dyn_into()
+        .map_err(|_| WasmError::Unsupported("navigator.locks.request".to_string()))?;
+    let requested: Promise = request
+        .call3(locks, &JsValue::from_str(name), &options, &callback)?
+        .dyn_into()
+        .map_err(|_| WasmError::JavaScript("Web Lock request did not return a Promise".into()))?;
+
+    // The request promise settles only after release, so if it finishes first
+    // the lock was never granted.
+    match select(JsFuture::from(granted), JsFuture::from(requested)).await {
+        Either::Left((Ok(_), _)) => Ok(release),
+        Either::Left((Err(err), _)) | Either::Right((Err(err), _)) => Err(WasmError::from(err)),
+        Either::Right((Ok(_), _)) => Err(WasmError::JavaScript(format!(
+            "Web Lock '{}' request finished before it was granted",
+            name
+        ))),
+    }
+}
+
+/// Create a pending promise together with the function that resolves it
+fn deferred() -> (Promise, Function) {
+    let mut resolve_fn = None;
+    let promise = Promise::new(&mut |resolve, _reject| resolve_fn = Some(resolve));
+    let resolve = resolve_fn.expect("Promise executor runs synchronously");
+    (promise, resolve)
+}
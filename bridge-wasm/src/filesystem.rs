@@ -37,7 +37,7 @@
 
 use async_trait::async_trait;
 use bridge_traits::{
-    error::Result as BridgeResult,
+    error::{BridgeError, Result as BridgeResult},
     storage::{FileMetadata, FileSystemAccess},
     DynAsyncRead, DynAsyncWrite,
 };
@@ -233,6 +233,40 @@ impl WasmFileSystem {
         Ok(promise)
     }
 
+    /// Convert an IDB transaction to a Promise that resolves on commit
+    ///
+    /// The promise rejects if the transaction errors or is aborted, in which
+    /// case none of its writes become visible.
+    fn transaction_to_promise(transaction: &IdbTransaction) -> js_sys::Promise {
+        js_sys::Promise::new(&mut |resolve, reject| {
+            let oncomplete = Closure::once(move || {
+                resolve.call0(&JsValue::NULL).unwrap();
+            });
+            transaction.set_oncomplete(Some(oncomplete.as_ref().unchecked_ref()));
+            oncomplete.forget();
+
+            let transaction_clone = transaction.clone();
+            let reject_clone = reject.clone();
+            let onerror = Closure::once(move || {
+                let error = transaction_clone
+                    .error()
+                    .map(JsValue::from)
+                    .unwrap_or_else(|| JsValue::from_str("Transaction failed"));
+                reject_clone.call1(&JsValue::NULL, &error).unwrap();
+            });
+            transaction.set_onerror(Some(onerror.as_ref().unchecked_ref()));
+            onerror.forget();
+
+            let onabort = Closure::once(move || {
+                reject
+                    .call1(&JsValue::NULL, &JsValue::from_str("Transaction aborted"))
+                    .unwrap();
+            });
+            transaction.set_onabort(Some(onabort.as_ref().unchecked_ref()));
+            onabort.forget();
+        })
+    }
+
     /// Get a transaction for the given store
     fn get_transaction(
         &self,
@@ -330,6 +364,21 @@ impl WasmFileSystem {
         Ok(())
     }
 
+    /// Build the JS object stored in the `chunks` store
+    fn chunk_to_js(chunk: FileChunk) -> WasmResult<Object> {
+        let chunk_id = format!("{}#{}", chunk.file_path, chunk.chunk_index);
+        let js_chunk = Object::new();
+        Reflect::set(&js_chunk, &"id".into(), &chunk_id.into())?;
+        Reflect::set(&js_chunk, &"file_path".into(), &chunk.file_path.into())?;
+        Reflect::set(
+            &js_chunk,
+            &"chunk_index".into(),
+            &(chunk.chunk_index as f64).into(),
+        )?;
+        Reflect::set(&js_chunk, &"data".into(), &chunk.data.into())?;
+        Ok(js_chunk)
+    }
+
     /// Store file chunks for large files
     async fn store_chunks(&self, file_path: &str, data: &[u8]) -> WasmResult<usize> {
         let chunk_count = (data.len() + CHUNK_SIZE - 1) / CHUNK_SIZE;
@@ -343,16 +392,7 @@ impl WasmFileSystem {
                 data: base64::encode(chunk_data),
             };
 
-            let chunk_id = format!("{}#{}", file_path, i);
-            let js_chunk = Object::new();
-            Reflect::set(&js_chunk, &"id".into(), &chunk_id.into())?;
-            Reflect::set(&js_chunk, &"file_path".into(), &chunk.file_path.into())?;
-            Reflect::set(
-                &js_chunk,
-                &"chunk_index".into(),
-                &(chunk.chunk_index as f64).into(),
-            )?;
-            Reflect::set(&js_chunk, &"data".into(), &chunk.data.into())?;
+            let js_chunk = Self::chunk_to_js(chunk)?;
 
             let request = store.put(&js_chunk).map_err(|e| WasmError::from(e))?;
             let promise = Self::request_to_promise(&request)?;
@@ -606,6 +646,73 @@ impl FileSystemAccess for WasmFileSystem {
         Ok(())
     }
 
+    async fn write_file_atomic(&self, path: &Path, data: Bytes) -> BridgeResult<()> {
+        // Ensure parent directory exists
+        if let Some(parent) = path.parent() {
+            self.ensure_directory_exists(parent).await?;
+        }
+
+        let now = js_sys::Date::now() as i64;
+        let normalized_path = Self::normalize_path(path);
+        let old_entry = self.get_file_entry(path).await?;
+
+        // Entry and chunks go through a single transaction spanning both
+        // stores, so the new record only becomes visible once it commits.
+        let store_names = Array::new();
+        store_names.push(&JsValue::from_str("files"));
+        store_names.push(&JsValue::from_str("chunks"));
+        let transaction = self
+            .db
+            .transaction_with_str_sequence_and_mode(&store_names, IdbTransactionMode::Readwrite)
+            .map_err(WasmError::from)?;
+        let files = self.get_store(&transaction, "files")?;
+        let chunks = self.get_store(&transaction, "chunks")?;
+
+        if let Some(old_entry) = old_entry.filter(|e| e.is_chunked) {
+            for i in 0..old_entry.chunk_count {
+                let chunk_id = format!("{}#{}", old_entry.path, i);
+                chunks
+                    .delete(&JsValue::from_str(&chunk_id))
+                    .map_err(WasmError::from)?;
+            }
+        }
+
+        let (is_chunked, chunk_count, content) = if data.len() > MAX_INLINE_SIZE {
+            let mut chunk_count = 0;
+            for (i, chunk_data) in data.chunks(CHUNK_SIZE).enumerate() {
+                let js_chunk = Self::chunk_to_js(FileChunk {
+                    file_path: normalized_path.clone(),
+                    chunk_index: i,
+                    data: base64::encode(chunk_data),
+                })?;
+                chunks.put(&js_chunk).map_err(WasmError::from)?;
+                chunk_count += 1;
+            }
+            (true, chunk_count, None)
+        } else {
+            (false, 0, Some(base64::encode(&data)))
+        };
+
+        let entry = FileEntry {
+            path: normalized_path,
+            content,
+            is_chunked,
+            chunk_count,
+            size: data.len() as u64,
+            created_at: now,
+            modified_at: now,
+            is_directory: false,
+        };
+        let js_entry = serde_wasm_bindgen::to_value(&entry)
+            .map_err(|e| WasmError::Serialization(serde_io_error(e.to_string())))?;
+        files.put(&js_entry).map_err(WasmError::from)?;
+
+        JsFuture::from(Self::transaction_to_promise(&transaction))
+            .await
+            .map_err(WasmError::from)?;
+        Ok(())
+    }
+
     async fn append_file(&self, path: &Path, data: Bytes) -> BridgeResult<()> {
         // Read existing content if file exists
         let existing_data = if self.exists(path).await? {
@@ -698,6 +805,43 @@ impl FileSystemAccess for WasmFileSystem {
         Ok(Box::new(cursor) as Box<DynAsyncRead>)
     }
 
+    async fn directory_size(&self, path: &Path) -> BridgeResult<u64> {
+        // Every descendant shares the directory's key prefix, so a single
+        // scan covers the whole tree without recursing
+        let normalized_path = Self::normalize_path(path);
+        let entries = self.list_entries_with_prefix(&normalized_path).await?;
+
+        Ok(entries
+            .iter()
+            .filter(|e| !e.is_directory && e.path != normalized_path)
+            .map(|e| e.size)
+            .sum())
+    }
+
+    async fn available_space(&self, _path: &Path) -> BridgeResult<u64> {
+        // IndexedDB shares a single origin-wide quota, so the path is irrelevant
+        let window = web_sys::window().ok_or(WasmError::JavaScript(
+            "No window object available".to_string(),
+        ))?;
+        let promise = window
+            .navigator()
+            .storage()
+            .estimate()
+            .map_err(|e| WasmError::from(e))?;
+        let estimate = JsFuture::from(promise).await.map_err(WasmError::from)?;
+
+        let quota = Reflect::get(&estimate, &"quota".into())
+            .map_err(WasmError::from)?
+            .as_f64()
+            .ok_or_else(|| BridgeError::NotAvailable("Storage quota unavailable".to_string()))?;
+        let usage = Reflect::get(&estimate, &"usage".into())
+            .map_err(WasmError::from)?
+            .as_f64()
+            .unwrap_or(0.0);
+
+        Ok((quota - usage).max(0.0) as u64)
+    }
+
     async fn open_write_stream(&self, _path: &Path) -> BridgeResult<Box<DynAsyncWrite>> {
         // WASM doesn't support true streaming writes to IndexedDB
         // This would require a more complex implementation with buffering
@@ -871,4 +1015,71 @@ mod tests {
         fs.delete_file(&test_path).await.unwrap();
         assert!(!fs.exists(&test_path).await.unwrap());
     }
+
+    #[wasm_bindgen_test]
+    async fn test_write_file_atomic() {
+        let fs = WasmFileSystem::new("test-atomic").await.unwrap();
+        let test_path = PathBuf::from("/cache/atomic.txt");
+
+        fs.write_file_atomic(&test_path, Bytes::from("first"))
+            .await
+            .unwrap();
+        fs.write_file_atomic(&test_path, Bytes::from("second"))
+            .await
+            .unwrap();
+
+        assert_eq!(fs.read_file(&test_path).await.unwrap(), "second");
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_aborted_atomic_write_is_not_visible() {
+        let fs = WasmFileSystem::new("test-atomic-abort").await.unwrap();
+        let test_path = PathBuf::from("/cache/aborted.txt");
+        let _ = fs.delete_file(&test_path).await;
+
+        // Simulate a crash: stage the entry, then abort before commit
+        let transaction = fs
+            .get_transaction("files", IdbTransactionMode::Readwrite)
+            .unwrap();
+        let store = fs.get_store(&transaction, "files").unwrap();
+        let entry = FileEntry {
+            path: WasmFileSystem::normalize_path(&test_path),
+            content: Some(base64::encode(b"partial")),
+            is_chunked: false,
+            chunk_count: 0,
+            size: 7,
+            created_at: 0,
+            modified_at: 0,
+            is_directory: false,
+        };
+        store
+            .put(&serde_wasm_bindgen::to_value(&entry).unwrap())
+            .unwrap();
+        let committed = WasmFileSystem::transaction_to_promise(&transaction);
+        transaction.abort().unwrap();
+        assert!(JsFuture::from(committed).await.is_err());
+
+        assert!(!fs.exists(&test_path).await.unwrap());
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_directory_size_nested() {
+        let fs = WasmFileSystem::new("test-dir-size").await.unwrap();
+        let root = PathBuf::from("/data/size-test");
+
+        fs.write_file(&root.join("a.bin"), Bytes::from(vec![0u8; 10]))
+            .await
+            .unwrap();
+        fs.write_file(&root.join("nested/b.bin"), Bytes::from(vec![0u8; 20]))
+            .await
+            .unwrap();
+        fs.write_file(
+            &root.join("nested/deeper/c.bin"),
+            Bytes::from(vec![0u8; 30]),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(fs.directory_size(&root).await.unwrap(), 60);
+    }
 }
@@ -4,7 +4,9 @@
 //! resulting `Response` objects back into the bridge-friendly `HttpResponse`
 //! type. It intentionally keeps behaviour minimal (no automatic retries yet)
 //! but honours per-request headers, bodies, and optional timeouts via
-//! `AbortController`.
+//! `AbortController`. Uploads use the buffered default of
+//! `HttpClient::upload_stream`, since `fetch` request streams are not widely
+//! supported.
 
 use async_trait::async_trait;
 use bridge_traits::{
@@ -0,0 +1,220 @@
+//! Structured errors surfaced to JavaScript
+//!
+//! WASM bindings used to flatten every failure to a plain string, which left JS
+//! callers unable to tell a missing entity from an expired session. `JsError`
+//! keeps the error category so the web side can branch on it:
+//!
+//! ```javascript
+//! try {
+//!   await sync.startFullSync(profileId);
+//! } catch (err) {
+//!   if (err.kind === 'Auth') {
+//!     await reauthenticate();
+//!   } else if (err.retryable) {
+//!     scheduleRetry();
+//!   }
+//! }
+//! ```
+//!
+//! Each binding crate converts its own error enum into a `JsError`; this module
+//! only provides the shared shape and the conversions for bridge-level errors.
+
+use crate::error::WasmError;
+use bridge_traits::error::BridgeError;
+use std::fmt;
+use wasm_bindgen::JsValue;
+
+/// Category of an error surfaced to JavaScript
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JsErrorKind {
+    /// Authentication or authorization failure (sign-in required)
+    Auth,
+    /// Requested entity does not exist
+    NotFound,
+    /// Caller supplied invalid arguments
+    InvalidInput,
+    /// Network request failed
+    Network,
+    /// Operation took too long
+    Timeout,
+    /// Operation was cancelled
+    Cancelled,
+    /// Operation conflicts with current state (e.g. already running)
+    Conflict,
+    /// Database or storage failure
+    Storage,
+    /// Capability is not available on this platform
+    Unavailable,
+    /// Any other failure
+    Internal,
+}
+
+impl JsErrorKind {
+    /// Name exposed to JavaScript as the `kind` property
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JsErrorKind::Auth => "Auth",
+            JsErrorKind::NotFound => "NotFound",
+            JsErrorKind::InvalidInput => "InvalidInput",
+            JsErrorKind::Network => "Network",
+            JsErrorKind::Timeout => "Timeout",
+            JsErrorKind::Cancelled => "Cancelled",
+            JsErrorKind::Conflict => "Conflict",
+            JsErrorKind::Storage => "Storage",
+            JsErrorKind::Unavailable => "Unavailable",
+            JsErrorKind::Internal => "Internal",
+        }
+    }
+
+    /// Whether errors of this kind are usually transient
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, JsErrorKind::Network | JsErrorKind::Timeout)
+    }
+}
+
+impl fmt::Display for JsErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error value handed to JavaScript
+///
+/// Converts into a JS `Error` whose `name` is `"JsError"` and which carries
+/// `kind`, `message` and `retryable` properties.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsError {
+    /// Error category
+    pub kind: JsErrorKind,
+    /// Human-readable description
+    pub message: String,
+    /// Whether retrying the same call may succeed
+    pub retryable: bool,
+}
+
+impl JsError {
+    /// Create an error whose retryability follows its kind
+    pub fn new(kind: JsErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            retryable: kind.is_retryable(),
+        }
+    }
+
+    /// Create an `Internal` error
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(JsErrorKind::Internal, message)
+    }
+
+    /// Create an `InvalidInput` error
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        Self::new(JsErrorKind::InvalidInput, message)
+    }
+
+    /// Override the retryable flag
+    pub fn with_retryable(mut self, retryable: bool) -> Self {
+        self.retryable = retryable;
+        self
+    }
+
+    /// Prefix the message with a description of the failed operation
+    pub fn with_context(mut self, context: &str) -> Self {
+        self.message = format!("{}: {}", context, self.message);
+        self
+    }
+}
+
+impl fmt::Display for JsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.kind, self.message)
+    }
+}
+
+impl From<JsError> for JsValue {
+    fn from(err: JsError) -> Self {
+        let error = js_sys::Error::new(&err.message);
+        error.set_name("JsError");
+        let value = JsValue::from(error);
+        let _ = js_sys::Reflect::set(
+            &value,
+            &JsValue::from_str("kind"),
+            &JsValue::from_str(err.kind.as_str()),
+        );
+        let _ = js_sys::Reflect::set(
+            &value,
+            &JsValue::from_str("retryable"),
+            &JsValue::from_bool(err.retryable),
+        );
+        value
+    }
+}
+
+impl From<BridgeError> for JsError {
+    fn from(err: BridgeError) -> Self {
+        let kind = match &err {
+            BridgeError::NotAvailable(_) => JsErrorKind::Unavailable,
+            BridgeError::OperationFailed(_) => JsErrorKind::Internal,
+            BridgeError::DatabaseError(_) => JsErrorKind::Storage,
+            BridgeError::Deserialization(_) => JsErrorKind::Storage,
+            BridgeError::RetriesExhausted { .. } => JsErrorKind::Network,
+            BridgeError::Io(io) => match io.kind() {
+                std::io::ErrorKind::NotFound => JsErrorKind::NotFound,
+                std::io::ErrorKind::TimedOut => JsErrorKind::Timeout,
+                std::io::ErrorKind::PermissionDenied => JsErrorKind::Auth,
+                _ => JsErrorKind::Storage,
+            },
+        };
+        Self::new(kind, err.to_string())
+    }
+}
+
+impl From<WasmError> for JsError {
+    fn from(err: WasmError) -> Self {
+        let kind = match &err {
+            WasmError::FileNotFound(_) | WasmError::DirectoryNotFound(_) => JsErrorKind::NotFound,
+            WasmError::AlreadyExists(_) => JsErrorKind::Conflict,
+            WasmError::InvalidPath(_) | WasmError::NotADirectory(_) | WasmError::NotAFile(_) => {
+                JsErrorKind::InvalidInput
+            }
+            WasmError::Cancelled => JsErrorKind::Cancelled,
+            WasmError::NotInitialized | WasmError::Unsupported(_) => JsErrorKind::Unavailable,
+            WasmError::IndexedDb(_) | WasmError::Io(_) => JsErrorKind::Storage,
+            WasmError::JavaScript(_) | WasmError::Serialization(_) => JsErrorKind::Internal,
+        };
+        Self::new(kind, err.to_string())
+    }
+}
+
+impl From<serde_wasm_bindgen::Error> for JsError {
+    fn from(err: serde_wasm_bindgen::Error) -> Self {
+        Self::internal(err.to_string())
+    }
+}
+
+impl From<serde_json::Error> for JsError {
+    fn from(err: serde_json::Error) -> Self {
+        match err.classify() {
+            serde_json::error::Category::Io => Self::internal(err.to_string()),
+            _ => Self::invalid_input(err.to_string()),
+        }
+    }
+}
+
+impl From<String> for JsError {
+    fn from(message: String) -> Self {
+        Self::internal(message)
+    }
+}
+
+impl From<&str> for JsError {
+    fn from(message: &str) -> Self {
+        Self::internal(message)
+    }
+}
+
+/// Convert any error with a known category into a JS error value
+pub fn to_js_error<E: Into<JsError>>(err: E) -> JsValue {
+    let err: JsError = err.into();
+    err.into()
+}
@@ -14,6 +14,8 @@
 //!
 //! - `WasmFileSystem`: IndexedDB-based file system simulation
 //! - `WasmDbAdapter`: WebAssembly-compatible database bridge (delegates to host-provided sql.js/IndexedDB runtime)
+//! - `DatabaseWriteLock`: Web Locks-based single-writer coordination across tabs and workers
+//! - `WasmNetworkMonitor`: `navigator.onLine` + Network Information API connectivity
 //! - More implementations to come (HTTP, storage, network, etc.)
 //!
 //! # Examples
@@ -35,11 +37,15 @@
 #![warn(missing_docs)]
 
 pub mod bootstrap;
+pub mod cancellation;
 pub mod database;
+pub mod db_lock;
 pub mod error;
 pub mod filesystem;
 pub mod fs_adapter;
 pub mod http;
+pub mod js_error;
+pub mod network;
 pub mod storage;
 
 // WebAssembly bindings (JavaScript-accessible wrappers)
@@ -48,11 +54,15 @@ pub mod wasm;
 
 // Re-export commonly used types
 pub use bootstrap::{build_wasm_bridges, WasmBridgeConfig, WasmBridgeSet};
+pub use cancellation::{cancelled_error, run_cancellable, token_from_abort_signal};
 pub use database::WasmDbAdapter;
+pub use db_lock::{DatabaseWriteLock, WriteLockGuard};
 pub use error::{WasmError, WasmResult};
 pub use filesystem::WasmFileSystem;
 pub use fs_adapter::WasmFileSystemAdapter;
 pub use http::WasmHttpClient;
+pub use js_error::{to_js_error, JsError, JsErrorKind};
+pub use network::WasmNetworkMonitor;
 pub use storage::{WasmSecureStore, WasmSettingsStore};
 
 // Re-export wasm bindings
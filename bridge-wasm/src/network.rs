@@ -0,0 +1,204 @@
+//! Network Monitoring for WebAssembly
+//!
+//! Reads connectivity from `navigator.onLine` and the Network Information API
+//! (`navigator.connection`). The Network Information API is only available in
+//! Chromium-based browsers; elsewhere the monitor reports the online flag and
+//! no connection type or throughput estimate.
+//!
+//! Changes are driven by the `online`/`offline` events of the global scope
+//! and the `change` event of `navigator.connection`.
+
+use async_trait::async_trait;
+use bridge_traits::{
+    error::Result,
+    network::{NetworkChangeStream, NetworkInfo, NetworkMonitor, NetworkStatus, NetworkType},
+};
+use futures::{channel::mpsc, StreamExt};
+use js_sys::Reflect;
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::EventTarget;
+
+/// Browser network monitor
+///
+/// Works in both window and worker scopes, since it reads `navigator` from
+/// the current global object.
+#[derive(Debug, Default, Clone)]
+pub struct WasmNetworkMonitor;
+
+impl WasmNetworkMonitor {
+    /// Create a new network monitor
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn read_info() -> NetworkInfo {
+        let online = navigator()
+            .and_then(|navigator| property(&navigator, "onLine"))
+            .and_then(|value| value.as_bool());
+        let status = match online {
+            Some(true) => NetworkStatus::Connected,
+            Some(false) => NetworkStatus::Disconnected,
+            None => NetworkStatus::Indeterminate,
+        };
+
+        let connection = connection();
+        let network_type = connection
+            .as_ref()
+            .filter(|_| status == NetworkStatus::Connected)
+            .and_then(|connection| property(connection, "type"))
+            .and_then(|value| value.as_string())
+            .map(|kind| match kind.as_str() {
+                "cellular" => NetworkType::Cellular,
+                "wifi" => NetworkType::WiFi,
+                "ethernet" => NetworkType::Ethernet,
+                _ => NetworkType::Other,
+            });
+
+        // Browsers don't expose metering; treat cellular and Data Saver as metered
+        let save_data = connection
+            .as_ref()
+            .and_then(|connection| property(connection, "saveData"))
+            .and_then(|value| value.as_bool())
+            .unwrap_or(false);
+        let is_metered = save_data || network_type == Some(NetworkType::Cellular);
+
+        NetworkInfo {
+            status,
+            network_type,
+            is_metered,
+            is_expensive: is_metered,
+            is_captive_portal: None,
+            is_vpn: None,
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl NetworkMonitor for WasmNetworkMonitor {
+    async fn get_network_info(&self) -> Result<NetworkInfo> {
+        Ok(Self::read_info())
+    }
+
+    async fn estimated_downlink_mbps(&self) -> Result<Option<f64>> {
+        Ok(connection()
+            .and_then(|connection| property(&connection, "downlink"))
+            .and_then(|value| value.as_f64())
+            .filter(|mbps| mbps.is_finite() && *mbps >= 0.0))
+    }
+
+    async fn subscribe_changes(&self) -> Result<Box<dyn NetworkChangeStream>> {
+        Ok(Box::new(WasmNetworkChangeStream {
+            events: ConnectivityEvents::listen(),
+            last: Self::read_info(),
+        }))
+    }
+}
+
+/// Network change stream woken by browser connectivity events
+struct WasmNetworkChangeStream {
+    events: ConnectivityEvents,
+    last: NetworkInfo,
+}
+
+#[async_trait(?Send)]
+impl NetworkChangeStream for WasmNetworkChangeStream {
+    async fn next(&mut self) -> Option<NetworkInfo> {
+        loop {
+            self.events.receiver.next().await?;
+
+            // `online` and `change` often fire together for one transition
+            let info = WasmNetworkMonitor::read_info();
+            if info.is_transition_from(&self.last) {
+                self.last = info.clone();
+                return Some(info);
+            }
+        }
+    }
+}
+
+/// Listeners for connectivity events, removed again on drop
+struct ConnectivityEvents {
+    receiver: mpsc::UnboundedReceiver<()>,
+    listeners: Vec<(EventTarget, &'static str, Closure<dyn FnMut(JsValue)>)>,
+}
+
+impl ConnectivityEvents {
+    fn listen() -> Self {
+        let (sender, receiver) = mpsc::unbounded();
+
+        let global: JsValue = js_sys::global().into();
+        let mut sources = vec![(global.clone(), "online"), (global, "offline")];
+        if let Some(connection) = connection() {
+            sources.push((connection, "change"));
+        }
+
+        let listeners = sources
+            .into_iter()
+            .filter_map(|(target, event)| {
+                let target: EventTarget = target.dyn_into().ok()?;
+                let sender = sender.clone();
+                let closure = Closure::<dyn FnMut(JsValue)>::new(move |_event: JsValue| {
+                    let _ = sender.unbounded_send(());
+                });
+                target
+                    .add_event_listener_with_callback(event, closure.as_ref().unchecked_ref())
+                    .ok()?;
+                Some((target, event, closure))
+            })
+            .collect();
+
+        Self {
+            receiver,
+            listeners,
+        }
+    }
+}
+
+impl Drop for ConnectivityEvents {
+    fn drop(&mut self) {
+        for (target, event, closure) in &self.listeners {
+            let _ =
+                target.remove_event_listener_with_callback(event, closure.as_ref().unchecked_ref());
+        }
+    }
+}
+
+/// `navigator` of the current global scope, if any
+fn navigator() -> Option<JsValue> {
+    property(&js_sys::global(), "navigator")
+}
+
+/// `navigator.connection`, if the Network Information API is supported
+fn connection() -> Option<JsValue> {
+    navigator().and_then(|navigator| property(&navigator, "connection"))
+}
+
+fn property(target: &JsValue, name: &str) -> Option<JsValue> {
+    let value = Reflect::get(target, &JsValue::from_str(name)).ok()?;
+    (!value.is_undefined() && !value.is_null()).then_some(value)
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    async fn downlink_estimate_is_non_negative() {
+        let monitor = WasmNetworkMonitor::new();
+        if let Some(mbps) = monitor.estimated_downlink_mbps().await.unwrap() {
+            assert!(mbps >= 0.0);
+        }
+    }
+
+    #[wasm_bindgen_test]
+    async fn subscribe_starts_with_current_info() {
+        let monitor = WasmNetworkMonitor::new();
+        let current = monitor.get_network_info().await.unwrap();
+
+        let mut stream = monitor.subscribe().await.unwrap();
+        assert_eq!(stream.next().await, Some(current));
+    }
+}
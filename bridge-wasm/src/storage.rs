@@ -30,10 +30,16 @@ use async_trait::async_trait;
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use bridge_traits::{
     error::{BridgeError, Result as BridgeResult},
-    storage::{SecureStore, SettingsStore, SettingsTransaction},
+    storage::{
+        SecureStore, SettingWatchStream, SettingsStore, SettingsTransaction, SettingsWatchers,
+    },
+    time::{Clock, SystemClock},
 };
 use rand::{rngs::OsRng, RngCore};
 use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
 use wasm_bindgen::{JsCast, JsValue};
 
 use aes_gcm::{
@@ -44,6 +50,8 @@ use aes_gcm::{
 const SECURE_MASTER_KEY_SUFFIX: &str = "secure-master-key";
 const SECURE_PREFIX: &str = "secure";
 const SETTINGS_PREFIX: &str = "settings";
+/// Expiry timestamps (Unix milliseconds) for settings stored with a TTL
+const SETTINGS_EXPIRY_PREFIX: &str = "settings-expiry";
 
 fn js_error(context: &str, err: JsValue) -> BridgeError {
     let message = if err.is_string() {
@@ -215,9 +223,16 @@ fn load_or_create_master_key(
 
 #[derive(Clone)]
 /// Browser-backed settings store (plain-text key/value pairs).
+///
+/// Values stored with a TTL keep their expiry timestamp in a sibling key and
+/// are dropped lazily when read after that time.
+///
+/// Clones share their watchers, but writes from other tabs are not observed.
 pub struct WasmSettingsStore {
     storage: web_sys::Storage,
     namespace: String,
+    clock: Arc<dyn Clock>,
+    watchers: Rc<SettingsWatchers>,
 }
 
 impl WasmSettingsStore {
@@ -226,16 +241,50 @@ impl WasmSettingsStore {
         Ok(Self {
             storage: local_storage()?,
             namespace: namespace.into(),
+            clock: Arc::new(SystemClock),
+            watchers: Rc::new(SettingsWatchers::new()),
         })
     }
 
+    /// Use a custom clock to decide when values expire.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
     fn key_for(&self, key: &str) -> String {
         scoped_key(&self.namespace, SETTINGS_PREFIX, key)
     }
 
+    fn expiry_key_for(&self, key: &str) -> String {
+        scoped_key(&self.namespace, SETTINGS_EXPIRY_PREFIX, key)
+    }
+
     fn prefix(&self) -> String {
         namespaced_prefix(&self.namespace, SETTINGS_PREFIX)
     }
+
+    fn expiry_prefix(&self) -> String {
+        namespaced_prefix(&self.namespace, SETTINGS_EXPIRY_PREFIX)
+    }
+
+    fn is_expired(&self, key: &str) -> BridgeResult<bool> {
+        let expires_at = self
+            .storage
+            .get_item(&self.expiry_key_for(key))
+            .map_err(|err| js_error("get setting expiry", err))?
+            .and_then(|value| value.parse::<i64>().ok());
+        Ok(matches!(expires_at, Some(at) if at <= self.clock.unix_timestamp_millis()))
+    }
+
+    fn remove_entry(&self, key: &str) -> BridgeResult<()> {
+        self.storage
+            .remove_item(&self.key_for(key))
+            .map_err(|err| js_error("remove setting", err))?;
+        self.storage
+            .remove_item(&self.expiry_key_for(key))
+            .map_err(|err| js_error("remove setting expiry", err))
+    }
 }
 
 #[async_trait(?Send)]
@@ -243,15 +292,49 @@ impl SettingsStore for WasmSettingsStore {
     async fn set_string(&self, key: &str, value: &str) -> BridgeResult<()> {
         self.storage
             .set_item(&self.key_for(key), value)
-            .map_err(|err| js_error("set setting", err))
+            .map_err(|err| js_error("set setting", err))?;
+        self.storage
+            .remove_item(&self.expiry_key_for(key))
+            .map_err(|err| js_error("remove setting expiry", err))?;
+        self.watchers.notify(key, Some(value));
+        Ok(())
     }
 
     async fn get_string(&self, key: &str) -> BridgeResult<Option<String>> {
+        if self.is_expired(key)? {
+            self.remove_entry(key)?;
+            return Ok(None);
+        }
         self.storage
             .get_item(&self.key_for(key))
             .map_err(|err| js_error("get setting", err))
     }
 
+    async fn set_string_with_ttl(&self, key: &str, value: &str, ttl: Duration) -> BridgeResult<()> {
+        let ttl_ms = i64::try_from(ttl.as_millis()).unwrap_or(i64::MAX);
+        let expires_at = self.clock.unix_timestamp_millis().saturating_add(ttl_ms);
+        self.storage
+            .set_item(&self.key_for(key), value)
+            .map_err(|err| js_error("set setting", err))?;
+        self.storage
+            .set_item(&self.expiry_key_for(key), &expires_at.to_string())
+            .map_err(|err| js_error("set setting expiry", err))?;
+        self.watchers.notify(key, Some(value));
+        Ok(())
+    }
+
+    async fn purge_expired(&self) -> BridgeResult<usize> {
+        let mut purged = 0;
+        for key in list_prefixed_keys(&self.storage, &self.expiry_prefix())? {
+            if self.is_expired(&key)? {
+                self.remove_entry(&key)?;
+                self.watchers.notify(&key, None);
+                purged += 1;
+            }
+        }
+        Ok(purged)
+    }
+
     async fn set_bool(&self, key: &str, value: bool) -> BridgeResult<()> {
         self.set_string(key, if value { "true" } else { "false" })
             .await
@@ -293,9 +376,9 @@ impl SettingsStore for WasmSettingsStore {
     }
 
     async fn delete(&self, key: &str) -> BridgeResult<()> {
-        self.storage
-            .remove_item(&self.key_for(key))
-            .map_err(|err| js_error("remove setting", err))
+        self.remove_entry(key)?;
+        self.watchers.notify(key, None);
+        Ok(())
     }
 
     async fn has_key(&self, key: &str) -> BridgeResult<bool> {
@@ -303,16 +386,22 @@ impl SettingsStore for WasmSettingsStore {
     }
 
     async fn list_keys(&self) -> BridgeResult<Vec<String>> {
-        list_prefixed_keys(&self.storage, &self.prefix())
+        let mut keys = Vec::new();
+        for key in list_prefixed_keys(&self.storage, &self.prefix())? {
+            if !self.is_expired(&key)? {
+                keys.push(key);
+            }
+        }
+        Ok(keys)
     }
 
     async fn clear_all(&self) -> BridgeResult<()> {
-        let keys = self.list_keys().await?;
+        let mut keys = list_prefixed_keys(&self.storage, &self.prefix())?;
+        keys.extend(list_prefixed_keys(&self.storage, &self.expiry_prefix())?);
         for key in keys {
-            self.storage
-                .remove_item(&self.key_for(&key))
-                .map_err(|err| js_error("clear setting", err))?;
+            self.remove_entry(&key)?;
         }
+        self.watchers.notify_cleared();
         Ok(())
     }
 
@@ -320,8 +409,14 @@ impl SettingsStore for WasmSettingsStore {
         Ok(Box::new(LocalSettingsTransaction::new(
             self.storage.clone(),
             self.namespace.clone(),
+            self.watchers.clone(),
         )))
     }
+
+    async fn watch(&self, key: &str) -> BridgeResult<SettingWatchStream> {
+        let current = self.get_string(key).await?;
+        Ok(self.watchers.subscribe(key, current))
+    }
 }
 
 fn list_prefixed_keys(storage: &web_sys::Storage, prefix: &str) -> BridgeResult<Vec<String>> {
@@ -345,15 +440,18 @@ fn list_prefixed_keys(storage: &web_sys::Storage, prefix: &str) -> BridgeResult<
 struct LocalSettingsTransaction {
     storage: web_sys::Storage,
     namespace: String,
-    staged: HashMap<String, String>,
+    watchers: Rc<SettingsWatchers>,
+    /// Staged values, `None` for keys to delete
+    staged: HashMap<String, Option<String>>,
     committed: bool,
 }
 
 impl LocalSettingsTransaction {
-    fn new(storage: web_sys::Storage, namespace: String) -> Self {
+    fn new(storage: web_sys::Storage, namespace: String, watchers: Rc<SettingsWatchers>) -> Self {
         Self {
             storage,
             namespace,
+            watchers,
             staged: HashMap::new(),
             committed: false,
         }
@@ -367,7 +465,12 @@ impl LocalSettingsTransaction {
 #[async_trait(?Send)]
 impl SettingsTransaction for LocalSettingsTransaction {
     async fn set_string(&mut self, key: &str, value: &str) -> BridgeResult<()> {
-        self.staged.insert(key.to_string(), value.to_string());
+        self.staged.insert(key.to_string(), Some(value.to_string()));
+        Ok(())
+    }
+
+    async fn delete(&mut self, key: &str) -> BridgeResult<()> {
+        self.staged.insert(key.to_string(), None);
         Ok(())
     }
 
@@ -376,9 +479,22 @@ impl SettingsTransaction for LocalSettingsTransaction {
             return Ok(());
         }
         for (key, value) in self.staged.iter() {
+            match value {
+                Some(value) => self
+                    .storage
+                    .set_item(&self.key_for(key), value)
+                    .map_err(|err| js_error("txn set_item", err))?,
+                None => self
+                    .storage
+                    .remove_item(&self.key_for(key))
+                    .map_err(|err| js_error("txn remove_item", err))?,
+            }
             self.storage
-                .set_item(&self.key_for(key), value)
-                .map_err(|err| js_error("txn set_item", err))?;
+                .remove_item(&scoped_key(&self.namespace, SETTINGS_EXPIRY_PREFIX, key))
+                .map_err(|err| js_error("txn remove expiry", err))?;
+        }
+        for (key, value) in self.staged.iter() {
+            self.watchers.notify(key, value.as_deref());
         }
         self.committed = true;
         Ok(())
@@ -457,4 +573,48 @@ mod tests {
         store.clear_all().await.expect("clear settings");
         assert!(store.list_keys().await.expect("list cleared").is_empty());
     }
+
+    #[wasm_bindgen_test]
+    async fn settings_store_ttl_expiry() {
+        console_error_panic_hook::set_once();
+        let ns = unique_namespace("settings-ttl");
+        let store = WasmSettingsStore::new(ns).expect("settings store init");
+
+        store
+            .set_string_with_ttl("fresh", "value", Duration::from_secs(3600))
+            .await
+            .expect("set fresh");
+        store
+            .set_string_with_ttl("stale", "value", Duration::ZERO)
+            .await
+            .expect("set stale");
+
+        assert_eq!(
+            store.get_string("fresh").await.expect("get fresh"),
+            Some("value".to_string())
+        );
+        assert_eq!(store.list_keys().await.expect("list keys"), vec!["fresh"]);
+        assert_eq!(store.purge_expired().await.expect("purge"), 1);
+        assert_eq!(store.get_string("stale").await.expect("get stale"), None);
+
+        store.clear_all().await.expect("clear settings");
+    }
+
+    #[wasm_bindgen_test]
+    async fn settings_store_watch() {
+        use futures::StreamExt;
+
+        console_error_panic_hook::set_once();
+        let ns = unique_namespace("settings-watch");
+        let store = WasmSettingsStore::new(ns).expect("settings store init");
+
+        let mut changes = store.watch("wifi_only").await.expect("watch");
+        assert_eq!(changes.next().await, Some(None));
+
+        store.set_bool("wifi_only", true).await.expect("set bool");
+        assert_eq!(changes.next().await, Some(Some("true".to_string())));
+
+        store.clear_all().await.expect("clear settings");
+        assert_eq!(changes.next().await, Some(None));
+    }
 }
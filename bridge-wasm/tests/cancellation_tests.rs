@@ -0,0 +1,81 @@
+#![cfg(target_arch = "wasm32")]
+//! Integration tests for AbortSignal-driven cancellation
+//!
+//! These tests verify that aborting a signal rejects the in-flight operation
+//! with a `Cancelled` error and that the operation stops doing work.
+//!
+use bridge_wasm::{run_cancellable, token_from_abort_signal};
+use core_async::time::{sleep, Duration};
+use js_sys::Reflect;
+use std::cell::Cell;
+use std::rc::Rc;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::*;
+use web_sys::AbortController;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn kind(err: &JsValue) -> Option<String> {
+    Reflect::get(err, &JsValue::from_str("kind"))
+        .ok()
+        .and_then(|kind| kind.as_string())
+}
+
+/// Aborting mid-flight rejects with `Cancelled` and skips the remaining work
+#[wasm_bindgen_test]
+async fn test_abort_rejects_and_stops_work() {
+    let controller = AbortController::new().unwrap();
+    let token = token_from_abort_signal(&controller.signal());
+    let steps = Rc::new(Cell::new(0));
+
+    let worker_steps = steps.clone();
+    let operation = run_cancellable(Some(token), async move {
+        for _ in 0..10 {
+            sleep(Duration::from_millis(20)).await;
+            worker_steps.set(worker_steps.get() + 1);
+        }
+        Ok(JsValue::NULL)
+    });
+
+    let abort = async {
+        sleep(Duration::from_millis(50)).await;
+        controller.abort();
+    };
+
+    let (result, ()) = futures::join!(operation, abort);
+    let err = result.unwrap_err();
+    assert_eq!(kind(&err).as_deref(), Some("Cancelled"));
+
+    // No further steps run once the operation has been dropped
+    let steps_at_abort = steps.get();
+    assert!(steps_at_abort < 10);
+    sleep(Duration::from_millis(100)).await;
+    assert_eq!(steps.get(), steps_at_abort);
+}
+
+/// A signal that is already aborted never starts the operation
+#[wasm_bindgen_test]
+async fn test_already_aborted_signal() {
+    let controller = AbortController::new().unwrap();
+    controller.abort();
+    let token = token_from_abort_signal(&controller.signal());
+    assert!(token.is_cancelled());
+
+    let started = Rc::new(Cell::new(false));
+    let flag = started.clone();
+    let result = run_cancellable(Some(token), async move {
+        flag.set(true);
+        Ok(JsValue::NULL)
+    })
+    .await;
+
+    assert_eq!(kind(&result.unwrap_err()).as_deref(), Some("Cancelled"));
+    assert!(!started.get());
+}
+
+/// Without a token the operation runs to completion
+#[wasm_bindgen_test]
+async fn test_no_token_runs_to_completion() {
+    let result = run_cancellable(None, async { Ok(JsValue::from_f64(42.0)) }).await;
+    assert_eq!(result.unwrap().as_f64(), Some(42.0));
+}
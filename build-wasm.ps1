@@ -86,7 +86,27 @@ Get-ChildItem pkg/ | ForEach-Object {
     Write-Host "  $($_.Name) - $size"
 }
 
-Write-Host "`nTypeScript definitions: pkg/$($Crate -replace '-', '_').d.ts" -ForegroundColor Green
+$dtsFile = "pkg/$($Crate -replace '-', '_').d.ts"
+Write-Host "`nTypeScript definitions: $dtsFile" -ForegroundColor Green
+
+# Verify the typed DTO definitions made it into the generated .d.ts
+if ($Crate -eq "core-library") {
+    $expected = @(
+        "export interface TrackDetails",
+        "export interface SearchResults",
+        "export interface Page<T>",
+        "Promise<TrackDetails>"
+    )
+    $dts = Get-Content $dtsFile -Raw
+    $missing = $expected | Where-Object { -not $dts.Contains($_) }
+    if ($missing) {
+        Write-Host "Error: $dtsFile is missing expected definitions:" -ForegroundColor Red
+        $missing | ForEach-Object { Write-Host "  $_" -ForegroundColor Red }
+        Set-Location $workspaceRoot
+        exit 1
+    }
+    Write-Host "TypeScript DTO definitions verified" -ForegroundColor Green
+}
 Write-Host "Ready to use in your web application!" -ForegroundColor Green
 
 # Return to workspace root
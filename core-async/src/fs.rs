@@ -5,10 +5,18 @@
 //! IndexedDB for storage. The APIs are intentionally kept similar so downstream
 //! crates can rely on a familiar surface area without depending on Tokio directly.
 //!
+//! # Copying With Progress
+//!
+//! [`copy_with_progress`] copies a file in chunks of [`COPY_CHUNK_SIZE`] bytes and
+//! reports a [`CopyProgress`] after each chunk, for large cache files where a
+//! plain `copy` gives no feedback. On WASM the copy is a single IndexedDB write
+//! and progress is reported once it completes.
+//!
 //! # WASM Limitations
 //!
-//! On WASM, the following operations are not supported:
-//! - `copy`, `rename` - Use read + write + delete pattern instead
+//! On WASM, the following operations are limited or not supported:
+//! - `copy`, `rename` - Emulated with read + write (+ delete); IndexedDB stores
+//!   each file as one value, so the whole file is held in memory
 //! - `hard_link`, `read_link` - IndexedDB doesn't support links
 //! - File permissions - No-op on WASM
 //!
@@ -39,11 +47,83 @@ pub use tokio::fs::{
 
 #[cfg(target_arch = "wasm32")]
 pub use crate::wasm::fs::{
-    copy, create_dir, create_dir_all, hard_link, metadata, read, read_dir, read_link,
-    read_to_string, remove_dir, remove_dir_all, remove_file, rename, set_permissions,
+    copy, copy_with_progress, create_dir, create_dir_all, hard_link, metadata, read, read_dir,
+    read_link, read_to_string, remove_dir, remove_dir_all, remove_file, rename, set_permissions,
     symlink_metadata, write, BridgeFileMetadata, DirBuilder, DirEntry, File, FileMetadata,
     OpenOptions, ReadDir, WasmFileSystemOps,
 };
 
 #[cfg(target_arch = "wasm32")]
 pub use crate::wasm::fs::init_filesystem;
+
+/// Size of the chunks [`copy_with_progress`] copies between progress reports
+pub const COPY_CHUNK_SIZE: usize = 256 * 1024;
+
+/// Progress of a [`copy_with_progress`] operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CopyProgress {
+    /// Bytes written to the destination so far
+    pub bytes_copied: u64,
+    /// Size of the source file
+    pub total_bytes: u64,
+}
+
+impl CopyProgress {
+    /// Completed fraction in `0.0..=1.0` (an empty file counts as complete)
+    pub fn fraction(&self) -> f64 {
+        if self.total_bytes == 0 {
+            1.0
+        } else {
+            self.bytes_copied as f64 / self.total_bytes as f64
+        }
+    }
+
+    /// Whether every byte has been copied
+    pub fn is_complete(&self) -> bool {
+        self.bytes_copied >= self.total_bytes
+    }
+}
+
+/// Copy `from` to `to`, calling `on_progress` after each chunk is written
+///
+/// The destination is created or truncated. Progress is always reported at
+/// least once, with the final report having `bytes_copied == total_bytes`.
+/// Returns the number of bytes copied.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn copy_with_progress<P, Q, F>(from: P, to: Q, mut on_progress: F) -> std::io::Result<u64>
+where
+    P: AsRef<std::path::Path>,
+    Q: AsRef<std::path::Path>,
+    F: FnMut(CopyProgress),
+{
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let mut source = File::open(from.as_ref()).await?;
+    let total_bytes = source.metadata().await?.len();
+    let mut destination = File::create(to.as_ref()).await?;
+
+    let mut buffer = vec![0u8; COPY_CHUNK_SIZE];
+    let mut bytes_copied = 0u64;
+    loop {
+        let read = source.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        destination.write_all(&buffer[..read]).await?;
+        bytes_copied += read as u64;
+        on_progress(CopyProgress {
+            bytes_copied,
+            // The source may have grown since it was opened
+            total_bytes: total_bytes.max(bytes_copied),
+        });
+    }
+    destination.flush().await?;
+
+    if bytes_copied == 0 {
+        on_progress(CopyProgress {
+            bytes_copied: 0,
+            total_bytes: 0,
+        });
+    }
+    Ok(bytes_copied)
+}
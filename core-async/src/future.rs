@@ -0,0 +1,75 @@
+//! Future combinators that work on every target.
+//!
+//! Native code could use `tokio::select!`, but it isn't available on WASM, so
+//! code that races two futures (most often cancellation against work) uses
+//! these helpers instead. Both targets share one implementation built on
+//! `futures::future::select`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use core_async::future::race_with_cancel;
+//! use core_async::sync::CancellationToken;
+//!
+//! # async fn example() {
+//! let token = CancellationToken::new();
+//! match race_with_cancel(async { 42 }, &token).await {
+//!     Ok(value) => println!("finished: {}", value),
+//!     Err(_) => println!("cancelled"),
+//! }
+//! # }
+//! ```
+
+use crate::sync::CancellationToken;
+use std::fmt;
+use std::future::Future;
+
+pub use futures::future::Either;
+
+/// Error returned by [`race_with_cancel`] when the token fires first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "operation cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+/// Wait for whichever of two futures completes first
+///
+/// The other future is dropped. If both are ready on the same poll, `first`
+/// wins.
+pub async fn race2<A, B>(first: A, second: B) -> Either<A::Output, B::Output>
+where
+    A: Future,
+    B: Future,
+{
+    futures::pin_mut!(first);
+    futures::pin_mut!(second);
+
+    match futures::future::select(first, second).await {
+        Either::Left((output, _)) => Either::Left(output),
+        Either::Right((output, _)) => Either::Right(output),
+    }
+}
+
+/// Run `future` until it completes or `token` is cancelled
+///
+/// Cancellation takes priority: an already-cancelled token returns
+/// [`Cancelled`] without polling `future`, and the future is dropped as soon as
+/// the token fires.
+pub async fn race_with_cancel<F>(
+    future: F,
+    token: &CancellationToken,
+) -> Result<F::Output, Cancelled>
+where
+    F: Future,
+{
+    match race2(token.cancelled(), future).await {
+        Either::Left(()) => Err(Cancelled),
+        Either::Right(output) => Ok(output),
+    }
+}
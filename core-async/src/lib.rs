@@ -15,6 +15,7 @@
 //! - `task`: Task spawning and execution
 //! - `time`: Time-related operations (sleep, duration, instant)
 //! - `sync`: Synchronization primitives (Mutex, RwLock, channels)
+//! - `future`: Racing futures, e.g. cancellation against work
 //!
 //! # Examples
 //!
@@ -45,6 +46,7 @@ pub mod test_support {
 
 // Core modules
 pub mod fs;
+pub mod future;
 pub mod io;
 pub mod runtime;
 pub mod sync;
@@ -16,6 +16,10 @@
 //! - Simpler implementations since there's no thread contention
 //! - Channels use `futures::channel` implementations
 //!
+//! ## All Platforms
+//! - [`metered_channel`] is a bounded channel that reports queue depth and
+//!   backpressure through [`ChannelMetrics`]
+//!
 //! # Examples
 //!
 //! ```rust
@@ -33,6 +37,16 @@
 //! }
 //! ```
 
+// ============================================================================
+// Metered Channel (all platforms)
+// ============================================================================
+
+mod metered;
+
+pub use metered::{
+    metered_channel, ChannelMetrics, MeteredReceiver, MeteredSender, SendError, TrySendError,
+};
+
 // ============================================================================
 // Native Implementation (Tokio)
 // ============================================================================
@@ -0,0 +1,329 @@
+//! Bounded MPSC channel with queue-depth metrics.
+//!
+//! [`metered_channel`] behaves like a bounded `mpsc` channel, but both halves
+//! can report how full the queue is and how often producers had to wait for
+//! the consumer. This makes producer/consumer imbalance visible, e.g. a
+//! streaming decoder that keeps filling the buffer faster than playback drains
+//! it.
+//!
+//! The implementation is shared by native and WASM targets: it only relies on
+//! `std` synchronization and wakers, so it is `Send + Sync` on native (for
+//! `T: Send`) and works unchanged on the single-threaded WASM executor.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use core_async::sync::metered_channel;
+//!
+//! # async fn example() {
+//! let (tx, mut rx) = metered_channel(8);
+//! tx.send(1).await.unwrap();
+//! assert_eq!(tx.metrics().len, 1);
+//! assert_eq!(rx.recv().await, Some(1));
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::poll_fn;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Poll, Waker};
+
+/// Create a bounded channel holding at most `capacity` queued messages
+///
+/// # Panics
+///
+/// Panics if `capacity` is zero.
+pub fn metered_channel<T>(capacity: usize) -> (MeteredSender<T>, MeteredReceiver<T>) {
+    assert!(capacity > 0, "metered channel requires capacity > 0");
+
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            queue: VecDeque::with_capacity(capacity),
+            senders: 1,
+            receiver_alive: true,
+            recv_waker: None,
+            send_wakers: Vec::new(),
+        }),
+        capacity,
+        high_watermark: AtomicUsize::new(0),
+        send_blocked_count: AtomicU64::new(0),
+    });
+
+    (
+        MeteredSender {
+            shared: Arc::clone(&shared),
+        },
+        MeteredReceiver { shared },
+    )
+}
+
+/// Snapshot of a metered channel's queue state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChannelMetrics {
+    /// Messages currently queued
+    pub len: usize,
+    /// Maximum number of queued messages
+    pub capacity: usize,
+    /// Highest `len` observed since the channel was created
+    pub high_watermark: usize,
+    /// Number of sends that found the queue full and had to wait
+    pub send_blocked_count: u64,
+}
+
+/// Error returned when sending on a channel whose receiver was dropped
+///
+/// Contains the value that could not be sent.
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub struct SendError<T>(pub T);
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SendError").finish_non_exhaustive()
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "channel closed")
+    }
+}
+
+impl<T> std::error::Error for SendError<T> {}
+
+/// Error returned by [`MeteredSender::try_send`]
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum TrySendError<T> {
+    /// The queue is at capacity
+    Full(T),
+    /// The receiver was dropped
+    Closed(T),
+}
+
+impl<T> fmt::Debug for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrySendError::Full(_) => f.write_str("Full(..)"),
+            TrySendError::Closed(_) => f.write_str("Closed(..)"),
+        }
+    }
+}
+
+impl<T> fmt::Display for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrySendError::Full(_) => write!(f, "channel full"),
+            TrySendError::Closed(_) => write!(f, "channel closed"),
+        }
+    }
+}
+
+impl<T> std::error::Error for TrySendError<T> {}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    capacity: usize,
+    high_watermark: AtomicUsize,
+    send_blocked_count: AtomicU64,
+}
+
+struct State<T> {
+    queue: VecDeque<T>,
+    senders: usize,
+    receiver_alive: bool,
+    recv_waker: Option<Waker>,
+    send_wakers: Vec<Waker>,
+}
+
+impl<T> Shared<T> {
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        // A panic while holding the lock cannot leave the queue inconsistent
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn metrics(&self) -> ChannelMetrics {
+        ChannelMetrics {
+            len: self.lock().queue.len(),
+            capacity: self.capacity,
+            high_watermark: self.high_watermark.load(Ordering::Relaxed),
+            send_blocked_count: self.send_blocked_count.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Enqueue `value` (the caller checked capacity) and wake the receiver
+    fn push(&self, state: &mut State<T>, value: T) {
+        state.queue.push_back(value);
+        self.high_watermark
+            .fetch_max(state.queue.len(), Ordering::Relaxed);
+        if let Some(waker) = state.recv_waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+fn wake_all(wakers: &mut Vec<Waker>) {
+    for waker in wakers.drain(..) {
+        waker.wake();
+    }
+}
+
+/// Sending half of a [`metered_channel`]
+pub struct MeteredSender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> MeteredSender<T> {
+    /// Send a value, waiting while the queue is full
+    ///
+    /// A send that has to wait is counted once in
+    /// [`ChannelMetrics::send_blocked_count`], however long it waits.
+    pub async fn send(&self, value: T) -> Result<(), SendError<T>> {
+        let mut value = Some(value);
+        let mut blocked = false;
+
+        poll_fn(|cx| {
+            let mut state = self.shared.lock();
+            if !state.receiver_alive {
+                return Poll::Ready(Err(SendError(
+                    value.take().expect("polled after completion"),
+                )));
+            }
+            if state.queue.len() < self.shared.capacity {
+                let value = value.take().expect("polled after completion");
+                self.shared.push(&mut state, value);
+                return Poll::Ready(Ok(()));
+            }
+
+            if !blocked {
+                blocked = true;
+                self.shared
+                    .send_blocked_count
+                    .fetch_add(1, Ordering::Relaxed);
+            }
+            state.send_wakers.push(cx.waker().clone());
+            Poll::Pending
+        })
+        .await
+    }
+
+    /// Send a value without waiting
+    ///
+    /// A full queue is reported as [`TrySendError::Full`] and is not counted as
+    /// a blocked send.
+    pub fn try_send(&self, value: T) -> Result<(), TrySendError<T>> {
+        let mut state = self.shared.lock();
+        if !state.receiver_alive {
+            return Err(TrySendError::Closed(value));
+        }
+        if state.queue.len() >= self.shared.capacity {
+            return Err(TrySendError::Full(value));
+        }
+        self.shared.push(&mut state, value);
+        Ok(())
+    }
+
+    /// Current queue metrics
+    pub fn metrics(&self) -> ChannelMetrics {
+        self.shared.metrics()
+    }
+
+    /// Whether the receiver has been dropped
+    pub fn is_closed(&self) -> bool {
+        !self.shared.lock().receiver_alive
+    }
+}
+
+impl<T> Clone for MeteredSender<T> {
+    fn clone(&self) -> Self {
+        self.shared.lock().senders += 1;
+        Self {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<T> Drop for MeteredSender<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.senders -= 1;
+        if state.senders == 0 {
+            if let Some(waker) = state.recv_waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl<T> fmt::Debug for MeteredSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MeteredSender")
+            .field("metrics", &self.metrics())
+            .finish()
+    }
+}
+
+/// Receiving half of a [`metered_channel`]
+pub struct MeteredReceiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> MeteredReceiver<T> {
+    /// Receive the next value
+    ///
+    /// Returns `None` once every sender has been dropped and the queue is empty.
+    pub async fn recv(&mut self) -> Option<T> {
+        poll_fn(|cx| {
+            let mut state = self.shared.lock();
+            if let Some(value) = state.queue.pop_front() {
+                // Wake every waiting sender: one of them may have been dropped
+                // after registering, so waking a single one could strand the rest.
+                wake_all(&mut state.send_wakers);
+                return Poll::Ready(Some(value));
+            }
+            if state.senders == 0 {
+                return Poll::Ready(None);
+            }
+            state.recv_waker = Some(cx.waker().clone());
+            Poll::Pending
+        })
+        .await
+    }
+
+    /// Receive a value if one is queued, without waiting
+    pub fn try_recv(&mut self) -> Option<T> {
+        let mut state = self.shared.lock();
+        let value = state.queue.pop_front();
+        if value.is_some() {
+            wake_all(&mut state.send_wakers);
+        }
+        value
+    }
+
+    /// Current queue metrics
+    pub fn metrics(&self) -> ChannelMetrics {
+        self.shared.metrics()
+    }
+}
+
+impl<T> Drop for MeteredReceiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.receiver_alive = false;
+        wake_all(&mut state.send_wakers);
+        // Queued values are dropped outside the lock
+        let queued = std::mem::take(&mut state.queue);
+        drop(state);
+        drop(queued);
+    }
+}
+
+impl<T> fmt::Debug for MeteredReceiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MeteredReceiver")
+            .field("metrics", &self.metrics())
+            .finish()
+    }
+}
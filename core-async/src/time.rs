@@ -30,6 +30,12 @@
 //!     println!("Took {:?}", elapsed);
 //! }
 //! ```
+//!
+//! # Timeouts and Retries
+//!
+//! [`with_timeout_or`] and [`retry_with_backoff`] wrap the usual
+//! timeout/retry glue so call sites don't reimplement it. Both use this
+//! module's `sleep` and `timeout`, so they behave the same on every target.
 
 // ============================================================================
 // Native Implementation (Tokio)
@@ -333,3 +339,163 @@ pub fn now_secs() -> u64 {
         .expect("system time before UNIX epoch")
         .as_secs()
 }
+
+/// Await `future`, returning `fallback` if it doesn't finish within `duration`
+///
+/// The future is dropped when the timeout expires.
+///
+/// # Examples
+///
+/// ```rust
+/// use core_async::time::{sleep, with_timeout_or, Duration};
+///
+/// # async fn example() {
+/// let value = with_timeout_or(
+///     async {
+///         sleep(Duration::from_secs(10)).await;
+///         1
+///     },
+///     Duration::from_millis(10),
+///     0,
+/// )
+/// .await;
+/// assert_eq!(value, 0);
+/// # }
+/// ```
+pub async fn with_timeout_or<F>(future: F, duration: Duration, fallback: F::Output) -> F::Output
+where
+    F: std::future::Future,
+{
+    timeout(duration, future).await.unwrap_or(fallback)
+}
+
+/// Backoff settings for [`retry_with_backoff`]
+#[derive(Debug, Clone)]
+pub struct RetryConfig {
+    /// Total number of attempts, including the first one (at least 1)
+    pub max_attempts: u32,
+    /// Delay before the first retry
+    pub initial_delay: Duration,
+    /// Upper bound for any single delay
+    pub max_delay: Duration,
+    /// Factor applied to the delay after each retry
+    pub multiplier: f64,
+    /// Stops retrying once cancelled
+    pub cancellation: Option<crate::sync::CancellationToken>,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(30),
+            multiplier: 2.0,
+            cancellation: None,
+        }
+    }
+}
+
+impl RetryConfig {
+    /// Create a config with `max_attempts` attempts and default delays
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            ..Self::default()
+        }
+    }
+
+    /// Set the delay before the first retry
+    pub fn with_initial_delay(mut self, delay: Duration) -> Self {
+        self.initial_delay = delay;
+        self
+    }
+
+    /// Set the upper bound for a single delay
+    pub fn with_max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Set the factor applied to the delay after each retry
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Stop retrying when `token` is cancelled
+    pub fn with_cancellation(mut self, token: crate::sync::CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Delay before retry number `retry` (0 for the first retry)
+    pub fn delay_for(&self, retry: u32) -> Duration {
+        let factor = self
+            .multiplier
+            .max(1.0)
+            .powi(retry.min(i32::MAX as u32) as i32);
+        let delay = self.initial_delay.as_secs_f64() * factor;
+        if delay.is_finite() && delay < self.max_delay.as_secs_f64() {
+            Duration::from_secs_f64(delay)
+        } else {
+            self.max_delay
+        }
+    }
+}
+
+/// Run `op` until it succeeds, sleeping with exponential backoff between attempts
+///
+/// Returns the first success, or the error of the last attempt once
+/// `config.max_attempts` attempts have failed. If `config.cancellation` is
+/// cancelled, no further attempt is started and the latest error is returned
+/// immediately, including while waiting out a backoff delay. The first attempt
+/// always runs. Dropping the returned future also stops the retries.
+///
+/// # Examples
+///
+/// ```rust
+/// use core_async::time::{retry_with_backoff, Duration, RetryConfig};
+///
+/// # async fn example() -> Result<(), String> {
+/// let config = RetryConfig::new(5).with_initial_delay(Duration::from_millis(50));
+/// let body = retry_with_backoff(|| async { Ok::<_, String>("ok") }, config).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn retry_with_backoff<F, Fut, T, E>(mut op: F, config: RetryConfig) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, E>>,
+{
+    let max_attempts = config.max_attempts.max(1);
+    let mut attempt = 1;
+    loop {
+        let error = match op().await {
+            Ok(value) => return Ok(value),
+            Err(error) => error,
+        };
+        if attempt >= max_attempts {
+            return Err(error);
+        }
+
+        let delay = sleep(config.delay_for(attempt - 1));
+        match &config.cancellation {
+            Some(token) => {
+                if token.is_cancelled() {
+                    return Err(error);
+                }
+                let cancelled = token.cancelled();
+                futures::pin_mut!(delay);
+                futures::pin_mut!(cancelled);
+                if let futures::future::Either::Right(_) =
+                    futures::future::select(delay, cancelled).await
+                {
+                    return Err(error);
+                }
+            }
+            None => delay.await,
+        }
+        attempt += 1;
+    }
+}
@@ -183,6 +183,14 @@ impl Default for CancellationToken {
     }
 }
 
+impl std::fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("is_cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
 /// Future returned by `CancellationToken::cancelled()`.
 struct CancelledFuture {
     token: CancellationToken,
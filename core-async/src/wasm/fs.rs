@@ -41,6 +41,7 @@ use std::{
 };
 
 // Re-export for convenience
+pub use crate::fs::CopyProgress;
 pub use std::fs::Permissions;
 
 /// File metadata returned by the filesystem
@@ -454,20 +455,53 @@ pub async fn remove_dir<P: AsRef<Path>>(path: P) -> io::Result<()> {
     remove_dir_all(path).await
 }
 
-/// Stub for copy - not supported on WASM
-pub async fn copy<P: AsRef<Path>, Q: AsRef<Path>>(_from: P, _to: Q) -> io::Result<u64> {
-    Err(io::Error::new(
-        io::ErrorKind::Unsupported,
-        "copy is not supported on WASM. Use read + write instead.",
-    ))
+/// Copy the contents of one file to another, returning the number of bytes copied
+///
+/// The whole file is read into memory, since IndexedDB stores it as one value.
+pub async fn copy<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> io::Result<u64> {
+    copy_with_progress(from, to, |_| {}).await
 }
 
-/// Stub for rename - not supported on WASM  
-pub async fn rename<P: AsRef<Path>, Q: AsRef<Path>>(_from: P, _to: Q) -> io::Result<()> {
-    Err(io::Error::new(
-        io::ErrorKind::Unsupported,
-        "rename is not supported on WASM. Use read + write + delete instead.",
-    ))
+/// Copy `from` to `to`, calling `on_progress` once the data is written
+///
+/// IndexedDB has no partial reads or writes, so the file is copied in a single
+/// operation and progress is reported once, with `bytes_copied == total_bytes`.
+pub async fn copy_with_progress<P, Q, F>(from: P, to: Q, mut on_progress: F) -> io::Result<u64>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+    F: FnMut(CopyProgress),
+{
+    let fs = get_filesystem()?;
+    let data = fs
+        .read_file(from.as_ref())
+        .await
+        .map_err(string_error_to_io)?;
+    let total_bytes = data.len() as u64;
+
+    fs.write_file(to.as_ref(), data)
+        .await
+        .map_err(string_error_to_io)?;
+
+    on_progress(CopyProgress {
+        bytes_copied: total_bytes,
+        total_bytes,
+    });
+    Ok(total_bytes)
+}
+
+/// Rename a file, replacing the destination if it exists
+///
+/// Emulated as copy + delete. Unlike a native rename this is not atomic: if
+/// the delete fails, both paths hold the data.
+pub async fn rename<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> io::Result<()> {
+    let from = from.as_ref();
+    let to = to.as_ref();
+    if from == to {
+        return Ok(());
+    }
+    copy(from, to).await?;
+    remove_file(from).await
 }
 
 /// Stub for hard_link - not supported on WASM
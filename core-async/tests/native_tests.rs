@@ -4,7 +4,7 @@
 
 #![cfg(not(target_arch = "wasm32"))]
 
-use core_async::{sync, task, time};
+use core_async::{fs, future, sync, task, time};
 use std::sync::Arc;
 
 #[core_async::test]
@@ -263,3 +263,182 @@ async fn test_watch_channel() {
 
     assert_eq!(last_value, 5);
 }
+
+#[core_async::test]
+async fn test_copy_with_progress() {
+    let dir = std::env::temp_dir().join(format!("core-async-copy-{}", std::process::id()));
+    fs::create_dir_all(&dir).await.unwrap();
+    let src = dir.join("source.bin");
+    let dst = dir.join("copy.bin");
+
+    // Several chunks plus a partial one
+    let data: Vec<u8> = (0..fs::COPY_CHUNK_SIZE * 3 + 1234)
+        .map(|i| (i % 251) as u8)
+        .collect();
+    fs::write(&src, &data).await.unwrap();
+
+    let mut reports = Vec::new();
+    let copied = fs::copy_with_progress(&src, &dst, |progress| reports.push(progress))
+        .await
+        .unwrap();
+
+    assert_eq!(copied, data.len() as u64);
+    assert_eq!(reports.len(), 4);
+    assert!(reports
+        .windows(2)
+        .all(|pair| pair[0].bytes_copied < pair[1].bytes_copied));
+    let last = reports.last().unwrap();
+    assert!(last.is_complete());
+    assert_eq!(last.fraction(), 1.0);
+    assert_eq!(last.total_bytes, data.len() as u64);
+    assert_eq!(fs::read(&dst).await.unwrap(), data);
+
+    let moved = dir.join("moved.bin");
+    fs::rename(&dst, &moved).await.unwrap();
+    assert!(fs::metadata(&dst).await.is_err());
+    assert_eq!(fs::read(&moved).await.unwrap(), data);
+
+    fs::remove_dir_all(&dir).await.unwrap();
+}
+
+#[core_async::test]
+async fn test_metered_channel_tracks_backpressure() {
+    use futures::FutureExt;
+
+    let (tx, mut rx) = sync::metered_channel(4);
+    for i in 0..4 {
+        tx.send(i).await.unwrap();
+    }
+    let metrics = tx.metrics();
+    assert_eq!(metrics.len, 4);
+    assert_eq!(metrics.high_watermark, 4);
+    assert_eq!(metrics.send_blocked_count, 0);
+
+    // The fifth send waits for room and is counted once
+    let mut blocked = Box::pin(tx.send(4));
+    assert!((&mut blocked).now_or_never().is_none());
+    assert!((&mut blocked).now_or_never().is_none());
+    assert_eq!(rx.metrics().send_blocked_count, 1);
+    assert!(matches!(tx.try_send(5), Err(sync::TrySendError::Full(5))));
+
+    assert_eq!(rx.recv().await, Some(0));
+    blocked.await.unwrap();
+
+    let metrics = rx.metrics();
+    assert_eq!(metrics.len, 4);
+    assert_eq!(metrics.high_watermark, metrics.capacity);
+    assert_eq!(metrics.send_blocked_count, 1);
+
+    drop(tx);
+    let mut received = Vec::new();
+    while let Some(value) = rx.recv().await {
+        received.push(value);
+    }
+    assert_eq!(received, vec![1, 2, 3, 4]);
+    assert_eq!(rx.metrics().len, 0);
+}
+
+#[core_async::test]
+async fn test_with_timeout_or_returns_fallback() {
+    let slow = time::with_timeout_or(
+        async {
+            time::sleep(time::Duration::from_millis(200)).await;
+            "done"
+        },
+        time::Duration::from_millis(20),
+        "fallback",
+    )
+    .await;
+    assert_eq!(slow, "fallback");
+
+    let fast = time::with_timeout_or(async { "done" }, time::Duration::from_millis(20), "fallback")
+        .await;
+    assert_eq!(fast, "done");
+}
+
+#[core_async::test]
+async fn test_retry_with_backoff() {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    let config = time::RetryConfig::new(4).with_initial_delay(time::Duration::from_millis(5));
+
+    // Fails twice, then succeeds on the third attempt
+    let calls = AtomicU32::new(0);
+    let result = time::retry_with_backoff(
+        || async {
+            let attempt = calls.fetch_add(1, Ordering::SeqCst) + 1;
+            if attempt < 3 {
+                Err(format!("attempt {attempt} failed"))
+            } else {
+                Ok(attempt)
+            }
+        },
+        config.clone(),
+    )
+    .await;
+    assert_eq!(result, Ok(3));
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+    // Always failing gives up after max_attempts with the last error
+    let calls = AtomicU32::new(0);
+    let result: Result<(), String> = time::retry_with_backoff(
+        || async { Err(format!("attempt {}", calls.fetch_add(1, Ordering::SeqCst) + 1)) },
+        config.clone(),
+    )
+    .await;
+    assert_eq!(result, Err("attempt 4".to_string()));
+
+    // Cancellation stops retrying during the backoff delay
+    let token = sync::CancellationToken::new();
+    let cancel = token.clone();
+    task::spawn(async move {
+        time::sleep(time::Duration::from_millis(20)).await;
+        cancel.cancel();
+    });
+    let calls = AtomicU32::new(0);
+    let start = time::Instant::now();
+    let result: Result<(), u32> = time::retry_with_backoff(
+        || async { Err(calls.fetch_add(1, Ordering::SeqCst) + 1) },
+        time::RetryConfig::new(10)
+            .with_initial_delay(time::Duration::from_secs(5))
+            .with_cancellation(token),
+    )
+    .await;
+    assert_eq!(result, Err(1));
+    assert!(start.elapsed() < time::Duration::from_secs(2));
+
+    assert_eq!(config.delay_for(0), time::Duration::from_millis(5));
+    assert_eq!(config.delay_for(2), time::Duration::from_millis(20));
+    assert_eq!(config.delay_for(100), config.max_delay);
+}
+
+#[core_async::test]
+async fn test_race_with_cancel() {
+    let token = sync::CancellationToken::new();
+    let cancel = token.clone();
+    task::spawn(async move {
+        time::sleep(time::Duration::from_millis(10)).await;
+        cancel.cancel();
+    });
+
+    let result = future::race_with_cancel(
+        async {
+            time::sleep(time::Duration::from_secs(5)).await;
+            "work"
+        },
+        &token,
+    )
+    .await;
+    assert_eq!(result, Err(future::Cancelled));
+
+    // Work that finishes first wins
+    let fresh = sync::CancellationToken::new();
+    assert_eq!(future::race_with_cancel(async { 7 }, &fresh).await, Ok(7));
+
+    let first = future::race2(
+        time::sleep(time::Duration::from_millis(5)),
+        time::sleep(time::Duration::from_secs(5)),
+    )
+    .await;
+    assert!(matches!(first, future::Either::Left(())));
+}
@@ -490,3 +490,70 @@ async fn test_broadcast_receiver_count() {
     drop(_rx2);
     assert_eq!(tx.receiver_count(), 2);
 }
+
+#[wasm_bindgen_test]
+async fn test_metered_channel_tracks_backpressure() {
+    use futures::FutureExt;
+
+    let (tx, mut rx) = sync::metered_channel(2);
+    tx.send(1).await.unwrap();
+    tx.send(2).await.unwrap();
+
+    let mut blocked = Box::pin(tx.send(3));
+    assert!((&mut blocked).now_or_never().is_none());
+    assert_eq!(tx.metrics().send_blocked_count, 1);
+    assert_eq!(tx.metrics().high_watermark, 2);
+
+    assert_eq!(rx.recv().await, Some(1));
+    blocked.await.unwrap();
+    assert_eq!(rx.metrics().len, 2);
+}
+
+#[wasm_bindgen_test]
+async fn test_timeout_fallback_and_retry() {
+    let value = time::with_timeout_or(
+        async {
+            time::sleep(time::Duration::from_millis(200)).await;
+            1
+        },
+        time::Duration::from_millis(20),
+        0,
+    )
+    .await;
+    assert_eq!(value, 0);
+
+    let calls = std::cell::Cell::new(0);
+    let result = time::retry_with_backoff(
+        || async {
+            calls.set(calls.get() + 1);
+            if calls.get() < 3 {
+                Err("flaky")
+            } else {
+                Ok(calls.get())
+            }
+        },
+        time::RetryConfig::new(3).with_initial_delay(time::Duration::from_millis(5)),
+    )
+    .await;
+    assert_eq!(result, Ok(3));
+}
+
+#[wasm_bindgen_test]
+async fn test_race_with_cancel() {
+    let token = sync::CancellationToken::new();
+    let cancel = token.clone();
+    task::spawn(async move {
+        time::sleep(time::Duration::from_millis(10)).await;
+        cancel.cancel();
+    });
+
+    let result = core_async::future::race_with_cancel(
+        async {
+            time::sleep(time::Duration::from_secs(5)).await;
+            "work"
+        },
+        &token,
+    )
+    .await;
+    assert_eq!(result, Err(core_async::future::Cancelled));
+}
@@ -54,10 +54,8 @@
 use crate::error::{AuthError, Result};
 use crate::oauth::{OAuthConfig, OAuthFlowManager, PkceVerifier};
 use crate::token_store::TokenStore;
-#[cfg(test)]
-use crate::types::OAuthTokens;
-use crate::types::{AuthState, ProfileId, ProviderKind};
-use bridge_traits::{http::HttpClient, SecureStore};
+use crate::types::{AuthState, OAuthTokens, ProfileId, ProviderKind};
+use bridge_traits::{http::HttpClient, NamespacedSecureStore, SecureStore};
 use core_async::sync::{Mutex, RwLock};
 use core_async::time::{timeout, Duration};
 use core_runtime::events::{AuthEvent, CoreEvent, EventBus};
@@ -115,8 +113,10 @@ struct SignInProgress {
 /// - Emitting auth state events
 /// - Handling concurrent operations safely
 pub struct AuthManager {
-    /// Token storage using platform-specific secure store
-    token_store: TokenStore,
+    /// Platform-specific secure store, shared by all profiles
+    ///
+    /// Each profile's tokens live in their own namespace; see `token_store`.
+    secure_store: Arc<dyn SecureStore>,
     /// Event bus for emitting auth events
     event_bus: EventBus,
     /// OAuth flow managers per provider
@@ -165,8 +165,6 @@ impl AuthManager {
         event_bus: EventBus,
         http_client: Arc<dyn HttpClient>,
     ) -> Self {
-        let token_store = TokenStore::new(secure_store);
-
         // Initialize OAuth managers for each provider
         let mut oauth_managers = HashMap::new();
         oauth_managers.insert(
@@ -179,7 +177,7 @@ impl AuthManager {
         );
 
         Self {
-            token_store,
+            secure_store,
             event_bus,
             oauth_managers,
             current_session: Arc::new(RwLock::new(None)),
@@ -453,7 +451,7 @@ impl AuthManager {
 
         // Create profile and store tokens
         let profile_id = ProfileId::new();
-        self.token_store
+        self.token_store(profile_id)
             .store_tokens(profile_id, &tokens)
             .await
             .map_err(|e| {
@@ -539,12 +537,20 @@ impl AuthManager {
     pub async fn sign_out(&self, profile_id: ProfileId) -> Result<()> {
         info!("Signing out profile");
 
-        // Delete tokens
-        self.token_store
-            .delete_tokens(profile_id)
+        // Delete everything in the profile's namespace, plus tokens saved
+        // before namespacing
+        self.profile_store(profile_id)
+            .clear_all()
             .await
             .map_err(|e| {
                 error!("Failed to delete tokens: {}", e);
+                AuthError::SecureStorageUnavailable(e.to_string())
+            })?;
+        TokenStore::new(self.secure_store.clone())
+            .delete_tokens(profile_id)
+            .await
+            .map_err(|e| {
+                error!("Failed to delete legacy tokens: {}", e);
                 e
             })?;
 
@@ -635,14 +641,10 @@ impl AuthManager {
         let _guard = refresh_lock.lock().await;
 
         // Retrieve current tokens
-        let tokens = self
-            .token_store
-            .retrieve_tokens(profile_id)
-            .await?
-            .ok_or_else(|| {
-                warn!("No tokens found for profile");
-                AuthError::ProfileNotFound(profile_id.to_string())
-            })?;
+        let tokens = self.load_tokens(profile_id).await?.ok_or_else(|| {
+            warn!("No tokens found for profile");
+            AuthError::ProfileNotFound(profile_id.to_string())
+        })?;
 
         // Check if token needs refresh (using is_expired_with_buffer from OAuthTokens)
         let needs_refresh = tokens.is_expired_with_buffer(TOKEN_REFRESH_BUFFER.as_secs() as i64);
@@ -717,7 +719,7 @@ impl AuthManager {
         };
 
         // Store refreshed tokens
-        self.token_store
+        self.token_store(profile_id)
             .store_tokens(profile_id, &new_tokens)
             .await?;
 
@@ -784,6 +786,41 @@ impl AuthManager {
         in_progress.remove(&provider).is_some()
     }
 
+    /// Secure store scoped to a single profile.
+    ///
+    /// Profiles share the injected store, so every profile gets its own
+    /// namespace; clearing one profile can never touch another's secrets.
+    fn profile_store(&self, profile_id: ProfileId) -> Arc<dyn SecureStore> {
+        Arc::new(NamespacedSecureStore::new(
+            self.secure_store.clone(),
+            format!("profile:{}", profile_id),
+        ))
+    }
+
+    /// Token store for a single profile.
+    fn token_store(&self, profile_id: ProfileId) -> TokenStore {
+        TokenStore::new(self.profile_store(profile_id))
+    }
+
+    /// Load a profile's tokens, moving them into its namespace if they were
+    /// saved before profiles were namespaced.
+    async fn load_tokens(&self, profile_id: ProfileId) -> Result<Option<OAuthTokens>> {
+        let token_store = self.token_store(profile_id);
+        if let Some(tokens) = token_store.retrieve_tokens(profile_id).await? {
+            return Ok(Some(tokens));
+        }
+
+        let legacy_store = TokenStore::new(self.secure_store.clone());
+        let Some(tokens) = legacy_store.retrieve_tokens(profile_id).await? else {
+            return Ok(None);
+        };
+
+        info!("Migrating tokens into profile namespace");
+        token_store.store_tokens(profile_id, &tokens).await?;
+        legacy_store.delete_tokens(profile_id).await?;
+        Ok(Some(tokens))
+    }
+
     /// Google Drive OAuth configuration.
     fn google_drive_config() -> OAuthConfig {
         OAuthConfig {
@@ -892,7 +929,7 @@ mod tests {
     fn test_create_auth_manager() {
         let event_bus = EventBus::new(100);
         let secure_store = Arc::new(MockSecureStore::new());
-        let http_client = Arc::new(MockHttpClient::default());
+        let http_client = Arc::new(MockHttpClient);
         let manager = AuthManager::new(secure_store, event_bus, http_client);
 
         // Verify providers are initialized
@@ -908,7 +945,7 @@ mod tests {
     async fn test_list_providers() {
         let event_bus = EventBus::new(100);
         let secure_store = Arc::new(MockSecureStore::new());
-        let http_client = Arc::new(MockHttpClient::default());
+        let http_client = Arc::new(MockHttpClient);
         let manager = AuthManager::new(secure_store, event_bus, http_client);
 
         let providers = manager.list_providers();
@@ -933,7 +970,7 @@ mod tests {
     async fn test_sign_in_initiates_flow() {
         let event_bus = EventBus::new(100);
         let secure_store = Arc::new(MockSecureStore::new());
-        let http_client = Arc::new(MockHttpClient::default());
+        let http_client = Arc::new(MockHttpClient);
         let manager = AuthManager::new(secure_store, event_bus.clone(), http_client);
 
         // Subscribe to events
@@ -962,7 +999,7 @@ mod tests {
     async fn test_concurrent_sign_in_prevented() {
         let event_bus = EventBus::new(100);
         let secure_store = Arc::new(MockSecureStore::new());
-        let http_client = Arc::new(MockHttpClient::default());
+        let http_client = Arc::new(MockHttpClient);
         let manager = AuthManager::new(secure_store, event_bus, http_client.clone());
 
         // First sign-in should succeed
@@ -982,7 +1019,7 @@ mod tests {
     async fn test_cancel_sign_in() {
         let event_bus = EventBus::new(100);
         let secure_store = Arc::new(MockSecureStore::new());
-        let http_client = Arc::new(MockHttpClient::default());
+        let http_client = Arc::new(MockHttpClient);
         let manager = AuthManager::new(secure_store, event_bus, http_client);
 
         // Initiate sign-in
@@ -1006,7 +1043,7 @@ mod tests {
     async fn test_sign_out() {
         let event_bus = EventBus::new(100);
         let secure_store = Arc::new(MockSecureStore::new());
-        let http_client = Arc::new(MockHttpClient::default());
+        let http_client = Arc::new(MockHttpClient);
         let manager = AuthManager::new(secure_store, event_bus.clone(), http_client);
 
         let profile_id = ProfileId::new();
@@ -1018,7 +1055,7 @@ mod tests {
             3600,
         );
         manager
-            .token_store
+            .token_store(profile_id)
             .store_tokens(profile_id, &tokens)
             .await
             .unwrap();
@@ -1032,7 +1069,7 @@ mod tests {
 
         // Verify tokens were deleted
         let retrieved = manager
-            .token_store
+            .token_store(profile_id)
             .retrieve_tokens(profile_id)
             .await
             .unwrap();
@@ -1052,7 +1089,7 @@ mod tests {
     async fn test_current_session_none_initially() {
         let event_bus = EventBus::new(100);
         let secure_store = Arc::new(MockSecureStore::new());
-        let http_client = Arc::new(MockHttpClient::default());
+        let http_client = Arc::new(MockHttpClient);
         let manager = AuthManager::new(secure_store, event_bus, http_client);
 
         let session = manager.current_session().await;
@@ -1063,7 +1100,7 @@ mod tests {
     async fn test_get_valid_token_no_profile() {
         let event_bus = EventBus::new(100);
         let secure_store = Arc::new(MockSecureStore::new());
-        let http_client = Arc::new(MockHttpClient::default());
+        let http_client = Arc::new(MockHttpClient);
         let manager = AuthManager::new(secure_store, event_bus, http_client);
 
         let profile_id = ProfileId::new();
@@ -1080,7 +1117,7 @@ mod tests {
     async fn test_provider_info_completeness() {
         let event_bus = EventBus::new(100);
         let secure_store = Arc::new(MockSecureStore::new());
-        let http_client = Arc::new(MockHttpClient::default());
+        let http_client = Arc::new(MockHttpClient);
         let manager = AuthManager::new(secure_store, event_bus, http_client);
 
         let providers = manager.list_providers();
@@ -1094,4 +1131,53 @@ mod tests {
             assert!(provider.token_url.starts_with("https://"));
         }
     }
+
+    #[core_async::test]
+    async fn test_sign_out_keeps_other_profiles() {
+        let event_bus = EventBus::new(100);
+        let secure_store = Arc::new(MockSecureStore::new());
+        let http_client = Arc::new(MockHttpClient);
+        let manager = AuthManager::new(secure_store.clone(), event_bus, http_client);
+
+        let tokens = OAuthTokens::new("token".to_string(), None, 3600);
+        let google = ProfileId::new();
+        let onedrive = ProfileId::new();
+        for profile_id in [google, onedrive] {
+            manager
+                .token_store(profile_id)
+                .store_tokens(profile_id, &tokens)
+                .await
+                .unwrap();
+        }
+
+        manager.sign_out(google).await.unwrap();
+
+        assert!(manager.load_tokens(google).await.unwrap().is_none());
+        assert!(manager.load_tokens(onedrive).await.unwrap().is_some());
+        assert_eq!(secure_store.list_keys().await.unwrap().len(), 1);
+    }
+
+    #[core_async::test]
+    async fn test_legacy_tokens_migrate_into_namespace() {
+        let event_bus = EventBus::new(100);
+        let secure_store = Arc::new(MockSecureStore::new());
+        let http_client = Arc::new(MockHttpClient);
+        let manager = AuthManager::new(secure_store.clone(), event_bus, http_client);
+
+        let profile_id = ProfileId::new();
+        let tokens = OAuthTokens::new("legacy".to_string(), None, 3600);
+        TokenStore::new(secure_store.clone())
+            .store_tokens(profile_id, &tokens)
+            .await
+            .unwrap();
+
+        let token = manager.get_valid_token(profile_id).await.unwrap();
+        assert_eq!(token, "legacy");
+
+        let keys = secure_store.list_keys().await.unwrap();
+        assert_eq!(
+            keys,
+            vec![format!("profile:{0}/oauth_tokens:{0}", profile_id)]
+        );
+    }
 }
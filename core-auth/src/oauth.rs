@@ -648,7 +648,7 @@ mod tests {
             token_url: "https://provider.com/token".to_string(),
         };
 
-        let manager = OAuthFlowManager::new(config, Arc::new(StubHttpClient::default()));
+        let manager = OAuthFlowManager::new(config, Arc::new(StubHttpClient));
         let result = manager.build_auth_url();
 
         assert!(result.is_ok());
@@ -678,7 +678,7 @@ mod tests {
             token_url: "https://provider.com/token".to_string(),
         };
 
-        let manager = OAuthFlowManager::new(config, Arc::new(StubHttpClient::default()));
+        let manager = OAuthFlowManager::new(config, Arc::new(StubHttpClient));
         let result = manager.build_auth_url();
 
         assert!(result.is_err());
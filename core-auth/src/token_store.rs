@@ -101,6 +101,10 @@ impl TokenStore {
     /// platform-specific secure store. If tokens already exist for
     /// this profile, they are securely overwritten.
     ///
+    /// The access token, refresh token and expiry share a single secret, so
+    /// a save replaces all three or none and costs one keyring round-trip,
+    /// without relying on the store's batch operations being atomic.
+    ///
     /// # Arguments
     ///
     /// * `profile_id` - The profile identifier
@@ -519,6 +523,29 @@ mod tests {
         assert_eq!(retrieved.refresh_token(), tokens.refresh_token());
     }
 
+    #[core_async::test]
+    async fn test_tokens_stored_as_single_secret() {
+        let secure_store = Arc::new(MockSecureStore::new());
+        let token_store = TokenStore::new(secure_store.clone());
+        let profile_id = ProfileId::new();
+
+        let tokens = OAuthTokens::new(
+            "access_token".to_string(),
+            Some("refresh_token".to_string()),
+            3600,
+        );
+        token_store.store_tokens(profile_id, &tokens).await.unwrap();
+
+        let keys = secure_store.list_keys().await.unwrap();
+        assert_eq!(keys.len(), 1);
+
+        let stored = secure_store.get_secrets(&[keys[0].as_str()]).await.unwrap();
+        let stored: StoredTokens = serde_json::from_slice(stored[0].as_ref().unwrap()).unwrap();
+        assert_eq!(stored.access_token, "access_token");
+        assert_eq!(stored.refresh_token.as_deref(), Some("refresh_token"));
+        assert_eq!(stored.expires_at, tokens.expires_at());
+    }
+
     #[core_async::test]
     async fn test_retrieve_nonexistent_tokens() {
         let secure_store = Arc::new(MockSecureStore::new());
@@ -3,8 +3,10 @@
 //! This module provides JavaScript/TypeScript-friendly bindings for the authentication
 //! functionality using wasm-bindgen.
 
+use crate::error::AuthError;
 use crate::manager::{AuthManager, ProviderInfo};
 use crate::types::{AuthState, ProviderKind, ProfileId};
+use bridge_wasm::{to_js_error, JsError, JsErrorKind};
 use bridge_wasm::{JsHttpClient, JsSecureStore}; // ✅ Use JS-compatible bridge types
 use core_runtime::wasm::JsEventBus; // ✅ Import JS-compatible EventBus
 use std::sync::Arc;
@@ -14,6 +16,38 @@ use wasm_bindgen::prelude::*;
 // Event creation functions are also from core-runtime.
 // We only export auth-specific functionality here.
 
+// =============================================================================
+// Error Handling Utilities
+// =============================================================================
+
+impl From<AuthError> for JsError {
+    fn from(err: AuthError) -> Self {
+        let kind = match &err {
+            AuthError::AuthenticationFailed { .. }
+            | AuthError::TokenRefreshFailed(_)
+            | AuthError::NotAuthenticated
+            | AuthError::StateMismatch { .. }
+            | AuthError::InvalidAuthCode(_)
+            | AuthError::TokenExpired
+            | AuthError::TokenCorrupted { .. }
+            | AuthError::NoSignInInProgress { .. }
+            | AuthError::InvalidState
+            | AuthError::NoRefreshToken { .. }
+            | AuthError::Other(_) => JsErrorKind::Auth,
+            AuthError::ProfileNotFound(_) => JsErrorKind::NotFound,
+            AuthError::InvalidProvider(_) => JsErrorKind::InvalidInput,
+            AuthError::NetworkError(_) => JsErrorKind::Network,
+            AuthError::OperationTimeout { .. } => JsErrorKind::Timeout,
+            AuthError::SignInInProgress { .. } => JsErrorKind::Conflict,
+            AuthError::SecureStorageUnavailable(_) => JsErrorKind::Storage,
+            AuthError::BridgeError(_)
+            | AuthError::SerializationFailed { .. }
+            | AuthError::SerializationError(_) => JsErrorKind::Internal,
+        };
+        JsError::new(kind, err.to_string())
+    }
+}
+
 // =============================================================================
 // Types - Exported to JavaScript
 // =============================================================================
@@ -151,6 +185,13 @@ pub struct JsAuthManager {
     inner: Arc<AuthManager>,
 }
 
+impl JsAuthManager {
+    /// Get the inner AuthManager (for other crates to use)
+    pub fn inner(&self) -> &Arc<AuthManager> {
+        &self.inner
+    }
+}
+
 #[wasm_bindgen]
 impl JsAuthManager {
     /// Create a new authentication manager
@@ -231,7 +272,7 @@ impl JsAuthManager {
         self.inner
             .sign_in(provider.into())
             .await
-            .map_err(|e| JsValue::from_str(&e.to_string()))
+            .map_err(to_js_error)
     }
 
     /// Complete the OAuth sign-in flow
@@ -271,7 +312,7 @@ impl JsAuthManager {
             .complete_sign_in(provider.into(), code, state)
             .await
             .map(|profile_id| profile_id.to_string())
-            .map_err(|e| JsValue::from_str(&e.to_string()))
+            .map_err(to_js_error)
     }
 
     /// Cancel an in-progress sign-in operation
@@ -298,12 +339,12 @@ impl JsAuthManager {
     #[wasm_bindgen(js_name = signOut)]
     pub async fn sign_out(&self, profile_id: String) -> std::result::Result<(), JsValue> {
         let pid = ProfileId::from_string(&profile_id)
-            .map_err(|e| JsValue::from_str(&format!("Invalid profile ID: {}", e)))?;
+            .map_err(|e| to_js_error(JsError::invalid_input(format!("Invalid profile ID: {}", e))))?;
 
         self.inner
             .sign_out(pid)
             .await
-            .map_err(|e| JsValue::from_str(&e.to_string()))
+            .map_err(to_js_error)
     }
 }
 
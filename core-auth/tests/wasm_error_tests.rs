@@ -0,0 +1,75 @@
+//! Integration tests for error mapping in the core-auth WASM bindings.
+//!
+//! These tests verify that auth failures reach JavaScript as structured
+//! `JsError` objects rather than opaque strings.
+
+#![cfg(target_arch = "wasm32")]
+
+use bridge_wasm::{JsError, JsHttpClient, JsSecureStore};
+use core_auth::wasm::{JsAuthManager, JsProviderKind};
+use core_auth::AuthError;
+use core_runtime::wasm::JsEventBus;
+use js_sys::Reflect;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn property(value: &JsValue, name: &str) -> JsValue {
+    Reflect::get(value, &JsValue::from_str(name)).unwrap()
+}
+
+fn create_manager() -> JsAuthManager {
+    JsAuthManager::new(
+        &JsEventBus::new(10),
+        &JsHttpClient::new(None).unwrap(),
+        &JsSecureStore::new("wasm-error-tests").unwrap(),
+    )
+    .unwrap()
+}
+
+#[wasm_bindgen_test]
+async fn test_auth_error_surfaces_kind() {
+    let manager = create_manager();
+
+    // No sign-in was started, so completing one must fail with an auth error
+    let err = manager
+        .complete_sign_in(
+            JsProviderKind::GoogleDrive,
+            "code".to_string(),
+            "state".to_string(),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(err.is_instance_of::<js_sys::Error>());
+    assert_eq!(property(&err, "kind").as_string().as_deref(), Some("Auth"));
+    assert_eq!(property(&err, "retryable").as_bool(), Some(false));
+    assert!(String::from(err.unchecked_ref::<js_sys::Error>().message())
+        .contains("No sign-in in progress"));
+}
+
+#[wasm_bindgen_test]
+async fn test_invalid_profile_id_is_invalid_input() {
+    let manager = create_manager();
+
+    let err = manager
+        .sign_out("not-a-uuid".to_string())
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        property(&err, "kind").as_string().as_deref(),
+        Some("InvalidInput")
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_network_error_is_retryable() {
+    let value = JsValue::from(JsError::from(AuthError::NetworkError(
+        "connection reset".to_string(),
+    )));
+
+    assert_eq!(property(&value, "kind").as_string().as_deref(), Some("Network"));
+    assert_eq!(property(&value, "retryable").as_bool(), Some(true));
+}
@@ -0,0 +1,8 @@
+-- Migration: 004_add_silence_trim
+-- Description: Store detected leading/trailing silence on tracks
+--
+-- Silence analysis finds long silent intros/outros so playback can skip them.
+-- NULL means the track hasn't been analyzed; 0 means no silence to trim.
+
+ALTER TABLE tracks ADD COLUMN leading_silence_ms INTEGER;
+ALTER TABLE tracks ADD COLUMN trailing_silence_ms INTEGER;
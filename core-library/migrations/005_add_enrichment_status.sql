@@ -0,0 +1,16 @@
+-- Migration: 005_add_enrichment_status
+-- Description: Record per-track progress of the metadata enrichment job
+--
+-- A run marks every track it will enrich as pending and records the outcome
+-- as each one finishes, so a run cut short by a crash or quit can resume
+-- with only the tracks still pending.
+
+CREATE TABLE IF NOT EXISTS enrichment_status (
+    track_id TEXT PRIMARY KEY NOT NULL,
+    status TEXT NOT NULL CHECK (status IN ('pending', 'done', 'failed')),
+    last_error TEXT,
+    updated_at INTEGER NOT NULL,
+    FOREIGN KEY (track_id) REFERENCES tracks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_enrichment_status ON enrichment_status(status);
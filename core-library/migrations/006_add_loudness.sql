@@ -0,0 +1,10 @@
+-- Migration: 006_add_loudness
+-- Description: Store EBU R128 loudness analysis results on tracks
+--
+-- Both values are in millibels (hundredths of a dB). Playback applies
+-- replay_gain_millibels to level tracks against each other and uses
+-- true_peak_millibels to keep the boosted signal from clipping.
+-- NULL means the track hasn't been analyzed.
+
+ALTER TABLE tracks ADD COLUMN replay_gain_millibels INTEGER;
+ALTER TABLE tracks ADD COLUMN true_peak_millibels INTEGER;
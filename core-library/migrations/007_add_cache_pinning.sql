@@ -0,0 +1,10 @@
+-- Migration: 007_add_cache_pinning
+-- Description: Let users pin cached tracks so eviction never removes them
+--
+-- Pinned tracks are skipped by every eviction policy. If pinned tracks
+-- alone exceed the cache size limit, eviction fails with CacheFull instead
+-- of silently deleting a track the user asked to keep.
+
+ALTER TABLE cache_metadata ADD COLUMN pinned INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_cache_pinned ON cache_metadata(pinned);
@@ -0,0 +1,10 @@
+-- Migration: 008_add_sync_job_profile
+-- Description: Record which profile each sync job ran for
+--
+-- Several profiles can link the same kind of provider, so cursors are
+-- cleared per profile rather than for every job of that provider. Jobs
+-- recorded before this migration keep a NULL profile.
+
+ALTER TABLE sync_jobs ADD COLUMN profile_id TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_sync_jobs_profile ON sync_jobs(profile_id);
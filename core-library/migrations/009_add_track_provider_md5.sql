@@ -0,0 +1,8 @@
+-- Migration: 009_add_track_provider_md5
+-- Description: Record the provider's MD5 checksum a track was synced from
+--
+-- A forced rescan compares it with the provider's current checksum to skip
+-- files whose content hasn't changed. Tracks synced before this migration
+-- keep a NULL checksum and fall back to provider_modified_at.
+
+ALTER TABLE tracks ADD COLUMN provider_md5 TEXT;
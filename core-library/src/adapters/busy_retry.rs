@@ -0,0 +1,193 @@
+//! Retries for transient database lock errors
+//!
+//! SQLite reports `SQLITE_BUSY` / `SQLITE_LOCKED` when another connection
+//! holds a conflicting lock, even in WAL mode. These errors are transient, so
+//! write paths retry them a few times with a short jittered backoff instead of
+//! surfacing them to callers. Any other error is returned immediately.
+
+use crate::repositories::PlatformArc;
+use bridge_traits::database::{DatabaseAdapter, QueryValue};
+use bridge_traits::error::{BridgeError, Result};
+use bridge_traits::http::{JitterMode, RetryPolicy};
+use bridge_traits::time::{Clock, SystemClock};
+use std::future::Future;
+use std::time::Duration;
+use tracing::debug;
+
+/// Error messages SQLite (and the adapters wrapping it) use for lock contention
+const BUSY_MARKERS: &[&str] = &[
+    "database is locked",
+    "database table is locked",
+    "database is busy",
+    "sqlite_busy",
+    "sqlite_locked",
+];
+
+/// Whether `error` is a transient lock error worth retrying
+pub fn is_busy_error(error: &BridgeError) -> bool {
+    match error {
+        BridgeError::DatabaseError(message) => {
+            let message = message.to_lowercase();
+            BUSY_MARKERS.iter().any(|marker| message.contains(marker))
+        }
+        _ => false,
+    }
+}
+
+/// Retries database operations that fail with busy/locked errors
+#[derive(Clone)]
+pub struct BusyRetry {
+    policy: RetryPolicy,
+    clock: PlatformArc<dyn Clock>,
+}
+
+impl BusyRetry {
+    /// Create a retry wrapper using the default policy and `clock` for jitter
+    pub fn new(clock: PlatformArc<dyn Clock>) -> Self {
+        Self {
+            policy: Self::default_policy(),
+            clock,
+        }
+    }
+
+    /// Replace the retry policy
+    pub fn with_policy(mut self, policy: RetryPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Three retries starting at 10ms, capped at 200ms
+    pub fn default_policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(200),
+            use_exponential_backoff: true,
+            jitter: JitterMode::Full,
+        }
+    }
+
+    /// Run `operation`, retrying it while it fails with a busy error
+    ///
+    /// Returns the last error once `max_attempts` retries are used up.
+    pub async fn run<T, F, Fut>(&self, mut operation: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut backoff = self.policy.backoff_from_clock(self.clock.as_ref());
+        let mut retries = 0;
+        loop {
+            match operation().await {
+                Err(error) if is_busy_error(&error) && retries < self.policy.max_attempts => {
+                    retries += 1;
+                    let delay = backoff.next_delay();
+                    debug!(
+                        retry = retries,
+                        delay_ms = delay.as_millis() as u64,
+                        "Database busy, retrying: {}",
+                        error
+                    );
+                    core_async::time::sleep(delay).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Execute a statement, retrying busy errors
+    pub async fn execute(
+        &self,
+        adapter: &dyn DatabaseAdapter,
+        statement: &str,
+        params: &[QueryValue],
+    ) -> Result<u64> {
+        self.run(|| adapter.execute(statement, params)).await
+    }
+}
+
+impl Default for BusyRetry {
+    fn default() -> Self {
+        Self::new(PlatformArc::new(SystemClock))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn busy() -> BridgeError {
+        BridgeError::DatabaseError(
+            "Execute failed: error returned from database: (code: 5) database is locked"
+                .to_string(),
+        )
+    }
+
+    #[test]
+    fn test_is_busy_error() {
+        assert!(is_busy_error(&busy()));
+        assert!(is_busy_error(&BridgeError::DatabaseError(
+            "SQLITE_LOCKED: database table is locked".to_string()
+        )));
+        assert!(!is_busy_error(&BridgeError::DatabaseError(
+            "UNIQUE constraint failed: tracks.id".to_string()
+        )));
+        assert!(!is_busy_error(&BridgeError::OperationFailed(
+            "database is locked".to_string()
+        )));
+    }
+
+    #[core_async::test]
+    async fn test_retries_busy_errors_until_success() {
+        let attempts = AtomicU32::new(0);
+        let retry = BusyRetry::default();
+
+        let result = retry
+            .run(|| async {
+                if attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+                    Err(busy())
+                } else {
+                    Ok(1u64)
+                }
+            })
+            .await;
+
+        assert_eq!(result.unwrap(), 1);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[core_async::test]
+    async fn test_does_not_retry_genuine_errors() {
+        let attempts = AtomicU32::new(0);
+        let retry = BusyRetry::default();
+
+        let result: Result<u64> = retry
+            .run(|| async {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Err(BridgeError::DatabaseError(
+                    "UNIQUE constraint failed".to_string(),
+                ))
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[core_async::test]
+    async fn test_gives_up_after_max_attempts() {
+        let attempts = AtomicU32::new(0);
+        let retry = BusyRetry::default();
+
+        let result: Result<u64> = retry
+            .run(|| async {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Err(busy())
+            })
+            .await;
+
+        assert!(is_busy_error(&result.unwrap_err()));
+        assert_eq!(attempts.load(Ordering::SeqCst), 4);
+    }
+}
@@ -1,7 +1,9 @@
 //! Database adapter implementations
 //!
 //! This module contains concrete implementations of the `DatabaseAdapter` trait
-//! for different platforms.
+//! for different platforms, plus helpers shared by all adapters.
+
+pub mod busy_retry;
 
 #[cfg(not(target_arch = "wasm32"))]
 pub mod sqlite_native;
@@ -11,3 +13,5 @@ pub use sqlite_native::SqliteAdapter;
 
 #[cfg(target_arch = "wasm32")]
 pub use bridge_wasm::database::WasmDbAdapter;
+
+pub use busy_retry::{is_busy_error, BusyRetry};
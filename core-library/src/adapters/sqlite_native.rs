@@ -9,7 +9,7 @@
 //! - WAL mode for better concurrency
 //! - Automatic migrations
 //! - Prepared statement caching
-//! - Transaction support with savepoints
+//! - Transactions on a dedicated pooled connection
 //! - Foreign key enforcement
 
 use async_trait::async_trait;
@@ -17,6 +17,8 @@ use bridge_traits::database::{
     DatabaseAdapter, DatabaseConfig, DatabaseStatistics, QueryRow, QueryValue, TransactionId,
 };
 use bridge_traits::error::{BridgeError, Result};
+use core_async::sync::Mutex;
+use sqlx::pool::PoolConnection;
 use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteSynchronous};
 use sqlx::{Column, Pool, Row, Sqlite};
 use std::collections::HashMap;
@@ -33,6 +35,8 @@ use tracing::{debug, info, warn};
 pub struct SqliteAdapter {
     pool: Pool<Sqlite>,
     transaction_counter: Arc<AtomicU64>,
+    /// Connections held by open transactions, keyed by transaction id
+    transactions: Arc<Mutex<HashMap<u64, PoolConnection<Sqlite>>>>,
     config: DatabaseConfig,
 }
 
@@ -65,6 +69,11 @@ impl SqliteAdapter {
         let mut connect_options = SqliteConnectOptions::from_str(&config.database_url)
             .map_err(|e| BridgeError::DatabaseError(format!("Invalid database URL: {}", e)))?;
 
+        // The key has to be set before anything reads the database
+        connect_options =
+            crate::db::apply_encryption_key(connect_options, config.encryption_key.as_ref())
+                .map_err(|e| BridgeError::DatabaseError(e.to_string()))?;
+
         // Configure SQLite connection options
         connect_options = connect_options
             // Enable WAL mode for better concurrency
@@ -109,6 +118,7 @@ impl SqliteAdapter {
         Ok(Self {
             pool,
             transaction_counter: Arc::new(AtomicU64::new(0)),
+            transactions: Arc::new(Mutex::new(HashMap::new())),
             config,
         })
     }
@@ -121,6 +131,7 @@ impl SqliteAdapter {
         Self {
             pool,
             transaction_counter: Arc::new(AtomicU64::new(0)),
+            transactions: Arc::new(Mutex::new(HashMap::new())),
             config: DatabaseConfig::default(),
         }
     }
@@ -132,6 +143,78 @@ impl SqliteAdapter {
         &self.pool
     }
 
+    // =========================================================================
+    // Raw SQL (advanced)
+    // =========================================================================
+
+    /// Execute an arbitrary SQL statement, bypassing the typed repositories
+    ///
+    /// Intended for maintenance and diagnostics (`PRAGMA`, `ANALYZE`,
+    /// `VACUUM`) that have no dedicated API. Statements run against the live
+    /// library database with no schema validation, so a careless statement
+    /// can corrupt data the repositories rely on.
+    ///
+    /// Values must be passed through `params`, never formatted into `sql`.
+    /// Raw statements are not added to the prepared statement cache.
+    ///
+    /// # Returns
+    ///
+    /// Number of rows affected
+    pub async fn execute_raw(&self, sql: &str, params: &[QueryValue]) -> Result<u64> {
+        info!(statement = %sql, param_count = params.len(), "Executing raw SQL statement");
+
+        let sqlx_query = sqlx::query(sql).persistent(false);
+        let sqlx_query = Self::bind_params(sqlx_query, params);
+
+        let result = sqlx_query
+            .execute(&self.pool)
+            .await
+            .map_err(|e| BridgeError::DatabaseError(format!("Raw execute failed: {}", e)))?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Run an arbitrary SQL query, bypassing the typed repositories
+    ///
+    /// The read counterpart of [`execute_raw`](Self::execute_raw), with the
+    /// same caveats.
+    pub async fn query_raw(&self, sql: &str, params: &[QueryValue]) -> Result<Vec<QueryRow>> {
+        info!(query = %sql, param_count = params.len(), "Executing raw SQL query");
+
+        let sqlx_query = sqlx::query(sql).persistent(false);
+        let sqlx_query = Self::bind_params(sqlx_query, params);
+
+        let rows = sqlx_query
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| BridgeError::DatabaseError(format!("Raw query failed: {}", e)))?;
+
+        Ok(rows.iter().map(Self::row_to_query_row).collect())
+    }
+
+    /// Run `PRAGMA integrity_check` and return SQLite's report
+    ///
+    /// A healthy database reports a single `"ok"` line; otherwise each line
+    /// describes one problem found.
+    pub async fn integrity_check(&self) -> Result<Vec<String>> {
+        let rows = self.query_raw("PRAGMA integrity_check", &[]).await?;
+
+        let report: Vec<String> = rows
+            .iter()
+            .filter_map(|row| row.get("integrity_check"))
+            .filter_map(|value| value.as_string())
+            .collect();
+
+        if report.first().map(String::as_str) != Some("ok") {
+            warn!(
+                problems = report.len(),
+                "Database integrity check found problems"
+            );
+        }
+
+        Ok(report)
+    }
+
     /// Convert a sqlx Row to a QueryRow (HashMap)
     fn row_to_query_row(row: &sqlx::sqlite::SqliteRow) -> QueryRow {
         let mut result = HashMap::new();
@@ -176,6 +259,18 @@ impl SqliteAdapter {
         query
     }
 
+    /// Remove an open transaction, handing back its connection
+    async fn take_transaction(
+        &self,
+        transaction_id: TransactionId,
+    ) -> Result<PoolConnection<Sqlite>> {
+        self.transactions
+            .lock()
+            .await
+            .remove(&transaction_id.0)
+            .ok_or_else(|| unknown_transaction(transaction_id))
+    }
+
     /// Run database migrations
     async fn run_migrations(&self) -> Result<()> {
         info!("Running database migrations");
@@ -201,6 +296,11 @@ impl DatabaseAdapter for SqliteAdapter {
         // Run migrations
         self.run_migrations().await?;
 
+        // Apply the configured search tokenizer
+        crate::fts::ensure_fts_tokenizer(self, self.config.fts_tokenizer)
+            .await
+            .map_err(|e| BridgeError::DatabaseError(format!("FTS setup failed: {}", e)))?;
+
         // Perform health check
         self.health_check().await?;
 
@@ -295,33 +395,55 @@ impl DatabaseAdapter for SqliteAdapter {
         Ok(Self::row_to_query_row(&row))
     }
 
+    /// Begin a transaction on a connection taken from the pool
+    ///
+    /// The connection stays out of the pool until the transaction is committed
+    /// or rolled back, so a transaction id that is never ended pins one pooled
+    /// connection for the life of the adapter.
     async fn begin_transaction(&self) -> Result<TransactionId> {
         let tx_id = self.transaction_counter.fetch_add(1, Ordering::SeqCst);
         let transaction_id = TransactionId(tx_id);
 
         debug!(transaction_id = tx_id, "Beginning transaction");
 
-        // LIMITATION: This simple transaction implementation doesn't guarantee
-        // that subsequent operations will use the same connection from the pool.
-        // For production use, consider:
-        // 1. Acquiring a dedicated connection for the transaction lifetime
-        // 2. Using sqlx::Transaction directly
-        // 3. Implementing a connection reservation system
-        //
-        // For now, this provides basic transaction support for simple cases.
-        self.execute("BEGIN TRANSACTION", &[])
+        // The transaction keeps its own connection until it ends, so its
+        // statements never land on another pooled connection
+        let mut connection =
+            self.pool.acquire().await.map_err(|e| {
+                BridgeError::DatabaseError(format!("Begin transaction failed: {}", e))
+            })?;
+        // Taking the write lock up front makes concurrent transactions wait
+        // here instead of failing when one of them upgrades a read lock
+        sqlx::query("BEGIN IMMEDIATE TRANSACTION")
+            .execute(&mut *connection)
             .await
             .map_err(|e| BridgeError::DatabaseError(format!("Begin transaction failed: {}", e)))?;
 
+        self.transactions.lock().await.insert(tx_id, connection);
         Ok(transaction_id)
     }
 
     async fn commit_transaction(&self, transaction_id: TransactionId) -> Result<()> {
         debug!(transaction_id = transaction_id.0, "Committing transaction");
 
-        self.execute("COMMIT TRANSACTION", &[])
+        let mut connection = self.take_transaction(transaction_id).await?;
+        if let Err(e) = sqlx::query("COMMIT TRANSACTION")
+            .execute(&mut *connection)
             .await
-            .map_err(|e| BridgeError::DatabaseError(format!("Commit transaction failed: {}", e)))?;
+        {
+            // Don't hand a connection with an open transaction back to the pool
+            if sqlx::query("ROLLBACK TRANSACTION")
+                .execute(&mut *connection)
+                .await
+                .is_err()
+            {
+                connection.close_on_drop();
+            }
+            return Err(BridgeError::DatabaseError(format!(
+                "Commit transaction failed: {}",
+                e
+            )));
+        }
 
         Ok(())
     }
@@ -332,35 +454,64 @@ impl DatabaseAdapter for SqliteAdapter {
             "Rolling back transaction"
         );
 
-        self.execute("ROLLBACK TRANSACTION", &[])
+        let mut connection = self.take_transaction(transaction_id).await?;
+        if let Err(e) = sqlx::query("ROLLBACK TRANSACTION")
+            .execute(&mut *connection)
             .await
-            .map_err(|e| {
-                BridgeError::DatabaseError(format!("Rollback transaction failed: {}", e))
-            })?;
+        {
+            // The transaction may still be open, so the connection can't be reused
+            connection.close_on_drop();
+            return Err(BridgeError::DatabaseError(format!(
+                "Rollback transaction failed: {}",
+                e
+            )));
+        }
 
         Ok(())
     }
 
     async fn query_in_transaction(
         &self,
-        _transaction_id: TransactionId,
+        transaction_id: TransactionId,
         query: &str,
         params: &[QueryValue],
     ) -> Result<Vec<QueryRow>> {
-        // Since we're using savepoints on the same connection pool,
-        // we can just execute the query normally
-        self.query(query, params).await
+        let mut transactions = self.transactions.lock().await;
+        let connection = transactions
+            .get_mut(&transaction_id.0)
+            .ok_or_else(|| unknown_transaction(transaction_id))?;
+
+        let sqlx_query = sqlx::query(query);
+        let sqlx_query = Self::bind_params(sqlx_query, params);
+
+        let rows = sqlx_query
+            .fetch_all(&mut **connection)
+            .await
+            .map_err(|e| BridgeError::DatabaseError(format!("Query failed: {}", e)))?;
+
+        Ok(rows.iter().map(Self::row_to_query_row).collect())
     }
 
     async fn execute_in_transaction(
         &self,
-        _transaction_id: TransactionId,
+        transaction_id: TransactionId,
         statement: &str,
         params: &[QueryValue],
     ) -> Result<u64> {
-        // Since we're using savepoints on the same connection pool,
-        // we can just execute the statement normally
-        self.execute(statement, params).await
+        let mut transactions = self.transactions.lock().await;
+        let connection = transactions
+            .get_mut(&transaction_id.0)
+            .ok_or_else(|| unknown_transaction(transaction_id))?;
+
+        let sqlx_query = sqlx::query(statement);
+        let sqlx_query = Self::bind_params(sqlx_query, params);
+
+        let result = sqlx_query
+            .execute(&mut **connection)
+            .await
+            .map_err(|e| BridgeError::DatabaseError(format!("Execute failed: {}", e)))?;
+
+        Ok(result.rows_affected())
     }
 
     async fn execute_batch(&self, statements: &[(&str, &[QueryValue])]) -> Result<Vec<u64>> {
@@ -480,6 +631,10 @@ impl DatabaseAdapter for SqliteAdapter {
     }
 }
 
+fn unknown_transaction(transaction_id: TransactionId) -> BridgeError {
+    BridgeError::DatabaseError(format!("Unknown transaction: {}", transaction_id.0))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -559,9 +714,6 @@ mod tests {
             .await
             .unwrap();
 
-        // NOTE: Due to connection pooling, the rollback might not affect the same connection.
-        // In a production system, you'd want to acquire a dedicated connection for the transaction.
-        // For now, we just commit to test the commit path.
         adapter.commit_transaction(tx_id).await.unwrap();
 
         // Verify data was committed
@@ -569,6 +721,78 @@ mod tests {
         assert_eq!(rows.len(), 1);
     }
 
+    #[core_async::test]
+    async fn test_transaction_rollback_uses_its_own_connection() {
+        let mut config = DatabaseConfig::in_memory();
+        config.max_connections = 4;
+        let mut adapter = SqliteAdapter::new(config).await.unwrap();
+        adapter.initialize().await.unwrap();
+        adapter
+            .execute("CREATE TABLE test (id INTEGER PRIMARY KEY, name TEXT)", &[])
+            .await
+            .unwrap();
+
+        let tx_id = adapter.begin_transaction().await.unwrap();
+        for id in 1..=3 {
+            let params = vec![
+                QueryValue::Integer(id),
+                QueryValue::Text("test".to_string()),
+            ];
+            adapter
+                .execute_in_transaction(tx_id, "INSERT INTO test (id, name) VALUES (?, ?)", &params)
+                .await
+                .unwrap();
+        }
+        let rows = adapter
+            .query_in_transaction(tx_id, "SELECT * FROM test", &[])
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 3);
+
+        adapter.rollback_transaction(tx_id).await.unwrap();
+        let rows = adapter.query("SELECT * FROM test", &[]).await.unwrap();
+        assert!(rows.is_empty());
+        assert!(adapter.commit_transaction(tx_id).await.is_err());
+    }
+
+    #[core_async::test]
+    async fn test_failed_commit_does_not_leak_open_transaction() {
+        let mut config = DatabaseConfig::in_memory();
+        config.max_connections = 1;
+        let mut adapter = SqliteAdapter::new(config).await.unwrap();
+        adapter.initialize().await.unwrap();
+        adapter
+            .execute("CREATE TABLE parent (id INTEGER PRIMARY KEY)", &[])
+            .await
+            .unwrap();
+        adapter
+            .execute(
+                "CREATE TABLE child (id INTEGER PRIMARY KEY, parent_id INTEGER \
+                 REFERENCES parent(id) DEFERRABLE INITIALLY DEFERRED)",
+                &[],
+            )
+            .await
+            .unwrap();
+
+        // The dangling reference is only checked, and rejected, at COMMIT
+        let tx_id = adapter.begin_transaction().await.unwrap();
+        adapter
+            .execute_in_transaction(
+                tx_id,
+                "INSERT INTO child (id, parent_id) VALUES (1, 42)",
+                &[],
+            )
+            .await
+            .unwrap();
+        assert!(adapter.commit_transaction(tx_id).await.is_err());
+
+        // The only pooled connection is back outside any transaction
+        let rows = adapter.query("SELECT * FROM child", &[]).await.unwrap();
+        assert!(rows.is_empty());
+        let tx_id = adapter.begin_transaction().await.unwrap();
+        adapter.rollback_transaction(tx_id).await.unwrap();
+    }
+
     #[core_async::test]
     async fn test_batch_execute() {
         let adapter = create_test_adapter().await;
@@ -604,6 +828,49 @@ mod tests {
         assert_eq!(rows.len(), 2);
     }
 
+    #[core_async::test]
+    async fn test_integrity_check_on_healthy_database() {
+        let adapter = create_test_adapter().await;
+        let report = adapter.integrity_check().await.unwrap();
+        assert_eq!(report, vec!["ok".to_string()]);
+    }
+
+    #[core_async::test]
+    async fn test_execute_raw_binds_parameters() {
+        let adapter = create_test_adapter().await;
+        adapter
+            .execute_raw(
+                "CREATE TABLE notes (id INTEGER PRIMARY KEY, body TEXT)",
+                &[],
+            )
+            .await
+            .unwrap();
+
+        // A value that would break out of a formatted string stays a value
+        let body = "x'); DROP TABLE notes; --";
+        let affected = adapter
+            .execute_raw(
+                "INSERT INTO notes (id, body) VALUES (?, ?)",
+                &[QueryValue::Integer(1), QueryValue::Text(body.to_string())],
+            )
+            .await
+            .unwrap();
+        assert_eq!(affected, 1);
+
+        let rows = adapter
+            .query_raw(
+                "SELECT body FROM notes WHERE id = ?",
+                &[QueryValue::Integer(1)],
+            )
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(
+            rows[0].get("body").and_then(|v| v.as_string()),
+            Some(body.to_string())
+        );
+    }
+
     #[core_async::test]
     async fn test_get_statistics() {
         let adapter = create_test_adapter().await;
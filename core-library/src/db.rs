@@ -9,7 +9,10 @@
 //! - **Statement Caching**: Automatic prepared statement caching
 //! - **Foreign Keys**: Enforced for referential integrity
 //! - **Automatic Migrations**: Runs on initialization
+//! - **Warm-up**: One connection is opened and checked while the pool is
+//!   created, so a bad path fails there instead of on the first query
 //! - **Health Checks**: Connection validation
+//! - **Encryption**: Whole-database encryption with SQLCipher (`sqlcipher` feature)
 //!
 //! ## Usage
 //!
@@ -26,6 +29,17 @@
 //!     .await?;
 //! ```
 //!
+//! ## Encryption
+//!
+//! With the `sqlcipher` feature, a database opened with a key is encrypted at
+//! rest. Existing plaintext databases are converted with [`encrypt_database`]:
+//!
+//! ```rust,ignore
+//! let key = DatabaseKey::Passphrase("correct horse".to_string());
+//! encrypt_database(Path::new("music.db"), &key).await?;
+//! let pool = create_pool(DatabaseConfig::new("music.db").encryption_key(key)).await?;
+//! ```
+//!
 //! ## Testing
 //!
 //! For tests, use in-memory databases:
@@ -35,10 +49,17 @@
 //! ```
 
 use crate::{LibraryError, Result};
+use bridge_traits::database::{DatabaseKey, FtsTokenizer};
+#[cfg(all(feature = "sqlcipher", not(target_arch = "wasm32")))]
+use bridge_traits::error::BridgeError;
 #[cfg(not(target_arch = "wasm32"))]
 use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteSynchronous};
+#[cfg(all(feature = "sqlcipher", not(target_arch = "wasm32")))]
+use sqlx::{ConnectOptions, Connection};
 #[cfg(not(target_arch = "wasm32"))]
 use sqlx::{Pool, Sqlite};
+#[cfg(all(feature = "sqlcipher", not(target_arch = "wasm32")))]
+use std::path::Path;
 use std::path::PathBuf;
 #[cfg(not(target_arch = "wasm32"))]
 use std::str::FromStr;
@@ -68,6 +89,12 @@ pub struct DatabaseConfig {
 
     /// Enable statement caching (number of statements to cache)
     pub statement_cache_capacity: usize,
+
+    /// Tokenizer used by the full-text search indexes
+    pub fts_tokenizer: FtsTokenizer,
+
+    /// Key of an encrypted database; requires the `sqlcipher` feature
+    pub encryption_key: Option<DatabaseKey>,
 }
 
 impl DatabaseConfig {
@@ -94,6 +121,8 @@ impl DatabaseConfig {
             max_lifetime: Some(Duration::from_secs(1800)), // 30 minutes
             idle_timeout: Some(Duration::from_secs(600)),  // 10 minutes
             statement_cache_capacity: 100,
+            fts_tokenizer: FtsTokenizer::default(),
+            encryption_key: None,
         }
     }
 
@@ -114,6 +143,8 @@ impl DatabaseConfig {
             max_lifetime: None,
             idle_timeout: None,
             statement_cache_capacity: 100,
+            fts_tokenizer: FtsTokenizer::default(),
+            encryption_key: None,
         }
     }
 
@@ -152,6 +183,24 @@ impl DatabaseConfig {
         self.statement_cache_capacity = capacity;
         self
     }
+
+    /// Set the full-text search tokenizer
+    ///
+    /// Changing the tokenizer of an existing database rebuilds its search
+    /// indexes when the pool is created.
+    pub fn fts_tokenizer(mut self, tokenizer: FtsTokenizer) -> Self {
+        self.fts_tokenizer = tokenizer;
+        self
+    }
+
+    /// Set the key of an encrypted database
+    ///
+    /// Opening a database with the wrong key, or an encrypted database
+    /// without one, fails when the pool is created.
+    pub fn encryption_key(mut self, key: DatabaseKey) -> Self {
+        self.encryption_key = Some(key);
+        self
+    }
 }
 
 impl Default for DatabaseConfig {
@@ -165,8 +214,11 @@ impl Default for DatabaseConfig {
 /// This function:
 /// 1. Configures SQLite connection options (WAL mode, foreign keys, etc.)
 /// 2. Creates a connection pool with the specified configuration
-/// 3. Runs database migrations
-/// 4. Performs a health check
+/// 3. Warms up the pool: opens a connection, runs `SELECT 1` and reports
+///    which pragmas took effect
+/// 4. Runs database migrations
+/// 5. Rebuilds the search indexes if the configured FTS tokenizer changed
+/// 6. Performs a health check
 ///
 /// # Arguments
 ///
@@ -179,7 +231,8 @@ impl Default for DatabaseConfig {
 /// # Errors
 ///
 /// Returns an error if:
-/// - The database file cannot be accessed
+/// - The database file cannot be opened or created
+///   ([`LibraryError::DatabaseUnavailable`], with a hint at the cause)
 /// - Connection pool creation fails
 /// - Migrations fail
 /// - Health check fails
@@ -207,6 +260,9 @@ pub async fn create_pool(config: DatabaseConfig) -> Result<Pool<Sqlite>> {
     let mut connect_options =
         SqliteConnectOptions::from_str(&config.database_url).map_err(LibraryError::Database)?;
 
+    // The key has to be set before anything reads the database
+    connect_options = apply_encryption_key(connect_options, config.encryption_key.as_ref())?;
+
     // Configure SQLite connection options
     connect_options = connect_options
         // Enable WAL mode for better concurrency
@@ -235,12 +291,10 @@ pub async fn create_pool(config: DatabaseConfig) -> Result<Pool<Sqlite>> {
         .acquire_timeout(config.acquire_timeout)
         .max_lifetime(config.max_lifetime)
         .idle_timeout(config.idle_timeout)
-        .connect_with(connect_options)
-        .await
-        .map_err(|e| {
-            warn!(error = %e, "Failed to create connection pool");
-            LibraryError::Database(e)
-        })?;
+        .connect_lazy_with(connect_options);
+
+    // Open the first connection now rather than on the first query
+    warm_up(&pool, &config.database_url).await?;
 
     info!(
         connections = pool.size(),
@@ -250,12 +304,255 @@ pub async fn create_pool(config: DatabaseConfig) -> Result<Pool<Sqlite>> {
     // Run migrations
     run_migrations(&pool).await?;
 
+    // Apply the configured search tokenizer
+    let adapter = crate::adapters::SqliteAdapter::from_pool(pool.clone());
+    crate::fts::ensure_fts_tokenizer(&adapter, config.fts_tokenizer).await?;
+
     // Perform health check
     health_check(&pool).await?;
 
     Ok(pool)
 }
 
+/// Connection settings as SQLite reports them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PragmaReport {
+    /// `wal`, or `memory` for in-memory databases
+    pub journal_mode: String,
+    /// Whether foreign key constraints are enforced
+    pub foreign_keys: bool,
+    /// `synchronous` level (1 = NORMAL)
+    pub synchronous: i64,
+    /// Page cache size; negative values are in KiB
+    pub cache_size: i64,
+}
+
+impl PragmaReport {
+    /// Whether the settings `create_pool` asks for are in effect
+    ///
+    /// In-memory databases can't use WAL, so `memory` journaling counts.
+    pub fn is_applied(&self) -> bool {
+        let journal_ok = matches!(self.journal_mode.as_str(), "wal" | "memory");
+        journal_ok && self.foreign_keys && self.synchronous == 1
+    }
+}
+
+/// Read the connection settings of one pooled connection
+pub async fn pragma_report(pool: &Pool<Sqlite>) -> Result<PragmaReport> {
+    let mut conn = pool.acquire().await?;
+    let (journal_mode,): (String,) = sqlx::query_as("PRAGMA journal_mode")
+        .fetch_one(&mut *conn)
+        .await?;
+    let (foreign_keys,): (i64,) = sqlx::query_as("PRAGMA foreign_keys")
+        .fetch_one(&mut *conn)
+        .await?;
+    let (synchronous,): (i64,) = sqlx::query_as("PRAGMA synchronous")
+        .fetch_one(&mut *conn)
+        .await?;
+    let (cache_size,): (i64,) = sqlx::query_as("PRAGMA cache_size")
+        .fetch_one(&mut *conn)
+        .await?;
+
+    Ok(PragmaReport {
+        journal_mode: journal_mode.to_lowercase(),
+        foreign_keys: foreign_keys == 1,
+        synchronous,
+        cache_size,
+    })
+}
+
+/// Open and validate a first connection of a new pool
+///
+/// Connecting applies the configured pragmas, so a bad path, a permission
+/// problem or a wrong encryption key shows up here.
+async fn warm_up(pool: &Pool<Sqlite>, database_url: &str) -> Result<PragmaReport> {
+    let unavailable = |e: sqlx::Error| {
+        warn!(error = %e, "Failed to open database");
+        database_unavailable(database_url, e)
+    };
+
+    let mut conn = pool.acquire().await.map_err(unavailable)?;
+    sqlx::query("SELECT 1")
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(unavailable)?;
+    drop(conn);
+
+    let report = pragma_report(pool).await?;
+    if report.is_applied() {
+        info!(
+            journal_mode = %report.journal_mode,
+            foreign_keys = report.foreign_keys,
+            synchronous = report.synchronous,
+            cache_size = report.cache_size,
+            "Database connection warmed up"
+        );
+    } else {
+        warn!(
+            journal_mode = %report.journal_mode,
+            foreign_keys = report.foreign_keys,
+            synchronous = report.synchronous,
+            cache_size = report.cache_size,
+            "Database connection warmed up, but some pragmas did not take effect"
+        );
+    }
+    Ok(report)
+}
+
+/// Describe why the database at `database_url` could not be opened
+fn database_unavailable(database_url: &str, error: sqlx::Error) -> LibraryError {
+    // Extended result codes keep the primary code in the low byte
+    let primary_code = match &error {
+        sqlx::Error::Database(e) => e
+            .code()
+            .and_then(|code| code.parse::<i32>().ok())
+            .map(|code| code & 0xff),
+        _ => None,
+    };
+    let hint = match primary_code {
+        // SQLITE_CANTOPEN
+        Some(14) => Some("check that the directory exists and is writable"),
+        // SQLITE_PERM, SQLITE_READONLY
+        Some(3) | Some(8) => Some("the database or its directory is read-only"),
+        // SQLITE_NOTADB
+        Some(26) => Some("the file is not a database, or the encryption key is wrong"),
+        _ => None,
+    };
+
+    LibraryError::DatabaseUnavailable {
+        url: database_url.to_string(),
+        reason: match hint {
+            Some(hint) => format!("{} ({})", error, hint),
+            None => error.to_string(),
+        },
+    }
+}
+
+/// Set the SQLCipher `key` pragma on `options` when a key is configured
+///
+/// sqlx issues `key` before every other pragma on connect, as SQLCipher
+/// requires.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn apply_encryption_key(
+    options: SqliteConnectOptions,
+    key: Option<&DatabaseKey>,
+) -> Result<SqliteConnectOptions> {
+    match key {
+        None => Ok(options),
+        #[cfg(feature = "sqlcipher")]
+        Some(key) => Ok(options.pragma("key", key.pragma_value())),
+        #[cfg(not(feature = "sqlcipher"))]
+        Some(_) => Err(LibraryError::InvalidInput {
+            field: "encryption_key".to_string(),
+            message: "database encryption requires the sqlcipher feature".to_string(),
+        }),
+    }
+}
+
+/// Encrypt the plaintext database at `path` in place with `key`
+///
+/// The database is exported into an encrypted copy next to it, which then
+/// replaces the original. Nothing else may have the database open while it
+/// is converted. If the conversion fails, the original file is left as is.
+///
+/// # Errors
+///
+/// Returns an error if the database cannot be opened (for instance because
+/// it is already encrypted) or the encrypted copy cannot be written.
+#[cfg(all(feature = "sqlcipher", not(target_arch = "wasm32")))]
+pub async fn encrypt_database(path: &Path, key: &DatabaseKey) -> Result<()> {
+    info!(path = %path.display(), "Encrypting database");
+
+    let mut encrypted_path = path.as_os_str().to_owned();
+    encrypted_path.push(".encrypting");
+    let encrypted_path = PathBuf::from(encrypted_path);
+    remove_if_exists(&encrypted_path)?;
+
+    if !path.exists() {
+        return Err(BridgeError::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("database not found: {}", path.display()),
+        ))
+        .into());
+    }
+
+    // ATTACH opens the copy with this connection's flags, so it must be
+    // allowed to create files
+    let mut conn = SqliteConnectOptions::new()
+        .filename(path)
+        .create_if_missing(true)
+        .read_only(false)
+        .connect()
+        .await?;
+
+    // Fold the WAL into the main file so the export sees every commit
+    sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)")
+        .execute(&mut conn)
+        .await?;
+    let (user_version,): (i64,) = sqlx::query_as("PRAGMA user_version")
+        .fetch_one(&mut conn)
+        .await?;
+
+    sqlx::query(&format!(
+        "ATTACH DATABASE ? AS encrypted KEY {}",
+        key.pragma_value()
+    ))
+    .bind(file_uri(&encrypted_path))
+    .execute(&mut conn)
+    .await?;
+    sqlx::query("SELECT sqlcipher_export('encrypted')")
+        .execute(&mut conn)
+        .await?;
+    // sqlcipher_export doesn't carry over the schema version
+    sqlx::query(&format!("PRAGMA encrypted.user_version = {}", user_version))
+        .execute(&mut conn)
+        .await?;
+    sqlx::query("DETACH DATABASE encrypted")
+        .execute(&mut conn)
+        .await?;
+    conn.close().await?;
+
+    // A leftover plaintext WAL would be replayed against the encrypted file
+    for suffix in ["-wal", "-shm"] {
+        let mut sidecar = path.as_os_str().to_owned();
+        sidecar.push(suffix);
+        remove_if_exists(Path::new(&sidecar))?;
+    }
+    std::fs::rename(&encrypted_path, path).map_err(BridgeError::Io)?;
+
+    info!(path = %path.display(), "Database encrypted");
+    Ok(())
+}
+
+/// Build a SQLite `file:` URI for `path`
+///
+/// Connections accept URIs, so a plain path that happened to start with
+/// `file:` or contain `?` would otherwise be misread.
+#[cfg(all(feature = "sqlcipher", not(target_arch = "wasm32")))]
+fn file_uri(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    #[cfg(windows)]
+    let path = format!("/{}", path.replace('\\', "/"));
+
+    let mut uri = String::from("file:");
+    for c in path.chars() {
+        match c {
+            '%' | '?' | '#' => uri.push_str(&format!("%{:02X}", c as u32)),
+            c => uri.push(c),
+        }
+    }
+    uri
+}
+
+#[cfg(all(feature = "sqlcipher", not(target_arch = "wasm32")))]
+fn remove_if_exists(path: &Path) -> Result<()> {
+    match std::fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(BridgeError::Io(e).into()),
+    }
+}
+
 /// Create a connection pool for testing with in-memory database
 ///
 /// This is a convenience function that creates an in-memory database
@@ -382,6 +679,35 @@ mod tests {
         assert!(result.is_ok(), "Health check should pass");
     }
 
+    #[core_async::test]
+    async fn test_warm_up_reports_applied_pragmas() {
+        let pool = create_test_pool().await.unwrap();
+        let report = pragma_report(&pool).await.unwrap();
+
+        assert!(report.foreign_keys);
+        assert_eq!(report.cache_size, -64000);
+        assert!(report.is_applied(), "{:?}", report);
+    }
+
+    #[core_async::test]
+    async fn test_unwritable_path_fails_on_pool_creation() {
+        // A regular file can't hold a database, even for a privileged user
+        let parent = std::env::temp_dir().join(format!("not-a-dir-{}", uuid::Uuid::new_v4()));
+        std::fs::write(&parent, b"").unwrap();
+
+        let config =
+            DatabaseConfig::new(parent.join("library.db")).acquire_timeout(Duration::from_secs(5));
+        let err = create_pool(config).await.unwrap_err();
+        let _ = std::fs::remove_file(&parent);
+
+        assert!(
+            matches!(err, LibraryError::DatabaseUnavailable { .. }),
+            "{:?}",
+            err
+        );
+        assert!(err.to_string().contains("writable"), "{}", err);
+    }
+
     #[core_async::test]
     async fn test_database_config_builder() {
         let config = DatabaseConfig::in_memory()
@@ -472,4 +798,79 @@ mod tests {
 
         assert_eq!(result.0, 1, "Tracks table should exist");
     }
+
+    #[cfg(not(feature = "sqlcipher"))]
+    #[core_async::test]
+    async fn test_encryption_key_requires_sqlcipher() {
+        let config = DatabaseConfig::in_memory()
+            .encryption_key(DatabaseKey::Passphrase("correct horse".to_string()));
+        assert!(matches!(
+            create_pool(config).await,
+            Err(LibraryError::InvalidInput { .. })
+        ));
+    }
+
+    #[cfg(feature = "sqlcipher")]
+    fn temp_database(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("{}-{}", name, uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.join("library.db")
+    }
+
+    #[cfg(feature = "sqlcipher")]
+    async fn provider_count(pool: &Pool<Sqlite>) -> i64 {
+        let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM providers")
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        count
+    }
+
+    #[cfg(feature = "sqlcipher")]
+    #[core_async::test]
+    async fn test_encrypted_database_requires_right_key() {
+        let path = temp_database("encrypted-db");
+        let key = DatabaseKey::Passphrase("correct horse".to_string());
+
+        let pool = create_pool(DatabaseConfig::new(&path).encryption_key(key.clone()))
+            .await
+            .unwrap();
+        insert_test_provider(&pool).await;
+        pool.close().await;
+
+        let wrong = DatabaseConfig::new(&path)
+            .encryption_key(DatabaseKey::Passphrase("battery staple".to_string()));
+        assert!(create_pool(wrong).await.is_err());
+        assert!(create_pool(DatabaseConfig::new(&path)).await.is_err());
+
+        let pool = create_pool(DatabaseConfig::new(&path).encryption_key(key))
+            .await
+            .unwrap();
+        assert_eq!(provider_count(&pool).await, 1);
+        pool.close().await;
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[cfg(feature = "sqlcipher")]
+    #[core_async::test]
+    async fn test_encrypt_existing_plaintext_database() {
+        let path = temp_database("plaintext-db").with_file_name("library #1.db");
+        let key = DatabaseKey::Raw([7; 32]);
+
+        let pool = create_pool(DatabaseConfig::new(&path)).await.unwrap();
+        insert_test_provider(&pool).await;
+        pool.close().await;
+
+        encrypt_database(&path, &key).await.unwrap();
+
+        assert!(create_pool(DatabaseConfig::new(&path)).await.is_err());
+        let pool = create_pool(DatabaseConfig::new(&path).encryption_key(key))
+            .await
+            .unwrap();
+        assert_eq!(provider_count(&pool).await, 1);
+        pool.close().await;
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
 }
@@ -16,6 +16,9 @@ pub enum LibraryError {
     #[error("Invalid input: {field} - {message}")]
     InvalidInput { field: String, message: String },
 
+    #[error("Cannot open database {url}: {reason}")]
+    DatabaseUnavailable { url: String, reason: String },
+
     #[error("Migration failed: {0}")]
     Migration(String),
 
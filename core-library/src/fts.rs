@@ -0,0 +1,390 @@
+//! # Full-Text Search Index Management
+//!
+//! The `tracks_fts`, `albums_fts` and `artists_fts` tables are FTS5 indexes kept
+//! in sync with their source tables by triggers. Their tokenizer decides how
+//! search behaves (accent folding, substring matching, stemming), and FTS5 fixes
+//! it when a table is created.
+//!
+//! The indexes store the `normalized_*` columns rather than the display names,
+//! so width and kana variants are folded before indexing (see [`crate::text`]).
+//!
+//! The migrations create the indexes with SQLite's built-in default tokenizer
+//! over the display names. [`ensure_fts_tokenizer`] runs after the migrations
+//! and rebuilds the indexes whenever they use the old layout or the configured
+//! [`FtsTokenizer`] differs from the one in use. This is the migration path for
+//! changing tokenizers: update `DatabaseConfig::fts_tokenizer` and the next
+//! startup rebuilds the indexes from the `tracks`, `albums` and `artists` tables.
+//!
+//! A rebuild is idempotent. If it is interrupted, the indexes no longer agree on
+//! a tokenizer and the next call rebuilds them again.
+
+use crate::error::Result;
+use crate::text::normalize_search_text;
+use bridge_traits::database::{DatabaseAdapter, FtsTokenizer, QueryValue};
+use tracing::info;
+
+/// FTS tables managed by this module
+const FTS_TABLES: [&str; 3] = ["tracks_fts", "albums_fts", "artists_fts"];
+
+/// Tokenizer shared by all library search indexes
+///
+/// Returns `None` if the indexes are missing, use different tokenizers, or use
+/// a tokenizer that is not an [`FtsTokenizer`] (such as the migration default).
+pub async fn current_tokenizer(adapter: &dyn DatabaseAdapter) -> Result<Option<FtsTokenizer>> {
+    let mut current = None;
+    for table in FTS_TABLES {
+        let row = adapter
+            .query_one_optional(
+                "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ?",
+                &[QueryValue::Text(table.to_string())],
+            )
+            .await?;
+
+        let tokenizer = row
+            .as_ref()
+            .and_then(|row| row.get("sql"))
+            .and_then(|sql| sql.as_str())
+            .and_then(tokenize_option)
+            .and_then(FtsTokenizer::from_tokenize_option);
+
+        match (tokenizer, current) {
+            (None, _) => return Ok(None),
+            (Some(tokenizer), Some(previous)) if tokenizer != previous => return Ok(None),
+            (Some(tokenizer), _) => current = Some(tokenizer),
+        }
+    }
+    Ok(current)
+}
+
+/// Make sure the search indexes use `tokenizer`, rebuilding them if needed
+///
+/// Returns `true` if the indexes were rebuilt.
+pub async fn ensure_fts_tokenizer(
+    adapter: &dyn DatabaseAdapter,
+    tokenizer: FtsTokenizer,
+) -> Result<bool> {
+    let indexes_normalized = indexes_normalized_columns(adapter).await?;
+    if indexes_normalized && current_tokenizer(adapter).await? == Some(tokenizer) {
+        return Ok(false);
+    }
+
+    if !indexes_normalized {
+        fold_normalized_columns(adapter).await?;
+    }
+    rebuild_fts_indexes(adapter, tokenizer).await?;
+    Ok(true)
+}
+
+/// Whether the index triggers store the `normalized_*` columns
+async fn indexes_normalized_columns(adapter: &dyn DatabaseAdapter) -> Result<bool> {
+    let row = adapter
+        .query_one_optional(
+            "SELECT sql FROM sqlite_master WHERE type = 'trigger' AND name = 'tracks_fts_insert'",
+            &[],
+        )
+        .await?;
+    Ok(row
+        .as_ref()
+        .and_then(|row| row.get("sql"))
+        .and_then(|sql| sql.as_str())
+        .is_some_and(|sql| sql.contains("new.normalized_title")))
+}
+
+/// Re-apply [`normalize_search_text`] to rows normalized before width and kana folding
+///
+/// Only non-ASCII values can change. Artists whose folded name collides with
+/// an existing artist keep their old value.
+async fn fold_normalized_columns(adapter: &dyn DatabaseAdapter) -> Result<()> {
+    const COLUMNS: [(&str, &str, &str); 4] = [
+        ("tracks", "normalized_title", "UPDATE"),
+        ("albums", "normalized_name", "UPDATE"),
+        ("artists", "normalized_name", "UPDATE OR IGNORE"),
+        ("playlists", "normalized_name", "UPDATE"),
+    ];
+
+    for (table, column, update) in COLUMNS {
+        let rows = adapter
+            .query(
+                &format!(
+                    "SELECT id, {column} AS value FROM {table} WHERE {column} GLOB '*[^ -~]*'"
+                ),
+                &[],
+            )
+            .await?;
+
+        for row in rows {
+            let (Some(id), Some(value)) = (
+                row.get("id").and_then(|value| value.as_str()),
+                row.get("value").and_then(|value| value.as_str()),
+            ) else {
+                continue;
+            };
+
+            let folded = normalize_search_text(value);
+            if folded != value {
+                adapter
+                    .execute(
+                        &format!("{update} {table} SET {column} = ? WHERE id = ?"),
+                        &[QueryValue::Text(folded), QueryValue::Text(id.to_string())],
+                    )
+                    .await?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Recreate all search indexes with `tokenizer` and repopulate them
+pub async fn rebuild_fts_indexes(
+    adapter: &dyn DatabaseAdapter,
+    tokenizer: FtsTokenizer,
+) -> Result<()> {
+    info!(tokenizer = ?tokenizer, "Rebuilding full-text search indexes");
+
+    let option = tokenizer.tokenize_option();
+    let create_tracks = format!(
+        "CREATE VIRTUAL TABLE tracks_fts USING fts5(
+            track_id UNINDEXED, title, artist_name, album_name, genre,
+            tokenize = '{option}'
+        )"
+    );
+    let create_albums = format!(
+        "CREATE VIRTUAL TABLE albums_fts USING fts5(
+            album_id UNINDEXED, name, artist_name, genre,
+            tokenize = '{option}'
+        )"
+    );
+    let create_artists = format!(
+        "CREATE VIRTUAL TABLE artists_fts USING fts5(
+            artist_id UNINDEXED, name,
+            tokenize = '{option}'
+        )"
+    );
+
+    let mut statements: Vec<&str> = DROP_FTS_SQL.to_vec();
+    statements.extend([
+        create_tracks.as_str(),
+        create_albums.as_str(),
+        create_artists.as_str(),
+    ]);
+    statements.extend(POPULATE_FTS_SQL);
+    statements.extend(FTS_TRIGGERS_SQL);
+
+    let batch: Vec<(&str, &[QueryValue])> = statements
+        .into_iter()
+        .map(|sql| (sql, &[] as &[QueryValue]))
+        .collect();
+    adapter.execute_batch(&batch).await?;
+
+    info!(tokenizer = ?tokenizer, "Full-text search indexes rebuilt");
+    Ok(())
+}
+
+/// Extract the quoted value of the `tokenize` option from a `CREATE VIRTUAL TABLE` statement
+fn tokenize_option(create_sql: &str) -> Option<&str> {
+    let start = create_sql.find("tokenize")?;
+    let rest = &create_sql[start..];
+    let open = rest.find('\'')? + 1;
+    let close = rest[open..].find('\'')?;
+    Some(&rest[open..open + close])
+}
+
+const DROP_FTS_SQL: [&str; 12] = [
+    "DROP TRIGGER IF EXISTS tracks_fts_insert",
+    "DROP TRIGGER IF EXISTS tracks_fts_update",
+    "DROP TRIGGER IF EXISTS tracks_fts_delete",
+    "DROP TRIGGER IF EXISTS albums_fts_insert",
+    "DROP TRIGGER IF EXISTS albums_fts_update",
+    "DROP TRIGGER IF EXISTS albums_fts_delete",
+    "DROP TRIGGER IF EXISTS artists_fts_insert",
+    "DROP TRIGGER IF EXISTS artists_fts_update",
+    "DROP TRIGGER IF EXISTS artists_fts_delete",
+    "DROP TABLE IF EXISTS tracks_fts",
+    "DROP TABLE IF EXISTS albums_fts",
+    "DROP TABLE IF EXISTS artists_fts",
+];
+
+const POPULATE_FTS_SQL: [&str; 3] = [
+    "INSERT INTO tracks_fts(rowid, track_id, title, artist_name, album_name, genre)
+     SELECT
+        t.rowid,
+        t.id,
+        t.normalized_title,
+        COALESCE(ar.normalized_name, ''),
+        COALESCE(al.normalized_name, ''),
+        COALESCE(t.genre, '')
+     FROM tracks t
+     LEFT JOIN artists ar ON ar.id = t.artist_id
+     LEFT JOIN albums al ON al.id = t.album_id",
+    "INSERT INTO albums_fts(rowid, album_id, name, artist_name, genre)
+     SELECT
+        a.rowid,
+        a.id,
+        a.normalized_name,
+        COALESCE(ar.normalized_name, ''),
+        COALESCE(a.genre, '')
+     FROM albums a
+     LEFT JOIN artists ar ON ar.id = a.artist_id",
+    "INSERT INTO artists_fts(rowid, artist_id, name)
+     SELECT rowid, id, normalized_name FROM artists",
+];
+
+// Same shape as the migration triggers (albums as of 002_add_model_fields),
+// but indexing the normalized columns
+const FTS_TRIGGERS_SQL: [&str; 9] = [
+    "CREATE TRIGGER tracks_fts_insert AFTER INSERT ON tracks BEGIN
+        INSERT INTO tracks_fts(rowid, track_id, title, artist_name, album_name, genre)
+        SELECT
+            new.rowid,
+            new.id,
+            new.normalized_title,
+            COALESCE((SELECT normalized_name FROM artists WHERE id = new.artist_id), ''),
+            COALESCE((SELECT normalized_name FROM albums WHERE id = new.album_id), ''),
+            COALESCE(new.genre, '');
+    END",
+    "CREATE TRIGGER tracks_fts_update AFTER UPDATE ON tracks BEGIN
+        UPDATE tracks_fts
+        SET
+            title = new.normalized_title,
+            artist_name = COALESCE((SELECT normalized_name FROM artists WHERE id = new.artist_id), ''),
+            album_name = COALESCE((SELECT normalized_name FROM albums WHERE id = new.album_id), ''),
+            genre = COALESCE(new.genre, '')
+        WHERE rowid = new.rowid;
+    END",
+    "CREATE TRIGGER tracks_fts_delete AFTER DELETE ON tracks BEGIN
+        DELETE FROM tracks_fts WHERE rowid = old.rowid;
+    END",
+    "CREATE TRIGGER albums_fts_insert AFTER INSERT ON albums BEGIN
+        INSERT INTO albums_fts(rowid, album_id, name, artist_name, genre)
+        SELECT
+            new.rowid,
+            new.id,
+            new.normalized_name,
+            COALESCE((SELECT normalized_name FROM artists WHERE id = new.artist_id), ''),
+            COALESCE(new.genre, '');
+    END",
+    "CREATE TRIGGER albums_fts_update AFTER UPDATE ON albums BEGIN
+        UPDATE albums_fts
+        SET
+            name = new.normalized_name,
+            artist_name = COALESCE((SELECT normalized_name FROM artists WHERE id = new.artist_id), ''),
+            genre = COALESCE(new.genre, '')
+        WHERE rowid = new.rowid;
+    END",
+    "CREATE TRIGGER albums_fts_delete AFTER DELETE ON albums BEGIN
+        DELETE FROM albums_fts WHERE rowid = old.rowid;
+    END",
+    "CREATE TRIGGER artists_fts_insert AFTER INSERT ON artists BEGIN
+        INSERT INTO artists_fts(rowid, artist_id, name)
+        VALUES (new.rowid, new.id, new.normalized_name);
+    END",
+    "CREATE TRIGGER artists_fts_update AFTER UPDATE ON artists BEGIN
+        UPDATE artists_fts SET name = new.normalized_name WHERE rowid = new.rowid;
+    END",
+    "CREATE TRIGGER artists_fts_delete AFTER DELETE ON artists BEGIN
+        DELETE FROM artists_fts WHERE rowid = old.rowid;
+    END",
+];
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::adapters::SqliteAdapter;
+    use crate::db::{create_pool, insert_test_provider, DatabaseConfig};
+    use crate::models::Track;
+    use crate::query::LibraryQueryService;
+    use crate::repositories::{SqliteTrackRepository, TrackRepository};
+    use sqlx::SqlitePool;
+
+    async fn pool_with(tokenizer: FtsTokenizer) -> SqlitePool {
+        let pool = create_pool(DatabaseConfig::in_memory().fts_tokenizer(tokenizer))
+            .await
+            .unwrap();
+        insert_test_provider(&pool).await;
+        pool
+    }
+
+    async fn insert_titled_track(pool: &SqlitePool, id: &str, title: &str) {
+        let mut track = Track::new(
+            title.to_string(),
+            "test-provider".to_string(),
+            format!("file-{id}"),
+            180_000,
+            1,
+        );
+        track.id = id.to_string();
+        track.lyrics_status = "not_fetched".to_string();
+        track.format = "mp3".to_string();
+        SqliteTrackRepository::from_pool(pool.clone())
+            .insert(&track)
+            .await
+            .unwrap();
+    }
+
+    async fn search_track_ids(pool: &SqlitePool, query: &str) -> Vec<String> {
+        LibraryQueryService::from_pool(pool.clone())
+            .search(query)
+            .await
+            .unwrap()
+            .tracks
+            .into_iter()
+            .map(|item| item.track.id)
+            .collect()
+    }
+
+    #[test]
+    fn tokenize_option_is_parsed_from_create_sql() {
+        let sql = "CREATE VIRTUAL TABLE t USING fts5(a, tokenize = 'porter unicode61 remove_diacritics 2')";
+        assert_eq!(
+            tokenize_option(sql),
+            Some("porter unicode61 remove_diacritics 2")
+        );
+        assert_eq!(
+            tokenize_option("CREATE VIRTUAL TABLE t USING fts5(a)"),
+            None
+        );
+    }
+
+    #[core_async::test]
+    async fn diacritic_insensitive_search_matches_accented_titles() {
+        for tokenizer in [FtsTokenizer::Unicode61, FtsTokenizer::Trigram] {
+            let pool = pool_with(tokenizer).await;
+            insert_titled_track(&pool, "halo", "Beyoncé Halo").await;
+
+            assert_eq!(search_track_ids(&pool, "beyonce").await, vec!["halo"]);
+        }
+    }
+
+    #[core_async::test]
+    async fn create_pool_applies_configured_tokenizer() {
+        let pool = pool_with(FtsTokenizer::Porter).await;
+        let adapter = SqliteAdapter::from_pool(pool.clone());
+        assert_eq!(
+            current_tokenizer(&adapter).await.unwrap(),
+            Some(FtsTokenizer::Porter)
+        );
+
+        insert_titled_track(&pool, "singing", "Singing in the Rain").await;
+        assert_eq!(search_track_ids(&pool, "sing").await, vec!["singing"]);
+    }
+
+    #[core_async::test]
+    async fn changing_tokenizer_rebuilds_existing_index() {
+        let pool = pool_with(FtsTokenizer::Unicode61).await;
+        insert_titled_track(&pool, "wonderwall", "Wonderwall").await;
+        assert!(search_track_ids(&pool, "wonder").await.is_empty());
+
+        let adapter = SqliteAdapter::from_pool(pool.clone());
+        assert!(ensure_fts_tokenizer(&adapter, FtsTokenizer::Trigram)
+            .await
+            .unwrap());
+        assert!(!ensure_fts_tokenizer(&adapter, FtsTokenizer::Trigram)
+            .await
+            .unwrap());
+
+        // Existing rows are reindexed and new rows are picked up by the triggers
+        assert_eq!(search_track_ids(&pool, "wonder").await, vec!["wonderwall"]);
+        insert_titled_track(&pool, "wonderland", "Wonderland").await;
+        assert_eq!(search_track_ids(&pool, "wonder").await.len(), 2);
+    }
+}
@@ -0,0 +1,864 @@
+//! # Mutation Journal
+//!
+//! Undo/redo support for user-initiated library edits.
+//!
+//! ## Overview
+//!
+//! Every mutation routed through [`MutationJournal`] captures the prior state of
+//! the rows it touches before applying the change. The captured state is kept
+//! as a list of reversible [`MutationOp`]s so that `undo()` can replay the
+//! inverse operations and `redo()` can re-apply the original ones.
+//!
+//! The journal is intentionally scoped to user edits (field edits, merges and
+//! deletes). Sync writes go straight to the repositories and are never
+//! recorded, which keeps the journal small and bounded by `capacity`.
+//!
+//! ## Atomicity
+//!
+//! `update_fields`, `delete_rows` and `merge` read the prior state of each row
+//! and apply the change inside one database transaction, so a concurrent write
+//! can't slip in between the read and the write and later be clobbered by an
+//! undo. If anything fails the transaction is rolled back and nothing is
+//! recorded. Undo, redo and [`MutationJournal::record`] replay already-captured
+//! operations in a transaction too; if one fails, none of them are applied and
+//! the undo and redo stacks are left as they were.
+//!
+//! ## Usage
+//!
+//! ```ignore
+//! use core_library::journal::{JournalTable, MutationJournal};
+//! use bridge_traits::database::QueryValue;
+//!
+//! let journal = MutationJournal::new(adapter);
+//! journal
+//!     .update_fields(
+//!         "Set genre to Jazz",
+//!         JournalTable::Tracks,
+//!         &track_ids,
+//!         vec![("genre".to_string(), QueryValue::Text("Jazz".into()))],
+//!     )
+//!     .await?;
+//!
+//! journal.undo().await?; // genres restored
+//! journal.redo().await?; // genres set to Jazz again
+//! ```
+
+use crate::error::{LibraryError, Result};
+use crate::repositories::PlatformArc;
+use bridge_traits::database::{DatabaseAdapter, QueryRow, QueryValue, TransactionId};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tracing::debug;
+
+/// Default number of mutations kept on the undo stack.
+pub const DEFAULT_JOURNAL_CAPACITY: usize = 100;
+
+/// Ordered list of `(column, value)` pairs captured from a row.
+pub type Columns = Vec<(String, QueryValue)>;
+
+/// Library tables whose rows can be journaled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JournalTable {
+    Tracks,
+    Albums,
+    Artists,
+    Playlists,
+}
+
+impl JournalTable {
+    /// SQL table name.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JournalTable::Tracks => "tracks",
+            JournalTable::Albums => "albums",
+            JournalTable::Artists => "artists",
+            JournalTable::Playlists => "playlists",
+        }
+    }
+}
+
+/// A single reversible row-level operation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MutationOp {
+    /// Columns of a row changed from `before` to `after`.
+    Update {
+        table: JournalTable,
+        id: String,
+        before: Columns,
+        after: Columns,
+    },
+    /// A full row was inserted.
+    Insert { table: JournalTable, row: Columns },
+    /// A full row was deleted; `row` holds its prior state.
+    Delete { table: JournalTable, row: Columns },
+}
+
+impl MutationOp {
+    /// Returns the operation that reverts this one.
+    pub fn inverse(&self) -> MutationOp {
+        match self {
+            MutationOp::Update {
+                table,
+                id,
+                before,
+                after,
+            } => MutationOp::Update {
+                table: *table,
+                id: id.clone(),
+                before: after.clone(),
+                after: before.clone(),
+            },
+            MutationOp::Insert { table, row } => MutationOp::Delete {
+                table: *table,
+                row: row.clone(),
+            },
+            MutationOp::Delete { table, row } => MutationOp::Insert {
+                table: *table,
+                row: row.clone(),
+            },
+        }
+    }
+
+    fn to_statement(&self) -> Result<(String, Vec<QueryValue>)> {
+        match self {
+            MutationOp::Update {
+                table, id, after, ..
+            } => {
+                let assignments = after
+                    .iter()
+                    .map(|(column, _)| format!("{column} = ?"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let mut params: Vec<QueryValue> =
+                    after.iter().map(|(_, value)| value.clone()).collect();
+                params.push(QueryValue::Text(id.clone()));
+                Ok((
+                    format!("UPDATE {} SET {assignments} WHERE id = ?", table.as_str()),
+                    params,
+                ))
+            }
+            MutationOp::Insert { table, row } => {
+                let columns = row
+                    .iter()
+                    .map(|(column, _)| column.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let placeholders = vec!["?"; row.len()].join(", ");
+                Ok((
+                    format!(
+                        "INSERT INTO {} ({columns}) VALUES ({placeholders})",
+                        table.as_str()
+                    ),
+                    row.iter().map(|(_, value)| value.clone()).collect(),
+                ))
+            }
+            MutationOp::Delete { table, row } => {
+                let id = row
+                    .iter()
+                    .find(|(column, _)| column == "id")
+                    .map(|(_, value)| value.clone())
+                    .ok_or_else(|| LibraryError::InvalidInput {
+                        field: "id".to_string(),
+                        message: "journaled row is missing its id column".to_string(),
+                    })?;
+                Ok((
+                    format!("DELETE FROM {} WHERE id = ?", table.as_str()),
+                    vec![id],
+                ))
+            }
+        }
+    }
+}
+
+/// A user-visible mutation made of one or more row operations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mutation {
+    /// Human-readable description (e.g. "Set genre to Jazz").
+    pub label: String,
+    /// Operations in the order they were applied.
+    pub ops: Vec<MutationOp>,
+}
+
+impl Mutation {
+    /// Returns the mutation that reverts this one (inverse ops in reverse order).
+    pub fn inverse(&self) -> Mutation {
+        Mutation {
+            label: self.label.clone(),
+            ops: self.ops.iter().rev().map(MutationOp::inverse).collect(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct JournalState {
+    undo: VecDeque<Mutation>,
+    redo: Vec<Mutation>,
+}
+
+/// Records reversible library mutations and replays them for undo/redo.
+pub struct MutationJournal {
+    adapter: PlatformArc<dyn DatabaseAdapter>,
+    state: Mutex<JournalState>,
+    capacity: usize,
+}
+
+impl MutationJournal {
+    /// Create a journal holding up to [`DEFAULT_JOURNAL_CAPACITY`] mutations.
+    pub fn new(adapter: PlatformArc<dyn DatabaseAdapter>) -> Self {
+        Self::with_capacity(adapter, DEFAULT_JOURNAL_CAPACITY)
+    }
+
+    /// Create a journal holding up to `capacity` mutations. Older entries are
+    /// dropped once the limit is reached.
+    pub fn with_capacity(adapter: PlatformArc<dyn DatabaseAdapter>, capacity: usize) -> Self {
+        Self {
+            adapter,
+            state: Mutex::new(JournalState::default()),
+            capacity: capacity.max(1),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Create a journal from a SQLite connection pool (native only).
+    pub fn from_pool(pool: sqlx::SqlitePool) -> Self {
+        use crate::adapters::sqlite_native::SqliteAdapter;
+        Self::new(PlatformArc::new(SqliteAdapter::from_pool(pool)))
+    }
+
+    /// Set `changes` on every row in `ids`, recording the previous values.
+    ///
+    /// `updated_at` is bumped automatically. Returns the number of rows changed.
+    pub async fn update_fields(
+        &self,
+        label: impl Into<String>,
+        table: JournalTable,
+        ids: &[String],
+        changes: Columns,
+    ) -> Result<usize> {
+        if changes.is_empty() {
+            return Err(LibraryError::InvalidInput {
+                field: "changes".to_string(),
+                message: "at least one field must be changed".to_string(),
+            });
+        }
+
+        let mut after = changes;
+        after.retain(|(column, _)| column != "updated_at");
+        after.push((
+            "updated_at".to_string(),
+            QueryValue::Integer(chrono::Utc::now().timestamp()),
+        ));
+        for (column, _) in &after {
+            validate_column(column)?;
+        }
+
+        let tx = self.adapter.begin_transaction().await?;
+        let ops = self
+            .finish(tx, self.update_fields_in(tx, table, ids, &after).await)
+            .await?;
+
+        let changed = ops.len();
+        self.push(Mutation {
+            label: label.into(),
+            ops,
+        });
+        Ok(changed)
+    }
+
+    /// Delete every row in `ids`, keeping their full prior state for undo.
+    ///
+    /// Rows referencing the deleted ones are subject to the schema's
+    /// `ON DELETE` rules, which are not journaled; use [`Self::merge`] to
+    /// re-point references first when they must survive an undo.
+    pub async fn delete_rows(
+        &self,
+        label: impl Into<String>,
+        table: JournalTable,
+        ids: &[String],
+    ) -> Result<usize> {
+        let tx = self.adapter.begin_transaction().await?;
+        let ops = self
+            .finish(tx, self.delete_rows_in(tx, table, ids).await)
+            .await?;
+
+        let deleted = ops.len();
+        self.push(Mutation {
+            label: label.into(),
+            ops,
+        });
+        Ok(deleted)
+    }
+
+    /// Merge `source_id` into `target_id`.
+    ///
+    /// All tracks (and, for artists, albums) referencing the source are
+    /// re-pointed at the target before the source row is deleted, so a single
+    /// `undo()` restores both the source and its references.
+    pub async fn merge(
+        &self,
+        label: impl Into<String>,
+        table: JournalTable,
+        source_id: &str,
+        target_id: &str,
+    ) -> Result<()> {
+        if source_id == target_id {
+            return Err(LibraryError::InvalidInput {
+                field: "target_id".to_string(),
+                message: "cannot merge an entity into itself".to_string(),
+            });
+        }
+
+        let references: &[(JournalTable, &str)] = match table {
+            JournalTable::Artists => &[
+                (JournalTable::Tracks, "artist_id"),
+                (JournalTable::Tracks, "album_artist_id"),
+                (JournalTable::Albums, "artist_id"),
+            ],
+            JournalTable::Albums => &[(JournalTable::Tracks, "album_id")],
+            JournalTable::Tracks | JournalTable::Playlists => {
+                return Err(LibraryError::InvalidInput {
+                    field: "table".to_string(),
+                    message: format!("merging {} is not supported", table.as_str()),
+                })
+            }
+        };
+
+        let tx = self.adapter.begin_transaction().await?;
+        let ops = self
+            .finish(
+                tx,
+                self.merge_in(tx, table, source_id, target_id, references)
+                    .await,
+            )
+            .await?;
+
+        self.push(Mutation {
+            label: label.into(),
+            ops,
+        });
+        Ok(())
+    }
+
+    /// Apply a pre-built mutation and push it onto the undo stack.
+    ///
+    /// Applying a new mutation clears the redo stack.
+    pub async fn record(&self, mutation: Mutation) -> Result<()> {
+        if mutation.ops.is_empty() {
+            return Ok(());
+        }
+
+        self.execute(&mutation).await?;
+        self.push(mutation);
+        Ok(())
+    }
+
+    /// Revert the most recent mutation.
+    ///
+    /// Returns the label of the reverted mutation, or `None` if there was
+    /// nothing to undo.
+    pub async fn undo(&self) -> Result<Option<String>> {
+        let Some(mutation) = self.lock_state().undo.pop_back() else {
+            return Ok(None);
+        };
+
+        if let Err(e) = self.execute(&mutation.inverse()).await {
+            self.lock_state().undo.push_back(mutation);
+            return Err(e);
+        }
+
+        debug!(label = %mutation.label, "Undid mutation");
+        let label = mutation.label.clone();
+        self.lock_state().redo.push(mutation);
+        Ok(Some(label))
+    }
+
+    /// Re-apply the most recently undone mutation.
+    ///
+    /// Returns the label of the re-applied mutation, or `None` if there was
+    /// nothing to redo.
+    pub async fn redo(&self) -> Result<Option<String>> {
+        let Some(mutation) = self.lock_state().redo.pop() else {
+            return Ok(None);
+        };
+
+        if let Err(e) = self.execute(&mutation).await {
+            self.lock_state().redo.push(mutation);
+            return Err(e);
+        }
+
+        debug!(label = %mutation.label, "Redid mutation");
+        let label = mutation.label.clone();
+        self.lock_state().undo.push_back(mutation);
+        Ok(Some(label))
+    }
+
+    /// Returns `true` if there is a mutation to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.lock_state().undo.is_empty()
+    }
+
+    /// Returns `true` if there is a mutation to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.lock_state().redo.is_empty()
+    }
+
+    /// Labels of undoable mutations, most recent first.
+    pub fn undo_labels(&self) -> Vec<String> {
+        self.lock_state()
+            .undo
+            .iter()
+            .rev()
+            .map(|mutation| mutation.label.clone())
+            .collect()
+    }
+
+    /// Drop all recorded history.
+    pub fn clear(&self) {
+        let mut state = self.lock_state();
+        state.undo.clear();
+        state.redo.clear();
+    }
+
+    fn push(&self, mutation: Mutation) {
+        if mutation.ops.is_empty() {
+            return;
+        }
+        debug!(label = %mutation.label, ops = mutation.ops.len(), "Recorded mutation");
+
+        let mut state = self.lock_state();
+        state.redo.clear();
+        state.undo.push_back(mutation);
+        while state.undo.len() > self.capacity {
+            state.undo.pop_front();
+        }
+    }
+
+    /// Commit `tx` if the work done in it succeeded, roll it back otherwise.
+    async fn finish<T>(&self, tx: TransactionId, result: Result<T>) -> Result<T> {
+        match result {
+            Ok(value) => {
+                self.adapter.commit_transaction(tx).await?;
+                Ok(value)
+            }
+            Err(e) => {
+                let _ = self.adapter.rollback_transaction(tx).await;
+                Err(e)
+            }
+        }
+    }
+
+    async fn update_fields_in(
+        &self,
+        tx: TransactionId,
+        table: JournalTable,
+        ids: &[String],
+        after: &Columns,
+    ) -> Result<Vec<MutationOp>> {
+        let columns: Vec<&str> = after.iter().map(|(column, _)| column.as_str()).collect();
+
+        let mut ops = Vec::with_capacity(ids.len());
+        for id in ids {
+            let row = self.fetch_row(tx, table, id, &columns.join(", ")).await?;
+            let before = columns
+                .iter()
+                .map(|column| {
+                    let value = row.get(*column).cloned().unwrap_or(QueryValue::Null);
+                    (column.to_string(), value)
+                })
+                .collect();
+            let op = MutationOp::Update {
+                table,
+                id: id.clone(),
+                before,
+                after: after.clone(),
+            };
+            self.apply(tx, &op).await?;
+            ops.push(op);
+        }
+        Ok(ops)
+    }
+
+    async fn delete_rows_in(
+        &self,
+        tx: TransactionId,
+        table: JournalTable,
+        ids: &[String],
+    ) -> Result<Vec<MutationOp>> {
+        let mut ops = Vec::with_capacity(ids.len());
+        for id in ids {
+            let row = self.fetch_row(tx, table, id, "*").await?;
+            let op = MutationOp::Delete {
+                table,
+                row: sorted_columns(row),
+            };
+            self.apply(tx, &op).await?;
+            ops.push(op);
+        }
+        Ok(ops)
+    }
+
+    async fn merge_in(
+        &self,
+        tx: TransactionId,
+        table: JournalTable,
+        source_id: &str,
+        target_id: &str,
+        references: &[(JournalTable, &str)],
+    ) -> Result<Vec<MutationOp>> {
+        // Make sure the target exists before touching anything.
+        self.fetch_row(tx, table, target_id, "id").await?;
+        let source = self.fetch_row(tx, table, source_id, "*").await?;
+
+        let now = QueryValue::Integer(chrono::Utc::now().timestamp());
+        let mut ops = Vec::new();
+        for (ref_table, column) in references {
+            let rows = self
+                .adapter
+                .query_in_transaction(
+                    tx,
+                    &format!(
+                        "SELECT id, updated_at FROM {} WHERE {column} = ?",
+                        ref_table.as_str()
+                    ),
+                    &[QueryValue::Text(source_id.to_string())],
+                )
+                .await?;
+            for row in rows {
+                let id = row
+                    .get("id")
+                    .and_then(|value| value.as_string())
+                    .ok_or_else(|| missing_column("id"))?;
+                let updated_at = row.get("updated_at").cloned().unwrap_or(QueryValue::Null);
+                let op = MutationOp::Update {
+                    table: *ref_table,
+                    id,
+                    before: vec![
+                        (column.to_string(), QueryValue::Text(source_id.to_string())),
+                        ("updated_at".to_string(), updated_at),
+                    ],
+                    after: vec![
+                        (column.to_string(), QueryValue::Text(target_id.to_string())),
+                        ("updated_at".to_string(), now.clone()),
+                    ],
+                };
+                self.apply(tx, &op).await?;
+                ops.push(op);
+            }
+        }
+        let op = MutationOp::Delete {
+            table,
+            row: sorted_columns(source),
+        };
+        self.apply(tx, &op).await?;
+        ops.push(op);
+        Ok(ops)
+    }
+
+    async fn apply(&self, tx: TransactionId, op: &MutationOp) -> Result<()> {
+        let (sql, params) = op.to_statement()?;
+        self.adapter
+            .execute_in_transaction(tx, &sql, &params)
+            .await?;
+        Ok(())
+    }
+
+    async fn execute(&self, mutation: &Mutation) -> Result<()> {
+        let tx = self.adapter.begin_transaction().await?;
+        self.finish(tx, self.apply_all(tx, &mutation.ops).await)
+            .await
+    }
+
+    async fn apply_all(&self, tx: TransactionId, ops: &[MutationOp]) -> Result<()> {
+        for op in ops {
+            self.apply(tx, op).await?;
+        }
+        Ok(())
+    }
+
+    async fn fetch_row(
+        &self,
+        tx: TransactionId,
+        table: JournalTable,
+        id: &str,
+        columns: &str,
+    ) -> Result<QueryRow> {
+        self.adapter
+            .query_in_transaction(
+                tx,
+                &format!("SELECT {columns} FROM {} WHERE id = ?", table.as_str()),
+                &[QueryValue::Text(id.to_string())],
+            )
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| LibraryError::NotFound {
+                entity_type: table.as_str().to_string(),
+                id: id.to_string(),
+            })
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, JournalState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn sorted_columns(row: QueryRow) -> Columns {
+    let mut columns: Columns = row.into_iter().collect();
+    columns.sort_by(|(a, _), (b, _)| a.cmp(b));
+    columns
+}
+
+fn validate_column(column: &str) -> Result<()> {
+    let valid = !column.is_empty()
+        && column
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(LibraryError::InvalidInput {
+            field: column.to_string(),
+            message: "invalid column name".to_string(),
+        })
+    }
+}
+
+fn missing_column(column: &str) -> LibraryError {
+    LibraryError::InvalidInput {
+        field: column.to_string(),
+        message: "missing column in result set".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{create_test_pool, insert_test_provider};
+    use crate::models::{Album, Artist, Track};
+    use crate::repositories::{
+        AlbumRepository, ArtistRepository, SqliteAlbumRepository, SqliteArtistRepository,
+        SqliteTrackRepository, TrackRepository,
+    };
+
+    fn make_track(id: &str, genre: &str) -> Track {
+        let mut track = Track::new(
+            format!("Track {id}"),
+            "test-provider".to_string(),
+            format!("file-{id}"),
+            180_000,
+            1,
+        );
+        track.id = id.to_string();
+        track.lyrics_status = "not_fetched".to_string();
+        track.genre = Some(genre.to_string());
+        track.format = "mp3".to_string();
+        track
+    }
+
+    #[core_async::test]
+    async fn test_bulk_genre_edit_undo_redo() {
+        let pool = create_test_pool().await.unwrap();
+        insert_test_provider(&pool).await;
+        let tracks = SqliteTrackRepository::from_pool(pool.clone());
+        tracks.insert(&make_track("t1", "Rock")).await.unwrap();
+        tracks.insert(&make_track("t2", "Pop")).await.unwrap();
+
+        let journal = MutationJournal::from_pool(pool);
+        let ids = vec!["t1".to_string(), "t2".to_string()];
+        let changed = journal
+            .update_fields(
+                "Set genre to Jazz",
+                JournalTable::Tracks,
+                &ids,
+                vec![("genre".to_string(), QueryValue::Text("Jazz".into()))],
+            )
+            .await
+            .unwrap();
+        assert_eq!(changed, 2);
+
+        let genre = |track: Option<Track>| track.unwrap().genre.unwrap();
+        assert_eq!(genre(tracks.find_by_id("t1").await.unwrap()), "Jazz");
+        assert_eq!(genre(tracks.find_by_id("t2").await.unwrap()), "Jazz");
+
+        assert_eq!(
+            journal.undo().await.unwrap().as_deref(),
+            Some("Set genre to Jazz")
+        );
+        assert_eq!(genre(tracks.find_by_id("t1").await.unwrap()), "Rock");
+        assert_eq!(genre(tracks.find_by_id("t2").await.unwrap()), "Pop");
+        assert!(journal.can_redo());
+
+        journal.redo().await.unwrap();
+        assert_eq!(genre(tracks.find_by_id("t1").await.unwrap()), "Jazz");
+        assert_eq!(genre(tracks.find_by_id("t2").await.unwrap()), "Jazz");
+        assert!(!journal.can_redo());
+    }
+
+    #[core_async::test]
+    async fn test_failed_edit_rolls_back_and_records_nothing() {
+        let pool = create_test_pool().await.unwrap();
+        insert_test_provider(&pool).await;
+        let tracks = SqliteTrackRepository::from_pool(pool.clone());
+        tracks.insert(&make_track("t1", "Rock")).await.unwrap();
+
+        let journal = MutationJournal::from_pool(pool);
+        let result = journal
+            .update_fields(
+                "Set genre to Jazz",
+                JournalTable::Tracks,
+                &["t1".to_string(), "missing".to_string()],
+                vec![("genre".to_string(), QueryValue::Text("Jazz".into()))],
+            )
+            .await;
+        assert!(matches!(result, Err(LibraryError::NotFound { .. })));
+
+        let track = tracks.find_by_id("t1").await.unwrap().unwrap();
+        assert_eq!(track.genre.as_deref(), Some("Rock"));
+        assert!(!journal.can_undo());
+    }
+
+    #[core_async::test]
+    async fn test_failed_undo_applies_nothing() {
+        let pool = create_test_pool().await.unwrap();
+        insert_test_provider(&pool).await;
+        let tracks = SqliteTrackRepository::from_pool(pool.clone());
+        tracks.insert(&make_track("t1", "Jazz")).await.unwrap();
+        tracks.insert(&make_track("t2", "Jazz")).await.unwrap();
+
+        // Undo reverts t1 first, then fails on t2's unknown column
+        let journal = MutationJournal::from_pool(pool);
+        let update = |id: &str, before: &str| MutationOp::Update {
+            table: JournalTable::Tracks,
+            id: id.to_string(),
+            before: vec![(before.to_string(), QueryValue::Text("Rock".into()))],
+            after: vec![("genre".to_string(), QueryValue::Text("Jazz".into()))],
+        };
+        journal
+            .record(Mutation {
+                label: "Set genre to Jazz".to_string(),
+                ops: vec![update("t2", "no_such_column"), update("t1", "genre")],
+            })
+            .await
+            .unwrap();
+
+        assert!(journal.undo().await.is_err());
+        let track = tracks.find_by_id("t1").await.unwrap().unwrap();
+        assert_eq!(track.genre.as_deref(), Some("Jazz"));
+        assert_eq!(journal.undo_labels(), vec!["Set genre to Jazz".to_string()]);
+        assert!(!journal.can_redo());
+    }
+
+    #[core_async::test]
+    async fn test_delete_undo_restores_row() {
+        let pool = create_test_pool().await.unwrap();
+        insert_test_provider(&pool).await;
+        let tracks = SqliteTrackRepository::from_pool(pool.clone());
+        let original = make_track("t1", "Rock");
+        tracks.insert(&original).await.unwrap();
+
+        let journal = MutationJournal::from_pool(pool);
+        journal
+            .delete_rows("Delete track", JournalTable::Tracks, &["t1".to_string()])
+            .await
+            .unwrap();
+        assert!(tracks.find_by_id("t1").await.unwrap().is_none());
+
+        journal.undo().await.unwrap();
+        assert_eq!(tracks.find_by_id("t1").await.unwrap(), Some(original));
+    }
+
+    #[core_async::test]
+    async fn test_merge_artists_undo() {
+        let pool = create_test_pool().await.unwrap();
+        insert_test_provider(&pool).await;
+        let artists = SqliteArtistRepository::from_pool(pool.clone());
+        let albums = SqliteAlbumRepository::from_pool(pool.clone());
+        let tracks = SqliteTrackRepository::from_pool(pool.clone());
+
+        let source = Artist::new("The Beatles".to_string());
+        let target = Artist::new("Beatles".to_string());
+        artists.insert(&source).await.unwrap();
+        artists.insert(&target).await.unwrap();
+        let album = Album::new("Abbey Road".to_string(), Some(source.id.clone()));
+        albums.insert(&album).await.unwrap();
+        let mut track = make_track("t1", "Rock");
+        track.artist_id = Some(source.id.clone());
+        tracks.insert(&track).await.unwrap();
+
+        let journal = MutationJournal::from_pool(pool);
+        journal
+            .merge("Merge artists", JournalTable::Artists, &source.id, &target.id)
+            .await
+            .unwrap();
+
+        assert!(artists.find_by_id(&source.id).await.unwrap().is_none());
+        let merged = tracks.find_by_id("t1").await.unwrap().unwrap();
+        assert_eq!(merged.artist_id.as_deref(), Some(target.id.as_str()));
+
+        journal.undo().await.unwrap();
+        assert!(artists.find_by_id(&source.id).await.unwrap().is_some());
+        let restored = tracks.find_by_id("t1").await.unwrap().unwrap();
+        assert_eq!(restored.artist_id.as_deref(), Some(source.id.as_str()));
+        let restored_album = albums.find_by_id(&album.id).await.unwrap().unwrap();
+        assert_eq!(restored_album.artist_id.as_deref(), Some(source.id.as_str()));
+    }
+
+    #[core_async::test]
+    async fn test_capacity_and_new_mutation_clears_redo() {
+        let pool = create_test_pool().await.unwrap();
+        insert_test_provider(&pool).await;
+        let tracks = SqliteTrackRepository::from_pool(pool.clone());
+        tracks.insert(&make_track("t1", "Rock")).await.unwrap();
+
+        let journal = MutationJournal::with_capacity(
+            PlatformArc::new(crate::adapters::SqliteAdapter::from_pool(pool)),
+            2,
+        );
+        let ids = vec!["t1".to_string()];
+        for genre in ["A", "B", "C"] {
+            journal
+                .update_fields(
+                    format!("Set genre to {genre}"),
+                    JournalTable::Tracks,
+                    &ids,
+                    vec![("genre".to_string(), QueryValue::Text(genre.into()))],
+                )
+                .await
+                .unwrap();
+        }
+        assert_eq!(
+            journal.undo_labels(),
+            vec!["Set genre to C".to_string(), "Set genre to B".to_string()]
+        );
+
+        journal.undo().await.unwrap();
+        assert!(journal.can_redo());
+        journal
+            .update_fields(
+                "Set genre to D",
+                JournalTable::Tracks,
+                &ids,
+                vec![("genre".to_string(), QueryValue::Text("D".into()))],
+            )
+            .await
+            .unwrap();
+        assert!(!journal.can_redo());
+    }
+
+    #[core_async::test]
+    async fn test_rejects_invalid_column() {
+        let pool = create_test_pool().await.unwrap();
+        let journal = MutationJournal::from_pool(pool);
+        let result = journal
+            .update_fields(
+                "bad",
+                JournalTable::Tracks,
+                &["t1".to_string()],
+                vec![("genre = 1; --".to_string(), QueryValue::Null)],
+            )
+            .await;
+        assert!(matches!(result, Err(LibraryError::InvalidInput { .. })));
+    }
+}
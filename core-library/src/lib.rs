@@ -10,6 +10,7 @@
 //! - Repository patterns for tracks, albums, artists, playlists
 //! - Query APIs with filtering, sorting, and pagination
 //! - Full-text search using FTS5
+//! - Undo/redo journal for user-initiated edits
 //!
 //! ## Database Abstraction
 //!
@@ -35,9 +36,12 @@ pub mod adapters;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod db;
 pub mod error;
+pub mod fts;
+pub mod journal;
 pub mod models;
 pub mod query;
 pub mod repositories;
+pub mod text;
 
 // WASM bindings
 #[cfg(target_arch = "wasm32")]
@@ -50,9 +54,11 @@ pub use adapters::SqliteAdapter;
 #[cfg(not(target_arch = "wasm32"))]
 pub use db::{create_pool, create_test_pool, DatabaseConfig};
 pub use error::{LibraryError, Result};
+pub use journal::{JournalTable, MutationJournal};
 pub use models::{AlbumId, ArtistId, PlaylistId, Track, TrackId};
 pub use query::{
-    AlbumFilter, AlbumListItem, AlbumSearchItem, AlbumSort, ArtistSearchItem, LibraryQueryService,
-    PlaylistSearchItem, SearchResults, TrackDetails, TrackFilter, TrackListItem, TrackSort,
+    AlbumFilter, AlbumListItem, AlbumSearchItem, AlbumSort, ArtistSearchItem, CompletenessReport,
+    GapSummary, LibraryQueryService, PlaylistSearchItem, ProviderUsage, SearchResults, TrackDetails,
+    TrackFilter, TrackGap, TrackListItem, TrackSort, DEFAULT_TRACK_SORT_KEY,
 };
 pub use repositories::{Page, PageRequest, SqliteTrackRepository, TrackRepository};
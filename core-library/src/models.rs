@@ -2,6 +2,7 @@
 //!
 //! This module contains rich domain models with validation and database mapping.
 
+use crate::text::normalize_search_text;
 use serde::{Deserialize, Serialize};
 #[cfg(not(target_arch = "wasm32"))]
 use sqlx::FromRow;
@@ -12,6 +13,20 @@ use uuid::Uuid;
 // ID Types
 // =============================================================================
 
+/// Namespace for deterministic (UUIDv5) library ids
+const ID_NAMESPACE: Uuid = Uuid::from_u128(0x6d70_6300_6c69_4272_a1d2_6964_7376_3500);
+
+/// Derive a stable id from an entity kind and its identifying parts
+///
+/// Parts are NUL-separated so `("ab", "c")` and `("a", "bc")` differ.
+fn deterministic_uuid(kind: &str, parts: &[&str]) -> Uuid {
+    let name = std::iter::once(kind)
+        .chain(parts.iter().copied())
+        .collect::<Vec<_>>()
+        .join("\0");
+    Uuid::new_v5(&ID_NAMESPACE, name.as_bytes())
+}
+
 /// Unique identifier for a track
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[cfg_attr(not(target_arch = "wasm32"), derive(sqlx::Type))]
@@ -23,6 +38,16 @@ impl TrackId {
         Self(Uuid::new_v4())
     }
 
+    /// Derive the id of the track for a provider file
+    ///
+    /// The same provider file always maps to the same id.
+    pub fn from_provider_file(provider_id: &str, provider_file_id: &str) -> Self {
+        Self(deterministic_uuid(
+            "track",
+            &[provider_id, provider_file_id],
+        ))
+    }
+
     pub fn from_string(s: &str) -> Result<Self, uuid::Error> {
         Ok(Self(Uuid::parse_str(s)?))
     }
@@ -51,6 +76,12 @@ impl AlbumId {
         Self(Uuid::new_v4())
     }
 
+    /// Derive the id of an album from its normalized name and artist
+    pub fn from_normalized_name(normalized_name: &str, artist_id: Option<&ArtistId>) -> Self {
+        let artist_id = artist_id.map(ToString::to_string).unwrap_or_default();
+        Self(deterministic_uuid("album", &[normalized_name, &artist_id]))
+    }
+
     pub fn from_string(s: &str) -> Result<Self, uuid::Error> {
         Ok(Self(Uuid::parse_str(s)?))
     }
@@ -79,6 +110,11 @@ impl ArtistId {
         Self(Uuid::new_v4())
     }
 
+    /// Derive the id of an artist from its normalized name
+    pub fn from_normalized_name(normalized_name: &str) -> Self {
+        Self(deterministic_uuid("artist", &[normalized_name]))
+    }
+
     pub fn from_string(s: &str) -> Result<Self, uuid::Error> {
         Ok(Self(Uuid::parse_str(s)?))
     }
@@ -192,11 +228,25 @@ pub struct Track {
     pub updated_at: i64,
     /// Last modified time from provider
     pub provider_modified_at: Option<i64>,
+
+    // Silence analysis
+    /// Silent intro to skip, in milliseconds (None if not analyzed)
+    pub leading_silence_ms: Option<i64>,
+    /// Silent outro to skip, in milliseconds (None if not analyzed)
+    pub trailing_silence_ms: Option<i64>,
+
+    // Loudness analysis
+    /// Gain that brings the track to the ReplayGain 2.0 reference of
+    /// -18 LUFS, in hundredths of a dB (None if not analyzed)
+    pub replay_gain_millibels: Option<i64>,
+    /// Highest inter-sample peak relative to full scale, in hundredths of a
+    /// dB (None if not analyzed)
+    pub true_peak_millibels: Option<i64>,
 }
 
 impl Track {
     /// Create a new track with required fields
-    /// 
+    ///
     /// # Arguments
     /// * `title` - Track title
     /// * `provider_id` - Provider identifier (e.g., "google_drive")
@@ -212,7 +262,7 @@ impl Track {
     ) -> Self {
         let normalized_title = Self::normalize(&title);
         let now = chrono::Utc::now().timestamp();
-        
+
         Self {
             id: Uuid::new_v4().to_string(),
             provider_id,
@@ -235,10 +285,14 @@ impl Track {
             file_size: None,
             mime_type: None,
             artwork_id: None,
-            lyrics_status: "none".to_string(),
+            lyrics_status: "not_fetched".to_string(),
             created_at: now,
             updated_at: now,
             provider_modified_at: None,
+            leading_silence_ms: None,
+            trailing_silence_ms: None,
+            replay_gain_millibels: None,
+            true_peak_millibels: None,
         }
     }
 
@@ -271,9 +325,9 @@ impl Track {
         Ok(())
     }
 
-    /// Normalize a string for searching (lowercase, trimmed)
+    /// Normalize a string for searching (see [`normalize_search_text`])
     pub fn normalize(s: &str) -> String {
-        s.trim().to_lowercase()
+        normalize_search_text(s)
     }
 }
 
@@ -342,9 +396,9 @@ impl Album {
         Ok(())
     }
 
-    /// Normalize a string for searching
+    /// Normalize a string for searching (see [`normalize_search_text`])
     pub fn normalize(s: &str) -> String {
-        s.trim().to_lowercase()
+        normalize_search_text(s)
     }
 }
 
@@ -394,9 +448,9 @@ impl Artist {
         Ok(())
     }
 
-    /// Normalize a string for searching
+    /// Normalize a string for searching (see [`normalize_search_text`])
     pub fn normalize(s: &str) -> String {
-        s.trim().to_lowercase()
+        normalize_search_text(s)
     }
 }
 
@@ -432,7 +486,7 @@ pub struct Playlist {
 impl Playlist {
     /// Create a new user playlist
     pub fn new(name: String) -> Self {
-        let normalized_name = name.trim().to_lowercase();
+        let normalized_name = normalize_search_text(&name);
         Self {
             id: Uuid::new_v4().to_string(),
             name,
@@ -722,6 +776,9 @@ pub struct CachedTrack {
     pub download_attempts: u32,
     /// Last error message if download failed
     pub last_error: Option<String>,
+    /// Whether the user pinned this track to keep it out of eviction
+    #[serde(default)]
+    pub pinned: bool,
 }
 
 impl CachedTrack {
@@ -744,6 +801,7 @@ impl CachedTrack {
             downloaded_bytes: 0,
             download_attempts: 0,
             last_error: None,
+            pinned: false,
         }
     }
 
@@ -782,6 +840,13 @@ impl CachedTrack {
         self.downloaded_bytes = downloaded_bytes;
     }
 
+    /// Discard a partial download so the next attempt starts from the
+    /// first byte.
+    pub fn reset_progress(&mut self) {
+        self.downloaded_bytes = 0;
+        self.cached_size = 0;
+    }
+
     /// Increment play count and update last accessed time.
     pub fn record_play(&mut self) {
         self.play_count += 1;
@@ -819,6 +884,10 @@ pub struct CacheStats {
     pub total_plays: u64,
     /// Number of tracks that need eviction
     pub tracks_pending_eviction: usize,
+    /// Number of pinned tracks (never evicted)
+    pub pinned_tracks: usize,
+    /// Bytes used by pinned tracks
+    pub pinned_bytes: u64,
     /// Timestamp when stats were calculated
     pub calculated_at: i64,
 }
@@ -1149,6 +1218,32 @@ mod tests {
         assert!(TrackId::from_string("invalid").is_err());
     }
 
+    #[test]
+    fn test_deterministic_ids() {
+        let track_id = TrackId::from_provider_file("Google Drive", "file-1");
+        assert_eq!(
+            track_id,
+            TrackId::from_provider_file("Google Drive", "file-1")
+        );
+        assert_eq!(track_id.0.get_version_num(), 5);
+        assert_ne!(track_id, TrackId::from_provider_file("OneDrive", "file-1"));
+        assert_ne!(
+            TrackId::from_provider_file("ab", "c"),
+            TrackId::from_provider_file("a", "bc")
+        );
+
+        let artist_id = ArtistId::from_normalized_name("the beatles");
+        assert_eq!(artist_id, ArtistId::from_normalized_name("the beatles"));
+        assert_eq!(
+            AlbumId::from_normalized_name("abbey road", Some(&artist_id)),
+            AlbumId::from_normalized_name("abbey road", Some(&artist_id))
+        );
+        assert_ne!(
+            AlbumId::from_normalized_name("abbey road", Some(&artist_id)),
+            AlbumId::from_normalized_name("abbey road", None)
+        );
+    }
+
     #[test]
     fn test_id_types_default() {
         let track_id = TrackId::default();
@@ -12,7 +12,9 @@ use crate::repositories::{
     AlbumRepository, ArtistRepository, LyricsRepository, Page, PageRequest, SqliteAlbumRepository,
     SqliteArtistRepository, SqliteLyricsRepository, SqliteTrackRepository, TrackRepository,
 };
+use crate::text::{contains_cjk, fold_search_text, normalize_search_text};
 use bridge_traits::database::{DatabaseAdapter, QueryRow, QueryValue};
+use bridge_traits::storage::{SettingsStore, SettingsStoreExt};
 use serde::{Deserialize, Serialize};
 #[cfg(not(target_arch = "wasm32"))]
 use sqlx::SqlitePool;
@@ -35,6 +37,11 @@ type TrackStream = BoxStream<'static, Result<TrackListItem>>;
 #[cfg(target_arch = "wasm32")]
 type TrackStream = LocalBoxStream<'static, Result<TrackListItem>>;
 
+#[cfg(not(target_arch = "wasm32"))]
+type TrackBatchStream = BoxStream<'static, Result<Vec<TrackListItem>>>;
+#[cfg(target_arch = "wasm32")]
+type TrackBatchStream = LocalBoxStream<'static, Result<Vec<TrackListItem>>>;
+
 /// Item returned when querying tracks. Includes the base `Track` plus
 /// commonly needed relational metadata to avoid additional round-trips.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -124,7 +131,7 @@ pub struct TrackDetails {
 }
 
 /// Filter options for querying tracks.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TrackFilter {
     pub album_id: Option<String>,
     pub artist_id: Option<String>,
@@ -137,29 +144,14 @@ pub struct TrackFilter {
     pub max_duration_ms: Option<i64>,
     pub search: Option<String>,
     pub folder_id: Option<String>,
-    pub sort: TrackSort,
-}
-
-impl Default for TrackFilter {
-    fn default() -> Self {
-        Self {
-            album_id: None,
-            artist_id: None,
-            album_artist_id: None,
-            playlist_id: None,
-            provider_id: None,
-            genre: None,
-            year: None,
-            min_duration_ms: None,
-            max_duration_ms: None,
-            search: None,
-            folder_id: None,
-            sort: TrackSort::TitleAsc,
-        }
-    }
+    /// Sort order; `None` uses the service's default track sort.
+    pub sort: Option<TrackSort>,
 }
 
 /// Sorting options for track queries.
+///
+/// Every sort ends with the track ID as a tiebreaker, so paging through a
+/// result never repeats or skips tracks that compare equal.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum TrackSort {
     #[default]
@@ -169,8 +161,41 @@ pub enum TrackSort {
     CreatedAtAsc,
     DurationDesc,
     DurationAsc,
+    /// Album name, then disc and track number; suited to album views.
+    AlbumThenTrack,
+    /// Album artist (falling back to track artist), then album, disc and
+    /// track number.
+    ArtistThenAlbum,
+}
+
+impl TrackSort {
+    /// `ORDER BY` terms for this sort, without the ID tiebreaker.
+    fn order_by(self) -> &'static str {
+        match self {
+            TrackSort::TitleAsc => "t.normalized_title ASC, t.created_at DESC",
+            TrackSort::TitleDesc => "t.normalized_title DESC, t.created_at DESC",
+            TrackSort::CreatedAtDesc => "t.created_at DESC, t.normalized_title ASC",
+            TrackSort::CreatedAtAsc => "t.created_at ASC, t.normalized_title ASC",
+            TrackSort::DurationDesc => "t.duration_ms DESC, t.normalized_title ASC",
+            TrackSort::DurationAsc => "t.duration_ms ASC, t.normalized_title ASC",
+            TrackSort::AlbumThenTrack => {
+                "alb.normalized_name ASC, t.album_id ASC, t.disc_number ASC, \
+                 t.track_number ASC, t.normalized_title ASC"
+            }
+            TrackSort::ArtistThenAlbum => {
+                "COALESCE(aa.normalized_name, art.normalized_name) ASC, \
+                 alb.normalized_name ASC, t.album_id ASC, t.disc_number ASC, \
+                 t.track_number ASC, t.normalized_title ASC"
+            }
+        }
+    }
 }
 
+/// Settings key holding the default [`TrackSort`], stored as JSON.
+///
+/// See [`LibraryQueryService::load_default_track_sort`].
+pub const DEFAULT_TRACK_SORT_KEY: &str = "library.default_track_sort";
+
 /// Filter options for querying albums.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AlbumFilter {
@@ -207,16 +232,112 @@ pub enum AlbumSort {
     TrackCountDesc,
 }
 
+/// Track metadata gap reported by [`LibraryQueryService::completeness_report`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrackGap {
+    /// Neither the track nor its album has artwork.
+    Artwork,
+    /// Release year is unknown.
+    Year,
+    /// Genre is missing or blank.
+    Genre,
+    /// No lyrics are stored for the track.
+    Lyrics,
+}
+
+impl TrackGap {
+    /// SQL condition over `tracks t` joined with `albums alb` selecting tracks with this gap.
+    fn condition(&self) -> &'static str {
+        match self {
+            TrackGap::Artwork => "COALESCE(t.artwork_id, alb.artwork_id) IS NULL",
+            TrackGap::Year => "t.year IS NULL",
+            TrackGap::Genre => "(t.genre IS NULL OR TRIM(t.genre) = '')",
+            TrackGap::Lyrics => "NOT EXISTS (SELECT 1 FROM lyrics l WHERE l.track_id = t.id)",
+        }
+    }
+}
+
+/// Count of library items with a gap plus a few examples.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GapSummary<T> {
+    /// Number of items with the gap.
+    pub count: u64,
+    /// First items with the gap, in listing order.
+    pub sample: Vec<T>,
+}
+
+/// Overview of incomplete metadata across the library.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompletenessReport {
+    /// Total number of tracks in the library.
+    pub total_tracks: u64,
+    /// Total number of albums in the library.
+    pub total_albums: u64,
+    /// Tracks without track or album artwork.
+    pub tracks_missing_artwork: GapSummary<TrackListItem>,
+    /// Tracks without a release year.
+    pub tracks_missing_year: GapSummary<TrackListItem>,
+    /// Tracks without a genre.
+    pub tracks_missing_genre: GapSummary<TrackListItem>,
+    /// Tracks without stored lyrics.
+    pub tracks_missing_lyrics: GapSummary<TrackListItem>,
+    /// Albums without cover artwork.
+    pub albums_missing_artwork: GapSummary<AlbumListItem>,
+}
+
+/// Track count and storage used by one provider.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderUsage {
+    /// Provider the tracks were synced from.
+    pub provider_id: String,
+    /// Number of tracks from the provider.
+    pub track_count: u64,
+    /// Sum of the tracks' file sizes in bytes; unknown sizes count as zero.
+    pub total_bytes: u64,
+}
+
+/// Number of examples included per gap in a [`CompletenessReport`].
+const COMPLETENESS_SAMPLE_SIZE: u32 = 5;
+
 /// High-level service composing complex library queries.
 #[derive(Clone)]
 pub struct LibraryQueryService {
     adapter: Arc<dyn DatabaseAdapter>,
+    /// Sort applied to track queries whose filter leaves `sort` unset.
+    default_track_sort: TrackSort,
 }
 
 impl LibraryQueryService {
     /// Create a new `LibraryQueryService` backed by the provided adapter.
     pub fn new(adapter: Arc<dyn DatabaseAdapter>) -> Self {
-        Self { adapter }
+        Self {
+            adapter,
+            default_track_sort: TrackSort::default(),
+        }
+    }
+
+    /// Use `sort` for track queries that do not specify one.
+    pub fn with_default_track_sort(mut self, sort: TrackSort) -> Self {
+        self.default_track_sort = sort;
+        self
+    }
+
+    /// Sort applied to track queries that do not specify one.
+    pub fn default_track_sort(&self) -> TrackSort {
+        self.default_track_sort
+    }
+
+    /// Use the default track sort stored under [`DEFAULT_TRACK_SORT_KEY`].
+    ///
+    /// Keeps the current default when the setting is absent.
+    pub async fn load_default_track_sort(mut self, settings: &dyn SettingsStore) -> Result<Self> {
+        if let Some(sort) = settings
+            .get_json::<TrackSort>(DEFAULT_TRACK_SORT_KEY)
+            .await?
+        {
+            self.default_track_sort = sort;
+        }
+        Ok(self)
     }
 
     #[cfg(not(target_arch = "wasm32"))]
@@ -240,7 +361,8 @@ impl LibraryQueryService {
             });
         }
 
-        let spec = build_track_query_spec(&filter);
+        let sort = filter.sort.unwrap_or(self.default_track_sort);
+        let spec = build_track_query_spec(&filter, sort);
         let total = self.count_with(&spec.count_sql, &spec.binds).await?.max(0);
 
         let mut paginated_sql = spec.select_sql.clone();
@@ -316,6 +438,92 @@ impl LibraryQueryService {
         }
     }
 
+    /// Stream full-text track search results in batches of `batch_size`.
+    ///
+    /// Unlike [`search`](Self::search), results are not capped: batches are
+    /// fetched with keyset pagination over `(normalized_title, id)`, so every
+    /// match is yielded exactly once even for very large libraries.
+    pub fn stream_search_tracks(&self, query: &str, batch_size: u32) -> Result<TrackBatchStream> {
+        if batch_size == 0 {
+            return Err(LibraryError::InvalidInput {
+                field: "batch_size".to_string(),
+                message: "Batch size must be greater than zero".into(),
+            });
+        }
+
+        let plan = SearchPlan::new(query);
+        let initial_state = SearchStreamState {
+            service: self.clone(),
+            done: plan.is_empty(),
+            plan,
+            last_key: None,
+            batch_size,
+        };
+
+        let stream = stream::try_unfold(initial_state, |mut state| async move {
+            if state.done {
+                return Ok(None);
+            }
+
+            let batch = state
+                .service
+                .search_tracks_after(&state.plan, state.last_key.as_ref(), state.batch_size)
+                .await?;
+            if batch.len() < state.batch_size as usize {
+                state.done = true;
+            }
+            if batch.is_empty() {
+                return Ok(None);
+            }
+
+            let last = &batch[batch.len() - 1].track;
+            state.last_key = Some((last.normalized_title.clone(), last.id.clone()));
+            Ok(Some((batch, state)))
+        });
+
+        Ok(Box::pin(stream))
+    }
+
+    /// Fetch the next page of FTS track matches after the `(normalized_title, id)` key.
+    async fn search_tracks_after(
+        &self,
+        plan: &SearchPlan,
+        after: Option<&(String, String)>,
+        limit: u32,
+    ) -> Result<Vec<TrackListItem>> {
+        let filter = plan.filter(&TRACK_SEARCH);
+        let mut sql = format!(
+            r#"
+            SELECT
+                t.*,
+                COALESCE(t.artwork_id, alb.artwork_id) AS display_artwork_id,
+                alb.name AS album_name,
+                art.name AS artist_name,
+                aa.name AS album_artist_name
+            FROM {}
+            LEFT JOIN albums alb ON alb.id = t.album_id
+            LEFT JOIN artists art ON art.id = t.artist_id
+            LEFT JOIN artists aa ON aa.id = t.album_artist_id
+            WHERE {}
+            "#,
+            filter.from, filter.condition
+        );
+        let mut args = filter.args;
+
+        if let Some((title, id)) = after {
+            sql.push_str(" AND (t.normalized_title > ? OR (t.normalized_title = ? AND t.id > ?))");
+            args.push(QueryValue::Text(title.clone()));
+            args.push(QueryValue::Text(title.clone()));
+            args.push(QueryValue::Text(id.clone()));
+        }
+
+        sql.push_str(" ORDER BY t.normalized_title ASC, t.id ASC LIMIT ?");
+        args.push(QueryValue::Integer(limit as i64));
+
+        let rows = self.adapter.query(&sql, &args).await?;
+        rows.into_iter().map(row_to_track_item).collect()
+    }
+
     /// Query albums with filtering, sorting, pagination, and aggregated metadata.
     pub async fn query_albums(
         &self,
@@ -343,10 +551,238 @@ impl LibraryQueryService {
         Ok(Page::new(items, total as u64, page_request))
     }
 
+    /// Albums the artist has tracks on without being the album artist.
+    ///
+    /// Covers compilations and guest features, for an artist page's "appears
+    /// on" section; albums credited to the artist are excluded. Newest albums
+    /// come first.
+    pub async fn artist_appears_on(&self, artist_id: &str) -> Result<Vec<AlbumListItem>> {
+        let sql = "SELECT \
+                alb.*, \
+                art.name AS artist_name, \
+                COUNT(DISTINCT t.id) AS actual_track_count, \
+                COALESCE(SUM(t.duration_ms), 0) AS actual_duration_ms \
+             FROM albums alb \
+             LEFT JOIN artists art ON art.id = alb.artist_id \
+             LEFT JOIN tracks t ON t.album_id = alb.id \
+             WHERE alb.id IN (SELECT album_id FROM tracks WHERE artist_id = ?) \
+               AND (alb.artist_id IS NULL OR alb.artist_id <> ?) \
+             GROUP BY alb.id \
+             ORDER BY alb.year DESC, alb.normalized_name ASC";
+
+        let args = [
+            QueryValue::Text(artist_id.to_string()),
+            QueryValue::Text(artist_id.to_string()),
+        ];
+        let rows = self.adapter.query(sql, &args).await?;
+        rows.into_iter().map(row_to_album_item).collect()
+    }
+
+    /// Summarize metadata gaps across the library.
+    ///
+    /// Counts come from a single aggregate query per table; each gap also
+    /// carries a small sample for previews. Use [`tracks_missing`](Self::tracks_missing)
+    /// and [`albums_missing_artwork`](Self::albums_missing_artwork) to page
+    /// through the full lists.
+    pub async fn completeness_report(&self) -> Result<CompletenessReport> {
+        let track_sql = format!(
+            "SELECT \
+                COUNT(*) AS total, \
+                COALESCE(SUM(CASE WHEN {} THEN 1 ELSE 0 END), 0) AS missing_artwork, \
+                COALESCE(SUM(CASE WHEN {} THEN 1 ELSE 0 END), 0) AS missing_year, \
+                COALESCE(SUM(CASE WHEN {} THEN 1 ELSE 0 END), 0) AS missing_genre, \
+                COALESCE(SUM(CASE WHEN {} THEN 1 ELSE 0 END), 0) AS missing_lyrics \
+             FROM tracks t \
+             LEFT JOIN albums alb ON alb.id = t.album_id",
+            TrackGap::Artwork.condition(),
+            TrackGap::Year.condition(),
+            TrackGap::Genre.condition(),
+            TrackGap::Lyrics.condition(),
+        );
+        let track_counts = self.adapter.query_one(&track_sql, &[]).await?;
+
+        let album_counts = self
+            .adapter
+            .query_one(
+                "SELECT \
+                    COUNT(*) AS total, \
+                    COALESCE(SUM(CASE WHEN artwork_id IS NULL THEN 1 ELSE 0 END), 0) AS missing_artwork \
+                 FROM albums",
+                &[],
+            )
+            .await?;
+
+        let sample = PageRequest::new(0, COMPLETENESS_SAMPLE_SIZE);
+        Ok(CompletenessReport {
+            total_tracks: required_i64(&track_counts, "total")?.max(0) as u64,
+            total_albums: required_i64(&album_counts, "total")?.max(0) as u64,
+            tracks_missing_artwork: self
+                .track_gap_summary(&track_counts, "missing_artwork", TrackGap::Artwork)
+                .await?,
+            tracks_missing_year: self
+                .track_gap_summary(&track_counts, "missing_year", TrackGap::Year)
+                .await?,
+            tracks_missing_genre: self
+                .track_gap_summary(&track_counts, "missing_genre", TrackGap::Genre)
+                .await?,
+            tracks_missing_lyrics: self
+                .track_gap_summary(&track_counts, "missing_lyrics", TrackGap::Lyrics)
+                .await?,
+            albums_missing_artwork: GapSummary {
+                count: required_i64(&album_counts, "missing_artwork")?.max(0) as u64,
+                sample: self.albums_missing_artwork(sample).await?.items,
+            },
+        })
+    }
+
+    /// Combine an aggregate gap count with a sample of the affected tracks.
+    async fn track_gap_summary(
+        &self,
+        counts: &QueryRow,
+        column: &str,
+        gap: TrackGap,
+    ) -> Result<GapSummary<TrackListItem>> {
+        let sample = PageRequest::new(0, COMPLETENESS_SAMPLE_SIZE);
+        Ok(GapSummary {
+            count: required_i64(counts, column)?.max(0) as u64,
+            sample: self.tracks_missing(gap, sample).await?.items,
+        })
+    }
+
+    /// Page through tracks with the given metadata gap, ordered by title.
+    pub async fn tracks_missing(
+        &self,
+        gap: TrackGap,
+        page_request: PageRequest,
+    ) -> Result<Page<TrackListItem>> {
+        let condition = gap.condition();
+        let count_sql = format!(
+            "SELECT COUNT(*) AS count FROM tracks t \
+             LEFT JOIN albums alb ON alb.id = t.album_id \
+             WHERE {}",
+            condition
+        );
+        let total = self.count_with(&count_sql, &[]).await?.max(0);
+
+        let sql = format!(
+            r#"
+            SELECT
+                t.*,
+                COALESCE(t.artwork_id, alb.artwork_id) AS display_artwork_id,
+                alb.name AS album_name,
+                art.name AS artist_name,
+                aa.name AS album_artist_name
+            FROM tracks t
+            LEFT JOIN albums alb ON alb.id = t.album_id
+            LEFT JOIN artists art ON art.id = t.artist_id
+            LEFT JOIN artists aa ON aa.id = t.album_artist_id
+            WHERE {}
+            ORDER BY t.normalized_title ASC, t.id ASC
+            LIMIT ? OFFSET ?
+            "#,
+            condition
+        );
+        let args = [
+            QueryValue::Integer(page_request.limit() as i64),
+            QueryValue::Integer(page_request.offset() as i64),
+        ];
+
+        let rows = self.adapter.query(&sql, &args).await?;
+        let items = rows
+            .into_iter()
+            .map(row_to_track_item)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Page::new(items, total as u64, page_request))
+    }
+
+    /// Page through tracks with neither track nor album artwork.
+    pub async fn tracks_missing_artwork(
+        &self,
+        page_request: PageRequest,
+    ) -> Result<Page<TrackListItem>> {
+        self.tracks_missing(TrackGap::Artwork, page_request).await
+    }
+
+    /// Page through albums without cover artwork, ordered by name.
+    pub async fn albums_missing_artwork(
+        &self,
+        page_request: PageRequest,
+    ) -> Result<Page<AlbumListItem>> {
+        let total = self
+            .count_with(
+                "SELECT COUNT(*) AS count FROM albums WHERE artwork_id IS NULL",
+                &[],
+            )
+            .await?
+            .max(0);
+
+        let sql = "SELECT \
+                alb.*, \
+                art.name AS artist_name, \
+                COUNT(DISTINCT t.id) AS actual_track_count, \
+                COALESCE(SUM(t.duration_ms), 0) AS actual_duration_ms \
+             FROM albums alb \
+             LEFT JOIN artists art ON art.id = alb.artist_id \
+             LEFT JOIN tracks t ON t.album_id = alb.id \
+             WHERE alb.artwork_id IS NULL \
+             GROUP BY alb.id \
+             ORDER BY alb.normalized_name ASC, alb.id ASC \
+             LIMIT ? OFFSET ?";
+        let args = [
+            QueryValue::Integer(page_request.limit() as i64),
+            QueryValue::Integer(page_request.offset() as i64),
+        ];
+
+        let rows = self.adapter.query(sql, &args).await?;
+        let items = rows
+            .into_iter()
+            .map(row_to_album_item)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Page::new(items, total as u64, page_request))
+    }
+
+    /// Track count and total size per provider, largest first.
+    ///
+    /// Each track row belongs to exactly one provider, its primary source, so
+    /// a track is never counted twice.
+    pub async fn provider_breakdown(&self) -> Result<Vec<ProviderUsage>> {
+        let rows = self
+            .adapter
+            .query(
+                "SELECT \
+                    provider_id, \
+                    COUNT(*) AS track_count, \
+                    COALESCE(SUM(file_size), 0) AS total_bytes \
+                 FROM tracks \
+                 GROUP BY provider_id \
+                 ORDER BY total_bytes DESC, provider_id ASC",
+                &[],
+            )
+            .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(ProviderUsage {
+                    provider_id: optional_string(row, "provider_id")
+                        .ok_or_else(|| missing_column("provider_id"))?,
+                    track_count: required_i64(row, "track_count")?.max(0) as u64,
+                    total_bytes: required_i64(row, "total_bytes")?.max(0) as u64,
+                })
+            })
+            .collect()
+    }
+
     /// Perform full-text search across tracks, albums, artists, and playlists.
+    ///
+    /// CJK terms are matched as substrings of the normalized names, since word
+    /// tokenizers cannot split CJK text into words; other terms use the FTS
+    /// indexes.
     pub async fn search(&self, query: &str) -> Result<SearchResults> {
         let trimmed = query.trim();
-        if trimmed.is_empty() {
+        let plan = SearchPlan::new(trimmed);
+        if plan.is_empty() {
             return Ok(SearchResults::default());
         }
 
@@ -359,10 +795,14 @@ impl LibraryQueryService {
 
         // Track search via FTS.
         {
+            let filter = plan.filter(&TRACK_SEARCH);
+            let mut args = filter.args;
+            args.push(QueryValue::Integer(TRACK_LIMIT));
             let rows = self
                 .adapter
                 .query(
-                    r#"
+                    &format!(
+                        r#"
                 SELECT
                     t.*,
                     COALESCE(t.artwork_id, alb.artwork_id) AS display_artwork_id,
@@ -370,19 +810,17 @@ impl LibraryQueryService {
                     art.name AS artist_name,
                     aa.name AS album_artist_name,
                     0.0 AS relevance
-                FROM tracks_fts
-                INNER JOIN tracks t ON t.id = tracks_fts.track_id
+                FROM {}
                 LEFT JOIN albums alb ON alb.id = t.album_id
                 LEFT JOIN artists art ON art.id = t.artist_id
                 LEFT JOIN artists aa ON aa.id = t.album_artist_id
-                WHERE tracks_fts MATCH ?
-                ORDER BY tracks_fts.rank ASC, t.normalized_title ASC
+                WHERE {}
+                ORDER BY {}t.normalized_title ASC
                 LIMIT ?
                 "#,
-                    &[
-                        QueryValue::Text(trimmed.to_string()),
-                        QueryValue::Integer(TRACK_LIMIT),
-                    ],
+                        filter.from, filter.condition, filter.rank_order
+                    ),
+                    &args,
                 )
                 .await?;
 
@@ -393,29 +831,31 @@ impl LibraryQueryService {
 
         // Album search via FTS.
         {
+            let filter = plan.filter(&ALBUM_SEARCH);
+            let mut args = filter.args;
+            args.push(QueryValue::Integer(ALBUM_LIMIT));
             let rows = self
                 .adapter
                 .query(
-                    r#"
+                    &format!(
+                        r#"
                 SELECT
                     alb.*,
                     art.name AS artist_name,
                     0.0 AS relevance,
                     COUNT(DISTINCT t.id) AS actual_track_count,
                     COALESCE(SUM(t.duration_ms), 0) AS actual_duration_ms
-                FROM albums_fts
-                INNER JOIN albums alb ON alb.id = albums_fts.album_id
+                FROM {}
                 LEFT JOIN artists art ON art.id = alb.artist_id
                 LEFT JOIN tracks t ON t.album_id = alb.id
-                WHERE albums_fts MATCH ?
+                WHERE {}
                 GROUP BY alb.id
-                ORDER BY albums_fts.rank ASC, alb.normalized_name ASC
+                ORDER BY {}alb.normalized_name ASC
                 LIMIT ?
                 "#,
-                    &[
-                        QueryValue::Text(trimmed.to_string()),
-                        QueryValue::Integer(ALBUM_LIMIT),
-                    ],
+                        filter.from, filter.condition, filter.rank_order
+                    ),
+                    &args,
                 )
                 .await?;
 
@@ -439,23 +879,25 @@ impl LibraryQueryService {
 
         // Artist search via FTS.
         {
+            let filter = plan.filter(&ARTIST_SEARCH);
+            let mut args = filter.args;
+            args.push(QueryValue::Integer(ARTIST_LIMIT));
             let rows = self
                 .adapter
                 .query(
-                    r#"
+                    &format!(
+                        r#"
                 SELECT
                     art.*,
                     0.0 AS relevance
-                FROM artists_fts
-                INNER JOIN artists art ON art.id = artists_fts.artist_id
-                WHERE artists_fts MATCH ?
-                ORDER BY artists_fts.rank ASC, art.normalized_name ASC
+                FROM {}
+                WHERE {}
+                ORDER BY {}art.normalized_name ASC
                 LIMIT ?
                 "#,
-                    &[
-                        QueryValue::Text(trimmed.to_string()),
-                        QueryValue::Integer(ARTIST_LIMIT),
-                    ],
+                        filter.from, filter.condition, filter.rank_order
+                    ),
+                    &args,
                 )
                 .await?;
 
@@ -468,7 +910,7 @@ impl LibraryQueryService {
 
         // Playlist search using normalized LIKE matching.
         {
-            let normalized = Playlist::new(trimmed.to_string()).normalized_name;
+            let normalized = normalize_search_text(trimmed);
             let pattern = format!("%{}%", normalized);
             let rows = self
                 .adapter
@@ -587,6 +1029,157 @@ struct TrackStreamState {
     page_size: u32,
 }
 
+#[derive(Clone)]
+struct SearchStreamState {
+    service: LibraryQueryService,
+    plan: SearchPlan,
+    last_key: Option<(String, String)>,
+    done: bool,
+    batch_size: u32,
+}
+
+/// Tables searched for one entity type
+struct SearchSource {
+    /// FTS index joined to the aliased source table
+    fts_from: &'static str,
+    /// Aliased source table, used when no term goes through FTS
+    table_from: &'static str,
+    /// FTS table name, for `MATCH` and rank ordering
+    fts_table: &'static str,
+    /// Normalized columns matched against CJK terms (joins must be in scope)
+    substring_columns: &'static [&'static str],
+}
+
+const TRACK_SEARCH: SearchSource = SearchSource {
+    fts_from: "tracks_fts INNER JOIN tracks t ON t.id = tracks_fts.track_id",
+    table_from: "tracks t",
+    fts_table: "tracks_fts",
+    substring_columns: &[
+        "t.normalized_title",
+        "art.normalized_name",
+        "alb.normalized_name",
+    ],
+};
+
+const ALBUM_SEARCH: SearchSource = SearchSource {
+    fts_from: "albums_fts INNER JOIN albums alb ON alb.id = albums_fts.album_id",
+    table_from: "albums alb",
+    fts_table: "albums_fts",
+    substring_columns: &["alb.normalized_name", "art.normalized_name"],
+};
+
+const ARTIST_SEARCH: SearchSource = SearchSource {
+    fts_from: "artists_fts INNER JOIN artists art ON art.id = artists_fts.artist_id",
+    table_from: "artists art",
+    fts_table: "artists_fts",
+    substring_columns: &["art.normalized_name"],
+};
+
+/// Search query split by script
+///
+/// Word tokenizers index a run of CJK text as a single token, so a partial CJK
+/// query never matches through FTS. CJK terms are matched as substrings of the
+/// normalized columns instead; all other terms go through the FTS index.
+#[derive(Debug, Clone, Default)]
+struct SearchPlan {
+    /// FTS5 query built from the non-CJK terms
+    fts_query: Option<String>,
+    /// `LIKE` patterns for the CJK terms
+    substring_patterns: Vec<String>,
+}
+
+/// SQL fragments produced by [`SearchPlan::filter`]
+struct SearchFilter {
+    from: &'static str,
+    condition: String,
+    args: Vec<QueryValue>,
+    /// `ORDER BY` prefix ranking FTS matches first (empty without FTS terms)
+    rank_order: String,
+}
+
+impl SearchPlan {
+    fn new(query: &str) -> Self {
+        let folded = fold_search_text(query.trim());
+        if !contains_cjk(&folded) {
+            // Pass the query through untouched so FTS5 syntax keeps working
+            return Self {
+                fts_query: (!folded.is_empty()).then_some(folded),
+                substring_patterns: Vec::new(),
+            };
+        }
+
+        let mut fts_terms = Vec::new();
+        let mut substring_patterns = Vec::new();
+        for term in folded.split_whitespace() {
+            if contains_cjk(term) {
+                substring_patterns.push(format!("%{}%", escape_like(&normalize_search_text(term))));
+            } else {
+                fts_terms.push(term);
+            }
+        }
+
+        Self {
+            fts_query: (!fts_terms.is_empty()).then(|| fts_terms.join(" ")),
+            substring_patterns,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.fts_query.is_none() && self.substring_patterns.is_empty()
+    }
+
+    fn filter(&self, source: &SearchSource) -> SearchFilter {
+        let mut conditions = Vec::new();
+        let mut args = Vec::new();
+
+        if let Some(fts_query) = &self.fts_query {
+            conditions.push(format!("{} MATCH ?", source.fts_table));
+            args.push(QueryValue::Text(fts_query.clone()));
+        }
+
+        for pattern in &self.substring_patterns {
+            let any_column = source
+                .substring_columns
+                .iter()
+                .map(|column| format!("{column} LIKE ? ESCAPE '\\'"))
+                .collect::<Vec<_>>()
+                .join(" OR ");
+            conditions.push(format!("({any_column})"));
+            args.extend(
+                source
+                    .substring_columns
+                    .iter()
+                    .map(|_| QueryValue::Text(pattern.clone())),
+            );
+        }
+
+        let (from, rank_order) = if self.fts_query.is_some() {
+            (source.fts_from, format!("{}.rank ASC, ", source.fts_table))
+        } else {
+            (source.table_from, String::new())
+        };
+
+        SearchFilter {
+            from,
+            condition: conditions.join(" AND "),
+            args,
+            rank_order,
+        }
+    }
+}
+
+/// Escape `LIKE` wildcards so they match literally (with `ESCAPE '\'`)
+fn escape_like(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
 #[derive(Debug, Clone)]
 struct AlbumQuerySpec {
     select_sql: String,
@@ -615,7 +1208,7 @@ fn binds_to_query_values(binds: &[BindValue]) -> Vec<QueryValue> {
     binds.iter().map(BindValue::to_query_value).collect()
 }
 
-fn build_track_query_spec(filter: &TrackFilter) -> TrackQuerySpec {
+fn build_track_query_spec(filter: &TrackFilter, sort: TrackSort) -> TrackQuerySpec {
     let mut select_sql = String::from(
         "SELECT \
             t.*, \
@@ -699,14 +1292,8 @@ fn build_track_query_spec(filter: &TrackFilter) -> TrackQuerySpec {
     }
 
     select_sql.push_str(" ORDER BY ");
-    select_sql.push_str(match filter.sort {
-        TrackSort::TitleAsc => "t.normalized_title ASC, t.created_at DESC",
-        TrackSort::TitleDesc => "t.normalized_title DESC, t.created_at DESC",
-        TrackSort::CreatedAtDesc => "t.created_at DESC, t.normalized_title ASC",
-        TrackSort::CreatedAtAsc => "t.created_at ASC, t.normalized_title ASC",
-        TrackSort::DurationDesc => "t.duration_ms DESC, t.normalized_title ASC",
-        TrackSort::DurationAsc => "t.duration_ms ASC, t.normalized_title ASC",
-    });
+    select_sql.push_str(sort.order_by());
+    select_sql.push_str(", t.id ASC");
 
     let mut count_sql = String::from("SELECT COUNT(*) AS count FROM tracks t");
     for join in &joins {
@@ -907,8 +1494,10 @@ fn missing_column(column: &str) -> LibraryError {
 #[cfg(all(test, not(target_arch = "wasm32")))]
 mod tests {
     use super::*;
-    use crate::db::create_test_pool;
+    use crate::db::{create_pool, create_test_pool, DatabaseConfig};
     use crate::repositories::{PlaylistRepository, SqlitePlaylistRepository};
+    use bridge_traits::database::FtsTokenizer;
+    use futures::TryStreamExt;
     use sqlx::SqlitePool;
 
     async fn insert_test_provider(pool: &SqlitePool) {
@@ -977,6 +1566,10 @@ mod tests {
             created_at: 1700000000,
             updated_at: 1700000000,
             provider_modified_at: Some(1700000000),
+            leading_silence_ms: None,
+            trailing_silence_ms: None,
+            replay_gain_millibels: None,
+            true_peak_millibels: None,
         }
     }
 
@@ -1024,10 +1617,12 @@ mod tests {
             .unwrap();
 
         let service = LibraryQueryService::from_pool(pool.clone());
-        let mut filter = TrackFilter::default();
-        filter.album_id = Some(album.id.clone());
-        filter.playlist_id = Some(playlist.id.clone());
-        filter.sort = TrackSort::TitleAsc;
+        let filter = TrackFilter {
+            album_id: Some(album.id.clone()),
+            playlist_id: Some(playlist.id.clone()),
+            sort: Some(TrackSort::TitleAsc),
+            ..Default::default()
+        };
 
         let page = service
             .query_tracks(filter, PageRequest::new(0, 10))
@@ -1063,9 +1658,11 @@ mod tests {
         track_b.duration_ms = 240000;
         insert_track(&pool, &track_b).await;
 
-        let mut filter = AlbumFilter::default();
-        filter.genre = Some("Jazz".to_string());
-        filter.sort = AlbumSort::TrackCountDesc;
+        let filter = AlbumFilter {
+            genre: Some("Jazz".to_string()),
+            sort: AlbumSort::TrackCountDesc,
+            ..Default::default()
+        };
 
         let service = LibraryQueryService::from_pool(pool.clone());
         let page = service
@@ -1085,6 +1682,51 @@ mod tests {
         assert_eq!(item.artist_name.as_deref(), Some(artist.name.as_str()));
     }
 
+    #[core_async::test]
+    async fn artist_appears_on_lists_compilations_but_not_own_albums() {
+        let pool = create_test_pool().await.unwrap();
+        insert_test_provider(&pool).await;
+        let artist = insert_artist(&pool, "artist-guest", "Guest Artist").await;
+        let various = insert_artist(&pool, "artist-various", "Various Artists").await;
+        let own_album = insert_album(&pool, "album-own", "Solo", Some(&artist.id), None).await;
+        let compilation = insert_album(
+            &pool,
+            "album-compilation",
+            "Summer Hits",
+            Some(&various.id),
+            None,
+        )
+        .await;
+
+        let solo = make_track("track-solo", Some(&own_album.id), Some(&artist.id));
+        insert_track(&pool, &solo).await;
+        let mut feature = make_track("track-feature", Some(&compilation.id), Some(&artist.id));
+        feature.album_artist_id = Some(various.id.clone());
+        insert_track(&pool, &feature).await;
+        let other = make_track("track-other", Some(&compilation.id), Some(&various.id));
+        insert_track(&pool, &other).await;
+
+        let service = LibraryQueryService::from_pool(pool.clone());
+        let appears_on = service.artist_appears_on(&artist.id).await.unwrap();
+        assert_eq!(appears_on.len(), 1);
+        let item = &appears_on[0];
+        assert_eq!(item.album.id, compilation.id);
+        assert_eq!(item.artist_name.as_deref(), Some(various.name.as_str()));
+        assert_eq!(item.actual_track_count, 2);
+
+        // The compilation isn't one of the artist's own albums
+        let filter = AlbumFilter {
+            artist_id: Some(artist.id.clone()),
+            ..Default::default()
+        };
+        let own = service
+            .query_albums(filter, PageRequest::new(0, 10))
+            .await
+            .unwrap();
+        assert_eq!(own.items.len(), 1);
+        assert_eq!(own.items[0].album.id, own_album.id);
+    }
+
     #[core_async::test]
     async fn search_returns_results_across_entities() {
         let pool = create_test_pool().await.unwrap();
@@ -1118,6 +1760,313 @@ mod tests {
             .any(|item| item.artist.id == artist.id));
     }
 
+    #[core_async::test]
+    async fn search_matches_cjk_substrings_and_kana_variants() {
+        // Word tokenizers index "夜に駆ける" as a single token
+        let config = DatabaseConfig::in_memory().fts_tokenizer(FtsTokenizer::Unicode61);
+        let pool = create_pool(config).await.unwrap();
+        insert_test_provider(&pool).await;
+        let artist = insert_artist(&pool, "artist-jp", "ＹＯＡＳＯＢＩ").await;
+        let mut track = make_track("cjk-track", None, Some(&artist.id));
+        track.title = "夜に駆ける".to_string();
+        track.normalized_title = Track::normalize(&track.title);
+        insert_track(&pool, &track).await;
+        let mut katakana = make_track("katakana-track", None, None);
+        katakana.title = "ハルジオン".to_string();
+        katakana.normalized_title = Track::normalize(&katakana.title);
+        insert_track(&pool, &katakana).await;
+
+        let fts_only: Vec<(String,)> =
+            sqlx::query_as("SELECT track_id FROM tracks_fts WHERE tracks_fts MATCH '駆け'")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert!(fts_only.is_empty());
+
+        let service = LibraryQueryService::from_pool(pool.clone());
+        let ids = |results: &SearchResults| -> Vec<String> {
+            results
+                .tracks
+                .iter()
+                .map(|item| item.track.id.clone())
+                .collect()
+        };
+
+        let results = service.search("駆け").await.unwrap();
+        assert_eq!(ids(&results), vec![track.id.clone()]);
+
+        // Hiragana and half-width katakana queries find a katakana title
+        let results = service.search("はるじ").await.unwrap();
+        assert_eq!(ids(&results), vec![katakana.id.clone()]);
+        let results = service.search("ｼﾞｵﾝ").await.unwrap();
+        assert_eq!(ids(&results), vec![katakana.id.clone()]);
+
+        // Full-width names are indexed in their half-width form
+        let results = service.search("yoasobi").await.unwrap();
+        assert!(results
+            .artists
+            .iter()
+            .any(|item| item.artist.id == artist.id));
+
+        // Mixed queries combine FTS terms with CJK substrings
+        let results = service.search("yoasobi 夜に").await.unwrap();
+        assert_eq!(ids(&results), vec![track.id.clone()]);
+        let results = service.search("yoasobi ハルジ").await.unwrap();
+        assert!(results.tracks.is_empty());
+
+        // LIKE wildcards in the query are literal
+        assert!(service.search("駆%").await.unwrap().tracks.is_empty());
+
+        let batches: Vec<Vec<TrackListItem>> = service
+            .stream_search_tracks("駆ける", 10)
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(batches.concat().len(), 1);
+    }
+
+    #[core_async::test]
+    async fn album_then_track_sort_orders_discs_and_pages_stably() {
+        let pool = create_test_pool().await.unwrap();
+        insert_test_provider(&pool).await;
+        insert_artist(&pool, "artist", "Artist").await;
+        insert_album(&pool, "album-a", "A Side", Some("artist"), None).await;
+        insert_album(&pool, "album-b", "B Side", Some("artist"), None).await;
+
+        // (id, album, disc, track, title), inserted out of order
+        let rows = [
+            ("b-2-1", "album-b", 2, 1, "Alpha"),
+            ("b-1-2", "album-b", 1, 2, "Zulu"),
+            ("a-1-1", "album-a", 1, 1, "Yankee"),
+            ("b-1-1", "album-b", 1, 1, "Mike"),
+            ("b-2-2", "album-b", 2, 2, "Bravo"),
+            // Indistinguishable except by ID
+            ("b-3-1-y", "album-b", 3, 1, "Hidden"),
+            ("b-3-1-x", "album-b", 3, 1, "Hidden"),
+        ];
+        for (id, album_id, disc, number, title) in rows {
+            let mut track = make_track(id, Some(album_id), Some("artist"));
+            track.disc_number = disc;
+            track.track_number = Some(number);
+            track.title = title.to_string();
+            track.normalized_title = Track::normalize(title);
+            insert_track(&pool, &track).await;
+        }
+        let expected = [
+            "a-1-1", "b-1-1", "b-1-2", "b-2-1", "b-2-2", "b-3-1-x", "b-3-1-y",
+        ];
+
+        let service = LibraryQueryService::from_pool(pool.clone());
+        let filter = TrackFilter {
+            sort: Some(TrackSort::AlbumThenTrack),
+            ..Default::default()
+        };
+        let page = service
+            .query_tracks(filter.clone(), PageRequest::new(0, 50))
+            .await
+            .unwrap();
+        let ids: Vec<_> = page
+            .items
+            .iter()
+            .map(|item| item.track.id.as_str())
+            .collect();
+        assert_eq!(ids, expected);
+
+        // Paging with a small page size visits every track exactly once
+        let mut paged = Vec::new();
+        for page_index in 0..4 {
+            let page = service
+                .query_tracks(filter.clone(), PageRequest::new(page_index, 2))
+                .await
+                .unwrap();
+            paged.extend(page.items.into_iter().map(|item| item.track.id));
+        }
+        assert_eq!(paged, expected);
+    }
+
+    #[core_async::test]
+    async fn default_track_sort_applies_when_filter_has_none() {
+        use bridge_desktop::SqliteSettingsStore;
+
+        let pool = create_test_pool().await.unwrap();
+        insert_test_provider(&pool).await;
+        insert_artist(&pool, "artist", "Artist").await;
+        insert_album(&pool, "album", "Album", Some("artist"), None).await;
+        for (id, number, title) in [("second", 2, "Alpha"), ("first", 1, "Omega")] {
+            let mut track = make_track(id, Some("album"), Some("artist"));
+            track.track_number = Some(number);
+            track.title = title.to_string();
+            track.normalized_title = Track::normalize(title);
+            insert_track(&pool, &track).await;
+        }
+
+        let settings = SqliteSettingsStore::in_memory().await.unwrap();
+        settings
+            .set_json(DEFAULT_TRACK_SORT_KEY, &TrackSort::AlbumThenTrack)
+            .await
+            .unwrap();
+        let service = LibraryQueryService::from_pool(pool.clone())
+            .load_default_track_sort(&settings)
+            .await
+            .unwrap();
+        assert_eq!(service.default_track_sort(), TrackSort::AlbumThenTrack);
+
+        let ids = |page: Page<TrackListItem>| -> Vec<String> {
+            page.items.into_iter().map(|item| item.track.id).collect()
+        };
+        let page = service
+            .query_tracks(TrackFilter::default(), PageRequest::new(0, 10))
+            .await
+            .unwrap();
+        assert_eq!(ids(page), vec!["first", "second"]);
+
+        // An explicit sort still wins
+        let filter = TrackFilter {
+            sort: Some(TrackSort::TitleAsc),
+            ..Default::default()
+        };
+        let page = service
+            .query_tracks(filter, PageRequest::new(0, 10))
+            .await
+            .unwrap();
+        assert_eq!(ids(page), vec!["second", "first"]);
+    }
+
+    #[core_async::test]
+    async fn provider_breakdown_groups_tracks_by_provider() {
+        let pool = create_test_pool().await.unwrap();
+        insert_test_provider(&pool).await;
+        sqlx::query(
+            "INSERT INTO providers (id, type, display_name, profile_id, created_at) \
+             VALUES ('other-provider', 'OneDrive', 'Other Provider', 'profile', 1700000000)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        for (id, provider, size) in [
+            ("usage-1", "test-provider", Some(1_000)),
+            ("usage-2", "test-provider", Some(2_500)),
+            ("usage-3", "test-provider", None),
+            ("usage-4", "other-provider", Some(10_000)),
+        ] {
+            let mut track = make_track(id, None, None);
+            track.provider_id = provider.to_string();
+            track.file_size = size;
+            insert_track(&pool, &track).await;
+        }
+
+        let service = LibraryQueryService::from_pool(pool.clone());
+        let usage = service.provider_breakdown().await.unwrap();
+
+        assert_eq!(
+            usage,
+            vec![
+                ProviderUsage {
+                    provider_id: "other-provider".to_string(),
+                    track_count: 1,
+                    total_bytes: 10_000,
+                },
+                ProviderUsage {
+                    provider_id: "test-provider".to_string(),
+                    track_count: 3,
+                    total_bytes: 3_500,
+                },
+            ]
+        );
+    }
+
+    #[core_async::test]
+    async fn completeness_report_counts_seeded_gaps() {
+        let pool = create_test_pool().await.unwrap();
+        insert_test_provider(&pool).await;
+        let artist = insert_artist(&pool, "artist-gap", "Gap Artist").await;
+
+        sqlx::query(
+            "INSERT INTO artworks (id, hash, binary_blob, mime_type, width, height, file_size, created_at) \
+             VALUES ('art-1', 'hash-1', X'FFD8FF', 'image/jpeg', 300, 300, 3, 1700000000)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        // Covered album with one track inheriting its art, bare album with two tracks
+        let covered = insert_album(&pool, "album-covered", "Covered", Some(&artist.id), None).await;
+        sqlx::query("UPDATE albums SET artwork_id = 'art-1' WHERE id = ?")
+            .bind(&covered.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let bare = insert_album(&pool, "album-bare", "Bare", Some(&artist.id), None).await;
+
+        insert_track(
+            &pool,
+            &make_track("gap-1", Some(&covered.id), Some(&artist.id)),
+        )
+        .await;
+
+        let mut no_year = make_track("gap-2", Some(&bare.id), Some(&artist.id));
+        no_year.year = None;
+        insert_track(&pool, &no_year).await;
+
+        let mut no_genre = make_track("gap-3", Some(&bare.id), Some(&artist.id));
+        no_genre.genre = Some("  ".to_string());
+        no_genre.artwork_id = Some("art-1".to_string());
+        insert_track(&pool, &no_genre).await;
+
+        let mut loose = make_track("gap-4", None, None);
+        loose.year = None;
+        loose.genre = None;
+        insert_track(&pool, &loose).await;
+
+        let lyrics_repo = SqliteLyricsRepository::from_pool(pool.clone());
+        for track_id in ["gap-1", "gap-3"] {
+            let lyrics = Lyrics::new(
+                track_id.to_string(),
+                "manual".to_string(),
+                false,
+                "Some words".to_string(),
+            );
+            lyrics_repo.insert(&lyrics).await.unwrap();
+        }
+
+        let service = LibraryQueryService::from_pool(pool.clone());
+        let report = service.completeness_report().await.unwrap();
+
+        assert_eq!(report.total_tracks, 4);
+        assert_eq!(report.total_albums, 2);
+        assert_eq!(report.tracks_missing_artwork.count, 2);
+        assert_eq!(report.tracks_missing_year.count, 2);
+        assert_eq!(report.tracks_missing_genre.count, 2);
+        assert_eq!(report.tracks_missing_lyrics.count, 2);
+        assert_eq!(report.albums_missing_artwork.count, 1);
+
+        let ids = |items: &[TrackListItem]| -> Vec<String> {
+            items.iter().map(|item| item.track.id.clone()).collect()
+        };
+        assert_eq!(
+            ids(&report.tracks_missing_artwork.sample),
+            vec!["gap-2", "gap-4"]
+        );
+        assert_eq!(
+            ids(&report.tracks_missing_lyrics.sample),
+            vec!["gap-2", "gap-4"]
+        );
+        assert_eq!(report.albums_missing_artwork.sample[0].album.id, bare.id);
+        assert_eq!(
+            report.albums_missing_artwork.sample[0].actual_track_count,
+            2
+        );
+
+        let page = service
+            .tracks_missing_artwork(PageRequest::new(1, 1))
+            .await
+            .unwrap();
+        assert_eq!(page.total, 2);
+        assert_eq!(ids(&page.items), vec!["gap-4"]);
+    }
+
     #[core_async::test]
     async fn get_track_details_eager_loads_relations() {
         let pool = create_test_pool().await.unwrap();
@@ -1161,4 +2110,57 @@ mod tests {
         );
         assert!(details.lyrics.is_some());
     }
+
+    #[core_async::test]
+    async fn stream_search_tracks_yields_all_matches_in_batches() {
+        let pool = create_test_pool().await.unwrap();
+        insert_test_provider(&pool).await;
+
+        for i in 0..230 {
+            let mut track = make_track(&format!("stream-{:03}", i), None, None);
+            track.title = format!("Streaming Song {}", i % 7);
+            track.normalized_title = Track::normalize(&track.title);
+            insert_track(&pool, &track).await;
+        }
+        let mut other = make_track("unrelated", None, None);
+        other.title = "Quiet Piece".to_string();
+        other.normalized_title = Track::normalize(&other.title);
+        insert_track(&pool, &other).await;
+
+        let service = LibraryQueryService::from_pool(pool.clone());
+        let batches: Vec<Vec<TrackListItem>> = service
+            .stream_search_tracks("streaming", 50)
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(batches.len(), 5);
+        assert!(batches.iter().all(|batch| batch.len() <= 50));
+
+        let ids: Vec<String> = batches
+            .iter()
+            .flatten()
+            .map(|item| item.track.id.clone())
+            .collect();
+        let unique: std::collections::HashSet<_> = ids.iter().collect();
+        assert_eq!(ids.len(), 230);
+        assert_eq!(unique.len(), 230);
+        assert!(!unique.contains(&other.id));
+    }
+
+    #[core_async::test]
+    async fn stream_search_tracks_empty_query_yields_nothing() {
+        let pool = create_test_pool().await.unwrap();
+        let service = LibraryQueryService::from_pool(pool);
+
+        let batches: Vec<Vec<TrackListItem>> = service
+            .stream_search_tracks("   ", 50)
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        assert!(batches.is_empty());
+        assert!(service.stream_search_tracks("anything", 0).is_err());
+    }
 }
@@ -1,12 +1,107 @@
 //! Album repository trait and implementation
 
+use crate::adapters::BusyRetry;
 use crate::error::{LibraryError, Result};
 use crate::models::Album;
-use crate::repositories::{Page, PageRequest, PlatformArc};
+use crate::repositories::{value_placeholders, Page, PageRequest, PlatformArc, MAX_BIND_PARAMS};
 use bridge_traits::database::{DatabaseAdapter, QueryRow, QueryValue};
 use bridge_traits::platform::PlatformSendSync;
 #[cfg(any(test, not(target_arch = "wasm32")))]
 use sqlx::SqlitePool;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+
+/// Columns written by an album insert
+const INSERT_COLUMNS: usize = 11;
+
+/// Identity of an album: its normalized name and album artist
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlbumKey {
+    /// Normalized album name
+    pub normalized_name: String,
+    /// Album artist ID, `None` for albums without one
+    pub artist_id: Option<String>,
+}
+
+impl AlbumKey {
+    /// Key of an existing or candidate album
+    pub fn of(album: &Album) -> Self {
+        Self {
+            normalized_name: album.normalized_name.clone(),
+            artist_id: album.artist_id.clone(),
+        }
+    }
+}
+
+/// Words marking a qualifier as an edition of the same album
+const EDITION_WORDS: &[&str] = &[
+    "anniversary",
+    "bonus",
+    "deluxe",
+    "edition",
+    "expanded",
+    "reissue",
+    "remaster",
+    "remastered",
+];
+
+/// How album names are reduced before normalization into an [`AlbumKey`]
+///
+/// Only the key is affected; albums keep the display name they were created
+/// with. Both options are off by default, so names match exactly as tagged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AlbumKeyOptions {
+    /// Ignore trailing edition qualifiers such as "(Deluxe Edition)",
+    /// "[2011 Remaster]" or " - Expanded Edition"
+    pub strip_edition_suffixes: bool,
+    /// Ignore any trailing bracketed qualifier, e.g. "(Live)" or "[Disc 1]"
+    pub strip_bracketed_qualifiers: bool,
+}
+
+impl AlbumKeyOptions {
+    /// Part of `name` used for matching
+    ///
+    /// Qualifiers are stripped from the end only, and never when nothing
+    /// would be left of the name.
+    pub fn matching_name<'a>(&self, name: &'a str) -> &'a str {
+        let mut name = name.trim();
+        while let Some(stripped) = self.strip_qualifier(name) {
+            if stripped.is_empty() {
+                break;
+            }
+            name = stripped;
+        }
+        name
+    }
+
+    /// `name` without its last qualifier, if that qualifier is ignored
+    fn strip_qualifier<'a>(&self, name: &'a str) -> Option<&'a str> {
+        let bracket = [('(', ')'), ('[', ']')]
+            .into_iter()
+            .find(|(_, close)| name.ends_with(*close));
+        if let Some((open, _)) = bracket {
+            let start = name.rfind(open)?;
+            let qualifier = &name[start + 1..name.len() - 1];
+            let strip = self.strip_bracketed_qualifiers
+                || (self.strip_edition_suffixes && is_edition(qualifier));
+            return strip.then(|| name[..start].trim_end());
+        }
+
+        if !self.strip_edition_suffixes {
+            return None;
+        }
+        let (head, suffix) = name.rsplit_once(" - ")?;
+        is_edition(suffix).then(|| head.trim_end())
+    }
+}
+
+fn is_edition(qualifier: &str) -> bool {
+    qualifier.split(|c: char| !c.is_alphanumeric()).any(|word| {
+        EDITION_WORDS
+            .iter()
+            .any(|edition| word.eq_ignore_ascii_case(edition))
+    })
+}
 
 /// Album repository interface for data access operations
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
@@ -83,17 +178,34 @@ pub trait AlbumRepository: PlatformSendSync {
     /// * `year` - Release year
     /// * `page_request` - Pagination parameters
     async fn query_by_year(&self, year: i32, page_request: PageRequest) -> Result<Page<Album>>;
+
+    /// Resolve albums by [`AlbumKey`], inserting the ones that don't exist
+    ///
+    /// Existing albums are looked up with one `IN (...)` query and missing
+    /// candidates are inserted in bulk. Candidates sharing a key are inserted
+    /// once.
+    ///
+    /// # Arguments
+    /// * `albums` - Candidate albums, used as-is when they don't exist yet
+    ///
+    /// # Returns
+    /// Map of album key to album ID for every candidate
+    async fn get_or_create_many(&self, albums: &[Album]) -> Result<HashMap<AlbumKey, String>>;
 }
 
 /// SQLite implementation of AlbumRepository
 pub struct SqliteAlbumRepository {
     adapter: PlatformArc<dyn DatabaseAdapter>,
+    busy_retry: BusyRetry,
 }
 
 impl SqliteAlbumRepository {
     /// Create a new repository using the provided database adapter.
     pub fn new(adapter: PlatformArc<dyn DatabaseAdapter>) -> Self {
-        Self { adapter }
+        Self {
+            adapter,
+            busy_retry: BusyRetry::default(),
+        }
     }
 
     fn validate_album(album: &Album) -> Result<()> {
@@ -194,8 +306,9 @@ impl AlbumRepository for SqliteAlbumRepository {
 
     async fn insert(&self, album: &Album) -> Result<()> {
         Self::validate_album(album)?;
-        self.adapter
+        self.busy_retry
             .execute(
+                self.adapter.as_ref(),
                 r#"
                 INSERT INTO albums (
                     id, name, normalized_name, artist_id, year,
@@ -212,8 +325,9 @@ impl AlbumRepository for SqliteAlbumRepository {
     async fn update(&self, album: &Album) -> Result<()> {
         Self::validate_album(album)?;
         let affected = self
-            .adapter
+            .busy_retry
             .execute(
+                self.adapter.as_ref(),
                 r#"
                 UPDATE albums
                 SET name = ?, normalized_name = ?, artist_id = ?, year = ?,
@@ -234,8 +348,9 @@ impl AlbumRepository for SqliteAlbumRepository {
 
     async fn delete(&self, id: &str) -> Result<bool> {
         let affected = self
-            .adapter
+            .busy_retry
             .execute(
+                self.adapter.as_ref(),
                 "DELETE FROM albums WHERE id = ?",
                 &[QueryValue::Text(id.to_string())],
             )
@@ -304,6 +419,64 @@ impl AlbumRepository for SqliteAlbumRepository {
         )
         .await
     }
+
+    async fn get_or_create_many(&self, albums: &[Album]) -> Result<HashMap<AlbumKey, String>> {
+        let keys: HashSet<AlbumKey> = albums.iter().map(AlbumKey::of).collect();
+        let mut names: Vec<&str> = keys
+            .iter()
+            .map(|key| key.normalized_name.as_str())
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+
+        let mut ids = HashMap::with_capacity(keys.len());
+        for chunk in names.chunks(MAX_BIND_PARAMS) {
+            let sql = format!(
+                "SELECT id, normalized_name, artist_id FROM albums WHERE normalized_name IN {}",
+                value_placeholders(1, chunk.len())
+            );
+            let params: Vec<QueryValue> = chunk
+                .iter()
+                .map(|name| QueryValue::Text(name.to_string()))
+                .collect();
+            for row in self.adapter.query(&sql, &params).await? {
+                let key = AlbumKey {
+                    normalized_name: get_string(&row, "normalized_name")?,
+                    artist_id: get_optional_string(&row, "artist_id")?,
+                };
+                if keys.contains(&key) {
+                    let id = get_string(&row, "id")?;
+                    ids.entry(key).or_insert(id);
+                }
+            }
+        }
+
+        let mut missing = Vec::new();
+        for album in albums {
+            if let Entry::Vacant(entry) = ids.entry(AlbumKey::of(album)) {
+                Self::validate_album(album)?;
+                entry.insert(album.id.clone());
+                missing.push(album);
+            }
+        }
+
+        for chunk in missing.chunks(MAX_BIND_PARAMS / INSERT_COLUMNS) {
+            let sql = format!(
+                "INSERT INTO albums (id, name, normalized_name, artist_id, year, genre, \
+                 artwork_id, track_count, total_duration_ms, created_at, updated_at) VALUES {}",
+                value_placeholders(chunk.len(), INSERT_COLUMNS)
+            );
+            let params: Vec<QueryValue> = chunk
+                .iter()
+                .flat_map(|album| Self::insert_params(album))
+                .collect();
+            self.busy_retry
+                .execute(self.adapter.as_ref(), &sql, &params)
+                .await?;
+        }
+
+        Ok(ids)
+    }
 }
 
 pub(crate) fn row_to_album(row: &QueryRow) -> Result<Album> {
@@ -348,10 +521,6 @@ fn get_optional_i64(row: &QueryRow, key: &str) -> Result<Option<i64>> {
     })
 }
 
-fn get_i32(row: &QueryRow, key: &str) -> Result<i32> {
-    Ok(get_i64(row, key)? as i32)
-}
-
 fn get_optional_i32(row: &QueryRow, key: &str) -> Result<Option<i32>> {
     Ok(get_optional_i64(row, key)?.map(|value| value as i32))
 }
@@ -383,6 +552,39 @@ mod tests {
     use crate::models::Artist;
     use crate::repositories::artist::{ArtistRepository, SqliteArtistRepository};
 
+    #[test]
+    fn test_album_key_options_strip_qualifiers() {
+        let editions = AlbumKeyOptions {
+            strip_edition_suffixes: true,
+            ..Default::default()
+        };
+        assert_eq!(editions.matching_name("Album (Deluxe)"), "Album");
+        assert_eq!(editions.matching_name("Album (Deluxe Edition) "), "Album");
+        assert_eq!(editions.matching_name("Album [2011 Remaster]"), "Album");
+        assert_eq!(editions.matching_name("Album - Expanded Edition"), "Album");
+        assert_eq!(editions.matching_name("Album (Live)"), "Album (Live)");
+        assert_eq!(
+            editions.matching_name("(What's the Story) Morning Glory?"),
+            "(What's the Story) Morning Glory?"
+        );
+        assert_eq!(editions.matching_name("(Deluxe)"), "(Deluxe)");
+
+        let bracketed = AlbumKeyOptions {
+            strip_bracketed_qualifiers: true,
+            ..Default::default()
+        };
+        assert_eq!(bracketed.matching_name("Album (Live) [Disc 1]"), "Album");
+        assert_eq!(
+            bracketed.matching_name("Album - Deluxe Edition"),
+            "Album - Deluxe Edition"
+        );
+
+        assert_eq!(
+            AlbumKeyOptions::default().matching_name("Album (Deluxe)"),
+            "Album (Deluxe)"
+        );
+    }
+
     #[core_async::test]
     async fn test_insert_and_find_album() {
         let pool = create_test_pool().await.unwrap();
@@ -521,6 +723,32 @@ mod tests {
             .all(|a| a.artist_id == Some(artist1.id.clone())));
     }
 
+    #[core_async::test]
+    async fn test_get_or_create_many() {
+        let pool = create_test_pool().await.unwrap();
+        let artist_repo = SqliteArtistRepository::from_pool(pool.clone());
+        let repo = SqliteAlbumRepository::from_pool(pool);
+
+        let artist = Artist::new("Test Artist".to_string());
+        artist_repo.insert(&artist).await.unwrap();
+        let existing = Album::new("Greatest Hits".to_string(), Some(artist.id.clone()));
+        repo.insert(&existing).await.unwrap();
+
+        // Same name without the artist is a different album
+        let unattributed = Album::new("Greatest Hits".to_string(), None);
+        let candidates = vec![
+            Album::new("Greatest Hits".to_string(), Some(artist.id.clone())),
+            unattributed.clone(),
+            Album::new("Greatest Hits".to_string(), None),
+        ];
+        let ids = repo.get_or_create_many(&candidates).await.unwrap();
+
+        assert_eq!(ids.len(), 2);
+        assert_eq!(ids[&AlbumKey::of(&existing)], existing.id);
+        assert_eq!(ids[&AlbumKey::of(&unattributed)], unattributed.id);
+        assert_eq!(repo.count().await.unwrap(), 2);
+    }
+
     #[core_async::test]
     async fn test_count_albums() {
         let pool = create_test_pool().await.unwrap();
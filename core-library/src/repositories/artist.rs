@@ -1,12 +1,18 @@
 //! Artist repository trait and implementation
 
+use crate::adapters::BusyRetry;
 use crate::error::{LibraryError, Result};
 use crate::models::Artist;
-use crate::repositories::{Page, PageRequest, PlatformArc};
+use crate::repositories::{value_placeholders, Page, PageRequest, PlatformArc, MAX_BIND_PARAMS};
 use bridge_traits::database::{DatabaseAdapter, QueryRow, QueryValue};
 use bridge_traits::platform::PlatformSendSync;
 #[cfg(any(test, not(target_arch = "wasm32")))]
 use sqlx::SqlitePool;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+/// Columns written by an artist insert
+const INSERT_COLUMNS: usize = 8;
 
 /// Artist repository interface for data access operations
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
@@ -71,17 +77,35 @@ pub trait ArtistRepository: PlatformSendSync {
     /// # Arguments
     /// * `name` - Artist name
     async fn find_by_name(&self, name: &str) -> Result<Option<Artist>>;
+
+    /// Resolve artists by normalized name, inserting the ones that don't exist
+    ///
+    /// Existing artists are looked up with one `IN (...)` query and missing
+    /// candidates are inserted in bulk, so resolving many artists costs a few
+    /// round trips instead of two per artist. Candidates sharing a normalized
+    /// name are inserted once.
+    ///
+    /// # Arguments
+    /// * `artists` - Candidate artists, used as-is when they don't exist yet
+    ///
+    /// # Returns
+    /// Map of normalized name to artist ID for every candidate
+    async fn get_or_create_many(&self, artists: &[Artist]) -> Result<HashMap<String, String>>;
 }
 
 /// SQLite implementation of ArtistRepository
 pub struct SqliteArtistRepository {
     adapter: PlatformArc<dyn DatabaseAdapter>,
+    busy_retry: BusyRetry,
 }
 
 impl SqliteArtistRepository {
     /// Create a new repository using the provided database adapter.
     pub fn new(adapter: PlatformArc<dyn DatabaseAdapter>) -> Self {
-        Self { adapter }
+        Self {
+            adapter,
+            busy_retry: BusyRetry::default(),
+        }
     }
 
     fn validate_artist(artist: &Artist) -> Result<()> {
@@ -176,8 +200,9 @@ impl ArtistRepository for SqliteArtistRepository {
 
     async fn insert(&self, artist: &Artist) -> Result<()> {
         Self::validate_artist(artist)?;
-        self.adapter
+        self.busy_retry
             .execute(
+                self.adapter.as_ref(),
                 r#"
                 INSERT INTO artists (
                     id, name, normalized_name, sort_name, bio, country,
@@ -194,8 +219,9 @@ impl ArtistRepository for SqliteArtistRepository {
     async fn update(&self, artist: &Artist) -> Result<()> {
         Self::validate_artist(artist)?;
         let affected = self
-            .adapter
+            .busy_retry
             .execute(
+                self.adapter.as_ref(),
                 r#"
                 UPDATE artists
                 SET name = ?, normalized_name = ?, sort_name = ?, bio = ?, country = ?,
@@ -216,8 +242,9 @@ impl ArtistRepository for SqliteArtistRepository {
 
     async fn delete(&self, id: &str) -> Result<bool> {
         let affected = self
-            .adapter
+            .busy_retry
             .execute(
+                self.adapter.as_ref(),
                 "DELETE FROM artists WHERE id = ?",
                 &[QueryValue::Text(id.to_string())],
             )
@@ -267,6 +294,59 @@ impl ArtistRepository for SqliteArtistRepository {
         )
         .await
     }
+
+    async fn get_or_create_many(&self, artists: &[Artist]) -> Result<HashMap<String, String>> {
+        let mut names: Vec<&str> = artists
+            .iter()
+            .map(|artist| artist.normalized_name.as_str())
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+
+        let mut ids = HashMap::with_capacity(names.len());
+        for chunk in names.chunks(MAX_BIND_PARAMS) {
+            let sql = format!(
+                "SELECT id, normalized_name FROM artists WHERE normalized_name IN {}",
+                value_placeholders(1, chunk.len())
+            );
+            let params: Vec<QueryValue> = chunk
+                .iter()
+                .map(|name| QueryValue::Text(name.to_string()))
+                .collect();
+            for row in self.adapter.query(&sql, &params).await? {
+                ids.insert(
+                    get_string(&row, "normalized_name")?,
+                    get_string(&row, "id")?,
+                );
+            }
+        }
+
+        let mut missing = Vec::new();
+        for artist in artists {
+            if let Entry::Vacant(entry) = ids.entry(artist.normalized_name.clone()) {
+                Self::validate_artist(artist)?;
+                entry.insert(artist.id.clone());
+                missing.push(artist);
+            }
+        }
+
+        for chunk in missing.chunks(MAX_BIND_PARAMS / INSERT_COLUMNS) {
+            let sql = format!(
+                "INSERT INTO artists (id, name, normalized_name, sort_name, bio, country, \
+                 created_at, updated_at) VALUES {}",
+                value_placeholders(chunk.len(), INSERT_COLUMNS)
+            );
+            let params: Vec<QueryValue> = chunk
+                .iter()
+                .flat_map(|artist| Self::insert_params(artist))
+                .collect();
+            self.busy_retry
+                .execute(self.adapter.as_ref(), &sql, &params)
+                .await?;
+        }
+
+        Ok(ids)
+    }
 }
 
 pub(crate) fn row_to_artist(row: &QueryRow) -> Result<Artist> {
@@ -431,6 +511,34 @@ mod tests {
         assert!(found.is_some());
     }
 
+    #[core_async::test]
+    async fn test_get_or_create_many() {
+        let pool = create_test_pool().await.unwrap();
+        let repo = SqliteArtistRepository::from_pool(pool);
+
+        let existing = Artist::new("Existing Artist".to_string());
+        repo.insert(&existing).await.unwrap();
+
+        // One candidate already exists and another is listed twice
+        let new_artist = Artist::new("New Artist".to_string());
+        let candidates = vec![
+            Artist::new("EXISTING ARTIST".to_string()),
+            new_artist.clone(),
+            Artist::new("New Artist".to_string()),
+        ];
+        let ids = repo.get_or_create_many(&candidates).await.unwrap();
+
+        assert_eq!(ids.len(), 2);
+        assert_eq!(ids[&existing.normalized_name], existing.id);
+        assert_eq!(ids[&new_artist.normalized_name], new_artist.id);
+        assert_eq!(repo.count().await.unwrap(), 2);
+
+        // Resolving again creates nothing
+        let again = repo.get_or_create_many(&candidates).await.unwrap();
+        assert_eq!(again, ids);
+        assert_eq!(repo.count().await.unwrap(), 2);
+    }
+
     #[core_async::test]
     async fn test_count_artists() {
         let pool = create_test_pool().await.unwrap();
@@ -1,5 +1,6 @@
 //! Artwork repository trait and implementation
 
+use crate::adapters::BusyRetry;
 use crate::error::{LibraryError, Result};
 use crate::models::Artwork;
 use crate::repositories::{Page, PageRequest, PlatformArc};
@@ -71,12 +72,16 @@ pub trait ArtworkRepository: PlatformSendSync {
 /// SQLite implementation of the artwork repository
 pub struct SqliteArtworkRepository {
     adapter: PlatformArc<dyn DatabaseAdapter>,
+    busy_retry: BusyRetry,
 }
 
 impl SqliteArtworkRepository {
     /// Create a new artwork repository with the given database adapter
     pub fn new(adapter: PlatformArc<dyn DatabaseAdapter>) -> Self {
-        Self { adapter }
+        Self {
+            adapter,
+            busy_retry: BusyRetry::default(),
+        }
     }
 
     #[cfg(not(target_arch = "wasm32"))]
@@ -121,23 +126,6 @@ impl SqliteArtworkRepository {
         })
     }
 
-    fn get_i32(row: &QueryRow, col: &str) -> Result<i32> {
-        row.get(col)
-            .and_then(|v| match v {
-                QueryValue::Integer(i) => Some(*i as i32),
-                _ => None,
-            })
-            .ok_or_else(|| Self::missing_column(col))
-    }
-
-    fn get_optional_i32(row: &QueryRow, col: &str) -> Option<i32> {
-        row.get(col).and_then(|v| match v {
-            QueryValue::Integer(i) => Some(*i as i32),
-            QueryValue::Null => None,
-            _ => None,
-        })
-    }
-
     fn get_blob(row: &QueryRow, col: &str) -> Result<Vec<u8>> {
         row.get(col)
             .and_then(|v| match v {
@@ -162,14 +150,6 @@ impl SqliteArtworkRepository {
         }
     }
 
-    // Helper to build an optional i32 parameter
-    fn opt_i32(value: Option<i32>) -> QueryValue {
-        match value {
-            Some(v) => QueryValue::Integer(v as i64),
-            None => QueryValue::Null,
-        }
-    }
-
     // Helper to convert a QueryRow into an Artwork
     fn row_to_artwork(row: QueryRow) -> Result<Artwork> {
         Ok(Artwork {
@@ -249,7 +229,9 @@ impl ArtworkRepository for SqliteArtworkRepository {
         "#;
 
         let params = Self::insert_params(artwork);
-        self.adapter.execute(sql, &params).await?;
+        self.busy_retry
+            .execute(self.adapter.as_ref(), sql, &params)
+            .await?;
 
         Ok(())
     }
@@ -269,7 +251,10 @@ impl ArtworkRepository for SqliteArtworkRepository {
         "#;
 
         let params = Self::update_params(artwork);
-        let rows_affected = self.adapter.execute(sql, &params).await?;
+        let rows_affected = self
+            .busy_retry
+            .execute(self.adapter.as_ref(), sql, &params)
+            .await?;
 
         if rows_affected == 0 {
             return Err(LibraryError::NotFound {
@@ -285,7 +270,10 @@ impl ArtworkRepository for SqliteArtworkRepository {
         let sql = "DELETE FROM artworks WHERE id = ?";
         let params = vec![QueryValue::Text(id.to_string())];
 
-        let rows_affected = self.adapter.execute(sql, &params).await?;
+        let rows_affected = self
+            .busy_retry
+            .execute(self.adapter.as_ref(), sql, &params)
+            .await?;
 
         Ok(rows_affected > 0)
     }
@@ -2,18 +2,21 @@
 //!
 //! Stores cache metadata in SQLite using the DatabaseAdapter trait for cross-platform compatibility.
 
+use crate::adapters::BusyRetry;
 use crate::error::{LibraryError, Result};
-use crate::models::{CachedTrack, CacheStats, CacheStatus, TrackId};
+use crate::models::{CacheStats, CacheStatus, CachedTrack, TrackId};
 use crate::repositories::PlatformArc;
 use bridge_traits::database::{DatabaseAdapter, QueryRow, QueryValue};
+use bridge_traits::platform::PlatformSendSync;
 use tracing::{debug, error, instrument};
 
-// Schema is now managed via migrations/003_add_cache_metadata.sql
+// Schema is now managed via migrations/003_add_cache_metadata.sql and
+// migrations/007_add_cache_pinning.sql
 
 /// Repository trait for cache metadata operations.
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
-pub trait CacheMetadataRepository {
+pub trait CacheMetadataRepository: PlatformSendSync {
     /// Initialize the repository (create tables if needed).
     async fn initialize(&self) -> Result<()>;
 
@@ -29,16 +32,16 @@ pub trait CacheMetadataRepository {
     /// Find all cache entries with a specific status.
     async fn find_by_status(&self, status: CacheStatus) -> Result<Vec<CachedTrack>>;
 
-    /// Find all cached tracks sorted by last accessed time (for LRU eviction).
+    /// Find all unpinned cached tracks sorted by last accessed time (for LRU eviction).
     async fn find_for_lru_eviction(&self, limit: usize) -> Result<Vec<CachedTrack>>;
 
-    /// Find all cached tracks sorted by play count (for LFU eviction).
+    /// Find all unpinned cached tracks sorted by play count (for LFU eviction).
     async fn find_for_lfu_eviction(&self, limit: usize) -> Result<Vec<CachedTrack>>;
 
-    /// Find all cached tracks sorted by cached time (for FIFO eviction).
+    /// Find all unpinned cached tracks sorted by cached time (for FIFO eviction).
     async fn find_for_fifo_eviction(&self, limit: usize) -> Result<Vec<CachedTrack>>;
 
-    /// Find all cached tracks sorted by size descending (for largest-first eviction).
+    /// Find all unpinned cached tracks sorted by size descending (for largest-first eviction).
     async fn find_for_largest_eviction(&self, limit: usize) -> Result<Vec<CachedTrack>>;
 
     /// Delete a cache entry by track ID.
@@ -54,12 +57,16 @@ pub trait CacheMetadataRepository {
 /// SQLite implementation of CacheMetadataRepository.
 pub struct SqliteCacheMetadataRepository {
     db: PlatformArc<dyn DatabaseAdapter>,
+    busy_retry: BusyRetry,
 }
 
 impl SqliteCacheMetadataRepository {
     /// Create a new repository with the given database adapter.
     pub fn new(db: PlatformArc<dyn DatabaseAdapter>) -> Self {
-        Self { db }
+        Self {
+            db,
+            busy_retry: BusyRetry::default(),
+        }
     }
 
     /// Convert a QueryRow to CachedTrack.
@@ -86,6 +93,7 @@ impl SqliteCacheMetadataRepository {
             downloaded_bytes: get_i64(row, "downloaded_bytes")? as u64,
             download_attempts: get_i64(row, "download_attempts")? as u32,
             last_error: get_optional_string(row, "last_error")?,
+            pinned: get_i64(row, "pinned")? != 0,
         })
     }
 
@@ -139,22 +147,21 @@ impl CacheMetadataRepository for SqliteCacheMetadataRepository {
                 download_started_at INTEGER,
                 downloaded_bytes INTEGER NOT NULL DEFAULT 0,
                 download_attempts INTEGER NOT NULL DEFAULT 0,
-                last_error TEXT
+                last_error TEXT,
+                pinned INTEGER NOT NULL DEFAULT 0
             )", &[] as &[QueryValue]),
             ("CREATE INDEX IF NOT EXISTS idx_cache_status ON cache_metadata(status)", &[]),
             ("CREATE INDEX IF NOT EXISTS idx_cache_last_accessed ON cache_metadata(last_accessed_at)", &[]),
             ("CREATE INDEX IF NOT EXISTS idx_cache_play_count ON cache_metadata(play_count)", &[]),
             ("CREATE INDEX IF NOT EXISTS idx_cache_cached_at ON cache_metadata(cached_at)", &[]),
             ("CREATE INDEX IF NOT EXISTS idx_cache_size ON cache_metadata(cached_size)", &[]),
+            ("CREATE INDEX IF NOT EXISTS idx_cache_pinned ON cache_metadata(pinned)", &[]),
         ];
 
-        self.db
-            .execute_batch(&statements)
-            .await
-            .map_err(|e| {
-                error!("Failed to create cache_metadata table: {}", e);
-                LibraryError::CacheError(format!("Failed to initialize repository: {}", e))
-            })?;
+        self.db.execute_batch(&statements).await.map_err(|e| {
+            error!("Failed to create cache_metadata table: {}", e);
+            LibraryError::CacheError(format!("Failed to initialize repository: {}", e))
+        })?;
 
         debug!("Cache metadata repository initialized");
         Ok(())
@@ -167,8 +174,9 @@ impl CacheMetadataRepository for SqliteCacheMetadataRepository {
             INSERT INTO cache_metadata (
                 track_id, cache_path, file_size, cached_size, content_hash,
                 encrypted, status, play_count, cached_at, last_accessed_at,
-                download_started_at, downloaded_bytes, download_attempts, last_error
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                download_started_at, downloaded_bytes, download_attempts, last_error,
+                pinned
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         "#;
 
         let params = vec![
@@ -193,12 +201,16 @@ impl CacheMetadataRepository for SqliteCacheMetadataRepository {
                 .as_ref()
                 .map(|s| QueryValue::Text(s.clone()))
                 .unwrap_or(QueryValue::Null),
+            QueryValue::Integer(if track.pinned { 1 } else { 0 }),
         ];
 
-        self.db.execute(sql, &params).await.map_err(|e| {
-            error!("Failed to insert cache entry: {}", e);
-            LibraryError::CacheError(format!("Failed to insert cache entry: {}", e))
-        })?;
+        self.busy_retry
+            .execute(self.db.as_ref(), sql, &params)
+            .await
+            .map_err(|e| {
+                error!("Failed to insert cache entry: {}", e);
+                LibraryError::CacheError(format!("Failed to insert cache entry: {}", e))
+            })?;
 
         Ok(())
     }
@@ -210,7 +222,7 @@ impl CacheMetadataRepository for SqliteCacheMetadataRepository {
                 cache_path = ?, file_size = ?, cached_size = ?, content_hash = ?,
                 encrypted = ?, status = ?, play_count = ?, cached_at = ?,
                 last_accessed_at = ?, download_started_at = ?, downloaded_bytes = ?,
-                download_attempts = ?, last_error = ?
+                download_attempts = ?, last_error = ?, pinned = ?
             WHERE track_id = ?
         "#;
 
@@ -235,13 +247,17 @@ impl CacheMetadataRepository for SqliteCacheMetadataRepository {
                 .as_ref()
                 .map(|s| QueryValue::Text(s.clone()))
                 .unwrap_or(QueryValue::Null),
+            QueryValue::Integer(if track.pinned { 1 } else { 0 }),
             QueryValue::Text(track.track_id.to_string()),
         ];
 
-        self.db.execute(sql, &params).await.map_err(|e| {
-            error!("Failed to update cache entry: {}", e);
-            LibraryError::CacheError(format!("Failed to update cache entry: {}", e))
-        })?;
+        self.busy_retry
+            .execute(self.db.as_ref(), sql, &params)
+            .await
+            .map_err(|e| {
+                error!("Failed to update cache entry: {}", e);
+                LibraryError::CacheError(format!("Failed to update cache entry: {}", e))
+            })?;
 
         Ok(())
     }
@@ -278,7 +294,7 @@ impl CacheMetadataRepository for SqliteCacheMetadataRepository {
 
     #[instrument(skip(self))]
     async fn find_for_lru_eviction(&self, limit: usize) -> Result<Vec<CachedTrack>> {
-        let sql = "SELECT * FROM cache_metadata WHERE status = 'cached' ORDER BY last_accessed_at ASC LIMIT ?";
+        let sql = "SELECT * FROM cache_metadata WHERE status = 'cached' AND pinned = 0 ORDER BY last_accessed_at ASC LIMIT ?";
         let params = vec![QueryValue::Integer(limit as i64)];
 
         let rows = self.db.query(sql, &params).await.map_err(|e| {
@@ -291,7 +307,8 @@ impl CacheMetadataRepository for SqliteCacheMetadataRepository {
 
     #[instrument(skip(self))]
     async fn find_for_lfu_eviction(&self, limit: usize) -> Result<Vec<CachedTrack>> {
-        let sql = "SELECT * FROM cache_metadata WHERE status = 'cached' ORDER BY play_count ASC LIMIT ?";
+        let sql =
+            "SELECT * FROM cache_metadata WHERE status = 'cached' AND pinned = 0 ORDER BY play_count ASC LIMIT ?";
         let params = vec![QueryValue::Integer(limit as i64)];
 
         let rows = self.db.query(sql, &params).await.map_err(|e| {
@@ -305,7 +322,7 @@ impl CacheMetadataRepository for SqliteCacheMetadataRepository {
     #[instrument(skip(self))]
     async fn find_for_fifo_eviction(&self, limit: usize) -> Result<Vec<CachedTrack>> {
         let sql =
-            "SELECT * FROM cache_metadata WHERE status = 'cached' ORDER BY cached_at ASC LIMIT ?";
+            "SELECT * FROM cache_metadata WHERE status = 'cached' AND pinned = 0 ORDER BY cached_at ASC LIMIT ?";
         let params = vec![QueryValue::Integer(limit as i64)];
 
         let rows = self.db.query(sql, &params).await.map_err(|e| {
@@ -318,7 +335,7 @@ impl CacheMetadataRepository for SqliteCacheMetadataRepository {
 
     #[instrument(skip(self))]
     async fn find_for_largest_eviction(&self, limit: usize) -> Result<Vec<CachedTrack>> {
-        let sql = "SELECT * FROM cache_metadata WHERE status = 'cached' ORDER BY cached_size DESC LIMIT ?";
+        let sql = "SELECT * FROM cache_metadata WHERE status = 'cached' AND pinned = 0 ORDER BY cached_size DESC LIMIT ?";
         let params = vec![QueryValue::Integer(limit as i64)];
 
         let rows = self.db.query(sql, &params).await.map_err(|e| {
@@ -334,10 +351,13 @@ impl CacheMetadataRepository for SqliteCacheMetadataRepository {
         let sql = "DELETE FROM cache_metadata WHERE track_id = ?";
         let params = vec![QueryValue::Text(track_id.to_string())];
 
-        self.db.execute(sql, &params).await.map_err(|e| {
-            error!("Failed to delete cache entry: {}", e);
-            LibraryError::CacheError(format!("Failed to delete cache entry: {}", e))
-        })?;
+        self.busy_retry
+            .execute(self.db.as_ref(), sql, &params)
+            .await
+            .map_err(|e| {
+                error!("Failed to delete cache entry: {}", e);
+                LibraryError::CacheError(format!("Failed to delete cache entry: {}", e))
+            })?;
 
         Ok(())
     }
@@ -353,7 +373,9 @@ impl CacheMetadataRepository for SqliteCacheMetadataRepository {
                 COALESCE(SUM(cached_size), 0) as total_bytes,
                 COALESCE(SUM(file_size), 0) as total_original_bytes,
                 SUM(CASE WHEN encrypted = 1 THEN 1 ELSE 0 END) as encrypted_tracks,
-                COALESCE(SUM(play_count), 0) as total_plays
+                COALESCE(SUM(play_count), 0) as total_plays,
+                SUM(CASE WHEN pinned = 1 THEN 1 ELSE 0 END) as pinned_tracks,
+                COALESCE(SUM(CASE WHEN pinned = 1 THEN cached_size ELSE 0 END), 0) as pinned_bytes
             FROM cache_metadata
         "#;
 
@@ -377,6 +399,8 @@ impl CacheMetadataRepository for SqliteCacheMetadataRepository {
             encrypted_tracks: get_i64(row, "encrypted_tracks")? as usize,
             total_plays: get_i64(row, "total_plays")? as u64,
             tracks_pending_eviction: 0, // Calculated separately if needed
+            pinned_tracks: get_i64(row, "pinned_tracks")? as usize,
+            pinned_bytes: get_i64(row, "pinned_bytes")? as u64,
             calculated_at: chrono::Utc::now().timestamp(),
         })
     }
@@ -0,0 +1,229 @@
+//! Enrichment status repository trait and implementation
+//!
+//! Records which tracks of the current enrichment run are still pending, so
+//! an interrupted run can pick up where it stopped.
+
+use crate::adapters::BusyRetry;
+use crate::error::{LibraryError, Result};
+use crate::repositories::{value_placeholders, PlatformArc, MAX_BIND_PARAMS};
+use bridge_traits::database::{DatabaseAdapter, QueryValue};
+use bridge_traits::platform::PlatformSendSync;
+#[cfg(any(test, not(target_arch = "wasm32")))]
+use sqlx::SqlitePool;
+
+/// Columns written by a status insert
+const INSERT_COLUMNS: usize = 3;
+
+/// Enrichment outcome of a track in the current run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EnrichmentStatus {
+    /// Queued but not processed yet
+    Pending,
+    /// Processed successfully
+    Done,
+    /// Processed, but enrichment failed after all retries
+    Failed,
+}
+
+impl EnrichmentStatus {
+    /// Database representation
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EnrichmentStatus::Pending => "pending",
+            EnrichmentStatus::Done => "done",
+            EnrichmentStatus::Failed => "failed",
+        }
+    }
+}
+
+/// Enrichment status repository interface
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait EnrichmentStatusRepository: PlatformSendSync {
+    /// Start a new run: forget the previous one and mark `track_ids` pending
+    async fn reset(&self, track_ids: &[String]) -> Result<()>;
+
+    /// Record the outcome of one track
+    ///
+    /// `error` is kept for failed tracks and cleared otherwise.
+    async fn set_status(
+        &self,
+        track_id: &str,
+        status: EnrichmentStatus,
+        error: Option<&str>,
+    ) -> Result<()>;
+
+    /// IDs of the tracks with `status`, in the order they were queued
+    async fn find_by_status(&self, status: EnrichmentStatus) -> Result<Vec<String>>;
+}
+
+/// SQLite implementation of EnrichmentStatusRepository
+pub struct SqliteEnrichmentStatusRepository {
+    adapter: PlatformArc<dyn DatabaseAdapter>,
+    busy_retry: BusyRetry,
+}
+
+impl SqliteEnrichmentStatusRepository {
+    /// Create a new repository with the given database adapter
+    pub fn new(adapter: PlatformArc<dyn DatabaseAdapter>) -> Self {
+        Self {
+            adapter,
+            busy_retry: BusyRetry::default(),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Create a new repository from a SQLite connection pool (native only)
+    pub fn from_pool(pool: SqlitePool) -> Self {
+        use crate::adapters::SqliteAdapter;
+        Self::new(PlatformArc::new(SqliteAdapter::from_pool(pool)))
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl EnrichmentStatusRepository for SqliteEnrichmentStatusRepository {
+    async fn reset(&self, track_ids: &[String]) -> Result<()> {
+        self.busy_retry
+            .execute(self.adapter.as_ref(), "DELETE FROM enrichment_status", &[])
+            .await?;
+
+        let now = chrono::Utc::now().timestamp();
+        for chunk in track_ids.chunks(MAX_BIND_PARAMS / INSERT_COLUMNS) {
+            let sql = format!(
+                "INSERT OR IGNORE INTO enrichment_status (track_id, status, updated_at) VALUES {}",
+                value_placeholders(chunk.len(), INSERT_COLUMNS)
+            );
+            let params: Vec<QueryValue> = chunk
+                .iter()
+                .flat_map(|track_id| {
+                    [
+                        QueryValue::Text(track_id.clone()),
+                        QueryValue::Text(EnrichmentStatus::Pending.as_str().to_string()),
+                        QueryValue::Integer(now),
+                    ]
+                })
+                .collect();
+            self.busy_retry
+                .execute(self.adapter.as_ref(), &sql, &params)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn set_status(
+        &self,
+        track_id: &str,
+        status: EnrichmentStatus,
+        error: Option<&str>,
+    ) -> Result<()> {
+        let error = match (status, error) {
+            (EnrichmentStatus::Failed, Some(error)) => QueryValue::Text(error.to_string()),
+            _ => QueryValue::Null,
+        };
+        let params = [
+            QueryValue::Text(track_id.to_string()),
+            QueryValue::Text(status.as_str().to_string()),
+            error,
+            QueryValue::Integer(chrono::Utc::now().timestamp()),
+        ];
+
+        self.busy_retry
+            .execute(
+                self.adapter.as_ref(),
+                "INSERT INTO enrichment_status (track_id, status, last_error, updated_at) \
+                 VALUES (?, ?, ?, ?) \
+                 ON CONFLICT(track_id) DO UPDATE SET status = excluded.status, \
+                 last_error = excluded.last_error, updated_at = excluded.updated_at",
+                &params,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn find_by_status(&self, status: EnrichmentStatus) -> Result<Vec<String>> {
+        let rows = self
+            .adapter
+            .query(
+                "SELECT track_id FROM enrichment_status WHERE status = ? ORDER BY rowid",
+                &[QueryValue::Text(status.as_str().to_string())],
+            )
+            .await?;
+
+        rows.iter()
+            .map(|row| {
+                row.get("track_id")
+                    .and_then(|value| value.as_string())
+                    .ok_or_else(|| LibraryError::InvalidInput {
+                        field: "track_id".to_string(),
+                        message: "missing column in result set".to_string(),
+                    })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::create_test_pool;
+    use crate::models::Track;
+    use crate::repositories::track::{SqliteTrackRepository, TrackRepository};
+
+    async fn insert_tracks(pool: &SqlitePool, count: usize) -> Vec<String> {
+        sqlx::query(
+            "INSERT INTO providers (id, type, display_name, profile_id, created_at) \
+             VALUES ('provider', 'GoogleDrive', 'Test Provider', 'profile', 1699200000)",
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+
+        let repo = SqliteTrackRepository::from_pool(pool.clone());
+        let mut ids = Vec::new();
+        for i in 0..count {
+            let track = Track::new(
+                format!("Track {}", i),
+                "provider".to_string(),
+                format!("file-{}", i),
+                180_000,
+                1,
+            );
+            repo.insert(&track).await.unwrap();
+            ids.push(track.id);
+        }
+        ids
+    }
+
+    #[core_async::test]
+    async fn test_reset_and_record_outcomes() {
+        let pool = create_test_pool().await.unwrap();
+        let ids = insert_tracks(&pool, 3).await;
+        let repo = SqliteEnrichmentStatusRepository::from_pool(pool);
+
+        repo.reset(&ids).await.unwrap();
+        repo.set_status(&ids[0], EnrichmentStatus::Done, None)
+            .await
+            .unwrap();
+        repo.set_status(&ids[1], EnrichmentStatus::Failed, Some("timeout"))
+            .await
+            .unwrap();
+
+        let pending = repo.find_by_status(EnrichmentStatus::Pending).await;
+        assert_eq!(pending.unwrap(), vec![ids[2].clone()]);
+        let failed = repo.find_by_status(EnrichmentStatus::Failed).await;
+        assert_eq!(failed.unwrap(), vec![ids[1].clone()]);
+
+        // A new run forgets the old outcomes
+        repo.reset(&ids[..2]).await.unwrap();
+        let pending = repo.find_by_status(EnrichmentStatus::Pending).await;
+        assert_eq!(pending.unwrap(), ids[..2].to_vec());
+        assert!(repo
+            .find_by_status(EnrichmentStatus::Done)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
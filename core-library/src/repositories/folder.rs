@@ -1,5 +1,6 @@
 //! Folder repository trait and implementation
 
+use crate::adapters::BusyRetry;
 use crate::error::{LibraryError, Result};
 use crate::models::Folder;
 use crate::repositories::{Page, PageRequest, PlatformArc};
@@ -90,12 +91,16 @@ pub trait FolderRepository: PlatformSendSync {
 /// SQLite implementation of FolderRepository
 pub struct SqliteFolderRepository {
     adapter: PlatformArc<dyn DatabaseAdapter>,
+    busy_retry: BusyRetry,
 }
 
 impl SqliteFolderRepository {
     /// Create a new repository using the provided database adapter.
     pub fn new(adapter: PlatformArc<dyn DatabaseAdapter>) -> Self {
-        Self { adapter }
+        Self {
+            adapter,
+            busy_retry: BusyRetry::default(),
+        }
     }
 
     fn validate_folder(folder: &Folder) -> Result<()> {
@@ -190,8 +195,9 @@ impl FolderRepository for SqliteFolderRepository {
 
     async fn insert(&self, folder: &Folder) -> Result<()> {
         Self::validate_folder(folder)?;
-        self.adapter
+        self.busy_retry
             .execute(
+                self.adapter.as_ref(),
                 r#"
                 INSERT INTO folders (
                     id, provider_id, provider_folder_id, name, normalized_name, parent_id, path,
@@ -208,8 +214,9 @@ impl FolderRepository for SqliteFolderRepository {
     async fn update(&self, folder: &Folder) -> Result<()> {
         Self::validate_folder(folder)?;
         let affected = self
-            .adapter
+            .busy_retry
             .execute(
+                self.adapter.as_ref(),
                 r#"
                 UPDATE folders
                 SET name = ?, normalized_name = ?, parent_id = ?, path = ?, updated_at = ?
@@ -229,8 +236,9 @@ impl FolderRepository for SqliteFolderRepository {
 
     async fn delete(&self, id: &str) -> Result<bool> {
         let affected = self
-            .adapter
+            .busy_retry
             .execute(
+                self.adapter.as_ref(),
                 "DELETE FROM folders WHERE id = ?",
                 &[QueryValue::Text(id.to_string())],
             )
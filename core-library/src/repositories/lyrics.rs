@@ -1,5 +1,6 @@
 //! Lyrics repository trait and implementation
 
+use crate::adapters::BusyRetry;
 use crate::error::{LibraryError, Result};
 use crate::models::Lyrics;
 use crate::repositories::{Page, PageRequest, PlatformArc};
@@ -82,12 +83,16 @@ pub trait LyricsRepository: PlatformSendSync {
 /// SQLite implementation of LyricsRepository
 pub struct SqliteLyricsRepository {
     adapter: PlatformArc<dyn DatabaseAdapter>,
+    busy_retry: BusyRetry,
 }
 
 impl SqliteLyricsRepository {
     /// Create a new lyrics repository with the given database adapter
     pub fn new(adapter: PlatformArc<dyn DatabaseAdapter>) -> Self {
-        Self { adapter }
+        Self {
+            adapter,
+            busy_retry: BusyRetry::default(),
+        }
     }
 
     #[cfg(not(target_arch = "wasm32"))]
@@ -212,7 +217,9 @@ impl LyricsRepository for SqliteLyricsRepository {
         "#;
 
         let params = Self::insert_params(lyrics);
-        self.adapter.execute(sql, &params).await?;
+        self.busy_retry
+            .execute(self.adapter.as_ref(), sql, &params)
+            .await?;
 
         Ok(())
     }
@@ -232,7 +239,10 @@ impl LyricsRepository for SqliteLyricsRepository {
         "#;
 
         let params = Self::update_params(lyrics);
-        let rows_affected = self.adapter.execute(sql, &params).await?;
+        let rows_affected = self
+            .busy_retry
+            .execute(self.adapter.as_ref(), sql, &params)
+            .await?;
 
         if rows_affected == 0 {
             return Err(LibraryError::NotFound {
@@ -248,7 +258,10 @@ impl LyricsRepository for SqliteLyricsRepository {
         let sql = "DELETE FROM lyrics WHERE track_id = ?";
         let params = vec![QueryValue::Text(track_id.to_string())];
 
-        let rows_affected = self.adapter.execute(sql, &params).await?;
+        let rows_affected = self
+            .busy_retry
+            .execute(self.adapter.as_ref(), sql, &params)
+            .await?;
 
         Ok(rows_affected > 0)
     }
@@ -391,6 +404,10 @@ mod tests {
             provider_id: provider_id.clone(),
             provider_file_id: "file-1".to_string(),
             provider_modified_at: Some(1699200000),
+            leading_silence_ms: None,
+            trailing_silence_ms: None,
+            replay_gain_millibels: None,
+            true_peak_millibels: None,
             hash: Some("test-hash".to_string()),
             title: "Test Track".to_string(),
             normalized_title: "test track".to_string(),
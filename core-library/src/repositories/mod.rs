@@ -20,29 +20,46 @@
 //! - `FolderRepository` - Cloud storage folder hierarchy
 //! - `ArtworkRepository` - Album artwork with deduplication support
 //! - `LyricsRepository` - Track lyrics (plain text and synced LRC format)
+//! - `EnrichmentStatusRepository` - Per-track progress of the enrichment job
 
 // Platform-conditional Arc type (Rc for WASM, Arc for native)
-#[cfg(not(target_arch = "wasm32"))]
-pub(crate) use std::sync::Arc as PlatformArc;
 #[cfg(target_arch = "wasm32")]
 pub(crate) use std::rc::Rc as PlatformArc;
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use std::sync::Arc as PlatformArc;
 
 pub mod album;
 pub mod artist;
 pub mod artwork;
 pub mod cache;
+pub mod enrichment;
 pub mod folder;
 pub mod lyrics;
 pub mod pagination;
 pub mod playlist;
 pub mod track;
 
-pub use album::{AlbumRepository, SqliteAlbumRepository};
+pub use album::{AlbumKey, AlbumKeyOptions, AlbumRepository, SqliteAlbumRepository};
 pub use artist::{ArtistRepository, SqliteArtistRepository};
 pub use artwork::{ArtworkRepository, SqliteArtworkRepository};
 pub use cache::{CacheMetadataRepository, SqliteCacheMetadataRepository};
+pub use enrichment::{
+    EnrichmentStatus, EnrichmentStatusRepository, SqliteEnrichmentStatusRepository,
+};
 pub use folder::{FolderRepository, SqliteFolderRepository};
 pub use lyrics::{LyricsRepository, SqliteLyricsRepository};
 pub use pagination::{Page, PageRequest};
-pub use playlist::{PlaylistRepository, SqlitePlaylistRepository};
-pub use track::{SqliteTrackRepository, TrackRepository};
+pub use playlist::{
+    PlaylistImportReport, PlaylistRepository, SqlitePlaylistRepository, TrackMatchStrategy,
+    UnmatchedPlaylistEntry,
+};
+pub use track::{SqliteTrackRepository, TrackDeletion, TrackRepository};
+
+/// Most bind parameters older SQLite builds accept in one statement
+pub(crate) const MAX_BIND_PARAMS: usize = 999;
+
+/// `rows` comma-separated `(?, ...)` groups of `columns` placeholders each
+pub(crate) fn value_placeholders(rows: usize, columns: usize) -> String {
+    let row = format!("({})", vec!["?"; columns].join(", "));
+    vec![row; rows].join(", ")
+}
@@ -1,5 +1,6 @@
 //! Playlist repository trait and implementation
 
+use crate::adapters::BusyRetry;
 use crate::error::{LibraryError, Result};
 use crate::models::Playlist;
 use crate::repositories::{Page, PageRequest, PlatformArc};
@@ -7,6 +8,41 @@ use bridge_traits::database::{DatabaseAdapter, QueryRow, QueryValue};
 use bridge_traits::platform::PlatformSendSync;
 #[cfg(any(test, not(target_arch = "wasm32")))]
 use sqlx::SqlitePool;
+use std::collections::HashSet;
+
+/// How entries of an imported playlist are matched to local tracks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrackMatchStrategy {
+    /// Same content hash
+    ContentHash,
+    /// Same provider and provider file ID
+    ProviderFile,
+    /// Content hash, falling back to provider file for unhashed tracks
+    #[default]
+    HashOrProviderFile,
+}
+
+/// Playlist entry that matched no local track during an import
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnmatchedPlaylistEntry {
+    /// Name of the playlist the entry belongs to
+    pub playlist_name: String,
+    /// Track ID in the source database
+    pub source_track_id: String,
+    /// Track title in the source database, if the track row exists there
+    pub title: Option<String>,
+}
+
+/// Outcome of [`PlaylistRepository::import_from`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlaylistImportReport {
+    /// IDs of the playlists created, in source order
+    pub created_playlist_ids: Vec<String>,
+    /// Entries added to the created playlists
+    pub matched_tracks: usize,
+    /// Entries left out because no local track matched
+    pub unmatched: Vec<UnmatchedPlaylistEntry>,
+}
 
 /// Playlist repository interface for data access operations
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
@@ -91,17 +127,37 @@ pub trait PlaylistRepository: PlatformSendSync {
 
     /// Count total playlists
     async fn count(&self) -> Result<i64>;
+
+    /// Import user playlists from another library database
+    ///
+    /// Each entry is matched to a track in this library using `strategy`.
+    /// Matched entries keep their relative order; the rest are listed in the
+    /// report. Imported playlists get new IDs, so importing the same source
+    /// twice creates duplicates. System playlists are not imported.
+    ///
+    /// # Arguments
+    /// * `source` - Adapter for the library to import from
+    /// * `strategy` - How entries are matched to local tracks
+    async fn import_from(
+        &self,
+        source: &dyn DatabaseAdapter,
+        strategy: TrackMatchStrategy,
+    ) -> Result<PlaylistImportReport>;
 }
 
 /// SQLite implementation of PlaylistRepository
 pub struct SqlitePlaylistRepository {
     adapter: PlatformArc<dyn DatabaseAdapter>,
+    busy_retry: BusyRetry,
 }
 
 impl SqlitePlaylistRepository {
     /// Create a new repository using the provided database adapter.
     pub fn new(adapter: PlatformArc<dyn DatabaseAdapter>) -> Self {
-        Self { adapter }
+        Self {
+            adapter,
+            busy_retry: BusyRetry::default(),
+        }
     }
 
     fn validate_playlist(playlist: &Playlist) -> Result<()> {
@@ -138,7 +194,7 @@ impl SqlitePlaylistRepository {
             QueryValue::Text(playlist.normalized_name.clone()),
             opt_text(&playlist.description),
             QueryValue::Text(playlist.sort_order.clone()),
-            QueryValue::Integer(playlist.is_public as i64),
+            QueryValue::Integer(playlist.is_public),
             QueryValue::Integer(playlist.track_count),
             QueryValue::Integer(playlist.total_duration_ms),
             opt_text(&playlist.artwork_id),
@@ -183,6 +239,52 @@ impl SqlitePlaylistRepository {
             .and_then(|value| value.as_i64())
             .ok_or_else(|| missing_column("count"))
     }
+
+    /// Local track matching a source entry, with its duration
+    async fn find_matching_track(
+        &self,
+        entry: &QueryRow,
+        strategy: TrackMatchStrategy,
+    ) -> Result<Option<(String, i64)>> {
+        let text = |key: &str| entry.get(key).and_then(|value| value.as_string());
+
+        let by_hash = matches!(
+            strategy,
+            TrackMatchStrategy::ContentHash | TrackMatchStrategy::HashOrProviderFile
+        );
+        let by_provider_file = matches!(
+            strategy,
+            TrackMatchStrategy::ProviderFile | TrackMatchStrategy::HashOrProviderFile
+        );
+
+        let mut row = None;
+        if let Some(hash) = text("hash").filter(|_| by_hash) {
+            row = self
+                .adapter
+                .query_one_optional(
+                    "SELECT id, duration_ms FROM tracks WHERE hash = ? LIMIT 1",
+                    &[QueryValue::Text(hash)],
+                )
+                .await?;
+        }
+        if row.is_none() && by_provider_file {
+            if let (Some(provider_id), Some(file_id)) =
+                (text("provider_id"), text("provider_file_id"))
+            {
+                row = self
+                    .adapter
+                    .query_one_optional(
+                        "SELECT id, duration_ms FROM tracks \
+                         WHERE provider_id = ? AND provider_file_id = ? LIMIT 1",
+                        &[QueryValue::Text(provider_id), QueryValue::Text(file_id)],
+                    )
+                    .await?;
+            }
+        }
+
+        row.map(|row| Ok((get_string(&row, "id")?, get_i64(&row, "duration_ms")?)))
+            .transpose()
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -207,8 +309,9 @@ impl PlaylistRepository for SqlitePlaylistRepository {
 
     async fn insert(&self, playlist: &Playlist) -> Result<()> {
         Self::validate_playlist(playlist)?;
-        self.adapter
+        self.busy_retry
             .execute(
+                self.adapter.as_ref(),
                 r#"
                 INSERT INTO playlists (
                     id, name, normalized_name, description, owner_type, sort_order,
@@ -225,8 +328,9 @@ impl PlaylistRepository for SqlitePlaylistRepository {
     async fn update(&self, playlist: &Playlist) -> Result<()> {
         Self::validate_playlist(playlist)?;
         let affected = self
-            .adapter
+            .busy_retry
             .execute(
+                self.adapter.as_ref(),
                 r#"
                 UPDATE playlists
                 SET name = ?, normalized_name = ?, description = ?, sort_order = ?, 
@@ -247,8 +351,9 @@ impl PlaylistRepository for SqlitePlaylistRepository {
 
     async fn delete(&self, id: &str) -> Result<bool> {
         // Delete playlist tracks first (due to foreign key)
-        self.adapter
+        self.busy_retry
             .execute(
+                self.adapter.as_ref(),
                 "DELETE FROM playlist_tracks WHERE playlist_id = ?",
                 &[QueryValue::Text(id.to_string())],
             )
@@ -256,8 +361,9 @@ impl PlaylistRepository for SqlitePlaylistRepository {
 
         // Then delete the playlist
         let affected = self
-            .adapter
+            .busy_retry
             .execute(
+                self.adapter.as_ref(),
                 "DELETE FROM playlists WHERE id = ?",
                 &[QueryValue::Text(id.to_string())],
             )
@@ -293,8 +399,9 @@ impl PlaylistRepository for SqlitePlaylistRepository {
     }
 
     async fn add_track(&self, playlist_id: &str, track_id: &str, position: i32) -> Result<()> {
-        self.adapter
+        self.busy_retry
             .execute(
+                self.adapter.as_ref(),
                 r#"
                 INSERT INTO playlist_tracks (playlist_id, track_id, position, added_at)
                 VALUES (?, ?, ?, ?)
@@ -312,8 +419,9 @@ impl PlaylistRepository for SqlitePlaylistRepository {
 
     async fn remove_track(&self, playlist_id: &str, track_id: &str) -> Result<bool> {
         let affected = self
-            .adapter
+            .busy_retry
             .execute(
+                self.adapter.as_ref(),
                 "DELETE FROM playlist_tracks WHERE playlist_id = ? AND track_id = ?",
                 &[
                     QueryValue::Text(playlist_id.to_string()),
@@ -343,6 +451,76 @@ impl PlaylistRepository for SqlitePlaylistRepository {
         self.count_with("SELECT COUNT(*) as count FROM playlists", vec![])
             .await
     }
+
+    async fn import_from(
+        &self,
+        source: &dyn DatabaseAdapter,
+        strategy: TrackMatchStrategy,
+    ) -> Result<PlaylistImportReport> {
+        let mut report = PlaylistImportReport::default();
+
+        let rows = source
+            .query(
+                "SELECT * FROM playlists WHERE owner_type = 'user' ORDER BY created_at ASC",
+                &[],
+            )
+            .await?;
+
+        for row in rows {
+            let source_playlist = row_to_playlist(&row)?;
+            let entries = source
+                .query(
+                    r#"
+                    SELECT pt.track_id, t.title, t.hash, t.provider_id, t.provider_file_id
+                    FROM playlist_tracks pt
+                    LEFT JOIN tracks t ON t.id = pt.track_id
+                    WHERE pt.playlist_id = ?
+                    ORDER BY pt.position ASC
+                    "#,
+                    &[QueryValue::Text(source_playlist.id.clone())],
+                )
+                .await?;
+
+            let mut members = Vec::new();
+            let mut seen = HashSet::new();
+            let mut total_duration_ms = 0;
+            for entry in &entries {
+                match self.find_matching_track(entry, strategy).await? {
+                    Some((track_id, duration_ms)) => {
+                        // Two source tracks can share content; keep the first
+                        if seen.insert(track_id.clone()) {
+                            total_duration_ms += duration_ms;
+                            members.push(track_id);
+                        }
+                    }
+                    None => report.unmatched.push(UnmatchedPlaylistEntry {
+                        playlist_name: source_playlist.name.clone(),
+                        source_track_id: get_string(entry, "track_id")?,
+                        title: get_optional_string(entry, "title")?,
+                    }),
+                }
+            }
+
+            let mut playlist = Playlist::new(source_playlist.name);
+            playlist.description = source_playlist.description;
+            playlist.sort_order = source_playlist.sort_order;
+            playlist.is_public = source_playlist.is_public;
+            playlist.track_count = members.len() as i64;
+            playlist.total_duration_ms = total_duration_ms;
+            self.insert(&playlist).await?;
+
+            for (index, track_id) in members.iter().enumerate() {
+                // Positions are 1-based
+                self.add_track(&playlist.id, track_id, index as i32 + 1)
+                    .await?;
+            }
+
+            report.matched_tracks += members.len();
+            report.created_playlist_ids.push(playlist.id);
+        }
+
+        Ok(report)
+    }
 }
 
 pub(crate) fn row_to_playlist(row: &QueryRow) -> Result<Playlist> {
@@ -381,10 +559,6 @@ fn get_i64(row: &QueryRow, key: &str) -> Result<i64> {
         .ok_or_else(|| missing_column(key))
 }
 
-fn get_i32(row: &QueryRow, key: &str) -> Result<i32> {
-    Ok(get_i64(row, key)? as i32)
-}
-
 fn missing_column(column: &str) -> LibraryError {
     LibraryError::InvalidInput {
         field: column.to_string(),
@@ -520,4 +694,84 @@ mod tests {
         let result = repo.insert(&playlist).await;
         assert!(result.is_err());
     }
+
+    #[core_async::test]
+    async fn test_import_playlist_from_other_database() {
+        use crate::adapters::sqlite_native::SqliteAdapter;
+        use crate::db::insert_test_provider;
+        use crate::models::Track;
+        use crate::repositories::track::{SqliteTrackRepository, TrackRepository};
+
+        fn track(file_id: &str, hash: Option<&str>) -> Track {
+            let mut track = Track::new(
+                format!("Song {}", file_id),
+                "test-provider".to_string(),
+                file_id.to_string(),
+                200_000,
+                1,
+            );
+            track.hash = hash.map(str::to_string);
+            track
+        }
+
+        // Source library: a playlist of four tracks
+        let source_pool = create_test_pool().await.unwrap();
+        insert_test_provider(&source_pool).await;
+        let source_tracks = SqliteTrackRepository::from_pool(source_pool.clone());
+        let source_playlists = SqlitePlaylistRepository::from_pool(source_pool.clone());
+
+        let mut playlist = Playlist::new("Road Trip".to_string());
+        playlist.description = Some("Summer 2024".to_string());
+        source_playlists.insert(&playlist).await.unwrap();
+        let entries = [
+            track("a", Some("hash-a")),
+            track("b", Some("hash-b")),
+            track("c", None),
+            track("missing", Some("hash-missing")),
+        ];
+        for (index, entry) in entries.iter().enumerate() {
+            source_tracks.insert(entry).await.unwrap();
+            source_playlists
+                .add_track(&playlist.id, &entry.id, index as i32 + 1)
+                .await
+                .unwrap();
+        }
+
+        // Target library: same content under different track IDs, and file
+        // "a" moved to a new provider file ID
+        let target_pool = create_test_pool().await.unwrap();
+        insert_test_provider(&target_pool).await;
+        let target_tracks = SqliteTrackRepository::from_pool(target_pool.clone());
+        let local_a = track("a-moved", Some("hash-a"));
+        let local_b = track("b", Some("hash-b"));
+        let local_c = track("c", None);
+        for local in [&local_a, &local_b, &local_c] {
+            target_tracks.insert(local).await.unwrap();
+        }
+
+        let repo = SqlitePlaylistRepository::from_pool(target_pool);
+        let source = SqliteAdapter::from_pool(source_pool);
+        let report = repo
+            .import_from(&source, TrackMatchStrategy::default())
+            .await
+            .unwrap();
+
+        assert_eq!(report.created_playlist_ids.len(), 1);
+        assert_eq!(report.matched_tracks, 3);
+        assert_eq!(report.unmatched.len(), 1);
+        assert_eq!(report.unmatched[0].playlist_name, "Road Trip");
+        assert_eq!(report.unmatched[0].source_track_id, entries[3].id);
+
+        let imported_id = &report.created_playlist_ids[0];
+        assert_ne!(imported_id, &playlist.id);
+        let imported = repo.find_by_id(imported_id).await.unwrap().unwrap();
+        assert_eq!(imported.name, "Road Trip");
+        assert_eq!(imported.description.as_deref(), Some("Summer 2024"));
+        assert_eq!(imported.track_count, 3);
+        assert_eq!(imported.total_duration_ms, 600_000);
+        assert_eq!(
+            repo.get_track_ids(imported_id).await.unwrap(),
+            vec![local_a.id, local_b.id, local_c.id]
+        );
+    }
 }
@@ -1,18 +1,39 @@
 //! Track repository trait and adapter-backed implementation.
 
+use crate::adapters::BusyRetry;
 use crate::error::{LibraryError, Result};
 use crate::models::Track;
 use crate::repositories::{Page, PageRequest, PlatformArc};
-use bridge_traits::database::{DatabaseAdapter, QueryRow, QueryValue};
+use bridge_traits::database::{DatabaseAdapter, QueryRow, QueryValue, TransactionId};
 use bridge_traits::platform::PlatformSendSync;
 #[cfg(any(test, not(target_arch = "wasm32")))]
 use sqlx::SqlitePool;
+use std::collections::BTreeSet;
 
 const TRACK_COLUMNS: &str = "id, provider_id, provider_file_id, hash, \
     title, normalized_title, album_id, artist_id, album_artist_id, \
     track_number, disc_number, genre, year, duration_ms, bitrate, \
     sample_rate, channels, format, file_size, mime_type, artwork_id, \
-    lyrics_status, created_at, updated_at, provider_modified_at";
+    lyrics_status, created_at, updated_at, provider_modified_at, \
+    leading_silence_ms, trailing_silence_ms, replay_gain_millibels, \
+    true_peak_millibels";
+
+/// Ids bound per statement by bulk operations, well under SQLite's
+/// variable limit
+const BULK_CHUNK_SIZE: usize = 500;
+
+/// Rows removed by [`TrackRepository::delete_many`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrackDeletion {
+    /// Number of tracks deleted
+    pub tracks: u64,
+    /// Albums left without tracks
+    pub album_ids: Vec<String>,
+    /// Artists left without tracks or albums
+    pub artist_ids: Vec<String>,
+    /// Artwork no longer used by any track, album or playlist
+    pub artwork_ids: Vec<String>,
+}
 
 /// Track repository interface for data access operations.
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
@@ -22,6 +43,12 @@ pub trait TrackRepository: PlatformSendSync {
     async fn insert(&self, track: &Track) -> Result<()>;
     async fn update(&self, track: &Track) -> Result<()>;
     async fn delete(&self, id: &str) -> Result<bool>;
+    /// Delete tracks in one transaction, then the albums, artists and artwork
+    /// they leave orphaned
+    ///
+    /// Only entities the deleted tracks pointed at are cleaned up; unrelated
+    /// empty albums or unused artwork are left alone.
+    async fn delete_many(&self, ids: &[String]) -> Result<TrackDeletion>;
     async fn query(&self, page_request: PageRequest) -> Result<Page<Track>>;
     async fn query_by_album(
         &self,
@@ -47,17 +74,23 @@ pub trait TrackRepository: PlatformSendSync {
     ) -> Result<Option<Track>>;
     async fn find_by_missing_artwork(&self) -> Result<Vec<Track>>;
     async fn find_by_lyrics_status(&self, status: &str) -> Result<Vec<Track>>;
+    /// Tracks whose artist has no biography or no country yet
+    async fn find_by_missing_artist_metadata(&self) -> Result<Vec<Track>>;
 }
 
 /// Adapter-backed track repository (works for both native and WASM targets).
 pub struct SqliteTrackRepository {
     adapter: PlatformArc<dyn DatabaseAdapter>,
+    busy_retry: BusyRetry,
 }
 
 impl SqliteTrackRepository {
     /// Create a new repository using the provided database adapter.
     pub fn new(adapter: PlatformArc<dyn DatabaseAdapter>) -> Self {
-        Self { adapter }
+        Self {
+            adapter,
+            busy_retry: BusyRetry::default(),
+        }
     }
 
     fn validate_track(track: &Track) -> Result<()> {
@@ -94,6 +127,10 @@ impl SqliteTrackRepository {
             QueryValue::Integer(track.created_at),
             QueryValue::Integer(track.updated_at),
             opt_i64(track.provider_modified_at),
+            opt_i64(track.leading_silence_ms),
+            opt_i64(track.trailing_silence_ms),
+            opt_i64(track.replay_gain_millibels),
+            opt_i64(track.true_peak_millibels),
         ]
     }
 
@@ -122,6 +159,10 @@ impl SqliteTrackRepository {
             QueryValue::Text(track.lyrics_status.clone()),
             QueryValue::Integer(track.updated_at),
             opt_i64(track.provider_modified_at),
+            opt_i64(track.leading_silence_ms),
+            opt_i64(track.trailing_silence_ms),
+            opt_i64(track.replay_gain_millibels),
+            opt_i64(track.true_peak_millibels),
         ];
         params.push(QueryValue::Text(track.id.clone()));
         params
@@ -162,6 +203,132 @@ impl SqliteTrackRepository {
             .and_then(|value| value.as_i64())
             .ok_or_else(|| missing_column("count"))
     }
+
+    async fn delete_many_in(&self, tx: TransactionId, ids: &[String]) -> Result<TrackDeletion> {
+        let mut deletion = TrackDeletion::default();
+        let mut album_ids = BTreeSet::new();
+        let mut artist_ids = BTreeSet::new();
+        let mut artwork_ids = BTreeSet::new();
+
+        for chunk in ids.chunks(BULK_CHUNK_SIZE) {
+            let params = text_params(chunk);
+            let rows = self
+                .adapter
+                .query_in_transaction(
+                    tx,
+                    &format!(
+                        "SELECT album_id, artist_id, album_artist_id, artwork_id \
+                         FROM tracks WHERE id IN ({})",
+                        placeholders(chunk.len())
+                    ),
+                    &params,
+                )
+                .await?;
+            for row in &rows {
+                album_ids.extend(get_optional_string(row, "album_id")?);
+                artist_ids.extend(get_optional_string(row, "artist_id")?);
+                artist_ids.extend(get_optional_string(row, "album_artist_id")?);
+                artwork_ids.extend(get_optional_string(row, "artwork_id")?);
+            }
+
+            deletion.tracks += self
+                .adapter
+                .execute_in_transaction(
+                    tx,
+                    &format!(
+                        "DELETE FROM tracks WHERE id IN ({})",
+                        placeholders(chunk.len())
+                    ),
+                    &params,
+                )
+                .await?;
+        }
+
+        // Albums go first, since their artists and artwork become candidates too
+        let albums = self
+            .find_orphans(
+                tx,
+                "SELECT id, artist_id, artwork_id FROM albums a WHERE a.id IN ({}) \
+                 AND NOT EXISTS (SELECT 1 FROM tracks t WHERE t.album_id = a.id)",
+                &album_ids,
+            )
+            .await?;
+        for row in &albums {
+            deletion.album_ids.push(get_string(row, "id")?);
+            artist_ids.extend(get_optional_string(row, "artist_id")?);
+            artwork_ids.extend(get_optional_string(row, "artwork_id")?);
+        }
+        self.delete_ids(tx, "albums", &deletion.album_ids).await?;
+
+        let artists = self
+            .find_orphans(
+                tx,
+                "SELECT id FROM artists ar WHERE ar.id IN ({}) \
+                 AND NOT EXISTS (SELECT 1 FROM tracks t \
+                     WHERE t.artist_id = ar.id OR t.album_artist_id = ar.id) \
+                 AND NOT EXISTS (SELECT 1 FROM albums al WHERE al.artist_id = ar.id)",
+                &artist_ids,
+            )
+            .await?;
+        for row in &artists {
+            deletion.artist_ids.push(get_string(row, "id")?);
+        }
+        self.delete_ids(tx, "artists", &deletion.artist_ids).await?;
+
+        let artworks = self
+            .find_orphans(
+                tx,
+                "SELECT id FROM artworks aw WHERE aw.id IN ({}) \
+                 AND NOT EXISTS (SELECT 1 FROM tracks t WHERE t.artwork_id = aw.id) \
+                 AND NOT EXISTS (SELECT 1 FROM albums al WHERE al.artwork_id = aw.id) \
+                 AND NOT EXISTS (SELECT 1 FROM playlists p WHERE p.artwork_id = aw.id)",
+                &artwork_ids,
+            )
+            .await?;
+        for row in &artworks {
+            deletion.artwork_ids.push(get_string(row, "id")?);
+        }
+        self.delete_ids(tx, "artworks", &deletion.artwork_ids)
+            .await?;
+
+        Ok(deletion)
+    }
+
+    /// Run `sql_template` (with `{}` standing for the id list) over `candidates`
+    async fn find_orphans(
+        &self,
+        tx: TransactionId,
+        sql_template: &str,
+        candidates: &BTreeSet<String>,
+    ) -> Result<Vec<QueryRow>> {
+        let candidates: Vec<String> = candidates.iter().cloned().collect();
+        let mut rows = Vec::new();
+        for chunk in candidates.chunks(BULK_CHUNK_SIZE) {
+            let sql = sql_template.replace("{}", &placeholders(chunk.len()));
+            rows.extend(
+                self.adapter
+                    .query_in_transaction(tx, &sql, &text_params(chunk))
+                    .await?,
+            );
+        }
+        Ok(rows)
+    }
+
+    async fn delete_ids(&self, tx: TransactionId, table: &str, ids: &[String]) -> Result<()> {
+        for chunk in ids.chunks(BULK_CHUNK_SIZE) {
+            self.adapter
+                .execute_in_transaction(
+                    tx,
+                    &format!(
+                        "DELETE FROM {table} WHERE id IN ({})",
+                        placeholders(chunk.len())
+                    ),
+                    &text_params(chunk),
+                )
+                .await?;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -169,7 +336,7 @@ impl SqliteTrackRepository {
     /// Convenience constructor for native targets using an existing `sqlx` pool.
     pub fn from_pool(pool: SqlitePool) -> Self {
         use crate::adapters::sqlite_native::SqliteAdapter;
-        Self::new(Arc::new(SqliteAdapter::from_pool(pool)))
+        Self::new(PlatformArc::new(SqliteAdapter::from_pool(pool)))
     }
 }
 
@@ -186,8 +353,9 @@ impl TrackRepository for SqliteTrackRepository {
 
     async fn insert(&self, track: &Track) -> Result<()> {
         Self::validate_track(track)?;
-        self.adapter
+        self.busy_retry
             .execute(
+                self.adapter.as_ref(),
                 r#"
                 INSERT INTO tracks (
                     id, provider_id, provider_file_id, hash,
@@ -195,14 +363,18 @@ impl TrackRepository for SqliteTrackRepository {
                     track_number, disc_number, genre, year,
                     duration_ms, bitrate, sample_rate, channels, format,
                     file_size, mime_type, artwork_id, lyrics_status,
-                    created_at, updated_at, provider_modified_at
+                    created_at, updated_at, provider_modified_at,
+                    leading_silence_ms, trailing_silence_ms,
+                    replay_gain_millibels, true_peak_millibels
                 ) VALUES (
                     ?, ?, ?, ?,
                     ?, ?, ?, ?, ?,
                     ?, ?, ?, ?,
                     ?, ?, ?, ?, ?,
                     ?, ?, ?, ?,
-                    ?, ?, ?
+                    ?, ?, ?,
+                    ?, ?,
+                    ?, ?
                 )
                 "#,
                 &Self::insert_params(track),
@@ -214,8 +386,9 @@ impl TrackRepository for SqliteTrackRepository {
     async fn update(&self, track: &Track) -> Result<()> {
         Self::validate_track(track)?;
         let affected = self
-            .adapter
+            .busy_retry
             .execute(
+                self.adapter.as_ref(),
                 r#"
                 UPDATE tracks SET
                     provider_id = ?, provider_file_id = ?, hash = ?,
@@ -223,7 +396,9 @@ impl TrackRepository for SqliteTrackRepository {
                     track_number = ?, disc_number = ?, genre = ?, year = ?,
                     duration_ms = ?, bitrate = ?, sample_rate = ?, channels = ?, format = ?,
                     file_size = ?, mime_type = ?, artwork_id = ?, lyrics_status = ?,
-                    updated_at = ?, provider_modified_at = ?
+                    updated_at = ?, provider_modified_at = ?,
+                    leading_silence_ms = ?, trailing_silence_ms = ?,
+                    replay_gain_millibels = ?, true_peak_millibels = ?
                 WHERE id = ?
                 "#,
                 &Self::update_params(track),
@@ -240,8 +415,9 @@ impl TrackRepository for SqliteTrackRepository {
 
     async fn delete(&self, id: &str) -> Result<bool> {
         let affected = self
-            .adapter
+            .busy_retry
             .execute(
+                self.adapter.as_ref(),
                 "DELETE FROM tracks WHERE id = ?",
                 &[QueryValue::Text(id.to_string())],
             )
@@ -249,6 +425,24 @@ impl TrackRepository for SqliteTrackRepository {
         Ok(affected > 0)
     }
 
+    async fn delete_many(&self, ids: &[String]) -> Result<TrackDeletion> {
+        if ids.is_empty() {
+            return Ok(TrackDeletion::default());
+        }
+
+        let tx = self.adapter.begin_transaction().await?;
+        match self.delete_many_in(tx, ids).await {
+            Ok(deletion) => {
+                self.adapter.commit_transaction(tx).await?;
+                Ok(deletion)
+            }
+            Err(e) => {
+                let _ = self.adapter.rollback_transaction(tx).await;
+                Err(e)
+            }
+        }
+    }
+
     async fn query(&self, page_request: PageRequest) -> Result<Page<Track>> {
         self.paginate(
             "SELECT COUNT(*) as count FROM tracks",
@@ -378,6 +572,18 @@ impl TrackRepository for SqliteTrackRepository {
         )
         .await
     }
+
+    async fn find_by_missing_artist_metadata(&self) -> Result<Vec<Track>> {
+        self.fetch_tracks(
+            &format!(
+                "SELECT {TRACK_COLUMNS} FROM tracks WHERE artist_id IN \
+                 (SELECT id FROM artists WHERE bio IS NULL OR country IS NULL) \
+                 ORDER BY created_at DESC"
+            ),
+            vec![],
+        )
+        .await
+    }
 }
 
 pub(crate) fn row_to_track(row: &QueryRow) -> Result<Track> {
@@ -407,6 +613,10 @@ pub(crate) fn row_to_track(row: &QueryRow) -> Result<Track> {
         created_at: get_i64(row, "created_at")?,
         updated_at: get_i64(row, "updated_at")?,
         provider_modified_at: get_optional_i64(row, "provider_modified_at")?,
+        leading_silence_ms: get_optional_i64(row, "leading_silence_ms")?,
+        trailing_silence_ms: get_optional_i64(row, "trailing_silence_ms")?,
+        replay_gain_millibels: get_optional_i64(row, "replay_gain_millibels")?,
+        true_peak_millibels: get_optional_i64(row, "true_peak_millibels")?,
     })
 }
 
@@ -451,6 +661,14 @@ fn missing_column(column: &str) -> LibraryError {
     }
 }
 
+fn placeholders(count: usize) -> String {
+    vec!["?"; count].join(", ")
+}
+
+fn text_params(ids: &[String]) -> Vec<QueryValue> {
+    ids.iter().cloned().map(QueryValue::Text).collect()
+}
+
 fn opt_text(value: &Option<String>) -> QueryValue {
     value
         .as_ref()
@@ -472,8 +690,11 @@ fn opt_i64(value: Option<i64>) -> QueryValue {
 mod tests {
     use super::*;
     use crate::db::{create_test_pool, insert_test_provider};
-    use crate::models::Track;
-    use uuid::Uuid;
+    use crate::models::{Album, Artist, Artwork, Track};
+    use crate::repositories::{
+        AlbumRepository, ArtistRepository, ArtworkRepository, SqliteAlbumRepository,
+        SqliteArtistRepository, SqliteArtworkRepository,
+    };
 
     async fn create_test_track(id: &str) -> Track {
         Track {
@@ -502,6 +723,10 @@ mod tests {
             created_at: 0,
             updated_at: 0,
             provider_modified_at: None,
+            leading_silence_ms: None,
+            trailing_silence_ms: None,
+            replay_gain_millibels: None,
+            true_peak_millibels: None,
         }
     }
 
@@ -535,6 +760,42 @@ mod tests {
         assert_eq!(found.unwrap().title, "Updated Title");
     }
 
+    #[core_async::test]
+    async fn test_silence_trim_round_trip() {
+        let pool = create_test_pool().await.unwrap();
+        insert_test_provider(&pool).await;
+        let repo = SqliteTrackRepository::from_pool(pool.clone());
+        let mut track = create_test_track("track-4").await;
+
+        repo.insert(&track).await.unwrap();
+        let found = repo.find_by_id("track-4").await.unwrap().unwrap();
+        assert_eq!(found.leading_silence_ms, None);
+
+        track.leading_silence_ms = Some(1_800);
+        track.trailing_silence_ms = Some(0);
+        repo.update(&track).await.unwrap();
+
+        let found = repo.find_by_id("track-4").await.unwrap().unwrap();
+        assert_eq!(found.leading_silence_ms, Some(1_800));
+        assert_eq!(found.trailing_silence_ms, Some(0));
+    }
+
+    #[core_async::test]
+    async fn test_loudness_round_trip() {
+        let pool = create_test_pool().await.unwrap();
+        insert_test_provider(&pool).await;
+        let repo = SqliteTrackRepository::from_pool(pool.clone());
+        let mut track = create_test_track("track-5").await;
+
+        track.replay_gain_millibels = Some(-520);
+        track.true_peak_millibels = Some(-30);
+        repo.insert(&track).await.unwrap();
+
+        let found = repo.find_by_id("track-5").await.unwrap().unwrap();
+        assert_eq!(found.replay_gain_millibels, Some(-520));
+        assert_eq!(found.true_peak_millibels, Some(-30));
+    }
+
     #[core_async::test]
     async fn test_delete_track() {
         let pool = create_test_pool().await.unwrap();
@@ -549,4 +810,67 @@ mod tests {
         let found = repo.find_by_id("track-3").await.unwrap();
         assert!(found.is_none());
     }
+
+    #[core_async::test]
+    async fn test_delete_many_removes_orphaned_album_and_artwork() {
+        let pool = create_test_pool().await.unwrap();
+        insert_test_provider(&pool).await;
+        let repo = SqliteTrackRepository::from_pool(pool.clone());
+        let artist_repo = SqliteArtistRepository::from_pool(pool.clone());
+        let album_repo = SqliteAlbumRepository::from_pool(pool.clone());
+        let artwork_repo = SqliteArtworkRepository::from_pool(pool);
+
+        let cover = Artwork::new(
+            "cover-hash".to_string(),
+            vec![1, 2, 3],
+            100,
+            100,
+            "image/jpeg".to_string(),
+        );
+        artwork_repo.insert(&cover).await.unwrap();
+
+        let artist = Artist::new("Solo Artist".to_string());
+        artist_repo.insert(&artist).await.unwrap();
+        let mut album = Album::new("Only Album".to_string(), Some(artist.id.clone()));
+        album.artwork_id = Some(cover.id.clone());
+        album_repo.insert(&album).await.unwrap();
+
+        // A second album keeps its track and must survive
+        let kept_album = Album::new("Kept Album".to_string(), None);
+        album_repo.insert(&kept_album).await.unwrap();
+        let mut kept = create_test_track("kept").await;
+        kept.album_id = Some(kept_album.id.clone());
+        repo.insert(&kept).await.unwrap();
+
+        let mut ids = Vec::new();
+        for id in ["doomed-1", "doomed-2"] {
+            let mut track = create_test_track(id).await;
+            track.album_id = Some(album.id.clone());
+            track.artist_id = Some(artist.id.clone());
+            track.artwork_id = Some(cover.id.clone());
+            repo.insert(&track).await.unwrap();
+            ids.push(id.to_string());
+        }
+
+        let deletion = repo.delete_many(&ids).await.unwrap();
+
+        assert_eq!(
+            deletion,
+            TrackDeletion {
+                tracks: 2,
+                album_ids: vec![album.id.clone()],
+                artist_ids: vec![artist.id.clone()],
+                artwork_ids: vec![cover.id.clone()],
+            }
+        );
+        assert!(album_repo.find_by_id(&album.id).await.unwrap().is_none());
+        assert!(artist_repo.find_by_id(&artist.id).await.unwrap().is_none());
+        assert!(artwork_repo.find_by_id(&cover.id).await.unwrap().is_none());
+        assert!(album_repo
+            .find_by_id(&kept_album.id)
+            .await
+            .unwrap()
+            .is_some());
+        assert!(repo.find_by_id("kept").await.unwrap().is_some());
+    }
 }
@@ -0,0 +1,183 @@
+//! # Search Text Normalization
+//!
+//! Titles and names are stored alongside a normalized form (`normalized_title`,
+//! `normalized_name`) that sorting, exact lookups and the full-text indexes use.
+//! Normalization makes visually equivalent spellings compare equal:
+//!
+//! - Full-width ASCII (`ＡＢＣ１２３`) folds to half-width (`abc123`) and the
+//!   ideographic space to a regular space
+//! - Half-width katakana (`ｶﾞｲﾄﾞ`) folds to full-width, combining its voicing marks
+//! - Katakana folds to hiragana, so `カケル` and `かける` match
+//! - Decomposed voicing marks (`か` + U+3099) are composed (`が`)
+//! - Case is folded and surrounding whitespace trimmed
+//!
+//! [`contains_cjk`] detects Chinese, Japanese and Korean script. CJK text has
+//! no spaces between words, so word-based FTS tokenizers index a whole phrase
+//! as one token; search matches CJK terms as substrings instead.
+
+/// Normalize text for storage in `normalized_*` columns and for search queries
+pub fn normalize_search_text(text: &str) -> String {
+    fold_search_text(text).trim().to_lowercase()
+}
+
+/// Fold character width and kana variants without changing case or spacing
+///
+/// Used for FTS query strings, where lowercasing would turn the `AND`, `OR`
+/// and `NOT` operators into plain terms.
+pub fn fold_search_text(text: &str) -> String {
+    let mut folded = String::with_capacity(text.len());
+    for c in text.chars() {
+        match voicing_mark(c) {
+            Some(mark) => {
+                let voiced = folded
+                    .chars()
+                    .next_back()
+                    .and_then(|previous| apply_voicing(previous, mark));
+                match voiced {
+                    Some(voiced) => {
+                        folded.pop();
+                        folded.push(voiced);
+                    }
+                    None => folded.push(fold_char(c)),
+                }
+            }
+            None => folded.push(fold_char(c)),
+        }
+    }
+    folded
+}
+
+/// Whether `text` contains any Chinese, Japanese or Korean characters
+pub fn contains_cjk(text: &str) -> bool {
+    text.chars().any(is_cjk)
+}
+
+/// Whether `c` belongs to a CJK script (Han, kana or Hangul)
+pub fn is_cjk(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1100..=0x11FF     // Hangul Jamo
+            | 0x3040..=0x309F // Hiragana
+            | 0x30A0..=0x30FF // Katakana
+            | 0x3130..=0x318F // Hangul Compatibility Jamo
+            | 0x31F0..=0x31FF // Katakana Phonetic Extensions
+            | 0x3400..=0x4DBF // CJK Unified Ideographs Extension A
+            | 0x4E00..=0x9FFF // CJK Unified Ideographs
+            | 0xAC00..=0xD7AF // Hangul Syllables
+            | 0xF900..=0xFAFF // CJK Compatibility Ideographs
+            | 0xFF66..=0xFF9F // Half-width Katakana
+            | 0x20000..=0x3134F // CJK Unified Ideographs Extensions B-G
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VoicingMark {
+    /// Dakuten (゛): か → が
+    Voiced,
+    /// Handakuten (゜): は → ぱ
+    SemiVoiced,
+}
+
+fn voicing_mark(c: char) -> Option<VoicingMark> {
+    match c {
+        '\u{3099}' | '\u{309B}' | '\u{FF9E}' => Some(VoicingMark::Voiced),
+        '\u{309A}' | '\u{309C}' | '\u{FF9F}' => Some(VoicingMark::SemiVoiced),
+        _ => None,
+    }
+}
+
+/// Compose a (folded, hiragana) kana with a voicing mark
+fn apply_voicing(kana: char, mark: VoicingMark) -> Option<char> {
+    let code = kana as u32;
+    let composed = match mark {
+        VoicingMark::Voiced => match code {
+            // か..ぢ: voiced form follows each unvoiced kana
+            0x304B | 0x304D | 0x304F | 0x3051 | 0x3053 | 0x3055 | 0x3057 | 0x3059 | 0x305B
+            | 0x305D | 0x305F | 0x3061 => code + 1,
+            // つ, て, と
+            0x3064 | 0x3066 | 0x3068 => code + 1,
+            // は行: ば follows は, ぱ follows ば
+            0x306F | 0x3072 | 0x3075 | 0x3078 | 0x307B => code + 1,
+            // う → ゔ
+            0x3046 => 0x3094,
+            _ => return None,
+        },
+        VoicingMark::SemiVoiced => match code {
+            0x306F | 0x3072 | 0x3075 | 0x3078 | 0x307B => code + 2,
+            _ => return None,
+        },
+    };
+    char::from_u32(composed)
+}
+
+fn fold_char(c: char) -> char {
+    let code = c as u32;
+    let folded = match code {
+        // Ideographic space
+        0x3000 => 0x20,
+        // Full-width ASCII variants
+        0xFF01..=0xFF5E => code - 0xFEE0,
+        // Half-width katakana and punctuation
+        0xFF61..=0xFF9F => HALF_WIDTH_KANA[(code - 0xFF61) as usize] as u32,
+        _ => code,
+    };
+    // Katakana ァ..ヶ to hiragana ぁ..ゖ
+    let folded = match folded {
+        0x30A1..=0x30F6 => folded - 0x60,
+        other => other,
+    };
+    char::from_u32(folded).unwrap_or(c)
+}
+
+/// Full-width equivalents of U+FF61..=U+FF9F (katakana is folded to hiragana afterwards)
+const HALF_WIDTH_KANA: [char; 63] = [
+    '。', '「', '」', '、', '・', 'ヲ', 'ァ', 'ィ', 'ゥ', 'ェ', 'ォ', 'ャ', 'ュ', 'ョ', 'ッ', 'ー',
+    'ア', 'イ', 'ウ', 'エ', 'オ', 'カ', 'キ', 'ク', 'ケ', 'コ', 'サ', 'シ', 'ス', 'セ', 'ソ', 'タ',
+    'チ', 'ツ', 'テ', 'ト', 'ナ', 'ニ', 'ヌ', 'ネ', 'ノ', 'ハ', 'ヒ', 'フ', 'ヘ', 'ホ', 'マ', 'ミ',
+    'ム', 'メ', 'モ', 'ヤ', 'ユ', 'ヨ', 'ラ', 'リ', 'ル', 'レ', 'ロ', 'ワ', 'ン', '゛', '゜',
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn folds_full_width_ascii_and_case() {
+        assert_eq!(
+            normalize_search_text("  ＹＯＡＳＯＢＩ　１２３ "),
+            "yoasobi 123"
+        );
+        assert_eq!(normalize_search_text("Test Album"), "test album");
+    }
+
+    #[test]
+    fn folds_kana_variants_to_hiragana() {
+        assert_eq!(normalize_search_text("カケル"), "かける");
+        assert_eq!(normalize_search_text("ｶｹﾙ"), "かける");
+        assert_eq!(normalize_search_text("ｶﾞｲﾄﾞ"), "がいど");
+        assert_eq!(normalize_search_text("ﾊﾟﾝ"), "ぱん");
+        assert_eq!(normalize_search_text("か\u{3099}"), "が");
+        assert_eq!(normalize_search_text("ヴ"), "ゔ");
+        // The prolonged sound mark and middle dot are not kana letters
+        assert_eq!(normalize_search_text("ラーメン・ｺﾞﾊﾝ"), "らーめん・ごはん");
+    }
+
+    #[test]
+    fn stray_voicing_mark_is_kept() {
+        assert_eq!(normalize_search_text("a\u{FF9E}"), "a\u{309B}");
+    }
+
+    #[test]
+    fn detects_cjk_scripts() {
+        assert!(contains_cjk("夜に駆ける"));
+        assert!(contains_cjk("아이유"));
+        assert!(contains_cjk("ｶｹﾙ"));
+        assert!(!contains_cjk("Beyoncé"));
+        assert!(!contains_cjk("ＡＢＣ"));
+    }
+
+    #[test]
+    fn fold_keeps_case_for_query_operators() {
+        assert_eq!(fold_search_text("Rock AND ｶｹﾙ"), "Rock AND かける");
+    }
+}
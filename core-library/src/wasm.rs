@@ -26,10 +26,12 @@ use crate::models::*;
 use crate::repositories::*;
 use bridge_traits::database::{DatabaseAdapter, DatabaseConfig};
 use bridge_wasm::database::WasmDbAdapter;
+use bridge_wasm::{run_cancellable, to_js_error, token_from_abort_signal, JsError, JsErrorKind};
 use js_sys::Promise;
 use std::rc::Rc;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::future_to_promise;
+use web_sys::AbortSignal;
 
 // Use `wee_alloc` as the global allocator for smaller binary size
 #[cfg(feature = "wee_alloc_feature")]
@@ -47,17 +49,227 @@ pub fn init_panic_hook() {
 // Error Handling Utilities
 // =============================================================================
 
-/// Convert Rust Result to JS-friendly Result
-fn to_js_error<E: std::fmt::Display>(err: E) -> JsValue {
-    JsValue::from_str(&err.to_string())
+impl From<LibraryError> for JsError {
+    fn from(err: LibraryError) -> Self {
+        let message = err.to_string();
+        let kind = match err {
+            LibraryError::Bridge(bridge) => JsError::from(bridge).kind,
+            LibraryError::NotFound { .. } => JsErrorKind::NotFound,
+            LibraryError::InvalidInput { .. } => JsErrorKind::InvalidInput,
+            LibraryError::DatabaseUnavailable { .. }
+            | LibraryError::Migration(_)
+            | LibraryError::CacheError(_) => JsErrorKind::Storage,
+        };
+        JsError::new(kind, message)
+    }
+}
+
+/// Convert an ID parse failure into an `InvalidInput` error
+fn invalid_id(err: uuid::Error) -> JsValue {
+    to_js_error(JsError::invalid_input(format!("Invalid ID: {}", err)))
 }
 
-/// Convert LibraryError to JsValue
-#[allow(dead_code)]
-fn library_error_to_js(err: LibraryError) -> JsValue {
-    to_js_error(err)
+/// Convert a model validation failure into an `InvalidInput` error
+fn invalid_model(message: String) -> JsValue {
+    to_js_error(JsError::invalid_input(message))
 }
 
+// =============================================================================
+// TypeScript Definitions
+// =============================================================================
+
+// Shapes of the DTOs that cross the boundary as plain objects via
+// `serde_wasm_bindgen`. Keep these in sync with the serde representation of the
+// Rust types (snake_case fields, `None` serialized as `undefined`, 64-bit
+// integers as `number`).
+#[wasm_bindgen(typescript_custom_section)]
+const TS_DTO_DEFINITIONS: &str = r#"
+export interface Page<T> {
+  items: T[];
+  total: number;
+  page: number;
+  total_pages: number;
+  page_size: number;
+}
+
+export interface Track {
+  id: string;
+  provider_id: string;
+  provider_file_id: string;
+  hash?: string;
+  title: string;
+  normalized_title: string;
+  album_id?: string;
+  artist_id?: string;
+  album_artist_id?: string;
+  track_number?: number;
+  disc_number: number;
+  genre?: string;
+  year?: number;
+  duration_ms: number;
+  bitrate?: number;
+  sample_rate?: number;
+  channels?: number;
+  format: string;
+  file_size?: number;
+  mime_type?: string;
+  artwork_id?: string;
+  lyrics_status: string;
+  created_at: number;
+  updated_at: number;
+  provider_modified_at?: number;
+  leading_silence_ms?: number;
+  trailing_silence_ms?: number;
+  replay_gain_millibels?: number;
+  true_peak_millibels?: number;
+}
+
+export interface Album {
+  id: string;
+  name: string;
+  normalized_name: string;
+  artist_id?: string;
+  year?: number;
+  genre?: string;
+  artwork_id?: string;
+  track_count: number;
+  total_duration_ms: number;
+  created_at: number;
+  updated_at: number;
+}
+
+export interface Artist {
+  id: string;
+  name: string;
+  normalized_name: string;
+  sort_name?: string;
+  bio?: string;
+  country?: string;
+  created_at: number;
+  updated_at: number;
+}
+
+export interface Playlist {
+  id: string;
+  name: string;
+  normalized_name: string;
+  description?: string;
+  owner_type: string;
+  sort_order: string;
+  is_public: number;
+  track_count: number;
+  total_duration_ms: number;
+  artwork_id?: string;
+  created_at: number;
+  updated_at: number;
+}
+
+export interface Folder {
+  id: string;
+  provider_id: string;
+  provider_folder_id: string;
+  name: string;
+  normalized_name: string;
+  parent_id?: string;
+  path: string;
+  created_at: number;
+  updated_at: number;
+}
+
+export interface Lyrics {
+  track_id: string;
+  source: string;
+  synced: number;
+  body: string;
+  language?: string;
+  last_checked_at: number;
+  created_at: number;
+  updated_at: number;
+}
+
+export interface TrackListItem {
+  track: Track;
+  album_name?: string;
+  artist_name?: string;
+  album_artist_name?: string;
+  display_artwork_id?: string;
+}
+
+export interface AlbumListItem {
+  album: Album;
+  artist_name?: string;
+  actual_track_count: number;
+  actual_duration_ms: number;
+}
+
+export interface AlbumSearchItem {
+  album: Album;
+  artist_name?: string;
+  score: number;
+}
+
+export interface ArtistSearchItem {
+  artist: Artist;
+  score: number;
+}
+
+export interface PlaylistSearchItem {
+  playlist: Playlist;
+  score: number;
+}
+
+export interface SearchResults {
+  tracks: TrackListItem[];
+  albums: AlbumSearchItem[];
+  artists: ArtistSearchItem[];
+  playlists: PlaylistSearchItem[];
+}
+
+export interface TrackDetails {
+  track: Track;
+  album?: Album;
+  artist?: Artist;
+  album_artist?: Artist;
+  lyrics?: Lyrics;
+  display_artwork_id?: string;
+}
+
+export type CacheStatus = "not_cached" | "downloading" | "cached" | "failed" | "stale";
+
+export interface CachedTrack {
+  track_id: string;
+  cache_path: string;
+  file_size: number;
+  cached_size: number;
+  content_hash: string;
+  encrypted: boolean;
+  status: CacheStatus;
+  play_count: number;
+  cached_at: number;
+  last_accessed_at: number;
+  download_started_at?: number;
+  downloaded_bytes: number;
+  download_attempts: number;
+  last_error?: string;
+  pinned: boolean;
+}
+
+export interface CacheStats {
+  total_tracks: number;
+  cached_tracks: number;
+  downloading_tracks: number;
+  failed_tracks: number;
+  total_bytes: number;
+  total_original_bytes: number;
+  encrypted_tracks: number;
+  total_plays: number;
+  tracks_pending_eviction: number;
+  pinned_tracks: number;
+  pinned_bytes: number;
+  calculated_at: number;
+}
+"#;
+
 // =============================================================================
 // Utility Functions
 // =============================================================================
@@ -155,7 +367,7 @@ impl JsTrackId {
     pub fn from_string(s: &str) -> std::result::Result<JsTrackId, JsValue> {
         TrackId::from_string(s)
             .map(|inner| JsTrackId { inner })
-            .map_err(to_js_error)
+            .map_err(invalid_id)
     }
 
     /// Convert to string
@@ -185,7 +397,7 @@ impl JsAlbumId {
     pub fn from_string(s: &str) -> std::result::Result<JsAlbumId, JsValue> {
         AlbumId::from_string(s)
             .map(|inner| JsAlbumId { inner })
-            .map_err(to_js_error)
+            .map_err(invalid_id)
     }
 
     #[wasm_bindgen(js_name = toString)]
@@ -214,7 +426,7 @@ impl JsArtistId {
     pub fn from_string(s: &str) -> std::result::Result<JsArtistId, JsValue> {
         ArtistId::from_string(s)
             .map(|inner| JsArtistId { inner })
-            .map_err(to_js_error)
+            .map_err(invalid_id)
     }
 
     #[wasm_bindgen(js_name = toString)]
@@ -243,7 +455,7 @@ impl JsPlaylistId {
     pub fn from_string(s: &str) -> std::result::Result<JsPlaylistId, JsValue> {
         PlaylistId::from_string(s)
             .map(|inner| JsPlaylistId { inner })
-            .map_err(to_js_error)
+            .map_err(invalid_id)
     }
 
     #[wasm_bindgen(js_name = toString)]
@@ -310,12 +522,12 @@ impl JsTrack {
     pub fn from_object(obj: JsValue) -> std::result::Result<JsTrack, JsValue> {
         serde_wasm_bindgen::from_value(obj)
             .map(|inner| JsTrack { inner })
-            .map_err(to_js_error)
+            .map_err(|e| to_js_error(JsError::invalid_input(e.to_string())))
     }
 
     /// Validate the track
     pub fn validate(&self) -> std::result::Result<(), JsValue> {
-        self.inner.validate().map_err(to_js_error)
+        self.inner.validate().map_err(invalid_model)
     }
 
     // Getters
@@ -465,12 +677,12 @@ impl JsAlbum {
     pub fn from_object(obj: JsValue) -> std::result::Result<JsAlbum, JsValue> {
         serde_wasm_bindgen::from_value(obj)
             .map(|inner| JsAlbum { inner })
-            .map_err(to_js_error)
+            .map_err(|e| to_js_error(JsError::invalid_input(e.to_string())))
     }
 
     /// Validate the album
     pub fn validate(&self) -> std::result::Result<(), JsValue> {
-        self.inner.validate().map_err(to_js_error)
+        self.inner.validate().map_err(invalid_model)
     }
 
     /// Normalize a string
@@ -576,12 +788,12 @@ impl JsArtist {
     pub fn from_object(obj: JsValue) -> std::result::Result<JsArtist, JsValue> {
         serde_wasm_bindgen::from_value(obj)
             .map(|inner| JsArtist { inner })
-            .map_err(to_js_error)
+            .map_err(|e| to_js_error(JsError::invalid_input(e.to_string())))
     }
 
     /// Validate the artist
     pub fn validate(&self) -> std::result::Result<(), JsValue> {
-        self.inner.validate().map_err(to_js_error)
+        self.inner.validate().map_err(invalid_model)
     }
 
     /// Normalize a string
@@ -680,12 +892,12 @@ impl JsPlaylist {
     pub fn from_object(obj: JsValue) -> std::result::Result<JsPlaylist, JsValue> {
         serde_wasm_bindgen::from_value(obj)
             .map(|inner| JsPlaylist { inner })
-            .map_err(to_js_error)
+            .map_err(|e| to_js_error(JsError::invalid_input(e.to_string())))
     }
 
     /// Validate the playlist
     pub fn validate(&self) -> std::result::Result<(), JsValue> {
-        self.inner.validate().map_err(to_js_error)
+        self.inner.validate().map_err(invalid_model)
     }
 
     // Getters
@@ -787,12 +999,12 @@ impl JsFolder {
     pub fn from_object(obj: JsValue) -> std::result::Result<JsFolder, JsValue> {
         serde_wasm_bindgen::from_value(obj)
             .map(|inner| JsFolder { inner })
-            .map_err(to_js_error)
+            .map_err(|e| to_js_error(JsError::invalid_input(e.to_string())))
     }
 
     /// Validate the folder
     pub fn validate(&self) -> std::result::Result<(), JsValue> {
-        self.inner.validate().map_err(to_js_error)
+        self.inner.validate().map_err(invalid_model)
     }
 
     // Getters
@@ -866,12 +1078,12 @@ impl JsLyrics {
     pub fn from_object(obj: JsValue) -> std::result::Result<JsLyrics, JsValue> {
         serde_wasm_bindgen::from_value(obj)
             .map(|inner| JsLyrics { inner })
-            .map_err(to_js_error)
+            .map_err(|e| to_js_error(JsError::invalid_input(e.to_string())))
     }
 
     /// Validate the lyrics
     pub fn validate(&self) -> std::result::Result<(), JsValue> {
-        self.inner.validate().map_err(to_js_error)
+        self.inner.validate().map_err(invalid_model)
     }
 
     /// Check if lyrics are in LRC format
@@ -933,7 +1145,7 @@ impl JsLibrary {
             // Create adapter instance wrapped in Rc (PlatformArc on WASM)
             let adapter = WasmDbAdapter::new(config)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to create database: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to create database")))?;
 
             let adapter_rc: Rc<dyn DatabaseAdapter> = Rc::new(adapter);
 
@@ -976,7 +1188,7 @@ impl JsLibrary {
             adapter
                 .health_check()
                 .await
-                .map_err(|e| to_js_error(format!("Health check failed: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Health check failed")))?;
             Ok(JsValue::NULL)
         })
     }
@@ -989,7 +1201,7 @@ impl JsLibrary {
             adapter
                 .health_check()
                 .await
-                .map_err(|e| to_js_error(format!("Health check failed: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Health check failed")))?;
             Ok(JsValue::NULL)
         })
     }
@@ -1006,7 +1218,7 @@ impl JsLibrary {
         future_to_promise(async move {
             repo.insert(&track_model)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to insert track: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to insert track")))?;
             Ok(JsValue::NULL)
         })
     }
@@ -1019,7 +1231,7 @@ impl JsLibrary {
             let track = repo
                 .find_by_id(&id)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to get track: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to get track")))?;
 
             Ok(track.map(JsTrack::from_track).map(JsValue::from).unwrap_or(JsValue::NULL))
         })
@@ -1033,7 +1245,7 @@ impl JsLibrary {
         future_to_promise(async move {
             repo.update(&track_model)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to update track: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to update track")))?;
             Ok(JsValue::NULL)
         })
     }
@@ -1046,13 +1258,13 @@ impl JsLibrary {
             let deleted = repo
                 .delete(&id)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to delete track: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to delete track")))?;
             Ok(JsValue::from_bool(deleted))
         })
     }
 
     /// List tracks with pagination
-    #[wasm_bindgen(js_name = listTracks)]
+    #[wasm_bindgen(js_name = listTracks, unchecked_return_type = "Promise<Page<Track>>")]
     pub fn list_tracks(&self, page_request: JsPageRequest) -> Promise {
         let repo = self.track_repo();
         let page_req = page_request.into();
@@ -1060,7 +1272,7 @@ impl JsLibrary {
             let page = repo
                 .query(page_req)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to list tracks: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to list tracks")))?;
 
             // Convert to JSON for JS consumption
             serde_wasm_bindgen::to_value(&page).map_err(to_js_error)
@@ -1068,7 +1280,7 @@ impl JsLibrary {
     }
 
     /// Query tracks by album
-    #[wasm_bindgen(js_name = queryTracksByAlbum)]
+    #[wasm_bindgen(js_name = queryTracksByAlbum, unchecked_return_type = "Promise<Page<Track>>")]
     pub fn query_tracks_by_album(&self, album_id: String, page_request: JsPageRequest) -> Promise {
         let repo = self.track_repo();
         let page_req = page_request.into();
@@ -1076,14 +1288,14 @@ impl JsLibrary {
             let page = repo
                 .query_by_album(&album_id, page_req)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to query tracks by album: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to query tracks by album")))?;
 
             serde_wasm_bindgen::to_value(&page).map_err(to_js_error)
         })
     }
 
     /// Query tracks by artist
-    #[wasm_bindgen(js_name = queryTracksByArtist)]
+    #[wasm_bindgen(js_name = queryTracksByArtist, unchecked_return_type = "Promise<Page<Track>>")]
     pub fn query_tracks_by_artist(&self, artist_id: String, page_request: JsPageRequest) -> Promise {
         let repo = self.track_repo();
         let page_req = page_request.into();
@@ -1091,14 +1303,14 @@ impl JsLibrary {
             let page = repo
                 .query_by_artist(&artist_id, page_req)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to query tracks by artist: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to query tracks by artist")))?;
 
             serde_wasm_bindgen::to_value(&page).map_err(to_js_error)
         })
     }
 
     /// Search tracks by query string
-    #[wasm_bindgen(js_name = searchTracks)]
+    #[wasm_bindgen(js_name = searchTracks, unchecked_return_type = "Promise<Page<Track>>")]
     pub fn search_tracks(&self, query: String, page_request: JsPageRequest) -> Promise {
         let repo = self.track_repo();
         let page_req = page_request.into();
@@ -1106,7 +1318,7 @@ impl JsLibrary {
             let page = repo
                 .search(&query, page_req)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to search tracks: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to search tracks")))?;
 
             serde_wasm_bindgen::to_value(&page).map_err(to_js_error)
         })
@@ -1120,7 +1332,7 @@ impl JsLibrary {
             let count = repo
                 .count()
                 .await
-                .map_err(|e| to_js_error(format!("Failed to count tracks: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to count tracks")))?;
             Ok(JsValue::from_f64(count as f64))
         })
     }
@@ -1137,7 +1349,7 @@ impl JsLibrary {
         future_to_promise(async move {
             repo.insert(&album_model)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to insert album: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to insert album")))?;
             Ok(JsValue::NULL)
         })
     }
@@ -1150,7 +1362,7 @@ impl JsLibrary {
             let album = repo
                 .find_by_id(&id)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to get album: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to get album")))?;
 
             Ok(album.map(JsAlbum::from_album).map(JsValue::from).unwrap_or(JsValue::NULL))
         })
@@ -1164,7 +1376,7 @@ impl JsLibrary {
         future_to_promise(async move {
             repo.update(&album_model)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to update album: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to update album")))?;
             Ok(JsValue::NULL)
         })
     }
@@ -1177,13 +1389,13 @@ impl JsLibrary {
             let deleted = repo
                 .delete(&id)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to delete album: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to delete album")))?;
             Ok(JsValue::from_bool(deleted))
         })
     }
 
     /// List albums with pagination
-    #[wasm_bindgen(js_name = listAlbums)]
+    #[wasm_bindgen(js_name = listAlbums, unchecked_return_type = "Promise<Page<Album>>")]
     pub fn list_albums(&self, page_request: JsPageRequest) -> Promise {
         let repo = self.album_repo();
         let page_req = page_request.into();
@@ -1191,14 +1403,14 @@ impl JsLibrary {
             let page = repo
                 .query(page_req)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to list albums: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to list albums")))?;
 
             serde_wasm_bindgen::to_value(&page).map_err(to_js_error)
         })
     }
 
     /// Search albums
-    #[wasm_bindgen(js_name = searchAlbums)]
+    #[wasm_bindgen(js_name = searchAlbums, unchecked_return_type = "Promise<Page<Album>>")]
     pub fn search_albums(&self, query: String, page_request: JsPageRequest) -> Promise {
         let repo = self.album_repo();
         let page_req = page_request.into();
@@ -1206,7 +1418,7 @@ impl JsLibrary {
             let page = repo
                 .search(&query, page_req)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to search albums: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to search albums")))?;
 
             serde_wasm_bindgen::to_value(&page).map_err(to_js_error)
         })
@@ -1220,7 +1432,7 @@ impl JsLibrary {
             let count = repo
                 .count()
                 .await
-                .map_err(|e| to_js_error(format!("Failed to count albums: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to count albums")))?;
             Ok(JsValue::from_f64(count as f64))
         })
     }
@@ -1237,7 +1449,7 @@ impl JsLibrary {
         future_to_promise(async move {
             repo.insert(&artist_model)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to insert artist: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to insert artist")))?;
             Ok(JsValue::NULL)
         })
     }
@@ -1250,7 +1462,7 @@ impl JsLibrary {
             let artist = repo
                 .find_by_id(&id)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to get artist: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to get artist")))?;
 
             Ok(artist.map(JsArtist::from_artist).map(JsValue::from).unwrap_or(JsValue::NULL))
         })
@@ -1264,7 +1476,7 @@ impl JsLibrary {
         future_to_promise(async move {
             repo.update(&artist_model)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to update artist: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to update artist")))?;
             Ok(JsValue::NULL)
         })
     }
@@ -1277,13 +1489,13 @@ impl JsLibrary {
             let deleted = repo
                 .delete(&id)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to delete artist: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to delete artist")))?;
             Ok(JsValue::from_bool(deleted))
         })
     }
 
     /// List artists with pagination
-    #[wasm_bindgen(js_name = listArtists)]
+    #[wasm_bindgen(js_name = listArtists, unchecked_return_type = "Promise<Page<Artist>>")]
     pub fn list_artists(&self, page_request: JsPageRequest) -> Promise {
         let repo = self.artist_repo();
         let page_req = page_request.into();
@@ -1291,14 +1503,14 @@ impl JsLibrary {
             let page = repo
                 .query(page_req)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to list artists: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to list artists")))?;
 
             serde_wasm_bindgen::to_value(&page).map_err(to_js_error)
         })
     }
 
     /// Search artists
-    #[wasm_bindgen(js_name = searchArtists)]
+    #[wasm_bindgen(js_name = searchArtists, unchecked_return_type = "Promise<Page<Artist>>")]
     pub fn search_artists(&self, query: String, page_request: JsPageRequest) -> Promise {
         let repo = self.artist_repo();
         let page_req = page_request.into();
@@ -1306,7 +1518,7 @@ impl JsLibrary {
             let page = repo
                 .search(&query, page_req)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to search artists: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to search artists")))?;
 
             serde_wasm_bindgen::to_value(&page).map_err(to_js_error)
         })
@@ -1320,7 +1532,7 @@ impl JsLibrary {
             let count = repo
                 .count()
                 .await
-                .map_err(|e| to_js_error(format!("Failed to count artists: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to count artists")))?;
             Ok(JsValue::from_f64(count as f64))
         })
     }
@@ -1337,7 +1549,7 @@ impl JsLibrary {
         future_to_promise(async move {
             repo.insert(&playlist_model)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to insert playlist: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to insert playlist")))?;
             Ok(JsValue::NULL)
         })
     }
@@ -1350,7 +1562,7 @@ impl JsLibrary {
             let playlist = repo
                 .find_by_id(&id)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to get playlist: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to get playlist")))?;
 
             Ok(playlist.map(JsPlaylist::from_playlist).map(JsValue::from).unwrap_or(JsValue::NULL))
         })
@@ -1364,7 +1576,7 @@ impl JsLibrary {
         future_to_promise(async move {
             repo.update(&playlist_model)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to update playlist: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to update playlist")))?;
             Ok(JsValue::NULL)
         })
     }
@@ -1377,7 +1589,7 @@ impl JsLibrary {
             let deleted = repo
                 .delete(&id)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to delete playlist: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to delete playlist")))?;
             Ok(JsValue::from_bool(deleted))
         })
     }
@@ -1389,7 +1601,7 @@ impl JsLibrary {
         future_to_promise(async move {
             repo.add_track(&playlist_id, &track_id, position)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to add track to playlist: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to add track to playlist")))?;
             Ok(JsValue::NULL)
         })
     }
@@ -1402,13 +1614,13 @@ impl JsLibrary {
             let removed = repo
                 .remove_track(&playlist_id, &track_id)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to remove track from playlist: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to remove track from playlist")))?;
             Ok(JsValue::from_bool(removed))
         })
     }
 
     /// List playlists with pagination
-    #[wasm_bindgen(js_name = listPlaylists)]
+    #[wasm_bindgen(js_name = listPlaylists, unchecked_return_type = "Promise<Page<Playlist>>")]
     pub fn list_playlists(&self, page_request: JsPageRequest) -> Promise {
         let repo = self.playlist_repo();
         let page_req = page_request.into();
@@ -1416,7 +1628,7 @@ impl JsLibrary {
             let page = repo
                 .query(page_req)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to list playlists: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to list playlists")))?;
 
             serde_wasm_bindgen::to_value(&page).map_err(to_js_error)
         })
@@ -1434,7 +1646,7 @@ impl JsLibrary {
         future_to_promise(async move {
             repo.insert(&folder_model)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to insert folder: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to insert folder")))?;
             Ok(JsValue::NULL)
         })
     }
@@ -1447,14 +1659,14 @@ impl JsLibrary {
             let folder = repo
                 .find_by_id(&id)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to get folder: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to get folder")))?;
 
             Ok(folder.map(JsFolder::from_folder).map(JsValue::from).unwrap_or(JsValue::NULL))
         })
     }
 
     /// List folders with pagination
-    #[wasm_bindgen(js_name = listFolders)]
+    #[wasm_bindgen(js_name = listFolders, unchecked_return_type = "Promise<Page<Folder>>")]
     pub fn list_folders(&self, page_request: JsPageRequest) -> Promise {
         let repo = self.folder_repo();
         let page_req = page_request.into();
@@ -1462,7 +1674,7 @@ impl JsLibrary {
             let page = repo
                 .query(page_req)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to list folders: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to list folders")))?;
 
             serde_wasm_bindgen::to_value(&page).map_err(to_js_error)
         })
@@ -1480,7 +1692,7 @@ impl JsLibrary {
         future_to_promise(async move {
             repo.insert(&lyrics_model)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to insert lyrics: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to insert lyrics")))?;
             Ok(JsValue::NULL)
         })
     }
@@ -1493,7 +1705,7 @@ impl JsLibrary {
             let lyrics = repo
                 .find_by_track_id(&track_id)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to get lyrics: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to get lyrics")))?;
 
             Ok(lyrics.map(JsLyrics::from_lyrics).map(JsValue::from).unwrap_or(JsValue::NULL))
         })
@@ -1507,7 +1719,7 @@ impl JsLibrary {
         future_to_promise(async move {
             repo.update(&lyrics_model)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to update lyrics: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to update lyrics")))?;
             Ok(JsValue::NULL)
         })
     }
@@ -1520,7 +1732,7 @@ impl JsLibrary {
             let deleted = repo
                 .delete(&track_id)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to delete lyrics: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to delete lyrics")))?;
             Ok(JsValue::from_bool(deleted))
         })
     }
@@ -1587,6 +1799,20 @@ impl JsTrackSort {
             inner: crate::query::TrackSort::DurationAsc,
         }
     }
+
+    #[wasm_bindgen(js_name = albumThenTrack)]
+    pub fn album_then_track() -> Self {
+        Self {
+            inner: crate::query::TrackSort::AlbumThenTrack,
+        }
+    }
+
+    #[wasm_bindgen(js_name = artistThenAlbum)]
+    pub fn artist_then_album() -> Self {
+        Self {
+            inner: crate::query::TrackSort::ArtistThenAlbum,
+        }
+    }
 }
 
 impl From<JsTrackSort> for crate::query::TrackSort {
@@ -1732,7 +1958,7 @@ impl JsTrackFilter {
     /// Set sort order
     #[wasm_bindgen(js_name = setSort)]
     pub fn set_sort(&mut self, sort: JsTrackSort) {
-        self.inner.sort = sort.into();
+        self.inner.sort = Some(sort.into());
     }
 }
 
@@ -1872,7 +2098,7 @@ impl JsCachedTrack {
     /// Create a new cache entry
     #[wasm_bindgen(constructor)]
     pub fn new(track_id: String, cache_path: String, file_size: f64) -> std::result::Result<JsCachedTrack, JsValue> {
-        let track_id = TrackId::from_string(&track_id).map_err(to_js_error)?;
+        let track_id = TrackId::from_string(&track_id).map_err(invalid_id)?;
         Ok(Self {
             inner: CachedTrack::new(track_id, cache_path, file_size as u64),
         })
@@ -1903,7 +2129,7 @@ impl JsCachedTrack {
     pub fn from_object(obj: JsValue) -> std::result::Result<JsCachedTrack, JsValue> {
         serde_wasm_bindgen::from_value(obj)
             .map(|inner| JsCachedTrack { inner })
-            .map_err(to_js_error)
+            .map_err(|e| to_js_error(JsError::invalid_input(e.to_string())))
     }
 
     // Getters
@@ -1927,6 +2153,11 @@ impl JsCachedTrack {
         self.inner.cached_size as f64
     }
 
+    #[wasm_bindgen(js_name = isPinned)]
+    pub fn is_pinned(&self) -> bool {
+        self.inner.pinned
+    }
+
     #[wasm_bindgen(js_name = downloadProgress)]
     pub fn download_progress(&self) -> u8 {
         self.inner.download_progress()
@@ -1976,7 +2207,7 @@ impl JsArtwork {
 
     /// Validate the artwork
     pub fn validate(&self) -> std::result::Result<(), JsValue> {
-        self.inner.validate().map_err(to_js_error)
+        self.inner.validate().map_err(invalid_model)
     }
 
     // Getters
@@ -2044,70 +2275,161 @@ impl JsQueryService {
     }
 
     /// Query tracks with filtering, sorting, and pagination
-    #[wasm_bindgen(js_name = queryTracks)]
-    pub fn query_tracks(&self, filter: JsTrackFilter, page_request: JsPageRequest) -> Promise {
+    ///
+    /// Aborting `signal` rejects the promise with a `Cancelled` error.
+    #[wasm_bindgen(js_name = queryTracks, unchecked_return_type = "Promise<Page<TrackListItem>>")]
+    pub fn query_tracks(
+        &self,
+        filter: JsTrackFilter,
+        page_request: JsPageRequest,
+        signal: Option<AbortSignal>,
+    ) -> Promise {
         use crate::query::LibraryQueryService;
         let service = LibraryQueryService::new(self.adapter.clone());
         let filter = filter.into();
         let page_req = page_request.into();
+        let token = signal.as_ref().map(token_from_abort_signal);
 
-        future_to_promise(async move {
+        future_to_promise(run_cancellable(token, async move {
             let page = service
                 .query_tracks(filter, page_req)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to query tracks: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to query tracks")))?;
 
             serde_wasm_bindgen::to_value(&page).map_err(to_js_error)
-        })
+        }))
     }
 
     /// Query albums with filtering, sorting, and pagination
-    #[wasm_bindgen(js_name = queryAlbums)]
-    pub fn query_albums(&self, filter: JsAlbumFilter, page_request: JsPageRequest) -> Promise {
+    ///
+    /// Aborting `signal` rejects the promise with a `Cancelled` error.
+    #[wasm_bindgen(js_name = queryAlbums, unchecked_return_type = "Promise<Page<AlbumListItem>>")]
+    pub fn query_albums(
+        &self,
+        filter: JsAlbumFilter,
+        page_request: JsPageRequest,
+        signal: Option<AbortSignal>,
+    ) -> Promise {
         use crate::query::LibraryQueryService;
         let service = LibraryQueryService::new(self.adapter.clone());
         let filter = filter.into();
         let page_req = page_request.into();
+        let token = signal.as_ref().map(token_from_abort_signal);
 
-        future_to_promise(async move {
+        future_to_promise(run_cancellable(token, async move {
             let page = service
                 .query_albums(filter, page_req)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to query albums: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to query albums")))?;
 
             serde_wasm_bindgen::to_value(&page).map_err(to_js_error)
-        })
+        }))
+    }
+
+    /// List albums the artist appears on without being the album artist
+    ///
+    /// Aborting `signal` rejects the promise with a `Cancelled` error.
+    #[wasm_bindgen(js_name = artistAppearsOn, unchecked_return_type = "Promise<AlbumListItem[]>")]
+    pub fn artist_appears_on(&self, artist_id: String, signal: Option<AbortSignal>) -> Promise {
+        use crate::query::LibraryQueryService;
+        let service = LibraryQueryService::new(self.adapter.clone());
+        let token = signal.as_ref().map(token_from_abort_signal);
+
+        future_to_promise(run_cancellable(token, async move {
+            let albums = service
+                .artist_appears_on(&artist_id)
+                .await
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to query albums")))?;
+
+            serde_wasm_bindgen::to_value(&albums).map_err(to_js_error)
+        }))
     }
 
     /// Perform full-text search across all entities
-    pub fn search(&self, query: String) -> Promise {
+    ///
+    /// Aborting `signal` rejects the promise with a `Cancelled` error.
+    #[wasm_bindgen(unchecked_return_type = "Promise<SearchResults>")]
+    pub fn search(&self, query: String, signal: Option<AbortSignal>) -> Promise {
         use crate::query::LibraryQueryService;
         let service = LibraryQueryService::new(self.adapter.clone());
+        let token = signal.as_ref().map(token_from_abort_signal);
 
-        future_to_promise(async move {
+        future_to_promise(run_cancellable(token, async move {
             let results = service
                 .search(&query)
                 .await
-                .map_err(|e| to_js_error(format!("Search failed: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Search failed")))?;
 
             serde_wasm_bindgen::to_value(&results).map_err(to_js_error)
-        })
+        }))
+    }
+
+    /// Stream full-text track search results in batches
+    ///
+    /// `on_batch` is called with each `TrackListItem[]` page as soon as it is
+    /// loaded, so the UI can render results incrementally instead of waiting for
+    /// the whole result set. Resolves to the total number of tracks delivered.
+    /// Aborting `signal` stops fetching further batches and rejects the promise
+    /// with a `Cancelled` error.
+    ///
+    /// ```javascript
+    /// const controller = new AbortController();
+    /// const total = await query.searchStream('love', (batch) => {
+    ///   results.push(...batch);
+    ///   render(results);
+    /// }, controller.signal);
+    /// ```
+    #[wasm_bindgen(js_name = searchStream, unchecked_return_type = "Promise<number>")]
+    pub fn search_stream(
+        &self,
+        query: String,
+        #[wasm_bindgen(unchecked_param_type = "(batch: TrackListItem[]) => void")]
+        on_batch: js_sys::Function,
+        signal: Option<AbortSignal>,
+    ) -> Promise {
+        use crate::query::LibraryQueryService;
+        use futures::StreamExt;
+
+        const SEARCH_BATCH_SIZE: u32 = 100;
+
+        let service = LibraryQueryService::new(self.adapter.clone());
+        let token = signal.as_ref().map(token_from_abort_signal);
+
+        future_to_promise(run_cancellable(token, async move {
+            let mut batches = service
+                .stream_search_tracks(&query, SEARCH_BATCH_SIZE)
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Search failed")))?;
+
+            let mut delivered = 0usize;
+            while let Some(batch) = batches.next().await {
+                let batch =
+                    batch.map_err(|e| to_js_error(JsError::from(e).with_context("Search failed")))?;
+                delivered += batch.len();
+                let value = serde_wasm_bindgen::to_value(&batch).map_err(to_js_error)?;
+                on_batch.call1(&JsValue::NULL, &value)?;
+            }
+
+            Ok(JsValue::from_f64(delivered as f64))
+        }))
     }
 
     /// Get detailed track information with all relations loaded
-    #[wasm_bindgen(js_name = getTrackDetails)]
-    pub fn get_track_details(&self, track_id: String) -> Promise {
+    ///
+    /// Aborting `signal` rejects the promise with a `Cancelled` error.
+    #[wasm_bindgen(js_name = getTrackDetails, unchecked_return_type = "Promise<TrackDetails>")]
+    pub fn get_track_details(&self, track_id: String, signal: Option<AbortSignal>) -> Promise {
         use crate::query::LibraryQueryService;
         let service = LibraryQueryService::new(self.adapter.clone());
+        let token = signal.as_ref().map(token_from_abort_signal);
 
-        future_to_promise(async move {
+        future_to_promise(run_cancellable(token, async move {
             let details = service
                 .get_track_details(&track_id)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to get track details: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to get track details")))?;
 
             serde_wasm_bindgen::to_value(&details).map_err(to_js_error)
-        })
+        }))
     }
 }
 
@@ -2138,7 +2460,7 @@ impl JsLibrary {
         future_to_promise(async move {
             repo.insert(&track_model)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to insert cached track: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to insert cached track")))?;
             Ok(JsValue::NULL)
         })
     }
@@ -2148,11 +2470,11 @@ impl JsLibrary {
     pub fn get_cached_track(&self, track_id: String) -> Promise {
         let repo = self.cache_repo();
         future_to_promise(async move {
-            let track_id = TrackId::from_string(&track_id).map_err(to_js_error)?;
+            let track_id = TrackId::from_string(&track_id).map_err(invalid_id)?;
             let track = repo
                 .find_by_track_id(&track_id)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to get cached track: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to get cached track")))?;
 
             Ok(track.map(JsCachedTrack::from_cached_track).map(JsValue::from).unwrap_or(JsValue::NULL))
         })
@@ -2166,7 +2488,7 @@ impl JsLibrary {
         future_to_promise(async move {
             repo.update(&track_model)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to update cached track: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to update cached track")))?;
             Ok(JsValue::NULL)
         })
     }
@@ -2176,37 +2498,37 @@ impl JsLibrary {
     pub fn delete_cached_track(&self, track_id: String) -> Promise {
         let repo = self.cache_repo();
         future_to_promise(async move {
-            let track_id = TrackId::from_string(&track_id).map_err(to_js_error)?;
+            let track_id = TrackId::from_string(&track_id).map_err(invalid_id)?;
             repo.delete(&track_id)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to delete cached track: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to delete cached track")))?;
             Ok(JsValue::NULL)
         })
     }
 
     /// Get cache statistics
-    #[wasm_bindgen(js_name = getCacheStats)]
+    #[wasm_bindgen(js_name = getCacheStats, unchecked_return_type = "Promise<CacheStats>")]
     pub fn get_cache_stats(&self) -> Promise {
         let repo = self.cache_repo();
         future_to_promise(async move {
             let stats = repo
                 .get_stats()
                 .await
-                .map_err(|e| to_js_error(format!("Failed to get cache stats: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to get cache stats")))?;
 
             serde_wasm_bindgen::to_value(&stats).map_err(to_js_error)
         })
     }
 
     /// Get all cached tracks
-    #[wasm_bindgen(js_name = getAllCachedTracks)]
+    #[wasm_bindgen(js_name = getAllCachedTracks, unchecked_return_type = "Promise<CachedTrack[]>")]
     pub fn get_all_cached_tracks(&self) -> Promise {
         let repo = self.cache_repo();
         future_to_promise(async move {
             let tracks = repo
                 .find_all()
                 .await
-                .map_err(|e| to_js_error(format!("Failed to get all cached tracks: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to get all cached tracks")))?;
 
             serde_wasm_bindgen::to_value(&tracks).map_err(to_js_error)
         })
@@ -2224,7 +2546,7 @@ impl JsLibrary {
         future_to_promise(async move {
             repo.insert(&artwork_model)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to insert artwork: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to insert artwork")))?;
             Ok(JsValue::NULL)
         })
     }
@@ -2237,7 +2559,7 @@ impl JsLibrary {
             let artwork = repo
                 .find_by_id(&id)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to get artwork: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to get artwork")))?;
 
             Ok(artwork.map(JsArtwork::from_artwork).map(JsValue::from).unwrap_or(JsValue::NULL))
         })
@@ -2251,7 +2573,7 @@ impl JsLibrary {
             let artwork = repo
                 .find_by_hash(&hash)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to get artwork by hash: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to get artwork by hash")))?;
 
             Ok(artwork.map(JsArtwork::from_artwork).map(JsValue::from).unwrap_or(JsValue::NULL))
         })
@@ -2265,7 +2587,7 @@ impl JsLibrary {
         future_to_promise(async move {
             repo.update(&artwork_model)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to update artwork: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to update artwork")))?;
             Ok(JsValue::NULL)
         })
     }
@@ -2278,7 +2600,7 @@ impl JsLibrary {
             let deleted = repo
                 .delete(&id)
                 .await
-                .map_err(|e| to_js_error(format!("Failed to delete artwork: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to delete artwork")))?;
             Ok(JsValue::from_bool(deleted))
         })
     }
@@ -2291,7 +2613,7 @@ impl JsLibrary {
             let count = repo
                 .count()
                 .await
-                .map_err(|e| to_js_error(format!("Failed to count artworks: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to count artworks")))?;
             Ok(JsValue::from_f64(count as f64))
         })
     }
@@ -2304,7 +2626,7 @@ impl JsLibrary {
             let size = repo
                 .total_size()
                 .await
-                .map_err(|e| to_js_error(format!("Failed to get artworks total size: {}", e)))?;
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to get artworks total size")))?;
             Ok(JsValue::from_f64(size as f64))
         })
     }
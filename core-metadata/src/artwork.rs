@@ -43,14 +43,12 @@
 use crate::error::{MetadataError, Result};
 use crate::extractor::ExtractedArtwork;
 use bytes::Bytes;
-use core_async::sync::RwLock;
 use core_library::models::Artwork;
 use core_library::repositories::ArtworkRepository;
+use core_runtime::cache::{CacheMetrics, LruCache};
 use image::{DynamicImage, ImageFormat};
-use lru::LruCache;
 use sha2::{Digest, Sha256};
 use std::io::Cursor;
-use std::num::NonZeroUsize;
 use std::sync::Arc;
 use tracing::{debug, info};
 
@@ -59,6 +57,10 @@ use bridge_traits::http::HttpClient;
 
 #[cfg(feature = "artwork-remote")]
 use crate::providers::{lastfm::LastFmClient, musicbrainz::MusicBrainzClient};
+#[cfg(feature = "artwork-remote")]
+use core_runtime::config::MetadataApiConfig;
+#[cfg(feature = "artwork-remote")]
+use tracing::warn;
 
 /// Standard artwork sizes for optimization
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -105,12 +107,8 @@ pub struct ProcessedArtwork {
 pub struct ArtworkService {
     /// Artwork repository for database operations
     repository: Arc<dyn ArtworkRepository>,
-    /// LRU cache for artwork data (in-memory)
-    cache: Arc<RwLock<LruCache<String, Bytes>>>,
-    /// Maximum cache size in bytes
-    max_cache_size: usize,
-    /// Current cache size in bytes
-    cache_size: Arc<RwLock<usize>>,
+    /// LRU cache for artwork data (in-memory), bounded by total bytes
+    cache: Arc<LruCache<String, Bytes>>,
     /// HTTP client for remote artwork fetching (optional)
     #[cfg(feature = "artwork-remote")]
     http_client: Option<Arc<dyn HttpClient>>,
@@ -134,12 +132,9 @@ impl ArtworkService {
     ///
     /// New ArtworkService instance
     pub fn new(repository: Arc<dyn ArtworkRepository>, max_cache_size: usize) -> Self {
-        let cache_capacity = NonZeroUsize::new(100).unwrap(); // 100 items in LRU
         Self {
             repository,
-            cache: Arc::new(RwLock::new(LruCache::new(cache_capacity))),
-            max_cache_size,
-            cache_size: Arc::new(RwLock::new(0)),
+            cache: new_artwork_cache(max_cache_size),
             #[cfg(feature = "artwork-remote")]
             http_client: None,
             #[cfg(feature = "artwork-remote")]
@@ -172,8 +167,6 @@ impl ArtworkService {
         lastfm_api_key: Option<String>,
         rate_limit_delay_ms: u64,
     ) -> Self {
-        let cache_capacity = NonZeroUsize::new(100).unwrap();
-
         // Create MusicBrainz client if user agent provided
         let musicbrainz_client = musicbrainz_user_agent
             .map(|ua| MusicBrainzClient::new(http_client.clone(), ua, rate_limit_delay_ms));
@@ -184,15 +177,47 @@ impl ArtworkService {
 
         Self {
             repository,
-            cache: Arc::new(RwLock::new(LruCache::new(cache_capacity))),
-            max_cache_size,
-            cache_size: Arc::new(RwLock::new(0)),
+            cache: new_artwork_cache(max_cache_size),
             http_client: Some(http_client),
             musicbrainz_client,
             lastfm_client,
         }
     }
 
+    /// Create a new ArtworkService configured from the runtime metadata API settings
+    ///
+    /// Equivalent to `with_remote_fetching` followed by
+    /// `with_max_concurrent_requests`, reading every knob from `config`.
+    #[cfg(feature = "artwork-remote")]
+    pub fn from_api_config(
+        repository: Arc<dyn ArtworkRepository>,
+        http_client: Arc<dyn HttpClient>,
+        max_cache_size: usize,
+        config: &MetadataApiConfig,
+    ) -> Self {
+        Self::with_remote_fetching(
+            repository,
+            http_client,
+            max_cache_size,
+            config.musicbrainz_user_agent.clone(),
+            config.lastfm_api_key.clone(),
+            config.rate_limit_delay_ms,
+        )
+        .with_max_concurrent_requests(config.max_concurrent_requests)
+    }
+
+    /// Limit how many requests each remote artwork client may have in flight
+    #[cfg(feature = "artwork-remote")]
+    pub fn with_max_concurrent_requests(mut self, max_concurrent: usize) -> Self {
+        self.musicbrainz_client = self
+            .musicbrainz_client
+            .map(|client| client.with_max_concurrent_requests(max_concurrent));
+        self.lastfm_client = self
+            .lastfm_client
+            .map(|client| client.with_max_concurrent_requests(max_concurrent));
+        self
+    }
+
     /// Create a new ArtworkService with HTTP client for remote fetching (deprecated)
     ///
     /// Use `with_remote_fetching` instead for better control over API configuration.
@@ -203,12 +228,9 @@ impl ArtworkService {
         http_client: Arc<dyn HttpClient>,
         max_cache_size: usize,
     ) -> Self {
-        let cache_capacity = NonZeroUsize::new(100).unwrap();
         Self {
             repository,
-            cache: Arc::new(RwLock::new(LruCache::new(cache_capacity))),
-            max_cache_size,
-            cache_size: Arc::new(RwLock::new(0)),
+            cache: new_artwork_cache(max_cache_size),
             http_client: Some(http_client),
             musicbrainz_client: None,
             lastfm_client: None,
@@ -300,25 +322,9 @@ impl ArtworkService {
         mime_type: &str,
         _source: &str,
     ) -> Result<ProcessedArtwork> {
-        // Load image for processing
-        let img = image::load_from_memory(data).map_err(|e| MetadataError::ImageProcessing {
-            message: format!("Failed to load image: {}", e),
-        })?;
-
-        let original_width = img.width();
-        let original_height = img.height();
-
-        // Extract dominant color
-        let dominant_color = self.extract_dominant_color(&img);
-
-        // Create artwork model
-        let artwork = Artwork::new(
-            hash.to_string(),
-            data.to_vec(),
-            original_width as i64,
-            original_height as i64,
-            mime_type.to_string(),
-        );
+        let artwork = self.decode_artwork(data, hash, mime_type)?;
+        let original_width = artwork.width as u32;
+        let original_height = artwork.height as u32;
 
         // Store in database
         self.repository
@@ -339,11 +345,42 @@ impl ArtworkService {
             hash: hash.to_string(),
             original_width,
             original_height,
-            dominant_color: Some(dominant_color),
+            dominant_color: artwork.dominant_color,
             deduplicated: false,
         })
     }
 
+    /// Decode embedded artwork into a model ready to be stored
+    ///
+    /// Unlike [`Self::extract_embedded`] nothing is written to the database,
+    /// so callers can deduplicate and insert the artwork inside their own
+    /// transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image data cannot be decoded
+    pub fn prepare_embedded(&self, artwork: &ExtractedArtwork) -> Result<Artwork> {
+        let hash = self.calculate_hash(&artwork.data);
+        self.decode_artwork(&artwork.data, &hash, &artwork.mime_type)
+    }
+
+    /// Load image data and build the artwork model with its dominant color
+    fn decode_artwork(&self, data: &Bytes, hash: &str, mime_type: &str) -> Result<Artwork> {
+        let img = image::load_from_memory(data).map_err(|e| MetadataError::ImageProcessing {
+            message: format!("Failed to load image: {}", e),
+        })?;
+
+        let mut artwork = Artwork::new(
+            hash.to_string(),
+            data.to_vec(),
+            img.width() as i64,
+            img.height() as i64,
+            mime_type.to_string(),
+        );
+        artwork.dominant_color = Some(self.extract_dominant_color(&img));
+        Ok(artwork)
+    }
+
     /// Get artwork by ID
     ///
     /// Retrieves artwork from cache if available, otherwise from database.
@@ -365,12 +402,9 @@ impl ArtworkService {
     /// ```
     pub async fn get(&self, artwork_id: &str) -> Result<Bytes> {
         // Check cache first
-        {
-            let mut cache = self.cache.write().await;
-            if let Some(data) = cache.get(artwork_id) {
-                debug!("Artwork {} found in cache", artwork_id);
-                return Ok(data.clone());
-            }
+        if let Some(data) = self.cache.get(artwork_id) {
+            debug!("Artwork {} found in cache", artwork_id);
+            return Ok(data);
         }
 
         // Cache miss - fetch from database
@@ -395,28 +429,13 @@ impl ArtworkService {
     /// Add artwork to LRU cache with size limits
     async fn add_to_cache(&self, artwork_id: String, data: Bytes) {
         let data_size = data.len();
-
-        // Check if adding would exceed cache size
-        let mut cache_size = self.cache_size.write().await;
-        if *cache_size + data_size > self.max_cache_size {
-            // Evict oldest items until there's space
-            let mut cache = self.cache.write().await;
-            while *cache_size + data_size > self.max_cache_size && !cache.is_empty() {
-                if let Some((_, evicted_data)) = cache.pop_lru() {
-                    *cache_size -= evicted_data.len();
-                    debug!("Evicted artwork from cache (size: {})", evicted_data.len());
-                }
-            }
-        }
-
-        // Add to cache
-        let mut cache = self.cache.write().await;
-        cache.put(artwork_id.clone(), data);
-        *cache_size += data_size;
+        self.cache.insert(artwork_id.clone(), data);
 
         debug!(
             "Added artwork {} to cache (size: {} bytes, total cache: {} bytes)",
-            artwork_id, data_size, *cache_size
+            artwork_id,
+            data_size,
+            self.cache.weight()
         );
     }
 
@@ -528,24 +547,21 @@ impl ArtworkService {
         album: &str,
         mbid: Option<&str>,
     ) -> Result<Option<ProcessedArtwork>> {
-        let http_client = self.http_client.as_ref().ok_or_else(|| {
-            MetadataError::ConfigurationError(
+        if self.http_client.is_none() {
+            return Err(MetadataError::ConfigurationError(
                 "HTTP client required for remote artwork fetching".to_string(),
-            )
-        })?;
+            ));
+        }
 
         info!("Fetching remote artwork for '{} - {}'", artist, album);
 
         // Try MusicBrainz first (higher quality)
-        if let Some(artwork) = self
-            .fetch_from_musicbrainz(artist, album, mbid, http_client)
-            .await?
-        {
+        if let Some(artwork) = self.fetch_from_musicbrainz(artist, album, mbid).await? {
             return Ok(Some(artwork));
         }
 
         // Fallback to Last.fm
-        if let Some(artwork) = self.fetch_from_lastfm(artist, album, http_client).await? {
+        if let Some(artwork) = self.fetch_from_lastfm(artist, album).await? {
             return Ok(Some(artwork));
         }
 
@@ -554,13 +570,15 @@ impl ArtworkService {
     }
 
     /// Fetch artwork from MusicBrainz Cover Art Archive
+    ///
+    /// Returns `None` without a network call when no MusicBrainz client is
+    /// configured.
     #[cfg(feature = "artwork-remote")]
-    async fn fetch_from_musicbrainz(
+    pub(crate) async fn fetch_from_musicbrainz(
         &self,
         artist: &str,
         album: &str,
         mbid: Option<&str>,
-        _http_client: &Arc<dyn HttpClient>,
     ) -> Result<Option<ProcessedArtwork>> {
         // Check if MusicBrainz client is configured
         let client = match &self.musicbrainz_client {
@@ -590,12 +608,14 @@ impl ArtworkService {
     }
 
     /// Fetch artwork from Last.fm API
+    ///
+    /// Returns `None` without a network call when no Last.fm client is
+    /// configured.
     #[cfg(feature = "artwork-remote")]
-    async fn fetch_from_lastfm(
+    pub(crate) async fn fetch_from_lastfm(
         &self,
         artist: &str,
         album: &str,
-        _http_client: &Arc<dyn HttpClient>,
     ) -> Result<Option<ProcessedArtwork>> {
         // Check if Last.fm client is configured
         let client = match &self.lastfm_client {
@@ -672,21 +692,34 @@ impl ArtworkService {
     ///
     /// Tuple of (items_count, total_bytes)
     pub async fn cache_stats(&self) -> (usize, usize) {
-        let cache = self.cache.read().await;
-        let size = *self.cache_size.read().await;
-        (cache.len(), size)
+        let metrics = self.cache.metrics();
+        (metrics.entries, metrics.weight as usize)
+    }
+
+    /// Hit/miss counters of the in-memory artwork cache
+    pub fn cache_metrics(&self) -> CacheMetrics {
+        self.cache.metrics()
     }
 
     /// Clear artwork cache
     pub async fn clear_cache(&self) {
-        let mut cache = self.cache.write().await;
-        let mut cache_size = self.cache_size.write().await;
-        cache.clear();
-        *cache_size = 0;
+        self.cache.clear();
         info!("Cleared artwork cache");
     }
 }
 
+/// Maximum number of artworks kept in memory, whatever their size
+const ARTWORK_CACHE_MAX_ENTRIES: usize = 100;
+
+fn new_artwork_cache(max_cache_size: usize) -> Arc<LruCache<String, Bytes>> {
+    Arc::new(
+        LruCache::weighted(max_cache_size as u64, |_id: &String, data: &Bytes| {
+            data.len() as u64
+        })
+        .with_max_entries(ARTWORK_CACHE_MAX_ENTRIES),
+    )
+}
+
 /// Detects MIME type from image data by examining the magic bytes
 ///
 /// # Arguments
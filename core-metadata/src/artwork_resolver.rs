@@ -0,0 +1,357 @@
+//! Artwork Resolver - Ordered Fallback Across Artwork Sources
+//!
+//! `ArtworkResolver` tries a configurable list of artwork sources for a
+//! single track and stops at the first one that yields an image:
+//! - Embedded artwork from the audio tags
+//! - A sibling image (`cover.jpg`, `folder.png`, ...) in the provider folder
+//! - MusicBrainz Cover Art Archive (requires `artwork-remote`)
+//! - Last.fm (requires `artwork-remote`)
+//!
+//! Every source stores its result through [`ArtworkService`], so artwork is
+//! deduplicated the same way regardless of where it came from. The winning
+//! source is recorded on the returned [`ResolvedArtwork`].
+//!
+//! ## Usage
+//!
+//! ```ignore
+//! use core_metadata::artwork_resolver::{ArtworkRequest, ArtworkResolver};
+//!
+//! let resolver = ArtworkResolver::new(artwork_service)
+//!     .with_storage_provider(provider);
+//!
+//! let request = ArtworkRequest::new("Radiohead", "OK Computer")
+//!     .with_provider_file_id(track.provider_file_id.clone())
+//!     .with_embedded(extracted.artwork);
+//!
+//! if let Some(resolved) = resolver.resolve(&request).await? {
+//!     println!("Artwork {} from {}", resolved.artwork.id, resolved.source.as_str());
+//! }
+//! ```
+
+use crate::artwork::{ArtworkService, ProcessedArtwork};
+use crate::error::Result;
+use crate::extractor::{ArtworkType, ExtractedArtwork};
+use bridge_traits::error::BridgeError;
+use bridge_traits::storage::{RemoteFile, StorageProvider};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+/// File stems recognised as folder artwork, in order of preference
+const FOLDER_ART_STEMS: &[&str] = &["cover", "folder", "front", "album"];
+
+/// Image extensions recognised as folder artwork
+const FOLDER_ART_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png"];
+
+/// Where a piece of artwork was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ArtworkSource {
+    /// Artwork embedded in the audio file tags
+    Embedded,
+    /// Image file stored next to the track in the provider folder
+    Folder,
+    /// MusicBrainz Cover Art Archive
+    MusicBrainz,
+    /// Last.fm album artwork
+    LastFm,
+}
+
+impl ArtworkSource {
+    /// Default resolution order: local sources first, then remote APIs
+    pub const DEFAULT_ORDER: [ArtworkSource; 4] = [
+        ArtworkSource::Embedded,
+        ArtworkSource::Folder,
+        ArtworkSource::MusicBrainz,
+        ArtworkSource::LastFm,
+    ];
+
+    /// Stable string identifier for logging and persistence
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ArtworkSource::Embedded => "embedded",
+            ArtworkSource::Folder => "folder",
+            ArtworkSource::MusicBrainz => "musicbrainz",
+            ArtworkSource::LastFm => "lastfm",
+        }
+    }
+}
+
+/// Inputs needed to resolve artwork for one track
+#[derive(Debug, Clone, Default)]
+pub struct ArtworkRequest {
+    /// Artist name used by the remote sources
+    pub artist: String,
+    /// Album title used by the remote sources
+    pub album: String,
+    /// MusicBrainz release ID (optional, improves matching)
+    pub mbid: Option<String>,
+    /// Provider file ID of the track, used to locate its folder
+    pub provider_file_id: Option<String>,
+    /// Artwork extracted from the track's tags
+    pub embedded: Vec<ExtractedArtwork>,
+}
+
+impl ArtworkRequest {
+    /// Create a request for the given artist and album
+    pub fn new(artist: impl Into<String>, album: impl Into<String>) -> Self {
+        Self {
+            artist: artist.into(),
+            album: album.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the MusicBrainz release ID
+    pub fn with_mbid(mut self, mbid: impl Into<String>) -> Self {
+        self.mbid = Some(mbid.into());
+        self
+    }
+
+    /// Set the provider file ID of the track
+    pub fn with_provider_file_id(mut self, file_id: impl Into<String>) -> Self {
+        self.provider_file_id = Some(file_id.into());
+        self
+    }
+
+    /// Set the artwork extracted from the track's tags
+    pub fn with_embedded(mut self, embedded: Vec<ExtractedArtwork>) -> Self {
+        self.embedded = embedded;
+        self
+    }
+}
+
+/// Artwork found by the resolver, along with the source that produced it
+#[derive(Debug, Clone)]
+pub struct ResolvedArtwork {
+    /// Stored artwork
+    pub artwork: ProcessedArtwork,
+    /// Source the artwork was found in
+    pub source: ArtworkSource,
+}
+
+/// Resolves artwork by trying sources in a configurable order
+pub struct ArtworkResolver {
+    artwork_service: Arc<ArtworkService>,
+    storage_provider: Option<Arc<dyn StorageProvider>>,
+    order: Vec<ArtworkSource>,
+}
+
+impl ArtworkResolver {
+    /// Create a resolver using [`ArtworkSource::DEFAULT_ORDER`]
+    ///
+    /// Without a storage provider the folder source is skipped.
+    pub fn new(artwork_service: Arc<ArtworkService>) -> Self {
+        Self {
+            artwork_service,
+            storage_provider: None,
+            order: ArtworkSource::DEFAULT_ORDER.to_vec(),
+        }
+    }
+
+    /// Set the storage provider used to look up folder artwork
+    pub fn with_storage_provider(mut self, provider: Arc<dyn StorageProvider>) -> Self {
+        self.storage_provider = Some(provider);
+        self
+    }
+
+    /// Override the order in which sources are tried
+    ///
+    /// Sources left out of `order` are never consulted.
+    pub fn with_order(mut self, order: Vec<ArtworkSource>) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Sources in the order they will be tried
+    pub fn order(&self) -> &[ArtworkSource] {
+        &self.order
+    }
+
+    /// Resolve artwork for a track, stopping at the first source that succeeds
+    ///
+    /// Returns `None` when no source yields artwork. Storage errors from the
+    /// artwork service are propagated; a source that is merely unavailable
+    /// is skipped.
+    pub async fn resolve(&self, request: &ArtworkRequest) -> Result<Option<ResolvedArtwork>> {
+        for &source in &self.order {
+            let artwork = match source {
+                ArtworkSource::Embedded => self.resolve_embedded(request).await?,
+                ArtworkSource::Folder => self.resolve_folder(request).await?,
+                ArtworkSource::MusicBrainz => self.resolve_musicbrainz(request).await?,
+                ArtworkSource::LastFm => self.resolve_lastfm(request).await?,
+            };
+
+            if let Some(artwork) = artwork {
+                info!(
+                    "Resolved artwork {} for '{} - {}' from {}",
+                    artwork.id,
+                    request.artist,
+                    request.album,
+                    source.as_str()
+                );
+                return Ok(Some(ResolvedArtwork { artwork, source }));
+            }
+
+            debug!("No artwork from {} source", source.as_str());
+        }
+
+        Ok(None)
+    }
+
+    async fn resolve_embedded(&self, request: &ArtworkRequest) -> Result<Option<ProcessedArtwork>> {
+        if request.embedded.is_empty() {
+            return Ok(None);
+        }
+
+        let processed = self
+            .artwork_service
+            .extract_embedded(request.embedded.clone())
+            .await?;
+        Ok(processed.into_iter().next())
+    }
+
+    async fn resolve_folder(&self, request: &ArtworkRequest) -> Result<Option<ProcessedArtwork>> {
+        let (Some(provider), Some(file_id)) = (&self.storage_provider, &request.provider_file_id)
+        else {
+            return Ok(None);
+        };
+
+        let image = match find_folder_image(provider.as_ref(), file_id).await {
+            Ok(Some(image)) => image,
+            Ok(None) => return Ok(None),
+            Err(BridgeError::NotAvailable(reason)) => {
+                debug!("Folder artwork unavailable: {}", reason);
+                return Ok(None);
+            }
+            Err(e) => {
+                warn!("Failed to look up folder artwork for {}: {}", file_id, e);
+                return Ok(None);
+            }
+        };
+
+        let data = match provider.download(&image.id, None).await {
+            Ok(data) => data,
+            Err(e) => {
+                warn!("Failed to download folder artwork {}: {}", image.name, e);
+                return Ok(None);
+            }
+        };
+
+        let artwork = ExtractedArtwork {
+            data,
+            mime_type: folder_image_mime_type(&image),
+            picture_type: ArtworkType::CoverFront,
+            description: Some(image.name.clone()),
+            width: None,
+            height: None,
+        };
+
+        let processed = self.artwork_service.extract_embedded(vec![artwork]).await?;
+        Ok(processed.into_iter().next())
+    }
+
+    #[cfg(feature = "artwork-remote")]
+    async fn resolve_musicbrainz(
+        &self,
+        request: &ArtworkRequest,
+    ) -> Result<Option<ProcessedArtwork>> {
+        self.artwork_service
+            .fetch_from_musicbrainz(&request.artist, &request.album, request.mbid.as_deref())
+            .await
+    }
+
+    #[cfg(not(feature = "artwork-remote"))]
+    async fn resolve_musicbrainz(
+        &self,
+        _request: &ArtworkRequest,
+    ) -> Result<Option<ProcessedArtwork>> {
+        Ok(None)
+    }
+
+    #[cfg(feature = "artwork-remote")]
+    async fn resolve_lastfm(&self, request: &ArtworkRequest) -> Result<Option<ProcessedArtwork>> {
+        self.artwork_service
+            .fetch_from_lastfm(&request.artist, &request.album)
+            .await
+    }
+
+    #[cfg(not(feature = "artwork-remote"))]
+    async fn resolve_lastfm(&self, _request: &ArtworkRequest) -> Result<Option<ProcessedArtwork>> {
+        Ok(None)
+    }
+}
+
+/// Find the preferred artwork image among the siblings of `file_id`
+async fn find_folder_image(
+    provider: &dyn StorageProvider,
+    file_id: &str,
+) -> bridge_traits::error::Result<Option<RemoteFile>> {
+    let file = provider.get_metadata(file_id).await?;
+
+    for parent_id in &file.parent_ids {
+        let siblings = provider.list_folder(parent_id).await?;
+        let best = siblings
+            .into_iter()
+            .filter(|f| !f.is_folder)
+            .filter_map(|f| folder_art_rank(&f.name).map(|rank| (rank, f)))
+            .min_by_key(|(rank, _)| *rank);
+
+        if let Some((_, image)) = best {
+            return Ok(Some(image));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Preference rank of a folder artwork file name, lower is better
+fn folder_art_rank(name: &str) -> Option<usize> {
+    let lower = name.to_ascii_lowercase();
+    let (stem, extension) = lower.rsplit_once('.')?;
+
+    if !FOLDER_ART_EXTENSIONS.contains(&extension) {
+        return None;
+    }
+
+    FOLDER_ART_STEMS.iter().position(|s| *s == stem)
+}
+
+/// MIME type for a folder image, preferring the provider's value
+fn folder_image_mime_type(file: &RemoteFile) -> String {
+    if let Some(mime) = file.mime_type.as_ref().filter(|m| m.starts_with("image/")) {
+        return mime.clone();
+    }
+
+    if file.name.to_ascii_lowercase().ends_with(".png") {
+        "image/png".to_string()
+    } else {
+        "image/jpeg".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_folder_art_rank_prefers_cover() {
+        assert_eq!(folder_art_rank("cover.jpg"), Some(0));
+        assert_eq!(folder_art_rank("Folder.PNG"), Some(1));
+        assert_eq!(folder_art_rank("album.jpeg"), Some(3));
+        assert_eq!(folder_art_rank("cover.gif"), None);
+        assert_eq!(folder_art_rank("track01.jpg"), None);
+        assert_eq!(folder_art_rank("cover"), None);
+    }
+
+    #[test]
+    fn test_default_order() {
+        assert_eq!(
+            ArtworkSource::DEFAULT_ORDER,
+            [
+                ArtworkSource::Embedded,
+                ArtworkSource::Folder,
+                ArtworkSource::MusicBrainz,
+                ArtworkSource::LastFm,
+            ]
+        );
+    }
+}
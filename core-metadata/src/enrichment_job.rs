@@ -7,11 +7,13 @@
 //! ## Overview
 //!
 //! The enrichment job system:
-//! - Queries tracks missing artwork or lyrics
+//! - Queries tracks missing artwork or lyrics, optionally narrowed to one kind
+//!   of data or to specific artists and albums
 //! - Processes tracks in configurable batches
 //! - Respects network constraints (WiFi-only option)
 //! - Retries failed fetches with exponential backoff
 //! - Emits progress events for UI updates
+//! - Optionally persists per-track status so interrupted runs can resume
 //! - Integrates with BackgroundExecutor for scheduling
 //!
 //! ## Architecture
@@ -67,16 +69,31 @@
 //! job.run().await?;
 //! ```
 //!
+//! ### Resuming After a Restart
+//!
+//! With a status repository attached, each run records which tracks are still
+//! pending. After a crash or quit, `resume()` enriches only those tracks.
+//!
+//! ```ignore
+//! use core_library::repositories::SqliteEnrichmentStatusRepository;
+//!
+//! let job = EnrichmentJob::new(config, enrichment_service, repo, events)
+//!     .with_status_repository(Arc::new(SqliteEnrichmentStatusRepository::from_pool(pool)));
+//!
+//! job.resume().await?;
+//! ```
+//!
 //! ### With Background Executor
 //!
 //! ```ignore
-//! use bridge_traits::background::{BackgroundExecutor, TaskConstraints};
+//! use bridge_traits::background::{BackgroundExecutor, TaskConstraints, TaskPriority};
 //! use std::time::Duration;
 //!
-//! // Schedule daily enrichment
+//! // Schedule daily enrichment, behind syncs when the executor is busy
 //! let constraints = TaskConstraints {
 //!     requires_wifi: true,
 //!     requires_network: true,
+//!     priority: TaskPriority::Low,
 //!     ..Default::default()
 //! };
 //!
@@ -89,15 +106,17 @@
 
 use crate::enrichment_service::{EnrichmentRequest, EnrichmentResponse, EnrichmentService};
 use crate::error::{MetadataError, Result};
+use bridge_traits::background::{BackgroundExecutor, SpawnedTask, TaskHandle};
 use bridge_traits::network::{NetworkMonitor, NetworkType};
 #[cfg(not(target_arch = "wasm32"))]
 use core_async::sync::Semaphore;
 use core_async::time::sleep;
 use core_library::models::Track;
+use core_library::repositories::enrichment::{EnrichmentStatus, EnrichmentStatusRepository};
 use core_library::repositories::track::TrackRepository;
 use core_runtime::events::{CoreEvent, EventBus, LibraryEvent};
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, PoisonError};
 use std::time::Duration;
 use tracing::{debug, error, info, instrument, warn};
 
@@ -134,6 +153,27 @@ pub struct EnrichmentConfig {
 
     /// Timeout for individual operations (seconds)
     pub operation_timeout_secs: u64,
+
+    /// Only fetch artwork, for tracks that have none
+    ///
+    /// Setting any `only_missing_*` flag narrows the job to exactly the
+    /// flagged data, overriding `enable_artwork`, `enable_lyrics` and
+    /// `enable_artist_enrichment`.
+    pub only_missing_artwork: bool,
+
+    /// Only fetch lyrics, for tracks whose lyrics were never fetched
+    pub only_missing_lyrics: bool,
+
+    /// Only enrich artists that lack a biography or country
+    pub only_missing_metadata: bool,
+
+    /// Limit the job to tracks by these artists (empty for all artists)
+    ///
+    /// Matches both the track artist and the album artist.
+    pub artist_ids: Vec<String>,
+
+    /// Limit the job to tracks on these albums (empty for all albums)
+    pub album_ids: Vec<String>,
 }
 
 impl Default for EnrichmentConfig {
@@ -148,6 +188,11 @@ impl Default for EnrichmentConfig {
             max_retries: 3,
             base_retry_delay_ms: 100,
             operation_timeout_secs: 30,
+            only_missing_artwork: false,
+            only_missing_lyrics: false,
+            only_missing_metadata: false,
+            artist_ids: Vec::new(),
+            album_ids: Vec::new(),
         }
     }
 }
@@ -211,6 +256,83 @@ impl EnrichmentConfig {
         self.operation_timeout_secs = timeout;
         self
     }
+
+    /// Target only tracks missing artwork
+    pub fn with_only_missing_artwork(mut self, only: bool) -> Self {
+        self.only_missing_artwork = only;
+        self
+    }
+
+    /// Target only tracks missing lyrics
+    pub fn with_only_missing_lyrics(mut self, only: bool) -> Self {
+        self.only_missing_lyrics = only;
+        self
+    }
+
+    /// Target only artists missing biography or country
+    pub fn with_only_missing_metadata(mut self, only: bool) -> Self {
+        self.only_missing_metadata = only;
+        self
+    }
+
+    /// Limit the job to tracks by the given artists
+    pub fn with_artist_filter(mut self, artist_ids: Vec<String>) -> Self {
+        self.artist_ids = artist_ids;
+        self
+    }
+
+    /// Limit the job to tracks on the given albums
+    pub fn with_album_filter(mut self, album_ids: Vec<String>) -> Self {
+        self.album_ids = album_ids;
+        self
+    }
+
+    /// Whether any `only_missing_*` flag narrows the job
+    fn is_targeted(&self) -> bool {
+        self.only_missing_artwork || self.only_missing_lyrics || self.only_missing_metadata
+    }
+
+    /// Whether this run fetches artwork
+    pub fn targets_artwork(&self) -> bool {
+        if self.is_targeted() {
+            self.only_missing_artwork
+        } else {
+            self.enable_artwork
+        }
+    }
+
+    /// Whether this run fetches lyrics
+    pub fn targets_lyrics(&self) -> bool {
+        if self.is_targeted() {
+            self.only_missing_lyrics
+        } else {
+            self.enable_lyrics
+        }
+    }
+
+    /// Whether this run enriches artist biography and country
+    pub fn targets_artist_metadata(&self) -> bool {
+        if self.is_targeted() {
+            self.only_missing_metadata
+        } else {
+            self.enable_artist_enrichment
+        }
+    }
+
+    /// Whether `track` passes the artist and album filters
+    fn matches_filters(&self, track: &Track) -> bool {
+        let artist_matches = self.artist_ids.is_empty()
+            || [&track.artist_id, &track.album_artist_id]
+                .into_iter()
+                .flatten()
+                .any(|id| self.artist_ids.contains(id));
+        let album_matches = self.album_ids.is_empty()
+            || track
+                .album_id
+                .as_ref()
+                .is_some_and(|id| self.album_ids.contains(id));
+        artist_matches && album_matches
+    }
 }
 
 // =============================================================================
@@ -299,6 +421,9 @@ pub struct EnrichmentJob {
     track_repository: Arc<dyn TrackRepository>,
     event_bus: Arc<EventBus>,
     network_monitor: Option<Arc<dyn NetworkMonitor>>,
+    status_repository: Option<Arc<dyn EnrichmentStatusRepository>>,
+    /// Runs started with `spawn_on`, cancelled by `shutdown`
+    spawned: Mutex<Vec<TaskHandle>>,
 }
 
 impl EnrichmentJob {
@@ -315,6 +440,8 @@ impl EnrichmentJob {
             track_repository,
             event_bus,
             network_monitor: None,
+            status_repository: None,
+            spawned: Mutex::new(Vec::new()),
         }
     }
 
@@ -324,7 +451,19 @@ impl EnrichmentJob {
         self
     }
 
+    /// Persist per-track progress so an interrupted run can be resumed
+    pub fn with_status_repository(
+        mut self,
+        repository: Arc<dyn EnrichmentStatusRepository>,
+    ) -> Self {
+        self.status_repository = Some(repository);
+        self
+    }
+
     /// Run the enrichment job
+    ///
+    /// With a status repository attached, this starts a new run: all tracks
+    /// found are marked pending, replacing the progress of any earlier run.
     #[instrument(skip(self), name = "enrichment_job")]
     pub async fn run(&self) -> Result<EnrichmentProgress> {
         info!("Starting metadata enrichment job");
@@ -337,6 +476,104 @@ impl EnrichmentJob {
         // Query tracks needing enrichment
         let tracks = self.query_tracks_needing_enrichment().await?;
 
+        if let Some(repository) = &self.status_repository {
+            let track_ids: Vec<String> = tracks.iter().map(|t| t.id.clone()).collect();
+            repository
+                .reset(&track_ids)
+                .await
+                .map_err(|e| MetadataError::Database(e.to_string()))?;
+        }
+
+        self.process_run(tracks).await
+    }
+
+    /// Start a run on `executor` without waiting for it
+    ///
+    /// The handle is kept so [`shutdown`](Self::shutdown) can cancel the run,
+    /// for instance when the user signs out mid-enrichment.
+    pub fn spawn_on(self: &Arc<Self>, executor: &dyn BackgroundExecutor) -> TaskHandle {
+        let job = Arc::clone(self);
+        let handle = executor.spawn(SpawnedTask::new(move |_token| async move {
+            match job.run().await {
+                Ok(progress) => info!(
+                    processed = progress.processed,
+                    failed = progress.failed,
+                    "Background enrichment run finished"
+                ),
+                Err(e) => warn!(error = %e, "Background enrichment run failed"),
+            }
+        }));
+
+        let mut spawned = self.spawned.lock().unwrap_or_else(PoisonError::into_inner);
+        spawned.retain(|handle| !handle.is_finished());
+        spawned.push(handle.clone());
+        handle
+    }
+
+    /// Cancel every run started with [`spawn_on`](Self::spawn_on)
+    ///
+    /// Tracks a cancelled run left pending can be picked up later with
+    /// [`resume`](Self::resume).
+    pub fn shutdown(&self) {
+        let spawned =
+            std::mem::take(&mut *self.spawned.lock().unwrap_or_else(PoisonError::into_inner));
+        if !spawned.is_empty() {
+            info!(
+                runs = spawned.len(),
+                "Cancelling background enrichment runs"
+            );
+        }
+        for handle in spawned {
+            handle.cancel();
+        }
+    }
+
+    /// Continue the last run, enriching only the tracks it left pending
+    ///
+    /// Tracks that were already enriched or failed are skipped. Returns an
+    /// empty progress when nothing is pending.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MetadataError::ConfigurationError`] if no status repository
+    /// is attached.
+    #[instrument(skip(self), name = "enrichment_job_resume")]
+    pub async fn resume(&self) -> Result<EnrichmentProgress> {
+        let repository = self.status_repository.as_ref().ok_or_else(|| {
+            MetadataError::ConfigurationError(
+                "Resuming enrichment requires a status repository".to_string(),
+            )
+        })?;
+
+        info!("Resuming metadata enrichment job");
+
+        if self.config.require_wifi {
+            self.check_wifi().await?;
+        }
+
+        let pending = repository
+            .find_by_status(EnrichmentStatus::Pending)
+            .await
+            .map_err(|e| MetadataError::Database(e.to_string()))?;
+
+        let mut tracks = Vec::with_capacity(pending.len());
+        for track_id in pending {
+            match self
+                .track_repository
+                .find_by_id(&track_id)
+                .await
+                .map_err(|e| MetadataError::Database(e.to_string()))?
+            {
+                Some(track) => tracks.push(track),
+                None => debug!(track_id = %track_id, "Pending track no longer exists"),
+            }
+        }
+
+        self.process_run(tracks).await
+    }
+
+    /// Enrich `tracks`, emitting progress events along the way
+    async fn process_run(&self, tracks: Vec<Track>) -> Result<EnrichmentProgress> {
         if tracks.is_empty() {
             info!("No tracks need enrichment");
             return Ok(EnrichmentProgress::new(0));
@@ -409,7 +646,7 @@ impl EnrichmentJob {
         let mut tracks = Vec::new();
 
         // Query tracks without artwork
-        if self.config.enable_artwork {
+        if self.config.targets_artwork() {
             let artwork_tracks = self
                 .track_repository
                 .find_by_missing_artwork()
@@ -422,7 +659,7 @@ impl EnrichmentJob {
         }
 
         // Query tracks without lyrics
-        if self.config.enable_lyrics {
+        if self.config.targets_lyrics() {
             let lyrics_tracks = self
                 .track_repository
                 .find_by_lyrics_status("not_fetched")
@@ -439,6 +676,32 @@ impl EnrichmentJob {
             }
         }
 
+        // Query tracks whose artist lacks biography or country
+        if self.config.targets_artist_metadata() {
+            let metadata_tracks = self
+                .track_repository
+                .find_by_missing_artist_metadata()
+                .await
+                .map_err(|e| MetadataError::Database(e.to_string()))?;
+
+            debug!(
+                count = metadata_tracks.len(),
+                "Found tracks with artists missing metadata"
+            );
+
+            // One track per artist is enough to enrich it
+            for track in metadata_tracks {
+                if !tracks
+                    .iter()
+                    .any(|t| t.id == track.id || t.artist_id == track.artist_id)
+                {
+                    tracks.push(track);
+                }
+            }
+        }
+
+        tracks.retain(|track| self.config.matches_filters(track));
+
         Ok(tracks)
     }
 
@@ -467,19 +730,22 @@ impl EnrichmentJob {
                 let track = track.clone();
                 let job = self.clone_for_task();
 
+                let track_id = track.id.clone();
                 let handle = core_async::task::spawn(async move {
                     let result = job.enrich_track(&track).await;
                     drop(permit);
                     result
                 });
 
-                handles.push(handle);
+                handles.push((track_id, handle));
             }
 
             // Wait for batch to complete
-            for handle in handles {
+            for (track_id, handle) in handles {
                 match handle.await {
                     Ok(result) => {
+                        self.record_status(&track_id, result.error.as_deref()).await;
+
                         // Update progress
                         progress.processed += 1;
 
@@ -499,6 +765,8 @@ impl EnrichmentJob {
                     }
                     Err(e) => {
                         error!(error = %e, "Task panicked");
+                        self.record_status(&track_id, Some("enrichment task panicked"))
+                            .await;
                         progress.processed += 1;
                         progress.failed += 1;
                     }
@@ -532,6 +800,7 @@ impl EnrichmentJob {
             progress.phase = format!("Processing track {}/{}", idx + 1, tracks.len());
 
             let result = self.enrich_track(track).await;
+            self.record_status(&track.id, result.error.as_deref()).await;
 
             // Update progress
             progress.processed += 1;
@@ -578,8 +847,8 @@ impl EnrichmentJob {
         // Create enrichment request
         let request = EnrichmentRequest {
             track: track.clone(),
-            fetch_artwork: self.config.enable_artwork,
-            fetch_lyrics: self.config.enable_lyrics,
+            fetch_artwork: self.config.targets_artwork(),
+            fetch_lyrics: self.config.targets_lyrics(),
         };
 
         // Perform enrichment with retry logic
@@ -618,9 +887,38 @@ impl EnrichmentJob {
             }
         }
 
+        // Enrich the artist record (skipped by the service if already complete)
+        if self.config.targets_artist_metadata() {
+            if let Some(artist_id) = &track.artist_id {
+                if let Err(e) = self.enrichment_service.enrich_artist(artist_id).await {
+                    warn!(error = %e, artist_id = %artist_id, "Artist enrichment failed");
+                    result.error.get_or_insert_with(|| e.to_string());
+                }
+            }
+        }
+
         result
     }
 
+    /// Persist the outcome of one track, if a status repository is attached
+    ///
+    /// A failed write is logged rather than aborting the run; the track then
+    /// stays pending and is simply enriched again on resume.
+    async fn record_status(&self, track_id: &str, error: Option<&str>) {
+        let Some(repository) = &self.status_repository else {
+            return;
+        };
+
+        let status = if error.is_some() {
+            EnrichmentStatus::Failed
+        } else {
+            EnrichmentStatus::Done
+        };
+        if let Err(e) = repository.set_status(track_id, status, error).await {
+            warn!(track_id = %track_id, error = %e, "Failed to record enrichment status");
+        }
+    }
+
     /// Enrich track with retry logic
     async fn enrich_with_retry(&self, request: &EnrichmentRequest) -> Result<EnrichmentResponse> {
         for attempt in 0..=self.config.max_retries {
@@ -669,6 +967,8 @@ impl EnrichmentJob {
             track_repository: Arc::clone(&self.track_repository),
             event_bus: Arc::clone(&self.event_bus),
             network_monitor: self.network_monitor.as_ref().map(Arc::clone),
+            status_repository: self.status_repository.as_ref().map(Arc::clone),
+            spawned: Mutex::new(Vec::new()),
         }
     }
 }
@@ -737,6 +1037,27 @@ mod tests {
         assert_eq!(config.max_retries, 3);
     }
 
+    #[test]
+    fn test_only_missing_flags_narrow_targets() {
+        let config = EnrichmentConfig::default();
+        assert!(config.targets_artwork());
+        assert!(config.targets_lyrics());
+        assert!(!config.targets_artist_metadata());
+
+        let config = EnrichmentConfig::default().with_only_missing_lyrics(true);
+        assert!(!config.targets_artwork());
+        assert!(config.targets_lyrics());
+        assert!(!config.targets_artist_metadata());
+
+        let config = EnrichmentConfig::default()
+            .with_artwork(false)
+            .with_only_missing_artwork(true)
+            .with_only_missing_metadata(true);
+        assert!(config.targets_artwork());
+        assert!(!config.targets_lyrics());
+        assert!(config.targets_artist_metadata());
+    }
+
     #[test]
     fn test_calculate_backoff() {
         let config = EnrichmentConfig::default();
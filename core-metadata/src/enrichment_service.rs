@@ -545,6 +545,10 @@ mod tests {
             created_at: 1000000,
             updated_at: 1000000,
             provider_modified_at: None,
+            leading_silence_ms: None,
+            trailing_silence_ms: None,
+            replay_gain_millibels: None,
+            true_peak_millibels: None,
         };
 
         let request = EnrichmentRequest {
@@ -587,6 +591,10 @@ mod tests {
             created_at: 1000000,
             updated_at: 1000000,
             provider_modified_at: None,
+            leading_silence_ms: None,
+            trailing_silence_ms: None,
+            replay_gain_millibels: None,
+            true_peak_millibels: None,
         };
 
         let response = EnrichmentResponse {
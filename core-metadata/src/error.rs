@@ -1,5 +1,24 @@
+use std::fmt;
 use thiserror::Error;
 
+/// Why a file was skipped rather than extracted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipReason {
+    /// The file contains no bytes
+    EmptyFile,
+    /// The file looks like a podcast, audiobook or voice memo
+    NonMusic,
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkipReason::EmptyFile => write!(f, "file is empty"),
+            SkipReason::NonMusic => write!(f, "file is likely not music"),
+        }
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum MetadataError {
     #[error("Failed to extract metadata: {0}")]
@@ -14,9 +33,18 @@ pub enum MetadataError {
     #[error("Corrupted file: {0}")]
     CorruptedFile(String),
 
+    #[error("Truncated file: {0}")]
+    Truncated(String),
+
+    #[error("Skipped: {0}")]
+    Skipped(SkipReason),
+
     #[error("Artwork processing failed: {0}")]
     ArtworkError(String),
 
+    #[error("Artwork too large: {0}")]
+    ArtworkTooLarge(String),
+
     #[error("Artwork not found: {artwork_id}")]
     ArtworkNotFound { artwork_id: String },
 
@@ -73,6 +101,13 @@ pub enum MetadataError {
 
     #[error("Network error: {0}")]
     NetworkError(String),
+
+    #[error("{provider} request timed out after {attempts} attempt(s) of {timeout_ms}ms each")]
+    RequestTimeout {
+        provider: String,
+        attempts: u32,
+        timeout_ms: u64,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, MetadataError>;
@@ -7,7 +7,7 @@
 //!
 //! - Extracts comprehensive metadata (title, artist, album, year, etc.)
 //! - Normalizes metadata (trim whitespace, title case, standardize track numbers)
-//! - Extracts embedded artwork
+//! - Extracts embedded artwork, downscaling or dropping oversized pictures
 //! - Calculates SHA-256 content hash for deduplication
 //! - Handles corrupted files gracefully with partial metadata
 //!
@@ -33,16 +33,22 @@
 
 use bridge_traits::storage::FileSystemAccess;
 use bytes::Bytes;
+use image::{DynamicImage, ImageFormat, ImageReader};
 use lofty::config::ParseOptions;
 use lofty::file::{AudioFile, TaggedFileExt};
 use lofty::picture::MimeType;
 use lofty::probe::Probe;
 use lofty::tag::{Accessor, ItemKey};
 use sha2::{Digest, Sha256};
+use std::io::Cursor;
 use std::path::Path;
 use tracing::{debug, warn};
 
-use crate::error::{MetadataError, Result};
+use crate::error::{MetadataError, Result, SkipReason};
+use crate::normalizer::{NormalizationRules, TitleNormalizer};
+
+/// Length of an ID3v2 header (and of its optional footer)
+const ID3V2_HEADER_LEN: usize = 10;
 
 #[cfg(not(target_arch = "wasm32"))]
 use core_async::fs;
@@ -105,6 +111,16 @@ pub struct ExtractedMetadata {
     pub has_errors: bool,
     /// Partial success - some tags extracted despite errors
     pub partial_metadata: bool,
+    /// Text fields that were cut to the configured [`FieldLimits`]
+    pub truncated_fields: Vec<&'static str>,
+
+    // Title normalization
+    /// Title as tagged, when the [`TitleNormalizer`] changed it
+    pub original_title: Option<String>,
+    /// Version qualifier moved out of the title (e.g. "2011 Remaster")
+    pub version: Option<String>,
+    /// Featured artists credited in the title or artist tag
+    pub featured_artists: Vec<String>,
 }
 
 /// Extracted artwork/cover image
@@ -149,6 +165,56 @@ impl From<lofty::picture::PictureType> for ArtworkType {
     }
 }
 
+/// Limits applied to embedded artwork during extraction
+///
+/// Some files embed multi-megabyte scans as cover art. Pictures over the hard
+/// limits are dropped without being decoded; pictures whose longest edge
+/// exceeds `max_edge` are downscaled and re-encoded before they are returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArtworkLimits {
+    /// Largest encoded picture accepted, in bytes
+    pub max_bytes: usize,
+    /// Largest width or height accepted, in pixels
+    pub max_dimension: u32,
+    /// Longest edge kept after downscaling, in pixels
+    pub max_edge: u32,
+}
+
+impl Default for ArtworkLimits {
+    fn default() -> Self {
+        Self {
+            max_bytes: 32 * 1024 * 1024,
+            max_dimension: 10_000,
+            // Matches ArtworkSize::Full
+            max_edge: 1200,
+        }
+    }
+}
+
+/// Maximum lengths, in characters, of sanitized text fields
+///
+/// Tags occasionally carry whole liner notes or garbage in a single frame,
+/// which breaks layouts and bloats the FTS index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldLimits {
+    /// Title, artist, album, album artist and composer
+    pub max_name_chars: usize,
+    /// Genre
+    pub max_genre_chars: usize,
+    /// Comment
+    pub max_comment_chars: usize,
+}
+
+impl Default for FieldLimits {
+    fn default() -> Self {
+        Self {
+            max_name_chars: 512,
+            max_genre_chars: 128,
+            max_comment_chars: 2048,
+        }
+    }
+}
+
 /// Audio metadata extractor
 ///
 /// Extracts metadata from audio files using the `lofty` crate.
@@ -156,19 +222,48 @@ impl From<lofty::picture::PictureType> for ArtworkType {
 pub struct MetadataExtractor {
     /// Parse options for lofty
     parse_options: ParseOptions,
+    /// Limits for embedded artwork
+    artwork_limits: ArtworkLimits,
+    /// Limits for text fields
+    field_limits: FieldLimits,
+    /// Rules for splitting featured artists and versions out of titles
+    title_normalizer: TitleNormalizer,
 }
 
 impl MetadataExtractor {
     /// Create a new metadata extractor with default settings
     pub fn new() -> Self {
-        Self {
-            parse_options: ParseOptions::new(),
-        }
+        Self::with_options(ParseOptions::new())
     }
 
     /// Create extractor with custom parse options
     pub fn with_options(parse_options: ParseOptions) -> Self {
-        Self { parse_options }
+        Self {
+            parse_options,
+            artwork_limits: ArtworkLimits::default(),
+            field_limits: FieldLimits::default(),
+            title_normalizer: TitleNormalizer::default(),
+        }
+    }
+
+    /// Set the limits applied to embedded artwork
+    pub fn with_artwork_limits(mut self, artwork_limits: ArtworkLimits) -> Self {
+        self.artwork_limits = artwork_limits;
+        self
+    }
+
+    /// Set the length limits applied to text fields
+    pub fn with_field_limits(mut self, field_limits: FieldLimits) -> Self {
+        self.field_limits = field_limits;
+        self
+    }
+
+    /// Set the rules used to normalize titles
+    ///
+    /// Use [`NormalizationRules::none`] to keep titles exactly as tagged.
+    pub fn with_title_rules(mut self, rules: NormalizationRules) -> Self {
+        self.title_normalizer = TitleNormalizer::new(rules);
+        self
     }
 
     /// Extract metadata from an audio file (native platform)
@@ -242,7 +337,8 @@ impl MetadataExtractor {
     /// # Errors
     ///
     /// Returns an error if:
-    /// - File cannot be opened or read
+    /// - `file_data` is empty ([`MetadataError::Skipped`] with [`SkipReason::EmptyFile`])
+    /// - The data ends before its headers do ([`MetadataError::Truncated`])
     /// - File format is completely unsupported
     /// - Critical parsing errors prevent any metadata extraction
     ///
@@ -259,16 +355,28 @@ impl MetadataExtractor {
         file_data: &[u8],
         path: &Path,
     ) -> Result<ExtractedMetadata> {
+        if file_data.is_empty() {
+            debug!("Skipping empty file: {:?}", path);
+            return Err(MetadataError::Skipped(SkipReason::EmptyFile));
+        }
+        Self::check_truncated(file_data)?;
+
         let file_size = file_data.len() as u64;
-        let content_hash = self.calculate_hash(&file_data);
+        let content_hash = self.calculate_hash(file_data);
 
         // Probe the file to determine format
         let tagged_file = Probe::new(std::io::Cursor::new(&file_data))
             .options(self.parse_options)
             .guess_file_type()
-            .map_err(|e| MetadataError::ExtractionFailed(format!("Failed to probe file: {}", e)))?
+            .map_err(|e| {
+                if e.kind() == std::io::ErrorKind::UnexpectedEof {
+                    MetadataError::Truncated(format!("Failed to probe file: {}", e))
+                } else {
+                    MetadataError::ExtractionFailed(format!("Failed to probe file: {}", e))
+                }
+            })?
             .read()
-            .map_err(|e| MetadataError::ExtractionFailed(format!("Failed to parse file: {}", e)))?;
+            .map_err(|e| Self::map_lofty_error("Failed to parse file", e))?;
 
         let file_type = tagged_file.file_type();
         let properties = tagged_file.properties();
@@ -286,6 +394,7 @@ impl MetadataExtractor {
         // Try to extract tags from primary tag, falling back to first available tag
         let mut has_errors = false;
         let mut partial_metadata = false;
+        let mut truncated_fields = Vec::new();
 
         let tag = if let Some(primary_tag) = tagged_file.primary_tag() {
             Some(primary_tag)
@@ -308,20 +417,35 @@ impl MetadataExtractor {
             composer,
             comment,
         ) = if let Some(tag) = tag {
+            let limits = self.field_limits;
+            let mut sanitize = |field: &'static str, value: Option<&str>, max_chars: usize| {
+                Self::sanitize_field(field, value?, max_chars, &mut truncated_fields)
+            };
             (
-                tag.title().map(|s| Self::normalize_text(s.as_ref())),
-                tag.artist().map(|s| Self::normalize_text(s.as_ref())),
-                tag.album().map(|s| Self::normalize_text(s.as_ref())),
-                tag.get_string(&ItemKey::AlbumArtist)
-                    .map(Self::normalize_text),
+                sanitize("title", tag.title().as_deref(), limits.max_name_chars),
+                sanitize("artist", tag.artist().as_deref(), limits.max_name_chars),
+                sanitize("album", tag.album().as_deref(), limits.max_name_chars),
+                sanitize(
+                    "album_artist",
+                    tag.get_string(&ItemKey::AlbumArtist),
+                    limits.max_name_chars,
+                ),
                 tag.year().map(|y| y as i32),
                 tag.track(),
                 tag.track_total(),
                 tag.disk(),
                 tag.disk_total(),
-                tag.genre().map(|s| Self::normalize_text(s.as_ref())),
-                tag.get_string(&ItemKey::Composer).map(Self::normalize_text),
-                tag.comment().map(|s| Self::normalize_text(s.as_ref())),
+                sanitize("genre", tag.genre().as_deref(), limits.max_genre_chars),
+                sanitize(
+                    "composer",
+                    tag.get_string(&ItemKey::Composer),
+                    limits.max_name_chars,
+                ),
+                sanitize(
+                    "comment",
+                    tag.comment().as_deref(),
+                    limits.max_comment_chars,
+                ),
             )
         } else {
             warn!(
@@ -354,9 +478,27 @@ impl MetadataExtractor {
             )
         };
 
+        // Split featured artists and version qualifiers out of the title
+        let mut featured_artists = Vec::new();
+        let artist = artist.map(|artist| {
+            let (main, featured) = self.title_normalizer.normalize_artist(&artist);
+            featured_artists.extend(featured);
+            main
+        });
+        let (title, original_title, version) = match title {
+            Some(raw) => {
+                let normalized = self.title_normalizer.normalize(&raw);
+                featured_artists.extend(normalized.featured_artists.iter().cloned());
+                let original_title = normalized.is_changed().then_some(normalized.original);
+                (Some(normalized.title), original_title, normalized.version)
+            }
+            None => (None, None, None),
+        };
+        featured_artists.dedup();
+
         // Extract artwork
         let artwork = if let Some(tag) = tag {
-            Self::extract_artwork(tag)
+            self.extract_artwork(tag)
         } else {
             Vec::new()
         };
@@ -385,6 +527,10 @@ impl MetadataExtractor {
             artwork,
             has_errors,
             partial_metadata,
+            truncated_fields,
+            original_title,
+            version,
+            featured_artists,
         })
     }
 
@@ -402,16 +548,44 @@ impl MetadataExtractor {
     /// - Normalizes consecutive whitespace to single space
     /// - Removes null bytes and control characters
     fn normalize_text(text: &str) -> String {
-        text.split_whitespace()
+        // Control characters act as separators so "A\0B" doesn't become "AB"
+        text.split(|c: char| c.is_whitespace() || c.is_control())
+            .filter(|word| !word.is_empty())
             .collect::<Vec<_>>()
             .join(" ")
-            .chars()
-            .filter(|c| !c.is_control())
-            .collect()
+    }
+
+    /// Normalize a text field and cut it to `max_chars`
+    ///
+    /// Returns `None` when nothing is left after normalization. Truncated
+    /// fields are recorded in `truncated_fields`.
+    fn sanitize_field(
+        field: &'static str,
+        value: &str,
+        max_chars: usize,
+        truncated_fields: &mut Vec<&'static str>,
+    ) -> Option<String> {
+        let mut text = Self::normalize_text(value);
+
+        if let Some((cut, _)) = text.char_indices().nth(max_chars) {
+            debug!(
+                "Truncating {} from {} bytes to {} characters",
+                field,
+                text.len(),
+                max_chars
+            );
+            text.truncate(cut);
+            text.truncate(text.trim_end().len());
+            truncated_fields.push(field);
+        }
+
+        (!text.is_empty()).then_some(text)
     }
 
     /// Extract all artwork/pictures from tag
-    fn extract_artwork(tag: &lofty::tag::Tag) -> Vec<ExtractedArtwork> {
+    ///
+    /// Pictures over the configured [`ArtworkLimits`] are downscaled or dropped.
+    fn extract_artwork(&self, tag: &lofty::tag::Tag) -> Vec<ExtractedArtwork> {
         tag.pictures()
             .iter()
             .filter_map(|pic| {
@@ -428,18 +602,160 @@ impl MetadataExtractor {
                     return None;
                 }
 
-                Some(ExtractedArtwork {
+                let artwork = ExtractedArtwork {
                     data,
                     mime_type,
                     picture_type: ArtworkType::from(pic.pic_type()),
                     description: pic.description().map(|s| s.to_string()),
-                    width: None,  // lofty doesn't provide dimensions
-                    height: None, // lofty doesn't provide dimensions
-                })
+                    width: None,
+                    height: None,
+                };
+
+                match self.apply_artwork_limits(artwork) {
+                    Ok(artwork) => Some(artwork),
+                    Err(e) => {
+                        warn!("Dropping embedded artwork: {}", e);
+                        None
+                    }
+                }
             })
             .collect()
     }
 
+    /// Enforce [`ArtworkLimits`] on one picture
+    ///
+    /// Fills in the picture dimensions when the header can be read. Pictures
+    /// whose dimensions can't be read are returned unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MetadataError::ArtworkTooLarge` if the picture exceeds
+    /// `max_bytes` or `max_dimension`, and `MetadataError::ImageProcessing` if
+    /// downscaling fails.
+    fn apply_artwork_limits(&self, mut artwork: ExtractedArtwork) -> Result<ExtractedArtwork> {
+        let limits = &self.artwork_limits;
+
+        if artwork.data.len() > limits.max_bytes {
+            return Err(MetadataError::ArtworkTooLarge(format!(
+                "{} bytes exceeds the {} byte limit",
+                artwork.data.len(),
+                limits.max_bytes
+            )));
+        }
+
+        // Only the header is read here, so oversized images are never decoded
+        let dimensions = ImageReader::new(Cursor::new(&artwork.data[..]))
+            .with_guessed_format()
+            .map_err(|e| e.to_string())
+            .and_then(|reader| reader.into_dimensions().map_err(|e| e.to_string()));
+        let (width, height) = match dimensions {
+            Ok(dimensions) => dimensions,
+            Err(e) => {
+                debug!("Could not read embedded artwork dimensions: {}", e);
+                return Ok(artwork);
+            }
+        };
+
+        if width > limits.max_dimension || height > limits.max_dimension {
+            return Err(MetadataError::ArtworkTooLarge(format!(
+                "{}x{} exceeds the {} pixel limit",
+                width, height, limits.max_dimension
+            )));
+        }
+
+        artwork.width = Some(width);
+        artwork.height = Some(height);
+        if width.max(height) <= limits.max_edge {
+            return Ok(artwork);
+        }
+
+        let img =
+            image::load_from_memory(&artwork.data).map_err(|e| MetadataError::ImageProcessing {
+                message: format!("Failed to decode artwork: {}", e),
+            })?;
+        let resized = img.resize(
+            limits.max_edge,
+            limits.max_edge,
+            image::imageops::FilterType::Lanczos3,
+        );
+
+        // Keep PNG lossless; everything else is stored as JPEG, which has no alpha
+        let (format, resized) = if artwork.mime_type == "image/png" {
+            (ImageFormat::Png, resized)
+        } else {
+            (
+                ImageFormat::Jpeg,
+                DynamicImage::ImageRgb8(resized.to_rgb8()),
+            )
+        };
+
+        let mut buffer = Vec::new();
+        resized
+            .write_to(&mut Cursor::new(&mut buffer), format)
+            .map_err(|e| MetadataError::ImageProcessing {
+                message: format!("Failed to encode artwork: {}", e),
+            })?;
+
+        debug!(
+            "Downscaled embedded artwork from {}x{} ({} bytes) to {}x{} ({} bytes)",
+            width,
+            height,
+            artwork.data.len(),
+            resized.width(),
+            resized.height(),
+            buffer.len()
+        );
+
+        artwork.data = Bytes::from(buffer);
+        artwork.mime_type = format.to_mime_type().to_string();
+        artwork.width = Some(resized.width());
+        artwork.height = Some(resized.height());
+        Ok(artwork)
+    }
+
+    /// Reject data that ends inside a leading ID3v2 tag
+    ///
+    /// lofty reports such files as an unknown format, which hides the real
+    /// problem (usually an interrupted download).
+    fn check_truncated(file_data: &[u8]) -> Result<()> {
+        if !file_data.starts_with(b"ID3") {
+            return Ok(());
+        }
+        if file_data.len() < ID3V2_HEADER_LEN {
+            return Err(MetadataError::Truncated(format!(
+                "ID3v2 header needs {} bytes, got {}",
+                ID3V2_HEADER_LEN,
+                file_data.len()
+            )));
+        }
+
+        // Tag size is a 28-bit synchsafe integer excluding the header and footer
+        let body_len = file_data[6..10]
+            .iter()
+            .fold(0usize, |acc, byte| (acc << 7) | (*byte & 0x7F) as usize);
+        let has_footer = file_data[5] & 0x10 != 0;
+        let tag_len = ID3V2_HEADER_LEN + body_len + if has_footer { ID3V2_HEADER_LEN } else { 0 };
+
+        if file_data.len() < tag_len {
+            return Err(MetadataError::Truncated(format!(
+                "ID3v2 tag declares {} bytes, got {}",
+                tag_len,
+                file_data.len()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Map a lofty error, keeping unexpected EOF distinct from other failures
+    fn map_lofty_error(context: &str, error: lofty::error::LoftyError) -> MetadataError {
+        match error.kind() {
+            lofty::error::ErrorKind::Io(io) if io.kind() == std::io::ErrorKind::UnexpectedEof => {
+                MetadataError::Truncated(format!("{}: {}", context, error))
+            }
+            _ => MetadataError::ExtractionFailed(format!("{}: {}", context, error)),
+        }
+    }
+
     /// Convert lofty MimeType to string
     fn mime_type_to_string(mime_type: &MimeType) -> String {
         match mime_type {
@@ -500,6 +816,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_sanitize_field() {
+        let mut truncated = Vec::new();
+
+        assert_eq!(
+            MetadataExtractor::sanitize_field("title", "Title \0\0\0", 512, &mut truncated),
+            Some("Title".to_string())
+        );
+        assert_eq!(
+            MetadataExtractor::sanitize_field("artist", "A\0B", 512, &mut truncated),
+            Some("A B".to_string())
+        );
+        assert_eq!(
+            MetadataExtractor::sanitize_field("album", "\0 \u{7}", 512, &mut truncated),
+            None
+        );
+        assert!(truncated.is_empty());
+
+        // Cut on a character boundary and drop the dangling space
+        assert_eq!(
+            MetadataExtractor::sanitize_field("genre", "夜に 駆ける", 3, &mut truncated),
+            Some("夜に".to_string())
+        );
+        assert_eq!(truncated, vec!["genre"]);
+    }
+
     #[test]
     fn test_calculate_hash() {
         let extractor = MetadataExtractor::new();
@@ -591,5 +933,85 @@ mod tests {
         );
     }
 
+    fn encoded_artwork(width: u32, height: u32, format: ImageFormat) -> ExtractedArtwork {
+        let img = DynamicImage::ImageRgb8(image::RgbImage::from_pixel(
+            width,
+            height,
+            image::Rgb([0, 128, 255]),
+        ));
+        let mut buffer = Vec::new();
+        img.write_to(&mut Cursor::new(&mut buffer), format).unwrap();
+
+        ExtractedArtwork {
+            data: Bytes::from(buffer),
+            mime_type: format.to_mime_type().to_string(),
+            picture_type: ArtworkType::CoverFront,
+            description: None,
+            width: None,
+            height: None,
+        }
+    }
+
+    #[test]
+    fn test_artwork_within_limits_is_kept() {
+        let extractor = MetadataExtractor::new();
+        let original = encoded_artwork(64, 32, ImageFormat::Png);
+
+        let artwork = extractor.apply_artwork_limits(original.clone()).unwrap();
+        assert_eq!(artwork.data, original.data);
+        assert_eq!((artwork.width, artwork.height), (Some(64), Some(32)));
+    }
+
+    #[test]
+    fn test_oversized_artwork_is_downscaled() {
+        let extractor = MetadataExtractor::new().with_artwork_limits(ArtworkLimits {
+            max_edge: 100,
+            ..ArtworkLimits::default()
+        });
+
+        let png = extractor
+            .apply_artwork_limits(encoded_artwork(400, 200, ImageFormat::Png))
+            .unwrap();
+        assert_eq!((png.width, png.height), (Some(100), Some(50)));
+        assert_eq!(png.mime_type, "image/png");
+        let decoded = image::load_from_memory(&png.data).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (100, 50));
+
+        let jpeg = extractor
+            .apply_artwork_limits(encoded_artwork(300, 300, ImageFormat::Jpeg))
+            .unwrap();
+        assert_eq!((jpeg.width, jpeg.height), (Some(100), Some(100)));
+        assert_eq!(jpeg.mime_type, "image/jpeg");
+    }
+
+    #[test]
+    fn test_pathological_artwork_is_rejected() {
+        let extractor = MetadataExtractor::new().with_artwork_limits(ArtworkLimits {
+            max_bytes: 64 * 1024,
+            max_dimension: 4000,
+            max_edge: 100,
+        });
+
+        // A BMP header claiming 50000x50000 pixels; only the header is ever read
+        let mut artwork = encoded_artwork(1, 1, ImageFormat::Bmp);
+        let mut data = artwork.data.to_vec();
+        data[18..22].copy_from_slice(&50_000i32.to_le_bytes());
+        data[22..26].copy_from_slice(&50_000i32.to_le_bytes());
+        artwork.data = Bytes::from(data);
+        let result = extractor.apply_artwork_limits(artwork);
+        assert!(
+            matches!(result, Err(MetadataError::ArtworkTooLarge(ref msg)) if msg.contains("50000x50000")),
+            "got {:?}",
+            result
+        );
+
+        let mut artwork = encoded_artwork(1, 1, ImageFormat::Png);
+        artwork.data = Bytes::from(vec![0u8; 64 * 1024 + 1]);
+        assert!(matches!(
+            extractor.apply_artwork_limits(artwork),
+            Err(MetadataError::ArtworkTooLarge(_))
+        ));
+    }
+
     // Integration tests with actual audio files would go in tests/ directory
 }
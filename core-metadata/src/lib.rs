@@ -13,16 +13,28 @@
 //! - Background enrichment jobs for batch processing
 
 pub mod artwork;
+pub mod artwork_resolver;
 pub mod enrichment_job;
 pub mod enrichment_service;
 pub mod error;
 pub mod extractor;
 pub mod lyrics;
+pub mod normalizer;
 pub mod providers;
+pub(crate) mod rate_limit;
+pub mod request_policy;
 
 pub use artwork::{ArtworkService, ArtworkSize, ProcessedArtwork};
+pub use artwork_resolver::{ArtworkRequest, ArtworkResolver, ArtworkSource, ResolvedArtwork};
 pub use enrichment_job::{EnrichmentConfig, EnrichmentJob, EnrichmentProgress, EnrichmentResult};
 pub use enrichment_service::{EnrichmentRequest, EnrichmentResponse, EnrichmentService};
-pub use error::{MetadataError, Result};
-pub use extractor::{ArtworkType, ExtractedArtwork, ExtractedMetadata, MetadataExtractor};
-pub use lyrics::{LyricsProvider, LyricsResult, LyricsSearchQuery, LyricsService, LyricsSource};
+pub use error::{MetadataError, Result, SkipReason};
+pub use extractor::{
+    ArtworkLimits, ArtworkType, ExtractedArtwork, ExtractedMetadata, FieldLimits, MetadataExtractor,
+};
+pub use lyrics::{
+    AlignmentConfig, LrcAlignment, LyricsProvider, LyricsResult, LyricsSearchQuery, LyricsService,
+    LyricsSource,
+};
+pub use normalizer::{NormalizationRules, NormalizedTitle, TitleNormalizer};
+pub use request_policy::RequestPolicy;
@@ -207,6 +207,185 @@ pub trait LyricsProvider: Send + Sync {
     }
 }
 
+// =============================================================================
+// LRC Alignment
+// =============================================================================
+
+/// Settings for validating synced lyrics against the track duration
+#[derive(Debug, Clone)]
+pub struct AlignmentConfig {
+    /// Apply the LRC `[offset:]` tag to every timestamp and drop the tag
+    pub apply_offset: bool,
+    /// How far the last line may run past the end of the track, as a
+    /// fraction of the duration, before the lyrics are rejected instead of
+    /// clamped
+    pub max_overrun_ratio: f64,
+}
+
+impl Default for AlignmentConfig {
+    fn default() -> Self {
+        Self {
+            apply_offset: true,
+            max_overrun_ratio: 0.1,
+        }
+    }
+}
+
+/// Outcome of aligning LRC text against a track duration
+#[derive(Debug, Clone, PartialEq)]
+pub struct LrcAlignment {
+    /// LRC text after adjustment (unchanged if nothing was adjusted)
+    pub text: String,
+    /// Number of lines whose timestamps were clamped to the track duration
+    pub clamped_lines: usize,
+    /// Offset applied from the `[offset:]` tag, in milliseconds
+    pub offset_ms: i64,
+}
+
+impl LrcAlignment {
+    /// Whether the lyrics were modified to fit the track
+    pub fn is_adjusted(&self) -> bool {
+        self.clamped_lines > 0 || self.offset_ms != 0
+    }
+}
+
+/// Check LRC timestamps against the track duration and fix what can be fixed
+///
+/// Lines past the end of the track are clamped to `duration_ms`. Lyrics whose
+/// timeline overruns the track by more than `max_overrun_ratio`, or that
+/// carry no timestamps at all, are rejected with `ValidationError`.
+pub fn align_lrc(text: &str, duration_ms: u64, config: &AlignmentConfig) -> Result<LrcAlignment> {
+    let offset_ms = if config.apply_offset {
+        text.lines().find_map(parse_lrc_offset).unwrap_or(0)
+    } else {
+        0
+    };
+
+    let mut lines: Vec<(Vec<u64>, &str)> = Vec::new();
+    let mut last_timestamp = None;
+    for line in text.lines() {
+        if config.apply_offset && parse_lrc_offset(line).is_some() {
+            continue;
+        }
+
+        let (timestamps, lyric) = split_lrc_timestamps(line);
+        let timestamps: Vec<u64> = timestamps
+            .into_iter()
+            .map(|t| (t as i64 - offset_ms).max(0) as u64)
+            .collect();
+        if let Some(&max) = timestamps.iter().max() {
+            last_timestamp = Some(last_timestamp.map_or(max, |last: u64| last.max(max)));
+        }
+        lines.push((timestamps, lyric));
+    }
+
+    let last_timestamp = last_timestamp.ok_or_else(|| {
+        MetadataError::ValidationError("Synced lyrics contain no timestamps".to_string())
+    })?;
+
+    let max_allowed = duration_ms as f64 * (1.0 + config.max_overrun_ratio);
+    if last_timestamp as f64 > max_allowed {
+        return Err(MetadataError::ValidationError(format!(
+            "Lyrics timeline ({}ms) does not fit track duration ({}ms)",
+            last_timestamp, duration_ms
+        )));
+    }
+
+    let mut clamped_lines = 0;
+    for (timestamps, _) in &mut lines {
+        if timestamps.iter().any(|&t| t > duration_ms) {
+            clamped_lines += 1;
+            for t in timestamps.iter_mut() {
+                *t = (*t).min(duration_ms);
+            }
+        }
+    }
+
+    if clamped_lines == 0 && offset_ms == 0 {
+        return Ok(LrcAlignment {
+            text: text.to_string(),
+            clamped_lines,
+            offset_ms,
+        });
+    }
+
+    let text = lines
+        .iter()
+        .map(|(timestamps, lyric)| {
+            let mut line: String = timestamps
+                .iter()
+                .map(|&t| format_lrc_timestamp(t))
+                .collect();
+            line.push_str(lyric);
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Ok(LrcAlignment {
+        text,
+        clamped_lines,
+        offset_ms,
+    })
+}
+
+/// Split leading `[mm:ss.xx]` tags off an LRC line
+///
+/// Non-timestamp tags such as `[ar:Artist]` are left in the returned text.
+fn split_lrc_timestamps(line: &str) -> (Vec<u64>, &str) {
+    let mut timestamps = Vec::new();
+    let mut rest = line;
+
+    while let Some(tag) = rest.strip_prefix('[') {
+        let Some(end) = tag.find(']') else { break };
+        let Some(ms) = parse_lrc_timestamp(&tag[..end]) else {
+            break;
+        };
+        timestamps.push(ms);
+        rest = &tag[end + 1..];
+    }
+
+    if timestamps.is_empty() {
+        (timestamps, line)
+    } else {
+        (timestamps, rest)
+    }
+}
+
+/// Parse `mm:ss`, `mm:ss.xx` or `mm:ss.xxx` into milliseconds
+fn parse_lrc_timestamp(tag: &str) -> Option<u64> {
+    let (minutes, seconds) = tag.split_once(':')?;
+    if minutes.is_empty() || !minutes.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    if seconds.is_empty() || !seconds.chars().all(|c| c.is_ascii_digit() || c == '.') {
+        return None;
+    }
+
+    let minutes: u64 = minutes.parse().ok()?;
+    let seconds: f64 = seconds.parse().ok()?;
+    Some(minutes * 60_000 + (seconds * 1000.0).round() as u64)
+}
+
+/// Parse an `[offset:+/-ms]` tag line
+fn parse_lrc_offset(line: &str) -> Option<i64> {
+    let inner = line.trim().strip_prefix('[')?.strip_suffix(']')?;
+    let (key, value) = inner.split_once(':')?;
+    if !key.trim().eq_ignore_ascii_case("offset") {
+        return None;
+    }
+    value.trim().trim_start_matches('+').parse().ok()
+}
+
+fn format_lrc_timestamp(ms: u64) -> String {
+    format!(
+        "[{:02}:{:02}.{:02}]",
+        ms / 60_000,
+        (ms / 1000) % 60,
+        (ms % 1000) / 10
+    )
+}
+
 // =============================================================================
 // Lyrics Service
 // =============================================================================
@@ -216,6 +395,7 @@ pub struct LyricsService {
     providers: Vec<Box<dyn LyricsProvider>>,
     repository: Arc<dyn LyricsRepository>,
     retry_config: RetryConfig,
+    alignment: AlignmentConfig,
 }
 
 impl LyricsService {
@@ -248,6 +428,7 @@ impl LyricsService {
             providers,
             repository,
             retry_config: RetryConfig::default(),
+            alignment: AlignmentConfig::default(),
         }
     }
 
@@ -257,9 +438,16 @@ impl LyricsService {
             providers: Vec::new(),
             repository,
             retry_config: RetryConfig::default(),
+            alignment: AlignmentConfig::default(),
         }
     }
 
+    /// Set how synced lyrics are validated against the track duration
+    pub fn with_alignment_config(mut self, alignment: AlignmentConfig) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
     /// Fetch lyrics from providers or cache
     ///
     /// This method:
@@ -296,7 +484,30 @@ impl LyricsService {
             );
 
             match self.fetch_with_retry(provider.as_ref(), query).await {
-                Ok(Some(result)) => {
+                Ok(Some(mut result)) => {
+                    if let (true, Some(duration)) = (result.is_synced, query.duration) {
+                        match align_lrc(&result.text, duration as u64 * 1000, &self.alignment) {
+                            Ok(alignment) => {
+                                if alignment.is_adjusted() {
+                                    debug!(
+                                        clamped_lines = alignment.clamped_lines,
+                                        offset_ms = alignment.offset_ms,
+                                        "Adjusted synced lyrics to track duration"
+                                    );
+                                }
+                                result.text = alignment.text;
+                            }
+                            Err(e) => {
+                                warn!(
+                                    source = %provider.source().as_str(),
+                                    error = %e,
+                                    "Rejected misaligned synced lyrics"
+                                );
+                                continue;
+                            }
+                        }
+                    }
+
                     info!(
                         source = %provider.source().as_str(),
                         synced = result.is_synced,
@@ -378,6 +589,39 @@ impl LyricsService {
         }))
     }
 
+    /// Validate and persist lyrics for a track of known duration
+    ///
+    /// Synced lyrics are aligned first and `lyrics.body` is replaced with the
+    /// adjusted text; the alignment report is returned so callers can surface
+    /// it. Lyrics that fail alignment are not stored.
+    pub async fn store_lyrics(
+        &self,
+        lyrics: &mut Lyrics,
+        duration_ms: u64,
+    ) -> Result<Option<LrcAlignment>> {
+        let alignment = if lyrics.synced != 0 && duration_ms > 0 {
+            let alignment = align_lrc(&lyrics.body, duration_ms, &self.alignment)?;
+            lyrics.body = alignment.text.clone();
+            Some(alignment)
+        } else {
+            None
+        };
+
+        let existing = self
+            .repository
+            .find_by_track_id(&lyrics.track_id)
+            .await
+            .map_err(|e| MetadataError::Database(format!("Failed to look up lyrics: {}", e)))?;
+        let stored = if existing.is_some() {
+            self.repository.update(lyrics).await
+        } else {
+            self.repository.insert(lyrics).await
+        };
+        stored.map_err(|e| MetadataError::Database(format!("Failed to store lyrics: {}", e)))?;
+
+        Ok(alignment)
+    }
+
     /// Update existing lyrics in database
     pub async fn update_lyrics(&self, lyrics: &Lyrics) -> Result<()> {
         self.repository
@@ -461,26 +705,76 @@ impl RetryConfig {
 #[cfg(feature = "lyrics")]
 mod providers {
     use super::*;
-    use bridge_traits::http::{HttpMethod, HttpRequest};
+    use crate::rate_limit::RateLimiter;
+    use bridge_traits::http::{HttpMethod, HttpRequest, HttpResponse};
+    use bridge_traits::time::SystemClock;
+    use core_async::sync::Mutex;
+
+    /// Minimum interval between LRCLib requests
+    const LRCLIB_RATE_LIMIT_MS: u64 = 200;
+
+    /// Largest duration difference accepted for a search result, in seconds
+    ///
+    /// Matches LRCLib's own tolerance for `/get` lookups.
+    const LRCLIB_DURATION_TOLERANCE_SECS: f64 = 2.0;
 
     /// LRCLib provider - Free, open-source synced lyrics
+    ///
+    /// Tries an exact `/get` lookup first and falls back to `/search`, picking
+    /// the result closest to the track duration and preferring timed (LRC)
+    /// lyrics. Requests are spaced out by a rate limiter.
     pub struct LrcLibProvider {
         http_client: Arc<dyn HttpClient>,
         base_url: String,
+        rate_limiter: Arc<Mutex<RateLimiter>>,
     }
 
     impl LrcLibProvider {
         pub fn new(http_client: Arc<dyn HttpClient>) -> Self {
+            Self::with_rate_limit(http_client, LRCLIB_RATE_LIMIT_MS)
+        }
+
+        /// Create a provider with a custom minimum delay between requests
+        pub fn with_rate_limit(http_client: Arc<dyn HttpClient>, rate_limit_delay_ms: u64) -> Self {
             Self {
                 http_client,
                 base_url: "https://lrclib.net/api".to_string(),
+                rate_limiter: Arc::new(Mutex::new(RateLimiter::new(
+                    rate_limit_delay_ms,
+                    Arc::new(SystemClock),
+                ))),
             }
         }
-    }
 
-    #[async_trait]
-    impl LyricsProvider for LrcLibProvider {
-        async fn fetch(&self, query: &LyricsSearchQuery) -> Result<Option<LyricsResult>> {
+        /// Use a different API base URL (e.g. a self-hosted LRCLib instance)
+        pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+            self.base_url = base_url.into();
+            self
+        }
+
+        /// Send a GET request, returning `None` on 404
+        async fn get(&self, url: &str) -> Result<Option<HttpResponse>> {
+            self.rate_limiter.lock().await.wait_if_needed().await;
+
+            let request = HttpRequest::new(HttpMethod::Get, url);
+            let response = self.http_client.execute(request).await?;
+
+            if response.status == 404 {
+                return Ok(None);
+            }
+
+            if response.status != 200 {
+                return Err(MetadataError::LyricsFetchFailed(format!(
+                    "LRCLib API error: HTTP {}",
+                    response.status
+                )));
+            }
+
+            Ok(Some(response))
+        }
+
+        /// Exact lookup by artist, title and (optionally) album and duration
+        async fn get_exact(&self, query: &LyricsSearchQuery) -> Result<Option<LrcLibRecord>> {
             let mut url = format!(
                 "{}/get?artist_name={}&track_name={}",
                 self.base_url,
@@ -496,49 +790,69 @@ mod providers {
                 url.push_str(&format!("&duration={}", duration));
             }
 
-            let request = HttpRequest::new(HttpMethod::Get, &url);
-            let response = self.http_client.execute(request).await?;
-
-            if response.status == 404 {
-                return Ok(None);
+            match self.get(&url).await? {
+                Some(response) => response
+                    .json()
+                    .map(Some)
+                    .map_err(|e| MetadataError::LyricsFetchFailed(format!("Parse error: {}", e))),
+                None => Ok(None),
             }
+        }
 
-            if response.status != 200 {
-                return Err(MetadataError::LyricsFetchFailed(format!(
-                    "LRCLib API error: HTTP {}",
-                    response.status
-                )));
-            }
+        /// Fuzzy search by artist and title, keeping the best match
+        async fn search(&self, query: &LyricsSearchQuery) -> Result<Option<LrcLibRecord>> {
+            let url = format!(
+                "{}/search?artist_name={}&track_name={}",
+                self.base_url,
+                urlencoding::encode(&query.artist),
+                urlencoding::encode(&query.track)
+            );
 
-            let lrc_response: LrcLibResponse = response
+            let Some(response) = self.get(&url).await? else {
+                return Ok(None);
+            };
+            let records: Vec<LrcLibRecord> = response
                 .json()
                 .map_err(|e| MetadataError::LyricsFetchFailed(format!("Parse error: {}", e)))?;
 
-            // Prefer synced lyrics if available
-            if let Some(synced_lyrics) = lrc_response.synced_lyrics {
-                if !synced_lyrics.is_empty() {
-                    return Ok(Some(LyricsResult::new(
-                        synced_lyrics,
-                        true,
-                        LyricsSource::LrcLib,
-                        None,
-                    )));
-                }
-            }
+            Ok(Self::best_match(records, query.duration))
+        }
 
-            // Fall back to plain lyrics
-            if let Some(plain_lyrics) = lrc_response.plain_lyrics {
-                if !plain_lyrics.is_empty() {
-                    return Ok(Some(LyricsResult::new(
-                        plain_lyrics,
-                        false,
-                        LyricsSource::LrcLib,
-                        None,
-                    )));
+        /// Pick the search result to use
+        ///
+        /// Results whose duration is off by more than the tolerance are
+        /// dropped; among the rest, synced lyrics win, then the closest
+        /// duration.
+        fn best_match(records: Vec<LrcLibRecord>, duration: Option<u32>) -> Option<LrcLibRecord> {
+            let distance = |record: &LrcLibRecord| match (duration, record.duration) {
+                (Some(expected), Some(actual)) => (actual - f64::from(expected)).abs(),
+                _ => 0.0,
+            };
+
+            records
+                .into_iter()
+                .filter(|record| record.has_lyrics())
+                .filter(|record| distance(record) <= LRCLIB_DURATION_TOLERANCE_SECS)
+                .min_by(|a, b| {
+                    b.is_synced()
+                        .cmp(&a.is_synced())
+                        .then(distance(a).total_cmp(&distance(b)))
+                })
+        }
+    }
+
+    #[async_trait]
+    impl LyricsProvider for LrcLibProvider {
+        async fn fetch(&self, query: &LyricsSearchQuery) -> Result<Option<LyricsResult>> {
+            let record = match self.get_exact(query).await? {
+                Some(record) if record.has_lyrics() => Some(record),
+                _ => {
+                    debug!("No exact LRCLib match, searching");
+                    self.search(query).await?
                 }
-            }
+            };
 
-            Ok(None)
+            Ok(record.and_then(LrcLibRecord::into_result))
         }
 
         fn source(&self) -> LyricsSource {
@@ -547,11 +861,49 @@ mod providers {
     }
 
     #[derive(Debug, Deserialize)]
-    struct LrcLibResponse {
+    struct LrcLibRecord {
         #[serde(rename = "syncedLyrics")]
         synced_lyrics: Option<String>,
         #[serde(rename = "plainLyrics")]
         plain_lyrics: Option<String>,
+        /// Track duration in seconds
+        #[serde(default)]
+        duration: Option<f64>,
+    }
+
+    impl LrcLibRecord {
+        fn is_synced(&self) -> bool {
+            self.synced_lyrics
+                .as_ref()
+                .is_some_and(|text| !text.is_empty())
+        }
+
+        /// Whether the record has any lyrics (instrumentals have none)
+        fn has_lyrics(&self) -> bool {
+            self.is_synced()
+                || self
+                    .plain_lyrics
+                    .as_ref()
+                    .is_some_and(|text| !text.is_empty())
+        }
+
+        /// Convert to a result, preferring synced lyrics
+        fn into_result(self) -> Option<LyricsResult> {
+            if let Some(synced_lyrics) = self.synced_lyrics.filter(|text| !text.is_empty()) {
+                return Some(LyricsResult::new(
+                    synced_lyrics,
+                    true,
+                    LyricsSource::LrcLib,
+                    None,
+                ));
+            }
+
+            self.plain_lyrics
+                .filter(|text| !text.is_empty())
+                .map(|plain_lyrics| {
+                    LyricsResult::new(plain_lyrics, false, LyricsSource::LrcLib, None)
+                })
+        }
     }
 
     /// Musixmatch provider - Commercial lyrics (requires API key)
@@ -839,6 +1191,10 @@ mod tests {
             artwork_id: None,
             lyrics_status: "not_fetched".to_string(), // Valid value: 'not_fetched', 'fetching', 'available', 'unavailable'
             provider_modified_at: Some(now),
+            leading_silence_ms: None,
+            trailing_silence_ms: None,
+            replay_gain_millibels: None,
+            true_peak_millibels: None,
             created_at: now,
             updated_at: now,
         };
@@ -1044,4 +1400,72 @@ mod tests {
         let fetched = repository.find_by_track_id("track-1").await.unwrap();
         assert!(fetched.is_none());
     }
+
+    #[test]
+    fn test_align_lrc_applies_offset_tag() {
+        let text = "[ar:Artist]\n[offset:+500]\n[00:01.00]First\n[00:02.50]Second";
+        let alignment = align_lrc(text, 10_000, &AlignmentConfig::default()).unwrap();
+
+        assert_eq!(alignment.offset_ms, 500);
+        assert_eq!(alignment.clamped_lines, 0);
+        assert_eq!(
+            alignment.text,
+            "[ar:Artist]\n[00:00.50]First\n[00:02.00]Second"
+        );
+    }
+
+    #[test]
+    fn test_align_lrc_rejects_wild_mismatch() {
+        let text = "[00:10.00]First\n[03:00.00]Last";
+        let err = align_lrc(text, 60_000, &AlignmentConfig::default()).unwrap_err();
+        assert!(matches!(err, MetadataError::ValidationError(_)));
+    }
+
+    #[core_async::test]
+    async fn test_store_lyrics_clamps_lines_past_duration() {
+        let pool = create_test_pool().await.unwrap();
+        insert_test_provider(&pool).await;
+        let track_repo = SqliteTrackRepository::from_pool(pool.clone());
+        create_test_track(&track_repo, "track-1").await.unwrap();
+
+        let repository = Arc::new(SqliteLyricsRepository::from_pool(pool.clone()));
+        let service = LyricsService::without_providers(repository.clone());
+
+        // Track is 180s; the last line lands 5s past the end
+        let mut lyrics = Lyrics::new(
+            "track-1".to_string(),
+            "lrclib".to_string(),
+            true,
+            "[00:10.00]First\n[03:05.00]Last".to_string(),
+        );
+        let alignment = service
+            .store_lyrics(&mut lyrics, 180_000)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(alignment.clamped_lines, 1);
+        assert!(alignment.is_adjusted());
+
+        let stored = repository
+            .find_by_track_id("track-1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.body, "[00:10.00]First\n[03:00.00]Last");
+
+        // Far past the end is rejected and leaves the stored lyrics untouched
+        let mut broken = Lyrics::new(
+            "track-1".to_string(),
+            "lrclib".to_string(),
+            true,
+            "[00:10.00]First\n[06:00.00]Last".to_string(),
+        );
+        assert!(service.store_lyrics(&mut broken, 180_000).await.is_err());
+        let stored = repository
+            .find_by_track_id("track-1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.body, "[00:10.00]First\n[03:00.00]Last");
+    }
 }
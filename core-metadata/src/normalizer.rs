@@ -0,0 +1,359 @@
+//! Title Normalization
+//!
+//! Tags often pack extra information into the title: featured artists
+//! (`"Song (feat. X)"`) and version qualifiers (`"Song - 2011 Remaster"`,
+//! `"Song [Live]"`). Left in place they split one song into several groups
+//! and clutter the display.
+//!
+//! `TitleNormalizer` pulls those parts out according to a set of
+//! [`NormalizationRules`]:
+//! - Featured artists are returned as a separate list
+//! - Version qualifiers are moved into a `version` field
+//! - The remaining display title is cleaned up
+//!
+//! Normalization is reversible: [`NormalizedTitle::original`] keeps the
+//! title exactly as tagged.
+//!
+//! ## Usage
+//!
+//! ```
+//! use core_metadata::normalizer::TitleNormalizer;
+//!
+//! let normalized = TitleNormalizer::default().normalize("Song (feat. X) - 2011 Remaster");
+//! assert_eq!(normalized.title, "Song");
+//! assert_eq!(normalized.version.as_deref(), Some("2011 Remaster"));
+//! assert_eq!(normalized.featured_artists, vec!["X".to_string()]);
+//! ```
+
+/// Prefixes that introduce featured artists, matched case-insensitively
+const FEATURING_MARKERS: &[&str] = &["featuring ", "feat. ", "feat ", "ft. ", "ft "];
+
+/// Words that mark a bracketed or dash-separated suffix as a version qualifier
+const VERSION_KEYWORDS: &[&str] = &[
+    "remaster",
+    "remastered",
+    "version",
+    "edit",
+    "mix",
+    "remix",
+    "live",
+    "mono",
+    "stereo",
+    "acoustic",
+    "demo",
+    "instrumental",
+    "deluxe",
+];
+
+/// Separators between several featured artists
+const ARTIST_SEPARATORS: &[&str] = &[", ", " & ", " and ", " x "];
+
+/// Which normalization rules are applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NormalizationRules {
+    /// Move `feat.`/`ft.`/`featuring` credits into the featured artist list
+    pub extract_featured: bool,
+    /// Move remaster, live, edit and similar qualifiers into `version`
+    pub extract_version: bool,
+}
+
+impl NormalizationRules {
+    /// Rules that leave titles untouched
+    pub fn none() -> Self {
+        Self {
+            extract_featured: false,
+            extract_version: false,
+        }
+    }
+}
+
+impl Default for NormalizationRules {
+    fn default() -> Self {
+        Self {
+            extract_featured: true,
+            extract_version: true,
+        }
+    }
+}
+
+/// Result of normalizing a title
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NormalizedTitle {
+    /// Cleaned display title
+    pub title: String,
+    /// Featured artists credited in the title, in order
+    pub featured_artists: Vec<String>,
+    /// Version qualifier, e.g. "2011 Remaster" or "Live"
+    pub version: Option<String>,
+    /// Title exactly as it was tagged
+    pub original: String,
+}
+
+impl NormalizedTitle {
+    /// Whether any rule changed the title
+    pub fn is_changed(&self) -> bool {
+        self.title != self.original
+    }
+}
+
+/// Rule-driven title normalizer
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TitleNormalizer {
+    rules: NormalizationRules,
+}
+
+impl TitleNormalizer {
+    /// Create a normalizer applying `rules`
+    pub fn new(rules: NormalizationRules) -> Self {
+        Self { rules }
+    }
+
+    /// Rules this normalizer applies
+    pub fn rules(&self) -> NormalizationRules {
+        self.rules
+    }
+
+    /// Split a track title into display title, featured artists and version
+    ///
+    /// Falls back to the original title if the rules would leave it empty.
+    pub fn normalize(&self, raw: &str) -> NormalizedTitle {
+        let original = raw.to_string();
+        let mut title = raw.trim().to_string();
+        let mut featured_artists = Vec::new();
+        let mut versions = Vec::new();
+
+        // "Song - 2011 Remaster"
+        let mut dash_version = None;
+        if self.rules.extract_version {
+            if let Some((head, tail)) = title.rsplit_once(" - ") {
+                if is_version_qualifier(tail) {
+                    dash_version = Some(tail.trim().to_string());
+                    title = head.trim_end().to_string();
+                }
+            }
+        }
+
+        // "Song (feat. X) [Live]"
+        let mut kept = String::with_capacity(title.len());
+        let mut rest = title.as_str();
+        while let Some((start, open, close)) = find_bracket(rest) {
+            let Some(end) = rest[start + 1..].find(close).map(|i| start + 1 + i) else {
+                break;
+            };
+            let inner = rest[start + 1..end].trim();
+
+            let featuring = self
+                .rules
+                .extract_featured
+                .then(|| featuring_names(inner))
+                .flatten();
+            let consumed = if let Some(names) = featuring {
+                featured_artists.extend(names);
+                true
+            } else if self.rules.extract_version && is_version_qualifier(inner) {
+                versions.push(inner.to_string());
+                true
+            } else {
+                false
+            };
+
+            kept.push_str(&rest[..start]);
+            if !consumed {
+                kept.push(open);
+                kept.push_str(&rest[start + 1..end]);
+                kept.push(close);
+            }
+            rest = &rest[end + 1..];
+        }
+        kept.push_str(rest);
+        title = kept;
+
+        // "Song feat. X"
+        if self.rules.extract_featured {
+            let (head, names) = split_featuring(&title);
+            featured_artists.extend(names);
+            title = head;
+        }
+
+        versions.extend(dash_version);
+        let title = collapse_whitespace(&title);
+        if title.is_empty() {
+            return NormalizedTitle {
+                title: collapse_whitespace(&original),
+                featured_artists: Vec::new(),
+                version: None,
+                original,
+            };
+        }
+
+        NormalizedTitle {
+            title,
+            featured_artists,
+            version: (!versions.is_empty()).then(|| versions.join(", ")),
+            original,
+        }
+    }
+
+    /// Split an artist credit such as `"A feat. B"` into the main artist and
+    /// featured artists
+    pub fn normalize_artist(&self, raw: &str) -> (String, Vec<String>) {
+        if !self.rules.extract_featured {
+            return (raw.to_string(), Vec::new());
+        }
+
+        let (main, featured) = split_featuring(raw);
+        let main = collapse_whitespace(&main);
+        if main.is_empty() {
+            (raw.to_string(), Vec::new())
+        } else {
+            (main, featured)
+        }
+    }
+}
+
+/// First `(` or `[` in `text`, with its byte offset and closing bracket
+fn find_bracket(text: &str) -> Option<(usize, char, char)> {
+    text.char_indices().find_map(|(i, c)| match c {
+        '(' => Some((i, '(', ')')),
+        '[' => Some((i, '[', ']')),
+        _ => None,
+    })
+}
+
+/// Names credited by `text` if it starts with a featuring marker
+fn featuring_names(text: &str) -> Option<Vec<String>> {
+    // ASCII lowercasing keeps byte offsets aligned with `text`
+    let lower = text.to_ascii_lowercase();
+    FEATURING_MARKERS
+        .iter()
+        .find(|marker| lower.starts_with(*marker))
+        .map(|marker| split_artists(&text[marker.len()..]))
+}
+
+/// Cut an unbracketed featuring credit off the end of `text`
+fn split_featuring(text: &str) -> (String, Vec<String>) {
+    let lower = text.to_ascii_lowercase();
+    let found = FEATURING_MARKERS
+        .iter()
+        .filter_map(|marker| {
+            lower
+                .find(&format!(" {}", marker))
+                .map(|i| (i, marker.len() + 1))
+        })
+        .min_by_key(|(i, _)| *i);
+
+    match found {
+        Some((start, marker_len)) => (
+            text[..start].to_string(),
+            split_artists(&text[start + marker_len..]),
+        ),
+        None => (text.to_string(), Vec::new()),
+    }
+}
+
+/// Split a list of artist names on the usual separators
+fn split_artists(text: &str) -> Vec<String> {
+    let mut names = vec![text.to_string()];
+    for separator in ARTIST_SEPARATORS {
+        names = names
+            .iter()
+            .flat_map(|name| name.split(separator))
+            .map(str::to_string)
+            .collect();
+    }
+    names
+        .iter()
+        .map(|name| collapse_whitespace(name))
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+/// Whether `text` names a version, e.g. "2011 Remaster" or "Radio Edit"
+fn is_version_qualifier(text: &str) -> bool {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| VERSION_KEYWORDS.contains(&word))
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_featured_and_remaster_are_extracted() {
+        let normalized = TitleNormalizer::default().normalize("Song (feat. X) - 2011 Remaster");
+
+        assert_eq!(normalized.title, "Song");
+        assert_eq!(normalized.version.as_deref(), Some("2011 Remaster"));
+        assert_eq!(normalized.featured_artists, vec!["X".to_string()]);
+        assert_eq!(normalized.original, "Song (feat. X) - 2011 Remaster");
+        assert!(normalized.is_changed());
+    }
+
+    #[test]
+    fn test_bracketed_version_and_unbracketed_featuring() {
+        let normalized = TitleNormalizer::default().normalize("Track ft. A & B [Live at Wembley]");
+
+        assert_eq!(normalized.title, "Track");
+        assert_eq!(normalized.version.as_deref(), Some("Live at Wembley"));
+        assert_eq!(normalized.featured_artists, vec!["A", "B"]);
+    }
+
+    #[test]
+    fn test_unrelated_brackets_and_dashes_are_kept() {
+        let normalizer = TitleNormalizer::default();
+
+        let normalized = normalizer.normalize("Love Song (Part 2) - Interlude");
+        assert_eq!(normalized.title, "Love Song (Part 2) - Interlude");
+        assert!(normalized.version.is_none());
+        assert!(!normalized.is_changed());
+
+        // "Often" contains "ft" but is not a featuring credit
+        assert_eq!(normalizer.normalize("Often Left").title, "Often Left");
+    }
+
+    #[test]
+    fn test_rules_can_be_disabled() {
+        let versions_only = TitleNormalizer::new(NormalizationRules {
+            extract_featured: false,
+            extract_version: true,
+        });
+        let normalized = versions_only.normalize("Song (feat. X) - Radio Edit");
+        assert_eq!(normalized.title, "Song (feat. X)");
+        assert_eq!(normalized.version.as_deref(), Some("Radio Edit"));
+        assert!(normalized.featured_artists.is_empty());
+
+        let untouched = TitleNormalizer::new(NormalizationRules::none());
+        assert_eq!(
+            untouched.normalize("Song (feat. X) - Radio Edit").title,
+            "Song (feat. X) - Radio Edit"
+        );
+    }
+
+    #[test]
+    fn test_title_that_is_only_a_qualifier_is_kept() {
+        let normalized = TitleNormalizer::default().normalize("(Live)");
+        assert_eq!(normalized.title, "(Live)");
+        assert!(normalized.version.is_none());
+    }
+
+    #[test]
+    fn test_normalize_artist_credit() {
+        let normalizer = TitleNormalizer::default();
+        assert_eq!(
+            normalizer.normalize_artist("Main Artist featuring Guest One, Guest Two"),
+            (
+                "Main Artist".to_string(),
+                vec!["Guest One".to_string(), "Guest Two".to_string()]
+            )
+        );
+        assert_eq!(
+            normalizer.normalize_artist("Simon & Garfunkel"),
+            ("Simon & Garfunkel".to_string(), Vec::new())
+        );
+    }
+}
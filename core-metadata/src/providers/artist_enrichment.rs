@@ -39,9 +39,11 @@
 //! ```
 
 use crate::error::{MetadataError, Result};
+use crate::rate_limit::{ConcurrencyLimiter, RateLimiter, DEFAULT_MAX_CONCURRENT_REQUESTS};
 use bridge_traits::http::{HttpClient, HttpMethod, HttpRequest};
 use bridge_traits::time::{Clock, SystemClock};
 use core_async::sync::Mutex;
+use core_runtime::config::MetadataApiConfig;
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -76,38 +78,7 @@ pub struct ArtistEnrichmentProvider {
     http_client: Arc<dyn HttpClient>,
     user_agent: String,
     rate_limiter: Arc<Mutex<RateLimiter>>,
-}
-
-/// Simple rate limiter to enforce delay between requests
-struct RateLimiter {
-    clock: Arc<dyn Clock>,
-    last_request_ms: Option<i64>,
-    min_delay: Duration,
-}
-
-impl RateLimiter {
-    fn new(delay_ms: u64, clock: Arc<dyn Clock>) -> Self {
-        Self {
-            clock,
-            last_request_ms: None,
-            min_delay: Duration::from_millis(delay_ms),
-        }
-    }
-
-    async fn wait_if_needed(&mut self) {
-        if let Some(last) = self.last_request_ms {
-            let now = self.clock.unix_timestamp_millis();
-            let elapsed_ms = now - last;
-            let required_ms = self.min_delay.as_millis() as i64;
-            if elapsed_ms < required_ms {
-                let wait_ms = (required_ms - elapsed_ms) as u64;
-                let wait_time = Duration::from_millis(wait_ms);
-                debug!("Rate limiting: waiting {:?}", wait_time);
-                core_async::time::sleep(wait_time).await;
-            }
-        }
-        self.last_request_ms = Some(self.clock.unix_timestamp_millis());
-    }
+    concurrency: ConcurrencyLimiter,
 }
 
 /// MusicBrainz artist search response
@@ -180,9 +151,30 @@ impl ArtistEnrichmentProvider {
             http_client,
             user_agent,
             rate_limiter: Arc::new(Mutex::new(RateLimiter::new(rate_limit_delay_ms, clock))),
+            concurrency: ConcurrencyLimiter::new(DEFAULT_MAX_CONCURRENT_REQUESTS),
         }
     }
 
+    /// Create a provider from the runtime metadata API configuration
+    ///
+    /// Returns `None` when no MusicBrainz user agent is configured.
+    pub fn from_api_config(
+        http_client: Arc<dyn HttpClient>,
+        config: &MetadataApiConfig,
+    ) -> Option<Self> {
+        let user_agent = config.musicbrainz_user_agent.clone()?;
+        Some(
+            Self::new(http_client, user_agent, config.rate_limit_delay_ms)
+                .with_max_concurrent_requests(config.max_concurrent_requests),
+        )
+    }
+
+    /// Limit how many MusicBrainz requests may be in flight at once
+    pub fn with_max_concurrent_requests(mut self, max_concurrent: usize) -> Self {
+        self.concurrency = ConcurrencyLimiter::new(max_concurrent);
+        self
+    }
+
     /// Fetch artist metadata (biography, country) from MusicBrainz
     ///
     /// # Arguments
@@ -228,7 +220,8 @@ impl ArtistEnrichmentProvider {
 
         debug!("Searching for artist: {}", url);
 
-        // Wait for rate limit
+        // Wait for a free slot and the rate limit
+        let _permit = self.concurrency.acquire().await;
         self.rate_limiter.lock().await.wait_if_needed().await;
 
         // Make request
@@ -242,6 +235,8 @@ impl ArtistEnrichmentProvider {
             headers,
             body: None,
             timeout: Some(REQUEST_TIMEOUT),
+            max_retries: None,
+            content_length: None,
         };
 
         let response = self.http_client.execute(request).await.map_err(|e| {
@@ -294,7 +289,8 @@ impl ArtistEnrichmentProvider {
 
         debug!("Looking up artist details: {}", url);
 
-        // Wait for rate limit
+        // Wait for a free slot and the rate limit
+        let _permit = self.concurrency.acquire().await;
         self.rate_limiter.lock().await.wait_if_needed().await;
 
         // Make request
@@ -308,6 +304,8 @@ impl ArtistEnrichmentProvider {
             headers,
             body: None,
             timeout: Some(REQUEST_TIMEOUT),
+            max_retries: None,
+            content_length: None,
         };
 
         let response = self.http_client.execute(request).await.map_err(|e| {
@@ -404,6 +402,40 @@ impl ArtistEnrichmentProvider {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use bridge_traits::error::Result as BridgeResult;
+    use bridge_traits::http::HttpResponse;
+    use bridge_traits::DynAsyncRead;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// HTTP client that records the peak number of concurrent requests
+    #[derive(Default)]
+    struct CountingHttpClient {
+        in_flight: AtomicUsize,
+        peak: AtomicUsize,
+        total: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl HttpClient for CountingHttpClient {
+        async fn execute(&self, _request: HttpRequest) -> BridgeResult<HttpResponse> {
+            let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            self.total.fetch_add(1, Ordering::SeqCst);
+
+            core_async::time::sleep(Duration::from_millis(20)).await;
+
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok(HttpResponse {
+                status: 200,
+                headers: HashMap::new(),
+                body: bytes::Bytes::from_static(br#"{"artists":[]}"#),
+            })
+        }
+
+        async fn download_stream(&self, _url: String) -> BridgeResult<Box<DynAsyncRead>> {
+            unimplemented!()
+        }
+    }
 
     #[test]
     fn test_escape_lucene_query() {
@@ -451,4 +483,30 @@ mod tests {
         assert!(cleaned.len() <= 5003); // 5000 + "..."
         assert!(cleaned.ends_with("..."));
     }
+
+    #[core_async::test]
+    async fn test_concurrent_lookups_respect_concurrency_cap() {
+        let http_client = Arc::new(CountingHttpClient::default());
+        let provider = Arc::new(
+            ArtistEnrichmentProvider::new(http_client.clone(), "Test/1.0 (test)".to_string(), 0)
+                .with_max_concurrent_requests(2),
+        );
+
+        let handles: Vec<_> = (0..10)
+            .map(|i| {
+                let provider = Arc::clone(&provider);
+                core_async::task::spawn(async move {
+                    let _ = provider
+                        .fetch_artist_metadata(&format!("Artist {}", i))
+                        .await;
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        assert_eq!(http_client.total.load(Ordering::SeqCst), 10);
+        assert!(http_client.peak.load(Ordering::SeqCst) <= 2);
+    }
 }
@@ -36,22 +36,19 @@
 //! ```
 
 use crate::error::{MetadataError, Result};
+use crate::rate_limit::{ConcurrencyLimiter, RateLimiter, DEFAULT_MAX_CONCURRENT_REQUESTS};
+use crate::request_policy::{execute_with_policy, RequestPolicy};
 use bridge_traits::http::{HttpClient, HttpMethod, HttpRequest};
 use bridge_traits::time::{Clock, SystemClock};
 use bytes::Bytes;
 use core_async::sync::Mutex;
-use core_async::time::sleep;
 use serde::Deserialize;
 use std::sync::Arc;
-use std::time::Duration;
 use tracing::{debug, info, warn};
 
 /// Last.fm API base URL
 const LASTFM_API_BASE: &str = "https://ws.audioscrobbler.com/2.0/";
 
-/// Timeout for API requests
-const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
-
 /// Last.fm API client
 ///
 /// Handles fetching album information and artwork from Last.fm.
@@ -60,38 +57,8 @@ pub struct LastFmClient {
     http_client: Arc<dyn HttpClient>,
     api_key: String,
     rate_limiter: Arc<Mutex<RateLimiter>>,
-}
-
-/// Simple rate limiter to enforce delay between requests
-struct RateLimiter {
-    clock: Arc<dyn Clock>,
-    last_request_ms: Option<i64>,
-    min_delay: Duration,
-}
-
-impl RateLimiter {
-    fn new(delay_ms: u64, clock: Arc<dyn Clock>) -> Self {
-        Self {
-            clock,
-            last_request_ms: None,
-            min_delay: Duration::from_millis(delay_ms),
-        }
-    }
-
-    async fn wait_if_needed(&mut self) {
-        if let Some(last) = self.last_request_ms {
-            let now = self.clock.unix_timestamp_millis();
-            let elapsed_ms = now - last;
-            let required_ms = self.min_delay.as_millis() as i64;
-            if elapsed_ms < required_ms {
-                let wait_ms = (required_ms - elapsed_ms) as u64;
-                let wait_time = Duration::from_millis(wait_ms);
-                debug!("Rate limiting: waiting {:?}", wait_time);
-                sleep(wait_time).await;
-            }
-        }
-        self.last_request_ms = Some(self.clock.unix_timestamp_millis());
-    }
+    concurrency: ConcurrencyLimiter,
+    request_policy: RequestPolicy,
 }
 
 /// Last.fm album image
@@ -159,9 +126,23 @@ impl LastFmClient {
             http_client,
             api_key,
             rate_limiter: Arc::new(Mutex::new(RateLimiter::new(rate_limit_delay_ms, clock))),
+            concurrency: ConcurrencyLimiter::new(DEFAULT_MAX_CONCURRENT_REQUESTS),
+            request_policy: RequestPolicy::default(),
         }
     }
 
+    /// Limit how many Last.fm requests may be in flight at once
+    pub fn with_max_concurrent_requests(mut self, max_concurrent: usize) -> Self {
+        self.concurrency = ConcurrencyLimiter::new(max_concurrent);
+        self
+    }
+
+    /// Set the timeout and retry policy for Last.fm requests
+    pub fn with_request_policy(mut self, policy: RequestPolicy) -> Self {
+        self.request_policy = policy;
+        self
+    }
+
     /// Fetches artwork for an album
     ///
     /// Queries the Last.fm album.getInfo API to retrieve album information,
@@ -230,19 +211,22 @@ impl LastFmClient {
             artist, album
         );
 
-        // Apply rate limiting
+        // Apply concurrency and rate limiting
+        let _permit = self.concurrency.acquire().await;
         self.rate_limiter.lock().await.wait_if_needed().await;
 
         // Make request
         let request = HttpRequest::new(HttpMethod::Get, url)
             .header("User-Agent", "MusicPlatformCore/1.0")
-            .header("Accept", "application/json")
-            .timeout(REQUEST_TIMEOUT);
+            .header("Accept", "application/json");
 
-        let response =
-            self.http_client.execute(request).await.map_err(|e| {
-                MetadataError::NetworkError(format!("Last.fm request failed: {}", e))
-            })?;
+        let response = execute_with_policy(
+            self.http_client.as_ref(),
+            request,
+            &self.request_policy,
+            "Last.fm",
+        )
+        .await?;
 
         // Check status
         if !response.is_success() {
@@ -323,18 +307,21 @@ impl LastFmClient {
     async fn download_image(&self, url: &str) -> Result<Option<Bytes>> {
         debug!("Downloading image from: {}", url);
 
-        // Apply rate limiting (even for CDN requests to be respectful)
+        // Apply concurrency and rate limiting (even for CDN requests to be respectful)
+        let _permit = self.concurrency.acquire().await;
         self.rate_limiter.lock().await.wait_if_needed().await;
 
         // Make request
         let request = HttpRequest::new(HttpMethod::Get, url.to_string())
-            .header("User-Agent", "MusicPlatformCore/1.0")
-            .timeout(REQUEST_TIMEOUT);
-
-        let response =
-            self.http_client.execute(request).await.map_err(|e| {
-                MetadataError::NetworkError(format!("Image download failed: {}", e))
-            })?;
+            .header("User-Agent", "MusicPlatformCore/1.0");
+
+        let response = execute_with_policy(
+            self.http_client.as_ref(),
+            request,
+            &self.request_policy,
+            "Last.fm",
+        )
+        .await?;
 
         // Check status
         if !response.is_success() {
@@ -41,14 +41,14 @@
 //! ```
 
 use crate::error::{MetadataError, Result};
+use crate::rate_limit::{ConcurrencyLimiter, RateLimiter, DEFAULT_MAX_CONCURRENT_REQUESTS};
+use crate::request_policy::{execute_with_policy, RequestPolicy};
 use bridge_traits::http::{HttpClient, HttpMethod, HttpRequest};
 use bridge_traits::time::{Clock, SystemClock};
 use bytes::Bytes;
 use core_async::sync::Mutex;
-use core_async::time::sleep;
 use serde::Deserialize;
 use std::sync::Arc;
-use std::time::Duration;
 use tracing::{debug, info, warn};
 
 /// MusicBrainz API base URL
@@ -60,9 +60,6 @@ const COVERART_ARCHIVE_BASE: &str = "https://coverartarchive.org";
 /// Maximum number of search results to retrieve
 const MAX_SEARCH_RESULTS: u32 = 5;
 
-/// Timeout for API requests
-const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
-
 /// MusicBrainz API client
 ///
 /// Handles searching for releases and fetching cover artwork from the Cover Art Archive.
@@ -71,38 +68,8 @@ pub struct MusicBrainzClient {
     http_client: Arc<dyn HttpClient>,
     user_agent: String,
     rate_limiter: Arc<Mutex<RateLimiter>>,
-}
-
-/// Simple rate limiter to enforce delay between requests
-struct RateLimiter {
-    clock: Arc<dyn Clock>,
-    last_request_ms: Option<i64>,
-    min_delay: Duration,
-}
-
-impl RateLimiter {
-    fn new(delay_ms: u64, clock: Arc<dyn Clock>) -> Self {
-        Self {
-            clock,
-            last_request_ms: None,
-            min_delay: Duration::from_millis(delay_ms),
-        }
-    }
-
-    async fn wait_if_needed(&mut self) {
-        if let Some(last) = self.last_request_ms {
-            let now = self.clock.unix_timestamp_millis();
-            let elapsed_ms = now - last;
-            let required_ms = self.min_delay.as_millis() as i64;
-            if elapsed_ms < required_ms {
-                let wait_ms = (required_ms - elapsed_ms) as u64;
-                let wait_time = Duration::from_millis(wait_ms);
-                debug!("Rate limiting: waiting {:?}", wait_time);
-                sleep(wait_time).await;
-            }
-        }
-        self.last_request_ms = Some(self.clock.unix_timestamp_millis());
-    }
+    concurrency: ConcurrencyLimiter,
+    request_policy: RequestPolicy,
 }
 
 /// MusicBrainz release group search result
@@ -163,9 +130,23 @@ impl MusicBrainzClient {
             http_client,
             user_agent,
             rate_limiter: Arc::new(Mutex::new(RateLimiter::new(rate_limit_delay_ms, clock))),
+            concurrency: ConcurrencyLimiter::new(DEFAULT_MAX_CONCURRENT_REQUESTS),
+            request_policy: RequestPolicy::default(),
         }
     }
 
+    /// Limit how many MusicBrainz requests may be in flight at once
+    pub fn with_max_concurrent_requests(mut self, max_concurrent: usize) -> Self {
+        self.concurrency = ConcurrencyLimiter::new(max_concurrent);
+        self
+    }
+
+    /// Set the timeout and retry policy for MusicBrainz requests
+    pub fn with_request_policy(mut self, policy: RequestPolicy) -> Self {
+        self.request_policy = policy;
+        self
+    }
+
     /// Fetches cover art for an album
     ///
     /// Searches for the release group by artist and album name, then attempts to
@@ -251,18 +232,22 @@ impl MusicBrainzClient {
 
         debug!("Searching MusicBrainz: {}", url);
 
-        // Apply rate limiting
+        // Apply concurrency and rate limiting
+        let _permit = self.concurrency.acquire().await;
         self.rate_limiter.lock().await.wait_if_needed().await;
 
         // Make request
         let request = HttpRequest::new(HttpMethod::Get, url)
             .header("User-Agent", &self.user_agent)
-            .header("Accept", "application/json")
-            .timeout(REQUEST_TIMEOUT);
+            .header("Accept", "application/json");
 
-        let response = self.http_client.execute(request).await.map_err(|e| {
-            MetadataError::NetworkError(format!("MusicBrainz search failed: {}", e))
-        })?;
+        let response = execute_with_policy(
+            self.http_client.as_ref(),
+            request,
+            &self.request_policy,
+            "MusicBrainz",
+        )
+        .await?;
 
         // Check status
         if !response.is_success() {
@@ -312,18 +297,20 @@ impl MusicBrainzClient {
 
         debug!("Fetching cover art: {}", url);
 
-        // Apply rate limiting (Cover Art Archive shares rate limits with MusicBrainz)
+        // Apply concurrency and rate limiting (Cover Art Archive shares MusicBrainz limits)
+        let _permit = self.concurrency.acquire().await;
         self.rate_limiter.lock().await.wait_if_needed().await;
 
         // Make request
-        let request = HttpRequest::new(HttpMethod::Get, url)
-            .header("User-Agent", &self.user_agent)
-            .timeout(REQUEST_TIMEOUT);
-
-        let response =
-            self.http_client.execute(request).await.map_err(|e| {
-                MetadataError::NetworkError(format!("Cover art fetch failed: {}", e))
-            })?;
+        let request = HttpRequest::new(HttpMethod::Get, url).header("User-Agent", &self.user_agent);
+
+        let response = execute_with_policy(
+            self.http_client.as_ref(),
+            request,
+            &self.request_policy,
+            "MusicBrainz",
+        )
+        .await?;
 
         // Check status
         match response.status {
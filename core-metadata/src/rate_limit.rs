@@ -0,0 +1,72 @@
+//! Request Rate Limiting
+//!
+//! Minimum-interval and in-flight limiters shared by the remote metadata and
+//! lyrics clients so each stays within its API's terms of service.
+
+use bridge_traits::time::Clock;
+use core_async::sync::{Semaphore, SemaphorePermit};
+use core_async::time::sleep;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::debug;
+
+/// Simple rate limiter to enforce delay between requests
+pub(crate) struct RateLimiter {
+    clock: Arc<dyn Clock>,
+    pub(crate) last_request_ms: Option<i64>,
+    min_delay: Duration,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(delay_ms: u64, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            clock,
+            last_request_ms: None,
+            min_delay: Duration::from_millis(delay_ms),
+        }
+    }
+
+    /// Wait until `min_delay` has passed since the previous request
+    pub(crate) async fn wait_if_needed(&mut self) {
+        if let Some(last) = self.last_request_ms {
+            let now = self.clock.unix_timestamp_millis();
+            let elapsed_ms = now - last;
+            let required_ms = self.min_delay.as_millis() as i64;
+            if elapsed_ms < required_ms {
+                let wait_ms = (required_ms - elapsed_ms) as u64;
+                let wait_time = Duration::from_millis(wait_ms);
+                debug!("Rate limiting: waiting {:?}", wait_time);
+                sleep(wait_time).await;
+            }
+        }
+        self.last_request_ms = Some(self.clock.unix_timestamp_millis());
+    }
+}
+
+/// Default number of requests a single client may have in flight
+pub(crate) const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 1;
+
+/// Caps how many requests a client has in flight at once
+///
+/// `RateLimiter` only spaces out request starts; slow responses can still
+/// pile up behind it. Clones share the same permits.
+#[derive(Clone)]
+pub(crate) struct ConcurrencyLimiter {
+    semaphore: Arc<Semaphore>,
+}
+
+impl ConcurrencyLimiter {
+    pub(crate) fn new(max_concurrent: usize) -> Self {
+        Self {
+            semaphore: Arc::new(Semaphore::new(max_concurrent.max(1))),
+        }
+    }
+
+    /// Wait for a free slot; the request may proceed while the permit is held
+    pub(crate) async fn acquire(&self) -> SemaphorePermit<'_> {
+        self.semaphore
+            .acquire()
+            .await
+            .expect("concurrency limiter semaphore is never closed")
+    }
+}
@@ -0,0 +1,251 @@
+//! Request Timeouts and Retries
+//!
+//! Per-client timeout and retry settings for the remote metadata clients,
+//! built on the shared `core_async::time` combinators. A hung request is cut
+//! off after the configured timeout and retried like a transport failure;
+//! `503 Service Unavailable` is retried after waiting out `Retry-After`.
+
+use crate::error::{MetadataError, Result};
+use bridge_traits::http::{HttpClient, HttpRequest, HttpResponse};
+use core_async::time::{retry_with_backoff, sleep, timeout, RetryConfig};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// Default per-attempt timeout for metadata API requests
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Timeout and retry settings for a metadata API client
+#[derive(Debug, Clone)]
+pub struct RequestPolicy {
+    /// Time allowed for a single attempt
+    pub timeout: Duration,
+    /// Attempts and backoff between them
+    pub retry: RetryConfig,
+}
+
+impl Default for RequestPolicy {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_REQUEST_TIMEOUT,
+            retry: RetryConfig::new(3)
+                .with_initial_delay(Duration::from_secs(1))
+                .with_max_delay(Duration::from_secs(30)),
+        }
+    }
+}
+
+impl RequestPolicy {
+    /// Set the time allowed for a single attempt
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the attempts and backoff between them
+    pub fn with_retry(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+}
+
+/// Why a single attempt should be retried
+#[cfg_attr(not(feature = "artwork-remote"), allow(dead_code))]
+enum AttemptFailure {
+    TimedOut,
+    Network(String),
+    Unavailable(HttpResponse),
+}
+
+/// Send `request` under `policy`, retrying timeouts, transport errors and 503s
+///
+/// Any other response, including error statuses, is returned as-is for the
+/// caller to interpret. A 503 that is still failing after the last attempt
+/// is returned too, so callers keep their existing handling for it.
+#[cfg_attr(not(feature = "artwork-remote"), allow(dead_code))]
+pub(crate) async fn execute_with_policy(
+    http_client: &dyn HttpClient,
+    request: HttpRequest,
+    policy: &RequestPolicy,
+    provider: &str,
+) -> Result<HttpResponse> {
+    // Retries are owned by the policy, not the platform client
+    let request = request.timeout(policy.timeout).max_retries(0);
+    let max_attempts = policy.retry.max_attempts.max(1);
+    let mut attempt = 0;
+
+    let result = retry_with_backoff(
+        || {
+            attempt += 1;
+            let attempt = attempt;
+            let request = request.clone();
+            async move {
+                let response = match timeout(policy.timeout, http_client.execute(request)).await {
+                    Err(_) => {
+                        warn!(
+                            "{} request timed out after {:?} (attempt {}/{})",
+                            provider, policy.timeout, attempt, max_attempts
+                        );
+                        return Err(AttemptFailure::TimedOut);
+                    }
+                    Ok(Err(e)) => {
+                        warn!(
+                            "{} request failed (attempt {}/{}): {}",
+                            provider, attempt, max_attempts, e
+                        );
+                        return Err(AttemptFailure::Network(e.to_string()));
+                    }
+                    Ok(Ok(response)) => response,
+                };
+
+                if response.status != 503 || attempt >= max_attempts {
+                    return Ok(response);
+                }
+
+                let retry_after = retry_after(&response)
+                    .unwrap_or_default()
+                    .min(policy.retry.max_delay);
+                debug!(
+                    "{} unavailable (503), retrying after {:?} (attempt {}/{})",
+                    provider, retry_after, attempt, max_attempts
+                );
+                sleep(retry_after).await;
+                Err(AttemptFailure::Unavailable(response))
+            }
+        },
+        policy.retry.clone(),
+    )
+    .await;
+
+    match result {
+        Ok(response) => Ok(response),
+        Err(AttemptFailure::TimedOut) => Err(MetadataError::RequestTimeout {
+            provider: provider.to_string(),
+            attempts: attempt,
+            timeout_ms: policy.timeout.as_millis() as u64,
+        }),
+        Err(AttemptFailure::Network(message)) => Err(MetadataError::NetworkError(format!(
+            "{} request failed: {}",
+            provider, message
+        ))),
+        Err(AttemptFailure::Unavailable(response)) => Ok(response),
+    }
+}
+
+/// Delay requested by a `Retry-After` header given in seconds
+#[cfg_attr(not(feature = "artwork-remote"), allow(dead_code))]
+fn retry_after(response: &HttpResponse) -> Option<Duration> {
+    response
+        .headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("retry-after"))
+        .and_then(|(_, value)| value.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bridge_traits::http::HttpMethod;
+    use bridge_traits::test_util::{MockHttpClient, MockResponse};
+
+    const URL: &str = "https://api.example.com/lookup";
+
+    fn fast_policy(max_attempts: u32) -> RequestPolicy {
+        RequestPolicy::default()
+            .with_timeout(Duration::from_millis(50))
+            .with_retry(RetryConfig::new(max_attempts).with_initial_delay(Duration::from_millis(1)))
+    }
+
+    #[core_async::test]
+    async fn test_timeout_then_success_is_retried() {
+        let http = MockHttpClient::new();
+        http.stub(
+            HttpMethod::Get,
+            URL,
+            MockResponse::ok("late").with_delay(Duration::from_secs(5)),
+        )
+        .stub(HttpMethod::Get, URL, MockResponse::ok("on time"));
+
+        let response = execute_with_policy(
+            &http,
+            HttpRequest::new(HttpMethod::Get, URL),
+            &fast_policy(3),
+            "Test",
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.body, "on time");
+        assert_eq!(http.requests().len(), 2);
+    }
+
+    #[core_async::test]
+    async fn test_persistent_timeout_surfaces_timeout_error() {
+        let http = MockHttpClient::new();
+        http.stub(
+            HttpMethod::Get,
+            URL,
+            MockResponse::ok("late").with_delay(Duration::from_secs(5)),
+        );
+
+        let err = execute_with_policy(
+            &http,
+            HttpRequest::new(HttpMethod::Get, URL),
+            &fast_policy(2),
+            "Test",
+        )
+        .await
+        .unwrap_err();
+
+        assert!(matches!(
+            err,
+            MetadataError::RequestTimeout {
+                attempts: 2,
+                timeout_ms: 50,
+                ..
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Test request timed out after 2 attempt(s) of 50ms each"
+        );
+    }
+
+    #[core_async::test]
+    async fn test_service_unavailable_respects_retry_after() {
+        let http = MockHttpClient::new();
+        http.stub(
+            HttpMethod::Get,
+            URL,
+            MockResponse::new(503).with_header("Retry-After", "0"),
+        )
+        .stub(HttpMethod::Get, URL, MockResponse::ok("back"));
+
+        let response = execute_with_policy(
+            &http,
+            HttpRequest::new(HttpMethod::Get, URL),
+            &fast_policy(3),
+            "Test",
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.status, 200);
+        assert_eq!(http.requests().len(), 2);
+    }
+
+    #[test]
+    fn test_retry_after_parsing() {
+        let mut response = HttpResponse {
+            status: 503,
+            headers: Default::default(),
+            body: Default::default(),
+        };
+        assert_eq!(retry_after(&response), None);
+
+        response
+            .headers
+            .insert("retry-after".to_string(), " 7 ".to_string());
+        assert_eq!(retry_after(&response), Some(Duration::from_secs(7)));
+    }
+}
@@ -0,0 +1,142 @@
+//! Integration tests for artwork source resolution
+//!
+//! These tests verify that the resolver consults local sources before any
+//! remote API and records which source produced the artwork.
+
+#![cfg(feature = "artwork-remote")]
+
+use bridge_traits::error::{BridgeError, Result as BridgeResult};
+use bridge_traits::storage::{RemoteFile, StorageProvider};
+use bridge_traits::test_util::MockHttpClient;
+use bytes::Bytes;
+use core_library::db::create_test_pool;
+use core_library::repositories::artwork::SqliteArtworkRepository;
+use core_metadata::{ArtworkRequest, ArtworkResolver, ArtworkService, ArtworkSource};
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+
+fn remote_file(id: &str, name: &str, mime_type: &str, parent: &str) -> RemoteFile {
+    RemoteFile {
+        id: id.to_string(),
+        name: name.to_string(),
+        mime_type: Some(mime_type.to_string()),
+        size: None,
+        created_at: None,
+        modified_at: None,
+        is_folder: false,
+        parent_ids: vec![parent.to_string()],
+        md5_checksum: None,
+        metadata: HashMap::new(),
+    }
+}
+
+fn sample_png() -> Bytes {
+    let img = image::RgbImage::from_pixel(4, 4, image::Rgb([200, 40, 40]));
+    let mut buf = Cursor::new(Vec::new());
+    image::DynamicImage::ImageRgb8(img)
+        .write_to(&mut buf, image::ImageFormat::Png)
+        .unwrap();
+    Bytes::from(buf.into_inner())
+}
+
+/// Provider with a single folder holding a track and a cover image
+struct FolderProvider {
+    files: Vec<RemoteFile>,
+    cover: Bytes,
+    downloads: Mutex<Vec<String>>,
+}
+
+impl FolderProvider {
+    fn new() -> Self {
+        Self {
+            files: vec![
+                remote_file("track-1", "01 - Airbag.mp3", "audio/mpeg", "folder-1"),
+                remote_file("notes-1", "notes.txt", "text/plain", "folder-1"),
+                remote_file("cover-1", "cover.jpg", "image/png", "folder-1"),
+            ],
+            cover: sample_png(),
+            downloads: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl StorageProvider for FolderProvider {
+    async fn list_media(
+        &self,
+        _cursor: Option<String>,
+    ) -> BridgeResult<(Vec<RemoteFile>, Option<String>)> {
+        Ok((Vec::new(), None))
+    }
+
+    async fn get_metadata(&self, file_id: &str) -> BridgeResult<RemoteFile> {
+        self.files
+            .iter()
+            .find(|f| f.id == file_id)
+            .cloned()
+            .ok_or_else(|| BridgeError::OperationFailed(format!("File not found: {}", file_id)))
+    }
+
+    async fn list_folder(&self, folder_id: &str) -> BridgeResult<Vec<RemoteFile>> {
+        Ok(self
+            .files
+            .iter()
+            .filter(|f| f.parent_ids.iter().any(|p| p == folder_id))
+            .cloned()
+            .collect())
+    }
+
+    async fn download(&self, file_id: &str, _range: Option<&str>) -> BridgeResult<Bytes> {
+        self.downloads.lock().unwrap().push(file_id.to_string());
+        match file_id {
+            "cover-1" => Ok(self.cover.clone()),
+            other => Err(BridgeError::OperationFailed(format!(
+                "Unexpected download: {}",
+                other
+            ))),
+        }
+    }
+
+    async fn get_changes(
+        &self,
+        _cursor: Option<String>,
+    ) -> BridgeResult<(Vec<RemoteFile>, Option<String>)> {
+        Ok((Vec::new(), None))
+    }
+}
+
+#[core_async::test]
+async fn test_folder_image_used_before_network_sources() {
+    let pool = create_test_pool().await.unwrap();
+    sqlx::migrate!("../core-library/migrations")
+        .run(&pool)
+        .await
+        .unwrap();
+
+    // Any HTTP request panics: no stubs are registered
+    let http = Arc::new(MockHttpClient::new());
+    let artwork_service = Arc::new(ArtworkService::with_remote_fetching(
+        Arc::new(SqliteArtworkRepository::from_pool(pool)),
+        http.clone(),
+        10 * 1024 * 1024,
+        Some("mpc-tests/1.0".to_string()),
+        Some("test-key".to_string()),
+        0,
+    ));
+
+    let provider = Arc::new(FolderProvider::new());
+    let resolver = ArtworkResolver::new(artwork_service).with_storage_provider(provider.clone());
+
+    let request = ArtworkRequest::new("Radiohead", "OK Computer").with_provider_file_id("track-1");
+    let resolved = resolver
+        .resolve(&request)
+        .await
+        .unwrap()
+        .expect("folder artwork should resolve");
+
+    assert_eq!(resolved.source, ArtworkSource::Folder);
+    assert_eq!(resolved.artwork.original_width, 4);
+    assert_eq!(*provider.downloads.lock().unwrap(), vec!["cover-1"]);
+    assert!(http.requests().is_empty());
+}
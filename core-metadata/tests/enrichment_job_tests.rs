@@ -1,12 +1,19 @@
 //! Integration tests for metadata enrichment job
 
+use bridge_traits::background::{BackgroundExecutor, TaskConstraints, TaskId, TaskStatus};
+use bridge_traits::error::{BridgeError, Result as BridgeResult};
+use core_async::time::{sleep, Duration};
 use core_library::db::create_test_pool;
 use core_library::models::Track;
 use core_library::repositories::artwork::SqliteArtworkRepository;
+use core_library::repositories::enrichment::{
+    EnrichmentStatus, EnrichmentStatusRepository, SqliteEnrichmentStatusRepository,
+};
 use core_library::repositories::lyrics::SqliteLyricsRepository;
 use core_library::repositories::track::{SqliteTrackRepository, TrackRepository};
 use core_metadata::artwork::ArtworkService;
 use core_metadata::enrichment_job::{EnrichmentConfig, EnrichmentJob};
+use core_metadata::enrichment_service::EnrichmentService;
 use core_metadata::lyrics::LyricsService;
 use core_runtime::events::EventBus;
 use sqlx::SqlitePool;
@@ -51,9 +58,35 @@ fn create_test_track(id: &str, title: &str, artwork_id: Option<String>) -> Track
         created_at: 1699200000,
         updated_at: 1699200000,
         provider_modified_at: Some(1699200000),
+        leading_silence_ms: None,
+        trailing_silence_ms: None,
+        replay_gain_millibels: None,
+        true_peak_millibels: None,
     }
 }
 
+/// Create an enrichment service without remote providers
+fn create_enrichment_service(
+    pool: &SqlitePool,
+    track_repo: Arc<dyn TrackRepository>,
+) -> Arc<EnrichmentService> {
+    let artwork_repo = Arc::new(SqliteArtworkRepository::from_pool(pool.clone()));
+    let lyrics_repo = Arc::new(SqliteLyricsRepository::from_pool(pool.clone()));
+    let artist_repo = Arc::new(
+        core_library::repositories::artist::SqliteArtistRepository::from_pool(pool.clone()),
+    );
+    let album_repo =
+        Arc::new(core_library::repositories::album::SqliteAlbumRepository::from_pool(pool.clone()));
+
+    Arc::new(EnrichmentService::new(
+        artist_repo,
+        album_repo,
+        track_repo,
+        Arc::new(ArtworkService::new(artwork_repo, 200 * 1024 * 1024)),
+        Arc::new(LyricsService::without_providers(lyrics_repo)),
+    ))
+}
+
 #[core_async::test]
 async fn test_enrichment_config_builder() {
     let config = EnrichmentConfig::builder()
@@ -261,3 +294,226 @@ async fn test_enrichment_config_defaults() {
     assert_eq!(config.base_retry_delay_ms, 100);
     assert_eq!(config.operation_timeout_secs, 30);
 }
+
+#[core_async::test]
+async fn test_resume_processes_only_remaining_tracks() {
+    let pool = create_test_pool()
+        .await
+        .expect("Failed to create test pool");
+    insert_test_provider(&pool).await;
+
+    let track_repo: Arc<dyn TrackRepository> =
+        Arc::new(SqliteTrackRepository::from_pool(pool.clone()));
+    let ids: Vec<String> = (1..=4).map(|i| format!("track-{}", i)).collect();
+    for id in &ids {
+        track_repo
+            .insert(&create_test_track(id, id, None))
+            .await
+            .expect("Failed to insert track");
+    }
+
+    let enrichment_service = create_enrichment_service(&pool, track_repo.clone());
+    let status_repo = Arc::new(SqliteEnrichmentStatusRepository::from_pool(pool.clone()));
+
+    // A run queued all four tracks and was interrupted after finishing two
+    status_repo.reset(&ids).await.unwrap();
+    for id in &ids[..2] {
+        status_repo
+            .set_status(id, EnrichmentStatus::Done, None)
+            .await
+            .unwrap();
+    }
+
+    let job = EnrichmentJob::new(
+        EnrichmentConfig::default().with_max_retries(0),
+        enrichment_service,
+        track_repo,
+        Arc::new(EventBus::new(100)),
+    )
+    .with_status_repository(status_repo.clone());
+
+    let progress = job.resume().await.expect("Resume failed");
+    assert_eq!(progress.total_tracks, 2);
+    assert_eq!(progress.processed, 2);
+
+    assert!(status_repo
+        .find_by_status(EnrichmentStatus::Pending)
+        .await
+        .unwrap()
+        .is_empty());
+    let mut finished = status_repo
+        .find_by_status(EnrichmentStatus::Done)
+        .await
+        .unwrap();
+    finished.extend(
+        status_repo
+            .find_by_status(EnrichmentStatus::Failed)
+            .await
+            .unwrap(),
+    );
+    finished.sort();
+    assert_eq!(finished, ids);
+
+    // Nothing is left for a second resume
+    let progress = job.resume().await.expect("Resume failed");
+    assert_eq!(progress.total_tracks, 0);
+}
+
+#[core_async::test]
+async fn test_only_missing_artwork_skips_tracks_with_artwork() {
+    let pool = create_test_pool()
+        .await
+        .expect("Failed to create test pool");
+    insert_test_provider(&pool).await;
+
+    sqlx::query(
+        "INSERT INTO artworks (id, hash, binary_blob, mime_type, width, height, file_size, created_at)
+         VALUES ('artwork-1', 'hash123', X'FFD8FF', 'image/jpeg', 300, 300, 1024, 1699200000)",
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to insert dummy artwork");
+
+    // track-2 has artwork but no lyrics, so only lyrics would be fetched for it
+    let track_repo: Arc<dyn TrackRepository> =
+        Arc::new(SqliteTrackRepository::from_pool(pool.clone()));
+    for (id, artwork_id) in [
+        ("track-1", None),
+        ("track-2", Some("artwork-1".to_string())),
+        ("track-3", None),
+    ] {
+        track_repo
+            .insert(&create_test_track(id, id, artwork_id))
+            .await
+            .expect("Failed to insert track");
+    }
+
+    let status_repo = Arc::new(SqliteEnrichmentStatusRepository::from_pool(pool.clone()));
+    let job = EnrichmentJob::new(
+        EnrichmentConfig::default()
+            .with_only_missing_artwork(true)
+            .with_max_retries(0),
+        create_enrichment_service(&pool, track_repo.clone()),
+        track_repo,
+        Arc::new(EventBus::new(100)),
+    )
+    .with_status_repository(status_repo.clone());
+
+    let progress = job.run().await.expect("Enrichment failed");
+    assert_eq!(progress.total_tracks, 2);
+    assert_eq!(progress.processed, 2);
+
+    let mut processed = status_repo
+        .find_by_status(EnrichmentStatus::Done)
+        .await
+        .unwrap();
+    processed.extend(
+        status_repo
+            .find_by_status(EnrichmentStatus::Failed)
+            .await
+            .unwrap(),
+    );
+    processed.sort();
+    assert_eq!(processed, ["track-1", "track-3"]);
+}
+
+/// Executor that only supports `spawn`, through its default implementation
+struct SpawnOnlyExecutor;
+
+#[async_trait::async_trait]
+impl BackgroundExecutor for SpawnOnlyExecutor {
+    async fn schedule_task(
+        &self,
+        _task_id: &str,
+        _interval: Duration,
+        _constraints: TaskConstraints,
+    ) -> BridgeResult<TaskId> {
+        Err(BridgeError::NotAvailable("schedule_task".to_string()))
+    }
+
+    async fn schedule_once(
+        &self,
+        _task_id: &str,
+        _delay: Duration,
+        _constraints: TaskConstraints,
+    ) -> BridgeResult<TaskId> {
+        Err(BridgeError::NotAvailable("schedule_once".to_string()))
+    }
+
+    async fn cancel_task(&self, _task_id: &TaskId) -> BridgeResult<()> {
+        Err(BridgeError::NotAvailable("cancel_task".to_string()))
+    }
+
+    async fn get_task_status(&self, _task_id: &TaskId) -> BridgeResult<TaskStatus> {
+        Err(BridgeError::NotAvailable("get_task_status".to_string()))
+    }
+
+    async fn list_tasks(&self) -> BridgeResult<Vec<TaskId>> {
+        Ok(Vec::new())
+    }
+
+    async fn next_execution_time(&self, _task_id: &TaskId) -> BridgeResult<Option<Duration>> {
+        Ok(None)
+    }
+}
+
+#[core_async::test]
+async fn test_shutdown_cancels_spawned_run() {
+    let pool = create_test_pool()
+        .await
+        .expect("Failed to create test pool");
+    insert_test_provider(&pool).await;
+
+    let track_repo: Arc<dyn TrackRepository> =
+        Arc::new(SqliteTrackRepository::from_pool(pool.clone()));
+    for i in 1..=50 {
+        let id = format!("track-{}", i);
+        track_repo
+            .insert(&create_test_track(&id, &id, None))
+            .await
+            .expect("Failed to insert track");
+    }
+
+    let enrichment_service = create_enrichment_service(&pool, track_repo.clone());
+    let status_repo = Arc::new(SqliteEnrichmentStatusRepository::from_pool(pool.clone()));
+
+    // One track per batch, with a pause after each, keeps the run going
+    let job = Arc::new(
+        EnrichmentJob::new(
+            EnrichmentConfig::default()
+                .with_batch_size(1)
+                .with_max_retries(0),
+            enrichment_service,
+            track_repo,
+            Arc::new(EventBus::new(100)),
+        )
+        .with_status_repository(status_repo.clone()),
+    );
+
+    let handle = job.spawn_on(&SpawnOnlyExecutor);
+    sleep(Duration::from_millis(250)).await;
+    assert!(!handle.is_finished());
+
+    job.shutdown();
+    sleep(Duration::from_millis(50)).await;
+    assert!(handle.is_cancelled());
+    assert!(handle.is_finished());
+
+    let pending = status_repo
+        .find_by_status(EnrichmentStatus::Pending)
+        .await
+        .unwrap()
+        .len();
+    assert!(pending > 0, "run finished before it was cancelled");
+
+    // Nothing is processed after the cancellation
+    sleep(Duration::from_millis(300)).await;
+    assert_eq!(
+        status_repo
+            .find_by_status(EnrichmentStatus::Pending)
+            .await
+            .unwrap()
+            .len(),
+        pending
+    );
+}
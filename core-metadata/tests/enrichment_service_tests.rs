@@ -143,6 +143,10 @@ async fn create_test_track(
         created_at: 1000000,
         updated_at: 1000000,
         provider_modified_at: None,
+        leading_silence_ms: None,
+        trailing_silence_ms: None,
+        replay_gain_millibels: None,
+        true_peak_millibels: None,
     };
 
     let repo = SqliteTrackRepository::from_pool(pool.clone());
@@ -275,6 +279,10 @@ async fn test_enrich_track_artist_not_in_database() {
         created_at: 1000000,
         updated_at: 1000000,
         provider_modified_at: None,
+        leading_silence_ms: None,
+        trailing_silence_ms: None,
+        replay_gain_millibels: None,
+        true_peak_millibels: None,
     };
 
     let request = EnrichmentRequest {
@@ -330,6 +338,10 @@ async fn test_enrich_track_album_not_in_database() {
         created_at: 1000000,
         updated_at: 1000000,
         provider_modified_at: None,
+        leading_silence_ms: None,
+        trailing_silence_ms: None,
+        replay_gain_millibels: None,
+        true_peak_millibels: None,
     };
 
     let request = EnrichmentRequest {
@@ -448,6 +460,10 @@ async fn test_enrichment_request_structure() {
         created_at: 1000000,
         updated_at: 1000000,
         provider_modified_at: None,
+        leading_silence_ms: None,
+        trailing_silence_ms: None,
+        replay_gain_millibels: None,
+        true_peak_millibels: None,
     };
 
     let request = EnrichmentRequest {
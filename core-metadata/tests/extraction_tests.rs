@@ -3,9 +3,10 @@
 //! These tests verify basic error handling and API functionality.
 //! For full format testing with real audio files, see tests/fixtures/README.md
 
-use core_metadata::extractor::MetadataExtractor;
+use core_metadata::error::{MetadataError, SkipReason};
+use core_metadata::extractor::{FieldLimits, MetadataExtractor};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Helper to get the fixtures directory
 fn fixtures_dir() -> PathBuf {
@@ -49,13 +50,122 @@ async fn test_extract_corrupted_file() {
     assert!(result.is_err(), "Should fail for corrupted file");
 }
 
+#[core_async::test]
+async fn test_extract_empty_and_truncated_files() {
+    let extractor = MetadataExtractor::new();
+
+    let empty = extractor
+        .extract_from_bytes(&[], Path::new("empty.mp3"))
+        .await;
+    assert!(
+        matches!(empty, Err(MetadataError::Skipped(SkipReason::EmptyFile))),
+        "Empty file should be skipped, got {:?}",
+        empty
+    );
+
+    // Cut sample.mp3 off inside its ID3v2 tag
+    let sample = fs::read(fixtures_dir().join("sample.mp3")).expect("Failed to read fixture");
+    for len in [4, 100] {
+        let truncated = extractor
+            .extract_from_bytes(&sample[..len], Path::new("truncated.mp3"))
+            .await;
+        assert!(
+            matches!(truncated, Err(MetadataError::Truncated(_))),
+            "{}-byte prefix should be truncated, got {:?}",
+            len,
+            truncated
+        );
+    }
+
+    // The complete file still extracts
+    let full = extractor
+        .extract_from_bytes(&sample, Path::new("sample.mp3"))
+        .await;
+    assert!(full.is_ok(), "Full sample should extract: {:?}", full.err());
+}
+
+#[core_async::test]
+async fn test_extract_sanitizes_and_bounds_text_fields() {
+    use lofty::config::WriteOptions;
+    use lofty::tag::{Accessor, Tag, TagExt, TagType};
+
+    let sample = fs::read(fixtures_dir().join("sample.mp3")).expect("Failed to read fixture");
+    let mut file = std::io::Cursor::new(sample);
+
+    let mut tag = Tag::new(TagType::Id3v2);
+    tag.set_title("  Night\u{1}Drive\0\0\0".to_string());
+    tag.set_artist("Some\tArtist ".to_string());
+    tag.set_comment("x".repeat(10_000));
+    tag.save_to(&mut file, WriteOptions::default())
+        .expect("Failed to write tag");
+
+    let extractor = MetadataExtractor::new();
+    let metadata = extractor
+        .extract_from_bytes(file.get_ref(), Path::new("tagged.mp3"))
+        .await
+        .expect("Extraction should succeed");
+
+    assert_eq!(metadata.title.as_deref(), Some("Night Drive"));
+    assert_eq!(metadata.artist.as_deref(), Some("Some Artist"));
+    let comment = metadata.comment.expect("Comment should be kept");
+    assert_eq!(
+        comment.chars().count(),
+        FieldLimits::default().max_comment_chars
+    );
+    assert_eq!(metadata.truncated_fields, vec!["comment"]);
+}
+
+#[core_async::test]
+async fn test_extract_normalizes_title() {
+    use core_metadata::NormalizationRules;
+    use lofty::config::WriteOptions;
+    use lofty::tag::{Accessor, Tag, TagExt, TagType};
+
+    let sample = fs::read(fixtures_dir().join("sample.mp3")).expect("Failed to read fixture");
+    let mut file = std::io::Cursor::new(sample);
+
+    let mut tag = Tag::new(TagType::Id3v2);
+    tag.set_title("Song (feat. X) - 2011 Remaster".to_string());
+    tag.set_artist("Main Artist".to_string());
+    tag.save_to(&mut file, WriteOptions::default())
+        .expect("Failed to write tag");
+
+    let metadata = MetadataExtractor::new()
+        .extract_from_bytes(file.get_ref(), Path::new("tagged.mp3"))
+        .await
+        .expect("Extraction should succeed");
+
+    assert_eq!(metadata.title.as_deref(), Some("Song"));
+    assert_eq!(metadata.version.as_deref(), Some("2011 Remaster"));
+    assert_eq!(metadata.featured_artists, vec!["X".to_string()]);
+    assert_eq!(metadata.artist.as_deref(), Some("Main Artist"));
+    assert_eq!(
+        metadata.original_title.as_deref(),
+        Some("Song (feat. X) - 2011 Remaster")
+    );
+
+    // With normalization disabled the title is kept as tagged
+    let untouched = MetadataExtractor::new()
+        .with_title_rules(NormalizationRules::none())
+        .extract_from_bytes(file.get_ref(), Path::new("tagged.mp3"))
+        .await
+        .expect("Extraction should succeed");
+
+    assert_eq!(
+        untouched.title.as_deref(),
+        Some("Song (feat. X) - 2011 Remaster")
+    );
+    assert!(untouched.version.is_none());
+    assert!(untouched.featured_artists.is_empty());
+    assert!(untouched.original_title.is_none());
+}
+
 #[core_async::test]
 async fn test_extractor_creation() {
     let _extractor1 = MetadataExtractor::new();
     let _extractor2 = MetadataExtractor::default();
 
     // Both should be usable (just verify they compile and construct)
-    assert!(true);
 }
 
 // The following tests require actual audio files in tests/fixtures/
@@ -0,0 +1,92 @@
+//! Integration tests for the LRCLib synced-lyrics provider
+//!
+//! Responses are served from fixtures by a mock HTTP client.
+
+#![cfg(feature = "lyrics")]
+
+use bridge_traits::http::HttpMethod;
+use bridge_traits::test_util::{MockHttpClient, MockResponse};
+use core_metadata::lyrics::{LrcLibProvider, LyricsProvider, LyricsSearchQuery, LyricsSource};
+use std::sync::Arc;
+
+const BASE_URL: &str = "https://lrclib.test/api";
+const GET_URL: &str = "https://lrclib.test/api/get?artist_name=Daft%20Punk\
+                       &track_name=Something%20About%20Us&album_name=Discovery&duration=232";
+const SEARCH_URL: &str =
+    "https://lrclib.test/api/search?artist_name=Daft%20Punk&track_name=Something%20About%20Us";
+
+fn query() -> LyricsSearchQuery {
+    LyricsSearchQuery::new(
+        "Daft Punk",
+        "Something About Us",
+        "Discovery",
+        232,
+        "track-1",
+    )
+}
+
+fn provider(http_client: Arc<MockHttpClient>) -> LrcLibProvider {
+    LrcLibProvider::with_rate_limit(http_client, 0).with_base_url(BASE_URL)
+}
+
+#[core_async::test]
+async fn test_search_returns_synced_lyrics_closest_to_duration() {
+    let http_client = Arc::new(MockHttpClient::new());
+    http_client
+        .stub(HttpMethod::Get, GET_URL, MockResponse::new(404))
+        .stub(
+            HttpMethod::Get,
+            SEARCH_URL,
+            MockResponse::ok(include_str!("fixtures/lrclib_search.json")),
+        );
+
+    let result = provider(http_client.clone())
+        .fetch(&query())
+        .await
+        .expect("LRCLib fetch failed")
+        .expect("Expected a match");
+
+    // The live edit is too long and the exact-duration match has no timings
+    assert!(result.is_synced);
+    assert!(result.is_valid_lrc());
+    assert!(result.text.starts_with("[00:19.74]"), "{}", result.text);
+    assert_eq!(result.source, LyricsSource::LrcLib);
+    assert_eq!(http_client.requests().len(), 2);
+}
+
+#[core_async::test]
+async fn test_exact_match_skips_search() {
+    let http_client = Arc::new(MockHttpClient::new());
+    http_client.stub(
+        HttpMethod::Get,
+        GET_URL,
+        MockResponse::ok(
+            r#"{"duration": 232.0, "plainLyrics": "Line", "syncedLyrics": "[00:01.00] Line"}"#,
+        ),
+    );
+
+    let result = provider(http_client.clone())
+        .fetch(&query())
+        .await
+        .expect("LRCLib fetch failed")
+        .expect("Expected a match");
+
+    assert!(result.is_synced);
+    assert_eq!(result.text, "[00:01.00] Line");
+    assert_eq!(http_client.requests().len(), 1);
+}
+
+#[core_async::test]
+async fn test_no_match_returns_none() {
+    let http_client = Arc::new(MockHttpClient::new());
+    http_client
+        .stub(HttpMethod::Get, GET_URL, MockResponse::new(404))
+        .stub(HttpMethod::Get, SEARCH_URL, MockResponse::ok("[]"));
+
+    let result = provider(http_client)
+        .fetch(&query())
+        .await
+        .expect("LRCLib fetch failed");
+
+    assert!(result.is_none());
+}
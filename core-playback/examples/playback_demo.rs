@@ -91,7 +91,7 @@ impl AudioDecoder for SimpleAudioDecoder {
         )))
     }
 
-    async fn seek(&mut self, position: Duration) -> Result<()> {
+    async fn seek(&mut self, position: Duration) -> Result<Duration> {
         let target_frame = (position.as_secs_f64() * self.sample_rate as f64) as usize;
         let target_sample = target_frame * self.channels as usize;
 
@@ -100,7 +100,9 @@ impl AudioDecoder for SimpleAudioDecoder {
         }
 
         self.position = target_sample;
-        Ok(())
+        Ok(Duration::from_secs_f64(
+            target_frame as f64 / self.sample_rate as f64,
+        ))
     }
 }
 
@@ -0,0 +1,499 @@
+//! # Audio Analysis
+//!
+//! Spectral analysis of decoded audio for visualizations.
+//!
+//! ## Overview
+//!
+//! `AnalysisService::spectrogram` decodes a track and runs a short-time
+//! Fourier transform over it, yielding one `SpectrogramColumn` per hop as
+//! soon as enough audio has been decoded. Channels are mixed down to mono
+//! first. Only one window of samples (plus the chunk being decoded) is held
+//! at a time, so memory stays bounded regardless of track length.
+//!
+//! The stream is lazy: nothing is decoded until it is polled, and dropping
+//! it stops decoding, which is how callers cancel an analysis.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use core_playback::{AnalysisService, AudioDecoder, SpectrogramConfig};
+//! use futures::StreamExt;
+//!
+//! # async fn example(decoder: impl AudioDecoder) -> core_playback::Result<()> {
+//! let columns = AnalysisService::new().spectrogram(decoder, SpectrogramConfig::default());
+//! futures::pin_mut!(columns);
+//! while let Some(column) = columns.next().await {
+//!     let column = column?;
+//!     println!("{:?}: peak bin {:?}", column.timestamp, column.peak_bin());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{PlaybackError, Result};
+use crate::traits::{AudioDecoder, AudioFrameChunk};
+use futures::stream::{self, Stream};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::f32::consts::PI;
+use std::time::Duration;
+
+/// Window applied to each frame before the FFT.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowFunction {
+    /// Hann window, a good default with low spectral leakage
+    #[default]
+    Hann,
+    /// Hamming window, narrower main lobe than Hann
+    Hamming,
+    /// No windowing
+    Rectangular,
+}
+
+impl WindowFunction {
+    /// Coefficients for a window of `size` samples.
+    pub fn coefficients(self, size: usize) -> Vec<f32> {
+        let denominator = size.saturating_sub(1).max(1) as f32;
+        (0..size)
+            .map(|n| {
+                let cosine = (2.0 * PI * n as f32 / denominator).cos();
+                match self {
+                    WindowFunction::Hann => 0.5 - 0.5 * cosine,
+                    WindowFunction::Hamming => 0.54 - 0.46 * cosine,
+                    WindowFunction::Rectangular => 1.0,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Parameters for the short-time Fourier transform.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SpectrogramConfig {
+    /// Samples per FFT frame; must be a power of two (default: 2048)
+    pub fft_size: usize,
+    /// Samples between the starts of consecutive frames (default: 512)
+    pub hop_size: usize,
+    /// Window applied to each frame (default: Hann)
+    pub window: WindowFunction,
+}
+
+impl Default for SpectrogramConfig {
+    fn default() -> Self {
+        Self {
+            fft_size: 2048,
+            hop_size: 512,
+            window: WindowFunction::Hann,
+        }
+    }
+}
+
+impl SpectrogramConfig {
+    /// Set the FFT size.
+    pub fn with_fft_size(mut self, fft_size: usize) -> Self {
+        self.fft_size = fft_size;
+        self
+    }
+
+    /// Set the hop size.
+    pub fn with_hop_size(mut self, hop_size: usize) -> Self {
+        self.hop_size = hop_size;
+        self
+    }
+
+    /// Set the window function.
+    pub fn with_window(mut self, window: WindowFunction) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Validate the configuration.
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        if self.fft_size < 2 || !self.fft_size.is_power_of_two() {
+            return Err(format!(
+                "fft_size must be a power of two of at least 2, got {}",
+                self.fft_size
+            ));
+        }
+
+        if self.hop_size == 0 || self.hop_size > self.fft_size {
+            return Err(format!(
+                "hop_size must be between 1 and fft_size ({}), got {}",
+                self.fft_size, self.hop_size
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Number of frequency bins in each column.
+    pub fn bins(&self) -> usize {
+        self.fft_size / 2 + 1
+    }
+
+    /// Centre frequency in Hz of `bin` for audio at `sample_rate`.
+    pub fn bin_frequency(&self, bin: usize, sample_rate: u32) -> f32 {
+        bin as f32 * sample_rate as f32 / self.fft_size as f32
+    }
+}
+
+/// Magnitude spectrum of one FFT frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpectrogramColumn {
+    /// Position of the first sample of the frame in the track
+    pub timestamp: Duration,
+    /// Magnitude per bin from DC to Nyquist (`fft_size / 2 + 1` values),
+    /// scaled so a full-scale sine peaks near 1.0
+    pub magnitudes: Vec<f32>,
+}
+
+impl SpectrogramColumn {
+    /// Index of the loudest bin, or `None` for an empty column.
+    pub fn peak_bin(&self) -> Option<usize> {
+        self.magnitudes
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(bin, _)| bin)
+    }
+}
+
+/// Produces analysis data for visualizations from decoded audio.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AnalysisService;
+
+impl AnalysisService {
+    /// Create a new analysis service.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Stream the spectrogram of everything `decoder` produces.
+    ///
+    /// Columns are yielded progressively, one per `hop_size` samples, until
+    /// a frame has covered the end of the audio. Audio shorter than one frame
+    /// is zero-padded. An invalid config, a probe error or a decode error is
+    /// yielded as the last item. Drop the stream to cancel.
+    pub fn spectrogram<D: AudioDecoder>(
+        &self,
+        decoder: D,
+        config: SpectrogramConfig,
+    ) -> impl Stream<Item = Result<SpectrogramColumn>> {
+        let state = match config.validate() {
+            Ok(()) => Ok(Spectrogram::new(decoder, config)),
+            Err(e) => Err(PlaybackError::Internal(format!(
+                "Invalid spectrogram config: {}",
+                e
+            ))),
+        };
+
+        stream::unfold(Some(state), |state| async move {
+            match state? {
+                Ok(mut spectrogram) => match spectrogram.next_column().await {
+                    Ok(Some(column)) => Some((Ok(column), Some(Ok(spectrogram)))),
+                    Ok(None) => None,
+                    Err(e) => Some((Err(e), None)),
+                },
+                Err(e) => Some((Err(e), None)),
+            }
+        })
+    }
+}
+
+/// Streaming STFT state over one decoder
+struct Spectrogram<D> {
+    decoder: D,
+    config: SpectrogramConfig,
+    fft: Fft,
+    sample_rate: Option<u32>,
+    /// Mono samples not yet shifted out by a hop
+    buffer: VecDeque<f32>,
+    /// Track position of `buffer[0]`, in frames
+    position: u64,
+    /// Samples at the end of `buffer` not covered by any column yet
+    unanalyzed: usize,
+    finished: bool,
+}
+
+impl<D: AudioDecoder> Spectrogram<D> {
+    fn new(decoder: D, config: SpectrogramConfig) -> Self {
+        Self {
+            decoder,
+            config,
+            fft: Fft::new(config.fft_size, config.window),
+            sample_rate: None,
+            buffer: VecDeque::with_capacity(config.fft_size * 2),
+            position: 0,
+            unanalyzed: 0,
+            finished: false,
+        }
+    }
+
+    async fn next_column(&mut self) -> Result<Option<SpectrogramColumn>> {
+        let sample_rate = match self.sample_rate {
+            Some(rate) => rate,
+            None => {
+                let probe = self.decoder.probe().await?;
+                self.sample_rate = Some(probe.format.sample_rate);
+                probe.format.sample_rate
+            }
+        };
+
+        while self.buffer.len() < self.config.fft_size && !self.finished {
+            match self.decoder.decode_frames(self.config.fft_size).await? {
+                Some(chunk) => self.push(&chunk),
+                None => self.finished = true,
+            }
+        }
+
+        if self.unanalyzed == 0 {
+            return Ok(None);
+        }
+
+        let column = SpectrogramColumn {
+            timestamp: Duration::from_secs_f64(self.position as f64 / sample_rate.max(1) as f64),
+            magnitudes: self.fft.magnitudes(&self.buffer),
+        };
+
+        let hop = self.config.hop_size.min(self.buffer.len());
+        let beyond_frame = self.buffer.len().saturating_sub(self.config.fft_size);
+        self.unanalyzed = self.unanalyzed.min(beyond_frame);
+        self.buffer.drain(..hop);
+        self.position += hop as u64;
+        Ok(Some(column))
+    }
+
+    /// Mix `chunk` down to mono and append it
+    fn push(&mut self, chunk: &AudioFrameChunk) {
+        if chunk.frames == 0 {
+            return;
+        }
+
+        let channels = (chunk.samples.len() / chunk.frames).max(1);
+        self.buffer.extend(
+            chunk
+                .samples
+                .chunks_exact(channels)
+                .map(|frame| frame.iter().sum::<f32>() / channels as f32),
+        );
+        self.unanalyzed += chunk.frames;
+    }
+}
+
+/// Radix-2 FFT with precomputed window and twiddle factors
+struct Fft {
+    window: Vec<f32>,
+    /// `e^(-2πik/n)` for `k` in `0..n/2`
+    twiddles: Vec<(f32, f32)>,
+    /// Scale turning bin magnitudes into sine amplitudes
+    scale: f32,
+}
+
+impl Fft {
+    fn new(size: usize, window: WindowFunction) -> Self {
+        let window = window.coefficients(size);
+        let twiddles = (0..size / 2)
+            .map(|k| {
+                let (sin, cos) = (-2.0 * std::f64::consts::PI * k as f64 / size as f64).sin_cos();
+                (cos as f32, sin as f32)
+            })
+            .collect();
+        let scale = 2.0 / window.iter().sum::<f32>();
+        Self {
+            window,
+            twiddles,
+            scale,
+        }
+    }
+
+    /// Windowed magnitude spectrum of the first `n` samples, zero-padded if
+    /// fewer are available
+    fn magnitudes(&self, samples: &VecDeque<f32>) -> Vec<f32> {
+        let n = self.window.len();
+        let mut re: Vec<f32> = samples
+            .iter()
+            .chain(std::iter::repeat(&0.0))
+            .zip(&self.window)
+            .map(|(sample, weight)| sample * weight)
+            .collect();
+        let mut im = vec![0.0; n];
+        self.transform(&mut re, &mut im);
+
+        (0..=n / 2)
+            .map(|bin| (re[bin] * re[bin] + im[bin] * im[bin]).sqrt() * self.scale)
+            .collect()
+    }
+
+    /// In-place iterative Cooley-Tukey transform
+    fn transform(&self, re: &mut [f32], im: &mut [f32]) {
+        let n = re.len();
+
+        let mut j = 0;
+        for i in 1..n {
+            let mut bit = n >> 1;
+            while j & bit != 0 {
+                j ^= bit;
+                bit >>= 1;
+            }
+            j |= bit;
+            if i < j {
+                re.swap(i, j);
+                im.swap(i, j);
+            }
+        }
+
+        let mut len = 2;
+        while len <= n {
+            let half = len / 2;
+            let stride = n / len;
+            for start in (0..n).step_by(len) {
+                for k in 0..half {
+                    let (w_re, w_im) = self.twiddles[k * stride];
+                    let (a, b) = (start + k, start + k + half);
+                    let t_re = re[b] * w_re - im[b] * w_im;
+                    let t_im = re[b] * w_im + im[b] * w_re;
+                    re[b] = re[a] - t_re;
+                    im[b] = im[a] - t_im;
+                    re[a] += t_re;
+                    im[a] += t_im;
+                }
+            }
+            len <<= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::{AudioFormat, ProbeResult};
+    use async_trait::async_trait;
+    use futures::StreamExt;
+
+    const RATE: u32 = 44_100;
+
+    /// Stereo decoder playing each `(frequency, seconds)` segment in turn
+    struct ToneDecoder {
+        segments: Vec<(f32, f64)>,
+        position: usize,
+    }
+
+    impl ToneDecoder {
+        fn frequency_at(&self, frame: usize) -> Option<f32> {
+            let mut end = 0;
+            for &(frequency, seconds) in &self.segments {
+                end += (seconds * RATE as f64) as usize;
+                if frame < end {
+                    return Some(frequency);
+                }
+            }
+            None
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+    impl AudioDecoder for ToneDecoder {
+        async fn probe(&mut self) -> Result<ProbeResult> {
+            Ok(ProbeResult::new(AudioFormat::cd_quality()))
+        }
+
+        async fn decode_frames(&mut self, max_frames: usize) -> Result<Option<AudioFrameChunk>> {
+            let start = self.position;
+            let samples: Vec<f32> = (start..start + max_frames)
+                .map_while(|frame| {
+                    let frequency = self.frequency_at(frame)?;
+                    let phase = 2.0 * PI * frequency * frame as f32 / RATE as f32;
+                    Some(0.8 * phase.sin())
+                })
+                .flat_map(|sample| [sample; 2])
+                .collect();
+
+            let frames = samples.len() / 2;
+            if frames == 0 {
+                return Ok(None);
+            }
+            self.position += frames;
+            let timestamp = Duration::from_secs_f64(start as f64 / RATE as f64);
+            Ok(Some(AudioFrameChunk::new(samples, frames, timestamp)))
+        }
+
+        async fn seek(&mut self, position: Duration) -> Result<Duration> {
+            Ok(position)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_peak_bin_tracks_tone_frequency() {
+        let config = SpectrogramConfig::default();
+        let decoder = ToneDecoder {
+            segments: vec![(440.0, 1.0), (1760.0, 1.0)],
+            position: 0,
+        };
+
+        let columns: Vec<SpectrogramColumn> = AnalysisService::new()
+            .spectrogram(decoder, config)
+            .map(|column| column.unwrap())
+            .collect()
+            .await;
+
+        // One column per hop until a frame reaches the end of the audio
+        let frames = 2 * RATE as usize;
+        let expected = (frames - config.fft_size + config.hop_size).div_ceil(config.hop_size);
+        assert_eq!(columns.len(), expected);
+
+        let window = Duration::from_secs_f64(config.fft_size as f64 / RATE as f64);
+        let bin_width = config.bin_frequency(1, RATE);
+        let mut checked = [0; 2];
+        for (index, column) in columns.iter().enumerate() {
+            let offset = (index * config.hop_size) as f64 / RATE as f64;
+            assert_eq!(column.timestamp, Duration::from_secs_f64(offset));
+            assert_eq!(column.magnitudes.len(), config.bins());
+
+            // Skip frames straddling the change or running past the end
+            let end = column.timestamp + window;
+            let expected = if end <= Duration::from_secs(1) {
+                checked[0] += 1;
+                440.0
+            } else if column.timestamp >= Duration::from_secs(1) && end <= Duration::from_secs(2) {
+                checked[1] += 1;
+                1760.0
+            } else {
+                continue;
+            };
+
+            let peak = column.peak_bin().unwrap();
+            let frequency = config.bin_frequency(peak, RATE);
+            assert!(
+                (frequency - expected).abs() <= bin_width,
+                "column at {:?} peaks at {}Hz, expected {}Hz",
+                column.timestamp,
+                frequency,
+                expected
+            );
+            assert!(
+                (0.6..=0.9).contains(&column.magnitudes[peak]),
+                "peak magnitude {}",
+                column.magnitudes[peak]
+            );
+        }
+        assert!(checked[0] > 70 && checked[1] > 70, "{:?}", checked);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_config_is_reported() {
+        let decoder = ToneDecoder {
+            segments: vec![(440.0, 1.0)],
+            position: 0,
+        };
+        let config = SpectrogramConfig::default().with_fft_size(1000);
+
+        let items: Vec<_> = AnalysisService::new()
+            .spectrogram(decoder, config)
+            .collect()
+            .await;
+        assert_eq!(items.len(), 1);
+        assert!(matches!(items[0], Err(PlaybackError::Internal(_))));
+    }
+}
@@ -29,6 +29,10 @@ pub struct CacheConfig {
 
     /// Base directory for cache files (relative to app data dir)
     pub cache_directory: String,
+
+    /// Bytes requested per ranged download; progress is saved after each
+    /// chunk so an interrupted download resumes there (default: 1MB)
+    pub download_chunk_size: usize,
 }
 
 impl Default for CacheConfig {
@@ -42,6 +46,7 @@ impl Default for CacheConfig {
             verify_integrity: true,
             max_retry_attempts: 3,
             cache_directory: "offline_cache".to_string(),
+            download_chunk_size: 1024 * 1024,
         }
     }
 }
@@ -88,6 +93,12 @@ impl CacheConfig {
         self
     }
 
+    /// Set the size of each ranged download request.
+    pub fn with_download_chunk_size(mut self, bytes: usize) -> Self {
+        self.download_chunk_size = bytes;
+        self
+    }
+
     /// Validate configuration.
     pub fn validate(&self) -> Result<(), String> {
         if self.max_cache_size_bytes == 0 {
@@ -102,6 +113,10 @@ impl CacheConfig {
             return Err("cache_directory cannot be empty".to_string());
         }
 
+        if self.download_chunk_size == 0 {
+            return Err("download_chunk_size must be greater than 0".to_string());
+        }
+
         Ok(())
     }
 }
@@ -177,6 +192,9 @@ mod tests {
 
         let invalid_dir = CacheConfig::default().with_cache_directory(String::new());
         assert!(invalid_dir.validate().is_err());
+
+        let invalid_chunk = CacheConfig::default().with_download_chunk_size(0);
+        assert!(invalid_chunk.validate().is_err());
     }
 
     #[test]
@@ -5,6 +5,13 @@ use bytes::Bytes;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
+/// Magic bytes opening a cache file that was encrypted chunk by chunk.
+const CHUNKED_MAGIC: &[u8; 8] = b"MPCCHNK1";
+
+/// Bytes each encrypted chunk adds to its plaintext: a 4-byte length
+/// prefix, the 12-byte nonce and the 16-byte GCM tag.
+pub const CHUNK_OVERHEAD: usize = 4 + 12 + 16;
+
 /// Encryption key for cache files.
 ///
 /// Uses AES-256-GCM for authenticated encryption when the 'offline-cache' feature is enabled.
@@ -161,6 +168,118 @@ impl CacheEncryptor {
             "Encryption not enabled. Enable 'offline-cache' feature.".to_string(),
         ))
     }
+
+    /// Header written before the first chunk of a chunked cache file.
+    pub fn chunked_header() -> &'static [u8] {
+        CHUNKED_MAGIC
+    }
+
+    /// Encrypt one chunk of a file, starting at plaintext byte `offset`.
+    ///
+    /// Each chunk gets its own random nonce and is prefixed with its
+    /// plaintext length, so a file can be written one chunk at a time and
+    /// resumed after an interruption. The offset is authenticated, so
+    /// chunks cannot be moved around within a file.
+    #[cfg(feature = "offline-cache")]
+    pub fn encrypt_chunk(&self, offset: u64, plaintext: &[u8]) -> Result<Bytes> {
+        use aes_gcm::{
+            aead::{rand_core::RngCore, Aead, KeyInit, OsRng, Payload},
+            Aes256Gcm, Nonce,
+        };
+
+        let length = u32::try_from(plaintext.len()).map_err(|_| {
+            PlaybackError::EncryptionError("Chunk too large to encrypt".to_string())
+        })?;
+
+        let key = aes_gcm::Key::<Aes256Gcm>::from_slice(&self.key.key_bytes);
+        let cipher = Aes256Gcm::new(key);
+
+        let mut nonce_bytes = [0u8; 12];
+        OsRng.fill_bytes(&mut nonce_bytes);
+        let aad = offset.to_be_bytes();
+        let ciphertext = cipher
+            .encrypt(
+                Nonce::from_slice(&nonce_bytes),
+                Payload {
+                    msg: plaintext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|e| PlaybackError::EncryptionError(format!("Encryption failed: {}", e)))?;
+
+        // Layout: plaintext length, nonce, ciphertext with tag
+        let mut result = Vec::with_capacity(plaintext.len() + CHUNK_OVERHEAD);
+        result.extend_from_slice(&length.to_be_bytes());
+        result.extend_from_slice(&nonce_bytes);
+        result.extend_from_slice(&ciphertext);
+
+        Ok(Bytes::from(result))
+    }
+
+    /// Encrypt a chunk (stub when feature is disabled).
+    #[cfg(not(feature = "offline-cache"))]
+    pub fn encrypt_chunk(&self, _offset: u64, _plaintext: &[u8]) -> Result<Bytes> {
+        Err(PlaybackError::EncryptionError(
+            "Encryption not enabled. Enable 'offline-cache' feature.".to_string(),
+        ))
+    }
+
+    /// Decrypt a whole cache file, whether it was written by `encrypt` or
+    /// chunk by chunk after `chunked_header`.
+    pub fn decrypt_file(&self, data: &[u8]) -> Result<Bytes> {
+        match data.strip_prefix(CHUNKED_MAGIC.as_slice()) {
+            Some(chunks) => self.decrypt_chunks(chunks),
+            None => self.decrypt(data),
+        }
+    }
+
+    /// Decrypt consecutive chunks produced by `encrypt_chunk`.
+    #[cfg(feature = "offline-cache")]
+    fn decrypt_chunks(&self, mut data: &[u8]) -> Result<Bytes> {
+        use aes_gcm::{
+            aead::{Aead, KeyInit, Payload},
+            Aes256Gcm, Nonce,
+        };
+
+        let key = aes_gcm::Key::<Aes256Gcm>::from_slice(&self.key.key_bytes);
+        let cipher = Aes256Gcm::new(key);
+        let mut plaintext = Vec::with_capacity(data.len());
+
+        while !data.is_empty() {
+            let truncated = || {
+                PlaybackError::EncryptionError("Invalid ciphertext: truncated chunk".to_string())
+            };
+            let prefix: [u8; 4] = data
+                .get(..4)
+                .and_then(|bytes| bytes.try_into().ok())
+                .ok_or_else(truncated)?;
+            let length = u32::from_be_bytes(prefix) as usize;
+            let chunk = data.get(4..length + CHUNK_OVERHEAD).ok_or_else(truncated)?;
+
+            let aad = (plaintext.len() as u64).to_be_bytes();
+            let decrypted = cipher
+                .decrypt(
+                    Nonce::from_slice(&chunk[..12]),
+                    Payload {
+                        msg: &chunk[12..],
+                        aad: &aad,
+                    },
+                )
+                .map_err(|e| PlaybackError::EncryptionError(format!("Decryption failed: {}", e)))?;
+            plaintext.extend_from_slice(&decrypted);
+            data = &data[length + CHUNK_OVERHEAD..];
+        }
+
+        Ok(Bytes::from(plaintext))
+    }
+
+    /// Decrypt chunks (stub when feature is disabled).
+    #[cfg(not(feature = "offline-cache"))]
+    fn decrypt_chunks(&self, _data: &[u8]) -> Result<Bytes> {
+        Err(PlaybackError::EncryptionError(
+            "Encryption not enabled. Enable 'offline-cache' feature.".to_string(),
+        ))
+    }
 }
 
 // Hex serialization helper for serde
@@ -271,6 +390,48 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[cfg(feature = "offline-cache")]
+    #[test]
+    fn test_chunked_roundtrip() {
+        let encryptor = CacheEncryptor::new(EncryptionKey::generate().unwrap());
+        let plaintext: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+
+        let mut file = CacheEncryptor::chunked_header().to_vec();
+        let mut offset = 0;
+        for chunk in plaintext.chunks(4096) {
+            let encrypted = encryptor.encrypt_chunk(offset, chunk).unwrap();
+            assert_eq!(encrypted.len(), chunk.len() + CHUNK_OVERHEAD);
+            file.extend_from_slice(&encrypted);
+            offset += chunk.len() as u64;
+        }
+
+        assert_eq!(encryptor.decrypt_file(&file).unwrap().as_ref(), plaintext);
+
+        // Single-shot files still decrypt
+        let single = encryptor.encrypt(&plaintext).unwrap();
+        assert_eq!(encryptor.decrypt_file(&single).unwrap().as_ref(), plaintext);
+    }
+
+    #[cfg(feature = "offline-cache")]
+    #[test]
+    fn test_chunked_rejects_reordered_and_truncated_chunks() {
+        let encryptor = CacheEncryptor::new(EncryptionKey::generate().unwrap());
+        let first = encryptor.encrypt_chunk(0, &[1u8; 16]).unwrap();
+        let second = encryptor.encrypt_chunk(16, &[2u8; 16]).unwrap();
+
+        // Swapped chunks fail authentication
+        let mut swapped = CacheEncryptor::chunked_header().to_vec();
+        swapped.extend_from_slice(&second);
+        swapped.extend_from_slice(&first);
+        assert!(encryptor.decrypt_file(&swapped).is_err());
+
+        // A chunk cut short is reported
+        let mut truncated = CacheEncryptor::chunked_header().to_vec();
+        truncated.extend_from_slice(&first);
+        truncated.extend_from_slice(&second[..20]);
+        assert!(encryptor.decrypt_file(&truncated).is_err());
+    }
+
     #[cfg(not(feature = "offline-cache"))]
     #[test]
     fn test_encryption_disabled_returns_error() {
@@ -5,20 +5,21 @@
 //! This module provides production-ready caching with:
 //! - Concurrent downloads with semaphore-based throttling
 //! - Automatic LRU/LFU/FIFO eviction when cache is full
+//! - Pinned tracks that eviction never removes
 //! - Optional AES-256-GCM encryption
-//! - Progress tracking and retry logic
-//! - Integrity verification using SHA-256 hashes
+//! - Chunked, resumable downloads with progress tracking and retry logic
+//! - Integrity verification against provider MD5 checksums or SHA-256 hashes
 //! - Cross-platform support (native and WASM)
 
 use crate::cache::{
     config::{CacheConfig, EvictionPolicy},
     encryption::{CacheEncryptor, EncryptionKey},
-    stats::{CacheStats, DownloadProgress},
-    CachedTrack, CacheMetadataRepository, CacheStatus, SqliteCacheMetadataRepository,
+    stats::{CacheReconciliation, CacheStats, DownloadProgress},
+    CacheMetadataRepository, CacheStatus, CachedTrack, SqliteCacheMetadataRepository,
 };
 use crate::error::{PlaybackError, Result};
 use bridge_traits::{
-    database::DatabaseAdapter, http::HttpClient, storage::FileSystemAccess,
+    database::DatabaseAdapter, error::BridgeError, http::HttpClient, storage::FileSystemAccess,
     storage::StorageProvider,
 };
 use bytes::Bytes;
@@ -26,10 +27,11 @@ use core_async::sync::{Mutex, Semaphore};
 use core_async::time::timeout;
 use core_library::models::{Track, TrackId};
 use core_library::repositories::TrackRepository;
-use core_runtime::events::EventBus;
+use core_runtime::events::{CoreEvent, EventBus, PlaybackEvent};
+use md5::Md5;
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 use tracing::{debug, error, info, instrument, warn};
@@ -46,6 +48,7 @@ pub struct OfflineCacheManager {
     repository: PlatformArc<dyn CacheMetadataRepository>,
     track_repository: Arc<dyn TrackRepository>,
     fs: Arc<dyn FileSystemAccess>,
+    #[allow(dead_code)]
     http_client: Arc<dyn HttpClient>,
     storage_provider: Arc<dyn StorageProvider>,
     encryptor: Option<Arc<CacheEncryptor>>,
@@ -177,10 +180,13 @@ impl OfflineCacheManager {
     ///
     /// This method:
     /// 1. Looks up track metadata from the library
-    /// 2. Downloads the file from the storage provider
-    /// 3. Optionally encrypts the data
-    /// 4. Verifies integrity using SHA-256 hash
-    /// 5. Stores the file and updates metadata
+    /// 2. Downloads the file from the storage provider in ranged chunks
+    /// 3. Optionally encrypts each chunk
+    /// 4. Appends each chunk to the cache file and saves progress
+    /// 5. Verifies integrity using SHA-256 hash and updates metadata
+    ///
+    /// A download interrupted part-way resumes from the last saved chunk
+    /// when retried, as long as the provider supports byte ranges.
     ///
     /// # Arguments
     ///
@@ -200,15 +206,12 @@ impl OfflineCacheManager {
         }
 
         // Acquire download permit from semaphore
-        let _permit = timeout(
-            Duration::from_secs(30),
-            self.download_semaphore.acquire(),
-        )
-        .await
-        .map_err(|_| {
-            PlaybackError::CacheError("Timeout waiting for download slot".to_string())
-        })?
-        .map_err(|_| PlaybackError::CacheError("Semaphore closed".to_string()))?;
+        let _permit = timeout(Duration::from_secs(30), self.download_semaphore.acquire())
+            .await
+            .map_err(|_| {
+                PlaybackError::CacheError("Timeout waiting for download slot".to_string())
+            })?
+            .map_err(|_| PlaybackError::CacheError("Semaphore closed".to_string()))?;
 
         // Look up track from library
         let track = self
@@ -221,22 +224,22 @@ impl OfflineCacheManager {
             })?
             .ok_or_else(|| PlaybackError::TrackNotFound(track_id.to_string()))?;
 
+        // Make sure the track fits before recording a download
+        let file_size = track.file_size.unwrap_or(0) as u64;
+        if let Some(cache_base) = self.cache_base_path.lock().await.clone() {
+            ensure_available_space(self.fs.as_ref(), &cache_base, file_size).await?;
+        }
+
         // Get or create cache entry
         let mut cached_track = match self.repository.find_by_track_id(&track_id).await? {
             Some(mut entry) => {
-                // Reset if failed/stale
-                if entry.status.needs_download() {
-                    entry.mark_downloading();
-                    entry
-                } else {
-                    entry
+                // A stale file cannot be resumed; a failed one can
+                if entry.status == CacheStatus::Stale {
+                    entry.reset_progress();
                 }
+                entry
             }
-            None => {
-                let cache_filename = format!("{}.cache", track_id);
-                let file_size = track.file_size.unwrap_or(0) as u64;
-                CachedTrack::new(track_id, cache_filename, file_size)
-            }
+            None => CachedTrack::new(track_id, cache_file_name(&track_id), file_size),
         };
 
         cached_track.mark_downloading();
@@ -249,7 +252,6 @@ impl OfflineCacheManager {
         }
 
         // Register active download
-        let file_size = track.file_size.unwrap_or(0) as u64;
         let progress = Arc::new(Mutex::new(DownloadProgress::new(
             track_id.to_string(),
             file_size,
@@ -328,6 +330,9 @@ impl OfflineCacheManager {
     }
 
     /// Internal download implementation.
+    ///
+    /// Picks up after the bytes already recorded in `cached_track`, saving
+    /// progress after every chunk.
     async fn download_track_internal(
         &self,
         track: &Track,
@@ -342,85 +347,226 @@ impl OfflineCacheManager {
             .map_err(|e| {
                 PlaybackError::CacheError(format!("Failed to get file metadata: {}", e))
             })?;
+        let total_bytes = remote_file.size.unwrap_or(cached_track.file_size);
+        progress.lock().await.total_bytes = total_bytes;
 
-        // Download file content
-        debug!("Downloading file from provider: {}", remote_file.name);
-        let data = self
-            .storage_provider
-            .download(&track.provider_file_id, None)
+        // Get cache file path
+        let cache_base = self
+            .cache_base_path
+            .lock()
             .await
-            .map_err(|e| PlaybackError::CacheError(format!("Download failed: {}", e)))?;
+            .clone()
+            .ok_or_else(|| PlaybackError::CacheError("Cache not initialized".to_string()))?;
 
-        // Update progress
-        {
-            let mut p = progress.lock().await;
-            p.update(data.len() as u64);
-        }
-
-        // Verify integrity
-        if self.config.verify_integrity {
-            if let Some(track_hash) = &track.hash {
-                if !track_hash.is_empty() {
-                    let hash = self.calculate_hash(&data);
-                    if hash != *track_hash {
-                        return Err(PlaybackError::CacheError(format!(
-                            "Hash mismatch: expected {}, got {}",
-                            track_hash, hash
-                        )));
-                    }
-                }
-            }
-        }
+        let cache_file_path = cache_file_path(&cache_base, &cached_track.cache_path);
 
         // Optionally encrypt
-        let (final_data, encrypted) = if self.config.enable_encryption {
-            if let Some(encryptor) = &self.encryptor {
-                debug!("Encrypting cached file");
-                let encrypted_data = encryptor.encrypt(&data)?;
-                (encrypted_data, true)
-            } else {
+        let encryptor = match (&self.encryptor, self.config.enable_encryption) {
+            (Some(encryptor), true) => Some(encryptor.as_ref()),
+            (None, true) => {
                 warn!("Encryption enabled but no encryptor available");
-                (data, false)
+                None
             }
-        } else {
-            (data, false)
+            (_, false) => None,
         };
 
-        // Get cache file path
-        let cache_base = self
-            .cache_base_path
-            .lock()
-            .await
-            .clone()
-            .ok_or_else(|| PlaybackError::CacheError("Cache not initialized".to_string()))?;
+        // Resume only from progress that matches the file on disk
+        if cached_track.downloaded_bytes > 0
+            && !self
+                .can_resume(cached_track, &cache_file_path, encryptor.is_some())
+                .await
+        {
+            debug!("Cannot resume download of track {}, restarting", track.id);
+            cached_track.reset_progress();
+        }
+        cached_track.encrypted = encryptor.is_some();
 
-        let cache_file_path = cache_base.join(&cached_track.cache_path);
+        // Download file content
+        let chunk_size = self.config.download_chunk_size as u64;
+        if self.storage_provider.supports_range() && total_bytes > 0 {
+            if cached_track.downloaded_bytes > 0 {
+                info!(
+                    "Resuming download of {} at byte {}",
+                    remote_file.name, cached_track.downloaded_bytes
+                );
+            }
+            while cached_track.downloaded_bytes < total_bytes {
+                let start = cached_track.downloaded_bytes;
+                let end = (start + chunk_size).min(total_bytes);
+                let range = format!("bytes={}-{}", start, end - 1);
+                debug!("Downloading {} of {}", range, remote_file.name);
+                let data = self
+                    .storage_provider
+                    .download(&track.provider_file_id, Some(&range))
+                    .await
+                    .map_err(|e| PlaybackError::CacheError(format!("Download failed: {}", e)))?;
+                if data.is_empty() || data.len() as u64 > end - start {
+                    return Err(PlaybackError::CacheError(format!(
+                        "Provider returned {} bytes for {}",
+                        data.len(),
+                        range
+                    )));
+                }
 
-        // Write to filesystem
-        debug!("Writing cached file to {:?}", cache_file_path);
-        self.fs
-            .write_file(&cache_file_path, final_data.clone())
-            .await
-            .map_err(|e| {
-                PlaybackError::CacheError(format!("Failed to write cache file: {}", e))
+                self.append_chunk(&cache_file_path, cached_track, encryptor, &data)
+                    .await?;
+                self.save_progress(track, cached_track, &progress).await?;
+            }
+        } else {
+            // Without ranges the whole file is fetched again on every attempt
+            debug!("Downloading file from provider: {}", remote_file.name);
+            let data = self
+                .storage_provider
+                .download(&track.provider_file_id, None)
+                .await
+                .map_err(|e| PlaybackError::CacheError(format!("Download failed: {}", e)))?;
+
+            cached_track.reset_progress();
+            for chunk in data.chunks(chunk_size as usize) {
+                self.append_chunk(&cache_file_path, cached_track, encryptor, chunk)
+                    .await?;
+            }
+            self.save_progress(track, cached_track, &progress).await?;
+        }
+
+        // Read back the assembled file
+        let stored =
+            self.fs.read_file(&cache_file_path).await.map_err(|e| {
+                PlaybackError::CacheError(format!("Failed to read cache file: {}", e))
             })?;
+        let data = match encryptor {
+            Some(encryptor) => encryptor.decrypt_file(&stored)?,
+            None => stored,
+        };
 
-        // Calculate content hash for verification
-        let content_hash = self.calculate_hash(&final_data);
+        // Check the download against the provider checksum, or remember a
+        // local SHA-256 when the provider gives none
+        let provider_md5 = remote_file
+            .md5_checksum
+            .as_deref()
+            .filter(|md5| !md5.is_empty());
+        let content_hash = match provider_md5 {
+            Some(md5) => {
+                let content_hash = format!("{}{}", MD5_PREFIX, md5.to_lowercase());
+                if !matches_content_hash(&content_hash, &data) {
+                    cached_track.reset_progress();
+                    return Err(PlaybackError::CacheError(format!(
+                        "Checksum mismatch: provider reports MD5 {}",
+                        md5
+                    )));
+                }
+                content_hash
+            }
+            None => self.calculate_hash(&data),
+        };
+
+        // Verify integrity
+        if self.config.verify_integrity {
+            if let Some(track_hash) = track.hash.as_deref().filter(|hash| !hash.is_empty()) {
+                let hash = self.calculate_hash(&data);
+                if hash != track_hash {
+                    cached_track.reset_progress();
+                    return Err(PlaybackError::CacheError(format!(
+                        "Hash mismatch: expected {}, got {}",
+                        track_hash, hash
+                    )));
+                }
+            }
+        }
 
         // Update cached track metadata
-        cached_track.mark_cached(final_data.len() as u64, content_hash, encrypted);
+        let encrypted = encryptor.is_some();
+        cached_track.mark_cached(cached_track.cached_size, content_hash, encrypted);
 
         info!(
             "Track {} cached successfully (size: {} bytes, encrypted: {})",
-            track.id,
-            final_data.len(),
-            encrypted
+            track.id, cached_track.cached_size, encrypted
         );
 
         Ok(())
     }
 
+    /// Whether the partial file on disk matches the progress recorded in
+    /// `cached_track` and was written with the same encryption setting.
+    async fn can_resume(&self, cached_track: &CachedTrack, path: &Path, encrypted: bool) -> bool {
+        if cached_track.encrypted != encrypted {
+            return false;
+        }
+        match self.fs.metadata(path).await {
+            Ok(metadata) => metadata.size == cached_track.cached_size,
+            Err(_) => false,
+        }
+    }
+
+    /// Encrypt `data` if needed and append it to the cache file, starting a
+    /// new file when nothing has been downloaded yet.
+    async fn append_chunk(
+        &self,
+        path: &Path,
+        cached_track: &mut CachedTrack,
+        encryptor: Option<&CacheEncryptor>,
+        data: &[u8],
+    ) -> Result<()> {
+        let offset = cached_track.downloaded_bytes;
+        let chunk = match encryptor {
+            Some(encryptor) => {
+                let encrypted = encryptor.encrypt_chunk(offset, data)?;
+                if offset == 0 {
+                    let mut first = CacheEncryptor::chunked_header().to_vec();
+                    first.extend_from_slice(&encrypted);
+                    Bytes::from(first)
+                } else {
+                    encrypted
+                }
+            }
+            None => Bytes::copy_from_slice(data),
+        };
+
+        let written = chunk.len() as u64;
+        let result = if offset == 0 {
+            self.fs.write_file(path, chunk).await
+        } else {
+            self.fs.append_file(path, chunk).await
+        };
+        result
+            .map_err(|e| PlaybackError::CacheError(format!("Failed to write cache file: {}", e)))?;
+
+        cached_track.cached_size = if offset == 0 {
+            written
+        } else {
+            cached_track.cached_size + written
+        };
+        cached_track.update_progress(offset + data.len() as u64);
+        Ok(())
+    }
+
+    /// Persist download progress and report it to listeners.
+    async fn save_progress(
+        &self,
+        track: &Track,
+        cached_track: &CachedTrack,
+        progress: &Mutex<DownloadProgress>,
+    ) -> Result<()> {
+        self.repository.update(cached_track).await?;
+
+        let total_bytes = {
+            let mut p = progress.lock().await;
+            p.update(cached_track.downloaded_bytes);
+            p.total_bytes
+        };
+
+        if let Some(event_bus) = &self.event_bus {
+            event_bus
+                .emit(CoreEvent::Playback(PlaybackEvent::DownloadProgress {
+                    track_id: track.id.clone(),
+                    downloaded_bytes: cached_track.downloaded_bytes,
+                    total_bytes,
+                }))
+                .ok();
+        }
+        Ok(())
+    }
+
     /// Calculate SHA-256 hash of data.
     fn calculate_hash(&self, data: &[u8]) -> String {
         let mut hasher = Sha256::new();
@@ -454,6 +600,7 @@ impl OfflineCacheManager {
     /// Evict tracks from cache to free up space.
     ///
     /// Uses the configured eviction policy to determine which tracks to remove.
+    /// Pinned tracks are never considered.
     #[instrument(skip(self))]
     pub async fn evict_tracks(&self, bytes_needed: u64) -> Result<usize> {
         info!("Evicting tracks to free {} bytes", bytes_needed);
@@ -508,9 +655,20 @@ impl OfflineCacheManager {
     }
 
     /// Evict oldest tracks until cache is under the size limit.
+    ///
+    /// Returns `PlaybackError::CacheFull` without evicting anything if the
+    /// pinned tracks alone exceed the limit.
     #[instrument(skip(self))]
     pub async fn evict_oldest(&self) -> Result<usize> {
         let stats = self.get_cache_stats().await?;
+        if stats.pinned_bytes > self.config.max_cache_size_bytes {
+            warn!(
+                "Pinned tracks use {} bytes, over the {} byte cache limit",
+                stats.pinned_bytes, self.config.max_cache_size_bytes
+            );
+            return Err(PlaybackError::CacheFull);
+        }
+
         let bytes_over = stats.space_needed(self.config.max_cache_size_bytes);
 
         if bytes_over > 0 {
@@ -520,6 +678,33 @@ impl OfflineCacheManager {
         }
     }
 
+    /// Pin a track so eviction never removes it.
+    #[instrument(skip(self))]
+    pub async fn pin(&self, track_id: &TrackId) -> Result<()> {
+        self.set_pinned(track_id, true).await
+    }
+
+    /// Unpin a track, making it a candidate for eviction again.
+    #[instrument(skip(self))]
+    pub async fn unpin(&self, track_id: &TrackId) -> Result<()> {
+        self.set_pinned(track_id, false).await
+    }
+
+    async fn set_pinned(&self, track_id: &TrackId, pinned: bool) -> Result<()> {
+        let mut cached_track = self
+            .repository
+            .find_by_track_id(track_id)
+            .await?
+            .ok_or_else(|| PlaybackError::NotCached(track_id.to_string()))?;
+
+        if cached_track.pinned != pinned {
+            cached_track.pinned = pinned;
+            self.repository.update(&cached_track).await?;
+            debug!("Set pinned = {} for track {}", pinned, track_id);
+        }
+        Ok(())
+    }
+
     /// Evict a single track from the cache.
     async fn evict_single_track(&self, track_id: &TrackId) -> Result<()> {
         // Get cache entry
@@ -537,7 +722,7 @@ impl OfflineCacheManager {
             .clone()
             .ok_or_else(|| PlaybackError::CacheError("Cache not initialized".to_string()))?;
 
-        let cache_file_path = cache_base.join(&cached_track.cache_path);
+        let cache_file_path = cache_file_path(&cache_base, &cached_track.cache_path);
 
         // Delete file from filesystem
         if let Err(e) = self.fs.delete_file(&cache_file_path).await {
@@ -569,6 +754,30 @@ impl OfflineCacheManager {
             )));
         }
 
+        let final_data = self.load_cached_data(&cached_track).await?;
+
+        // Verify integrity
+        if self.config.verify_integrity
+            && !cached_track.content_hash.is_empty()
+            && !matches_content_hash(&cached_track.content_hash, &final_data)
+        {
+            warn!("Cache integrity check failed for track {}", track_id);
+            cached_track.mark_stale();
+            self.repository.update(&cached_track).await?;
+            return Err(PlaybackError::CacheError(
+                "Cache integrity check failed".to_string(),
+            ));
+        }
+
+        // Update play count and last accessed time
+        cached_track.record_play();
+        self.repository.update(&cached_track).await?;
+
+        Ok(final_data)
+    }
+
+    /// Read a cached file from disk and decrypt it if needed.
+    async fn load_cached_data(&self, cached_track: &CachedTrack) -> Result<Bytes> {
         // Get cache file path
         let cache_base = self
             .cache_base_path
@@ -577,7 +786,7 @@ impl OfflineCacheManager {
             .clone()
             .ok_or_else(|| PlaybackError::CacheError("Cache not initialized".to_string()))?;
 
-        let cache_file_path = cache_base.join(&cached_track.cache_path);
+        let cache_file_path = cache_file_path(&cache_base, &cached_track.cache_path);
 
         // Read file
         let data = self.fs.read_file(&cache_file_path).await.map_err(|e| {
@@ -589,7 +798,7 @@ impl OfflineCacheManager {
         let final_data = if cached_track.encrypted {
             if let Some(encryptor) = &self.encryptor {
                 debug!("Decrypting cached file");
-                encryptor.decrypt(&data)?
+                encryptor.decrypt_file(&data)?
             } else {
                 return Err(PlaybackError::EncryptionError(
                     "Track is encrypted but no decryption key available".to_string(),
@@ -599,31 +808,58 @@ impl OfflineCacheManager {
             data
         };
 
-        // Verify integrity
-        if self.config.verify_integrity && !cached_track.content_hash.is_empty() {
-            let hash = self.calculate_hash(&final_data);
-            if hash != cached_track.content_hash {
-                warn!("Cache integrity check failed for track {}", track_id);
-                cached_track.mark_stale();
-                self.repository.update(&cached_track).await?;
-                return Err(PlaybackError::CacheError(
-                    "Cache integrity check failed".to_string(),
-                ));
-            }
+        Ok(final_data)
+    }
+
+    /// Check a cached track against its stored checksum.
+    ///
+    /// Re-reads and decrypts the cached file and compares it with the
+    /// provider's MD5 checksum, or with the SHA-256 computed at download time
+    /// when the provider gave none. A file that no longer matches, or no
+    /// longer decrypts, is marked `Stale` so it will be downloaded again.
+    ///
+    /// Returns `Ok(true)` if the file is intact.
+    #[instrument(skip(self))]
+    pub async fn verify(&self, track_id: &TrackId) -> Result<bool> {
+        let mut cached_track = self
+            .repository
+            .find_by_track_id(track_id)
+            .await?
+            .ok_or_else(|| PlaybackError::NotCached(track_id.to_string()))?;
+
+        if !cached_track.status.is_available() {
+            return Err(PlaybackError::NotCached(format!(
+                "Track {} is not available (status: {:?})",
+                track_id, cached_track.status
+            )));
+        }
+        if cached_track.content_hash.is_empty() {
+            debug!("Track {} has no checksum to verify against", track_id);
+            return Ok(true);
         }
 
-        // Update play count and last accessed time
-        cached_track.record_play();
-        self.repository.update(&cached_track).await?;
+        let intact = match self.load_cached_data(&cached_track).await {
+            Ok(data) => matches_content_hash(&cached_track.content_hash, &data),
+            Err(PlaybackError::EncryptionError(e)) if self.encryptor.is_some() => {
+                warn!("Cached track {} failed to decrypt: {}", track_id, e);
+                false
+            }
+            Err(e) => return Err(e),
+        };
 
-        Ok(final_data)
+        if !intact {
+            warn!("Cache integrity check failed for track {}", track_id);
+            cached_track.mark_stale();
+            self.repository.update(&cached_track).await?;
+        }
+        Ok(intact)
     }
 
     /// Get cache statistics.
     #[instrument(skip(self))]
     pub async fn get_cache_stats(&self) -> Result<CacheStats> {
         let repo_stats = self.repository.get_stats().await?;
-        
+
         // Convert RepoCacheStats to CacheStats (same structure)
         Ok(CacheStats {
             total_tracks: repo_stats.total_tracks,
@@ -635,6 +871,8 @@ impl OfflineCacheManager {
             encrypted_tracks: repo_stats.encrypted_tracks,
             total_plays: repo_stats.total_plays,
             tracks_pending_eviction: repo_stats.tracks_pending_eviction,
+            pinned_tracks: repo_stats.pinned_tracks,
+            pinned_bytes: repo_stats.pinned_bytes,
             calculated_at: repo_stats.calculated_at,
         })
     }
@@ -645,6 +883,55 @@ impl OfflineCacheManager {
         Ok(stats.total_bytes)
     }
 
+    /// Compare the database-tracked cache size with what is actually on disk.
+    ///
+    /// Discrepancies are logged and returned; nothing is repaired.
+    #[instrument(skip(self))]
+    pub async fn reconcile(&self) -> Result<CacheReconciliation> {
+        let cache_base = self
+            .cache_base_path
+            .lock()
+            .await
+            .clone()
+            .ok_or_else(|| PlaybackError::CacheError("Cache not initialized".to_string()))?;
+
+        let mut report = CacheReconciliation::default();
+        for track in self.list_cached_tracks().await? {
+            report.tracked_bytes += track.cached_size;
+
+            let exists = self
+                .fs
+                .exists(&cache_file_path(&cache_base, &track.cache_path))
+                .await
+                .map_err(|e| {
+                    PlaybackError::CacheError(format!("Failed to stat cache file: {}", e))
+                })?;
+            if !exists {
+                report.missing_files.push(track.track_id.to_string());
+            }
+        }
+
+        report.on_disk_bytes = self.fs.directory_size(&cache_base).await.map_err(|e| {
+            PlaybackError::CacheError(format!("Failed to measure cache directory: {}", e))
+        })?;
+
+        if report.is_consistent() {
+            debug!(
+                "Cache database matches disk ({} bytes)",
+                report.on_disk_bytes
+            );
+        } else {
+            warn!(
+                "Cache drift: {} bytes tracked, {} bytes on disk, {} missing files",
+                report.tracked_bytes,
+                report.on_disk_bytes,
+                report.missing_files.len()
+            );
+        }
+
+        Ok(report)
+    }
+
     /// Clear all cached tracks.
     #[instrument(skip(self))]
     pub async fn clear_cache(&self) -> Result<usize> {
@@ -673,9 +960,198 @@ impl OfflineCacheManager {
 
     /// Get list of all cached tracks.
     pub async fn list_cached_tracks(&self) -> Result<Vec<CachedTrack>> {
-        let tracks = self.repository
-            .find_by_status(CacheStatus::Cached)
-            .await?;
+        let tracks = self.repository.find_by_status(CacheStatus::Cached).await?;
         Ok(tracks)
     }
 }
+
+/// Cache file name for a track
+///
+/// Keys are lowercased so they map to the same file on case-sensitive and
+/// case-insensitive filesystems.
+fn cache_file_name(track_id: &TrackId) -> String {
+    format!("{}.cache", track_id).to_lowercase()
+}
+
+/// Prefix marking a `content_hash` that holds the provider's MD5 checksum
+/// rather than a SHA-256 computed locally
+const MD5_PREFIX: &str = "md5:";
+
+/// Whether `data` matches `content_hash`
+fn matches_content_hash(content_hash: &str, data: &[u8]) -> bool {
+    match content_hash.strip_prefix(MD5_PREFIX) {
+        Some(md5) => format!("{:x}", Md5::digest(data)).eq_ignore_ascii_case(md5),
+        None => format!("{:x}", Sha256::digest(data)) == content_hash,
+    }
+}
+
+/// Absolute path of a cache entry, normalized like `cache_file_name`
+fn cache_file_path(cache_base: &Path, cache_path: &str) -> PathBuf {
+    cache_base.join(cache_path.to_lowercase())
+}
+
+/// Fail with `InsufficientSpace` when the volume holding `path` cannot fit
+/// `required` bytes.
+///
+/// Unknown sizes and filesystems that cannot report capacity are assumed to
+/// have room; the write itself will still fail if they don't.
+async fn ensure_available_space(
+    fs: &dyn FileSystemAccess,
+    path: &Path,
+    required: u64,
+) -> Result<()> {
+    if required == 0 {
+        return Ok(());
+    }
+
+    match fs.available_space(path).await {
+        Ok(available) if available < required => {
+            warn!(
+                "Not enough space to cache track: {} bytes required, {} available",
+                required, available
+            );
+            Err(PlaybackError::InsufficientSpace {
+                required,
+                available,
+            })
+        }
+        Ok(_) => Ok(()),
+        Err(BridgeError::NotAvailable(_)) => Ok(()),
+        Err(e) => {
+            warn!("Failed to query available space, skipping check: {}", e);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bridge_traits::error::Result as BridgeResult;
+    use bridge_traits::storage::FileMetadata;
+    use bridge_traits::{DynAsyncRead, DynAsyncWrite};
+
+    /// Filesystem that only reports a fixed amount of free space
+    struct QuotaFileSystem {
+        available: Option<u64>,
+    }
+
+    #[async_trait::async_trait]
+    impl FileSystemAccess for QuotaFileSystem {
+        async fn get_cache_directory(&self) -> BridgeResult<PathBuf> {
+            Ok(PathBuf::from("/cache"))
+        }
+
+        async fn get_data_directory(&self) -> BridgeResult<PathBuf> {
+            Ok(PathBuf::from("/data"))
+        }
+
+        async fn exists(&self, _path: &Path) -> BridgeResult<bool> {
+            Ok(false)
+        }
+
+        async fn metadata(&self, _path: &Path) -> BridgeResult<FileMetadata> {
+            unimplemented!()
+        }
+
+        async fn create_dir_all(&self, _path: &Path) -> BridgeResult<()> {
+            Ok(())
+        }
+
+        async fn read_file(&self, _path: &Path) -> BridgeResult<Bytes> {
+            unimplemented!()
+        }
+
+        async fn write_file(&self, _path: &Path, _data: Bytes) -> BridgeResult<()> {
+            unimplemented!()
+        }
+
+        async fn append_file(&self, _path: &Path, _data: Bytes) -> BridgeResult<()> {
+            unimplemented!()
+        }
+
+        async fn delete_file(&self, _path: &Path) -> BridgeResult<()> {
+            unimplemented!()
+        }
+
+        async fn delete_dir_all(&self, _path: &Path) -> BridgeResult<()> {
+            unimplemented!()
+        }
+
+        async fn list_directory(&self, _path: &Path) -> BridgeResult<Vec<PathBuf>> {
+            unimplemented!()
+        }
+
+        async fn open_read_stream(&self, _path: &Path) -> BridgeResult<Box<DynAsyncRead>> {
+            unimplemented!()
+        }
+
+        async fn open_write_stream(&self, _path: &Path) -> BridgeResult<Box<DynAsyncWrite>> {
+            unimplemented!()
+        }
+
+        async fn available_space(&self, _path: &Path) -> BridgeResult<u64> {
+            self.available
+                .ok_or_else(|| BridgeError::NotAvailable("no capacity API".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_insufficient_space_is_reported() {
+        let fs = QuotaFileSystem {
+            available: Some(1024),
+        };
+
+        let err = ensure_available_space(&fs, Path::new("/cache/tracks"), 4096)
+            .await
+            .unwrap_err();
+
+        match err {
+            PlaybackError::InsufficientSpace {
+                required,
+                available,
+            } => {
+                assert_eq!(required, 4096);
+                assert_eq!(available, 1024);
+            }
+            other => panic!("expected InsufficientSpace, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_space_check_passes_when_track_fits() {
+        let fs = QuotaFileSystem {
+            available: Some(4096),
+        };
+
+        ensure_available_space(&fs, Path::new("/cache/tracks"), 4096)
+            .await
+            .unwrap();
+        // Unknown track size skips the check
+        ensure_available_space(&fs, Path::new("/cache/tracks"), 0)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_unknown_capacity_skips_space_check() {
+        let fs = QuotaFileSystem { available: None };
+
+        ensure_available_space(&fs, Path::new("/cache/tracks"), 4096)
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn test_cache_keys_are_case_normalized() {
+        let track_id = TrackId::new();
+        assert_eq!(
+            cache_file_name(&track_id),
+            cache_file_name(&track_id).to_uppercase().to_lowercase()
+        );
+        assert_eq!(
+            cache_file_path(Path::new("/cache"), "ABC.Cache"),
+            cache_file_path(Path::new("/cache"), "abc.cache")
+        );
+    }
+}
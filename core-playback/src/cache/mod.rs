@@ -61,7 +61,7 @@ pub mod stats;
 pub use config::{CacheConfig, EvictionPolicy};
 pub use encryption::{CacheEncryptor, EncryptionKey};
 pub use manager::OfflineCacheManager;
-pub use stats::{CacheStats, DownloadProgress};
+pub use stats::{CacheReconciliation, CacheStats, DownloadProgress};
 
 // Re-export from core-library
 pub use core_library::models::{CachedTrack, CacheStatus};
@@ -32,6 +32,12 @@ pub struct CacheStats {
     /// Number of tracks that need eviction
     pub tracks_pending_eviction: usize,
 
+    /// Number of pinned tracks, which eviction never removes
+    pub pinned_tracks: usize,
+
+    /// Bytes used by pinned tracks (sum of their cached_size)
+    pub pinned_bytes: u64,
+
     /// Timestamp when stats were calculated
     pub calculated_at: i64,
 }
@@ -58,11 +64,7 @@ impl CacheStats {
 
     /// Calculate space that would be freed (in bytes).
     pub fn space_needed(&self, max_size: u64) -> u64 {
-        if self.total_bytes <= max_size {
-            0
-        } else {
-            self.total_bytes - max_size
-        }
+        self.total_bytes.saturating_sub(max_size)
     }
 
     /// Calculate compression ratio (if encrypted).
@@ -102,6 +104,31 @@ impl CacheStats {
     }
 }
 
+/// Comparison between the cache database and the files actually on disk.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CacheReconciliation {
+    /// Bytes the database records for cached tracks
+    pub tracked_bytes: u64,
+
+    /// Bytes found under the cache directory
+    pub on_disk_bytes: u64,
+
+    /// Tracks marked as cached whose file is missing
+    pub missing_files: Vec<String>,
+}
+
+impl CacheReconciliation {
+    /// On-disk size minus tracked size (positive means untracked files).
+    pub fn drift_bytes(&self) -> i64 {
+        self.on_disk_bytes as i64 - self.tracked_bytes as i64
+    }
+
+    /// Whether the database and the filesystem agree.
+    pub fn is_consistent(&self) -> bool {
+        self.drift_bytes() == 0 && self.missing_files.is_empty()
+    }
+}
+
 /// Download progress information for a specific track.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DownloadProgress {
@@ -162,17 +189,13 @@ impl DownloadProgress {
         }
 
         // Calculate download speed
-        if elapsed > 0 {
-            self.speed_bytes_per_sec = downloaded_bytes / elapsed;
+        if let Some(speed) = downloaded_bytes.checked_div(elapsed) {
+            self.speed_bytes_per_sec = speed;
         }
 
         // Calculate ETA
-        if self.speed_bytes_per_sec > 0 {
-            let remaining_bytes = self.total_bytes.saturating_sub(downloaded_bytes);
-            self.eta_seconds = Some(remaining_bytes / self.speed_bytes_per_sec);
-        } else {
-            self.eta_seconds = None;
-        }
+        let remaining_bytes = self.total_bytes.saturating_sub(downloaded_bytes);
+        self.eta_seconds = remaining_bytes.checked_div(self.speed_bytes_per_sec);
     }
 
     /// Returns true if download is complete.
@@ -234,6 +257,8 @@ mod tests {
             encrypted_tracks: 80,
             total_plays: 400,
             tracks_pending_eviction: 0,
+            pinned_tracks: 0,
+            pinned_bytes: 0,
             calculated_at: chrono::Utc::now().timestamp(),
         };
 
@@ -264,6 +289,8 @@ mod tests {
             encrypted_tracks: 80,
             total_plays: 400,
             tracks_pending_eviction: 0,
+            pinned_tracks: 0,
+            pinned_bytes: 0,
             calculated_at: chrono::Utc::now().timestamp(),
         };
 
@@ -345,4 +372,22 @@ mod tests {
         assert!(progress.eta_seconds.is_some());
         assert!(progress.speed_bytes_per_sec > 0);
     }
+
+    #[test]
+    fn test_cache_reconciliation_drift() {
+        let mut report = CacheReconciliation {
+            tracked_bytes: 1000,
+            on_disk_bytes: 1000,
+            missing_files: Vec::new(),
+        };
+        assert!(report.is_consistent());
+
+        report.on_disk_bytes = 1500;
+        assert_eq!(report.drift_bytes(), 500);
+        assert!(!report.is_consistent());
+
+        report.on_disk_bytes = 1000;
+        report.missing_files.push("track-1".to_string());
+        assert!(!report.is_consistent());
+    }
 }
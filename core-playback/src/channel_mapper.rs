@@ -0,0 +1,247 @@
+//! # Channel Mapping
+//!
+//! Converts decoded audio between channel layouts so it matches the output
+//! device.
+//!
+//! ## Overview
+//!
+//! Decoders emit audio in the source's layout, but playback devices vary: a
+//! 5.1 FLAC has to be downmixed for stereo headphones, and a mono file has to
+//! be duplicated to both speakers. `ChannelMapper` applies a mixing matrix to
+//! each interleaved frame of an `AudioFrameChunk`:
+//!
+//! | Input | Output | Mix |
+//! |-------|--------|-----|
+//! | N | N | Passthrough |
+//! | Mono | Stereo | Duplicate to both channels |
+//! | Stereo | Mono | Average of both channels |
+//! | 5.1 | Stereo | ITU-R BS.775 (`DownmixCoefficients`) |
+//!
+//! 5.1 input is expected in the WAV/Symphonia channel order:
+//! front left, front right, center, LFE, surround left, surround right.
+//!
+//! Mixed samples may exceed [-1.0, 1.0]; the streaming service's final stage
+//! (limiter or clip) brings them back into range.
+
+use crate::error::{PlaybackError, Result};
+use crate::traits::AudioFrameChunk;
+
+/// Gains used when downmixing 5.1 to stereo.
+///
+/// Each front channel passes through at unity gain; the center and LFE are
+/// added to both outputs and each surround channel to its side.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DownmixCoefficients {
+    /// Gain applied to the center channel (default: -3dB)
+    pub center: f32,
+    /// Gain applied to the surround channels (default: -3dB)
+    pub surround: f32,
+    /// Gain applied to the LFE channel (default: 0, discarded)
+    pub lfe: f32,
+}
+
+impl DownmixCoefficients {
+    /// ITU-R BS.775 coefficients: center and surrounds at -3dB, no LFE.
+    pub const ITU_R_BS775: Self = Self {
+        center: std::f32::consts::FRAC_1_SQRT_2,
+        surround: std::f32::consts::FRAC_1_SQRT_2,
+        lfe: 0.0,
+    };
+}
+
+impl Default for DownmixCoefficients {
+    fn default() -> Self {
+        Self::ITU_R_BS775
+    }
+}
+
+/// Remaps interleaved PCM between channel layouts.
+#[derive(Debug, Clone)]
+pub struct ChannelMapper {
+    input_channels: u16,
+    output_channels: u16,
+    /// Row-major gains: `matrix[out * input_channels + in]`
+    matrix: Vec<f32>,
+}
+
+impl ChannelMapper {
+    /// Create a mapper from `input_channels` to `output_channels` using the
+    /// default downmix coefficients.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PlaybackError::InvalidFormat` if either channel count is zero
+    /// or the conversion isn't supported.
+    pub fn new(input_channels: u16, output_channels: u16) -> Result<Self> {
+        Self::with_coefficients(
+            input_channels,
+            output_channels,
+            DownmixCoefficients::default(),
+        )
+    }
+
+    /// Create a mapper with custom 5.1 downmix coefficients.
+    pub fn with_coefficients(
+        input_channels: u16,
+        output_channels: u16,
+        coefficients: DownmixCoefficients,
+    ) -> Result<Self> {
+        let matrix = match (input_channels, output_channels) {
+            (0, _) | (_, 0) => {
+                return Err(PlaybackError::InvalidFormat(
+                    "Channel count must be > 0".to_string(),
+                ))
+            }
+            (input, output) if input == output => identity(input as usize),
+            (1, 2) => vec![1.0, 1.0],
+            (2, 1) => vec![0.5, 0.5],
+            (6, 2) => {
+                let DownmixCoefficients {
+                    center,
+                    surround,
+                    lfe,
+                } = coefficients;
+                vec![
+                    // FL   FR   FC      LFE  SL        SR
+                    1.0, 0.0, center, lfe, surround, 0.0, // Left
+                    0.0, 1.0, center, lfe, 0.0, surround, // Right
+                ]
+            }
+            (input, output) => {
+                return Err(PlaybackError::InvalidFormat(format!(
+                    "Unsupported channel mapping: {} to {} channels",
+                    input, output
+                )))
+            }
+        };
+
+        Ok(Self {
+            input_channels,
+            output_channels,
+            matrix,
+        })
+    }
+
+    /// Number of channels expected in input chunks.
+    pub fn input_channels(&self) -> u16 {
+        self.input_channels
+    }
+
+    /// Number of channels in output chunks.
+    pub fn output_channels(&self) -> u16 {
+        self.output_channels
+    }
+
+    /// Returns `true` if the mapper leaves audio unchanged.
+    pub fn is_passthrough(&self) -> bool {
+        self.input_channels == self.output_channels
+    }
+
+    /// Remap a chunk to the output layout.
+    ///
+    /// The frame count and timestamp are preserved.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PlaybackError::InvalidFormat` if the chunk's sample count
+    /// doesn't match `frames * input_channels`.
+    pub fn map(&self, chunk: AudioFrameChunk) -> Result<AudioFrameChunk> {
+        let input = self.input_channels as usize;
+        let output = self.output_channels as usize;
+
+        if chunk.samples.len() != chunk.frames * input {
+            return Err(PlaybackError::InvalidFormat(format!(
+                "Chunk has {} samples, expected {} frames of {} channels",
+                chunk.samples.len(),
+                chunk.frames,
+                input
+            )));
+        }
+
+        if self.is_passthrough() {
+            return Ok(chunk);
+        }
+
+        let mut samples = Vec::with_capacity(chunk.frames * output);
+        for frame in chunk.samples.chunks_exact(input) {
+            for gains in self.matrix.chunks_exact(input) {
+                let mixed: f32 = frame.iter().zip(gains).map(|(s, g)| s * g).sum();
+                samples.push(mixed);
+            }
+        }
+
+        Ok(AudioFrameChunk::new(samples, chunk.frames, chunk.timestamp))
+    }
+}
+
+fn identity(channels: usize) -> Vec<f32> {
+    (0..channels * channels)
+        .map(|i| if i % (channels + 1) == 0 { 1.0 } else { 0.0 })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_1_SQRT_2;
+    use std::time::Duration;
+
+    fn assert_close(actual: &[f32], expected: &[f32]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-6, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_downmix_5_1_to_stereo() {
+        // Two frames: center only, then every channel at a distinct level
+        let samples = vec![
+            0.0, 0.0, 0.5, 0.0, 0.0, 0.0, // FL FR FC LFE SL SR
+            0.1, 0.2, 0.3, 0.4, 0.2, 0.1,
+        ];
+        let chunk = AudioFrameChunk::new(samples, 2, Duration::from_millis(250));
+
+        let mapper = ChannelMapper::new(6, 2).unwrap();
+        let mixed = mapper.map(chunk.clone()).unwrap();
+        assert_eq!(mixed.frames, 2);
+        assert_eq!(mixed.timestamp, Duration::from_millis(250));
+
+        // Center lands in both channels at -3dB; LFE is discarded
+        let c = 0.5 * FRAC_1_SQRT_2;
+        let left = 0.1 + 0.3 * FRAC_1_SQRT_2 + 0.2 * FRAC_1_SQRT_2;
+        let right = 0.2 + 0.3 * FRAC_1_SQRT_2 + 0.1 * FRAC_1_SQRT_2;
+        assert_close(&mixed.samples, &[c, c, left, right]);
+
+        // A non-zero LFE gain mixes it into both channels
+        let coefficients = DownmixCoefficients {
+            lfe: 0.5,
+            ..DownmixCoefficients::ITU_R_BS775
+        };
+        let mapper = ChannelMapper::with_coefficients(6, 2, coefficients).unwrap();
+        let mixed = mapper.map(chunk).unwrap();
+        assert_close(&mixed.samples, &[c, c, left + 0.2, right + 0.2]);
+    }
+
+    #[test]
+    fn test_mono_upmix_and_passthrough() {
+        let chunk = AudioFrameChunk::new(vec![0.25, -0.5], 2, Duration::ZERO);
+        let stereo = ChannelMapper::new(1, 2).unwrap().map(chunk).unwrap();
+        assert_eq!(stereo.samples, vec![0.25, 0.25, -0.5, -0.5]);
+
+        let mapper = ChannelMapper::new(2, 2).unwrap();
+        assert!(mapper.is_passthrough());
+        assert_eq!(mapper.map(stereo.clone()).unwrap().samples, stereo.samples);
+    }
+
+    #[test]
+    fn test_rejects_unsupported_layouts() {
+        assert!(ChannelMapper::new(6, 4).is_err());
+        assert!(ChannelMapper::new(0, 2).is_err());
+
+        // Sample count must match the input layout
+        let mapper = ChannelMapper::new(6, 2).unwrap();
+        let chunk = AudioFrameChunk::new(vec![0.0; 4], 1, Duration::ZERO);
+        assert!(mapper.map(chunk).is_err());
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! Configuration types for the audio streaming service.
 
+use crate::limiter::LimiterConfig;
+use crate::traits::BufferSizeRange;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
@@ -25,6 +27,21 @@ pub struct StreamingConfig {
     #[serde(default = "default_min_buffer_frames")]
     pub min_buffer_frames: usize,
 
+    /// Audio to buffer before playback starts, overriding `min_buffer_frames`.
+    ///
+    /// Converted to frames at the source's sample rate. Playback also starts
+    /// at end of stream or once `prebuffer_timeout` has passed.
+    ///
+    /// Default: None (use `min_buffer_frames`).
+    #[serde(default)]
+    pub prebuffer_duration: Option<Duration>,
+
+    /// Maximum time to wait for the prebuffer before starting anyway.
+    ///
+    /// Default: 10 seconds.
+    #[serde(default = "default_prebuffer_timeout")]
+    pub prebuffer_timeout: Duration,
+
     /// Buffer level (fraction, 0.0-1.0) that triggers prefetching more data.
     ///
     /// When buffer falls below this level, the service will download and decode
@@ -68,6 +85,72 @@ pub struct StreamingConfig {
     /// Default: true.
     #[serde(default = "default_enable_adaptive_streaming")]
     pub enable_adaptive_streaming: bool,
+
+    /// How many times a transient source failure is retried before the
+    /// stream fails.
+    ///
+    /// Each retry seeks the decoder back to the current decode position,
+    /// which re-fetches the source from there.
+    ///
+    /// Default: 3.
+    #[serde(default = "default_recovery_attempts")]
+    pub recovery_attempts: u32,
+
+    /// Delay before the first recovery attempt; later attempts back off
+    /// exponentially.
+    ///
+    /// Default: 500 milliseconds.
+    #[serde(default = "default_recovery_base_delay")]
+    pub recovery_base_delay: Duration,
+
+    /// Peak limiter applied as the final DSP stage.
+    ///
+    /// When unset, samples outside [-1.0, 1.0] are hard-clipped.
+    ///
+    /// Default: None.
+    #[serde(default)]
+    pub limiter: Option<LimiterConfig>,
+
+    /// How long before the end of the track the service reports that it is
+    /// approaching the end, so the next track can be preloaded.
+    ///
+    /// Should cover any cross-fade plus the time needed to open the next
+    /// source.
+    ///
+    /// Default: 10 seconds.
+    #[serde(default = "default_approaching_end_lead")]
+    pub approaching_end_lead: Duration,
+
+    /// Skip the silent intro and outro detected by silence analysis.
+    ///
+    /// Only applies to requests that carry a `SilenceTrim`.
+    ///
+    /// Default: false.
+    #[serde(default)]
+    pub skip_silence: bool,
+
+    /// Target output latency.
+    ///
+    /// Sizes the device buffer to the closest size the device supports and
+    /// prebuffers two device buffers before playback starts, overriding
+    /// `min_buffer_frames`. Low targets favour responsiveness, high targets
+    /// ride out scheduling hiccups in background playback.
+    ///
+    /// Default: None (device default buffer, prebuffer from `min_buffer_frames`).
+    #[serde(default)]
+    pub latency_target: Option<Duration>,
+
+    /// Overlap between the end of this track and the start of the next.
+    ///
+    /// The end of the track is held back and mixed with equal-power fades
+    /// into the head of the decoder queued with
+    /// `StreamingService::set_next_decoder`. A track shorter than the
+    /// crossfade overlaps for its whole length. Without a next decoder, or if
+    /// it fails to decode, the tracks are joined with a hard cut.
+    ///
+    /// Default: None (hard cut).
+    #[serde(default)]
+    pub crossfade_duration: Option<Duration>,
 }
 
 impl Default for StreamingConfig {
@@ -75,12 +158,21 @@ impl Default for StreamingConfig {
         Self {
             buffer_frames: default_buffer_frames(),
             min_buffer_frames: default_min_buffer_frames(),
+            prebuffer_duration: None,
+            prebuffer_timeout: default_prebuffer_timeout(),
             prefetch_threshold: default_prefetch_threshold(),
             decode_chunk_frames: default_decode_chunk_frames(),
             http_chunk_bytes: default_http_chunk_bytes(),
             http_timeout: default_http_timeout(),
             decode_timeout: default_decode_timeout(),
             enable_adaptive_streaming: default_enable_adaptive_streaming(),
+            recovery_attempts: default_recovery_attempts(),
+            recovery_base_delay: default_recovery_base_delay(),
+            limiter: None,
+            approaching_end_lead: default_approaching_end_lead(),
+            skip_silence: false,
+            latency_target: None,
+            crossfade_duration: None,
         }
     }
 }
@@ -152,6 +244,60 @@ impl StreamingConfig {
     pub fn min_buffer_samples(&self, channels: u16) -> usize {
         self.min_buffer_frames * channels as usize
     }
+
+    /// Calculate the samples to buffer before playback starts.
+    ///
+    /// Uses `prebuffer_duration` when set, then the negotiated `latency`,
+    /// otherwise `min_buffer_frames`.
+    pub fn prebuffer_samples(
+        &self,
+        sample_rate: u32,
+        channels: u16,
+        latency: Option<&OutputLatency>,
+    ) -> usize {
+        match (self.prebuffer_duration, latency) {
+            (Some(duration), _) => {
+                let frames = (duration.as_secs_f64() * sample_rate as f64).ceil() as usize;
+                frames * channels as usize
+            }
+            (None, Some(latency)) => latency.prebuffer_frames * channels as usize,
+            (None, None) => self.min_buffer_samples(channels),
+        }
+    }
+
+    /// Negotiate `latency_target` against the device's supported buffer sizes.
+    ///
+    /// Returns `None` when no target is set.
+    pub fn output_latency(
+        &self,
+        sample_rate: u32,
+        device: BufferSizeRange,
+    ) -> Option<OutputLatency> {
+        let target = self.latency_target?;
+        let sample_rate = sample_rate.max(1);
+        let frames = (target.as_secs_f64() * sample_rate as f64).round();
+        let device_buffer_frames = device.closest(frames.clamp(1.0, u32::MAX as f64) as u32);
+
+        Some(OutputLatency {
+            target,
+            device_buffer_frames,
+            prebuffer_frames: device_buffer_frames as usize * 2,
+            achieved: Duration::from_secs_f64(device_buffer_frames as f64 / sample_rate as f64),
+        })
+    }
+}
+
+/// Output buffering negotiated from `StreamingConfig::latency_target`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputLatency {
+    /// Latency that was requested.
+    pub target: Duration,
+    /// Device buffer size to open the output with, in frames.
+    pub device_buffer_frames: u32,
+    /// Frames to buffer before playback starts.
+    pub prebuffer_frames: usize,
+    /// Latency the device buffer actually adds.
+    pub achieved: Duration,
 }
 
 // ============================================================================
@@ -170,6 +316,10 @@ fn default_prefetch_threshold() -> f32 {
     0.3 // 30%
 }
 
+fn default_prebuffer_timeout() -> Duration {
+    Duration::from_secs(10)
+}
+
 fn default_decode_chunk_frames() -> usize {
     4096 // ~93ms at 44.1kHz
 }
@@ -190,6 +340,18 @@ fn default_enable_adaptive_streaming() -> bool {
     true
 }
 
+fn default_recovery_attempts() -> u32 {
+    3
+}
+
+fn default_recovery_base_delay() -> Duration {
+    Duration::from_millis(500)
+}
+
+fn default_approaching_end_lead() -> Duration {
+    Duration::from_secs(10)
+}
+
 // ============================================================================
 // Streaming State
 // ============================================================================
@@ -207,6 +369,8 @@ pub enum StreamingState {
     Paused,
     /// Stalled due to network issues or buffer underrun.
     Stalled,
+    /// Retrying after a transient source failure.
+    Recovering,
     /// Completed streaming (end of track reached).
     Completed,
     /// Error occurred, service stopped.
@@ -216,7 +380,10 @@ pub enum StreamingState {
 impl StreamingState {
     /// Returns `true` if the service is in an active state.
     pub fn is_active(&self) -> bool {
-        matches!(self, Self::Buffering | Self::Streaming | Self::Paused)
+        matches!(
+            self,
+            Self::Buffering | Self::Streaming | Self::Paused | Self::Recovering
+        )
     }
 
     /// Returns `true` if the service is in a terminal state.
@@ -234,16 +401,23 @@ pub struct StreamingStats {
     pub total_frames_consumed: usize,
     /// Current buffer level in frames.
     pub current_buffer_frames: usize,
+    /// Fraction of the prebuffer filled (0.0 to 1.0); 1.0 once playback
+    /// has started.
+    pub prebuffer_progress: f32,
     /// Total bytes downloaded from network.
     pub total_bytes_downloaded: u64,
     /// Total number of HTTP requests made.
     pub http_requests: u64,
     /// Number of buffer underruns encountered.
     pub underrun_count: u32,
+    /// Samples the ring buffer discarded under its overflow policy.
+    pub dropped_samples: usize,
     /// Average download speed in bytes per second.
     pub avg_download_speed: f64,
     /// Average decode time per chunk in milliseconds.
     pub avg_decode_time_ms: f64,
+    /// Output buffering negotiated from the latency target, if one was set.
+    pub output_latency: Option<OutputLatency>,
 }
 
 impl StreamingStats {
@@ -309,7 +483,6 @@ mod tests {
         // Invalid: threshold out of range
         config.prefetch_threshold = 1.5;
         assert!(config.validate().is_err());
-        config.prefetch_threshold = 0.3;
     }
 
     #[test]
@@ -326,6 +499,41 @@ mod tests {
         assert_eq!(config.buffer_samples(6), 529200);
     }
 
+    #[test]
+    fn test_latency_target_negotiates_device_buffer() {
+        let device = BufferSizeRange::new(256, 2048);
+        let latency = |millis: u64| StreamingConfig {
+            latency_target: Some(Duration::from_millis(millis)),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            StreamingConfig::default().output_latency(48_000, device),
+            None
+        );
+
+        // 1ms is below the device minimum, 1s above its maximum
+        let low = latency(1).output_latency(48_000, device).unwrap();
+        let high = latency(1_000).output_latency(48_000, device).unwrap();
+        assert_eq!(low.device_buffer_frames, 256);
+        assert_eq!(high.device_buffer_frames, 2048);
+        assert_eq!(low.achieved, Duration::from_secs_f64(256.0 / 48_000.0));
+
+        // Prebuffer follows the device buffer unless set explicitly
+        let config = latency(20);
+        let negotiated = config.output_latency(48_000, device).unwrap();
+        assert_eq!(negotiated.device_buffer_frames, 960);
+        assert_eq!(negotiated.achieved, Duration::from_millis(20));
+        assert_eq!(
+            config.prebuffer_samples(48_000, 2, Some(&negotiated)),
+            960 * 2 * 2
+        );
+        assert_eq!(
+            config.prebuffer_samples(48_000, 2, None),
+            config.min_buffer_samples(2)
+        );
+    }
+
     #[test]
     fn test_streaming_state() {
         assert!(StreamingState::Buffering.is_active());
@@ -339,8 +547,10 @@ mod tests {
 
     #[test]
     fn test_streaming_stats() {
-        let mut stats = StreamingStats::default();
-        stats.current_buffer_frames = 50000;
+        let stats = StreamingStats {
+            current_buffer_frames: 50000,
+            ..Default::default()
+        };
 
         assert!((stats.buffer_fill_percentage(100000) - 0.5).abs() < 0.01);
         assert!(!stats.is_buffer_critical(40000));
@@ -0,0 +1,464 @@
+//! # Decode Cache
+//!
+//! Keeps the decoded PCM of short tracks in memory so repeated plays skip
+//! decoding entirely.
+//!
+//! ## Overview
+//!
+//! UI sounds and short, frequently replayed tracks would otherwise be
+//! decoded from scratch on every play. `DecodeCache::open` looks the track up
+//! before a decoder is created:
+//!
+//! - **Hit**: returns a decoder replaying the cached PCM; the decoder
+//!   factory is never called.
+//! - **Miss**: calls the factory and wraps the decoder so that, once it
+//!   reaches the end of the stream, the decoded audio is stored.
+//!
+//! Only tracks under `max_track_duration` and `max_track_bytes` are
+//! recorded; longer tracks, seeks and decode errors abandon the recording.
+//! Entries are evicted through the shared [`LruCache`] once their total size
+//! exceeds `max_bytes`.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use core_playback::{
+//!     AudioDecoder, AudioSource, DecodeCache, DecodeCacheConfig, DecodeCacheKey,
+//!     SymphoniaDecoder,
+//! };
+//!
+//! # async fn example(source: AudioSource) -> core_playback::Result<()> {
+//! let cache = DecodeCache::new(DecodeCacheConfig::default());
+//!
+//! let key = DecodeCacheKey::new(&source, &());
+//! let mut decoder = cache
+//!     .open(key, || SymphoniaDecoder::new(source.clone()))
+//!     .await?;
+//! let probe = decoder.probe().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::Result;
+use crate::traits::{AudioDecoder, AudioFrameChunk, AudioSource, ProbeResult};
+use async_trait::async_trait;
+use bridge_traits::platform::PlatformSend;
+use core_runtime::cache::{CacheMetrics, LruCache};
+use sha2::{Digest, Sha256};
+use std::future::Future;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::debug;
+
+/// Limits for the decode cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeCacheConfig {
+    /// Total size of cached PCM in bytes (default: 64 MiB)
+    pub max_bytes: u64,
+    /// Longest track that is cached (default: 30 seconds)
+    pub max_track_duration: Duration,
+    /// Largest decoded track that is cached, in bytes (default: 16 MiB)
+    pub max_track_bytes: u64,
+}
+
+impl Default for DecodeCacheConfig {
+    fn default() -> Self {
+        Self {
+            max_bytes: 64 * 1024 * 1024,
+            max_track_duration: Duration::from_secs(30),
+            max_track_bytes: 16 * 1024 * 1024,
+        }
+    }
+}
+
+/// Identifies decoded audio: the source plus the parameters it was decoded
+/// with.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DecodeCacheKey {
+    source: String,
+    params: u64,
+}
+
+impl DecodeCacheKey {
+    /// Build a key for `source` decoded with `params`.
+    ///
+    /// `params` should cover everything that changes the decoded PCM (e.g.
+    /// `DecoderOptions`); pass `&()` when there is nothing to distinguish.
+    /// In-memory sources are identified by a hash of their contents.
+    pub fn new<P: Hash + ?Sized>(source: &AudioSource, params: &P) -> Self {
+        let source = match source {
+            AudioSource::LocalFile { path } => format!("file:{}", path.display()),
+            AudioSource::RemoteStream { url, .. } => format!("url:{}", url),
+            AudioSource::CachedChunk { data, .. } => {
+                format!("data:{}", hex::encode(Sha256::digest(data)))
+            }
+        };
+
+        let mut hasher = DefaultHasher::new();
+        params.hash(&mut hasher);
+
+        Self {
+            source,
+            params: hasher.finish(),
+        }
+    }
+}
+
+/// Fully decoded track held by the cache
+#[derive(Debug)]
+struct DecodedTrack {
+    probe: ProbeResult,
+    samples: Vec<f32>,
+}
+
+impl DecodedTrack {
+    fn size_bytes(&self) -> u64 {
+        (self.samples.len() * std::mem::size_of::<f32>()) as u64
+    }
+}
+
+/// Bounded in-memory cache of decoded PCM for short tracks.
+///
+/// Cloning is cheap; clones share the same entries.
+#[derive(Clone)]
+pub struct DecodeCache {
+    config: DecodeCacheConfig,
+    tracks: Arc<LruCache<DecodeCacheKey, Arc<DecodedTrack>>>,
+}
+
+impl DecodeCache {
+    /// Create an empty cache with the given limits.
+    pub fn new(config: DecodeCacheConfig) -> Self {
+        let tracks = LruCache::weighted(config.max_bytes, |_key, track: &Arc<DecodedTrack>| {
+            track.size_bytes()
+        });
+        Self {
+            config,
+            tracks: Arc::new(tracks),
+        }
+    }
+
+    /// Cache limits.
+    pub fn config(&self) -> &DecodeCacheConfig {
+        &self.config
+    }
+
+    /// Hit/miss counters and current size.
+    pub fn metrics(&self) -> CacheMetrics {
+        self.tracks.metrics()
+    }
+
+    /// Returns `true` if decoded audio for `key` is cached.
+    pub fn contains(&self, key: &DecodeCacheKey) -> bool {
+        self.tracks.contains_key(key)
+    }
+
+    /// Drop every cached track.
+    pub fn clear(&self) {
+        self.tracks.clear();
+    }
+
+    /// Get a decoder for `key`, creating one with `open_decoder` only on a
+    /// cache miss.
+    ///
+    /// On a miss the new decoder is wrapped so that a complete, linear
+    /// decode of a short track is stored for the next play.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from `open_decoder`.
+    pub async fn open<D, F, Fut>(
+        &self,
+        key: DecodeCacheKey,
+        open_decoder: F,
+    ) -> Result<Box<dyn AudioDecoder>>
+    where
+        D: AudioDecoder + PlatformSend + 'static,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<D>>,
+    {
+        if let Some(track) = self.tracks.get(&key) {
+            debug!("Serving {} from decode cache", key.source);
+            return Ok(Box::new(CachedDecoder { track, position: 0 }));
+        }
+
+        let decoder = open_decoder().await?;
+        Ok(Box::new(RecordingDecoder {
+            inner: decoder,
+            cache: self.clone(),
+            key: Some(key),
+            probe: None,
+            samples: Vec::new(),
+        }))
+    }
+}
+
+/// Replays a cached track
+struct CachedDecoder {
+    track: Arc<DecodedTrack>,
+    /// Next frame to return
+    position: usize,
+}
+
+impl CachedDecoder {
+    fn channels(&self) -> usize {
+        self.track.probe.format.channels.max(1) as usize
+    }
+
+    fn total_frames(&self) -> usize {
+        self.track.samples.len() / self.channels()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl AudioDecoder for CachedDecoder {
+    async fn probe(&mut self) -> Result<ProbeResult> {
+        Ok(self.track.probe.clone())
+    }
+
+    async fn decode_frames(&mut self, max_frames: usize) -> Result<Option<AudioFrameChunk>> {
+        let frames = max_frames.min(self.total_frames() - self.position);
+        if frames == 0 {
+            return Ok(None);
+        }
+
+        let channels = self.channels();
+        let start = self.position * channels;
+        let samples = self.track.samples[start..start + frames * channels].to_vec();
+        let timestamp = Duration::from_secs_f64(
+            self.position as f64 / self.track.probe.format.sample_rate as f64,
+        );
+
+        self.position += frames;
+        Ok(Some(AudioFrameChunk::new(samples, frames, timestamp)))
+    }
+
+    async fn seek(&mut self, position: Duration) -> Result<Duration> {
+        let sample_rate = self.track.probe.format.sample_rate as f64;
+        let frame = position.as_secs_f64() * sample_rate;
+        self.position = (frame as usize).min(self.total_frames());
+        Ok(Duration::from_secs_f64(self.position as f64 / sample_rate))
+    }
+}
+
+/// Passes through to a real decoder while recording its output for the
+/// cache
+struct RecordingDecoder<D> {
+    inner: D,
+    cache: DecodeCache,
+    /// `None` once the track turned out to be uncacheable
+    key: Option<DecodeCacheKey>,
+    probe: Option<ProbeResult>,
+    samples: Vec<f32>,
+}
+
+impl<D> RecordingDecoder<D> {
+    fn abandon(&mut self, reason: &str) {
+        if let Some(key) = self.key.take() {
+            debug!("Not caching {}: {}", key.source, reason);
+            self.samples = Vec::new();
+        }
+    }
+
+    fn record(&mut self, chunk: &AudioFrameChunk) {
+        if self.key.is_none() {
+            return;
+        }
+        let Some(probe) = &self.probe else {
+            self.abandon("decoded before probing");
+            return;
+        };
+
+        self.samples.extend_from_slice(&chunk.samples);
+
+        let channels = probe.format.channels.max(1) as usize;
+        let duration = Duration::from_secs_f64(
+            (self.samples.len() / channels) as f64 / probe.format.sample_rate as f64,
+        );
+        let bytes = (self.samples.len() * std::mem::size_of::<f32>()) as u64;
+        if duration > self.cache.config.max_track_duration {
+            self.abandon("track too long");
+        } else if bytes > self.cache.config.max_track_bytes {
+            self.abandon("track too large");
+        }
+    }
+
+    fn store(&mut self) {
+        let (Some(key), Some(probe)) = (self.key.take(), self.probe.take()) else {
+            return;
+        };
+
+        let channels = probe.format.channels.max(1) as usize;
+        let duration = Duration::from_secs_f64(
+            (self.samples.len() / channels) as f64 / probe.format.sample_rate as f64,
+        );
+        let track = DecodedTrack {
+            probe: probe.with_duration(Some(duration)),
+            samples: std::mem::take(&mut self.samples),
+        };
+
+        debug!(
+            "Caching {} bytes of decoded audio for {}",
+            track.size_bytes(),
+            key.source
+        );
+        self.cache.tracks.insert(key, Arc::new(track));
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<D> AudioDecoder for RecordingDecoder<D>
+where
+    D: AudioDecoder + PlatformSend,
+{
+    async fn probe(&mut self) -> Result<ProbeResult> {
+        let probe = self.inner.probe().await?;
+        if probe
+            .duration
+            .is_some_and(|duration| duration > self.cache.config.max_track_duration)
+        {
+            self.abandon("track too long");
+        } else if self.key.is_some() {
+            self.probe = Some(probe.clone());
+        }
+        Ok(probe)
+    }
+
+    async fn decode_frames(&mut self, max_frames: usize) -> Result<Option<AudioFrameChunk>> {
+        match self.inner.decode_frames(max_frames).await {
+            Ok(Some(chunk)) => {
+                self.record(&chunk);
+                Ok(Some(chunk))
+            }
+            Ok(None) => {
+                self.store();
+                Ok(None)
+            }
+            Err(e) => {
+                self.abandon("decode error");
+                Err(e)
+            }
+        }
+    }
+
+    async fn seek(&mut self, position: Duration) -> Result<Duration> {
+        // The recording must cover the track linearly from the start
+        self.abandon("seeked");
+        self.inner.seek(position).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::AudioFormat;
+    use bytes::Bytes;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Stereo decoder producing `frames` frames of a ramp in 100-frame chunks
+    struct RampDecoder {
+        frames: usize,
+        position: usize,
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+    impl AudioDecoder for RampDecoder {
+        async fn probe(&mut self) -> Result<ProbeResult> {
+            let duration = Duration::from_secs_f64(self.frames as f64 / 44_100.0);
+            Ok(ProbeResult::new(AudioFormat::cd_quality()).with_duration(Some(duration)))
+        }
+
+        async fn decode_frames(&mut self, max_frames: usize) -> Result<Option<AudioFrameChunk>> {
+            let frames = max_frames.min(100).min(self.frames - self.position);
+            if frames == 0 {
+                return Ok(None);
+            }
+            let samples = (self.position..self.position + frames)
+                .flat_map(|frame| [frame as f32 / 1e6; 2])
+                .collect();
+            let timestamp = Duration::from_secs_f64(self.position as f64 / 44_100.0);
+            self.position += frames;
+            Ok(Some(AudioFrameChunk::new(samples, frames, timestamp)))
+        }
+
+        async fn seek(&mut self, position: Duration) -> Result<Duration> {
+            Ok(position)
+        }
+    }
+
+    fn source(name: &'static str) -> AudioSource {
+        AudioSource::CachedChunk {
+            data: Bytes::from_static(name.as_bytes()),
+            codec_hint: None,
+        }
+    }
+
+    /// Open `frames` frames through the cache and decode them all, counting
+    /// how often a real decoder was created
+    async fn play(
+        cache: &DecodeCache,
+        key: DecodeCacheKey,
+        frames: usize,
+        opened: &AtomicUsize,
+    ) -> Vec<f32> {
+        let mut decoder = cache
+            .open(key, || async {
+                opened.fetch_add(1, Ordering::SeqCst);
+                Ok(RampDecoder {
+                    frames,
+                    position: 0,
+                })
+            })
+            .await
+            .unwrap();
+
+        decoder.probe().await.unwrap();
+        let mut samples = Vec::new();
+        while let Some(chunk) = decoder.decode_frames(64).await.unwrap() {
+            samples.extend(chunk.samples);
+        }
+        samples
+    }
+
+    #[tokio::test]
+    async fn test_short_track_replays_from_cache() {
+        let cache = DecodeCache::new(DecodeCacheConfig::default());
+        let key = DecodeCacheKey::new(&source("click.wav"), &());
+        let opened = AtomicUsize::new(0);
+
+        let first = play(&cache, key.clone(), 1_000, &opened).await;
+        assert!(cache.contains(&key));
+
+        let second = play(&cache, key.clone(), 1_000, &opened).await;
+        assert_eq!(opened.load(Ordering::SeqCst), 1);
+        assert_eq!(first.len(), 2_000);
+        assert_eq!(first, second);
+        assert_eq!(cache.metrics().hits, 1);
+
+        // Different decode parameters are a different entry
+        let other = DecodeCacheKey::new(&source("click.wav"), &48_000u32);
+        play(&cache, other, 1_000, &opened).await;
+        assert_eq!(opened.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_long_track_bypasses_cache() {
+        let config = DecodeCacheConfig {
+            max_track_duration: Duration::from_millis(100),
+            ..Default::default()
+        };
+        let cache = DecodeCache::new(config);
+        let key = DecodeCacheKey::new(&source("song.flac"), &());
+        let opened = AtomicUsize::new(0);
+
+        // 10,000 frames at 44.1kHz is ~227ms
+        play(&cache, key.clone(), 10_000, &opened).await;
+        play(&cache, key.clone(), 10_000, &opened).await;
+        assert_eq!(opened.load(Ordering::SeqCst), 2);
+        assert!(!cache.contains(&key));
+    }
+}
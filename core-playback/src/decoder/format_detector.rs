@@ -216,13 +216,13 @@ mod tests {
     #[test]
     fn test_hint_from_path() {
         let path = Path::new("/music/song.mp3");
-        let hint = FormatDetector::hint_from_path(path);
+        let _hint = FormatDetector::hint_from_path(path);
         // Hint is opaque, but should not panic
     }
 
     #[test]
     fn test_hint_from_mime_type() {
-        let hint = FormatDetector::hint_from_mime_type("audio/mpeg");
+        let _hint = FormatDetector::hint_from_mime_type("audio/mpeg");
         // Hint is opaque, but should not panic
     }
 
@@ -80,13 +80,13 @@ mod sample_converter;
 mod symphonia;
 
 #[cfg(feature = "core-decoder")]
-pub use self::symphonia::SymphoniaDecoder;
+pub use self::symphonia::{DecoderOptions, SeekPrecision, SymphoniaDecoder};
 
 #[cfg(feature = "core-decoder")]
 pub use format_detector::FormatDetector;
 
 #[cfg(feature = "core-decoder")]
-pub use sample_converter::SampleConverter;
+pub use sample_converter::{Dither, DitherConfig, DitherMode, SampleConverter};
 
 // Re-export when decoder is not available
 #[cfg(not(feature = "core-decoder"))]
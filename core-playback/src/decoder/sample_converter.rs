@@ -1,21 +1,326 @@
 //! # Sample Format Converter
 //!
 //! Converts audio samples between different formats and layouts.
+//!
+//! ## Dithering
+//!
+//! Rounding f32 samples straight to integers makes the quantization error a
+//! deterministic function of the signal, which is heard as distortion on
+//! quiet passages. `Dither` adds TPDF (triangular probability density) noise
+//! of ±1 LSB before rounding, which decorrelates the error from the signal and
+//! leaves a constant, benign noise floor instead. Optional first-order noise
+//! shaping feeds the previous error back so more of that noise sits at high
+//! frequencies.
+//!
+//! ## Resampling
+//!
+//! Output devices often run at one fixed rate (e.g., 48kHz). A converter
+//! created with `SampleConverter::with_target_rate` interpolates each output
+//! frame from a Blackman-windowed sinc kernel spanning 16 zero crossings on
+//! either side. When downsampling, the kernel is stretched to cut off at the
+//! output's Nyquist frequency so nothing above it aliases. The kernel needs
+//! input past the frame it produces, which delays output by its half-width;
+//! see `SampleConverter::latency`.
 
-use crate::error::Result;
+use crate::error::{PlaybackError, Result};
+use std::f64::consts::PI;
+use std::time::Duration;
 use symphonia::core::audio::{AudioBuffer, AudioBufferRef, Signal};
 use symphonia::core::conv::IntoSample;
 use symphonia::core::sample::Sample;
-use tracing::warn;
+use tracing::{debug, warn};
+
+/// How quantization to integer samples is dithered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DitherMode {
+    /// Round to the nearest integer (no dithering)
+    None,
+    /// Triangular PDF dither of ±1 LSB
+    #[default]
+    Tpdf,
+    /// TPDF dither with first-order error-feedback noise shaping
+    TpdfNoiseShaped,
+}
+
+/// Dithering configuration for integer output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DitherConfig {
+    /// Dither algorithm
+    pub mode: DitherMode,
+    /// Target bit depth (2-32); quantized values span this many bits
+    pub bit_depth: u8,
+}
+
+impl Default for DitherConfig {
+    fn default() -> Self {
+        Self {
+            mode: DitherMode::default(),
+            bit_depth: 16,
+        }
+    }
+}
+
+/// Stateful quantizer converting f32 samples to integers with dithering.
+///
+/// Keeps the random generator and per-channel noise shaping state, so one
+/// instance should be used for the whole stream.
+#[derive(Debug, Clone)]
+pub struct Dither {
+    config: DitherConfig,
+    rng: u64,
+    /// Previous quantization error per channel (noise shaping)
+    errors: Vec<f64>,
+}
+
+impl Dither {
+    const DEFAULT_SEED: u64 = 0x9E37_79B9_7F4A_7C15;
+
+    /// Create a quantizer for interleaved audio with `channels` channels.
+    ///
+    /// `bit_depth` is clamped to 2-32.
+    pub fn new(config: DitherConfig, channels: u16) -> Self {
+        Self {
+            config: DitherConfig {
+                bit_depth: config.bit_depth.clamp(2, 32),
+                ..config
+            },
+            rng: Self::DEFAULT_SEED,
+            errors: vec![0.0; channels.max(1) as usize],
+        }
+    }
+
+    /// Seed the dither noise generator (for reproducible output).
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        // xorshift state must be non-zero
+        self.rng = seed.max(1);
+        self
+    }
+
+    /// The effective configuration.
+    pub fn config(&self) -> DitherConfig {
+        self.config
+    }
+
+    /// Quantize interleaved samples to signed integers of `bit_depth` bits.
+    ///
+    /// Values are in `[-2^(bit_depth-1), 2^(bit_depth-1) - 1]`; input outside
+    /// [-1.0, 1.0] is clipped.
+    pub fn quantize(&mut self, samples: &[f32]) -> Vec<i32> {
+        let scale = (1u64 << (self.config.bit_depth - 1)) as f64;
+        let (min, max) = (-scale, scale - 1.0);
+        let channels = self.errors.len();
+
+        samples
+            .iter()
+            .enumerate()
+            .map(|(i, &sample)| {
+                let target = sample as f64 * scale;
+                let shaped = match self.config.mode {
+                    DitherMode::TpdfNoiseShaped => target - self.errors[i % channels],
+                    _ => target,
+                };
+                let noise = match self.config.mode {
+                    DitherMode::None => 0.0,
+                    DitherMode::Tpdf | DitherMode::TpdfNoiseShaped => self.tpdf(),
+                };
+                let quantized = (shaped + noise).round().clamp(min, max);
+                self.errors[i % channels] = quantized - shaped;
+                quantized as i32
+            })
+            .collect()
+    }
+
+    /// Triangular noise in (-1, 1) LSB: the sum of two uniform values
+    fn tpdf(&mut self) -> f64 {
+        self.uniform() + self.uniform() - 1.0
+    }
+
+    /// Uniform value in [0, 1) from a xorshift64* generator
+    fn uniform(&mut self) -> f64 {
+        self.rng ^= self.rng >> 12;
+        self.rng ^= self.rng << 25;
+        self.rng ^= self.rng >> 27;
+        let value = self.rng.wrapping_mul(0x2545_F491_4F6C_DD1D);
+        (value >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Windowed-sinc sample rate converter for interleaved f32 audio.
+#[derive(Debug, Clone)]
+struct Resampler {
+    input_rate: u32,
+    channels: usize,
+    /// Input frames per output frame
+    step: f64,
+    /// Lowpass cutoff as a fraction of the input Nyquist frequency
+    cutoff: f64,
+    /// Kernel half-width in input frames
+    half_width: usize,
+    /// Input still needed by the kernel, interleaved
+    history: Vec<f32>,
+    /// Position of the next output frame, in input frames into `history`
+    position: f64,
+}
+
+impl Resampler {
+    const ZERO_CROSSINGS: usize = 16;
+
+    fn new(input_rate: u32, output_rate: u32, channels: u16) -> Self {
+        let cutoff = (output_rate as f64 / input_rate as f64).min(1.0);
+        let half_width = (Self::ZERO_CROSSINGS as f64 / cutoff).ceil() as usize;
+        let channels = channels.max(1) as usize;
+        Self {
+            input_rate,
+            channels,
+            step: input_rate as f64 / output_rate as f64,
+            cutoff,
+            half_width,
+            // Silence before the first frame lets output start at time zero
+            history: vec![0.0; half_width * channels],
+            position: half_width as f64,
+        }
+    }
+
+    fn process(&mut self, input: &[f32]) -> Vec<f32> {
+        self.history.extend_from_slice(input);
+        let frames = self.history.len() / self.channels;
+        let mut output = Vec::new();
+
+        while self.position as usize + self.half_width < frames {
+            let center = self.position as usize;
+            let start = output.len();
+            output.resize(start + self.channels, 0.0);
+            for tap in center + 1 - self.half_width..=center + self.half_width {
+                let weight = self.kernel(tap as f64 - self.position) as f32;
+                let frame = &self.history[tap * self.channels..(tap + 1) * self.channels];
+                for (out, &sample) in output[start..].iter_mut().zip(frame) {
+                    *out += sample * weight;
+                }
+            }
+            self.position += self.step;
+        }
+
+        // Keep only the input the next output frame reaches back to
+        let consumed = (self.position as usize + 1).saturating_sub(self.half_width);
+        self.history.drain(..consumed * self.channels);
+        self.position -= consumed as f64;
+        output
+    }
+
+    /// Push silence through the kernel to emit the frames it still holds
+    fn flush(&mut self) -> Vec<f32> {
+        let tail = vec![0.0; self.half_width * self.channels];
+        self.process(&tail)
+    }
+
+    /// Windowed sinc at `x` input frames from the output position
+    fn kernel(&self, x: f64) -> f64 {
+        let width = self.half_width as f64;
+        if x.abs() >= width {
+            return 0.0;
+        }
+        let sinc = if x == 0.0 {
+            1.0
+        } else {
+            let arg = PI * x * self.cutoff;
+            arg.sin() / arg
+        };
+        let phase = PI * x / width;
+        let window = 0.42 + 0.5 * phase.cos() + 0.08 * (2.0 * phase).cos();
+        self.cutoff * sinc * window
+    }
+
+    fn latency(&self) -> Duration {
+        Duration::from_secs_f64(self.half_width as f64 / self.input_rate as f64)
+    }
+}
 
 /// Sample converter that normalizes audio to f32 interleaved format.
 ///
 /// Symphonia outputs audio in various formats (i16, i24, i32, f32, f64)
 /// and layouts (planar, interleaved). This converter normalizes everything
 /// to interleaved f32 samples in the range [-1.0, 1.0].
-pub struct SampleConverter;
+///
+/// An instance created with `with_target_rate` also converts the sample
+/// rate, keeping filter state between chunks, so one instance should be
+/// used for the whole stream.
+#[derive(Debug, Clone, Default)]
+pub struct SampleConverter {
+    target_rate: Option<u32>,
+    resampler: Option<Resampler>,
+}
 
 impl SampleConverter {
+    /// Create a converter resampling to `target_rate` Hz.
+    ///
+    /// Input at the target rate passes through untouched.
+    pub fn with_target_rate(target_rate: u32) -> Self {
+        Self {
+            target_rate: Some(target_rate.max(1)),
+            resampler: None,
+        }
+    }
+
+    /// The output sample rate, if the converter resamples.
+    pub fn target_rate(&self) -> Option<u32> {
+        self.target_rate
+    }
+
+    /// Resample interleaved `samples` recorded at `sample_rate` to the target
+    /// rate.
+    ///
+    /// A chunk with a different rate or channel count than the previous one
+    /// starts a new stream; call `flush` at the end of each track first, or
+    /// its last few milliseconds are dropped. Without a target rate, the
+    /// samples are returned as they are.
+    pub fn resample(&mut self, samples: &[f32], sample_rate: u32, channels: u16) -> Vec<f32> {
+        let target_rate = match self.target_rate {
+            Some(rate) if sample_rate != 0 && rate != sample_rate => rate,
+            _ => {
+                self.resampler = None;
+                return samples.to_vec();
+            }
+        };
+
+        let resampler = match self.resampler.take() {
+            Some(resampler)
+                if resampler.input_rate == sample_rate
+                    && resampler.channels == channels.max(1) as usize =>
+            {
+                resampler
+            }
+            _ => {
+                debug!(
+                    "Resampling {}Hz {}-channel audio to {}Hz",
+                    sample_rate, channels, target_rate
+                );
+                Resampler::new(sample_rate, target_rate, channels)
+            }
+        };
+        self.resampler.insert(resampler).process(samples)
+    }
+
+    /// Emit the audio still held by the resampling filter.
+    ///
+    /// Call at the end of a stream; the converter is then ready for the next.
+    pub fn flush(&mut self) -> Vec<f32> {
+        self.resampler
+            .take()
+            .map(|mut resampler| resampler.flush())
+            .unwrap_or_default()
+    }
+
+    /// Delay the resampling filter adds between input and output.
+    ///
+    /// Zero until the first chunk is resampled, and while the input is
+    /// already at the target rate.
+    pub fn latency(&self) -> Duration {
+        self.resampler
+            .as_ref()
+            .map(Resampler::latency)
+            .unwrap_or_default()
+    }
+
     /// Convert Symphonia AudioBufferRef to interleaved f32 samples.
     ///
     /// This is the main conversion function that handles all sample formats
@@ -43,7 +348,7 @@ impl SampleConverter {
         match buffer {
             AudioBufferRef::F32(buf) => {
                 // Already f32, just interleave if needed
-                Ok(Self::interleave_f32_planes(&**buf))
+                Ok(Self::interleave_f32_planes(buf))
             }
             AudioBufferRef::F64(buf) => {
                 // Convert from f64 to f32
@@ -70,7 +375,7 @@ impl SampleConverter {
                 // Convert from i24 to f32
                 Ok(Self::convert_and_interleave(
                     &**buf,
-                    |sample| IntoSample::into_sample(sample),
+                    IntoSample::into_sample,
                 ))
             }
             AudioBufferRef::S8(buf) => {
@@ -98,7 +403,7 @@ impl SampleConverter {
                 // Convert from u24 to f32
                 Ok(Self::convert_and_interleave(
                     &**buf,
-                    |sample| IntoSample::into_sample(sample),
+                    IntoSample::into_sample,
                 ))
             }
             AudioBufferRef::U8(buf) => {
@@ -169,7 +474,7 @@ impl SampleConverter {
     pub fn validate_samples(samples: &[f32]) -> usize {
         let clipped = samples
             .iter()
-            .filter(|&&s| s < -1.0 || s > 1.0)
+            .filter(|&&s| !(-1.0..=1.0).contains(&s))
             .count();
 
         if clipped > 0 {
@@ -192,6 +497,32 @@ impl SampleConverter {
             *sample = sample.clamp(-1.0, 1.0);
         }
     }
+
+    /// Convert f32 samples to i16 for platform audio output.
+    ///
+    /// Samples are quantized at `dither`'s bit depth and scaled to the i16
+    /// range, so a lower bit depth (e.g., 8) emulates that resolution. Use
+    /// `DitherMode::None` for plain rounding.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PlaybackError::InvalidFormat` if the bit depth exceeds 16.
+    pub fn to_i16(samples: &[f32], dither: &mut Dither) -> Result<Vec<i16>> {
+        let bit_depth = dither.config().bit_depth;
+        if bit_depth > 16 {
+            return Err(PlaybackError::InvalidFormat(format!(
+                "Cannot quantize {}-bit samples to i16",
+                bit_depth
+            )));
+        }
+
+        let shift = 16 - bit_depth;
+        Ok(dither
+            .quantize(samples)
+            .into_iter()
+            .map(|value| (value << shift) as i16)
+            .collect())
+    }
 }
 
 #[cfg(test)]
@@ -223,4 +554,170 @@ mod tests {
         assert_eq!(samples[3], 0.5);
         assert_eq!(samples[4], -0.5);
     }
+
+    /// Quiet sine (0.4 LSB at 16 bits) and its quantization error in LSBs
+    fn quantization_error(mode: DitherMode) -> (Vec<f64>, Vec<f64>) {
+        let lsb = 1.0 / 32768.0;
+        let signal: Vec<f32> = (0..48_000)
+            .map(|i| (0.4 * lsb * (i as f64 * 2.0 * std::f64::consts::PI / 48.0).sin()) as f32)
+            .collect();
+
+        let mut dither = Dither::new(
+            DitherConfig {
+                mode,
+                bit_depth: 16,
+            },
+            1,
+        )
+        .with_seed(42);
+        let output = SampleConverter::to_i16(&signal, &mut dither).unwrap();
+
+        let signal: Vec<f64> = signal.iter().map(|&s| s as f64 / lsb).collect();
+        let error = output
+            .iter()
+            .zip(&signal)
+            .map(|(&q, &s)| q as f64 - s)
+            .collect();
+        (signal, error)
+    }
+
+    fn correlation(a: &[f64], b: &[f64]) -> f64 {
+        let dot = |x: &[f64], y: &[f64]| x.iter().zip(y).map(|(x, y)| x * y).sum::<f64>();
+        dot(a, b) / (dot(a, a) * dot(b, b)).sqrt()
+    }
+
+    #[test]
+    fn test_tpdf_dither_decorrelates_quantization_error() {
+        // Without dither the quiet sine rounds to silence: the error is
+        // exactly the inverted signal
+        let (signal, error) = quantization_error(DitherMode::None);
+        assert!(correlation(&signal, &error) < -0.99);
+
+        // With TPDF dither the error is uncorrelated noise with zero mean and
+        // a variance of 1/4 LSB² (1/12 rounding + 1/6 triangular dither)
+        let (signal, error) = quantization_error(DitherMode::Tpdf);
+        let mean = error.iter().sum::<f64>() / error.len() as f64;
+        let variance = error.iter().map(|e| (e - mean).powi(2)).sum::<f64>() / error.len() as f64;
+        assert!(correlation(&signal, &error).abs() < 0.03);
+        assert!(mean.abs() < 0.02, "mean {}", mean);
+        assert!((0.22..0.28).contains(&variance), "variance {}", variance);
+    }
+
+    #[test]
+    fn test_to_i16_bit_depth() {
+        let mut dither = Dither::new(
+            DitherConfig {
+                mode: DitherMode::None,
+                bit_depth: 8,
+            },
+            2,
+        );
+        let output = SampleConverter::to_i16(&[0.5, -1.0, 1.0, 0.0], &mut dither).unwrap();
+        assert_eq!(output, vec![64 << 8, -128 << 8, 127 << 8, 0]);
+
+        let mut dither = Dither::new(
+            DitherConfig {
+                bit_depth: 24,
+                ..Default::default()
+            },
+            2,
+        );
+        assert!(SampleConverter::to_i16(&[0.0], &mut dither).is_err());
+    }
+
+    /// Stereo sine at `frequency` Hz, `frames` long
+    fn sine(frequency: f64, sample_rate: u32, frames: usize) -> Vec<f32> {
+        (0..frames)
+            .flat_map(|i| {
+                let sample = (2.0 * PI * frequency * i as f64 / sample_rate as f64).sin() as f32;
+                [0.5 * sample; 2]
+            })
+            .collect()
+    }
+
+    /// Frequency of the left channel from the span between its first and last
+    /// rising zero crossings
+    fn measure_frequency(samples: &[f32], sample_rate: u32) -> f64 {
+        let left: Vec<f64> = samples.iter().step_by(2).map(|&s| s as f64).collect();
+        let crossings: Vec<f64> = left
+            .windows(2)
+            .enumerate()
+            .filter(|(_, pair)| pair[0] < 0.0 && pair[1] >= 0.0)
+            .map(|(i, pair)| i as f64 + pair[0] / (pair[0] - pair[1]))
+            .collect();
+        let span = crossings.last().unwrap() - crossings.first().unwrap();
+        (crossings.len() - 1) as f64 * sample_rate as f64 / span
+    }
+
+    /// Resample in decoder-sized chunks, then flush
+    fn resample_all(converter: &mut SampleConverter, input: &[f32], rate: u32) -> Vec<f32> {
+        let mut output = Vec::new();
+        for chunk in input.chunks(1152 * 2) {
+            output.extend(converter.resample(chunk, rate, 2));
+        }
+        output.extend(converter.flush());
+        output
+    }
+
+    #[test]
+    fn test_resampling_preserves_frequency() {
+        let mut converter = SampleConverter::with_target_rate(48_000);
+        let input = sine(1_000.0, 44_100, 44_100);
+        let output = resample_all(&mut converter, &input, 44_100);
+
+        // One second in, one second out
+        assert_eq!(output.len(), 2 * 48_000);
+        let frequency = measure_frequency(&output, 48_000);
+        assert!((frequency - 1_000.0).abs() < 0.5, "frequency {}", frequency);
+
+        // Amplitude survives away from the edges
+        let peak = output[4_800..2 * 43_200]
+            .iter()
+            .fold(0.0f32, |peak, &s| peak.max(s.abs()));
+        assert!((peak - 0.5).abs() < 0.005, "peak {}", peak);
+
+        // Channels stay in step
+        assert!(output.chunks(2).all(|frame| frame[0] == frame[1]));
+    }
+
+    #[test]
+    fn test_resampler_follows_input_rate_changes() {
+        let mut converter = SampleConverter::with_target_rate(48_000);
+        assert_eq!(converter.target_rate(), Some(48_000));
+
+        // Already at the target rate: untouched and without delay
+        let native = sine(440.0, 48_000, 4_800);
+        assert_eq!(converter.resample(&native, 48_000, 2), native);
+        assert_eq!(converter.latency(), Duration::ZERO);
+
+        // The next track is upsampled, delayed by the kernel half-width
+        let input = sine(440.0, 22_050, 22_050);
+        let output = resample_all(&mut converter, &input, 22_050);
+        assert_eq!(output.len(), 2 * 48_000);
+        let frequency = measure_frequency(&output, 48_000);
+        assert!((frequency - 440.0).abs() < 0.5, "frequency {}", frequency);
+
+        converter.resample(&input[..2_000], 22_050, 2);
+        assert_eq!(
+            converter.latency(),
+            Duration::from_secs_f64(16.0 / 22_050.0)
+        );
+
+        // Downsampling widens the kernel to filter below the new Nyquist
+        converter.flush();
+        converter.resample(&sine(440.0, 96_000, 1_000), 96_000, 2);
+        assert_eq!(
+            converter.latency(),
+            Duration::from_secs_f64(32.0 / 96_000.0)
+        );
+    }
+
+    #[test]
+    fn test_converter_without_target_rate_passes_through() {
+        let mut converter = SampleConverter::default();
+        let input = sine(440.0, 44_100, 1_000);
+        assert_eq!(converter.resample(&input, 44_100, 2), input);
+        assert!(converter.flush().is_empty());
+        assert_eq!(converter.latency(), Duration::ZERO);
+    }
 }
@@ -4,9 +4,10 @@
 
 use crate::decoder::format_detector::FormatDetector;
 use crate::decoder::sample_converter::SampleConverter;
-use crate::error::{PlaybackError, Result};
+use crate::error::{is_transient_io_error, PlaybackError, Result};
 use crate::traits::{
-    AudioCodec, AudioDecoder, AudioFormat, AudioFrameChunk, AudioSource, ProbeResult,
+    AudioCodec, AudioDecoder, AudioFormat, AudioFrameChunk, AudioSource, AudioStreamInfo,
+    ProbeResult,
 };
 use async_trait::async_trait;
 use bytes::Bytes;
@@ -14,15 +15,79 @@ use std::collections::HashMap;
 use std::io::Cursor;
 use std::path::PathBuf;
 use std::time::Duration;
-use symphonia::core::codecs::{Decoder, DecoderOptions, CODEC_TYPE_NULL};
-use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::codecs::{
+    CodecParameters, Decoder, DecoderOptions as SymphoniaDecoderOptions, CODEC_TYPE_FLAC,
+    CODEC_TYPE_NULL, CODEC_TYPE_OPUS,
+};
+use symphonia::core::errors::{Error as SymphoniaError, SeekErrorKind};
 use symphonia::core::formats::{FormatOptions, FormatReader, SeekMode, SeekTo};
 use symphonia::core::io::{MediaSource, MediaSourceStream};
 use symphonia::core::meta::MetadataOptions;
 use symphonia::core::probe::Hint;
-use symphonia::core::units::Time;
+use symphonia::core::units::TimeBase;
 use tracing::{debug, error, info, instrument, warn};
 
+/// Format-specific options forwarded to Symphonia.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DecoderOptions {
+    /// Audio stream to decode (see `AudioStreamInfo::index`); `None` selects
+    /// the first one.
+    pub stream_index: Option<usize>,
+
+    /// Verify decoded audio against the stream's checksum where the format
+    /// has one (FLAC STREAMINFO MD5).
+    ///
+    /// A mismatch is reported as `PlaybackError::CorruptedStream` when the end
+    /// of the stream is reached. Seeking skips verification for that pass.
+    ///
+    /// Default: false.
+    pub verify: bool,
+
+    /// Apply the output gain from the Opus identification header, as
+    /// required by RFC 7845.
+    ///
+    /// Default: true.
+    pub apply_opus_gain: bool,
+
+    /// Trim encoder delay and padding (e.g., LAME/iTunes gapless info) so
+    /// consecutive tracks join without gaps.
+    ///
+    /// When disabled, they are reported through `ProbeResult` instead and
+    /// `StreamingService` skips them.
+    ///
+    /// Default: false.
+    pub gapless: bool,
+
+    /// How exactly `seek` lands on the requested position.
+    ///
+    /// Default: `SeekPrecision::Accurate`.
+    pub seek_precision: SeekPrecision,
+}
+
+impl Default for DecoderOptions {
+    fn default() -> Self {
+        Self {
+            stream_index: None,
+            verify: false,
+            apply_opus_gain: true,
+            gapless: false,
+            seek_precision: SeekPrecision::default(),
+        }
+    }
+}
+
+/// Seek precision for `SymphoniaDecoder`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SeekPrecision {
+    /// Jump to the nearest packet the container can reach directly; the
+    /// returned position may be before or after the request
+    Coarse,
+    /// Land on the exact frame, decoding and discarding audio from the
+    /// preceding packet boundary
+    #[default]
+    Accurate,
+}
+
 /// Production-ready Symphonia decoder implementing the AudioDecoder trait.
 ///
 /// This decoder handles all supported audio formats through Symphonia's
@@ -55,15 +120,42 @@ pub struct SymphoniaDecoder {
     /// Selected track ID
     track_id: u32,
 
+    /// All audio streams in the container
+    streams: Vec<AudioStreamInfo>,
+
+    /// Index into `streams` of the selected track
+    selected_stream: usize,
+
+    /// Whether the decoder's checksum is checked at end of stream
+    verify: bool,
+
+    /// Linear gain applied to decoded samples (Opus output gain)
+    output_gain: f32,
+
     /// Audio format information
     format: AudioFormat,
 
     /// Track duration (if known)
     duration: Option<Duration>,
 
+    /// Track length in frames (if known)
+    total_frames: Option<u64>,
+
+    /// Time base of packet timestamps, for converting seek targets
+    time_base: TimeBase,
+
+    /// How `seek` lands on the requested position
+    seek_precision: SeekPrecision,
+
+    /// Frames still to discard before output resumes after a seek
+    skip_frames: u64,
+
     /// Metadata tags
     tags: HashMap<String, String>,
 
+    /// Encoder delay and padding in frames, left for the caller to trim
+    encoder_trim: (u32, u32),
+
     /// Current decode position in frames
     position_frames: u64,
 
@@ -97,8 +189,37 @@ impl SymphoniaDecoder {
     /// - Format is not recognized
     /// - No supported audio tracks found
     /// - Codec is not supported
-    #[instrument(skip(source), fields(source = ?source))]
     pub async fn new(source: AudioSource) -> Result<Self> {
+        Self::with_options(source, DecoderOptions::default()).await
+    }
+
+    /// Create a decoder for a specific audio stream of a multi-stream file.
+    ///
+    /// `stream_index` counts audio streams in container order, matching
+    /// `AudioStreamInfo::index` from `probe()`. `new` decodes stream 0.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as `new`, plus `PlaybackError::TrackNotFound`
+    /// if the container has no stream at `stream_index`.
+    pub async fn with_stream(source: AudioSource, stream_index: usize) -> Result<Self> {
+        Self::with_options(
+            source,
+            DecoderOptions {
+                stream_index: Some(stream_index),
+                ..Default::default()
+            },
+        )
+        .await
+    }
+
+    /// Create a decoder with format-specific options.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as `with_stream`.
+    #[instrument(skip(source), fields(source = ?source))]
+    pub async fn with_options(source: AudioSource, options: DecoderOptions) -> Result<Self> {
         info!("Creating Symphonia decoder");
 
         // Step 1: Open media source
@@ -109,7 +230,10 @@ impl SymphoniaDecoder {
             .format(
                 &hint,
                 media_source,
-                &FormatOptions::default(),
+                &FormatOptions {
+                    enable_gapless: options.gapless,
+                    ..Default::default()
+                },
                 &MetadataOptions::default(),
             )
             .map_err(|e| {
@@ -120,18 +244,53 @@ impl SymphoniaDecoder {
         let format_reader = probe_result.format;
         let _metadata = probe_result.metadata; // TODO: Extract tags when API is clarified
 
-        // Step 3: Find first audio track with supported codec
-        let track = format_reader
+        // Step 3: List audio tracks and select the requested one
+        let tracks: Vec<_> = format_reader
             .tracks()
             .iter()
-            .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
-            .ok_or_else(|| {
-                error!("No supported audio tracks found");
-                PlaybackError::FormatNotDecodable("No supported audio tracks".to_string())
-            })?;
+            .filter(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+            .collect();
+        if tracks.is_empty() {
+            error!("No supported audio tracks found");
+            return Err(PlaybackError::FormatNotDecodable(
+                "No supported audio tracks".to_string(),
+            ));
+        }
+
+        let streams: Vec<AudioStreamInfo> = tracks
+            .iter()
+            .enumerate()
+            .map(|(index, t)| AudioStreamInfo {
+                index,
+                track_id: t.id,
+                codec: FormatDetector::detect_codec(t.codec_params.codec),
+                channels: t.codec_params.channels.map(|ch| ch.count() as u16),
+                sample_rate: t.codec_params.sample_rate,
+                language: t.language.clone(),
+            })
+            .collect();
+
+        let selected_stream = options.stream_index.unwrap_or(0);
+        let track = *tracks.get(selected_stream).ok_or_else(|| {
+            error!(
+                "Audio stream {} requested but only {} available",
+                selected_stream,
+                tracks.len()
+            );
+            PlaybackError::TrackNotFound(format!(
+                "Audio stream {} (file has {} audio streams)",
+                selected_stream,
+                tracks.len()
+            ))
+        })?;
 
         let track_id = track.id;
-        debug!("Selected track ID: {}", track_id);
+        debug!(
+            "Selected track ID: {} (stream {} of {})",
+            track_id,
+            selected_stream,
+            streams.len()
+        );
 
         // Step 4: Detect and validate codec
         let codec_type = track.codec_params.codec;
@@ -162,10 +321,30 @@ impl SymphoniaDecoder {
         );
 
         // Step 6: Calculate duration
-        let duration = track
+        let total_frames = track.codec_params.n_frames;
+        let duration =
+            total_frames.map(|frames| Duration::from_secs_f64(frames as f64 / sample_rate as f64));
+        let time_base = track
             .codec_params
-            .n_frames
-            .map(|frames| Duration::from_secs_f64(frames as f64 / sample_rate as f64));
+            .time_base
+            .unwrap_or_else(|| TimeBase::new(1, sample_rate));
+
+        // Symphonia already trims the encoder delay and padding in gapless
+        // mode; otherwise they are reported for the caller to skip
+        let encoder_trim = if options.gapless {
+            (0, 0)
+        } else {
+            (
+                track.codec_params.delay.unwrap_or(0),
+                track.codec_params.padding.unwrap_or(0),
+            )
+        };
+        if encoder_trim != (0, 0) {
+            debug!(
+                "Encoder delay {} frames, padding {} frames",
+                encoder_trim.0, encoder_trim.1
+            );
+        }
 
         if let Some(dur) = duration {
             debug!("Track duration: {:?}", dur);
@@ -175,7 +354,12 @@ impl SymphoniaDecoder {
 
         // Step 7: Create codec decoder
         let decoder = symphonia::default::get_codecs()
-            .make(&track.codec_params, &DecoderOptions::default())
+            .make(
+                &track.codec_params,
+                &SymphoniaDecoderOptions {
+                    verify: options.verify,
+                },
+            )
             .map_err(|e| {
                 error!("Failed to create decoder: {}", e);
                 PlaybackError::DecoderError(format!("Failed to create codec decoder: {}", e))
@@ -183,6 +367,12 @@ impl SymphoniaDecoder {
 
         info!("Decoder initialized successfully");
 
+        let output_gain = if options.apply_opus_gain {
+            opus_output_gain(&track.codec_params)
+        } else {
+            1.0
+        };
+
         // Step 8: Extract metadata tags
         // Note: Metadata extraction simplified - advanced metadata handling
         // can be added later based on actual Symphonia version API
@@ -192,9 +382,18 @@ impl SymphoniaDecoder {
             format_reader,
             decoder,
             track_id,
+            streams,
+            selected_stream,
+            verify: options.verify,
+            output_gain,
             format: AudioFormat::new(codec, sample_rate, channels, bits_per_sample, bitrate),
             duration,
+            total_frames,
+            time_base,
+            seek_precision: options.seek_precision,
+            skip_frames: 0,
             tags,
+            encoder_trim,
             position_frames: 0,
             sample_rate,
             channels,
@@ -377,6 +576,46 @@ impl SymphoniaDecoder {
         }
     }
 
+    /// Check the decoded audio against the stream checksum, if requested.
+    fn verify_checksum(&mut self) -> Result<()> {
+        if !self.verify {
+            return Ok(());
+        }
+
+        match self.decoder.finalize().verify_ok {
+            Some(false) => {
+                error!("Checksum verification failed ({})", self.source_info);
+                Err(PlaybackError::CorruptedStream(
+                    "Decoded audio does not match the stream checksum".to_string(),
+                ))
+            }
+            Some(true) => {
+                debug!("Checksum verified ({})", self.source_info);
+                Ok(())
+            }
+            None => {
+                debug!("Stream has no checksum to verify ({})", self.source_info);
+                Ok(())
+            }
+        }
+    }
+
+    /// Convert a frame count to a timestamp in the track's time base.
+    fn frames_to_ts(&self, frames: u64) -> u64 {
+        let TimeBase { numer, denom } = self.time_base;
+        (frames as u128 * denom as u128 / (numer as u128 * self.sample_rate as u128)) as u64
+    }
+
+    /// Convert a timestamp in the track's time base to a frame count.
+    fn ts_to_frames(&self, ts: u64) -> u64 {
+        let TimeBase { numer, denom } = self.time_base;
+        (ts as u128 * numer as u128 * self.sample_rate as u128 / denom as u128) as u64
+    }
+
+    fn frames_to_duration(&self, frames: u64) -> Duration {
+        Duration::from_secs_f64(frames as f64 / self.sample_rate as f64)
+    }
+
     // Metadata extraction removed for now - Symphonia API varies by version
     // Will be added back once we lock in the exact Symphonia version and API
 
@@ -422,8 +661,12 @@ impl SymphoniaDecoder {
                 }
                 Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
                     // Normal end of stream
-                    debug!("Reached end of stream at {} frames", self.position_frames);
+                    debug!(
+                        "Reached end of stream at {} frames ({})",
+                        self.position_frames, self.source_info
+                    );
                     self.eof = true;
+                    self.verify_checksum()?;
                     return Ok(None);
                 }
                 Err(SymphoniaError::IoError(e)) => {
@@ -435,10 +678,16 @@ impl SymphoniaDecoder {
 
                     if consecutive_errors >= MAX_CONSECUTIVE_ERRORS {
                         error!("Too many consecutive I/O errors, giving up");
-                        return Err(PlaybackError::SourceError(format!(
+                        let message = format!(
                             "Stream I/O failure after {} attempts: {}",
                             MAX_CONSECUTIVE_ERRORS, e
-                        )));
+                        );
+                        // Connection failures may succeed when re-fetched
+                        return Err(if is_transient_io_error(e.kind()) {
+                            PlaybackError::SourceUnavailable(message)
+                        } else {
+                            PlaybackError::SourceError(message)
+                        });
                     }
 
                     continue; // Try next packet
@@ -486,7 +735,10 @@ impl SymphoniaDecoder {
                     );
 
                     // Convert to owned interleaved f32 samples
-                    let samples = SampleConverter::to_interleaved_f32(&decoded)?;
+                    let mut samples = SampleConverter::to_interleaved_f32(&decoded)?;
+                    if self.output_gain != 1.0 {
+                        samples.iter_mut().for_each(|s| *s *= self.output_gain);
+                    }
 
                     return Ok(Some(samples));
                 }
@@ -546,7 +798,9 @@ impl AudioDecoder for SymphoniaDecoder {
 
         Ok(ProbeResult::new(self.format.clone())
             .with_duration(self.duration)
-            .with_tags(self.tags.clone()))
+            .with_tags(self.tags.clone())
+            .with_streams(self.streams.clone(), self.selected_stream)
+            .with_encoder_trim(self.encoder_trim.0, self.encoder_trim.1))
     }
 
     async fn decode_frames(&mut self, max_frames: usize) -> Result<Option<AudioFrameChunk>> {
@@ -554,54 +808,137 @@ impl AudioDecoder for SymphoniaDecoder {
             return Ok(None);
         }
 
-        // Decode next packet - returns owned interleaved f32 samples
-        let samples = match self.decode_next_packet()? {
-            Some(samples) => samples,
-            None => return Ok(None),
-        };
+        loop {
+            // Decode next packet - returns owned interleaved f32 samples
+            let samples = match self.decode_next_packet()? {
+                Some(samples) => samples,
+                None => return Ok(None),
+            };
 
-        // Calculate actual frame count from sample count
-        let total_frames = samples.len() / self.channels as usize;
-        let frames = total_frames.min(max_frames);
-        let samples_to_take = frames * self.channels as usize;
-        let chunk_samples = samples.into_iter().take(samples_to_take).collect();
+            // Calculate actual frame count from sample count
+            let channels = self.channels as usize;
+            let total_frames = samples.len() / channels;
+            // Position was already advanced past this packet
+            let packet_start = self.position_frames - total_frames as u64;
 
-        // Calculate timestamp (subtract frames we just decoded since position was already updated)
-        let timestamp = Duration::from_secs_f64(
-            (self.position_frames - total_frames as u64) as f64 / self.sample_rate as f64,
-        );
+            // Discard audio before an accurate seek target
+            let skip = (self.skip_frames as usize).min(total_frames);
+            self.skip_frames -= skip as u64;
+            if skip == total_frames {
+                continue;
+            }
+
+            let frames = (total_frames - skip).min(max_frames);
+            let chunk_samples = samples[skip * channels..(skip + frames) * channels].to_vec();
+            let timestamp = self.frames_to_duration(packet_start + skip as u64);
 
-        Ok(Some(AudioFrameChunk::new(chunk_samples, frames, timestamp)))
+            return Ok(Some(AudioFrameChunk::new(chunk_samples, frames, timestamp)));
+        }
     }
 
-    async fn seek(&mut self, position: Duration) -> Result<()> {
-        if self.duration.is_some() && position > self.duration.unwrap() {
-            return Err(PlaybackError::SeekOutOfBounds(position));
+    async fn seek(&mut self, position: Duration) -> Result<Duration> {
+        debug!("Seeking to {:?} ({:?})", position, self.seek_precision);
+
+        let mut target = (position.as_secs_f64() * self.sample_rate as f64).round() as u64;
+
+        // The checksum covers a linear decode from the start
+        self.verify = false;
+
+        // Past-the-end seeks land at the end of the track
+        if let Some(total_frames) = self.total_frames {
+            if target >= total_frames {
+                debug!("Seek past end clamped to {} frames", total_frames);
+                self.position_frames = total_frames;
+                self.skip_frames = 0;
+                self.eof = true;
+                return Ok(self.frames_to_duration(total_frames));
+            }
         }
 
-        debug!("Seeking to {:?}", position);
+        let mode = match self.seek_precision {
+            SeekPrecision::Coarse => SeekMode::Coarse,
+            SeekPrecision::Accurate => SeekMode::Accurate,
+        };
 
-        // Convert duration to time units
-        let time = Time::from(position.as_secs_f64());
+        // Symphonia's FLAC reader keeps the packet it was building when it
+        // hit end of stream and glues it onto the first packet after a seek;
+        // a throwaway seek and read flushes it
+        if self.eof && self.decoder.codec_params().codec == CODEC_TYPE_FLAC {
+            let start = SeekTo::TimeStamp {
+                ts: 0,
+                track_id: self.track_id,
+            };
+            if self.format_reader.seek(SeekMode::Coarse, start).is_ok() {
+                let _ = self.format_reader.next_packet();
+            }
+        }
+        let seeked = self.format_reader.seek(
+            mode,
+            SeekTo::TimeStamp {
+                ts: self.frames_to_ts(target),
+                track_id: self.track_id,
+            },
+        );
 
-        // Attempt seek
-        self.format_reader
-            .seek(SeekMode::Accurate, SeekTo::Time { time, track_id: None })
-            .map_err(|e| {
+        match seeked {
+            Ok(seeked) => {
+                // The reader lands on a packet boundary at or before the target
+                // in accurate mode; the difference is decoded and discarded
+                let actual = self.ts_to_frames(seeked.actual_ts);
+                self.position_frames = actual;
+                match self.seek_precision {
+                    SeekPrecision::Accurate => self.skip_frames = target.saturating_sub(actual),
+                    SeekPrecision::Coarse => {
+                        self.skip_frames = 0;
+                        target = actual;
+                    }
+                }
+                self.decoder.reset();
+            }
+            Err(SymphoniaError::SeekError(
+                SeekErrorKind::Unseekable | SeekErrorKind::ForwardOnly,
+            )) if target >= self.position_frames => {
+                // No seek table or index: keep decoding and drop audio up
+                // to the target
+                warn!(
+                    "Format cannot seek, decoding forward to {} frames ({})",
+                    target, self.source_info
+                );
+                self.skip_frames = target - self.position_frames;
+            }
+            Err(SymphoniaError::SeekError(SeekErrorKind::OutOfRange)) => {
+                error!("Seek to {:?} is out of range", position);
+                return Err(PlaybackError::SeekOutOfBounds(position));
+            }
+            Err(e) => {
                 error!("Seek failed: {}", e);
-                PlaybackError::SeekNotSupported
-            })?;
-
-        // Reset decoder state
-        self.decoder.reset();
+                return Err(PlaybackError::SeekNotSupported);
+            }
+        }
 
-        // Update position
-        let new_position_frames = (position.as_secs_f64() * self.sample_rate as f64) as u64;
-        self.position_frames = new_position_frames;
         self.eof = false;
 
-        info!("Seek completed to {:?}", position);
-        Ok(())
+        let actual = self.frames_to_duration(target);
+        info!("Seek completed to {:?}", actual);
+        Ok(actual)
+    }
+}
+
+/// Linear output gain from an Opus identification header ("OpusHead")
+///
+/// The gain is a Q7.8 dB value at byte offset 16; see RFC 7845 section 5.1.
+fn opus_output_gain(params: &CodecParameters) -> f32 {
+    if params.codec != CODEC_TYPE_OPUS {
+        return 1.0;
+    }
+
+    match params.extra_data.as_deref() {
+        Some(header) if header.len() >= 18 && header.starts_with(b"OpusHead") => {
+            let gain_db = i16::from_le_bytes([header[16], header[17]]) as f32 / 256.0;
+            debug!("Applying Opus output gain of {:.2} dB", gain_db);
+            10f32.powf(gain_db / 20.0)
+        }
+        _ => 1.0,
     }
 }
 
@@ -109,6 +109,10 @@ pub enum PlaybackError {
     #[error("Cache storage full")]
     CacheFull,
 
+    /// Not enough free space on the cache volume to store the track.
+    #[error("Insufficient space: {required} bytes required, {available} available")]
+    InsufficientSpace { required: u64, available: u64 },
+
     /// Cache encryption/decryption failed.
     #[error("Cache encryption error: {0}")]
     EncryptionError(String),
@@ -159,13 +163,14 @@ pub enum PlaybackError {
 impl PlaybackError {
     /// Returns `true` if this error is transient and the operation can be retried.
     pub fn is_transient(&self) -> bool {
-        matches!(
-            self,
+        match self {
             PlaybackError::StreamingFailed(_)
-                | PlaybackError::BufferUnderrun
-                | PlaybackError::SourceUnavailable(_)
-                | PlaybackError::AudioDeviceUnavailable(_)
-        )
+            | PlaybackError::BufferUnderrun
+            | PlaybackError::SourceUnavailable(_)
+            | PlaybackError::AudioDeviceUnavailable(_) => true,
+            PlaybackError::IoError(e) => is_transient_io_error(e.kind()),
+            _ => false,
+        }
     }
 
     /// Returns `true` if this error is due to network issues.
@@ -187,5 +192,23 @@ impl PlaybackError {
     }
 }
 
+/// Returns `true` for I/O errors caused by a connection hiccup rather than
+/// the data itself.
+pub(crate) fn is_transient_io_error(kind: std::io::ErrorKind) -> bool {
+    use std::io::ErrorKind;
+
+    matches!(
+        kind,
+        ErrorKind::ConnectionReset
+            | ErrorKind::ConnectionAborted
+            | ErrorKind::ConnectionRefused
+            | ErrorKind::NotConnected
+            | ErrorKind::BrokenPipe
+            | ErrorKind::TimedOut
+            | ErrorKind::Interrupted
+            | ErrorKind::WouldBlock
+    )
+}
+
 /// Result type for playback operations.
 pub type Result<T> = std::result::Result<T, PlaybackError>;
@@ -9,6 +9,11 @@
 //! - **Audio Decoding**: Convert encoded audio (MP3, AAC, FLAC, etc.) to PCM samples
 //! - **Playback Control**: Platform-agnostic playback adapter trait
 //! - **Streaming Service**: Producer-consumer architecture for efficient audio streaming
+//! - **Channel Mapping**: Downmix/upmix decoded audio to the output device's layout
+//! - **Limiting**: Lookahead peak limiter keeping output below a ceiling
+//! - **Silence Trimming**: Detect and skip silent intros and outros
+//! - **Loudness Analysis**: EBU R128 loudness and true peak for volume leveling
+//! - **Decode Cache**: In-memory PCM cache for short, frequently replayed tracks
 //! - **Offline Cache**: Optional encrypted cache for offline playback
 //!
 //! ## Architecture
@@ -65,13 +70,19 @@
 //! }
 //! ```
 
+pub mod analysis;
 #[cfg(feature = "offline-cache")]
 pub mod cache;
+pub mod channel_mapper;
 pub mod config;
+pub mod decode_cache;
 #[cfg(feature = "core-decoder")]
 pub mod decoder;
 pub mod error;
+pub mod limiter;
+pub mod loudness;
 pub mod ring_buffer;
+pub mod silence;
 pub mod streaming;
 pub mod traits;
 
@@ -80,13 +91,22 @@ pub mod traits;
 pub mod wasm;
 
 // Re-export commonly used types
-pub use config::{StreamingConfig, StreamingState, StreamingStats};
+pub use analysis::{AnalysisService, SpectrogramColumn, SpectrogramConfig, WindowFunction};
+pub use channel_mapper::{ChannelMapper, DownmixCoefficients};
+pub use config::{OutputLatency, StreamingConfig, StreamingState, StreamingStats};
+pub use decode_cache::{DecodeCache, DecodeCacheConfig, DecodeCacheKey};
 #[cfg(feature = "core-decoder")]
-pub use decoder::{FormatDetector, SampleConverter, SymphoniaDecoder};
+pub use decoder::{
+    DecoderOptions, Dither, DitherConfig, DitherMode, FormatDetector, SampleConverter,
+    SeekPrecision, SymphoniaDecoder,
+};
 pub use error::{PlaybackError, Result};
-pub use ring_buffer::RingBuffer;
+pub use limiter::{Limiter, LimiterConfig};
+pub use loudness::{Loudness, LoudnessAnalyzer, REFERENCE_LUFS};
+pub use ring_buffer::{MeterFrame, OverflowPolicy, RingBuffer};
+pub use silence::{SilenceAnalyzer, SilenceConfig, SilenceTrim};
 pub use streaming::{StreamingRequest, StreamingService};
 pub use traits::{
-    AudioCodec, AudioDecoder, AudioFormat, AudioFrameChunk, AudioSource, PlaybackAdapter,
-    ProbeResult,
+    AudioCodec, AudioDecoder, AudioFormat, AudioFrameChunk, AudioSink, AudioSource,
+    AudioStreamInfo, BufferSizeRange, PlaybackAdapter, ProbeResult,
 };
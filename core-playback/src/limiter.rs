@@ -0,0 +1,435 @@
+//! # Peak Limiter
+//!
+//! Final DSP stage that keeps the output below a ceiling without clipping.
+//!
+//! ## Overview
+//!
+//! Gain stages (ReplayGain, EQ boosts, upmixing) can push samples past ±1.0,
+//! and hard-clipping them is audible. `Limiter` instead lowers the gain just
+//! before a peak arrives:
+//!
+//! 1. **Peak detection**: each frame's peak across channels, optionally
+//!    including inter-sample peaks estimated by 4x windowed-sinc
+//!    interpolation ("true peak")
+//! 2. **Gain computer**: the gain that brings the peak to the ceiling, with
+//!    an optional soft knee that starts reducing gain below it
+//! 3. **Lookahead**: the audio is delayed by `lookahead` while the required
+//!    gain is held and smoothed over the same window, so gain reduction ramps
+//!    in before the peak instead of snapping down on it
+//! 4. **Release**: gain recovers exponentially once the peak has passed
+//!
+//! Output is delayed by `Limiter::latency()`; call `flush()` at the end of a
+//! stream to drain the delay line.
+
+use crate::traits::AudioFrameChunk;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Taps of the inter-sample peak interpolator
+const INTERPOLATOR_TAPS: usize = 16;
+
+/// Fractional positions between samples checked for inter-sample peaks
+const INTERPOLATOR_PHASES: [f64; 3] = [0.25, 0.5, 0.75];
+
+/// Limiter configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LimiterConfig {
+    /// Maximum output level in dBFS.
+    ///
+    /// Default: -0.3 dBFS.
+    pub ceiling_db: f32,
+
+    /// Width of the soft knee in dB below the ceiling.
+    ///
+    /// `0.0` is a brick-wall limiter; larger values start reducing gain
+    /// gradually this far below the ceiling.
+    ///
+    /// Default: 0.0.
+    pub knee_db: f32,
+
+    /// How far ahead peaks are detected.
+    ///
+    /// Default: 5ms.
+    pub lookahead: Duration,
+
+    /// Time constant for gain recovery after a peak.
+    ///
+    /// Default: 100ms.
+    pub release: Duration,
+
+    /// Whether to detect inter-sample peaks.
+    ///
+    /// Default: true.
+    pub true_peak: bool,
+}
+
+impl Default for LimiterConfig {
+    fn default() -> Self {
+        Self {
+            ceiling_db: -0.3,
+            knee_db: 0.0,
+            lookahead: Duration::from_millis(5),
+            release: Duration::from_millis(100),
+            true_peak: true,
+        }
+    }
+}
+
+/// Lookahead peak limiter for interleaved PCM.
+pub struct Limiter {
+    config: LimiterConfig,
+    sample_rate: u32,
+    channels: usize,
+    /// Linear ceiling
+    ceiling: f32,
+    /// Lookahead in frames
+    lookahead: usize,
+    release_coefficient: f32,
+    /// Interpolation filter, one row of taps per phase
+    interpolator: Vec<[f32; INTERPOLATOR_TAPS]>,
+    /// Most recent input frames, interleaved (interpolator window)
+    history: VecDeque<f32>,
+    /// Peak of the interval before the frame currently entering the delay line
+    previous_interval_peak: f32,
+    /// Frames waiting for their gain, interleaved
+    delay: VecDeque<f32>,
+    /// Monotonic window of (frame, required gain) for the running minimum
+    min_window: VecDeque<(u64, f32)>,
+    /// Held gains being averaged, and their sum
+    average_window: VecDeque<f32>,
+    average_sum: f64,
+    envelope: f32,
+    frames_in: u64,
+    frames_out: u64,
+    /// Timestamp at the end of the last input chunk
+    input_end: Duration,
+}
+
+impl Limiter {
+    /// Create a limiter for audio at `sample_rate` with `channels` channels.
+    pub fn new(config: LimiterConfig, sample_rate: u32, channels: u16) -> Self {
+        let channels = channels.max(1) as usize;
+        let sample_rate = sample_rate.max(1);
+        let lookahead =
+            ((config.lookahead.as_secs_f64() * sample_rate as f64).round() as usize).max(1);
+        let release_frames = config.release.as_secs_f64() * sample_rate as f64;
+        let release_coefficient = if release_frames > 0.0 {
+            (-1.0 / release_frames).exp() as f32
+        } else {
+            0.0
+        };
+
+        let window = lookahead + 1;
+        Self {
+            config,
+            sample_rate,
+            channels,
+            ceiling: db_to_linear(config.ceiling_db),
+            lookahead,
+            release_coefficient,
+            interpolator: INTERPOLATOR_PHASES
+                .iter()
+                .map(|&phase| interpolation_taps(phase))
+                .collect(),
+            history: VecDeque::from(vec![0.0; INTERPOLATOR_TAPS * channels]),
+            previous_interval_peak: 0.0,
+            delay: VecDeque::with_capacity(window * channels),
+            min_window: VecDeque::with_capacity(window),
+            average_window: VecDeque::from(vec![1.0; window]),
+            average_sum: window as f64,
+            envelope: 1.0,
+            frames_in: 0,
+            frames_out: 0,
+            input_end: Duration::ZERO,
+        }
+    }
+
+    /// The limiter configuration.
+    pub fn config(&self) -> &LimiterConfig {
+        &self.config
+    }
+
+    /// Delay between a frame entering and leaving the limiter.
+    pub fn latency(&self) -> Duration {
+        Duration::from_secs_f64(self.latency_frames() as f64 / self.sample_rate as f64)
+    }
+
+    fn latency_frames(&self) -> usize {
+        self.lookahead + INTERPOLATOR_TAPS / 2
+    }
+
+    /// Limit a chunk.
+    ///
+    /// Returns the frames that have cleared the lookahead window, which lags
+    /// the input by `latency()`; the first chunks of a stream are shorter.
+    pub fn process(&mut self, chunk: &AudioFrameChunk) -> AudioFrameChunk {
+        let mut samples = Vec::with_capacity(chunk.samples.len());
+        for frame in chunk.samples.chunks_exact(self.channels) {
+            self.push_frame(frame, &mut samples);
+        }
+
+        self.input_end = chunk.timestamp + chunk.duration(self.sample_rate);
+        let output_end = self.input_end.saturating_sub(self.latency());
+        self.output_chunk(samples, output_end)
+    }
+
+    /// Drain the frames still held in the lookahead window.
+    ///
+    /// The limiter is reset afterwards, ready for a new stream.
+    pub fn flush(&mut self) -> AudioFrameChunk {
+        let pending = (self.frames_in - self.frames_out) as usize;
+        let silence = vec![0.0; self.channels];
+        let mut samples = Vec::with_capacity(self.latency_frames() * self.channels);
+        for _ in 0..self.latency_frames() {
+            self.push_frame(&silence, &mut samples);
+        }
+        samples.truncate(pending * self.channels);
+
+        let chunk = self.output_chunk(samples, self.input_end);
+        *self = Self::new(self.config, self.sample_rate, self.channels as u16);
+        chunk
+    }
+
+    /// Wrap output samples ending at `output_end` into a chunk
+    fn output_chunk(&mut self, samples: Vec<f32>, output_end: Duration) -> AudioFrameChunk {
+        let frames = samples.len() / self.channels;
+        self.frames_out += frames as u64;
+        let timestamp = output_end.saturating_sub(self.frame_time(frames as u64));
+        AudioFrameChunk::new(samples, frames, timestamp)
+    }
+
+    fn frame_time(&self, frames: u64) -> Duration {
+        Duration::from_secs_f64(frames as f64 / self.sample_rate as f64)
+    }
+
+    /// Feed one frame, appending any frame that leaves the delay line
+    fn push_frame(&mut self, frame: &[f32], output: &mut Vec<f32>) {
+        self.history.drain(..self.channels);
+        self.history.extend(frame);
+        self.frames_in += 1;
+
+        // The frame entering the delay line sits at the interpolator's center
+        let center = INTERPOLATOR_TAPS / 2 - 1;
+        let interval_peak = self.interval_peak(center);
+        let peak = interval_peak.max(self.previous_interval_peak);
+        self.previous_interval_peak = interval_peak;
+        if self.frames_in <= (INTERPOLATOR_TAPS - 1 - center) as u64 {
+            // Still priming the interpolator with the first frames
+            return;
+        }
+        let required = self.required_gain(peak);
+
+        // Hold the lowest gain required within the lookahead window...
+        let index = self.frames_in;
+        while matches!(self.min_window.back(), Some(&(_, gain)) if gain >= required) {
+            self.min_window.pop_back();
+        }
+        self.min_window.push_back((index, required));
+        while matches!(self.min_window.front(), Some(&(i, _)) if i + (self.lookahead as u64) < index)
+        {
+            self.min_window.pop_front();
+        }
+        let held = self.min_window.front().map_or(1.0, |&(_, gain)| gain);
+
+        // ...and average it over the same window so it ramps in smoothly
+        self.average_sum += held as f64;
+        self.average_sum -= self.average_window.pop_front().unwrap_or(1.0) as f64;
+        self.average_window.push_back(held);
+        let target = (self.average_sum / self.average_window.len() as f64) as f32;
+
+        self.envelope = if target < self.envelope {
+            target
+        } else {
+            target + (self.envelope - target) * self.release_coefficient
+        };
+
+        let start = center * self.channels;
+        let delayed: Vec<f32> = self
+            .history
+            .range(start..start + self.channels)
+            .copied()
+            .collect();
+        self.delay.extend(delayed);
+        if self.delay.len() > self.lookahead * self.channels {
+            for sample in self.delay.drain(..self.channels) {
+                let limited = sample * self.envelope;
+                output.push(limited.clamp(-self.ceiling, self.ceiling));
+            }
+        }
+    }
+
+    /// Peak of the interval from history frame `center` to the next frame
+    fn interval_peak(&self, center: usize) -> f32 {
+        let mut peak = 0.0f32;
+        for channel in 0..self.channels {
+            let sample = |frame: usize| self.history[frame * self.channels + channel];
+            peak = peak.max(sample(center).abs());
+            if self.config.true_peak {
+                for taps in &self.interpolator {
+                    let interpolated: f32 = taps
+                        .iter()
+                        .enumerate()
+                        .map(|(frame, tap)| tap * sample(frame))
+                        .sum();
+                    peak = peak.max(interpolated.abs());
+                }
+            }
+        }
+        peak
+    }
+
+    /// Gain that brings `peak` to the knee's output level
+    fn required_gain(&self, peak: f32) -> f32 {
+        if peak <= 0.0 {
+            return 1.0;
+        }
+
+        let knee = self.config.knee_db.max(0.0);
+        let input_db = linear_to_db(peak);
+        let threshold = self.config.ceiling_db - knee;
+        if input_db <= threshold {
+            return 1.0;
+        }
+
+        // Above the threshold the output approaches the ceiling
+        // asymptotically; with no knee it is pinned to it
+        let output_db = if knee > 0.0 {
+            threshold + knee * (1.0 - (-(input_db - threshold) / knee).exp())
+        } else {
+            self.config.ceiling_db
+        };
+        db_to_linear(output_db - input_db).min(1.0)
+    }
+}
+
+fn db_to_linear(db: f32) -> f32 {
+    10f32.powf(db / 20.0)
+}
+
+fn linear_to_db(linear: f32) -> f32 {
+    20.0 * linear.log10()
+}
+
+/// Blackman-windowed sinc taps interpolating `phase` frames past the center
+fn interpolation_taps(phase: f64) -> [f32; INTERPOLATOR_TAPS] {
+    let center = (INTERPOLATOR_TAPS / 2 - 1) as f64;
+    let half_width = (INTERPOLATOR_TAPS / 2) as f64;
+    let mut taps = [0.0; INTERPOLATOR_TAPS];
+    for (index, tap) in taps.iter_mut().enumerate() {
+        let t = index as f64 - center - phase;
+        let sinc = if t == 0.0 {
+            1.0
+        } else {
+            (std::f64::consts::PI * t).sin() / (std::f64::consts::PI * t)
+        };
+        let x = (t / half_width + 1.0) / 2.0;
+        let window = 0.42 - 0.5 * (2.0 * std::f64::consts::PI * x).cos()
+            + 0.08 * (4.0 * std::f64::consts::PI * x).cos();
+        *tap = (sinc * window) as f32;
+    }
+    let sum: f32 = taps.iter().sum();
+    taps.map(|tap| tap / sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    const RATE: u32 = 44_100;
+
+    fn sine(amplitude: f32, frequency: f32, phase: f32, frames: usize) -> Vec<f32> {
+        (0..frames)
+            .map(|i| amplitude * (2.0 * PI * frequency * i as f32 / RATE as f32 + phase).sin())
+            .collect()
+    }
+
+    fn limit(limiter: &mut Limiter, input: &[f32]) -> Vec<f32> {
+        let chunk = AudioFrameChunk::new(input.to_vec(), input.len(), Duration::ZERO);
+        let mut output = limiter.process(&chunk).samples;
+        output.extend(limiter.flush().samples);
+        output
+    }
+
+    #[test]
+    fn test_limits_over_unity_signal_to_ceiling() {
+        let config = LimiterConfig::default();
+        let ceiling = db_to_linear(config.ceiling_db);
+        let mut limiter = Limiter::new(config, RATE, 1);
+
+        // Quiet intro, then a 1kHz sine 6dB over full scale
+        let mut input = sine(0.25, 1000.0, 0.0, 4410);
+        input.extend(sine(2.0, 1000.0, 0.0, 8820));
+        let output = limit(&mut limiter, &input);
+        assert_eq!(output.len(), input.len());
+
+        let peak = output.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+        assert!(peak <= ceiling, "peak {} above ceiling {}", peak, ceiling);
+        assert!(peak > ceiling * 0.95, "over-limited: peak {}", peak);
+
+        // The output is the input scaled by a smooth gain: no flat tops and
+        // no gain jumps between samples
+        let gains: Vec<f32> = output
+            .iter()
+            .zip(&input)
+            .filter(|(_, i)| i.abs() > 0.1)
+            .map(|(o, i)| o / i)
+            .collect();
+        let max_step = gains
+            .windows(2)
+            .map(|pair| (pair[1] - pair[0]).abs())
+            .fold(0.0f32, f32::max);
+        assert!(max_step < 0.02, "gain jumped by {}", max_step);
+        assert!(gains.iter().all(|g| *g > 0.0 && *g <= 1.0));
+
+        // The quiet intro passes through untouched
+        assert!((output[100] - input[100]).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_catches_inter_sample_peaks() {
+        // A sine at a quarter of the sample rate, sampled 45° off its peaks:
+        // samples reach only 0.707 of the true 1.2 peak
+        let input = sine(1.2, RATE as f32 / 4.0, PI / 4.0, 4410);
+        let sample_peak = input.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+        let ceiling = db_to_linear(LimiterConfig::default().ceiling_db);
+        assert!(sample_peak < ceiling);
+
+        let mut sample_peak_limiter = Limiter::new(
+            LimiterConfig {
+                true_peak: false,
+                ..Default::default()
+            },
+            RATE,
+            1,
+        );
+        let untouched = limit(&mut sample_peak_limiter, &input);
+        assert!((untouched[1000] - input[1000]).abs() < 1e-6);
+
+        // True-peak detection lowers the gain so the reconstructed peak
+        // stays (within interpolation error) under the ceiling
+        let mut limiter = Limiter::new(LimiterConfig::default(), RATE, 1);
+        let output = limit(&mut limiter, &input);
+        let gain = output[2000] / input[2000];
+        assert!(1.2 * gain <= ceiling * 1.02, "true peak {}", 1.2 * gain);
+    }
+
+    #[test]
+    fn test_soft_knee_reduces_gain_below_ceiling() {
+        let config = LimiterConfig {
+            knee_db: 6.0,
+            ..Default::default()
+        };
+        let limiter = Limiter::new(config, RATE, 2);
+
+        // Below the knee nothing happens; inside it the gain eases down
+        assert_eq!(limiter.required_gain(db_to_linear(-7.0)), 1.0);
+        let in_knee = limiter.required_gain(db_to_linear(-3.0));
+        assert!(in_knee < 1.0 && in_knee > db_to_linear(-2.7));
+
+        // Far above it the output still never passes the ceiling
+        let loud = db_to_linear(12.0);
+        assert!(loud * limiter.required_gain(loud) <= db_to_linear(-0.3));
+    }
+}
@@ -0,0 +1,450 @@
+//! # Loudness Analysis
+//!
+//! Measures integrated loudness and true peak per EBU R128 / ITU-R BS.1770.
+//!
+//! ## Overview
+//!
+//! `LoudnessAnalyzer` is fed decoded chunks and runs each channel through the
+//! K-weighting filter (a high-shelf boost followed by a high-pass), summing
+//! the filtered power into 400ms blocks that overlap by 75%. When the track
+//! ends, blocks quieter than -70 LUFS are dropped, then blocks more than
+//! 10 LU below the remaining average, and the rest are averaged into the
+//! integrated loudness.
+//!
+//! Only one value per 100ms is kept, so a whole track is analyzed without
+//! buffering its audio.
+//!
+//! True peak is measured on a 4x oversampled signal (2x above 96kHz), which
+//! catches the inter-sample peaks a DAC reconstructs between samples.
+//!
+//! [`Loudness::gain_db`] gives the gain that brings a track to the
+//! ReplayGain 2.0 reference of -18 LUFS. The result is stored on the library
+//! track (`replay_gain_millibels` / `true_peak_millibels`) so playback can
+//! level tracks against each other.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use core_playback::{AudioDecoder, LoudnessAnalyzer};
+//!
+//! # async fn example(mut decoder: impl AudioDecoder) -> core_playback::Result<()> {
+//! let loudness = LoudnessAnalyzer::analyze(&mut decoder).await?;
+//! println!(
+//!     "{:.1} LUFS, peak {:.1} dBTP, gain {:+.1} dB",
+//!     loudness.integrated_lufs,
+//!     loudness.true_peak_dbtp,
+//!     loudness.gain_db()
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::Result;
+use crate::traits::{AudioDecoder, AudioFrameChunk};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::f64::consts::PI;
+
+/// ReplayGain 2.0 reference loudness, in LUFS
+pub const REFERENCE_LUFS: f64 = -18.0;
+
+/// Blocks below this are never counted, in LUFS
+const ABSOLUTE_GATE_LUFS: f64 = -70.0;
+
+/// Blocks this far below the ungated average are dropped, in LU
+const RELATIVE_GATE_LU: f64 = 10.0;
+
+/// 100ms steps per 400ms gating block
+const STEPS_PER_BLOCK: usize = 4;
+
+/// Interpolation filter taps per oversampling phase
+const TRUE_PEAK_TAPS: usize = 12;
+
+/// Loudness of a track.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Loudness {
+    /// Gated integrated loudness, in LUFS (`-inf` for silence)
+    pub integrated_lufs: f64,
+    /// Highest inter-sample peak, in dBTP (`-inf` for digital silence)
+    pub true_peak_dbtp: f64,
+}
+
+impl Loudness {
+    /// Gain that brings the track to [`REFERENCE_LUFS`], in dB.
+    ///
+    /// Silent tracks get no gain.
+    pub fn gain_db(&self) -> f64 {
+        if self.integrated_lufs.is_finite() {
+            REFERENCE_LUFS - self.integrated_lufs
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Measures integrated loudness and true peak of decoded audio.
+#[derive(Debug, Clone)]
+pub struct LoudnessAnalyzer {
+    channels: usize,
+    weights: Vec<f64>,
+    /// High-shelf and high-pass stages of the K-weighting filter
+    shelf: Biquad,
+    high_pass: Biquad,
+    /// Filter state per channel, one pair per stage
+    state: Vec<[[f64; 2]; 2]>,
+    /// Frames per 100ms step
+    step_frames: usize,
+    step_power: f64,
+    step_len: usize,
+    /// Weighted power of the last `STEPS_PER_BLOCK` steps
+    recent_steps: VecDeque<f64>,
+    /// Mean weighted power of each 400ms block
+    blocks: Vec<f64>,
+    true_peak: TruePeak,
+}
+
+impl LoudnessAnalyzer {
+    /// Create an analyzer for interleaved audio at `sample_rate`.
+    pub fn new(sample_rate: u32, channels: u16) -> Self {
+        let rate = f64::from(sample_rate.max(1));
+        let channels = usize::from(channels.max(1));
+
+        Self {
+            channels,
+            weights: (0..channels)
+                .map(|index| channel_weight(index, channels))
+                .collect(),
+            shelf: Biquad::high_shelf(rate),
+            high_pass: Biquad::high_pass(rate),
+            state: vec![[[0.0; 2]; 2]; channels],
+            step_frames: (sample_rate as usize / 10).max(1),
+            step_power: 0.0,
+            step_len: 0,
+            recent_steps: VecDeque::with_capacity(STEPS_PER_BLOCK),
+            blocks: Vec::new(),
+            true_peak: TruePeak::new(sample_rate, channels),
+        }
+    }
+
+    /// Decode a whole track and measure its loudness.
+    ///
+    /// # Errors
+    ///
+    /// Returns any probe or decode error from `decoder`.
+    pub async fn analyze<D: AudioDecoder + ?Sized>(decoder: &mut D) -> Result<Loudness> {
+        let probe = decoder.probe().await?;
+        let mut analyzer = Self::new(probe.format.sample_rate, probe.format.channels);
+        while let Some(chunk) = decoder.decode_frames(4096).await? {
+            analyzer.process(&chunk);
+        }
+        Ok(analyzer.finish())
+    }
+
+    /// Feed the next chunk of interleaved audio.
+    pub fn process(&mut self, chunk: &AudioFrameChunk) {
+        for frame in chunk.samples.chunks_exact(self.channels) {
+            let mut power = 0.0;
+            for (channel, &sample) in frame.iter().enumerate() {
+                let sample = f64::from(sample);
+                self.true_peak.push(channel, sample);
+
+                let [shelf, high_pass] = &mut self.state[channel];
+                let filtered = self.high_pass.run(high_pass, self.shelf.run(shelf, sample));
+                power += self.weights[channel] * filtered * filtered;
+            }
+
+            self.step_power += power;
+            self.step_len += 1;
+            if self.step_len == self.step_frames {
+                self.finish_step();
+            }
+        }
+    }
+
+    /// Loudness of the audio processed so far.
+    ///
+    /// Tracks shorter than one 400ms block measure as silence.
+    pub fn finish(&self) -> Loudness {
+        let loud: Vec<f64> = self
+            .blocks
+            .iter()
+            .copied()
+            .filter(|&power| block_loudness(power) > ABSOLUTE_GATE_LUFS)
+            .collect();
+
+        let integrated_lufs = if loud.is_empty() {
+            f64::NEG_INFINITY
+        } else {
+            let threshold = block_loudness(mean(&loud)) - RELATIVE_GATE_LU;
+            let gated: Vec<f64> = loud
+                .into_iter()
+                .filter(|&power| block_loudness(power) > threshold)
+                .collect();
+            block_loudness(mean(&gated))
+        };
+
+        Loudness {
+            integrated_lufs,
+            true_peak_dbtp: 20.0 * self.true_peak.peak.log10(),
+        }
+    }
+
+    /// Close the current 100ms step and record the block it completes
+    fn finish_step(&mut self) {
+        if self.recent_steps.len() == STEPS_PER_BLOCK {
+            self.recent_steps.pop_front();
+        }
+        self.recent_steps.push_back(self.step_power);
+        self.step_power = 0.0;
+        self.step_len = 0;
+
+        if self.recent_steps.len() == STEPS_PER_BLOCK {
+            let total: f64 = self.recent_steps.iter().sum();
+            self.blocks
+                .push(total / (self.step_frames * STEPS_PER_BLOCK) as f64);
+        }
+    }
+}
+
+/// BS.1770 channel weight; surrounds of a 5.1 layout (L R C LFE Ls Rs) are
+/// boosted and the LFE is ignored
+fn channel_weight(index: usize, channels: usize) -> f64 {
+    match (channels, index) {
+        (6, 3) => 0.0,
+        (6, 4 | 5) => 1.41,
+        _ => 1.0,
+    }
+}
+
+/// Loudness of a block from its mean weighted power, in LUFS
+fn block_loudness(power: f64) -> f64 {
+    -0.691 + 10.0 * power.log10()
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+/// Second-order IIR section (transposed direct form II)
+#[derive(Debug, Clone, Copy)]
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+}
+
+impl Biquad {
+    /// K-weighting stage 1: +4dB shelf modelling the head's acoustics.
+    ///
+    /// BS.1770 only lists 48kHz coefficients; these are derived from the
+    /// analog prototype so any sample rate matches them.
+    fn high_shelf(rate: f64) -> Self {
+        let f0 = 1681.974450955533;
+        let gain_db = 3.999843853973347;
+        let q = 0.7071752369554196;
+
+        let k = (PI * f0 / rate).tan();
+        let vh = 10f64.powf(gain_db / 20.0);
+        let vb = vh.powf(0.4996667741545416);
+        let a0 = 1.0 + k / q + k * k;
+
+        Self {
+            b: [
+                (vh + vb * k / q + k * k) / a0,
+                2.0 * (k * k - vh) / a0,
+                (vh - vb * k / q + k * k) / a0,
+            ],
+            a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+        }
+    }
+
+    /// K-weighting stage 2: RLB high-pass at ~38Hz
+    fn high_pass(rate: f64) -> Self {
+        let f0 = 38.13547087602444;
+        let q = 0.5003270373238773;
+
+        let k = (PI * f0 / rate).tan();
+        let a0 = 1.0 + k / q + k * k;
+
+        Self {
+            b: [1.0, -2.0, 1.0],
+            a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+        }
+    }
+
+    fn run(&self, state: &mut [f64; 2], input: f64) -> f64 {
+        let output = self.b[0] * input + state[0];
+        state[0] = self.b[1] * input - self.a[0] * output + state[1];
+        state[1] = self.b[2] * input - self.a[1] * output;
+        output
+    }
+}
+
+/// Polyphase interpolator tracking the oversampled peak
+#[derive(Debug, Clone)]
+struct TruePeak {
+    /// Filter taps for each oversampling phase
+    phases: Vec<[f64; TRUE_PEAK_TAPS]>,
+    /// Most recent input samples per channel, newest first
+    history: Vec<[f64; TRUE_PEAK_TAPS]>,
+    peak: f64,
+}
+
+impl TruePeak {
+    fn new(sample_rate: u32, channels: usize) -> Self {
+        let factor = match sample_rate {
+            0..=95_999 => 4,
+            96_000..=191_999 => 2,
+            _ => 1,
+        };
+
+        // Windowed sinc low-pass at the original Nyquist frequency, split
+        // into phases each normalized to unity gain
+        let len = factor * TRUE_PEAK_TAPS;
+        let center = (len - 1) as f64 / 2.0;
+        let phases = (0..factor)
+            .map(|phase| {
+                let mut taps = [0.0; TRUE_PEAK_TAPS];
+                for (k, tap) in taps.iter_mut().enumerate() {
+                    let m = phase + k * factor;
+                    let x = (m as f64 - center) / factor as f64;
+                    let sinc = if x == 0.0 {
+                        1.0
+                    } else {
+                        (PI * x).sin() / (PI * x)
+                    };
+                    let window = 0.42 - 0.5 * (2.0 * PI * (m as f64 + 0.5) / len as f64).cos()
+                        + 0.08 * (4.0 * PI * (m as f64 + 0.5) / len as f64).cos();
+                    *tap = sinc * window;
+                }
+                let sum: f64 = taps.iter().sum();
+                taps.iter_mut().for_each(|tap| *tap /= sum);
+                taps
+            })
+            .collect();
+
+        Self {
+            phases,
+            history: vec![[0.0; TRUE_PEAK_TAPS]; channels],
+            peak: 0.0,
+        }
+    }
+
+    fn push(&mut self, channel: usize, sample: f64) {
+        let history = &mut self.history[channel];
+        history.copy_within(0..TRUE_PEAK_TAPS - 1, 1);
+        history[0] = sample;
+
+        let mut peak = self.peak.max(sample.abs());
+        for taps in &self.phases {
+            let value: f64 = taps.iter().zip(history.iter()).map(|(t, x)| t * x).sum();
+            peak = peak.max(value.abs());
+        }
+        self.peak = peak;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    const RATE: u32 = 48_000;
+
+    /// `seconds` of a sine at `frequency` with a peak of `level_db` dBFS,
+    /// copied to every channel
+    fn tone(seconds: f64, frequency: f64, level_db: f64, channels: usize) -> AudioFrameChunk {
+        tone_with_phase(seconds, frequency, level_db, channels, 0.0)
+    }
+
+    fn tone_with_phase(
+        seconds: f64,
+        frequency: f64,
+        level_db: f64,
+        channels: usize,
+        phase: f64,
+    ) -> AudioFrameChunk {
+        let amplitude = 10f64.powf(level_db / 20.0);
+        let frames = (seconds * RATE as f64) as usize;
+        let samples = (0..frames)
+            .flat_map(|n| {
+                let t = n as f64 / RATE as f64;
+                let sample = (amplitude * (2.0 * PI * frequency * t + phase).sin()) as f32;
+                vec![sample; channels]
+            })
+            .collect();
+        AudioFrameChunk::new(samples, frames, Duration::ZERO)
+    }
+
+    fn measure(channels: u16, chunks: &[AudioFrameChunk]) -> Loudness {
+        let mut analyzer = LoudnessAnalyzer::new(RATE, channels);
+        chunks.iter().for_each(|chunk| analyzer.process(chunk));
+        analyzer.finish()
+    }
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "expected {expected} ±{tolerance}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn test_stereo_sine_at_reference_level() {
+        // EBU Tech 3341 case 1: 1kHz at -23 dBFS on both channels is -23 LUFS
+        let loudness = measure(2, &[tone(5.0, 1000.0, -23.0, 2)]);
+
+        assert_close(loudness.integrated_lufs, -23.0, 1.0);
+        assert_close(loudness.gain_db(), 5.0, 1.0);
+    }
+
+    #[test]
+    fn test_mono_counts_one_channel() {
+        // A single channel carries half the power of the same tone in stereo
+        let loudness = measure(1, &[tone(5.0, 1000.0, -20.0, 1)]);
+
+        assert_close(loudness.integrated_lufs, -23.0, 1.0);
+    }
+
+    #[test]
+    fn test_relative_gate_ignores_quiet_passages() {
+        // EBU Tech 3341 case 3, shortened: quiet edges are gated out
+        let loudness = measure(
+            2,
+            &[
+                tone(2.0, 1000.0, -36.0, 2),
+                tone(6.0, 1000.0, -23.0, 2),
+                tone(2.0, 1000.0, -36.0, 2),
+            ],
+        );
+
+        assert_close(loudness.integrated_lufs, -23.0, 1.0);
+
+        // Silence falls below the absolute gate
+        let silent = measure(2, &[tone(2.0, 1000.0, -23.0, 2), tone(4.0, 0.0, 0.0, 2)]);
+        assert_close(silent.integrated_lufs, -23.0, 1.0);
+    }
+
+    #[test]
+    fn test_true_peak_catches_inter_sample_peaks() {
+        // A quarter-rate sine sampled 45° off its crests never hits a
+        // sample at full amplitude, so sample peak reads ~3dB low
+        let chunk = tone_with_phase(1.0, RATE as f64 / 4.0, -6.0, 2, PI / 4.0);
+        let sample_peak = chunk
+            .samples
+            .iter()
+            .fold(0.0f32, |peak, sample| peak.max(sample.abs()));
+        assert_close(20.0 * f64::from(sample_peak).log10(), -9.0, 0.1);
+
+        let loudness = measure(2, &[chunk]);
+        assert_close(loudness.true_peak_dbtp, -6.0, 0.5);
+    }
+
+    #[test]
+    fn test_silence_has_no_gain() {
+        let loudness = measure(2, &[tone(1.0, 0.0, 0.0, 2)]);
+
+        assert_eq!(loudness.integrated_lufs, f64::NEG_INFINITY);
+        assert_eq!(loudness.true_peak_dbtp, f64::NEG_INFINITY);
+        assert_eq!(loudness.gain_db(), 0.0);
+    }
+}
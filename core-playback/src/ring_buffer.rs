@@ -8,7 +8,8 @@
 //! - **Native**: Uses atomic operations for lock-free read/write
 //! - **WASM**: Uses Rc<RefCell<>> for single-threaded access
 //! - **Capacity**: Fixed size determined at creation
-//! - **Overwrite Policy**: Old samples are overwritten when buffer is full
+//! - **Overflow Policy**: Chosen with [`OverflowPolicy`]; by default old samples
+//!   are overwritten when the buffer is full
 //!
 //! ## Usage
 //!
@@ -25,10 +26,18 @@
 //! // Consumer: Read samples
 //! let mut output = vec![0.0f32; 1024];
 //! let read = buffer.read(&mut output);
+//!
+//! // Playback that must not lose samples waits for the consumer instead
+//! use core_playback::ring_buffer::OverflowPolicy;
+//! let reliable = RingBuffer::new(44100 * 2).with_policy(OverflowPolicy::Block);
+//!
+//! // Visualizers: levels over the last 1024 frames, without consuming them
+//! let stereo = RingBuffer::new(44100 * 2).with_channels(2);
+//! let meter = stereo.peek_meter(1024);
 //! ```
 
 #[cfg(not(target_arch = "wasm32"))]
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicU16, AtomicU8, AtomicUsize, Ordering};
 #[cfg(not(target_arch = "wasm32"))]
 use std::sync::Arc;
 
@@ -37,6 +46,70 @@ use std::cell::RefCell;
 #[cfg(target_arch = "wasm32")]
 use std::rc::Rc;
 
+/// What a [`RingBuffer`] does with samples written while it is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Wait for the consumer to free space, so no sample is ever lost.
+    ///
+    /// Suits reliable playback. On WASM there is no other thread to wait
+    /// for, so the write stops once the buffer is full and returns how many
+    /// samples fit; the caller retries the rest.
+    Block,
+    /// Keep the buffered samples and discard the ones that do not fit.
+    DropNewest,
+    /// Discard the oldest unread samples to make room, so the consumer
+    /// always sees the most recent audio. Suits live visualization.
+    #[default]
+    OverwriteOldest,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl OverflowPolicy {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0 => Self::Block,
+            1 => Self::DropNewest,
+            _ => Self::OverwriteOldest,
+        }
+    }
+}
+
+/// Per-channel levels over the most recent samples of a [`RingBuffer`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MeterFrame {
+    /// Peak absolute sample value of each channel.
+    pub peak: Vec<f32>,
+    /// Root mean square of each channel.
+    pub rms: Vec<f32>,
+    /// Number of frames the levels were measured over.
+    pub frames: usize,
+}
+
+impl MeterFrame {
+    /// Measure interleaved `samples` that start on a frame boundary.
+    fn measure(samples: &[f32], channels: usize) -> Self {
+        let frames = samples.len() / channels;
+        let mut peak = vec![0.0f32; channels];
+        let mut sum_squares = vec![0.0f64; channels];
+
+        for frame in samples.chunks_exact(channels) {
+            for (channel, &sample) in frame.iter().enumerate() {
+                peak[channel] = peak[channel].max(sample.abs());
+                sum_squares[channel] += sample as f64 * sample as f64;
+            }
+        }
+
+        let rms = sum_squares
+            .iter()
+            .map(|&sum| match frames {
+                0 => 0.0,
+                _ => (sum / frames as f64).sqrt() as f32,
+            })
+            .collect();
+        Self { peak, rms, frames }
+    }
+}
+
 // ============================================================================
 // Native Implementation (Lock-Free with Atomics)
 // ============================================================================
@@ -56,8 +129,20 @@ pub struct RingBuffer {
 struct RingBufferInner {
     buffer: parking_lot::Mutex<Vec<f32>>,
     capacity: usize,
+    /// Total samples ever written (not wrapped; index with `% capacity`).
     write_pos: AtomicUsize,
+    /// Total samples ever consumed (not wrapped; index with `% capacity`).
     read_pos: AtomicUsize,
+    /// [`OverflowPolicy`] as `u8`, read without locking on every write.
+    policy: AtomicU8,
+    /// Total samples discarded by the overflow policy.
+    dropped: AtomicUsize,
+    /// Signalled when the consumer frees space for a blocked writer.
+    space: parking_lot::Condvar,
+    /// Interleaved channels, used to split samples for metering.
+    channels: AtomicU16,
+    /// Last meter reading, returned when the buffer is busy.
+    last_meter: parking_lot::Mutex<MeterFrame>,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -72,34 +157,136 @@ impl RingBuffer {
                 capacity,
                 write_pos: AtomicUsize::new(0),
                 read_pos: AtomicUsize::new(0),
+                policy: AtomicU8::new(OverflowPolicy::default() as u8),
+                dropped: AtomicUsize::new(0),
+                space: parking_lot::Condvar::new(),
+                channels: AtomicU16::new(1),
+                last_meter: parking_lot::Mutex::new(MeterFrame::default()),
             }),
         }
     }
 
+    /// Set how many channels the samples are interleaved from.
+    ///
+    /// Only metering looks at this; buffers are treated as mono by default.
+    pub fn with_channels(self, channels: u16) -> Self {
+        self.inner
+            .channels
+            .store(channels.max(1), Ordering::Release);
+        self
+    }
+
+    /// Measure per-channel peak and RMS over the last `window` frames
+    /// written, without consuming them.
+    ///
+    /// Safe to call from a UI thread while the audio thread reads and
+    /// writes. The samples are copied out under the buffer lock only if it
+    /// is free; otherwise the previous reading is returned, so meters are
+    /// approximate under heavy contention. Writes of whole frames are
+    /// assumed.
+    pub fn peek_meter(&self, window: usize) -> MeterFrame {
+        let channels = self.inner.channels.load(Ordering::Acquire) as usize;
+        let samples = match self.inner.buffer.try_lock() {
+            Some(buffer) => {
+                let write_pos = self.inner.write_pos.load(Ordering::Acquire);
+                let history = write_pos.min(self.inner.capacity) / channels * channels;
+                let len = window.saturating_mul(channels).min(history);
+                let start = write_pos.wrapping_sub(len);
+                (0..len)
+                    .map(|i| buffer[start.wrapping_add(i) % self.inner.capacity])
+                    .collect::<Vec<_>>()
+            }
+            None => return self.inner.last_meter.lock().clone(),
+        };
+
+        let meter = MeterFrame::measure(&samples, channels);
+        *self.inner.last_meter.lock() = meter.clone();
+        meter
+    }
+
+    /// Set what happens to samples written while the buffer is full.
+    ///
+    /// The policy is shared by every clone of this buffer.
+    pub fn with_policy(self, policy: OverflowPolicy) -> Self {
+        self.inner.policy.store(policy as u8, Ordering::Release);
+        self
+    }
+
+    /// Returns the buffer's overflow policy.
+    pub fn policy(&self) -> OverflowPolicy {
+        OverflowPolicy::from_u8(self.inner.policy.load(Ordering::Acquire))
+    }
+
+    /// Returns the total number of samples discarded because the buffer
+    /// was full.
+    pub fn dropped(&self) -> usize {
+        self.inner.dropped.load(Ordering::Acquire)
+    }
+
     /// Write samples to the ring buffer.
     ///
-    /// Returns the number of samples actually written. If the buffer is full,
-    /// old samples will be overwritten.
+    /// Returns the number of samples actually written. What happens when the
+    /// buffer is full depends on its [`OverflowPolicy`]: old samples are
+    /// overwritten, the new ones are dropped, or the call blocks until the
+    /// consumer has read enough.
     pub fn write(&self, samples: &[f32]) -> usize {
         if samples.is_empty() {
             return 0;
         }
 
+        let policy = self.policy();
         let mut buffer = self.inner.buffer.lock();
-        let write_pos = self.inner.write_pos.load(Ordering::Acquire);
-        let mut written = 0;
-
-        for &sample in samples {
-            let pos = (write_pos + written) % self.inner.capacity;
-            buffer[pos] = sample;
-            written += 1;
+        let mut remaining = samples;
+
+        loop {
+            let write_pos = self.inner.write_pos.load(Ordering::Acquire);
+            let read_pos = self.inner.read_pos.load(Ordering::Acquire);
+            let count = match policy {
+                OverflowPolicy::OverwriteOldest => remaining.len(),
+                _ => {
+                    let free =
+                        self.inner.capacity - self.available_samples_internal(read_pos, write_pos);
+                    remaining.len().min(free)
+                }
+            };
+
+            for (i, &sample) in remaining[..count].iter().enumerate() {
+                let pos = write_pos.wrapping_add(i) % self.inner.capacity;
+                buffer[pos] = sample;
+            }
+            remaining = &remaining[count..];
+
+            let new_write_pos = write_pos.wrapping_add(count);
+            self.inner.write_pos.store(new_write_pos, Ordering::Release);
+
+            // Overwrite policy: drop the oldest samples so the reader only ever
+            // sees the most recent `capacity` samples.
+            let overrun = new_write_pos
+                .wrapping_sub(read_pos)
+                .saturating_sub(self.inner.capacity);
+            if overrun > 0 {
+                self.inner.read_pos.store(
+                    new_write_pos.wrapping_sub(self.inner.capacity),
+                    Ordering::Release,
+                );
+                self.inner.dropped.fetch_add(overrun, Ordering::AcqRel);
+            }
+
+            if remaining.is_empty() {
+                break;
+            }
+            match policy {
+                OverflowPolicy::Block => self.inner.space.wait(&mut buffer),
+                _ => {
+                    self.inner
+                        .dropped
+                        .fetch_add(remaining.len(), Ordering::AcqRel);
+                    break;
+                }
+            }
         }
 
-        self.inner
-            .write_pos
-            .store((write_pos + written) % self.inner.capacity, Ordering::Release);
-
-        written
+        samples.len() - remaining.len()
     }
 
     /// Read samples from the ring buffer.
@@ -118,14 +305,21 @@ impl RingBuffer {
         let available = self.available_samples_internal(read_pos, write_pos);
         let to_read = available.min(output.len());
 
-        for i in 0..to_read {
-            let pos = (read_pos + i) % self.inner.capacity;
-            output[i] = buffer[pos];
+        for (i, slot) in output.iter_mut().take(to_read).enumerate() {
+            let pos = read_pos.wrapping_add(i) % self.inner.capacity;
+            *slot = buffer[pos];
         }
 
         self.inner
             .read_pos
-            .store((read_pos + to_read) % self.inner.capacity, Ordering::Release);
+            .store(read_pos.wrapping_add(to_read), Ordering::Release);
+        drop(buffer);
+
+        // Wake a writer blocked on a full buffer; without one this is a
+        // single atomic check
+        if to_read > 0 {
+            self.inner.space.notify_one();
+        }
 
         to_read
     }
@@ -138,11 +332,7 @@ impl RingBuffer {
     }
 
     fn available_samples_internal(&self, read_pos: usize, write_pos: usize) -> usize {
-        if write_pos >= read_pos {
-            write_pos - read_pos
-        } else {
-            self.inner.capacity - read_pos + write_pos
-        }
+        write_pos.wrapping_sub(read_pos).min(self.inner.capacity)
     }
 
     /// Returns the number of samples that can be written before overwriting.
@@ -166,6 +356,8 @@ impl RingBuffer {
         buffer.fill(0.0);
         self.inner.write_pos.store(0, Ordering::Release);
         self.inner.read_pos.store(0, Ordering::Release);
+        drop(buffer);
+        self.inner.space.notify_all();
     }
 
     /// Returns `true` if the buffer has no samples available.
@@ -193,8 +385,15 @@ pub struct RingBuffer {
 struct RingBufferState {
     buffer: Vec<f32>,
     capacity: usize,
+    /// Total samples ever written (not wrapped; index with `% capacity`).
     write_pos: usize,
+    /// Total samples ever consumed (not wrapped; index with `% capacity`).
     read_pos: usize,
+    policy: OverflowPolicy,
+    /// Total samples discarded by the overflow policy.
+    dropped: usize,
+    /// Interleaved channels, used to split samples for metering.
+    channels: usize,
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -207,27 +406,92 @@ impl RingBuffer {
                 capacity,
                 write_pos: 0,
                 read_pos: 0,
+                policy: OverflowPolicy::default(),
+                dropped: 0,
+                channels: 1,
             })),
         }
     }
 
+    /// Set how many channels the samples are interleaved from.
+    ///
+    /// Only metering looks at this; buffers are treated as mono by default.
+    pub fn with_channels(self, channels: u16) -> Self {
+        self.inner.borrow_mut().channels = channels.max(1) as usize;
+        self
+    }
+
+    /// Measure per-channel peak and RMS over the last `window` frames
+    /// written, without consuming them.
+    pub fn peek_meter(&self, window: usize) -> MeterFrame {
+        let state = self.inner.borrow();
+        let channels = state.channels;
+        let history = state.write_pos.min(state.capacity) / channels * channels;
+        let len = window.saturating_mul(channels).min(history);
+        let start = state.write_pos.wrapping_sub(len);
+        let samples: Vec<f32> = (0..len)
+            .map(|i| state.buffer[start.wrapping_add(i) % state.capacity])
+            .collect();
+        MeterFrame::measure(&samples, channels)
+    }
+
+    /// Set what happens to samples written while the buffer is full.
+    ///
+    /// The policy is shared by every clone of this buffer.
+    pub fn with_policy(self, policy: OverflowPolicy) -> Self {
+        self.inner.borrow_mut().policy = policy;
+        self
+    }
+
+    /// Returns the buffer's overflow policy.
+    pub fn policy(&self) -> OverflowPolicy {
+        self.inner.borrow().policy
+    }
+
+    /// Returns the total number of samples discarded because the buffer
+    /// was full.
+    pub fn dropped(&self) -> usize {
+        self.inner.borrow().dropped
+    }
+
     /// Write samples to the ring buffer.
+    ///
+    /// A [`OverflowPolicy::Block`] buffer cannot wait here, so it writes
+    /// only what fits and returns the count.
     pub fn write(&self, samples: &[f32]) -> usize {
         if samples.is_empty() {
             return 0;
         }
 
         let mut state = self.inner.borrow_mut();
-        let mut written = 0;
-
-        for &sample in samples {
-            let pos = (state.write_pos + written) % state.capacity;
+        let count = match state.policy {
+            OverflowPolicy::OverwriteOldest => samples.len(),
+            _ => samples
+                .len()
+                .min(state.capacity - self.available_samples_internal(&state)),
+        };
+
+        for (i, &sample) in samples[..count].iter().enumerate() {
+            let pos = state.write_pos.wrapping_add(i) % state.capacity;
             state.buffer[pos] = sample;
-            written += 1;
         }
 
-        state.write_pos = (state.write_pos + written) % state.capacity;
-        written
+        state.write_pos = state.write_pos.wrapping_add(count);
+
+        // Overwrite policy: drop the oldest samples.
+        let overrun = state
+            .write_pos
+            .wrapping_sub(state.read_pos)
+            .saturating_sub(state.capacity);
+        if overrun > 0 {
+            state.read_pos = state.write_pos.wrapping_sub(state.capacity);
+            state.dropped += overrun;
+        }
+        if state.policy == OverflowPolicy::DropNewest {
+            state.dropped += samples.len() - count;
+        }
+
+        count
     }
 
     /// Read samples from the ring buffer.
@@ -240,12 +504,12 @@ impl RingBuffer {
         let available = self.available_samples_internal(&state);
         let to_read = available.min(output.len());
 
-        for i in 0..to_read {
-            let pos = (state.read_pos + i) % state.capacity;
-            output[i] = state.buffer[pos];
+        for (i, slot) in output.iter_mut().take(to_read).enumerate() {
+            let pos = state.read_pos.wrapping_add(i) % state.capacity;
+            *slot = state.buffer[pos];
         }
 
-        state.read_pos = (state.read_pos + to_read) % state.capacity;
+        state.read_pos = state.read_pos.wrapping_add(to_read);
         to_read
     }
 
@@ -256,11 +520,7 @@ impl RingBuffer {
     }
 
     fn available_samples_internal(&self, state: &RingBufferState) -> usize {
-        if state.write_pos >= state.read_pos {
-            state.write_pos - state.read_pos
-        } else {
-            state.capacity - state.read_pos + state.write_pos
-        }
+        state.write_pos.wrapping_sub(state.read_pos).min(state.capacity)
     }
 
     /// Returns the number of samples that can be written before overwriting.
@@ -420,4 +680,179 @@ mod tests {
         let free = buffer.free_space();
         assert_eq!(free, 70);
     }
+
+    #[test]
+    fn test_ring_buffer_drop_newest() {
+        let buffer = RingBuffer::new(4).with_policy(OverflowPolicy::DropNewest);
+
+        let written = buffer.write(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(written, 4);
+        assert_eq!(buffer.dropped(), 2);
+
+        // The buffered samples are kept
+        let mut output = vec![0.0; 4];
+        assert_eq!(buffer.read(&mut output), 4);
+        assert_eq!(output, vec![1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_ring_buffer_overwrite_counts_dropped() {
+        let buffer = RingBuffer::new(4);
+        assert_eq!(buffer.policy(), OverflowPolicy::OverwriteOldest);
+
+        let written = buffer.write(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(written, 6);
+        assert_eq!(buffer.dropped(), 2);
+
+        let mut output = vec![0.0; 4];
+        assert_eq!(buffer.read(&mut output), 4);
+        assert_eq!(output, vec![3.0, 4.0, 5.0, 6.0]);
+    }
+
+    /// Writes `0..total` in chunks as fast as possible while a slower
+    /// consumer drains the buffer, returning what the consumer received
+    #[cfg(not(target_arch = "wasm32"))]
+    fn saturate(policy: OverflowPolicy, total: usize) -> (RingBuffer, Vec<f32>) {
+        use std::sync::atomic::AtomicBool;
+        use std::time::Duration;
+
+        let buffer = RingBuffer::new(256).with_policy(policy);
+        let done = Arc::new(AtomicBool::new(false));
+
+        let producer = {
+            let buffer = buffer.clone();
+            let done = done.clone();
+            std::thread::spawn(move || {
+                let samples: Vec<f32> = (0..total).map(|i| i as f32).collect();
+                for chunk in samples.chunks(64) {
+                    buffer.write(chunk);
+                }
+                done.store(true, Ordering::Release);
+            })
+        };
+
+        let mut received = Vec::new();
+        let mut output = vec![0.0; 32];
+        loop {
+            let finished = done.load(Ordering::Acquire);
+            let read = buffer.read(&mut output);
+            received.extend_from_slice(&output[..read]);
+            if finished && read == 0 {
+                break;
+            }
+            std::thread::sleep(Duration::from_micros(200));
+        }
+        producer.join().unwrap();
+        (buffer, received)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_ring_buffer_block_applies_backpressure() {
+        let (buffer, received) = saturate(OverflowPolicy::Block, 4096);
+
+        let expected: Vec<f32> = (0..4096).map(|i| i as f32).collect();
+        assert_eq!(received, expected);
+        assert_eq!(buffer.dropped(), 0);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_ring_buffer_drop_newest_under_saturation() {
+        let (buffer, received) = saturate(OverflowPolicy::DropNewest, 4096);
+
+        // The first buffer's worth survives; later samples are lost, never reordered
+        assert!(buffer.dropped() > 0);
+        assert_eq!(received.len() + buffer.dropped(), 4096);
+        assert!(received.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(received[0], 0.0);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_ring_buffer_overwrite_oldest_under_saturation() {
+        let (buffer, received) = saturate(OverflowPolicy::OverwriteOldest, 4096);
+
+        // The newest samples survive; older ones are lost, never reordered
+        assert!(buffer.dropped() > 0);
+        assert_eq!(received.len() + buffer.dropped(), 4096);
+        assert!(received.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(*received.last().unwrap(), 4095.0);
+    }
+
+    #[test]
+    fn test_peek_meter_per_channel_levels() {
+        let buffer = RingBuffer::new(1024).with_channels(2);
+
+        // Left holds 0.5, right alternates between full scale and silence
+        let samples: Vec<f32> = (0..100)
+            .flat_map(|i| [0.5, if i % 2 == 0 { 1.0 } else { 0.0 }])
+            .collect();
+        buffer.write(&samples);
+
+        let meter = buffer.peek_meter(100);
+        assert_eq!(meter.frames, 100);
+        assert_eq!(meter.peak, vec![0.5, 1.0]);
+        assert!((meter.rms[0] - 0.5).abs() < 1e-6);
+        assert!((meter.rms[1] - 0.5f32.sqrt()).abs() < 1e-6);
+
+        // Nothing was consumed
+        assert_eq!(buffer.available(), 200);
+    }
+
+    #[test]
+    fn test_peek_meter_sine_and_window() {
+        let buffer = RingBuffer::new(4096);
+
+        // 0.8 amplitude sine over whole periods, then a short louder burst
+        let sine: Vec<f32> = (0..1000)
+            .map(|i| 0.8 * (2.0 * std::f32::consts::PI * i as f32 / 100.0).sin())
+            .collect();
+        buffer.write(&sine);
+        let meter = buffer.peek_meter(1000);
+        assert!((meter.peak[0] - 0.8).abs() < 1e-3);
+        assert!((meter.rms[0] - 0.8 / 2.0f32.sqrt()).abs() < 1e-3);
+
+        buffer.write(&[-0.9; 10]);
+        let meter = buffer.peek_meter(10);
+        assert_eq!(meter.frames, 10);
+        assert_eq!(meter.peak, vec![0.9]);
+        assert!((meter.rms[0] - 0.9).abs() < 1e-6);
+
+        // The window is limited to what has been written
+        assert_eq!(buffer.peek_meter(10_000).frames, 1010);
+        buffer.clear();
+        assert_eq!(
+            buffer.peek_meter(10),
+            MeterFrame {
+                peak: vec![0.0],
+                rms: vec![0.0],
+                frames: 0,
+            }
+        );
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_peek_meter_while_audio_thread_reads() {
+        let buffer = RingBuffer::new(512).with_channels(2);
+        let audio = {
+            let buffer = buffer.clone();
+            std::thread::spawn(move || {
+                let mut output = vec![0.0; 64];
+                for _ in 0..2000 {
+                    buffer.write(&[0.25; 64]);
+                    buffer.read(&mut output);
+                }
+            })
+        };
+
+        // Readings are stale at worst, never torn
+        for _ in 0..2000 {
+            let meter = buffer.peek_meter(128);
+            assert!(meter.peak.iter().all(|&peak| peak == 0.0 || peak == 0.25));
+        }
+        audio.join().unwrap();
+        assert_eq!(buffer.peek_meter(128).rms, vec![0.25, 0.25]);
+    }
 }
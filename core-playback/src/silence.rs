@@ -0,0 +1,207 @@
+//! # Silence Detection
+//!
+//! Finds long silent intros and outros so playback can skip them.
+//!
+//! ## Overview
+//!
+//! `SilenceAnalyzer` is fed decoded chunks and tracks the first and last
+//! frame where any channel rises above `threshold_db`. Silence is only
+//! reported when it lasts at least `min_duration`, and `padding` is left in
+//! place before the first and after the last audible frame, so quiet fades
+//! and short pauses are never cut.
+//!
+//! The result is stored on the library track (`leading_silence_ms` /
+//! `trailing_silence_ms`) and passed back to the streaming service in
+//! `StreamingRequest::silence`, which skips it when
+//! `StreamingConfig::skip_silence` is enabled.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use core_playback::{AudioDecoder, SilenceAnalyzer, SilenceConfig};
+//!
+//! # async fn example(mut decoder: impl AudioDecoder) -> core_playback::Result<()> {
+//! let trim = SilenceAnalyzer::analyze(&mut decoder, SilenceConfig::default()).await?;
+//! println!("Skip {:?} at the start, {:?} at the end", trim.leading, trim.trailing);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::Result;
+use crate::traits::{AudioDecoder, AudioFrameChunk};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Thresholds for silence detection.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SilenceConfig {
+    /// Level below which audio counts as silent, in dBFS (default: -60)
+    pub threshold_db: f32,
+    /// Shortest silence worth trimming (default: 1 second)
+    pub min_duration: Duration,
+    /// Audio kept next to the first/last audible frame (default: 100ms)
+    pub padding: Duration,
+}
+
+impl Default for SilenceConfig {
+    fn default() -> Self {
+        Self {
+            threshold_db: -60.0,
+            min_duration: Duration::from_secs(1),
+            padding: Duration::from_millis(100),
+        }
+    }
+}
+
+/// Silence to skip at either end of a track.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SilenceTrim {
+    /// Silent intro to skip
+    pub leading: Duration,
+    /// Silent outro to skip
+    pub trailing: Duration,
+}
+
+impl SilenceTrim {
+    /// Returns `true` if there is nothing to trim.
+    pub fn is_empty(&self) -> bool {
+        self.leading.is_zero() && self.trailing.is_zero()
+    }
+}
+
+/// Detects leading and trailing silence in decoded audio.
+#[derive(Debug, Clone)]
+pub struct SilenceAnalyzer {
+    config: SilenceConfig,
+    sample_rate: u32,
+    threshold: f32,
+    frames: u64,
+    /// First and last audible frame
+    audible: Option<(u64, u64)>,
+}
+
+impl SilenceAnalyzer {
+    /// Create an analyzer for audio at `sample_rate`.
+    pub fn new(config: SilenceConfig, sample_rate: u32) -> Self {
+        Self {
+            config,
+            sample_rate,
+            threshold: 10f32.powf(config.threshold_db / 20.0),
+            frames: 0,
+            audible: None,
+        }
+    }
+
+    /// Decode a whole track and measure its silence.
+    ///
+    /// # Errors
+    ///
+    /// Returns any probe or decode error from `decoder`.
+    pub async fn analyze<D: AudioDecoder + ?Sized>(
+        decoder: &mut D,
+        config: SilenceConfig,
+    ) -> Result<SilenceTrim> {
+        let probe = decoder.probe().await?;
+        let mut analyzer = Self::new(config, probe.format.sample_rate);
+        while let Some(chunk) = decoder.decode_frames(4096).await? {
+            analyzer.process(&chunk);
+        }
+        Ok(analyzer.finish())
+    }
+
+    /// Feed the next chunk of interleaved audio.
+    pub fn process(&mut self, chunk: &AudioFrameChunk) {
+        if chunk.frames == 0 {
+            return;
+        }
+
+        let channels = chunk.samples.len() / chunk.frames;
+        for (index, frame) in chunk.samples.chunks_exact(channels.max(1)).enumerate() {
+            if frame.iter().any(|sample| sample.abs() > self.threshold) {
+                let position = self.frames + index as u64;
+                let first = self.audible.map_or(position, |(first, _)| first);
+                self.audible = Some((first, position));
+            }
+        }
+        self.frames += chunk.frames as u64;
+    }
+
+    /// Silence found in the audio processed so far.
+    ///
+    /// A track that is silent throughout is left untrimmed.
+    pub fn finish(&self) -> SilenceTrim {
+        let Some((first, last)) = self.audible else {
+            return SilenceTrim::default();
+        };
+
+        SilenceTrim {
+            leading: self.trim(first),
+            trailing: self.trim(self.frames - last - 1),
+        }
+    }
+
+    /// Duration to skip for a silent run of `frames`, after padding
+    fn trim(&self, frames: u64) -> Duration {
+        if self.sample_rate == 0 {
+            return Duration::ZERO;
+        }
+
+        let silence = Duration::from_secs_f64(frames as f64 / self.sample_rate as f64);
+        if silence < self.config.min_duration {
+            return Duration::ZERO;
+        }
+        silence.saturating_sub(self.config.padding)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 44_100;
+
+    /// Stereo chunk of `seconds` of a 440Hz sine at `amplitude`
+    fn tone(seconds: f64, amplitude: f32) -> AudioFrameChunk {
+        let frames = (seconds * RATE as f64) as usize;
+        let samples = (0..frames)
+            .flat_map(|n| {
+                let phase = 2.0 * std::f32::consts::PI * 440.0 * n as f32 / RATE as f32;
+                [amplitude * phase.sin(); 2]
+            })
+            .collect();
+        AudioFrameChunk::new(samples, frames, Duration::ZERO)
+    }
+
+    fn analyze(chunks: &[AudioFrameChunk]) -> SilenceTrim {
+        let mut analyzer = SilenceAnalyzer::new(SilenceConfig::default(), RATE);
+        chunks.iter().for_each(|chunk| analyzer.process(chunk));
+        analyzer.finish()
+    }
+
+    #[test]
+    fn test_detects_two_seconds_of_leading_silence() {
+        // Near-silent noise floor (-80 dBFS) rather than digital zero
+        let trim = analyze(&[tone(2.0, 1e-4), tone(3.0, 0.5), tone(0.5, 0.0)]);
+
+        // 2s less the 100ms padding; the sine's first samples are tiny, so
+        // allow a few milliseconds for where it crosses the threshold
+        let expected = Duration::from_millis(1_900);
+        assert!(
+            trim.leading >= expected && trim.leading < expected + Duration::from_millis(5),
+            "leading trim {:?}",
+            trim.leading
+        );
+
+        // Half a second of outro is below `min_duration`
+        assert_eq!(trim.trailing, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_quiet_intro_is_not_trimmed() {
+        // A -40 dBFS intro is quiet but audible
+        assert!(analyze(&[tone(2.0, 0.01), tone(1.0, 0.5)]).is_empty());
+
+        // All-silent tracks are left alone
+        assert!(analyze(&[tone(5.0, 0.0)]).is_empty());
+    }
+}
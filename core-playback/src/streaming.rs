@@ -15,7 +15,8 @@
 //! │                                         │
 //! │  1. Download chunks (HttpClient)        │
 //! │  2. Decode to PCM (AudioDecoder)        │
-//! │  3. Write to RingBuffer                 │
+//! │  3. Remap channels, limit peaks         │
+//! │  4. Write to RingBuffer and AudioSinks  │
 //! └────────────┬────────────────────────────┘
 //!              │ PCM Samples
 //!              ▼
@@ -33,8 +34,10 @@
 //!
 //! ```rust,no_run
 //! use core_playback::streaming::{StreamingService, StreamingRequest};
-//! use core_playback::{AudioSource, RingBuffer, StreamingConfig};
+//! use core_playback::{AudioDecoder, AudioSource, RingBuffer, StreamingConfig};
+//! use bridge_traits::http::HttpClient;
 //! use core_async::sync::CancellationToken;
+//! use std::sync::Arc;
 //!
 //! async fn start_streaming(
 //!     http_client: Arc<dyn HttpClient>,
@@ -51,6 +54,9 @@
 //!         },
 //!         ring_buffer: ring_buffer.clone(),
 //!         config,
+//!         output_channels: Some(2), // stereo output device
+//!         output_buffer_sizes: None,
+//!         silence: None,
 //!     };
 //!     
 //!     let service = StreamingService::new(http_client, decoder);
@@ -60,13 +66,22 @@
 //! }
 //! ```
 
+use crate::channel_mapper::ChannelMapper;
 use crate::config::{StreamingConfig, StreamingState, StreamingStats};
 use crate::error::{PlaybackError, Result};
+use crate::limiter::Limiter;
 use crate::ring_buffer::RingBuffer;
-use crate::traits::{AudioDecoder, AudioSource};
-use bridge_traits::http::HttpClient;
+use crate::silence::SilenceTrim;
+use crate::traits::{
+    AudioDecoder, AudioFormat, AudioFrameChunk, AudioSink, AudioSource, BufferSizeRange,
+    ProbeResult,
+};
+use bridge_traits::background::SystemEvent;
+use bridge_traits::http::{Backoff, HttpClient, JitterMode, RetryPolicy};
+use bridge_traits::time::SystemClock;
 use core_async::sync::CancellationToken;
 use core_async::time::sleep;
+use std::collections::VecDeque;
 #[cfg(not(target_arch = "wasm32"))]
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -87,6 +102,478 @@ pub struct StreamingRequest {
     pub ring_buffer: RingBuffer,
     /// Streaming configuration.
     pub config: StreamingConfig,
+    /// Channel count of the output device.
+    ///
+    /// Decoded audio is downmixed/upmixed to this layout before it is
+    /// written to the ring buffer. `None` keeps the source layout.
+    pub output_channels: Option<u16>,
+    /// Buffer sizes the output device supports.
+    ///
+    /// Used to negotiate `config.latency_target`; see
+    /// `PlaybackAdapter::buffer_size_range`. `None` treats the device as
+    /// unconstrained.
+    pub output_buffer_sizes: Option<BufferSizeRange>,
+    /// Silence detected at the start and end of the track.
+    ///
+    /// Skipped when `config.skip_silence` is enabled.
+    pub silence: Option<SilenceTrim>,
+}
+
+/// DSP applied to decoded chunks before they are buffered.
+///
+/// Remaps channels to the output layout, then either limits peaks (when a
+/// limiter is configured) or hard-clips to [-1.0, 1.0] as the final stage.
+struct OutputStage {
+    mapper: ChannelMapper,
+    limiter: Option<Limiter>,
+}
+
+impl OutputStage {
+    fn new(format: &AudioFormat, request: &StreamingRequest) -> Result<Self> {
+        let output_channels = request.output_channels.unwrap_or(format.channels);
+        let mapper = ChannelMapper::new(format.channels, output_channels)?;
+        if !mapper.is_passthrough() {
+            info!(
+                "Mapping {} source channels to {} output channels",
+                format.channels, output_channels
+            );
+        }
+
+        let limiter = request
+            .config
+            .limiter
+            .map(|config| Limiter::new(config, format.sample_rate, output_channels));
+        Ok(Self { mapper, limiter })
+    }
+
+    fn output_channels(&self) -> u16 {
+        self.mapper.output_channels()
+    }
+
+    fn process(&mut self, chunk: AudioFrameChunk) -> Result<AudioFrameChunk> {
+        let mut chunk = self.mapper.map(chunk)?;
+        match self.limiter.as_mut() {
+            Some(limiter) => chunk = limiter.process(&chunk),
+            None => chunk
+                .samples
+                .iter_mut()
+                .for_each(|sample| *sample = sample.clamp(-1.0, 1.0)),
+        }
+        Ok(chunk)
+    }
+
+    /// Frames still held by the limiter's lookahead at the end of a stream
+    fn drain(&mut self) -> Option<AudioFrameChunk> {
+        self.limiter
+            .as_mut()
+            .map(Limiter::flush)
+            .filter(|chunk| !chunk.is_empty())
+    }
+}
+
+/// Tracks the decode position to report the approaching end of a track once.
+///
+/// Position is measured at the decoder, which runs ahead of playback by the
+/// buffered audio. When silence is trimmed, the track ends early and
+/// anything decoded past the new end is dropped.
+struct EndWatch {
+    lead: Duration,
+    duration: Option<Duration>,
+    sample_rate: u32,
+    decoded_frames: u64,
+    fired: bool,
+    clip: bool,
+}
+
+impl EndWatch {
+    fn new(lead: Duration, duration: Option<Duration>, sample_rate: u32) -> Self {
+        Self {
+            lead,
+            duration,
+            sample_rate,
+            decoded_frames: 0,
+            fired: false,
+            clip: false,
+        }
+    }
+
+    /// Start at the end of the leading silence and stop before the trailing
+    fn trim(&mut self, trim: SilenceTrim) {
+        self.decoded_frames = (trim.leading.as_secs_f64() * self.sample_rate as f64).round() as u64;
+        self.duration = self
+            .duration
+            .map(|duration| duration.saturating_sub(trim.trailing));
+        self.clip = true;
+    }
+
+    /// Count a decoded chunk, cutting it off at the trimmed end of the track
+    fn take(&mut self, mut chunk: AudioFrameChunk) -> Option<AudioFrameChunk> {
+        if let (true, Some(end)) = (self.clip, self.duration) {
+            let end_frame = (end.as_secs_f64() * self.sample_rate as f64).round() as u64;
+            let remaining = end_frame.saturating_sub(self.decoded_frames) as usize;
+            if remaining == 0 {
+                return None;
+            }
+            if chunk.frames > remaining {
+                let channels = chunk.samples.len() / chunk.frames;
+                chunk.samples.truncate(remaining * channels);
+                chunk.frames = remaining;
+            }
+        }
+        self.decoded_frames += chunk.frames as u64;
+        Some(chunk)
+    }
+
+    /// Source position of the next frame to decode
+    fn position(&self) -> Duration {
+        if self.sample_rate == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_secs_f64(self.decoded_frames as f64 / self.sample_rate as f64)
+    }
+
+    /// Time left in the track, the first time it falls within the lead time
+    fn poll(&mut self) -> Option<Duration> {
+        if self.fired || self.sample_rate == 0 {
+            return None;
+        }
+        let remaining = self.duration?.saturating_sub(self.position());
+        if remaining > self.lead {
+            return None;
+        }
+        self.fired = true;
+        Some(remaining)
+    }
+
+    /// Continue counting from `position` after a seek
+    fn seek(&mut self, position: Duration) {
+        self.decoded_frames = (position.as_secs_f64() * self.sample_rate as f64).round() as u64;
+        // Seeking back out of the lead window re-arms the notification
+        if self
+            .duration
+            .is_some_and(|duration| duration.saturating_sub(position) > self.lead)
+        {
+            self.fired = false;
+        }
+    }
+
+    /// At end of stream, reports zero time left if the duration was unknown
+    fn finish(&mut self) -> Option<Duration> {
+        if self.fired {
+            return None;
+        }
+        self.fired = true;
+        Some(Duration::ZERO)
+    }
+}
+
+/// Retry budget for transient source failures.
+///
+/// Attempts are counted per failure streak; a successfully decoded chunk
+/// restores the full budget.
+struct Recovery {
+    policy: RetryPolicy,
+    backoff: Backoff,
+    attempts: u32,
+    /// State to return to once decoding succeeds again
+    resume_state: Option<StreamingState>,
+}
+
+impl Recovery {
+    fn new(config: &StreamingConfig) -> Self {
+        let policy = RetryPolicy {
+            max_attempts: config.recovery_attempts,
+            base_delay: config.recovery_base_delay,
+            max_delay: config.recovery_base_delay.saturating_mul(8),
+            use_exponential_backoff: true,
+            jitter: JitterMode::Full,
+        };
+        Self {
+            backoff: policy.backoff_from_clock(&SystemClock),
+            policy,
+            attempts: 0,
+            resume_state: None,
+        }
+    }
+
+    /// Delay before the next attempt, or `None` once the budget is spent
+    fn next_delay(&mut self, current_state: StreamingState) -> Option<Duration> {
+        if self.attempts >= self.policy.max_attempts {
+            return None;
+        }
+        self.attempts += 1;
+        self.resume_state.get_or_insert(current_state);
+        Some(self.backoff.next_delay())
+    }
+
+    /// Record a successful decode, returning the state to resume if a
+    /// recovery just completed
+    fn succeeded(&mut self) -> Option<StreamingState> {
+        let resume_state = self.resume_state.take()?;
+        self.attempts = 0;
+        self.backoff = self.policy.backoff_from_clock(&SystemClock);
+        Some(resume_state)
+    }
+}
+
+/// How much audio the service may buffer ahead.
+///
+/// Each memory warning halves it, down to `min_buffer_frames`. The
+/// reduction carries over to later tracks.
+struct BufferBudget {
+    buffer_frames: usize,
+    floor: usize,
+    halvings: u32,
+}
+
+impl BufferBudget {
+    fn new(config: &StreamingConfig) -> Self {
+        Self {
+            buffer_frames: config.buffer_frames,
+            floor: config.min_buffer_frames,
+            halvings: 0,
+        }
+    }
+
+    /// Adopt a new track's config, keeping any reduction
+    fn configure(&mut self, config: &StreamingConfig) {
+        self.buffer_frames = config.buffer_frames;
+        self.floor = config.min_buffer_frames;
+    }
+
+    /// Current budget in frames
+    fn frames(&self) -> usize {
+        let halved = self.buffer_frames.checked_shr(self.halvings).unwrap_or(0);
+        halved.max(self.floor).min(self.buffer_frames)
+    }
+
+    /// Halve the budget, returning the new size if it shrank
+    fn shrink(&mut self) -> Option<usize> {
+        if self.frames() <= self.floor {
+            return None;
+        }
+        self.halvings += 1;
+        Some(self.frames())
+    }
+}
+
+/// Encoder delay and padding as time to skip at either end of a track, so
+/// consecutive tracks join without gaps
+fn encoder_trim(probe: &ProbeResult) -> SilenceTrim {
+    let sample_rate = probe.format.sample_rate;
+    if sample_rate == 0 {
+        return SilenceTrim::default();
+    }
+    let frames = |frames: u32| Duration::from_secs_f64(frames as f64 / sample_rate as f64);
+    SilenceTrim {
+        leading: frames(probe.encoder_delay_frames),
+        trailing: frames(probe.encoder_padding_frames),
+    }
+}
+
+/// Fraction of the prebuffer currently filled
+fn prebuffer_progress(buffer_level: usize, prebuffer_samples: usize) -> f32 {
+    if prebuffer_samples == 0 {
+        1.0
+    } else {
+        (buffer_level as f32 / prebuffer_samples as f32).min(1.0)
+    }
+}
+
+/// Holds back the end of a track so it can be mixed into the next one.
+///
+/// Processed chunks are held until more than the crossfade length follows
+/// them, then queued as ready to write. With no crossfade every chunk is
+/// ready at once.
+struct Crossfade {
+    frames: usize,
+    held: VecDeque<AudioFrameChunk>,
+    held_frames: usize,
+    ready: VecDeque<AudioFrameChunk>,
+}
+
+impl Crossfade {
+    fn new(duration: Option<Duration>, sample_rate: u32) -> Self {
+        let frames = duration.map_or(0, |duration| {
+            (duration.as_secs_f64() * sample_rate as f64).round() as usize
+        });
+        Self {
+            frames,
+            held: VecDeque::new(),
+            held_frames: 0,
+            ready: VecDeque::new(),
+        }
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.frames > 0
+    }
+
+    /// Hold `chunk`, releasing the chunks that can no longer be in the overlap
+    fn push(&mut self, chunk: AudioFrameChunk) {
+        self.held_frames += chunk.frames;
+        self.held.push_back(chunk);
+        while let Some(front) = self.held.front() {
+            if self.held_frames - front.frames < self.frames {
+                break;
+            }
+            self.held_frames -= front.frames;
+            self.ready.extend(self.held.pop_front());
+        }
+    }
+
+    /// Take the held end of the track, at most `frames` plus one chunk long
+    fn take_tail(&mut self) -> Vec<AudioFrameChunk> {
+        self.held_frames = 0;
+        self.held.drain(..).collect()
+    }
+
+    /// Drop everything not yet written, e.g. after a seek
+    fn clear(&mut self) {
+        self.held.clear();
+        self.held_frames = 0;
+        self.ready.clear();
+    }
+}
+
+/// Probe the next track and decode up to `frames` from its start, mapped to
+/// `channels` output channels.
+///
+/// # Errors
+///
+/// Fails if the next track can't be decoded or has a different sample rate.
+async fn read_head(
+    decoder: &mut dyn AudioDecoder,
+    sample_rate: u32,
+    channels: u16,
+    frames: usize,
+) -> Result<AudioFrameChunk> {
+    let format = decoder.probe().await?.format;
+    if format.sample_rate != sample_rate {
+        return Err(PlaybackError::InvalidFormat(format!(
+            "next track is {} Hz, current track is {} Hz",
+            format.sample_rate, sample_rate
+        )));
+    }
+
+    let mapper = ChannelMapper::new(format.channels, channels)?;
+    let mut head = AudioFrameChunk::new(Vec::new(), 0, Duration::ZERO);
+    while head.frames < frames {
+        let Some(chunk) = decoder.decode_frames(frames - head.frames).await? else {
+            break;
+        };
+        let chunk = mapper.map(chunk)?;
+        head.samples.extend_from_slice(&chunk.samples);
+        head.frames += chunk.frames;
+    }
+    Ok(head)
+}
+
+/// Mix `head` into the end of `tail` with equal-power fades.
+///
+/// The overlap is as long as the shorter of the two. Any head left over is
+/// appended in chunks of `chunk_frames`.
+fn mix_crossfade(
+    tail: Vec<AudioFrameChunk>,
+    head: AudioFrameChunk,
+    channels: usize,
+    sample_rate: u32,
+    chunk_frames: usize,
+) -> Vec<AudioFrameChunk> {
+    let tail_frames: usize = tail.iter().map(|chunk| chunk.frames).sum();
+    let overlap = tail_frames.min(head.frames);
+    let fade_start = tail_frames - overlap;
+
+    let mut mixed = Vec::with_capacity(tail.len() + 1);
+    let mut position = 0;
+    for mut chunk in tail {
+        for (index, frame) in chunk.samples.chunks_exact_mut(channels).enumerate() {
+            let Some(offset) = (position + index).checked_sub(fade_start) else {
+                continue;
+            };
+            let angle = (offset as f32 + 0.5) / overlap as f32 * std::f32::consts::FRAC_PI_2;
+            let incoming = &head.samples[offset * channels..(offset + 1) * channels];
+            for (sample, next) in frame.iter_mut().zip(incoming) {
+                *sample = (*sample * angle.cos() + next * angle.sin()).clamp(-1.0, 1.0);
+            }
+        }
+        position += chunk.frames;
+        mixed.push(chunk);
+    }
+
+    let rest = &head.samples[overlap * channels..];
+    for (index, samples) in rest.chunks(chunk_frames.max(1) * channels).enumerate() {
+        let frame = overlap + index * chunk_frames;
+        let timestamp = Duration::from_secs_f64(frame as f64 / sample_rate.max(1) as f64);
+        mixed.push(AudioFrameChunk::new(
+            samples.to_vec(),
+            samples.len() / channels,
+            timestamp,
+        ));
+    }
+    mixed
+}
+
+/// Discard audio buffered before a seek to `position`.
+///
+/// The limiter's lookahead and any audio held for a crossfade are dropped
+/// with it, and the end-of-track watch continues from the new position.
+fn flush_after_seek(
+    position: Duration,
+    ring_buffer: &RingBuffer,
+    output_stage: &mut OutputStage,
+    end_watch: &mut EndWatch,
+    crossfade: &mut Crossfade,
+) {
+    debug!("Flushing buffered audio after seek to {:?}", position);
+    ring_buffer.clear();
+    output_stage.drain();
+    crossfade.clear();
+    end_watch.seek(position);
+}
+
+/// Write the ready chunks that fit in the ring buffer, tapping each to the
+/// sinks, and return the frames written.
+async fn write_ready<S: AsRef<dyn AudioSink>>(
+    crossfade: &mut Crossfade,
+    ring_buffer: &RingBuffer,
+    sinks: &[S],
+) -> usize {
+    let mut frames = 0;
+    while crossfade
+        .ready
+        .front()
+        .is_some_and(|chunk| chunk.samples.len() <= ring_buffer.free_space())
+    {
+        let Some(chunk) = crossfade.ready.pop_front() else {
+            break;
+        };
+        ring_buffer.write(&chunk.samples);
+        write_sinks(sinks, &chunk).await;
+        frames += chunk.frames;
+    }
+    frames
+}
+
+/// Push a chunk to every sink; a failing sink is logged and skipped.
+async fn write_sinks<S: AsRef<dyn AudioSink>>(sinks: &[S], chunk: &AudioFrameChunk) {
+    if chunk.is_empty() {
+        return;
+    }
+    for sink in sinks {
+        if let Err(e) = sink.as_ref().write(chunk).await {
+            warn!("Audio sink write failed: {}", e);
+        }
+    }
+}
+
+/// Flush every sink at the end of a stream.
+async fn flush_sinks<S: AsRef<dyn AudioSink>>(sinks: &[S]) {
+    for sink in sinks {
+        if let Err(e) = sink.as_ref().flush().await {
+            warn!("Audio sink flush failed: {}", e);
+        }
+    }
 }
 
 // ============================================================================
@@ -96,7 +583,16 @@ pub struct StreamingRequest {
 #[cfg(not(target_arch = "wasm32"))]
 pub struct StreamingService {
     _http_client: Arc<dyn HttpClient>,
-    decoder: parking_lot::Mutex<Box<dyn AudioDecoder>>,
+    decoder: core_async::sync::Mutex<Box<dyn AudioDecoder>>,
+    sinks: Vec<Arc<dyn AudioSink>>,
+    approaching_end: Option<Box<dyn Fn(Duration) + Send + Sync>>,
+    buffer_resized: Option<Box<dyn Fn(usize) + Send + Sync>>,
+    buffer_budget: parking_lot::Mutex<BufferBudget>,
+    seeked: parking_lot::Mutex<Option<Duration>>,
+    next: parking_lot::Mutex<Option<StreamingRequest>>,
+    next_decoder: parking_lot::Mutex<Option<Box<dyn AudioDecoder>>>,
+    /// Position the next run starts from, past the head already crossfaded
+    handoff: parking_lot::Mutex<Option<Duration>>,
     state: parking_lot::Mutex<StreamingState>,
     stats: parking_lot::Mutex<StreamingStats>,
 }
@@ -112,12 +608,171 @@ impl StreamingService {
     pub fn new(http_client: Arc<dyn HttpClient>, decoder: Box<dyn AudioDecoder>) -> Self {
         Self {
             _http_client: http_client,
-            decoder: parking_lot::Mutex::new(decoder),
+            decoder: core_async::sync::Mutex::new(decoder),
+            sinks: Vec::new(),
+            approaching_end: None,
+            buffer_resized: None,
+            buffer_budget: parking_lot::Mutex::new(BufferBudget::new(&StreamingConfig::default())),
+            seeked: parking_lot::Mutex::new(None),
+            next: parking_lot::Mutex::new(None),
+            next_decoder: parking_lot::Mutex::new(None),
+            handoff: parking_lot::Mutex::new(None),
             state: parking_lot::Mutex::new(StreamingState::Idle),
             stats: parking_lot::Mutex::new(StreamingStats::default()),
         }
     }
 
+    /// Add a sink that receives every decoded chunk.
+    ///
+    /// Sinks are fed in the order they were added, after the chunk is
+    /// written to the ring buffer.
+    pub fn with_sink(mut self, sink: Arc<dyn AudioSink>) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    /// Register a callback fired once per track when the remaining audio
+    /// falls within `StreamingConfig::approaching_end_lead`.
+    ///
+    /// It receives the time left and is the host's cue to call `set_next`.
+    /// If the track's duration is unknown, it fires at end of stream with
+    /// zero time left.
+    pub fn on_approaching_end(
+        mut self,
+        callback: impl Fn(Duration) + Send + Sync + 'static,
+    ) -> Self {
+        self.approaching_end = Some(Box::new(callback));
+        self
+    }
+
+    /// Register a callback fired when a memory warning shrinks the buffer.
+    ///
+    /// It receives the new buffer size in frames, so the host can allocate a
+    /// smaller ring buffer for the next track.
+    pub fn on_buffer_resize(mut self, callback: impl Fn(usize) + Send + Sync + 'static) -> Self {
+        self.buffer_resized = Some(Box::new(callback));
+        self
+    }
+
+    /// React to an event from `LifecycleObserver::subscribe_system_events`.
+    ///
+    /// A memory warning halves how far ahead the service buffers, down to
+    /// `min_buffer_frames`; prefetching scales down with it. The ring buffer
+    /// keeps its allocation, but the service stops filling it past the
+    /// reduced size.
+    pub fn handle_system_event(&self, event: SystemEvent) {
+        match event {
+            SystemEvent::MemoryWarning => {
+                let resized = self.buffer_budget.lock().shrink();
+                self.notify_buffer_resized(resized);
+            }
+            SystemEvent::LowPowerMode(enabled) => {
+                debug!("Low-power mode {}", if enabled { "on" } else { "off" });
+            }
+        }
+    }
+
+    fn notify_buffer_resized(&self, resized: Option<usize>) {
+        let Some(frames) = resized else {
+            debug!("Memory warning with the buffer already at its minimum");
+            return;
+        };
+        warn!("Memory warning, reducing buffer to {} frames", frames);
+        if let Some(callback) = &self.buffer_resized {
+            callback(frames);
+        }
+    }
+
+    /// Seek the current track, returning the position actually reached.
+    ///
+    /// The decoder may snap or clamp the position; see `AudioDecoder::seek`.
+    /// Audio buffered from before the seek is flushed from the ring buffer
+    /// before the next chunk is written.
+    ///
+    /// # Errors
+    ///
+    /// Returns the decoder's error if it cannot seek; decoding then carries
+    /// on from the old position.
+    pub async fn seek_to(&self, position: Duration) -> Result<Duration> {
+        let mut decoder = self.decoder.lock().await;
+        let actual = decoder.seek(position).await?;
+        *self.seeked.lock() = Some(actual);
+        info!("Seeked to {:?} (requested {:?})", actual, position);
+        Ok(actual)
+    }
+
+    /// Queue the request to play after the current one.
+    ///
+    /// Replaces any previously queued request.
+    pub fn set_next(&self, request: StreamingRequest) {
+        *self.next.lock() = Some(request);
+    }
+
+    /// Take the queued next request, if any.
+    pub fn take_next(&self) -> Option<StreamingRequest> {
+        self.next.lock().take()
+    }
+
+    /// Queue the decoder for the next track, to crossfade into when
+    /// `StreamingConfig::crossfade_duration` is set.
+    ///
+    /// At the end of the current track the service switches to this decoder,
+    /// and the next `run` continues from where the crossfade left off.
+    /// Replaces any previously queued decoder.
+    pub fn set_next_decoder(&self, decoder: Box<dyn AudioDecoder>) {
+        *self.next_decoder.lock() = Some(decoder);
+    }
+
+    /// Mix the held end of the track into the head of the next decoder,
+    /// then switch to that decoder.
+    ///
+    /// Without a next decoder the tail is returned as is; if the next track
+    /// can't be decoded, it is rewound and joined with a hard cut.
+    async fn crossfade_into_next(
+        &self,
+        tail: Vec<AudioFrameChunk>,
+        crossfade_frames: usize,
+        sample_rate: u32,
+        channels: u16,
+        chunk_frames: usize,
+    ) -> Vec<AudioFrameChunk> {
+        let next = self.next_decoder.lock().take();
+        let Some(mut next) = next else {
+            return tail;
+        };
+
+        // A track shorter than the crossfade overlaps for its whole length
+        let tail_frames = tail.iter().map(|chunk| chunk.frames).sum::<usize>();
+        let overlap = crossfade_frames.min(tail_frames);
+        let audio = match read_head(next.as_mut(), sample_rate, channels, overlap).await {
+            Ok(head) => {
+                debug!("Crossfading {} frames into the next track", head.frames);
+                let position = Duration::from_secs_f64(head.frames as f64 / sample_rate as f64);
+                *self.handoff.lock() = Some(position);
+                mix_crossfade(tail, head, channels as usize, sample_rate, chunk_frames)
+            }
+            Err(e) => {
+                warn!("Next track failed to decode, skipping crossfade: {}", e);
+                if let Err(e) = next.seek(Duration::ZERO).await {
+                    warn!("Failed to rewind next track: {}", e);
+                }
+                tail
+            }
+        };
+        *self.decoder.lock().await = next;
+        audio
+    }
+
+    fn notify_approaching_end(&self, remaining: Duration) {
+        info!(
+            "Approaching end of track ({:.2}s left)",
+            remaining.as_secs_f64()
+        );
+        if let Some(callback) = &self.approaching_end {
+            callback(remaining);
+        }
+    }
+
     /// Get the current streaming state.
     pub fn state(&self) -> StreamingState {
         *self.state.lock()
@@ -162,22 +817,79 @@ impl StreamingService {
         info!("Starting streaming service");
         *self.state.lock() = StreamingState::Buffering;
         *self.stats.lock() = StreamingStats::default();
+        self.buffer_budget.lock().configure(&request.config);
 
         // Probe audio format
-        let format = {
-            let mut decoder = self.decoder.lock();
+        let (format, duration, encoder_trim) = {
+            let mut decoder = self.decoder.lock().await;
             let probe_result = decoder.probe().await?;
             debug!(
                 "Probed audio format: codec={:?}, sample_rate={}, channels={}",
                 probe_result.format.codec, probe_result.format.sample_rate, probe_result.format.channels
             );
-            probe_result.format
+            let encoder_trim = encoder_trim(&probe_result);
+            (probe_result.format, probe_result.duration, encoder_trim)
         };
 
-        // Calculate buffer requirements
-        let channels = format.channels;
+        // Calculate buffer requirements for the output layout
+        let mut output_stage = OutputStage::new(&format, &request)?;
+        let channels = output_stage.output_channels();
         let _sample_rate = format.sample_rate;
         let buffer_capacity_samples = request.config.buffer_samples(channels);
+        let mut end_watch = EndWatch::new(
+            request.config.approaching_end_lead,
+            duration,
+            format.sample_rate,
+        );
+
+        // Skip encoder delay and padding, and analyzed silence, at either
+        // end of the track
+        let silence = request
+            .silence
+            .filter(|_| request.config.skip_silence)
+            .unwrap_or_default();
+        let trim = SilenceTrim {
+            leading: silence.leading.max(encoder_trim.leading),
+            trailing: silence.trailing.max(encoder_trim.trailing),
+        };
+        let handoff = self.handoff.lock().take();
+        if !trim.is_empty() {
+            debug!(
+                "Trimming {:?} from the start and {:?} from the end of the track",
+                trim.leading, trim.trailing
+            );
+            if !trim.leading.is_zero() && handoff.is_none() {
+                self.decoder.lock().await.seek(trim.leading).await?;
+            }
+            end_watch.trim(trim);
+        }
+
+        // Pick up after the head already mixed into the previous track
+        if let Some(position) = handoff {
+            debug!("Continuing from {:?} after the crossfade", position);
+            end_watch.seek(position);
+        }
+        let mut crossfade = Crossfade::new(request.config.crossfade_duration, format.sample_rate);
+        let mut finished = false;
+
+        // Never wait for more than the ring buffer can hold
+        let mut recovery = Recovery::new(&request.config);
+        let chunk_samples = request.config.decode_chunk_frames * channels as usize;
+        let latency = request.config.output_latency(
+            format.sample_rate,
+            request.output_buffer_sizes.unwrap_or_default(),
+        );
+        if let Some(latency) = latency {
+            info!(
+                "Output latency {:?} (target {:?}, device buffer {} frames)",
+                latency.achieved, latency.target, latency.device_buffer_frames
+            );
+            self.stats.lock().output_latency = Some(latency);
+        }
+        let prebuffer_samples = request
+            .config
+            .prebuffer_samples(format.sample_rate, channels, latency.as_ref())
+            .min(request.ring_buffer.capacity().saturating_sub(chunk_samples));
 
         // Verify ring buffer capacity
         if request.ring_buffer.capacity() < buffer_capacity_samples {
@@ -196,38 +908,49 @@ impl StreamingService {
             // Check cancellation
             if cancel_token.is_cancelled() {
                 info!("Streaming cancelled");
+                flush_sinks(&self.sinks).await;
                 *self.state.lock() = StreamingState::Idle;
                 return Ok(());
             }
 
             // Check buffer level and decide action
             let buffer_level = request.ring_buffer.available();
-            let buffer_capacity = request.ring_buffer.capacity();
+            let budget = self.buffer_budget.lock().frames() * channels as usize;
+            let buffer_capacity = request.ring_buffer.capacity().min(budget);
+            let prebuffer_target =
+                prebuffer_samples.min(buffer_capacity.saturating_sub(chunk_samples));
             let fill_ratio = buffer_level as f32 / buffer_capacity as f32;
 
             // Update stats
             {
                 let mut stats = self.stats.lock();
                 stats.current_buffer_frames = buffer_level / channels as usize;
+                stats.dropped_samples = request.ring_buffer.dropped();
+                stats.prebuffer_progress = prebuffer_progress(buffer_level, prebuffer_target);
             }
 
             // State transitions
             let current_state = self.state();
             match current_state {
-                StreamingState::Buffering => {
-                    if buffer_level >= request.config.min_buffer_samples(channels) {
-                        info!("Initial buffering complete, transitioning to streaming");
-                        *self.state.lock() = StreamingState::Streaming;
-                    }
+                StreamingState::Buffering if buffer_level >= prebuffer_target => {
+                    info!("Initial buffering complete, transitioning to streaming");
+                    *self.state.lock() = StreamingState::Streaming;
                 }
-                StreamingState::Streaming => {
-                    // Check for underrun
-                    if fill_ratio < request.config.prefetch_threshold {
-                        debug!(
-                            "Buffer level low ({:.1}%), prefetching aggressively",
-                            fill_ratio * 100.0
-                        );
-                    }
+                StreamingState::Buffering
+                    if start_time.elapsed() >= request.config.prebuffer_timeout =>
+                {
+                    warn!(
+                        "Prebuffering timed out with {} of {} samples, starting playback",
+                        buffer_level, prebuffer_target
+                    );
+                    *self.state.lock() = StreamingState::Streaming;
+                }
+                // Check for underrun
+                StreamingState::Streaming if fill_ratio < request.config.prefetch_threshold => {
+                    debug!(
+                        "Buffer level low ({:.1}%), prefetching aggressively",
+                        fill_ratio * 100.0
+                    );
                 }
                 StreamingState::Completed | StreamingState::Error => {
                     break;
@@ -236,24 +959,58 @@ impl StreamingService {
             }
 
             // Decode next chunk if buffer has space
-            if request.ring_buffer.free_space() >= request.config.decode_chunk_frames * channels as usize {
+            if !finished
+                && crossfade.ready.is_empty()
+                && buffer_capacity.saturating_sub(buffer_level) >= chunk_samples
+            {
                 let decode_start = Instant::now();
 
                 let chunk_result = {
-                    let mut decoder = self.decoder.lock();
+                    let mut decoder = self.decoder.lock().await;
+                    // Drop audio from before a seek made since the last chunk
+                    let seeked = self.seeked.lock().take();
+                    if let Some(position) = seeked {
+                        flush_after_seek(
+                            position,
+                            &request.ring_buffer,
+                            &mut output_stage,
+                            &mut end_watch,
+                            &mut crossfade,
+                        );
+                    }
                     decoder.decode_frames(request.config.decode_chunk_frames).await
                 };
 
+                // Remap and limit before buffering
+                let chunk_result = chunk_result.and_then(|chunk| {
+                    chunk
+                        .and_then(|c| end_watch.take(c))
+                        .map(|c| output_stage.process(c))
+                        .transpose()
+                });
+                if let Some(remaining) = end_watch.poll() {
+                    self.notify_approaching_end(remaining);
+                }
+
                 match chunk_result {
                     Ok(Some(chunk)) => {
+                        if let Some(state) = recovery.succeeded() {
+                            info!("Source recovered, resuming");
+                            *self.state.lock() = state;
+                        }
+
                         let decode_elapsed = decode_start.elapsed();
                         decode_times.push(decode_elapsed.as_secs_f64() * 1000.0);
 
-                        // Write to ring buffer
-                        let written = request.ring_buffer.write(&chunk.samples);
+                        // Write to ring buffer, then tap the chunk to the sinks;
+                        // the end of the track is held back for a crossfade
+                        let frames = chunk.frames;
+                        crossfade.push(chunk);
+                        let written =
+                            write_ready(&mut crossfade, &request.ring_buffer, &self.sinks).await;
                         debug!(
-                            "Decoded {} frames, wrote {} samples to buffer (fill: {:.1}%)",
-                            chunk.frames,
+                            "Decoded {} frames, wrote {} frames to buffer (fill: {:.1}%)",
+                            frames,
                             written,
                             fill_ratio * 100.0
                         );
@@ -261,23 +1018,92 @@ impl StreamingService {
                         // Update stats
                         {
                             let mut stats = self.stats.lock();
-                            stats.total_frames_buffered += chunk.frames;
+                            stats.total_frames_buffered += written;
                             stats.avg_decode_time_ms = decode_times.iter().sum::<f64>() / decode_times.len() as f64;
                         }
                     }
                     Ok(None) => {
-                        // End of stream
+                        // End of stream; what is left is written as room frees up
                         info!("End of stream reached");
-                        *self.state.lock() = StreamingState::Completed;
-                        break;
+                        if let Some(remaining) = end_watch.finish() {
+                            self.notify_approaching_end(remaining);
+                        }
+                        if let Some(tail) = output_stage.drain() {
+                            crossfade.push(tail);
+                        }
+                        if crossfade.is_enabled() {
+                            let tail = crossfade.take_tail();
+                            let audio = self
+                                .crossfade_into_next(
+                                    tail,
+                                    crossfade.frames,
+                                    format.sample_rate,
+                                    channels,
+                                    request.config.decode_chunk_frames,
+                                )
+                                .await;
+                            crossfade.ready.extend(audio);
+                        }
+                        finished = true;
+                        continue;
                     }
                     Err(e) => {
+                        if let Some(delay) = e
+                            .is_transient()
+                            .then(|| recovery.next_delay(current_state))
+                            .flatten()
+                        {
+                            warn!(
+                                "Transient source error, retrying in {:?} ({}/{}): {}",
+                                delay, recovery.attempts, recovery.policy.max_attempts, e
+                            );
+                            *self.state.lock() = StreamingState::Recovering;
+                            sleep(delay).await;
+
+                            // Re-open the source from where decoding stopped
+                            let mut decoder = self.decoder.lock().await;
+                            match decoder.seek(end_watch.position()).await {
+                                Err(e) if !e.is_transient() => {
+                                    error!("Recovery failed: {}", e);
+                                    *self.state.lock() = StreamingState::Error;
+                                    return Err(e);
+                                }
+                                Err(e) => warn!("Recovery seek failed: {}", e),
+                                Ok(_) => {}
+                            }
+                            continue;
+                        }
+
                         error!("Decoding error: {}", e);
                         *self.state.lock() = StreamingState::Error;
                         return Err(e);
                     }
                 }
             } else {
+                // Buffer is full, but a seek still has to empty it
+                let seeked = self.seeked.lock().take();
+                if let Some(position) = seeked {
+                    flush_after_seek(
+                        position,
+                        &request.ring_buffer,
+                        &mut output_stage,
+                        &mut end_watch,
+                        &mut crossfade,
+                    );
+                    continue;
+                }
+
+                // Write what was held back, and finish once it is all out
+                if !crossfade.ready.is_empty() {
+                    let written =
+                        write_ready(&mut crossfade, &request.ring_buffer, &self.sinks).await;
+                    self.stats.lock().total_frames_buffered += written;
+                } else if finished {
+                    flush_sinks(&self.sinks).await;
+                    *self.state.lock() = StreamingState::Completed;
+                    break;
+                }
+
                 // Buffer is full, wait a bit
                 sleep(Duration::from_millis(10)).await;
             }
@@ -334,6 +1160,14 @@ use std::cell::RefCell;
 pub struct StreamingService {
     _http_client: Rc<dyn HttpClient>,
     decoder: RefCell<Box<dyn AudioDecoder>>,
+    sinks: Vec<Rc<dyn AudioSink>>,
+    approaching_end: Option<Box<dyn Fn(Duration)>>,
+    buffer_resized: Option<Box<dyn Fn(usize)>>,
+    buffer_budget: RefCell<BufferBudget>,
+    seeked: RefCell<Option<Duration>>,
+    next: RefCell<Option<StreamingRequest>>,
+    next_decoder: RefCell<Option<Box<dyn AudioDecoder>>>,
+    handoff: RefCell<Option<Duration>>,
     state: RefCell<StreamingState>,
     stats: RefCell<StreamingStats>,
 }
@@ -345,11 +1179,133 @@ impl StreamingService {
         Self {
             _http_client: http_client,
             decoder: RefCell::new(decoder),
+            sinks: Vec::new(),
+            approaching_end: None,
+            buffer_resized: None,
+            buffer_budget: RefCell::new(BufferBudget::new(&StreamingConfig::default())),
+            seeked: RefCell::new(None),
+            next: RefCell::new(None),
+            next_decoder: RefCell::new(None),
+            handoff: RefCell::new(None),
             state: RefCell::new(StreamingState::Idle),
             stats: RefCell::new(StreamingStats::default()),
         }
     }
 
+    /// Add a sink that receives every decoded chunk.
+    pub fn with_sink(mut self, sink: Rc<dyn AudioSink>) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    /// Register a callback fired once per track as it nears its end.
+    pub fn on_approaching_end(mut self, callback: impl Fn(Duration) + 'static) -> Self {
+        self.approaching_end = Some(Box::new(callback));
+        self
+    }
+
+    /// Register a callback fired when a memory warning shrinks the buffer.
+    pub fn on_buffer_resize(mut self, callback: impl Fn(usize) + 'static) -> Self {
+        self.buffer_resized = Some(Box::new(callback));
+        self
+    }
+
+    /// React to an event from `LifecycleObserver::subscribe_system_events`.
+    pub fn handle_system_event(&self, event: SystemEvent) {
+        match event {
+            SystemEvent::MemoryWarning => {
+                let resized = self.buffer_budget.borrow_mut().shrink();
+                self.notify_buffer_resized(resized);
+            }
+            SystemEvent::LowPowerMode(enabled) => {
+                debug!("Low-power mode {}", if enabled { "on" } else { "off" });
+            }
+        }
+    }
+
+    fn notify_buffer_resized(&self, resized: Option<usize>) {
+        let Some(frames) = resized else {
+            debug!("Memory warning with the buffer already at its minimum");
+            return;
+        };
+        warn!("Memory warning, reducing buffer to {} frames", frames);
+        if let Some(callback) = &self.buffer_resized {
+            callback(frames);
+        }
+    }
+
+    /// Seek the current track, returning the position actually reached.
+    ///
+    /// Audio buffered from before the seek is flushed from the ring buffer.
+    pub async fn seek_to(&self, position: Duration) -> Result<Duration> {
+        let actual = self.decoder.borrow_mut().seek(position).await?;
+        *self.seeked.borrow_mut() = Some(actual);
+        info!("Seeked to {:?} (requested {:?})", actual, position);
+        Ok(actual)
+    }
+
+    /// Queue the request to play after the current one.
+    pub fn set_next(&self, request: StreamingRequest) {
+        *self.next.borrow_mut() = Some(request);
+    }
+
+    /// Take the queued next request, if any.
+    pub fn take_next(&self) -> Option<StreamingRequest> {
+        self.next.borrow_mut().take()
+    }
+
+    /// Queue the decoder for the next track, to crossfade into.
+    pub fn set_next_decoder(&self, decoder: Box<dyn AudioDecoder>) {
+        *self.next_decoder.borrow_mut() = Some(decoder);
+    }
+
+    /// Mix the held end of the track into the head of the next decoder,
+    /// then switch to that decoder.
+    async fn crossfade_into_next(
+        &self,
+        tail: Vec<AudioFrameChunk>,
+        crossfade_frames: usize,
+        sample_rate: u32,
+        channels: u16,
+        chunk_frames: usize,
+    ) -> Vec<AudioFrameChunk> {
+        let next = self.next_decoder.borrow_mut().take();
+        let Some(mut next) = next else {
+            return tail;
+        };
+
+        // A track shorter than the crossfade overlaps for its whole length
+        let tail_frames = tail.iter().map(|chunk| chunk.frames).sum::<usize>();
+        let overlap = crossfade_frames.min(tail_frames);
+        let audio = match read_head(next.as_mut(), sample_rate, channels, overlap).await {
+            Ok(head) => {
+                debug!("Crossfading {} frames into the next track", head.frames);
+                let position = Duration::from_secs_f64(head.frames as f64 / sample_rate as f64);
+                *self.handoff.borrow_mut() = Some(position);
+                mix_crossfade(tail, head, channels as usize, sample_rate, chunk_frames)
+            }
+            Err(e) => {
+                warn!("Next track failed to decode, skipping crossfade: {}", e);
+                if let Err(e) = next.seek(Duration::ZERO).await {
+                    warn!("Failed to rewind next track: {}", e);
+                }
+                tail
+            }
+        };
+        *self.decoder.borrow_mut() = next;
+        audio
+    }
+
+    fn notify_approaching_end(&self, remaining: Duration) {
+        info!(
+            "Approaching end of track ({:.2}s left)",
+            remaining.as_secs_f64()
+        );
+        if let Some(callback) = &self.approaching_end {
+            callback(remaining);
+        }
+    }
+
     /// Get the current streaming state.
     pub fn state(&self) -> StreamingState {
         *self.state.borrow()
@@ -375,21 +1331,78 @@ impl StreamingService {
         info!("Starting streaming service (WASM)");
         *self.state.borrow_mut() = StreamingState::Buffering;
         *self.stats.borrow_mut() = StreamingStats::default();
+        self.buffer_budget.borrow_mut().configure(&request.config);
 
         // Probe audio format
-        let format = {
+        let (format, duration, encoder_trim) = {
             let mut decoder = self.decoder.borrow_mut();
             let probe_result = decoder.probe().await?;
             debug!(
                 "Probed audio format: codec={:?}, sample_rate={}, channels={}",
                 probe_result.format.codec, probe_result.format.sample_rate, probe_result.format.channels
             );
-            probe_result.format
+            let encoder_trim = encoder_trim(&probe_result);
+            (probe_result.format, probe_result.duration, encoder_trim)
         };
 
-        let channels = format.channels;
+        let mut output_stage = OutputStage::new(&format, &request)?;
+        let channels = output_stage.output_channels();
         let _sample_rate = format.sample_rate;
         let buffer_capacity_samples = request.config.buffer_samples(channels);
+        let mut end_watch = EndWatch::new(
+            request.config.approaching_end_lead,
+            duration,
+            format.sample_rate,
+        );
+
+        // Skip encoder delay and padding, and analyzed silence, at either
+        // end of the track
+        let silence = request
+            .silence
+            .filter(|_| request.config.skip_silence)
+            .unwrap_or_default();
+        let trim = SilenceTrim {
+            leading: silence.leading.max(encoder_trim.leading),
+            trailing: silence.trailing.max(encoder_trim.trailing),
+        };
+        let handoff = self.handoff.borrow_mut().take();
+        if !trim.is_empty() {
+            debug!(
+                "Trimming {:?} from the start and {:?} from the end of the track",
+                trim.leading, trim.trailing
+            );
+            if !trim.leading.is_zero() && handoff.is_none() {
+                self.decoder.borrow_mut().seek(trim.leading).await?;
+            }
+            end_watch.trim(trim);
+        }
+
+        // Pick up after the head already mixed into the previous track
+        if let Some(position) = handoff {
+            debug!("Continuing from {:?} after the crossfade", position);
+            end_watch.seek(position);
+        }
+        let mut crossfade = Crossfade::new(request.config.crossfade_duration, format.sample_rate);
+        let mut finished = false;
+
+        // Never wait for more than the ring buffer can hold
+        let mut recovery = Recovery::new(&request.config);
+        let chunk_samples = request.config.decode_chunk_frames * channels as usize;
+        let latency = request.config.output_latency(
+            format.sample_rate,
+            request.output_buffer_sizes.unwrap_or_default(),
+        );
+        if let Some(latency) = latency {
+            info!(
+                "Output latency {:?} (target {:?}, device buffer {} frames)",
+                latency.achieved, latency.target, latency.device_buffer_frames
+            );
+            self.stats.borrow_mut().output_latency = Some(latency);
+        }
+        let prebuffer_samples = request
+            .config
+            .prebuffer_samples(format.sample_rate, channels, latency.as_ref())
+            .min(request.ring_buffer.capacity().saturating_sub(chunk_samples));
 
         if request.ring_buffer.capacity() < buffer_capacity_samples {
             warn!(
@@ -406,27 +1419,39 @@ impl StreamingService {
         loop {
             if cancel_token.is_cancelled() {
                 info!("Streaming cancelled");
+                flush_sinks(&self.sinks).await;
                 *self.state.borrow_mut() = StreamingState::Idle;
                 return Ok(());
             }
 
             let buffer_level = request.ring_buffer.available();
-            let buffer_capacity = request.ring_buffer.capacity();
+            let budget = self.buffer_budget.borrow().frames() * channels as usize;
+            let buffer_capacity = request.ring_buffer.capacity().min(budget);
+            let prebuffer_target =
+                prebuffer_samples.min(buffer_capacity.saturating_sub(chunk_samples));
             let fill_ratio = buffer_level as f32 / buffer_capacity as f32;
 
             // Update stats
             {
                 let mut stats = self.stats.borrow_mut();
                 stats.current_buffer_frames = buffer_level / channels as usize;
+                stats.dropped_samples = request.ring_buffer.dropped();
+                stats.prebuffer_progress = prebuffer_progress(buffer_level, prebuffer_target);
             }
 
             // State transitions
             let current_state = self.state();
             match current_state {
                 StreamingState::Buffering => {
-                    if buffer_level >= request.config.min_buffer_samples(channels) {
+                    if buffer_level >= prebuffer_target {
                         info!("Initial buffering complete, transitioning to streaming");
                         *self.state.borrow_mut() = StreamingState::Streaming;
+                    } else if start_time.elapsed() >= request.config.prebuffer_timeout {
+                        warn!(
+                            "Prebuffering timed out with {} of {} samples, starting playback",
+                            buffer_level, prebuffer_target
+                        );
+                        *self.state.borrow_mut() = StreamingState::Streaming;
                     }
                 }
                 StreamingState::Streaming => {
@@ -444,45 +1469,143 @@ impl StreamingService {
             }
 
             // Decode next chunk
-            if request.ring_buffer.free_space() >= request.config.decode_chunk_frames * channels as usize {
+            if !finished
+                && crossfade.ready.is_empty()
+                && buffer_capacity.saturating_sub(buffer_level) >= chunk_samples
+            {
                 let decode_start = Instant::now();
 
                 let chunk_result = {
                     let mut decoder = self.decoder.borrow_mut();
+                    // Drop audio from before a seek made since the last chunk
+                    let seeked = self.seeked.borrow_mut().take();
+                    if let Some(position) = seeked {
+                        flush_after_seek(
+                            position,
+                            &request.ring_buffer,
+                            &mut output_stage,
+                            &mut end_watch,
+                            &mut crossfade,
+                        );
+                    }
                     decoder.decode_frames(request.config.decode_chunk_frames).await
                 };
 
+                // Remap and limit before buffering
+                let chunk_result = chunk_result.and_then(|chunk| {
+                    chunk
+                        .and_then(|c| end_watch.take(c))
+                        .map(|c| output_stage.process(c))
+                        .transpose()
+                });
+                if let Some(remaining) = end_watch.poll() {
+                    self.notify_approaching_end(remaining);
+                }
+
                 match chunk_result {
                     Ok(Some(chunk)) => {
+                        if let Some(state) = recovery.succeeded() {
+                            info!("Source recovered, resuming");
+                            *self.state.borrow_mut() = state;
+                        }
+
                         let decode_elapsed = decode_start.elapsed();
                         decode_times.push(decode_elapsed.as_secs_f64() * 1000.0);
 
-                        let written = request.ring_buffer.write(&chunk.samples);
+                        let frames = chunk.frames;
+                        crossfade.push(chunk);
+                        let written =
+                            write_ready(&mut crossfade, &request.ring_buffer, &self.sinks).await;
                         debug!(
-                            "Decoded {} frames, wrote {} samples to buffer (fill: {:.1}%)",
-                            chunk.frames,
+                            "Decoded {} frames, wrote {} frames to buffer (fill: {:.1}%)",
+                            frames,
                             written,
                             fill_ratio * 100.0
                         );
 
                         {
                             let mut stats = self.stats.borrow_mut();
-                            stats.total_frames_buffered += chunk.frames;
+                            stats.total_frames_buffered += written;
                             stats.avg_decode_time_ms = decode_times.iter().sum::<f64>() / decode_times.len() as f64;
                         }
                     }
                     Ok(None) => {
                         info!("End of stream reached");
-                        *self.state.borrow_mut() = StreamingState::Completed;
-                        break;
+                        if let Some(remaining) = end_watch.finish() {
+                            self.notify_approaching_end(remaining);
+                        }
+                        if let Some(tail) = output_stage.drain() {
+                            crossfade.push(tail);
+                        }
+                        if crossfade.is_enabled() {
+                            let tail = crossfade.take_tail();
+                            let audio = self
+                                .crossfade_into_next(
+                                    tail,
+                                    crossfade.frames,
+                                    format.sample_rate,
+                                    channels,
+                                    request.config.decode_chunk_frames,
+                                )
+                                .await;
+                            crossfade.ready.extend(audio);
+                        }
+                        finished = true;
+                        continue;
                     }
                     Err(e) => {
+                        if let Some(delay) = e
+                            .is_transient()
+                            .then(|| recovery.next_delay(current_state))
+                            .flatten()
+                        {
+                            warn!(
+                                "Transient source error, retrying in {:?} ({}/{}): {}",
+                                delay, recovery.attempts, recovery.policy.max_attempts, e
+                            );
+                            *self.state.borrow_mut() = StreamingState::Recovering;
+                            sleep(delay).await;
+
+                            // Re-open the source from where decoding stopped
+                            let mut decoder = self.decoder.borrow_mut();
+                            match decoder.seek(end_watch.position()).await {
+                                Err(e) if !e.is_transient() => {
+                                    error!("Recovery failed: {}", e);
+                                    *self.state.borrow_mut() = StreamingState::Error;
+                                    return Err(e);
+                                }
+                                Err(e) => warn!("Recovery seek failed: {}", e),
+                                Ok(_) => {}
+                            }
+                            continue;
+                        }
+
                         error!("Decoding error: {}", e);
                         *self.state.borrow_mut() = StreamingState::Error;
                         return Err(e);
                     }
                 }
             } else {
+                let seeked = self.seeked.borrow_mut().take();
+                if let Some(position) = seeked {
+                    flush_after_seek(
+                        position,
+                        &request.ring_buffer,
+                        &mut output_stage,
+                        &mut end_watch,
+                        &mut crossfade,
+                    );
+                    continue;
+                }
+                if !crossfade.ready.is_empty() {
+                    let written =
+                        write_ready(&mut crossfade, &request.ring_buffer, &self.sinks).await;
+                    self.stats.borrow_mut().total_frames_buffered += written;
+                } else if finished {
+                    flush_sinks(&self.sinks).await;
+                    *self.state.borrow_mut() = StreamingState::Completed;
+                    break;
+                }
                 sleep(Duration::from_millis(10)).await;
             }
 
@@ -540,6 +1663,9 @@ mod tests {
             },
             ring_buffer,
             config,
+            output_channels: None,
+            output_buffer_sizes: None,
+            silence: None,
         };
 
         // Verify configuration
@@ -17,8 +17,8 @@
 //!
 //! ## Threading Model
 //!
-//! - On **native** platforms: `AudioDecoder` and `PlaybackAdapter` must be `Send + Sync`
-//!   to support multi-threaded operation.
+//! - On **native** platforms: `AudioDecoder`, `PlaybackAdapter` and `AudioSink` must be
+//!   `Send + Sync` to support multi-threaded operation.
 //! - On **WASM**: Single-threaded execution, traits use `?Send` from `async_trait`.
 //!
 //! ## Usage Example
@@ -45,6 +45,7 @@
 
 use crate::error::Result;
 use async_trait::async_trait;
+use bridge_traits::platform::PlatformSendSync;
 use bytes::Bytes;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -270,6 +271,26 @@ impl AudioFrameChunk {
     }
 }
 
+/// Details of one audio stream in a container.
+///
+/// Multi-stream files (e.g., Matroska with several language tracks) list one
+/// entry per audio stream, in container order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AudioStreamInfo {
+    /// Position among the container's audio streams (0-based)
+    pub index: usize,
+    /// Container-specific track identifier
+    pub track_id: u32,
+    /// Audio codec of the stream
+    pub codec: AudioCodec,
+    /// Number of channels, if declared by the container
+    pub channels: Option<u16>,
+    /// Sample rate in Hz, if declared by the container
+    pub sample_rate: Option<u32>,
+    /// Language tag (e.g., "eng"), if declared by the container
+    pub language: Option<String>,
+}
+
 /// Result of probing an audio stream.
 ///
 /// Contains format metadata and optional tags extracted from the audio container.
@@ -281,6 +302,19 @@ pub struct ProbeResult {
     pub duration: Option<Duration>,
     /// Metadata tags (e.g., title, artist, album)
     pub tags: HashMap<String, String>,
+    /// All audio streams in the container
+    pub streams: Vec<AudioStreamInfo>,
+    /// Index into `streams` of the stream being decoded
+    pub selected_stream: usize,
+    /// Priming frames the encoder added before the audio (e.g., from a LAME
+    /// or iTunSMPB tag).
+    ///
+    /// Zero if the format has no such tag, or the decoder already trims it.
+    pub encoder_delay_frames: u32,
+    /// Frames the encoder appended after the audio to fill the last packet.
+    ///
+    /// Zero if the format has no such tag, or the decoder already trims it.
+    pub encoder_padding_frames: u32,
 }
 
 impl ProbeResult {
@@ -290,6 +324,10 @@ impl ProbeResult {
             format,
             duration: None,
             tags: HashMap::new(),
+            streams: Vec::new(),
+            selected_stream: 0,
+            encoder_delay_frames: 0,
+            encoder_padding_frames: 0,
         }
     }
 
@@ -304,6 +342,25 @@ impl ProbeResult {
         self.tags = tags;
         self
     }
+
+    /// Set the container's audio streams and which one is being decoded.
+    pub fn with_streams(mut self, streams: Vec<AudioStreamInfo>, selected_stream: usize) -> Self {
+        self.streams = streams;
+        self.selected_stream = selected_stream;
+        self
+    }
+
+    /// Set the encoder delay and padding to trim for gapless playback.
+    pub fn with_encoder_trim(mut self, delay_frames: u32, padding_frames: u32) -> Self {
+        self.encoder_delay_frames = delay_frames;
+        self.encoder_padding_frames = padding_frames;
+        self
+    }
+
+    /// Details of the stream being decoded, if the container listed any.
+    pub fn selected(&self) -> Option<&AudioStreamInfo> {
+        self.streams.get(self.selected_stream)
+    }
 }
 
 // ============================================================================
@@ -338,7 +395,7 @@ impl ProbeResult {
 /// # impl AudioDecoder for MyDecoder {
 /// #     async fn probe(&mut self) -> core_playback::Result<ProbeResult> { unimplemented!() }
 /// #     async fn decode_frames(&mut self, max_frames: usize) -> core_playback::Result<Option<core_playback::AudioFrameChunk>> { unimplemented!() }
-/// #     async fn seek(&mut self, position: Duration) -> core_playback::Result<()> { unimplemented!() }
+/// #     async fn seek(&mut self, position: Duration) -> core_playback::Result<Duration> { unimplemented!() }
 /// # }
 /// async fn decode_audio(mut decoder: impl AudioDecoder) {
 ///     let probe = decoder.probe().await.unwrap();
@@ -397,19 +454,61 @@ pub trait AudioDecoder {
     /// Seek to an absolute position in the audio stream.
     ///
     /// After seeking, the next call to `decode_frames()` will return audio
-    /// from the requested position.
+    /// from the returned position.
     ///
     /// # Arguments
     ///
     /// * `position` - Absolute timestamp to seek to
     ///
+    /// # Returns
+    ///
+    /// The position actually reached. It may be snapped to a frame or packet
+    /// boundary, or clamped to the track duration when `position` lies past
+    /// the end.
+    ///
     /// # Errors
     ///
     /// Returns an error if:
     /// - Seeking is not supported by this format
     /// - The requested position is out of bounds
     /// - A codec error occurs during seek
-    async fn seek(&mut self, position: Duration) -> Result<()>;
+    async fn seek(&mut self, position: Duration) -> Result<Duration>;
+}
+
+/// Output buffer sizes an audio device supports, in frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BufferSizeRange {
+    /// Smallest supported buffer
+    pub min_frames: u32,
+    /// Largest supported buffer
+    pub max_frames: u32,
+}
+
+impl BufferSizeRange {
+    /// A device that accepts any buffer size.
+    pub const UNCONSTRAINED: Self = Self {
+        min_frames: 1,
+        max_frames: u32::MAX,
+    };
+
+    /// Create a range of supported buffer sizes.
+    pub fn new(min_frames: u32, max_frames: u32) -> Self {
+        Self {
+            min_frames: min_frames.max(1),
+            max_frames: max_frames.max(min_frames.max(1)),
+        }
+    }
+
+    /// The supported buffer size closest to `frames`.
+    pub fn closest(&self, frames: u32) -> u32 {
+        frames.clamp(self.min_frames, self.max_frames)
+    }
+}
+
+impl Default for BufferSizeRange {
+    fn default() -> Self {
+        Self::UNCONSTRAINED
+    }
 }
 
 /// Trait for platform-specific playback adapters.
@@ -532,6 +631,45 @@ pub trait PlaybackAdapter {
     ///
     /// Returns `true` if audio is currently playing (not paused or stopped).
     async fn is_playing(&self) -> Result<bool>;
+
+    /// Output buffer sizes the audio device supports.
+    ///
+    /// Pass this as `StreamingRequest::output_buffer_sizes` so a
+    /// `latency_target` is negotiated against what the device can do.
+    /// Defaults to unconstrained.
+    fn buffer_size_range(&self) -> BufferSizeRange {
+        BufferSizeRange::UNCONSTRAINED
+    }
+}
+
+/// Trait for consumers of the final PCM produced by the streaming pipeline.
+///
+/// `StreamingService` pushes every decoded chunk (after channel mapping) to
+/// each registered sink in addition to the ring buffer. This lets hosts tap
+/// the audio that is playing for level meters, visualizers, "record what's
+/// playing", or to capture the pipeline output in tests.
+///
+/// ## Threading Model
+///
+/// - **Native**: Must be `Send + Sync`; sinks are shared with the service
+/// - **WASM**: Single-threaded, uses `?Send` from async_trait
+///
+/// ## Implementation Notes
+///
+/// - `write()` is called on the producer task, so it should return quickly
+/// - A failing sink is logged and skipped; it never stops playback
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait AudioSink: PlatformSendSync {
+    /// Receive the next chunk of decoded PCM.
+    ///
+    /// Chunks arrive in playback order, in the service's output layout.
+    async fn write(&self, chunk: &AudioFrameChunk) -> Result<()>;
+
+    /// Flush any buffered audio.
+    ///
+    /// Called when the stream ends or is cancelled.
+    async fn flush(&self) -> Result<()>;
 }
 
 // ============================================================================
@@ -6,7 +6,9 @@
 #[cfg(feature = "offline-cache")]
 use crate::cache::{CacheConfig, EncryptionKey, EvictionPolicy, OfflineCacheManager};
 use crate::config::{StreamingConfig, StreamingState, StreamingStats};
+use crate::limiter::LimiterConfig;
 use crate::ring_buffer::RingBuffer;
+use crate::silence::SilenceTrim;
 use crate::streaming::{StreamingRequest, StreamingService};
 use crate::traits::{AudioCodec, AudioFormat, AudioSource, ProbeResult};
 use crate::PlaybackError;
@@ -17,7 +19,7 @@ use core_async::task;
 use js_sys::{Float32Array, Object, Reflect, Uint8Array};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::future_to_promise;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::rc::Rc;
@@ -212,6 +214,12 @@ impl JsCacheConfig {
     pub fn set_cache_directory(&mut self, path: String) {
         self.inner.cache_directory = path;
     }
+
+    /// Set the size of each ranged download request in kilobytes
+    #[wasm_bindgen(js_name = setDownloadChunkSizeKB)]
+    pub fn set_download_chunk_size_kb(&mut self, kb: u32) {
+        self.inner.download_chunk_size = kb as usize * 1024;
+    }
 }
 
 #[cfg(feature = "offline-cache")]
@@ -485,26 +493,30 @@ impl JsAudioDecoder {
     /// # Arguments
     ///
     /// * `position_seconds` - Target position in seconds
+    ///
+    /// # Returns
+    ///
+    /// The position actually reached, in seconds
     #[wasm_bindgen(js_name = seek)]
-    pub async fn seek(&mut self, position_seconds: f64) -> Result<(), JsValue> {
+    pub async fn seek(&mut self, position_seconds: f64) -> Result<f64, JsValue> {
         let decoder = self
             .decoder
             .as_mut()
             .ok_or_else(|| JsValue::from_str("Decoder not initialized"))?;
 
         let duration = Duration::from_secs_f64(position_seconds);
-        decoder
+        let actual = decoder
             .seek(duration)
             .await
             .map_err(|e| JsValue::from_str(&format!("Seek error: {}", e)))?;
 
-        Ok(())
+        Ok(actual.as_secs_f64())
     }
 
     /// Reset decoder to beginning
     #[wasm_bindgen(js_name = reset)]
     pub async fn reset(&mut self) -> Result<(), JsValue> {
-        self.seek(0.0).await
+        self.seek(0.0).await.map(|_| ())
     }
 }
 
@@ -537,6 +549,18 @@ impl JsStreamingConfig {
         self.inner.min_buffer_frames = frames;
     }
 
+    /// Audio to buffer before playback starts; 0 uses `minBufferFrames`
+    #[wasm_bindgen(js_name = setPrebufferMs)]
+    pub fn set_prebuffer_ms(&mut self, prebuffer_ms: u32) {
+        self.inner.prebuffer_duration =
+            (prebuffer_ms > 0).then(|| Duration::from_millis(prebuffer_ms as u64));
+    }
+
+    #[wasm_bindgen(js_name = setPrebufferTimeoutMs)]
+    pub fn set_prebuffer_timeout_ms(&mut self, timeout_ms: u32) {
+        self.inner.prebuffer_timeout = Duration::from_millis(timeout_ms as u64);
+    }
+
     #[wasm_bindgen(js_name = setPrefetchThreshold)]
     pub fn set_prefetch_threshold(&mut self, threshold: f32) {
         self.inner.prefetch_threshold = threshold.clamp(0.0, 1.0);
@@ -567,6 +591,30 @@ impl JsStreamingConfig {
         self.inner.enable_adaptive_streaming = enabled;
     }
 
+    /// Enable the peak limiter with the given ceiling (dBFS), or disable it
+    #[wasm_bindgen(js_name = setLimiterCeilingDb)]
+    pub fn set_limiter_ceiling_db(&mut self, ceiling_db: Option<f32>) {
+        self.inner.limiter = ceiling_db.map(|ceiling_db| LimiterConfig {
+            ceiling_db,
+            ..Default::default()
+        });
+    }
+
+    #[wasm_bindgen(js_name = setRecoveryAttempts)]
+    pub fn set_recovery_attempts(&mut self, attempts: u32) {
+        self.inner.recovery_attempts = attempts;
+    }
+
+    #[wasm_bindgen(js_name = setApproachingEndLeadMs)]
+    pub fn set_approaching_end_lead_ms(&mut self, lead_ms: u32) {
+        self.inner.approaching_end_lead = Duration::from_millis(lead_ms as u64);
+    }
+
+    #[wasm_bindgen(js_name = setSkipSilence)]
+    pub fn set_skip_silence(&mut self, skip: bool) {
+        self.inner.skip_silence = skip;
+    }
+
     #[wasm_bindgen(js_name = validate)]
     pub fn validate(&self) -> Result<(), JsValue> {
         self.inner.validate().map_err(to_js_error)
@@ -587,6 +635,7 @@ pub enum JsStreamingState {
     Streaming,
     Paused,
     Stalled,
+    Recovering,
     Completed,
     Error,
 }
@@ -599,6 +648,7 @@ impl From<StreamingState> for JsStreamingState {
             StreamingState::Streaming => JsStreamingState::Streaming,
             StreamingState::Paused => JsStreamingState::Paused,
             StreamingState::Stalled => JsStreamingState::Stalled,
+            StreamingState::Recovering => JsStreamingState::Recovering,
             StreamingState::Completed => JsStreamingState::Completed,
             StreamingState::Error => JsStreamingState::Error,
         }
@@ -628,6 +678,11 @@ impl JsStreamingStats {
         self.inner.current_buffer_frames
     }
 
+    #[wasm_bindgen(js_name = prebufferProgress)]
+    pub fn prebuffer_progress(&self) -> f32 {
+        self.inner.prebuffer_progress
+    }
+
     #[wasm_bindgen(js_name = totalBytesDownloaded)]
     pub fn total_bytes_downloaded(&self) -> u64 {
         self.inner.total_bytes_downloaded
@@ -643,6 +698,11 @@ impl JsStreamingStats {
         self.inner.underrun_count
     }
 
+    #[wasm_bindgen(js_name = droppedSamples)]
+    pub fn dropped_samples(&self) -> usize {
+        self.inner.dropped_samples
+    }
+
     #[wasm_bindgen(js_name = avgDownloadSpeed)]
     pub fn avg_download_speed(&self) -> f64 {
         self.inner.avg_download_speed
@@ -680,7 +740,7 @@ impl JsRingBuffer {
             .ok_or_else(|| JsValue::from_str("Ring buffer capacity overflow"))?;
 
         Ok(Self {
-            inner: RingBuffer::new(samples),
+            inner: RingBuffer::new(samples).with_channels(channels),
             channels,
         })
     }
@@ -835,6 +895,7 @@ pub struct JsStreamingSession {
     source: AudioSource,
     probe: JsProbeResult,
     channels: u16,
+    silence: Cell<Option<SilenceTrim>>,
 }
 
 #[cfg(feature = "core-decoder")]
@@ -870,6 +931,7 @@ impl JsStreamingSession {
             source: audio_source,
             probe: probe_js,
             channels: probe.format.channels,
+            silence: Cell::new(None),
         })
     }
 
@@ -883,6 +945,9 @@ impl JsStreamingSession {
             source: self.source.clone(),
             ring_buffer: self.ring_buffer.clone(),
             config: self.config.clone(),
+            output_channels: None,
+            output_buffer_sizes: None,
+            silence: self.silence.get(),
         };
 
         let service = self.service.clone();
@@ -899,6 +964,15 @@ impl JsStreamingSession {
         Ok(())
     }
 
+    /// Silence to skip when the config enables it; set before `start`.
+    #[wasm_bindgen(js_name = setSilenceTrimMs)]
+    pub fn set_silence_trim_ms(&self, leading_ms: u32, trailing_ms: u32) {
+        self.silence.set(Some(SilenceTrim {
+            leading: Duration::from_millis(leading_ms as u64),
+            trailing: Duration::from_millis(trailing_ms as u64),
+        }));
+    }
+
     pub fn pause(&self) {
         self.service.pause();
     }
@@ -1191,6 +1265,26 @@ impl JsOfflineCacheManager {
         })
     }
 
+    #[wasm_bindgen(js_name = pinTrack)]
+    pub fn pin_track(&self, track_id: String) -> js_sys::Promise {
+        let manager = self.manager.clone();
+        future_to_promise(async move {
+            let id = TrackId::from_string(&track_id).map_err(to_js_error)?;
+            manager.pin(&id).await.map_err(playback_error_to_js)?;
+            Ok(JsValue::UNDEFINED)
+        })
+    }
+
+    #[wasm_bindgen(js_name = unpinTrack)]
+    pub fn unpin_track(&self, track_id: String) -> js_sys::Promise {
+        let manager = self.manager.clone();
+        future_to_promise(async move {
+            let id = TrackId::from_string(&track_id).map_err(to_js_error)?;
+            manager.unpin(&id).await.map_err(playback_error_to_js)?;
+            Ok(JsValue::UNDEFINED)
+        })
+    }
+
     #[wasm_bindgen(js_name = deleteCachedTrack)]
     pub fn delete_cached_track(&self, track_id: String) -> js_sys::Promise {
         let manager = self.manager.clone();
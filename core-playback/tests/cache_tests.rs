@@ -5,10 +5,10 @@
 #[cfg(test)]
 mod tests {
     use core_playback::cache::{
-        CacheConfig, EvictionPolicy, OfflineCacheManager,
+        CacheConfig, EvictionPolicy,
     };
-    use core_library::models::{CacheStatus, TrackId};
-    use std::sync::Arc;
+    use core_library::models::CacheStatus;
+    
     use std::time::Duration;
 
     fn create_test_config() -> CacheConfig {
@@ -21,6 +21,7 @@ mod tests {
             verify_integrity: true,
             max_retry_attempts: 2,
             cache_directory: "test_cache".to_string(),
+            download_chunk_size: 64 * 1024,
         }
     }
 
@@ -56,3 +57,518 @@ mod tests {
         assert!(!CacheStatus::Cached.needs_download());
     }
 }
+
+#[cfg(all(feature = "offline-cache", not(target_arch = "wasm32")))]
+mod download {
+    use async_trait::async_trait;
+    use bridge_traits::error::{BridgeError, Result as BridgeResult};
+    use bridge_traits::http::{HttpClient, HttpRequest, HttpResponse};
+    use bridge_traits::storage::{FileMetadata, FileSystemAccess, RemoteFile, StorageProvider};
+    use bridge_traits::{DynAsyncRead, DynAsyncWrite};
+    use bytes::Bytes;
+    use core_library::db::{create_test_pool, insert_test_provider};
+    use core_library::models::{CacheStatus, CachedTrack, Track, TrackId};
+    use core_library::repositories::{CacheMetadataRepository, SqliteCacheMetadataRepository};
+    use core_library::{SqliteAdapter, SqliteTrackRepository, TrackRepository};
+    use core_playback::cache::{CacheConfig, EncryptionKey, OfflineCacheManager};
+    use core_playback::PlaybackError;
+    use core_runtime::events::{CoreEvent, EventBus, PlaybackEvent};
+    use md5::Md5;
+    use sha2::{Digest, Sha256};
+    use std::collections::HashMap;
+    use std::path::{Path, PathBuf};
+    use std::sync::{Arc, Mutex};
+
+    /// HTTP client for provider downloads; never called
+    struct OfflineHttpClient;
+
+    #[async_trait]
+    impl HttpClient for OfflineHttpClient {
+        async fn execute(&self, _request: HttpRequest) -> BridgeResult<HttpResponse> {
+            Err(BridgeError::NotAvailable("offline".to_string()))
+        }
+
+        async fn download_stream(&self, _url: String) -> BridgeResult<Box<DynAsyncRead>> {
+            Err(BridgeError::NotAvailable("offline".to_string()))
+        }
+    }
+
+    /// Filesystem keeping files in memory
+    #[derive(Default)]
+    struct MemoryFileSystem {
+        files: Mutex<HashMap<PathBuf, Vec<u8>>>,
+    }
+
+    impl MemoryFileSystem {
+        fn file(&self, path: &Path) -> Option<Vec<u8>> {
+            self.files.lock().unwrap().get(path).cloned()
+        }
+    }
+
+    #[async_trait]
+    impl FileSystemAccess for MemoryFileSystem {
+        async fn get_cache_directory(&self) -> BridgeResult<PathBuf> {
+            Ok(PathBuf::from("/cache"))
+        }
+
+        async fn get_data_directory(&self) -> BridgeResult<PathBuf> {
+            Ok(PathBuf::from("/data"))
+        }
+
+        async fn exists(&self, path: &Path) -> BridgeResult<bool> {
+            Ok(self.files.lock().unwrap().contains_key(path))
+        }
+
+        async fn metadata(&self, path: &Path) -> BridgeResult<FileMetadata> {
+            let size = self
+                .file(path)
+                .ok_or_else(|| BridgeError::OperationFailed("No such file".to_string()))?
+                .len() as u64;
+            Ok(FileMetadata {
+                size,
+                created_at: None,
+                modified_at: None,
+                is_directory: false,
+            })
+        }
+
+        async fn create_dir_all(&self, _path: &Path) -> BridgeResult<()> {
+            Ok(())
+        }
+
+        async fn read_file(&self, path: &Path) -> BridgeResult<Bytes> {
+            self.file(path)
+                .map(Bytes::from)
+                .ok_or_else(|| BridgeError::OperationFailed("No such file".to_string()))
+        }
+
+        async fn write_file(&self, path: &Path, data: Bytes) -> BridgeResult<()> {
+            self.files
+                .lock()
+                .unwrap()
+                .insert(path.to_path_buf(), data.to_vec());
+            Ok(())
+        }
+
+        async fn append_file(&self, path: &Path, data: Bytes) -> BridgeResult<()> {
+            self.files
+                .lock()
+                .unwrap()
+                .entry(path.to_path_buf())
+                .or_default()
+                .extend_from_slice(&data);
+            Ok(())
+        }
+
+        async fn delete_file(&self, path: &Path) -> BridgeResult<()> {
+            self.files.lock().unwrap().remove(path);
+            Ok(())
+        }
+
+        async fn delete_dir_all(&self, _path: &Path) -> BridgeResult<()> {
+            unimplemented!()
+        }
+
+        async fn list_directory(&self, _path: &Path) -> BridgeResult<Vec<PathBuf>> {
+            unimplemented!()
+        }
+
+        async fn open_read_stream(&self, _path: &Path) -> BridgeResult<Box<DynAsyncRead>> {
+            unimplemented!()
+        }
+
+        async fn open_write_stream(&self, _path: &Path) -> BridgeResult<Box<DynAsyncWrite>> {
+            unimplemented!()
+        }
+    }
+
+    /// Provider serving `data` by byte range whose connection drops for
+    /// ranges starting at or after `drop_from`
+    struct RangedProvider {
+        data: Bytes,
+        md5_checksum: Option<String>,
+        drop_from: Mutex<Option<u64>>,
+        ranges: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl StorageProvider for RangedProvider {
+        async fn list_media(
+            &self,
+            _cursor: Option<String>,
+        ) -> BridgeResult<(Vec<RemoteFile>, Option<String>)> {
+            Ok((Vec::new(), None))
+        }
+
+        async fn get_metadata(&self, file_id: &str) -> BridgeResult<RemoteFile> {
+            Ok(RemoteFile {
+                id: file_id.to_string(),
+                name: "track.flac".to_string(),
+                mime_type: Some("audio/flac".to_string()),
+                size: Some(self.data.len() as u64),
+                created_at: None,
+                modified_at: None,
+                is_folder: false,
+                parent_ids: Vec::new(),
+                md5_checksum: self.md5_checksum.clone(),
+                metadata: HashMap::new(),
+            })
+        }
+
+        async fn download(&self, _file_id: &str, range: Option<&str>) -> BridgeResult<Bytes> {
+            let range = range.expect("downloads should be ranged");
+            self.ranges.lock().unwrap().push(range.to_string());
+
+            let (start, end) = range
+                .strip_prefix("bytes=")
+                .and_then(|bounds| bounds.split_once('-'))
+                .map(|(start, end)| (start.parse::<u64>().unwrap(), end.parse::<u64>().unwrap()))
+                .unwrap();
+            if matches!(*self.drop_from.lock().unwrap(), Some(from) if start >= from) {
+                return Err(BridgeError::OperationFailed("Connection reset".to_string()));
+            }
+            Ok(self.data.slice(start as usize..end as usize + 1))
+        }
+
+        fn supports_range(&self) -> bool {
+            true
+        }
+
+        async fn get_changes(
+            &self,
+            _cursor: Option<String>,
+        ) -> BridgeResult<(Vec<RemoteFile>, Option<String>)> {
+            Ok((Vec::new(), None))
+        }
+    }
+
+    const CHUNK: u64 = 64 * 1024;
+
+    fn track_data() -> Bytes {
+        (0..300_000u32).map(|i| (i % 251) as u8).collect()
+    }
+
+    /// Cache manager over an in-memory filesystem, with one library track
+    /// whose provider file is `data`
+    struct Fixture {
+        manager: OfflineCacheManager,
+        fs: Arc<MemoryFileSystem>,
+        provider: Arc<RangedProvider>,
+        cache_repository: SqliteCacheMetadataRepository,
+        events: tokio::sync::broadcast::Receiver<CoreEvent>,
+        track_id: TrackId,
+        data: Bytes,
+    }
+
+    impl Fixture {
+        async fn new(key: Option<EncryptionKey>, md5_checksum: Option<String>) -> Self {
+            let data = track_data();
+
+            let pool = create_test_pool().await.unwrap();
+            insert_test_provider(&pool).await;
+            let db = Arc::new(SqliteAdapter::from_pool(pool));
+            let track_repository = Arc::new(SqliteTrackRepository::new(db.clone()));
+            let cache_repository = SqliteCacheMetadataRepository::new(db.clone());
+
+            let mut track = Track::new(
+                "Long Track".to_string(),
+                "test-provider".to_string(),
+                "file-1".to_string(),
+                600_000,
+                1,
+            );
+            track.file_size = Some(data.len() as i64);
+            track.hash = Some(format!("{:x}", Sha256::digest(&data)));
+            track_repository.insert(&track).await.unwrap();
+            let track_id = TrackId::from_string(&track.id).unwrap();
+
+            let fs = Arc::new(MemoryFileSystem::default());
+            let provider = Arc::new(RangedProvider {
+                data: data.clone(),
+                md5_checksum,
+                drop_from: Mutex::new(None),
+                ranges: Mutex::new(Vec::new()),
+            });
+            let event_bus = Arc::new(EventBus::new(64));
+            let events = event_bus.subscribe();
+
+            let config = CacheConfig {
+                enable_encryption: key.is_some(),
+                max_retry_attempts: 1,
+                download_chunk_size: CHUNK as usize,
+                cache_directory: "test_cache".to_string(),
+                ..CacheConfig::default()
+            };
+            let mut manager = OfflineCacheManager::new(
+                config,
+                db.clone(),
+                track_repository,
+                fs.clone(),
+                Arc::new(OfflineHttpClient),
+                provider.clone(),
+            )
+            .with_event_bus(event_bus);
+            if let Some(key) = key {
+                manager = manager.with_encryption(key);
+            }
+            manager.initialize().await.unwrap();
+
+            Self {
+                manager,
+                fs,
+                provider,
+                cache_repository,
+                events,
+                track_id,
+                data,
+            }
+        }
+
+        async fn entry(&self) -> CachedTrack {
+            self.cache_repository
+                .find_by_track_id(&self.track_id)
+                .await
+                .unwrap()
+                .unwrap()
+        }
+
+        /// Where the track's cache file lives
+        async fn path(&self) -> PathBuf {
+            Path::new("/cache/test_cache").join(self.entry().await.cache_path)
+        }
+    }
+
+    /// Interrupt a download after two chunks, resume it, and return the
+    /// stored and decrypted files
+    async fn interrupt_and_resume(key: Option<EncryptionKey>) -> (Vec<u8>, Bytes, Bytes) {
+        let mut fixture = Fixture::new(key, None).await;
+        let (manager, provider, track_id) = (&fixture.manager, &fixture.provider, fixture.track_id);
+        *provider.drop_from.lock().unwrap() = Some(2 * CHUNK);
+
+        // The connection drops after two chunks; their progress is kept
+        assert!(manager.download_track(track_id).await.is_err());
+        let entry = fixture.entry().await;
+        assert_eq!(entry.status, CacheStatus::Failed);
+        assert_eq!(entry.downloaded_bytes, 2 * CHUNK);
+        let path = fixture.path().await;
+        assert_eq!(
+            fixture.fs.file(&path).unwrap().len() as u64,
+            entry.cached_size
+        );
+
+        // Retrying picks up at the first missing byte
+        *provider.drop_from.lock().unwrap() = None;
+        provider.ranges.lock().unwrap().clear();
+        manager.download_track(track_id).await.unwrap();
+        let ranges = provider.ranges.lock().unwrap().clone();
+        assert_eq!(ranges[0], format!("bytes={}-{}", 2 * CHUNK, 3 * CHUNK - 1));
+        assert_eq!(ranges.len(), 3);
+        let stored = manager.read_cached_track(&track_id).await.unwrap();
+
+        // Progress was reported after every chunk
+        let mut reported = Vec::new();
+        while let Ok(event) = fixture.events.try_recv() {
+            if let CoreEvent::Playback(PlaybackEvent::DownloadProgress {
+                downloaded_bytes,
+                total_bytes,
+                ..
+            }) = event
+            {
+                assert_eq!(total_bytes, fixture.data.len() as u64);
+                reported.push(downloaded_bytes);
+            }
+        }
+        assert_eq!(reported, vec![65_536, 131_072, 196_608, 262_144, 300_000]);
+
+        (fixture.fs.file(&path).unwrap(), stored, fixture.data)
+    }
+
+    #[tokio::test]
+    async fn test_resumed_download_is_byte_identical() {
+        let (on_disk, stored, data) = interrupt_and_resume(None).await;
+        assert_eq!(stored, data);
+        assert_eq!(on_disk, data.to_vec());
+    }
+
+    #[tokio::test]
+    async fn test_resumed_encrypted_download_decrypts_byte_identical() {
+        let key = EncryptionKey::generate().unwrap();
+        let (on_disk, stored, data) = interrupt_and_resume(Some(key)).await;
+        assert_eq!(stored, data);
+
+        // Five chunks, each sealed separately
+        assert_ne!(on_disk, data.to_vec());
+        assert_eq!(
+            on_disk.len(),
+            8 + data.len() + 5 * core_playback::cache::encryption::CHUNK_OVERHEAD
+        );
+    }
+
+    /// Download the track, flip one byte of its cache file and verify it
+    async fn corrupt_and_verify(fixture: &Fixture) {
+        let (manager, track_id) = (&fixture.manager, &fixture.track_id);
+        manager.download_track(*track_id).await.unwrap();
+        assert!(manager.verify(track_id).await.unwrap());
+
+        let path = fixture.path().await;
+        fixture.fs.files.lock().unwrap().get_mut(&path).unwrap()[1000] ^= 0xFF;
+
+        assert!(!manager.verify(track_id).await.unwrap());
+        assert_eq!(fixture.entry().await.status, CacheStatus::Stale);
+        assert!(manager.read_cached_track(track_id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_verify_against_provider_checksum() {
+        let md5 = format!("{:X}", Md5::digest(track_data()));
+        let fixture = Fixture::new(None, Some(md5.clone())).await;
+        corrupt_and_verify(&fixture).await;
+        assert_eq!(
+            fixture.entry().await.content_hash,
+            format!("md5:{}", md5.to_lowercase())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_against_local_checksum() {
+        let fixture = Fixture::new(None, None).await;
+        corrupt_and_verify(&fixture).await;
+        assert_eq!(
+            fixture.entry().await.content_hash,
+            format!("{:x}", Sha256::digest(&fixture.data))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_detects_tampered_encrypted_file() {
+        let key = EncryptionKey::generate().unwrap();
+        let fixture = Fixture::new(Some(key), None).await;
+        corrupt_and_verify(&fixture).await;
+    }
+
+    #[tokio::test]
+    async fn test_download_rejects_checksum_mismatch() {
+        let fixture = Fixture::new(None, Some("0".repeat(32))).await;
+        let err = fixture
+            .manager
+            .download_track(fixture.track_id)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Checksum mismatch"));
+
+        // Nothing is kept for the next attempt to resume from
+        let entry = fixture.entry().await;
+        assert_eq!(entry.status, CacheStatus::Failed);
+        assert_eq!(entry.downloaded_bytes, 0);
+    }
+
+    /// Cache manager with a `max_bytes` limit over tracks that are already
+    /// cached with the given sizes, least recently used first
+    async fn cached_library(
+        max_bytes: u64,
+        sizes: &[u64],
+    ) -> (
+        OfflineCacheManager,
+        SqliteCacheMetadataRepository,
+        Vec<TrackId>,
+    ) {
+        let pool = create_test_pool().await.unwrap();
+        insert_test_provider(&pool).await;
+        let db = Arc::new(SqliteAdapter::from_pool(pool));
+        let track_repository = Arc::new(SqliteTrackRepository::new(db.clone()));
+        let cache_repository = SqliteCacheMetadataRepository::new(db.clone());
+
+        let mut track_ids = Vec::new();
+        for (i, size) in sizes.iter().enumerate() {
+            let track = Track::new(
+                format!("Track {}", i),
+                "test-provider".to_string(),
+                format!("file-{}", i),
+                180_000,
+                1,
+            );
+            track_repository.insert(&track).await.unwrap();
+            let track_id = TrackId::from_string(&track.id).unwrap();
+
+            let mut entry = CachedTrack::new(track_id, track.id.clone(), *size);
+            entry.mark_cached(*size, String::new(), false);
+            entry.last_accessed_at = i as i64;
+            cache_repository.insert(&entry).await.unwrap();
+            track_ids.push(track_id);
+        }
+
+        let config = CacheConfig {
+            max_cache_size_bytes: max_bytes,
+            cache_directory: "test_cache".to_string(),
+            ..CacheConfig::default()
+        };
+        let manager = OfflineCacheManager::new(
+            config,
+            db.clone(),
+            track_repository,
+            Arc::new(MemoryFileSystem::default()),
+            Arc::new(OfflineHttpClient),
+            Arc::new(RangedProvider {
+                data: Bytes::new(),
+                md5_checksum: None,
+                drop_from: Mutex::new(None),
+                ranges: Mutex::new(Vec::new()),
+            }),
+        );
+        manager.initialize().await.unwrap();
+
+        (manager, cache_repository, track_ids)
+    }
+
+    async fn remaining(cache_repository: &SqliteCacheMetadataRepository) -> Vec<TrackId> {
+        let mut entries = cache_repository.find_all().await.unwrap();
+        entries.sort_by_key(|entry| entry.last_accessed_at);
+        entries.into_iter().map(|entry| entry.track_id).collect()
+    }
+
+    #[tokio::test]
+    async fn test_pinned_tracks_are_never_evicted() {
+        let (manager, cache_repository, ids) = cached_library(250, &[100; 4]).await;
+
+        // The two least recently used tracks are the ones LRU would pick
+        manager.pin(&ids[0]).await.unwrap();
+        manager.pin(&ids[1]).await.unwrap();
+        let entry = cache_repository.find_by_track_id(&ids[0]).await.unwrap();
+        assert!(entry.unwrap().pinned);
+
+        let stats = manager.get_cache_stats().await.unwrap();
+        assert_eq!(stats.pinned_tracks, 2);
+        assert_eq!(stats.pinned_bytes, 200);
+
+        assert_eq!(manager.evict_oldest().await.unwrap(), 2);
+        assert_eq!(remaining(&cache_repository).await, ids[..2].to_vec());
+
+        // Even asking for far more space than exists leaves the pins alone
+        assert_eq!(manager.evict_tracks(10_000).await.unwrap(), 0);
+        assert_eq!(remaining(&cache_repository).await, ids[..2].to_vec());
+    }
+
+    #[tokio::test]
+    async fn test_pins_over_limit_report_cache_full() {
+        let (manager, cache_repository, ids) = cached_library(250, &[100; 3]).await;
+        for id in &ids {
+            manager.pin(id).await.unwrap();
+        }
+
+        let err = manager.evict_oldest().await.unwrap_err();
+        assert!(matches!(err, PlaybackError::CacheFull));
+        assert_eq!(remaining(&cache_repository).await, ids);
+
+        // Once the pins fit again, the unpinned track is evicted
+        manager.unpin(&ids[2]).await.unwrap();
+        assert_eq!(manager.evict_oldest().await.unwrap(), 1);
+        assert_eq!(remaining(&cache_repository).await, ids[..2].to_vec());
+    }
+
+    #[tokio::test]
+    async fn test_pin_requires_cache_entry() {
+        let (manager, _, _) = cached_library(250, &[]).await;
+        let err = manager.pin(&TrackId::new()).await.unwrap_err();
+        assert!(matches!(err, PlaybackError::NotCached(_)));
+    }
+}
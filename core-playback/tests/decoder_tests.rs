@@ -0,0 +1,344 @@
+//! Integration tests for the Symphonia decoder against real container files
+//!
+//! `fixtures/dual_stream.mkv` is a Matroska file with two MP3 audio streams
+//! (44.1kHz stereo): stream 0 is tagged "eng" and holds 10 MP3 frames, stream
+//! 1 is tagged "jpn" and holds the first 5 of them.
+//!
+//! `fixtures/tone.flac` is a 44.1kHz stereo 16-bit FLAC of four 1024-sample
+//! frames with a valid STREAMINFO MD5. `fixtures/tone_corrupt.flac` is the
+//! same file with one audio byte flipped in the third frame; the demuxer
+//! silently drops the damaged frame, so only MD5 verification notices.
+//!
+//! Seek tests also use a generated one-second mono WAV whose samples ramp
+//! with the frame index, so misaligned audio cannot go unnoticed.
+//!
+//! Gapless tests generate a CBR MP3 of silent frames behind a LAME "Info"
+//! frame carrying the encoder delay and padding.
+
+#![cfg(all(feature = "core-decoder", not(target_arch = "wasm32")))]
+
+use bytes::Bytes;
+use core_playback::{
+    AudioCodec, AudioDecoder, AudioSource, DecoderOptions, PlaybackError, SeekPrecision,
+    SymphoniaDecoder,
+};
+use std::time::Duration;
+
+const DUAL_STREAM_MKV: &[u8] = include_bytes!("fixtures/dual_stream.mkv");
+const TONE_FLAC: &[u8] = include_bytes!("fixtures/tone.flac");
+const TONE_CORRUPT_FLAC: &[u8] = include_bytes!("fixtures/tone_corrupt.flac");
+
+fn dual_stream_source() -> AudioSource {
+    AudioSource::CachedChunk {
+        data: Bytes::from_static(DUAL_STREAM_MKV),
+        codec_hint: None,
+    }
+}
+
+fn flac_source(data: &'static [u8]) -> AudioSource {
+    AudioSource::CachedChunk {
+        data: Bytes::from_static(data),
+        codec_hint: Some(AudioCodec::Flac),
+    }
+}
+
+async fn verifying_decoder(data: &'static [u8]) -> SymphoniaDecoder {
+    let options = DecoderOptions {
+        verify: true,
+        ..Default::default()
+    };
+    SymphoniaDecoder::with_options(flac_source(data), options)
+        .await
+        .unwrap()
+}
+
+/// 44.1kHz mono 16-bit WAV of `frames` frames, each holding its own index
+#[cfg(feature = "decoder-wav")]
+fn ramp_wav_source(frames: u32) -> AudioSource {
+    let data_len = frames * 2;
+    let mut wav = Vec::with_capacity(44 + data_len as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+    wav.extend_from_slice(&44_100u32.to_le_bytes());
+    wav.extend_from_slice(&88_200u32.to_le_bytes()); // byte rate
+    wav.extend_from_slice(&2u16.to_le_bytes()); // block align
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    for frame in 0..frames {
+        wav.extend_from_slice(&((frame % 32_768) as i16).to_le_bytes());
+    }
+
+    AudioSource::CachedChunk {
+        data: Bytes::from(wav),
+        codec_hint: Some(AudioCodec::Wav),
+    }
+}
+
+/// 128kbps 44.1kHz stereo MP3 of `frames` silent frames, preceded by a LAME
+/// Info frame recording `delay` and `padding` samples
+fn lame_mp3_source(frames: u32, delay: u16, padding: u16) -> AudioSource {
+    // MPEG-1 Layer III, no CRC, 128kbps, 44.1kHz, no padding, stereo
+    const HEADER: [u8; 4] = [0xFF, 0xFB, 0x90, 0x00];
+    const FRAME_LEN: usize = 417;
+    const SIDE_INFO_LEN: usize = 32;
+
+    let mut info = vec![0; FRAME_LEN];
+    info[..4].copy_from_slice(&HEADER);
+    let mut tag = Vec::new();
+    tag.extend_from_slice(b"Info");
+    tag.extend_from_slice(&0x0Fu32.to_be_bytes()); // frames, bytes, TOC, quality
+    tag.extend_from_slice(&frames.to_be_bytes());
+    tag.extend_from_slice(&((frames + 1) * FRAME_LEN as u32).to_be_bytes());
+    tag.extend((0..100).map(|i| (i * 256 / 100) as u8));
+    tag.extend_from_slice(&0u32.to_be_bytes());
+    // LAME extension
+    tag.extend_from_slice(b"LAME3.100");
+    tag.extend_from_slice(&[0, 0]); // revision and VBR method, lowpass
+    tag.extend_from_slice(&[0; 8]); // peak, radio and audiophile gain
+    tag.extend_from_slice(&[0, 128]); // encoding flags, bitrate
+    let trim = (delay as u32) << 12 | padding as u32;
+    tag.extend_from_slice(&trim.to_be_bytes()[1..]);
+    tag.extend_from_slice(&[0; 10]); // misc, MP3 gain, preset, length, music CRC
+    let tag_start = 4 + SIDE_INFO_LEN;
+    info[tag_start..tag_start + tag.len()].copy_from_slice(&tag);
+    let crc_at = tag_start + tag.len();
+    let crc = crc16_arc(&info[..crc_at]);
+    info[crc_at..crc_at + 2].copy_from_slice(&crc.to_be_bytes());
+
+    let mut mp3 = info;
+    for _ in 0..frames {
+        // Zeroed side info and main data decode to silence
+        mp3.extend_from_slice(&HEADER);
+        mp3.extend_from_slice(&[0; FRAME_LEN - 4]);
+    }
+
+    AudioSource::CachedChunk {
+        data: Bytes::from(mp3),
+        codec_hint: Some(AudioCodec::Mp3),
+    }
+}
+
+/// CRC-16/ARC, as used for the LAME tag checksum
+fn crc16_arc(data: &[u8]) -> u16 {
+    data.iter().fold(0, |mut crc, &byte| {
+        crc ^= byte as u16;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xA001
+            } else {
+                crc >> 1
+            };
+        }
+        crc
+    })
+}
+
+/// Position of `frames` frames into a 44.1kHz stream
+fn frame_time(frames: u64) -> Duration {
+    Duration::from_secs_f64(frames as f64 / 44_100.0)
+}
+
+/// Decode the whole selected stream, returning the number of frames
+async fn decode_all(decoder: &mut SymphoniaDecoder) -> usize {
+    let mut frames = 0;
+    while let Some(chunk) = decoder.decode_frames(usize::MAX).await.unwrap() {
+        frames += chunk.frames;
+    }
+    frames
+}
+
+/// Decode the rest of the stream, returning its interleaved samples
+async fn decode_samples(decoder: &mut SymphoniaDecoder) -> Vec<f32> {
+    let mut samples = Vec::new();
+    while let Some(chunk) = decoder.decode_frames(usize::MAX).await.unwrap() {
+        samples.extend_from_slice(&chunk.samples);
+    }
+    samples
+}
+
+#[tokio::test]
+async fn test_probe_lists_all_audio_streams() {
+    let mut decoder = SymphoniaDecoder::new(dual_stream_source()).await.unwrap();
+    let probe = decoder.probe().await.unwrap();
+
+    assert_eq!(probe.streams.len(), 2);
+    assert_eq!(probe.selected_stream, 0);
+
+    for (index, stream) in probe.streams.iter().enumerate() {
+        assert_eq!(stream.index, index);
+        assert_eq!(stream.codec, AudioCodec::Mp3);
+        assert_eq!(stream.sample_rate, Some(44_100));
+    }
+    assert_eq!(probe.streams[0].language.as_deref(), Some("eng"));
+    assert_eq!(probe.streams[1].language.as_deref(), Some("jpn"));
+    assert_ne!(probe.streams[0].track_id, probe.streams[1].track_id);
+}
+
+#[tokio::test]
+async fn test_decodes_selected_stream() {
+    let mut default_stream = SymphoniaDecoder::new(dual_stream_source()).await.unwrap();
+    let mut second_stream = SymphoniaDecoder::with_stream(dual_stream_source(), 1)
+        .await
+        .unwrap();
+
+    let probe = second_stream.probe().await.unwrap();
+    assert_eq!(probe.selected_stream, 1);
+    assert_eq!(probe.selected().unwrap().language.as_deref(), Some("jpn"));
+
+    // 1152 samples per MP3 frame; the decoder may drop the first frame as
+    // priming, so compare the streams rather than exact counts
+    let default_frames = decode_all(&mut default_stream).await;
+    let second_frames = decode_all(&mut second_stream).await;
+    assert!(second_frames > 0);
+    assert_eq!(default_frames - second_frames, 5 * 1152);
+}
+
+#[tokio::test]
+async fn test_missing_stream_index_is_rejected() {
+    let result = SymphoniaDecoder::with_stream(dual_stream_source(), 2).await;
+    assert!(matches!(result, Err(PlaybackError::TrackNotFound(_))));
+}
+
+#[tokio::test]
+async fn test_flac_verification_passes_for_intact_file() {
+    let mut decoder = verifying_decoder(TONE_FLAC).await;
+    assert_eq!(decode_all(&mut decoder).await, 4 * 1024);
+}
+
+#[tokio::test]
+async fn test_flac_verification_reports_corrupt_frame() {
+    let mut decoder = verifying_decoder(TONE_CORRUPT_FLAC).await;
+
+    let result = loop {
+        match decoder.decode_frames(usize::MAX).await {
+            Ok(Some(_)) => continue,
+            other => break other,
+        }
+    };
+    assert!(matches!(result, Err(PlaybackError::CorruptedStream(_))));
+
+    // Without verification the damaged frame is skipped without an error
+    let mut decoder = SymphoniaDecoder::new(flac_source(TONE_CORRUPT_FLAC))
+        .await
+        .unwrap();
+    assert_eq!(decode_all(&mut decoder).await, 3 * 1024);
+}
+
+#[cfg(feature = "decoder-wav")]
+#[tokio::test]
+async fn test_wav_seek_lands_on_requested_frame() {
+    let mut decoder = SymphoniaDecoder::new(ramp_wav_source(44_100))
+        .await
+        .unwrap();
+    let samples = decode_samples(&mut decoder).await;
+    assert_eq!(samples.len(), 44_100);
+
+    for target in [22_050, 1, 44_099, 0, 10_000] {
+        let actual = decoder.seek(frame_time(target)).await.unwrap();
+        assert_eq!(actual, frame_time(target));
+
+        let chunk = decoder.decode_frames(usize::MAX).await.unwrap().unwrap();
+        assert_eq!(chunk.timestamp, actual);
+        let start = target as usize;
+        assert_eq!(chunk.samples[..], samples[start..start + chunk.frames]);
+    }
+}
+
+#[tokio::test]
+async fn test_flac_accurate_seek_aligns_timestamps() {
+    let mut decoder = SymphoniaDecoder::new(flac_source(TONE_FLAC)).await.unwrap();
+    let samples = decode_samples(&mut decoder).await;
+    assert_eq!(samples.len(), 4 * 1024 * 2);
+
+    // FLAC frame boundaries and positions inside a frame
+    for target in [2048, 1500, 0, 3000, 1024] {
+        let actual = decoder.seek(frame_time(target)).await.unwrap();
+        assert_eq!(actual, frame_time(target));
+
+        let chunk = decoder.decode_frames(usize::MAX).await.unwrap().unwrap();
+        assert_eq!(chunk.timestamp, actual);
+        let start = target as usize * 2;
+        assert_eq!(
+            chunk.samples[..],
+            samples[start..start + chunk.samples.len()]
+        );
+
+        let remaining = chunk.frames + decode_all(&mut decoder).await;
+        assert_eq!(remaining, 4 * 1024 - target as usize);
+    }
+}
+
+#[tokio::test]
+async fn test_flac_coarse_seek_snaps_to_frame_boundary() {
+    let options = DecoderOptions {
+        seek_precision: SeekPrecision::Coarse,
+        ..Default::default()
+    };
+    let mut decoder = SymphoniaDecoder::with_options(flac_source(TONE_FLAC), options)
+        .await
+        .unwrap();
+
+    let actual = decoder.seek(frame_time(1500)).await.unwrap();
+    let actual_frames = (actual.as_secs_f64() * 44_100.0).round() as u64;
+    assert_eq!(actual_frames % 1024, 0);
+    assert_eq!(actual, frame_time(actual_frames));
+
+    // Output resumes with the whole frame the reader landed on
+    let chunk = decoder.decode_frames(usize::MAX).await.unwrap().unwrap();
+    assert_eq!(chunk.timestamp, actual);
+    assert_eq!(chunk.frames, 1024);
+}
+
+#[tokio::test]
+async fn test_seek_past_end_clamps_to_duration() {
+    let mut decoder = SymphoniaDecoder::new(flac_source(TONE_FLAC)).await.unwrap();
+    let duration = decoder.probe().await.unwrap().duration.unwrap();
+
+    let actual = decoder.seek(Duration::from_secs(10)).await.unwrap();
+    assert_eq!(actual, duration);
+    assert_eq!(actual, frame_time(4 * 1024));
+    assert!(decoder.decode_frames(usize::MAX).await.unwrap().is_none());
+
+    // Seeking back into the track resumes decoding
+    assert_eq!(decoder.seek(Duration::ZERO).await.unwrap(), Duration::ZERO);
+    assert_eq!(decode_all(&mut decoder).await, 4 * 1024);
+}
+
+#[tokio::test]
+async fn test_lame_header_reports_encoder_delay_and_padding() {
+    let mut decoder = SymphoniaDecoder::new(lame_mp3_source(10, 576, 1_200))
+        .await
+        .unwrap();
+    let probe = decoder.probe().await.unwrap();
+
+    // Both are adjusted for the 529-sample MP3 decoder delay
+    assert_eq!(probe.encoder_delay_frames, 576 + 529);
+    assert_eq!(probe.encoder_padding_frames, 1_200 - 529);
+    assert_eq!(decode_all(&mut decoder).await, 10 * 1152);
+
+    // In gapless mode the decoder trims them itself
+    let options = DecoderOptions {
+        gapless: true,
+        ..Default::default()
+    };
+    let mut decoder = SymphoniaDecoder::with_options(lame_mp3_source(10, 576, 1_200), options)
+        .await
+        .unwrap();
+    let probe = decoder.probe().await.unwrap();
+    assert_eq!(probe.encoder_delay_frames, 0);
+    assert_eq!(probe.encoder_padding_frames, 0);
+    assert_eq!(decode_all(&mut decoder).await, 10 * 1152 - 1_105 - 671);
+}
+
+#[tokio::test]
+async fn test_formats_without_gapless_tags_report_zero() {
+    let mut decoder = SymphoniaDecoder::new(flac_source(TONE_FLAC)).await.unwrap();
+    let probe = decoder.probe().await.unwrap();
+    assert_eq!(probe.encoder_delay_frames, 0);
+    assert_eq!(probe.encoder_padding_frames, 0);
+}
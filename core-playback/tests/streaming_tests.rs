@@ -72,15 +72,19 @@ fn test_streaming_stats_default() {
 
 #[test]
 fn test_streaming_stats_calculations() {
-    let mut stats = StreamingStats::default();
-    stats.total_frames_buffered = 44100;
-    stats.total_frames_consumed = 22050;
-    stats.current_buffer_frames = 22050;
-    stats.total_bytes_downloaded = 1024 * 1024; // 1 MB
-    stats.http_requests = 10;
-    stats.underrun_count = 2;
-    stats.avg_download_speed = 128.0 * 1024.0; // 128 KB/s
-    stats.avg_decode_time_ms = 5.0;
+    let stats = StreamingStats {
+        total_frames_buffered: 44100,
+        total_frames_consumed: 22050,
+        current_buffer_frames: 22050,
+        prebuffer_progress: 1.0,
+        total_bytes_downloaded: 1024 * 1024, // 1 MB
+        http_requests: 10,
+        underrun_count: 2,
+        dropped_samples: 0,
+        avg_download_speed: 128.0 * 1024.0, // 128 KB/s
+        avg_decode_time_ms: 5.0,
+        output_latency: None,
+    };
 
     assert_eq!(stats.total_frames_buffered, 44100);
     assert_eq!(stats.total_frames_consumed, 22050);
@@ -94,8 +98,10 @@ fn test_streaming_stats_calculations() {
 
 #[test]
 fn test_streaming_stats_buffer_fill() {
-    let mut stats = StreamingStats::default();
-    stats.current_buffer_frames = 50;
+    let stats = StreamingStats {
+        current_buffer_frames: 50,
+        ..Default::default()
+    };
 
     assert_eq!(stats.buffer_fill_percentage(100), 0.5);
     assert_eq!(stats.buffer_fill_percentage(50), 1.0);
@@ -104,8 +110,10 @@ fn test_streaming_stats_buffer_fill() {
 
 #[test]
 fn test_streaming_stats_buffer_critical() {
-    let mut stats = StreamingStats::default();
-    stats.current_buffer_frames = 1000;
+    let stats = StreamingStats {
+        current_buffer_frames: 1000,
+        ..Default::default()
+    };
 
     assert!(!stats.is_buffer_critical(500));
     assert!(stats.is_buffer_critical(2000));
@@ -235,7 +243,7 @@ fn test_ring_buffer_fill_level() {
 fn test_ring_buffer_clear() {
     let buffer = RingBuffer::new(1000);
 
-    buffer.write(&vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+    buffer.write(&[1.0, 2.0, 3.0, 4.0, 5.0]);
     assert_eq!(buffer.available(), 5);
 
     buffer.clear();
@@ -293,3 +301,769 @@ fn test_audio_codec_is_lossy() {
     assert!(!AudioCodec::Flac.is_lossy());
     assert!(!AudioCodec::Wav.is_lossy());
 }
+
+#[cfg(not(target_arch = "wasm32"))]
+mod pipeline {
+    use async_trait::async_trait;
+    use bridge_traits::background::SystemEvent;
+    use bridge_traits::error::{BridgeError, Result as BridgeResult};
+    use bridge_traits::http::{HttpClient, HttpRequest, HttpResponse};
+    use bridge_traits::platform::DynAsyncRead;
+    use core_async::sync::CancellationToken;
+    use core_playback::{
+        AudioDecoder, AudioFormat, AudioFrameChunk, AudioSink, AudioSource, BufferSizeRange,
+        OverflowPolicy, PlaybackError, ProbeResult, Result, RingBuffer, SilenceTrim,
+        StreamingConfig, StreamingRequest, StreamingService, StreamingState,
+    };
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    /// HTTP client for local sources; never called
+    struct OfflineHttpClient;
+
+    #[async_trait]
+    impl HttpClient for OfflineHttpClient {
+        async fn execute(&self, _request: HttpRequest) -> BridgeResult<HttpResponse> {
+            Err(BridgeError::NotAvailable("offline".to_string()))
+        }
+
+        async fn download_stream(&self, _url: String) -> BridgeResult<Box<DynAsyncRead>> {
+            Err(BridgeError::NotAvailable("offline".to_string()))
+        }
+    }
+
+    /// Decoder emitting `chunks` stereo chunks of 100 frames at 44.1kHz,
+    /// each sample holding a tenth of its chunk index
+    struct CountingDecoder {
+        chunks: usize,
+        emitted: usize,
+    }
+
+    #[async_trait]
+    impl AudioDecoder for CountingDecoder {
+        async fn probe(&mut self) -> Result<ProbeResult> {
+            let duration = Duration::from_secs_f64(self.chunks as f64 * 100.0 / 44_100.0);
+            Ok(ProbeResult::new(AudioFormat::cd_quality()).with_duration(Some(duration)))
+        }
+
+        async fn decode_frames(&mut self, max_frames: usize) -> Result<Option<AudioFrameChunk>> {
+            if self.emitted == self.chunks {
+                return Ok(None);
+            }
+            let frames = max_frames.min(100);
+            let chunk = AudioFrameChunk::new(
+                vec![self.emitted as f32 / 10.0; frames * 2],
+                frames,
+                Duration::from_millis(self.emitted as u64),
+            );
+            self.emitted += 1;
+            Ok(Some(chunk))
+        }
+
+        async fn seek(&mut self, position: Duration) -> Result<Duration> {
+            Ok(position)
+        }
+    }
+
+    /// `CountingDecoder` that takes `delay` to produce each chunk
+    struct SlowDecoder {
+        inner: CountingDecoder,
+        delay: Duration,
+    }
+
+    #[async_trait]
+    impl AudioDecoder for SlowDecoder {
+        async fn probe(&mut self) -> Result<ProbeResult> {
+            self.inner.probe().await
+        }
+
+        async fn decode_frames(&mut self, max_frames: usize) -> Result<Option<AudioFrameChunk>> {
+            tokio::time::sleep(self.delay).await;
+            self.inner.decode_frames(max_frames).await
+        }
+
+        async fn seek(&mut self, position: Duration) -> Result<Duration> {
+            self.inner.seek(position).await
+        }
+    }
+
+    /// `CountingDecoder` that fails once with `error` before chunk `fail_at`,
+    /// recording where it is asked to seek
+    struct FlakyDecoder {
+        inner: CountingDecoder,
+        fail_at: usize,
+        error: Option<PlaybackError>,
+        seeks: Arc<Mutex<Vec<Duration>>>,
+    }
+
+    #[async_trait]
+    impl AudioDecoder for FlakyDecoder {
+        async fn probe(&mut self) -> Result<ProbeResult> {
+            self.inner.probe().await
+        }
+
+        async fn decode_frames(&mut self, max_frames: usize) -> Result<Option<AudioFrameChunk>> {
+            if self.inner.emitted == self.fail_at {
+                if let Some(error) = self.error.take() {
+                    return Err(error);
+                }
+            }
+            self.inner.decode_frames(max_frames).await
+        }
+
+        async fn seek(&mut self, position: Duration) -> Result<Duration> {
+            self.seeks.lock().unwrap().push(position);
+            Ok(position)
+        }
+    }
+
+    /// `FlakyDecoder` whose stream reports encoder delay and padding
+    struct PrimedDecoder {
+        inner: FlakyDecoder,
+        delay_frames: u32,
+        padding_frames: u32,
+    }
+
+    #[async_trait]
+    impl AudioDecoder for PrimedDecoder {
+        async fn probe(&mut self) -> Result<ProbeResult> {
+            let probe = self.inner.probe().await?;
+            Ok(probe.with_encoder_trim(self.delay_frames, self.padding_frames))
+        }
+
+        async fn decode_frames(&mut self, max_frames: usize) -> Result<Option<AudioFrameChunk>> {
+            self.inner.decode_frames(max_frames).await
+        }
+
+        async fn seek(&mut self, position: Duration) -> Result<Duration> {
+            self.inner.seek(position).await
+        }
+    }
+
+    /// Stereo decoder producing `frames` copies of `frame`
+    struct ConstantDecoder {
+        frame: [f32; 2],
+        frames: usize,
+        emitted: usize,
+    }
+
+    impl ConstantDecoder {
+        fn new(frame: [f32; 2], frames: usize) -> Self {
+            Self {
+                frame,
+                frames,
+                emitted: 0,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl AudioDecoder for ConstantDecoder {
+        async fn probe(&mut self) -> Result<ProbeResult> {
+            let duration = Duration::from_secs_f64(self.frames as f64 / 44_100.0);
+            Ok(ProbeResult::new(AudioFormat::cd_quality()).with_duration(Some(duration)))
+        }
+
+        async fn decode_frames(&mut self, max_frames: usize) -> Result<Option<AudioFrameChunk>> {
+            let frames = max_frames.min(100).min(self.frames - self.emitted);
+            if frames == 0 {
+                return Ok(None);
+            }
+            let timestamp = Duration::from_secs_f64(self.emitted as f64 / 44_100.0);
+            self.emitted += frames;
+            Ok(Some(AudioFrameChunk::new(
+                self.frame.repeat(frames),
+                frames,
+                timestamp,
+            )))
+        }
+
+        async fn seek(&mut self, position: Duration) -> Result<Duration> {
+            self.emitted = (position.as_secs_f64() * 44_100.0).round() as usize;
+            Ok(position)
+        }
+    }
+
+    /// Sink recording every chunk it receives
+    #[derive(Default)]
+    struct CapturingSink {
+        chunks: Mutex<Vec<AudioFrameChunk>>,
+        flushes: Mutex<usize>,
+    }
+
+    #[async_trait]
+    impl AudioSink for CapturingSink {
+        async fn write(&self, chunk: &AudioFrameChunk) -> Result<()> {
+            self.chunks.lock().unwrap().push(chunk.clone());
+            Ok(())
+        }
+
+        async fn flush(&self) -> Result<()> {
+            *self.flushes.lock().unwrap() += 1;
+            Ok(())
+        }
+    }
+
+    fn request(ring_buffer_samples: usize) -> StreamingRequest {
+        StreamingRequest {
+            source: AudioSource::LocalFile {
+                path: "/path/to/file.mp3".into(),
+            },
+            ring_buffer: RingBuffer::new(ring_buffer_samples),
+            config: StreamingConfig {
+                buffer_frames: 1000,
+                min_buffer_frames: 100,
+                decode_chunk_frames: 100,
+                ..Default::default()
+            },
+            output_channels: None,
+            output_buffer_sizes: None,
+            silence: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sinks_receive_decoded_frames_in_order() {
+        let recorder = Arc::new(CapturingSink::default());
+        let meter = Arc::new(CapturingSink::default());
+        let service = StreamingService::new(
+            Arc::new(OfflineHttpClient),
+            Box::new(CountingDecoder {
+                chunks: 5,
+                emitted: 0,
+            }),
+        )
+        .with_sink(recorder.clone())
+        .with_sink(meter.clone());
+
+        service
+            .run(request(10_000), CancellationToken::new())
+            .await
+            .unwrap();
+        assert_eq!(service.state(), StreamingState::Completed);
+
+        for sink in [&recorder, &meter] {
+            let chunks = sink.chunks.lock().unwrap();
+            assert_eq!(chunks.len(), 5);
+            for (index, chunk) in chunks.iter().enumerate() {
+                assert_eq!(chunk.frames, 100);
+                assert_eq!(chunk.timestamp, Duration::from_millis(index as u64));
+                assert!(chunk.samples.iter().all(|&s| s == index as f32 / 10.0));
+            }
+            assert_eq!(*sink.flushes.lock().unwrap(), 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_approaching_end_fires_once_at_lead_time() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorded = events.clone();
+        let service = StreamingService::new(
+            Arc::new(OfflineHttpClient),
+            Box::new(CountingDecoder {
+                chunks: 50,
+                emitted: 0,
+            }),
+        )
+        .on_approaching_end(move |remaining| recorded.lock().unwrap().push(remaining));
+
+        // 5000 frames at 44.1kHz is ~113ms; report 50ms before the end
+        let mut request = request(20_000);
+        request.config.buffer_frames = 10_000;
+        request.config.approaching_end_lead = Duration::from_millis(50);
+        service
+            .run(request, CancellationToken::new())
+            .await
+            .unwrap();
+        assert_eq!(service.state(), StreamingState::Completed);
+
+        // Fired by the first chunk that brought the end within the lead time
+        let events = events.lock().unwrap();
+        let chunk = Duration::from_secs_f64(100.0 / 44_100.0);
+        assert_eq!(events.len(), 1);
+        assert!(events[0] <= Duration::from_millis(50));
+        assert!(events[0] > Duration::from_millis(50) - chunk);
+    }
+
+    #[tokio::test]
+    async fn test_next_request_is_queued_for_the_host() {
+        let service = StreamingService::new(
+            Arc::new(OfflineHttpClient),
+            Box::new(CountingDecoder {
+                chunks: 1,
+                emitted: 0,
+            }),
+        );
+        assert!(service.take_next().is_none());
+
+        service.set_next(request(1_000));
+        service.set_next(request(2_000));
+        let next = service.take_next().unwrap();
+        assert_eq!(next.ring_buffer.capacity(), 2_000);
+        assert!(service.take_next().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_stays_buffering_until_prebuffer_is_filled() {
+        let service = StreamingService::new(
+            Arc::new(OfflineHttpClient),
+            Box::new(SlowDecoder {
+                inner: CountingDecoder {
+                    chunks: 30,
+                    emitted: 0,
+                },
+                delay: Duration::from_millis(5),
+            }),
+        );
+
+        // 1000 frames at 44.1kHz: ten chunks, ~50ms of decoding
+        let mut request = request(20_000);
+        request.config.buffer_frames = 10_000;
+        request.config.prebuffer_duration = Some(Duration::from_secs_f64(1000.0 / 44_100.0));
+        let ring_buffer = request.ring_buffer.clone();
+
+        let observe = async {
+            let mut observed = Vec::new();
+            while !service.state().is_terminal() {
+                // Nothing consumes the buffer, so its level only grows
+                let state = service.state();
+                observed.push((state, ring_buffer.available()));
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+            observed
+        };
+        let (result, observed) =
+            tokio::join!(service.run(request, CancellationToken::new()), observe);
+        result.unwrap();
+
+        assert!(observed
+            .iter()
+            .any(|&(state, level)| state == StreamingState::Buffering && level > 0));
+        for (state, level) in observed {
+            if state == StreamingState::Streaming {
+                assert!(level >= 2_000, "streaming with only {} samples", level);
+            }
+        }
+        assert_eq!(service.stats().prebuffer_progress, 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_recovers_from_transient_source_failure() {
+        let seeks = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::new(CapturingSink::default());
+        let service = StreamingService::new(
+            Arc::new(OfflineHttpClient),
+            Box::new(FlakyDecoder {
+                inner: CountingDecoder {
+                    chunks: 5,
+                    emitted: 0,
+                },
+                fail_at: 3,
+                error: Some(PlaybackError::StreamingFailed(
+                    "connection reset".to_string(),
+                )),
+                seeks: seeks.clone(),
+            }),
+        )
+        .with_sink(sink.clone());
+
+        let mut request = request(10_000);
+        request.config.recovery_base_delay = Duration::from_millis(1);
+        service
+            .run(request, CancellationToken::new())
+            .await
+            .unwrap();
+        assert_eq!(service.state(), StreamingState::Completed);
+
+        // Resumed from the end of the third chunk without losing audio
+        assert_eq!(
+            *seeks.lock().unwrap(),
+            vec![Duration::from_secs_f64(300.0 / 44_100.0)]
+        );
+        assert_eq!(sink.chunks.lock().unwrap().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_unrecoverable_error_fails_fast() {
+        let seeks = Arc::new(Mutex::new(Vec::new()));
+        let service = StreamingService::new(
+            Arc::new(OfflineHttpClient),
+            Box::new(FlakyDecoder {
+                inner: CountingDecoder {
+                    chunks: 5,
+                    emitted: 0,
+                },
+                fail_at: 3,
+                error: Some(PlaybackError::UnsupportedCodec("mystery".to_string())),
+                seeks: seeks.clone(),
+            }),
+        );
+
+        let result = service.run(request(10_000), CancellationToken::new()).await;
+        assert!(matches!(result, Err(PlaybackError::UnsupportedCodec(_))));
+        assert_eq!(service.state(), StreamingState::Error);
+        assert!(seeks.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_skips_leading_and_trailing_silence() {
+        let seeks = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::new(CapturingSink::default());
+        let service = StreamingService::new(
+            Arc::new(OfflineHttpClient),
+            Box::new(FlakyDecoder {
+                inner: CountingDecoder {
+                    chunks: 10,
+                    emitted: 0,
+                },
+                fail_at: usize::MAX,
+                error: None,
+                seeks: seeks.clone(),
+            }),
+        )
+        .with_sink(sink.clone());
+
+        let mut request = request(10_000);
+        request.config.skip_silence = true;
+        request.silence = Some(SilenceTrim {
+            leading: Duration::from_secs_f64(200.0 / 44_100.0),
+            trailing: Duration::from_secs_f64(250.0 / 44_100.0),
+        });
+        service
+            .run(request, CancellationToken::new())
+            .await
+            .unwrap();
+
+        // Starts past the intro and stops 250 frames before the end
+        assert_eq!(
+            *seeks.lock().unwrap(),
+            vec![Duration::from_secs_f64(200.0 / 44_100.0)]
+        );
+        let frames: usize = sink.chunks.lock().unwrap().iter().map(|c| c.frames).sum();
+        assert_eq!(frames, 550);
+        assert_eq!(service.state(), StreamingState::Completed);
+    }
+
+    #[tokio::test]
+    async fn test_latency_target_sizes_device_buffer_and_is_reported() {
+        let run = |target: Duration| async move {
+            let service = StreamingService::new(
+                Arc::new(OfflineHttpClient),
+                Box::new(CountingDecoder {
+                    chunks: 5,
+                    emitted: 0,
+                }),
+            );
+            let mut request = request(10_000);
+            request.config.latency_target = Some(target);
+            request.output_buffer_sizes = Some(BufferSizeRange::new(256, 4096));
+            service
+                .run(request, CancellationToken::new())
+                .await
+                .unwrap();
+            service.stats().output_latency.unwrap()
+        };
+
+        let low = run(Duration::from_millis(10)).await;
+        let high = run(Duration::from_millis(500)).await;
+
+        // 10ms is 441 frames, within what the device supports
+        assert_eq!(low.device_buffer_frames, 441);
+        assert_eq!(low.achieved, Duration::from_millis(10));
+        assert_eq!(low.prebuffer_frames, 882);
+
+        // 500ms exceeds the device's largest buffer
+        assert_eq!(high.device_buffer_frames, 4096);
+        assert_eq!(high.target, Duration::from_millis(500));
+        assert_eq!(high.achieved, Duration::from_secs_f64(4096.0 / 44_100.0));
+        assert!(low.device_buffer_frames < high.device_buffer_frames);
+    }
+
+    #[tokio::test]
+    async fn test_memory_warning_shrinks_buffer() {
+        let resizes = Arc::new(Mutex::new(Vec::new()));
+        let recorded = resizes.clone();
+        let service = StreamingService::new(
+            Arc::new(OfflineHttpClient),
+            Box::new(CountingDecoder {
+                chunks: 50,
+                emitted: 0,
+            }),
+        )
+        .on_buffer_resize(move |frames| recorded.lock().unwrap().push(frames));
+
+        let request = request(20_000);
+        let ring_buffer = request.ring_buffer.clone();
+        let cancel = CancellationToken::new();
+
+        let pressure = async {
+            // Nothing consumes, so the service fills its 1000-frame budget
+            while ring_buffer.available() < 2_000 {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+            service.handle_system_event(SystemEvent::LowPowerMode(true));
+            service.handle_system_event(SystemEvent::MemoryWarning);
+
+            // After draining, it only refills to the reduced budget
+            ring_buffer.read(&mut [0.0; 2_000]);
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            let refilled = ring_buffer.available();
+            cancel.cancel();
+            refilled
+        };
+        let (result, refilled) = tokio::join!(service.run(request, cancel.clone()), pressure);
+        result.unwrap();
+        assert_eq!(refilled, 1_000);
+
+        // Further warnings halve it down to `min_buffer_frames`
+        for _ in 0..5 {
+            service.handle_system_event(SystemEvent::MemoryWarning);
+        }
+        assert_eq!(*resizes.lock().unwrap(), vec![500, 250, 125, 100]);
+    }
+
+    #[tokio::test]
+    async fn test_stats_report_dropped_samples() {
+        let service = StreamingService::new(
+            Arc::new(OfflineHttpClient),
+            Box::new(CountingDecoder {
+                chunks: 5,
+                emitted: 0,
+            }),
+        );
+
+        // A visualizer tap overfilled the shared buffer before playback
+        let mut request = request(2_000);
+        request.ring_buffer = request.ring_buffer.with_policy(OverflowPolicy::DropNewest);
+        assert_eq!(request.ring_buffer.write(&[0.0; 2_500]), 2_000);
+        request.ring_buffer.read(&mut [0.0; 2_000]);
+
+        service
+            .run(request, CancellationToken::new())
+            .await
+            .unwrap();
+        assert_eq!(service.stats().dropped_samples, 500);
+        assert_eq!(service.stats().total_frames_buffered, 500);
+    }
+
+    #[tokio::test]
+    async fn test_seek_flushes_buffered_audio() {
+        let seeks = Arc::new(Mutex::new(Vec::new()));
+        let service = StreamingService::new(
+            Arc::new(OfflineHttpClient),
+            Box::new(FlakyDecoder {
+                inner: CountingDecoder {
+                    chunks: 50,
+                    emitted: 0,
+                },
+                fail_at: usize::MAX,
+                error: None,
+                seeks: seeks.clone(),
+            }),
+        );
+
+        let request = request(20_000);
+        let ring_buffer = request.ring_buffer.clone();
+        let cancel = CancellationToken::new();
+
+        let seek = async {
+            // Nothing consumes, so the buffer fills with chunks 0-9
+            while ring_buffer.available() < 2_000 {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+            let actual = service.seek_to(Duration::from_millis(50)).await.unwrap();
+
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            let mut buffered = vec![0.0; ring_buffer.available()];
+            ring_buffer.read(&mut buffered);
+            cancel.cancel();
+            (actual, buffered)
+        };
+        let (result, (actual, buffered)) = tokio::join!(service.run(request, cancel.clone()), seek);
+        result.unwrap();
+
+        assert_eq!(actual, Duration::from_millis(50));
+        assert_eq!(*seeks.lock().unwrap(), vec![Duration::from_millis(50)]);
+
+        // Refilled only with chunks decoded after the seek
+        assert_eq!(buffered.len(), 2_000);
+        assert!(buffered.iter().all(|&sample| sample >= 1.0));
+    }
+
+    #[tokio::test]
+    async fn test_skips_encoder_delay_and_padding() {
+        let run = |silence: Option<SilenceTrim>| async move {
+            let seeks = Arc::new(Mutex::new(Vec::new()));
+            let sink = Arc::new(CapturingSink::default());
+            let service = StreamingService::new(
+                Arc::new(OfflineHttpClient),
+                Box::new(PrimedDecoder {
+                    inner: FlakyDecoder {
+                        inner: CountingDecoder {
+                            chunks: 10,
+                            emitted: 0,
+                        },
+                        fail_at: usize::MAX,
+                        error: None,
+                        seeks: seeks.clone(),
+                    },
+                    delay_frames: 100,
+                    padding_frames: 300,
+                }),
+            )
+            .with_sink(sink.clone());
+
+            let mut request = request(10_000);
+            request.config.skip_silence = true;
+            request.silence = silence;
+            service
+                .run(request, CancellationToken::new())
+                .await
+                .unwrap();
+
+            let seeks = seeks.lock().unwrap().clone();
+            let frames: usize = sink.chunks.lock().unwrap().iter().map(|c| c.frames).sum();
+            (seeks, frames)
+        };
+
+        // Gapless trimming applies even without analyzed silence
+        let (seeks, frames) = run(None).await;
+        assert_eq!(seeks, vec![Duration::from_secs_f64(100.0 / 44_100.0)]);
+        assert_eq!(frames, 600);
+
+        // With both, the longer trim wins at each end
+        let (seeks, frames) = run(Some(SilenceTrim {
+            leading: Duration::from_secs_f64(200.0 / 44_100.0),
+            trailing: Duration::from_secs_f64(250.0 / 44_100.0),
+        }))
+        .await;
+        assert_eq!(seeks, vec![Duration::from_secs_f64(200.0 / 44_100.0)]);
+        assert_eq!(frames, 500);
+    }
+
+    fn crossfade_request() -> StreamingRequest {
+        let mut request = request(20_000);
+        request.config.buffer_frames = 10_000;
+        request.config.crossfade_duration = Some(Duration::from_secs_f64(1000.0 / 44_100.0));
+        request
+    }
+
+    /// Interleaved samples every sink chunk carried
+    fn captured(sink: &CapturingSink) -> Vec<f32> {
+        sink.chunks
+            .lock()
+            .unwrap()
+            .iter()
+            .flat_map(|chunk| chunk.samples.clone())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_crossfade_mixes_tail_into_next_track() {
+        let sink = Arc::new(CapturingSink::default());
+        let service = StreamingService::new(
+            Arc::new(OfflineHttpClient),
+            Box::new(ConstantDecoder::new([0.5, 0.0], 3000)),
+        )
+        .with_sink(sink.clone());
+        service.set_next_decoder(Box::new(ConstantDecoder::new([0.0, 0.5], 3000)));
+
+        service
+            .run(crossfade_request(), CancellationToken::new())
+            .await
+            .unwrap();
+        let first = captured(&sink);
+        assert_eq!(first.len(), 3000 * 2);
+
+        // Untouched up to the overlap
+        assert!(first[..2000 * 2].chunks(2).all(|frame| frame == [0.5, 0.0]));
+
+        // Equal-power fade: the energy of both tracks sums to that of either
+        let overlap: Vec<&[f32]> = first[2000 * 2..].chunks(2).collect();
+        for frame in &overlap {
+            let energy = frame[0] * frame[0] + frame[1] * frame[1];
+            assert!((energy - 0.25).abs() < 1e-4, "energy {}", energy);
+        }
+        assert!(overlap[0][0] > 0.49 && overlap[0][1] < 0.01);
+        assert!(overlap[999][0] < 0.01 && overlap[999][1] > 0.49);
+        assert!(overlap
+            .windows(2)
+            .all(|w| w[1][0] <= w[0][0] && w[1][1] >= w[0][1]));
+
+        // The next track continues after the mixed-in head
+        sink.chunks.lock().unwrap().clear();
+        service
+            .run(crossfade_request(), CancellationToken::new())
+            .await
+            .unwrap();
+        let second = captured(&sink);
+        assert_eq!(second.len(), 2000 * 2);
+        assert!(second.chunks(2).all(|frame| frame == [0.0, 0.5]));
+    }
+
+    #[tokio::test]
+    async fn test_crossfade_shorter_than_track_overlaps_whole_track() {
+        let sink = Arc::new(CapturingSink::default());
+        let service = StreamingService::new(
+            Arc::new(OfflineHttpClient),
+            Box::new(ConstantDecoder::new([0.5, 0.0], 400)),
+        )
+        .with_sink(sink.clone());
+        service.set_next_decoder(Box::new(ConstantDecoder::new([0.0, 0.5], 3000)));
+
+        service
+            .run(crossfade_request(), CancellationToken::new())
+            .await
+            .unwrap();
+        let first = captured(&sink);
+        assert_eq!(first.len(), 400 * 2);
+        assert!(first.chunks(2).all(|frame| {
+            let energy = frame[0] * frame[0] + frame[1] * frame[1];
+            (energy - 0.25).abs() < 1e-4
+        }));
+
+        sink.chunks.lock().unwrap().clear();
+        service
+            .run(crossfade_request(), CancellationToken::new())
+            .await
+            .unwrap();
+        assert_eq!(captured(&sink).len(), 2600 * 2);
+    }
+
+    #[tokio::test]
+    async fn test_crossfade_falls_back_to_hard_cut() {
+        let seeks = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::new(CapturingSink::default());
+        let service = StreamingService::new(
+            Arc::new(OfflineHttpClient),
+            Box::new(ConstantDecoder::new([0.5, 0.0], 3000)),
+        )
+        .with_sink(sink.clone());
+        service.set_next_decoder(Box::new(FlakyDecoder {
+            inner: CountingDecoder {
+                chunks: 10,
+                emitted: 0,
+            },
+            fail_at: 0,
+            error: Some(PlaybackError::DecodingError("corrupt frame".into())),
+            seeks: seeks.clone(),
+        }));
+
+        service
+            .run(crossfade_request(), CancellationToken::new())
+            .await
+            .unwrap();
+        let first = captured(&sink);
+        assert_eq!(first.len(), 3000 * 2);
+        assert!(first.chunks(2).all(|frame| frame == [0.5, 0.0]));
+        assert_eq!(*seeks.lock().unwrap(), vec![Duration::ZERO]);
+
+        // The next track plays from the start
+        sink.chunks.lock().unwrap().clear();
+        service
+            .run(crossfade_request(), CancellationToken::new())
+            .await
+            .unwrap();
+        assert_eq!(captured(&sink).len(), 1000 * 2);
+    }
+}
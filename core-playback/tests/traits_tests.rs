@@ -102,7 +102,7 @@ impl AudioDecoder for MockAudioDecoder {
         Ok(Some(chunk))
     }
 
-    async fn seek(&mut self, position: Duration) -> Result<()> {
+    async fn seek(&mut self, position: Duration) -> Result<Duration> {
         if !self.seek_supported {
             return Err(PlaybackError::SeekNotSupported);
         }
@@ -118,7 +118,7 @@ impl AudioDecoder for MockAudioDecoder {
         self.frames_decoded = target_frame;
         self.current_position = position;
 
-        Ok(())
+        Ok(position)
     }
 }
 
@@ -468,10 +468,11 @@ async fn test_audio_decoder_seek() {
     let mut decoder = MockAudioDecoder::new(AudioCodec::Mp3, 44100, 2, 180);
 
     // Seek to 30 seconds
-    decoder
+    let actual = decoder
         .seek(Duration::from_secs(30))
         .await
         .expect("Seek should succeed");
+    assert_eq!(actual, Duration::from_secs(30));
 
     // Decode next chunk should start from 30s position
     let chunk = decoder
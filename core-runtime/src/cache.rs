@@ -0,0 +1,447 @@
+//! # In-Memory LRU Cache
+//!
+//! A thread-safe least-recently-used cache shared by subsystems that keep hot
+//! data in memory (artwork thumbnails, decoded headers, ...).
+//!
+//! The cache is bounded by entry count, by total weight, or both. With a
+//! weigher each value reports its own size (e.g. its byte length), so a cache
+//! of images can be capped at a memory budget rather than an item count.
+//!
+//! All methods take `&self`; the cache is internally synchronized and can be
+//! shared behind an `Arc`. Values are returned as clones, so cheap-to-clone
+//! values (`Bytes`, `Arc<T>`) work best.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use core_runtime::cache::LruCache;
+//!
+//! let cache: LruCache<String, Vec<u8>> =
+//!     LruCache::weighted(1024, |_key, value: &Vec<u8>| value.len() as u64);
+//! cache.insert("cover".to_string(), vec![0; 512]);
+//! assert!(cache.get("cover").is_some());
+//! assert_eq!(cache.metrics().hits, 1);
+//! ```
+
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+type Weigher<K, V> = Arc<dyn Fn(&K, &V) -> u64 + Send + Sync>;
+
+/// Hit, miss and eviction counters plus the current size of an [`LruCache`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheMetrics {
+    /// Lookups that found a value
+    pub hits: u64,
+    /// Lookups that found nothing
+    pub misses: u64,
+    /// Entries removed to make room for new ones
+    pub evictions: u64,
+    /// Entries currently cached
+    pub entries: usize,
+    /// Total weight of the cached entries
+    pub weight: u64,
+}
+
+impl CacheMetrics {
+    /// Fraction of lookups that were hits (0.0 when there were none)
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+struct Entry<V> {
+    value: V,
+    weight: u64,
+    /// Position in the recency order; higher is more recent
+    tick: u64,
+}
+
+struct Inner<K, V> {
+    entries: HashMap<K, Entry<V>>,
+    /// Recency order: tick -> key, oldest first
+    order: BTreeMap<u64, K>,
+    next_tick: u64,
+    weight: u64,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+}
+
+/// Thread-safe LRU cache bounded by entry count and/or total weight
+pub struct LruCache<K, V> {
+    inner: Mutex<Inner<K, V>>,
+    max_entries: Option<usize>,
+    max_weight: Option<u64>,
+    weigher: Option<Weigher<K, V>>,
+}
+
+impl<K, V> LruCache<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    /// Create a cache holding at most `max_entries` values
+    pub fn new(max_entries: usize) -> Self {
+        Self::build(Some(max_entries), None, None)
+    }
+
+    /// Create a cache whose entries' total weight stays within `max_weight`
+    ///
+    /// `weigher` gives each entry's weight, e.g. its size in bytes. A value
+    /// heavier than `max_weight` on its own is never cached.
+    pub fn weighted<F>(max_weight: u64, weigher: F) -> Self
+    where
+        F: Fn(&K, &V) -> u64 + Send + Sync + 'static,
+    {
+        Self::build(None, Some(max_weight), Some(Arc::new(weigher)))
+    }
+
+    /// Additionally cap the number of entries
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = Some(max_entries);
+        self
+    }
+
+    fn build(
+        max_entries: Option<usize>,
+        max_weight: Option<u64>,
+        weigher: Option<Weigher<K, V>>,
+    ) -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                entries: HashMap::new(),
+                order: BTreeMap::new(),
+                next_tick: 0,
+                weight: 0,
+                hits: 0,
+                misses: 0,
+                evictions: 0,
+            }),
+            max_entries,
+            max_weight,
+            weigher,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner<K, V>> {
+        // Every update leaves the maps consistent before it can panic
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Look up a value, marking it most recently used
+    pub fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let mut inner = self.lock();
+        let value = inner.touch(key);
+        match value {
+            Some(_) => inner.hits += 1,
+            None => inner.misses += 1,
+        }
+        value
+    }
+
+    /// Look up a value without updating recency or metrics
+    pub fn peek<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.lock()
+            .entries
+            .get(key)
+            .map(|entry| entry.value.clone())
+    }
+
+    /// Whether `key` is cached (does not update recency or metrics)
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.lock().entries.contains_key(key)
+    }
+
+    /// Insert a value as most recently used, evicting older entries if needed
+    ///
+    /// Returns the value previously stored under `key`. A value heavier than
+    /// the weight limit is not cached.
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        let weight = self.weigh(&key, &value);
+        let mut inner = self.lock();
+        let previous = inner.remove(&key);
+
+        if self.max_weight.is_some_and(|max| weight > max) || self.max_entries == Some(0) {
+            return previous;
+        }
+
+        while inner.over_limit(weight, self.max_entries, self.max_weight) {
+            if !inner.evict_oldest() {
+                break;
+            }
+        }
+
+        inner.push(key, value, weight);
+        previous
+    }
+
+    /// Return the cached value for `key`, computing and caching it on a miss
+    ///
+    /// `compute` only runs on a miss and is called without holding the cache
+    /// lock. If another caller inserted the key in the meantime, that value
+    /// is kept and returned.
+    pub fn get_or_insert_with<F>(&self, key: K, compute: F) -> V
+    where
+        F: FnOnce() -> V,
+    {
+        if let Some(value) = self.get(&key) {
+            return value;
+        }
+
+        let value = compute();
+        if let Some(existing) = self.peek(&key) {
+            return existing;
+        }
+        self.insert(key, value.clone());
+        value
+    }
+
+    /// Remove a value
+    pub fn remove<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.lock().remove(key)
+    }
+
+    /// Remove every entry (metrics counters are kept)
+    pub fn clear(&self) {
+        let mut inner = self.lock();
+        inner.entries.clear();
+        inner.order.clear();
+        inner.weight = 0;
+    }
+
+    /// Number of cached entries
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Whether the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.lock().entries.is_empty()
+    }
+
+    /// Total weight of the cached entries (the entry count without a weigher)
+    pub fn weight(&self) -> u64 {
+        self.lock().weight
+    }
+
+    /// Current counters and size
+    pub fn metrics(&self) -> CacheMetrics {
+        let inner = self.lock();
+        CacheMetrics {
+            hits: inner.hits,
+            misses: inner.misses,
+            evictions: inner.evictions,
+            entries: inner.entries.len(),
+            weight: inner.weight,
+        }
+    }
+
+    fn weigh(&self, key: &K, value: &V) -> u64 {
+        match &self.weigher {
+            Some(weigher) => weigher(key, value),
+            None => 1,
+        }
+    }
+}
+
+impl<K, V> Inner<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    fn touch<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let tick = self.next_tick;
+        let entry = self.entries.get_mut(key)?;
+        let key = self.order.remove(&entry.tick)?;
+        entry.tick = tick;
+        let value = entry.value.clone();
+        self.order.insert(tick, key);
+        self.next_tick += 1;
+        Some(value)
+    }
+
+    fn push(&mut self, key: K, value: V, weight: u64) {
+        let tick = self.next_tick;
+        self.next_tick += 1;
+        self.order.insert(tick, key.clone());
+        self.entries.insert(
+            key,
+            Entry {
+                value,
+                weight,
+                tick,
+            },
+        );
+        self.weight += weight;
+    }
+
+    fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let entry = self.entries.remove(key)?;
+        self.order.remove(&entry.tick);
+        self.weight -= entry.weight;
+        Some(entry.value)
+    }
+
+    /// Whether adding an entry of `weight` would exceed either limit
+    fn over_limit(&self, weight: u64, max_entries: Option<usize>, max_weight: Option<u64>) -> bool {
+        max_entries.is_some_and(|max| self.entries.len() >= max)
+            || max_weight.is_some_and(|max| self.weight + weight > max)
+    }
+
+    fn evict_oldest(&mut self) -> bool {
+        let Some((_, key)) = self.order.pop_first() else {
+            return false;
+        };
+        if let Some(entry) = self.entries.remove(&key) {
+            self.weight -= entry.weight;
+            self.evictions += 1;
+        }
+        true
+    }
+}
+
+impl<K, V> fmt::Debug for LruCache<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inner = self
+            .inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        f.debug_struct("LruCache")
+            .field("entries", &inner.entries.len())
+            .field("weight", &inner.weight)
+            .field("max_entries", &self.max_entries)
+            .field("max_weight", &self.max_weight)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn evicts_least_recently_used_first() {
+        let cache = LruCache::new(3);
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        cache.insert("c", 3);
+
+        // Reading "a" makes "b" the oldest
+        assert_eq!(cache.get("a"), Some(1));
+        cache.insert("d", 4);
+
+        assert!(!cache.contains_key("b"));
+        assert!(cache.contains_key("a"));
+        assert!(cache.contains_key("c"));
+        assert!(cache.contains_key("d"));
+
+        cache.insert("e", 5);
+        assert!(!cache.contains_key("c"));
+        assert_eq!(cache.len(), 3);
+        assert_eq!(cache.metrics().evictions, 2);
+    }
+
+    #[test]
+    fn weighted_cache_evicts_until_value_fits() {
+        let cache = LruCache::weighted(100, |_key: &&str, value: &Vec<u8>| value.len() as u64);
+        cache.insert("small-1", vec![0; 30]);
+        cache.insert("small-2", vec![0; 30]);
+        cache.insert("small-3", vec![0; 30]);
+        assert_eq!(cache.weight(), 90);
+
+        // Needs 60 bytes: the two oldest entries go
+        cache.insert("large", vec![0; 60]);
+        assert!(!cache.contains_key("small-1"));
+        assert!(!cache.contains_key("small-2"));
+        assert!(cache.contains_key("small-3"));
+        assert_eq!(cache.weight(), 90);
+
+        // Too heavy to ever fit: not cached, nothing evicted
+        cache.insert("huge", vec![0; 101]);
+        assert!(!cache.contains_key("huge"));
+        assert_eq!(cache.len(), 2);
+
+        // Replacing a value updates the weight
+        cache.insert("large", vec![0; 10]);
+        assert_eq!(cache.weight(), 40);
+    }
+
+    #[test]
+    fn entry_limit_applies_to_weighted_cache() {
+        let cache = LruCache::weighted(1_000, |_key: &u32, value: &u64| *value).with_max_entries(2);
+        cache.insert(1, 10);
+        cache.insert(2, 10);
+        cache.insert(3, 10);
+        assert_eq!(cache.len(), 2);
+        assert!(!cache.contains_key(&1));
+        assert_eq!(cache.weight(), 20);
+    }
+
+    #[test]
+    fn get_or_insert_with_only_computes_on_miss() {
+        let cache = LruCache::new(10);
+        let calls = AtomicUsize::new(0);
+        let compute = || {
+            calls.fetch_add(1, Ordering::SeqCst);
+            "decoded".to_string()
+        };
+
+        assert_eq!(cache.get_or_insert_with("header", compute), "decoded");
+        assert_eq!(cache.get_or_insert_with("header", compute), "decoded");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let metrics = cache.metrics();
+        assert_eq!(metrics.misses, 1);
+        assert_eq!(metrics.hits, 1);
+        assert_eq!(metrics.hit_rate(), 0.5);
+    }
+
+    #[test]
+    fn remove_and_clear_reset_size() {
+        let cache = LruCache::new(4);
+        cache.insert(1, "one");
+        cache.insert(2, "two");
+        assert_eq!(cache.remove(&1), Some("one"));
+        assert_eq!(cache.len(), 1);
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(cache.weight(), 0);
+        assert_eq!(cache.peek(&2), None);
+    }
+}
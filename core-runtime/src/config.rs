@@ -219,6 +219,7 @@ pub struct FeatureFlags {
 ///     musicbrainz_user_agent: Some("MyMusicApp/1.0 (contact@example.com)".to_string()),
 ///     lastfm_api_key: Some("your_lastfm_api_key".to_string()),
 ///     rate_limit_delay_ms: 1000, // 1 request per second
+///     max_concurrent_requests: 1,
 /// };
 /// ```
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -245,6 +246,18 @@ pub struct MetadataApiConfig {
     /// MusicBrainz recommends 1 request/second for anonymous clients.
     /// Last.fm rate limits are higher but we apply the same for safety.
     pub rate_limit_delay_ms: u64,
+
+    /// Maximum number of requests each provider client may have in flight
+    ///
+    /// Default: 1
+    /// The rate limit only spaces out request starts; this cap keeps slow
+    /// responses from piling up into a burst of parallel connections.
+    #[serde(default = "default_max_concurrent_requests")]
+    pub max_concurrent_requests: usize,
+}
+
+fn default_max_concurrent_requests() -> usize {
+    1
 }
 
 impl MetadataApiConfig {
@@ -254,6 +267,7 @@ impl MetadataApiConfig {
             musicbrainz_user_agent: None,
             lastfm_api_key: None,
             rate_limit_delay_ms: 1000,
+            max_concurrent_requests: default_max_concurrent_requests(),
         }
     }
 
@@ -275,6 +289,12 @@ impl MetadataApiConfig {
         self
     }
 
+    /// Sets the maximum number of in-flight requests per provider client
+    pub fn with_max_concurrent_requests(mut self, max_concurrent: usize) -> Self {
+        self.max_concurrent_requests = max_concurrent;
+        self
+    }
+
     /// Validates the configuration
     pub fn validate(&self) -> Result<()> {
         // Validate MusicBrainz user agent format if provided
@@ -305,6 +325,12 @@ impl MetadataApiConfig {
             ));
         }
 
+        if self.max_concurrent_requests == 0 {
+            return Err(Error::Config(
+                "Max concurrent requests must be at least 1".to_string(),
+            ));
+        }
+
         Ok(())
     }
 
@@ -1003,6 +1029,10 @@ mod tests {
             Ok(())
         }
 
+        async fn delete(&mut self, _key: &str) -> std::result::Result<(), BridgeError> {
+            Ok(())
+        }
+
         async fn commit(self: Box<Self>) -> std::result::Result<(), BridgeError> {
             Ok(())
         }
@@ -112,6 +112,8 @@
 //! ### Sync Events
 //! - `Started`: Sync job initiated
 //! - `Progress`: Incremental progress update
+//! - `ItemStarted`: A file started processing (opt-in)
+//! - `ItemFinished`: A file finished processing (opt-in)
 //! - `Completed`: Sync finished successfully
 //! - `Failed`: Sync encountered an error
 //! - `Cancelled`: Sync was cancelled by user
@@ -131,6 +133,7 @@
 //! - `Stopped`: Playback stopped
 //! - `Completed`: Track finished playing
 //! - `PositionChanged`: Playback position updated
+//! - `DownloadProgress`: Offline cache download advanced
 //! - `Error`: Playback error occurred
 //!
 //! ## Error Handling
@@ -352,6 +355,32 @@ pub enum SyncEvent {
         /// Current phase (e.g., "Listing files", "Extracting metadata").
         phase: String,
     },
+    /// A file started processing.
+    ///
+    /// Only emitted when per-item events are enabled in the sync config.
+    ItemStarted {
+        /// The sync job ID.
+        job_id: String,
+        /// Name of the file being processed.
+        file_name: String,
+        /// The provider's ID for the file.
+        remote_file_id: String,
+    },
+    /// A file finished processing, whether it succeeded, was skipped or failed.
+    ///
+    /// Only emitted when per-item events are enabled in the sync config.
+    ItemFinished {
+        /// The sync job ID.
+        job_id: String,
+        /// Name of the processed file.
+        file_name: String,
+        /// The provider's ID for the file.
+        remote_file_id: String,
+        /// Whether the file added a new track.
+        added: bool,
+        /// Bytes downloaded for the file.
+        bytes: u64,
+    },
     /// Sync finished successfully.
     Completed {
         /// The sync job ID.
@@ -392,6 +421,8 @@ impl SyncEvent {
         match self {
             SyncEvent::Started { .. } => "Sync started",
             SyncEvent::Progress { .. } => "Sync in progress",
+            SyncEvent::ItemStarted { .. } => "Sync item started",
+            SyncEvent::ItemFinished { .. } => "Sync item finished",
             SyncEvent::Completed { .. } => "Sync completed successfully",
             SyncEvent::Failed { .. } => "Sync failed",
             SyncEvent::Cancelled { .. } => "Sync cancelled",
@@ -522,6 +553,15 @@ pub enum PlaybackEvent {
         /// Track duration (milliseconds).
         duration_ms: u64,
     },
+    /// A track download to the offline cache advanced.
+    DownloadProgress {
+        /// The track ID being cached.
+        track_id: String,
+        /// Bytes downloaded so far.
+        downloaded_bytes: u64,
+        /// Total file size in bytes (0 if unknown).
+        total_bytes: u64,
+    },
     /// Playback error occurred.
     Error {
         /// The track ID if available.
@@ -542,6 +582,7 @@ impl PlaybackEvent {
             PlaybackEvent::Stopped { .. } => "Playback stopped",
             PlaybackEvent::Completed { .. } => "Track completed",
             PlaybackEvent::PositionChanged { .. } => "Playback position changed",
+            PlaybackEvent::DownloadProgress { .. } => "Track download progressed",
             PlaybackEvent::Error { .. } => "Playback error",
         }
     }
@@ -4,6 +4,7 @@
 //! - Logging and tracing infrastructure
 //! - Configuration management
 //! - Event bus system
+//! - In-memory LRU cache
 //! - Task scheduling primitives
 //!
 //! ## Overview
@@ -12,6 +13,7 @@
 //! It establishes the async runtime patterns, logging conventions, and event
 //! broadcasting mechanisms used throughout the system.
 
+pub mod cache;
 pub mod config;
 pub mod error;
 pub mod events;
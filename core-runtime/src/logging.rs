@@ -40,7 +40,9 @@
 //! Provide a custom `LoggerSink` to mirror log events into a host-specific
 //! pipeline (e.g., `os_log`/`Logcat`). The sink receives structured
 //! [`LogEntry`](bridge_traits::time::LogEntry) instances with the original
-//! message plus any fields emitted on the event.
+//! message plus any fields emitted on the event or recorded on its enclosing
+//! spans (e.g. `profile_id`, `job_id`). Events below the sink's `min_level`
+//! are dropped before they are formatted.
 //!
 //! ```ignore
 //! use bridge_traits::time::{ConsoleLogger, LoggerSink};
@@ -76,6 +78,7 @@ use std::io;
 use std::sync::Arc;
 
 use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
 use tracing::{Event, Subscriber};
 use tracing_subscriber::{
     filter::EnvFilter,
@@ -406,6 +409,9 @@ where
 }
 
 /// Layer that forwards events to a `LoggerSink` implementation.
+///
+/// Span fields are kept in the span's extensions so every event inside the
+/// span carries them.
 struct LoggerSinkLayer {
     sink: Option<Arc<dyn LoggerSink>>,
 }
@@ -420,6 +426,30 @@ impl<S> Layer<S> for LoggerSinkLayer
 where
     S: Subscriber + for<'a> LookupSpan<'a>,
 {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = self.sink.as_ref().and_then(|_| ctx.span(id)) else {
+            return;
+        };
+
+        let mut visitor = SinkVisitor::default();
+        attrs.record(&mut visitor);
+        span.extensions_mut().insert(SpanFields(visitor.fields));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = self.sink.as_ref().and_then(|_| ctx.span(id)) else {
+            return;
+        };
+
+        let mut visitor = SinkVisitor::default();
+        values.record(&mut visitor);
+        let mut extensions = span.extensions_mut();
+        match extensions.get_mut::<SpanFields>() {
+            Some(fields) => fields.0.extend(visitor.fields),
+            None => extensions.insert(SpanFields(visitor.fields)),
+        }
+    }
+
     fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
         let Some(sink) = self.sink.as_ref() else {
             return;
@@ -441,6 +471,15 @@ where
 
         let mut entry = LogEntry::new(level, metadata.target(), message);
 
+        // Outer spans first, so inner spans and the event itself win
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope.from_root() {
+                if let Some(fields) = span.extensions().get::<SpanFields>() {
+                    entry.fields.extend(fields.0.clone());
+                }
+            }
+        }
+
         for (key, value) in visitor.fields {
             entry = entry.with_field(key, value);
         }
@@ -483,6 +522,9 @@ where
     }
 }
 
+/// Fields recorded on a span
+struct SpanFields(HashMap<String, String>);
+
 #[derive(Default)]
 struct SinkVisitor {
     message: Option<String>,
@@ -695,9 +737,71 @@ mod tests {
         assert_eq!(entry.fields.get("user"), Some(&"alice".to_string()));
     }
 
-    #[derive(Default)]
+    #[test]
+    fn test_logger_sink_layer_captures_span_fields() {
+        let sink = Arc::new(TestLoggerSink::default());
+        let trait_sink: Arc<dyn LoggerSink> = sink.clone();
+        let layer = LoggerSinkLayer::new(Some(trait_sink));
+        let subscriber = tracing_subscriber::registry().with(layer);
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let profile =
+            tracing::info_span!("profile", profile_id = "p1", job_id = tracing::field::Empty);
+        let job = profile.in_scope(|| tracing::info_span!("job", job_id = 7, stage = "scan"));
+        profile.record("job_id", 1);
+        job.in_scope(|| tracing::info!(stage = "index", "indexed"));
+        profile.in_scope(|| tracing::info!("between jobs"));
+
+        let entries = sink.entries.lock().unwrap();
+        assert_eq!(entries.len(), 2);
+
+        // Inner span fields override outer ones, event fields override both
+        let fields = &entries[0].fields;
+        assert_eq!(fields.get("profile_id"), Some(&"p1".to_string()));
+        assert_eq!(fields.get("job_id"), Some(&"7".to_string()));
+        assert_eq!(fields.get("stage"), Some(&"index".to_string()));
+
+        // Fields recorded after the span was created are picked up too
+        let fields = &entries[1].fields;
+        assert_eq!(fields.get("profile_id"), Some(&"p1".to_string()));
+        assert_eq!(fields.get("job_id"), Some(&"1".to_string()));
+        assert_eq!(fields.get("stage"), None);
+    }
+
+    #[test]
+    fn test_logger_sink_layer_drops_entries_below_min_level() {
+        let sink = Arc::new(TestLoggerSink {
+            min_level: LogLevel::Warn,
+            ..Default::default()
+        });
+        let trait_sink: Arc<dyn LoggerSink> = sink.clone();
+        let layer = LoggerSinkLayer::new(Some(trait_sink));
+        let subscriber = tracing_subscriber::registry().with(layer);
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        tracing::trace!("trace");
+        tracing::debug!("debug");
+        tracing::info!("info");
+        tracing::warn!("warn");
+        tracing::error!("error");
+
+        let entries = sink.entries.lock().unwrap();
+        let levels: Vec<LogLevel> = entries.iter().map(|entry| entry.level).collect();
+        assert_eq!(levels, vec![LogLevel::Warn, LogLevel::Error]);
+    }
+
     struct TestLoggerSink {
         entries: Mutex<Vec<LogEntry>>,
+        min_level: LogLevel,
+    }
+
+    impl Default for TestLoggerSink {
+        fn default() -> Self {
+            Self {
+                entries: Mutex::new(Vec::new()),
+                min_level: LogLevel::Trace,
+            }
+        }
     }
 
     #[async_trait]
@@ -709,7 +813,7 @@ mod tests {
         }
 
         fn min_level(&self) -> LogLevel {
-            LogLevel::Trace
+            self.min_level
         }
     }
 }
@@ -358,6 +358,12 @@ impl JsMetadataApiConfig {
         self.inner.rate_limit_delay_ms = delay_ms;
     }
 
+    /// Set maximum in-flight requests per provider client
+    #[wasm_bindgen(js_name = setMaxConcurrentRequests)]
+    pub fn set_max_concurrent_requests(&mut self, max_concurrent: usize) {
+        self.inner.max_concurrent_requests = max_concurrent;
+    }
+
     /// Validate the configuration
     pub fn validate(&self) -> Result<(), JsValue> {
         self.inner.validate().map_err(to_js_error)
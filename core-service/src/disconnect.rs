@@ -0,0 +1,318 @@
+//! Unlinking a storage provider and removing everything synced from it.
+
+use std::sync::Arc;
+
+use bridge_traits::database::QueryValue;
+use core_auth::{ProfileId, ProviderKind};
+use core_library::models::TrackId;
+use core_library::repositories::{SqliteTrackRepository, TrackDeletion, TrackRepository};
+use core_playback::PlaybackError;
+use core_sync::{SqliteSyncJobRepository, SyncError, SyncJobId, SyncJobRepository};
+use tracing::{info, warn};
+
+use crate::{CoreService, Result};
+
+/// What [`CoreService::disconnect_provider`] removed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProviderDisconnect {
+    /// Sync job that was running for the profile and got cancelled
+    pub cancelled_job: Option<SyncJobId>,
+    /// Tracks deleted, with the albums, artists and artwork they orphaned
+    pub removed: TrackDeletion,
+    /// Offline cache entries evicted for the deleted tracks
+    pub cache_entries_evicted: usize,
+    /// Stored sync cursors cleared for the profile and provider
+    pub cursors_cleared: u64,
+}
+
+impl CoreService {
+    /// Unlink `provider` from `profile_id` and remove its data
+    ///
+    /// Steps, in order:
+    /// 1. Cancel the profile's running sync, if a coordinator is attached
+    /// 2. Evict the provider's tracks from the offline cache, if attached
+    /// 3. Delete the tracks and whatever albums, artists and artwork they
+    ///    leave orphaned, in one transaction
+    /// 4. Clear the profile's sync cursors for the provider
+    /// 5. Sign the profile out, deleting its tokens
+    ///
+    /// Tokens go last so a disconnect that fails halfway can simply be run
+    /// again; every step is a no-op once its data is gone.
+    pub async fn disconnect_provider(
+        &self,
+        profile_id: ProfileId,
+        provider: ProviderKind,
+    ) -> Result<ProviderDisconnect> {
+        info!(%profile_id, %provider, "Disconnecting provider");
+        let db = Arc::clone(&self.deps.database);
+        let mut summary = ProviderDisconnect::default();
+
+        if let Some(coordinator) = &self.sync_coordinator {
+            if let Some(job_id) = coordinator.active_job_id(profile_id).await {
+                match coordinator.cancel_sync(job_id).await {
+                    // The job may have finished in the meantime
+                    Ok(()) | Err(SyncError::JobNotFound { .. }) => {
+                        summary.cancelled_job = Some(job_id)
+                    }
+                    Err(e) => return Err(e.into()),
+                }
+            }
+        }
+
+        // The sync coordinator stores tracks under the provider's display name,
+        // and the provider row records which profile linked it
+        let rows = db
+            .query(
+                "SELECT t.id FROM tracks t \
+                 JOIN providers p ON p.id = t.provider_id \
+                 WHERE t.provider_id = ? AND p.profile_id = ?",
+                &[
+                    QueryValue::Text(provider.to_string()),
+                    QueryValue::Text(profile_id.to_string()),
+                ],
+            )
+            .await
+            .map_err(core_library::LibraryError::from)?;
+        let track_ids: Vec<String> = rows
+            .iter()
+            .filter_map(|row| row.get("id").and_then(|value| value.as_string()))
+            .collect();
+
+        if let Some(cache) = &self.offline_cache {
+            for id in &track_ids {
+                let Ok(track_id) = TrackId::from_string(id) else {
+                    continue;
+                };
+                match cache.delete_cached_track(&track_id).await {
+                    Ok(()) => summary.cache_entries_evicted += 1,
+                    Err(PlaybackError::NotCached(_)) => {}
+                    Err(e) => warn!("Failed to evict cached track {}: {}", id, e),
+                }
+            }
+        }
+
+        summary.removed = SqliteTrackRepository::new(Arc::clone(&db))
+            .delete_many(&track_ids)
+            .await?;
+
+        summary.cursors_cleared = SqliteSyncJobRepository::new()
+            .clear_cursors(db.as_ref(), profile_id, provider)
+            .await?;
+
+        self.auth_manager.sign_out(profile_id).await?;
+
+        info!(
+            "Disconnected {}: {} tracks, {} albums, {} artists, {} artworks removed",
+            provider,
+            summary.removed.tracks,
+            summary.removed.album_ids.len(),
+            summary.removed.artist_ids.len(),
+            summary.removed.artwork_ids.len()
+        );
+        Ok(summary)
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32"), feature = "desktop-shims"))]
+mod tests {
+    use super::*;
+    use bridge_desktop::{SqliteSettingsStore, TokioFileSystem};
+    use bridge_traits::database::DatabaseAdapter;
+    use bridge_traits::error::Result as BridgeResult;
+    use bridge_traits::storage::{RemoteFile, StorageProvider};
+    use bridge_traits::test_util::{InMemorySecureStore, MockHttpClient};
+    use bridge_traits::BridgeError;
+    use bytes::Bytes;
+    use core_auth::{OAuthTokens, TokenStore};
+    use core_library::adapters::sqlite_native::SqliteAdapter;
+    use core_library::create_test_pool;
+    use core_library::models::Track;
+    use core_playback::cache::{
+        CacheConfig, CacheMetadataRepository, CacheStatus, CachedTrack, OfflineCacheManager,
+        SqliteCacheMetadataRepository,
+    };
+    use core_runtime::events::{AuthEvent, CoreEvent};
+
+    use crate::CoreDependencies;
+
+    /// Provider the cache never needs to download from in these tests
+    struct NoDownloads;
+
+    #[async_trait::async_trait]
+    impl StorageProvider for NoDownloads {
+        async fn list_media(
+            &self,
+            _cursor: Option<String>,
+        ) -> BridgeResult<(Vec<RemoteFile>, Option<String>)> {
+            Ok((Vec::new(), None))
+        }
+
+        async fn get_metadata(&self, _file_id: &str) -> BridgeResult<RemoteFile> {
+            Err(BridgeError::NotAvailable("get_metadata".to_string()))
+        }
+
+        async fn download(&self, _file_id: &str, _range: Option<&str>) -> BridgeResult<Bytes> {
+            Err(BridgeError::NotAvailable("download".to_string()))
+        }
+
+        async fn get_changes(
+            &self,
+            _cursor: Option<String>,
+        ) -> BridgeResult<(Vec<RemoteFile>, Option<String>)> {
+            Ok((Vec::new(), None))
+        }
+    }
+
+    async fn provider_track_ids(db: &dyn DatabaseAdapter, provider: ProviderKind) -> Vec<String> {
+        db.query(
+            "SELECT id FROM tracks WHERE provider_id = ?",
+            &[QueryValue::Text(provider.to_string())],
+        )
+        .await
+        .unwrap()
+        .iter()
+        .filter_map(|row| row.get("id").and_then(|value| value.as_string()))
+        .collect()
+    }
+
+    #[core_async::test]
+    async fn test_disconnect_removes_only_that_providers_data() {
+        let db: Arc<dyn DatabaseAdapter> =
+            Arc::new(SqliteAdapter::from_pool(create_test_pool().await.unwrap()));
+        let drive_profile = ProfileId::new();
+        let onedrive_profile = ProfileId::new();
+        for (id, kind, profile_id) in [
+            ("Google Drive", "GoogleDrive", drive_profile),
+            ("OneDrive", "OneDrive", onedrive_profile),
+        ] {
+            db.execute(
+                "INSERT INTO providers (id, type, display_name, profile_id, created_at)
+                 VALUES (?, ?, ?, ?, 0)",
+                &[
+                    QueryValue::Text(id.to_string()),
+                    QueryValue::Text(kind.to_string()),
+                    QueryValue::Text(id.to_string()),
+                    QueryValue::Text(profile_id.to_string()),
+                ],
+            )
+            .await
+            .unwrap();
+        }
+
+        let track_repo = Arc::new(SqliteTrackRepository::new(Arc::clone(&db)));
+        let cache_repo = SqliteCacheMetadataRepository::new(Arc::clone(&db));
+        for (provider, file_id) in [
+            (ProviderKind::GoogleDrive, "drive-1"),
+            (ProviderKind::GoogleDrive, "drive-2"),
+            (ProviderKind::OneDrive, "onedrive-1"),
+        ] {
+            let track = Track::new(
+                format!("Song {}", file_id),
+                provider.to_string(),
+                file_id.to_string(),
+                180_000,
+                1,
+            );
+            track_repo.insert(&track).await.unwrap();
+
+            let track_id = TrackId::from_string(&track.id).unwrap();
+            let mut cached = CachedTrack::new(track_id, format!("{}.cache", track.id), 1024);
+            cached.status = CacheStatus::Cached;
+            cache_repo.insert(&cached).await.unwrap();
+        }
+
+        let root = std::env::temp_dir().join(format!("disconnect-{}", TrackId::new()));
+        let fs = Arc::new(TokioFileSystem::with_directories(
+            root.join("cache"),
+            root.join("data"),
+        ));
+        let http_client = Arc::new(MockHttpClient::new());
+        let secure_store = Arc::new(InMemorySecureStore::new());
+
+        let cache = Arc::new(OfflineCacheManager::new(
+            CacheConfig::default().with_encryption(false),
+            Arc::clone(&db),
+            track_repo,
+            fs.clone(),
+            http_client.clone(),
+            Arc::new(NoDownloads),
+        ));
+        cache.initialize().await.unwrap();
+
+        let tokens = TokenStore::new(secure_store.clone());
+        for profile_id in [drive_profile, onedrive_profile] {
+            tokens
+                .store_tokens(
+                    profile_id,
+                    &OAuthTokens::new("access".to_string(), Some("refresh".to_string()), 3600),
+                )
+                .await
+                .unwrap();
+        }
+
+        let service = CoreService::new(CoreDependencies::new(
+            http_client,
+            fs,
+            Arc::clone(&db),
+            secure_store,
+            Arc::new(SqliteSettingsStore::in_memory().await.unwrap()),
+        ))
+        .with_offline_cache(Arc::clone(&cache));
+        let mut events = service.event_bus().subscribe();
+
+        // A profile that never linked Google Drive removes nothing of it
+        let unrelated = service
+            .disconnect_provider(ProfileId::new(), ProviderKind::GoogleDrive)
+            .await
+            .unwrap();
+        assert_eq!(unrelated.removed.tracks, 0);
+        assert_eq!(
+            provider_track_ids(db.as_ref(), ProviderKind::GoogleDrive)
+                .await
+                .len(),
+            2
+        );
+        let _ = events.try_recv();
+
+        let summary = service
+            .disconnect_provider(drive_profile, ProviderKind::GoogleDrive)
+            .await
+            .unwrap();
+        assert_eq!(summary.removed.tracks, 2);
+        assert_eq!(summary.cache_entries_evicted, 2);
+        assert_eq!(summary.cancelled_job, None);
+
+        assert!(provider_track_ids(db.as_ref(), ProviderKind::GoogleDrive)
+            .await
+            .is_empty());
+        let onedrive_tracks = provider_track_ids(db.as_ref(), ProviderKind::OneDrive).await;
+        assert_eq!(onedrive_tracks.len(), 1);
+
+        let cached: Vec<String> = cache
+            .list_cached_tracks()
+            .await
+            .unwrap()
+            .iter()
+            .map(|cached| cached.track_id.to_string())
+            .collect();
+        assert_eq!(cached, onedrive_tracks);
+
+        assert!(!tokens.has_tokens(drive_profile).await.unwrap());
+        assert!(tokens.has_tokens(onedrive_profile).await.unwrap());
+        match events.try_recv() {
+            Ok(CoreEvent::Auth(AuthEvent::SignedOut { profile_id })) => {
+                assert_eq!(profile_id, drive_profile.to_string())
+            }
+            other => panic!("expected a sign-out event, got {:?}", other),
+        }
+
+        // Running it again finds nothing left to remove
+        let again = service
+            .disconnect_provider(drive_profile, ProviderKind::GoogleDrive)
+            .await
+            .unwrap();
+        assert_eq!(again, ProviderDisconnect::default());
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
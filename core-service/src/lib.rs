@@ -6,8 +6,10 @@
 //! `bridge-desktop`), whereas WebAssembly builds enable the `wasm` feature and
 //! rely on the adapters from `bridge-wasm`.
 
+pub mod disconnect;
 pub mod error;
 
+pub use disconnect::ProviderDisconnect;
 pub use error::{CoreError, Result};
 
 use std::sync::Arc;
@@ -17,6 +19,10 @@ use bridge_traits::{
     http::HttpClient,
     storage::{FileSystemAccess, SecureStore, SettingsStore},
 };
+use core_auth::AuthManager;
+use core_playback::cache::OfflineCacheManager;
+use core_runtime::events::EventBus;
+use core_sync::SyncCoordinator;
 
 #[cfg(feature = "wasm")]
 pub use bridge_wasm::WasmBridgeConfig;
@@ -68,20 +74,60 @@ impl From<WasmBridgeSet> for CoreDependencies {
 #[derive(Clone)]
 pub struct CoreService {
     deps: Arc<CoreDependencies>,
+    event_bus: EventBus,
+    auth_manager: Arc<AuthManager>,
+    sync_coordinator: Option<Arc<SyncCoordinator>>,
+    offline_cache: Option<Arc<OfflineCacheManager>>,
 }
 
 impl CoreService {
     /// Create a new service from the provided dependencies.
+    ///
+    /// The service starts with its own auth manager, publishing on
+    /// [`CoreService::event_bus`].
     pub fn new(deps: CoreDependencies) -> Self {
+        let event_bus = EventBus::default();
+        let auth_manager = Arc::new(AuthManager::new(
+            Arc::clone(&deps.secure_store),
+            event_bus.clone(),
+            Arc::clone(&deps.http_client),
+        ));
         Self {
             deps: Arc::new(deps),
+            event_bus,
+            auth_manager,
+            sync_coordinator: None,
+            offline_cache: None,
         }
     }
 
+    /// Share the host's auth manager, so sign-outs clear its current session.
+    pub fn with_auth_manager(mut self, auth_manager: Arc<AuthManager>) -> Self {
+        self.auth_manager = auth_manager;
+        self
+    }
+
+    /// Share the host's sync coordinator, so its running syncs can be cancelled.
+    pub fn with_sync_coordinator(mut self, sync_coordinator: Arc<SyncCoordinator>) -> Self {
+        self.sync_coordinator = Some(sync_coordinator);
+        self
+    }
+
+    /// Share the host's offline cache, so removed tracks are evicted from it.
+    pub fn with_offline_cache(mut self, offline_cache: Arc<OfflineCacheManager>) -> Self {
+        self.offline_cache = Some(offline_cache);
+        self
+    }
+
     /// Access the bridge dependencies being used by the service.
     pub fn dependencies(&self) -> Arc<CoreDependencies> {
         Arc::clone(&self.deps)
     }
+
+    /// Event bus the service's own auth manager publishes on.
+    pub fn event_bus(&self) -> &EventBus {
+        &self.event_bus
+    }
 }
 
 /// Convenience bootstrapper for WebAssembly hosts.
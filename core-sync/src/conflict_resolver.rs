@@ -47,6 +47,7 @@
 use crate::error::{Result, SyncError};
 use bridge_traits::database::{DatabaseAdapter, QueryValue};
 use core_library::models::{Track, TrackId};
+use core_library::text::normalize_search_text;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::{debug, info, instrument, warn};
@@ -309,14 +310,14 @@ impl ConflictResolver {
             UPDATE tracks 
             SET provider_file_id = ?, 
                 title = ?,
-                normalized_title = LOWER(TRIM(?)),
+                normalized_title = ?,
                 updated_at = ?
             WHERE id = ?
             "#,
                 &[
                     QueryValue::Text(new_provider_file_id.to_string()),
                     QueryValue::Text(new_name.to_string()),
-                    QueryValue::Text(new_name.to_string()),
+                    QueryValue::Text(normalize_search_text(new_name)),
                     QueryValue::Integer(now),
                     QueryValue::Text(track_id.to_string()),
                 ],
@@ -534,9 +535,10 @@ impl ConflictResolver {
             match key.as_str() {
                 "title" => {
                     updates.push("title = ?");
-                    updates.push("normalized_title = LOWER(TRIM(?))");
-                    values.push(QueryValue::Text(value.clone()));
+                    updates.push("normalized_title = ?");
+                    let normalized = normalize_search_text(&value);
                     values.push(QueryValue::Text(value));
+                    values.push(QueryValue::Text(normalized));
                 }
                 "duration_ms" => {
                     updates.push("duration_ms = ?");
@@ -19,14 +19,14 @@
 //!
 //! ### Full Sync
 //! 1. Acquire valid access token from `AuthManager`
-//! 2. List all files from provider (paginated)
-//! 3. Filter for audio files (MIME type, extensions)
-//! 4. Enqueue work items to `ScanQueue`
+//! 2. List files from provider one page at a time
+//! 3. Filter the page for audio files (MIME type, extensions)
+//! 4. Enqueue the page's work items to `ScanQueue`
 //! 5. Process queue concurrently with throttling
 //! 6. Download and extract metadata for each file
-//! 7. Resolve conflicts (duplicates, renames)
-//! 8. Persist tracks to library database
-//! 9. Update provider sync cursor
+//! 7. Save the cursor for the next page so an interrupted job can resume
+//! 8. Resolve conflicts (duplicates, renames)
+//! 9. Persist tracks to library database
 //! 10. Emit completion event
 //!
 //! ### Incremental Sync
@@ -62,16 +62,18 @@ use crate::{
     conflict_resolution_orchestrator::{ConflictResolutionOrchestrator, ConflictResolutionStats},
     conflict_resolver::{ConflictPolicy, ConflictResolver},
     job::{SyncJob, SyncJobId, SyncJobStats, SyncType},
-    metadata_processor::{MetadataProcessor, ProcessorConfig},
+    metadata_processor::{IdStrategy, MetadataProcessor, ProcessorConfig},
+    non_music::NonMusicFilter,
     repository::{SqliteSyncJobRepository, SyncJobRepository},
-    scan_queue::{ScanQueue, WorkItem},
+    scan_queue::{ScanQueue, WorkItem, WorkItemStatus},
     Result, SyncError,
 };
 use bridge_traits::database::DatabaseAdapter;
 use bridge_traits::{
-    network::{NetworkMonitor, NetworkStatus, NetworkType},
+    network::{NetworkInfo, NetworkMonitor, NetworkStatus, NetworkType},
     storage::{FileSystemAccess, RemoteFile, StorageProvider},
 };
+use core_async::future::{race2, race_with_cancel, Either};
 use core_async::sync::{CancellationToken, Mutex, RwLock};
 use core_async::time::timeout;
 use core_auth::{AuthManager, ProfileId, ProviderKind};
@@ -80,18 +82,110 @@ use core_library::repositories::{
     SqliteArtistRepository, SqliteArtworkRepository, SqliteTrackRepository, TrackRepository,
 };
 use core_metadata::artwork::ArtworkService;
+use core_metadata::error::MetadataError;
 use core_runtime::events::{CoreEvent, EventBus, SyncEvent};
+use futures::{future, stream::FuturesUnordered, StreamExt};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 use tracing::{debug, error, info, instrument, warn};
 
+/// Age after which a leftover temporary download is considered orphaned
+const STALE_TEMP_FILE_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// Byte-identical files discovered in the same sync
+///
+/// Files are grouped by provider MD5 checksum and size. Only `primary` is
+/// downloaded and turned into a track; `duplicates` are reported and skipped.
+/// This is separate from [`crate::conflict_resolver::DuplicateSet`], which
+/// groups tracks already in the library by content hash.
+#[derive(Debug, Clone)]
+pub struct DiscoveredDuplicateSet {
+    /// Provider MD5 checksum shared by every file in the set
+    pub md5_checksum: String,
+
+    /// File size in bytes
+    pub size: u64,
+
+    /// File that is processed
+    pub primary: RemoteFile,
+
+    /// Files with the same content that are not processed
+    pub duplicates: Vec<RemoteFile>,
+}
+
+/// Split `files` into the files to process and sets of in-sync duplicates
+///
+/// The first file seen with a given checksum and size is kept, so the
+/// provider's listing order decides which copy becomes the track. Files
+/// without a checksum or size are always kept.
+pub fn group_duplicates(files: Vec<RemoteFile>) -> (Vec<RemoteFile>, Vec<DiscoveredDuplicateSet>) {
+    let mut unique = Vec::with_capacity(files.len());
+    let mut sets: Vec<DiscoveredDuplicateSet> = Vec::new();
+    let mut seen: HashMap<(String, u64), usize> = HashMap::new();
+
+    for file in files {
+        let Some(key) = content_key(&file) else {
+            unique.push(file);
+            continue;
+        };
+
+        match seen.get(&key) {
+            Some(&index) => {
+                let primary = &unique[index];
+                match sets.iter_mut().find(|set| set.primary.id == primary.id) {
+                    Some(set) => set.duplicates.push(file),
+                    None => sets.push(DiscoveredDuplicateSet {
+                        md5_checksum: key.0,
+                        size: key.1,
+                        primary: primary.clone(),
+                        duplicates: vec![file],
+                    }),
+                }
+            }
+            None => {
+                seen.insert(key, unique.len());
+                unique.push(file);
+            }
+        }
+    }
+
+    (unique, sets)
+}
+
+/// Checksum and size identifying a file's content, if the provider reports both
+fn content_key(file: &RemoteFile) -> Option<(String, u64)> {
+    match (&file.md5_checksum, file.size) {
+        (Some(md5), Some(size)) if !md5.is_empty() => Some((md5.to_lowercase(), size)),
+        _ => None,
+    }
+}
+
+/// Why `info` doesn't satisfy `SyncConfig::wifi_only`, if it doesn't
+fn wifi_only_violation(info: &NetworkInfo) -> Option<&'static str> {
+    if info.status != NetworkStatus::Connected {
+        Some("Network not available")
+    } else if !matches!(info.network_type, Some(NetworkType::WiFi)) {
+        Some("WiFi-only mode enabled but not connected to WiFi")
+    } else if info.is_metered {
+        Some("WiFi-only mode enabled but network is metered")
+    } else {
+        None
+    }
+}
+
 /// Sync coordinator configuration
 #[derive(Debug, Clone)]
 pub struct SyncConfig {
     /// Maximum concurrent file processing operations
     pub max_concurrent_downloads: usize,
 
+    /// Downlink estimate (Mbps) below which download concurrency is scaled down
+    ///
+    /// Concurrency shrinks in proportion to the estimate, never below one.
+    /// `None` (the default) always uses `max_concurrent_downloads`.
+    pub low_bandwidth_threshold_mbps: Option<f64>,
+
     /// Timeout for entire sync operation (seconds)
     pub sync_timeout_secs: u64,
 
@@ -101,6 +195,19 @@ pub struct SyncConfig {
     /// Whether to sync only on unmetered networks (WiFi)
     pub wifi_only: bool,
 
+    /// Maximum number of files queued for processing at once
+    ///
+    /// A full sync already works through one listing page at a time; files of
+    /// larger pages and incremental change sets join the queue as queued ones
+    /// finish, so memory stays bounded however big the library is.
+    pub max_in_memory_files: usize,
+
+    /// Whether to emit `ItemStarted`/`ItemFinished` events around every file.
+    ///
+    /// Off by default so huge libraries don't flood the event bus; the
+    /// throttled `Progress` events are sent either way.
+    pub emit_per_item_events: bool,
+
     /// Maximum file size to process (bytes). Files larger than this are skipped.
     pub max_file_size_bytes: u64,
 
@@ -122,20 +229,41 @@ pub struct SyncConfig {
 
     /// Number of retry attempts for failed downloads
     pub retry_attempts: u32,
+
+    /// How ids are assigned to imported tracks, albums and artists
+    ///
+    /// `IdStrategy::Deterministic` makes re-imports idempotent.
+    pub id_strategy: IdStrategy,
+
+    /// Heuristics for skipping or tagging podcasts and voice memos
+    ///
+    /// `None` (the default) imports every audio file as music.
+    pub non_music_filter: Option<NonMusicFilter>,
+
+    /// Whether to measure track loudness for volume leveling
+    ///
+    /// Only takes effect with full downloads (`header_only_download: false`).
+    pub analyze_loudness: bool,
 }
 
 impl Default for SyncConfig {
     fn default() -> Self {
         Self {
             max_concurrent_downloads: 4,
+            low_bandwidth_threshold_mbps: None,
             sync_timeout_secs: 3600, // 1 hour
             download_timeout_secs: 60,
             wifi_only: false,
+            max_in_memory_files: 500,
+            emit_per_item_events: false,
             max_file_size_bytes: 500 * 1024 * 1024, // 500 MB
             header_only_download: true,             // More efficient for metadata extraction
             header_size_bytes: 256 * 1024,          // 256KB should contain all metadata
             extract_artwork: true,
             retry_attempts: 3,
+            id_strategy: IdStrategy::Random,
+            non_music_filter: None,
+            analyze_loudness: false,
             audio_mime_types: vec![
                 "audio/mpeg".to_string(),
                 "audio/mp3".to_string(),
@@ -175,6 +303,15 @@ impl Default for SyncConfig {
     }
 }
 
+/// How a work item of the processing phase turned out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ItemStatus {
+    Added,
+    Updated,
+    Skipped,
+    Failed,
+}
+
 /// Active sync job tracking
 #[derive(Clone)]
 struct ActiveSync {
@@ -272,14 +409,20 @@ impl SyncCoordinator {
         let job_repository = Arc::new(SqliteSyncJobRepository::new());
 
         // Initialize repositories for metadata processor
+        #[cfg(not(target_arch = "wasm32"))]
+        let repository_db = db.clone();
+        // Repositories hold their adapter in an `Rc` on WASM
+        #[cfg(target_arch = "wasm32")]
+        let repository_db: std::rc::Rc<dyn DatabaseAdapter> = std::rc::Rc::new(db.clone());
+
         let track_repository =
-            Arc::new(SqliteTrackRepository::new(db.clone())) as Arc<dyn TrackRepository>;
-        let artist_repository =
-            Arc::new(SqliteArtistRepository::new(db.clone())) as Arc<dyn ArtistRepository>;
+            Arc::new(SqliteTrackRepository::new(repository_db.clone())) as Arc<dyn TrackRepository>;
+        let artist_repository = Arc::new(SqliteArtistRepository::new(repository_db.clone()))
+            as Arc<dyn ArtistRepository>;
         let album_repository =
-            Arc::new(SqliteAlbumRepository::new(db.clone())) as Arc<dyn AlbumRepository>;
+            Arc::new(SqliteAlbumRepository::new(repository_db.clone())) as Arc<dyn AlbumRepository>;
         let artwork_repository =
-            Arc::new(SqliteArtworkRepository::new(db.clone())) as Arc<dyn ArtworkRepository>;
+            Arc::new(SqliteArtworkRepository::new(repository_db)) as Arc<dyn ArtworkRepository>;
 
         // Initialize artwork service (if artwork extraction is enabled)
         let artwork_service = if config.extract_artwork {
@@ -299,6 +442,10 @@ impl SyncCoordinator {
             update_existing: false, // Don't update existing tracks by default
             max_download_retries: config.retry_attempts,
             download_timeout_secs: config.download_timeout_secs,
+            id_strategy: config.id_strategy,
+            non_music: config.non_music_filter.clone(),
+            analyze_loudness: config.analyze_loudness,
+            ..ProcessorConfig::default()
         };
 
         let metadata_processor = Arc::new(MetadataProcessor::new(
@@ -312,6 +459,11 @@ impl SyncCoordinator {
             db.clone(),
         ));
 
+        // Sweep downloads abandoned by a previous crash
+        if let Err(e) = metadata_processor.cleanup_temp(STALE_TEMP_FILE_AGE).await {
+            warn!("Failed to clean up temporary files: {}", e);
+        }
+
         // Initialize conflict resolution orchestrator
         let conflict_resolution_orchestrator = Arc::new(ConflictResolutionOrchestrator::new(
             conflict_resolver.clone(),
@@ -392,7 +544,36 @@ impl SyncCoordinator {
     /// ```
     #[instrument(skip(self), fields(profile_id = %profile_id))]
     pub async fn start_full_sync(&self, profile_id: ProfileId) -> Result<SyncJobId> {
-        self.start_sync_internal(profile_id, SyncType::Full, None)
+        self.start_sync_internal(profile_id, SyncType::Full, None, false)
+            .await
+    }
+
+    /// Force a full rescan that reconciles the library with the provider
+    ///
+    /// Discards every stored sync cursor for the provider so the next
+    /// incremental sync can't resume from a stale position, then re-lists all
+    /// files. Files whose track is already up to date (same provider file and
+    /// a recorded modification time at least as new as the remote one) are
+    /// not downloaded again; everything else is re-processed. Tracks for files
+    /// the provider no longer lists are removed and content duplicates are
+    /// merged during conflict resolution, so the library is reconciled rather
+    /// than rebuilt.
+    ///
+    /// # Arguments
+    ///
+    /// * `profile_id` - User profile to rescan
+    ///
+    /// # Returns
+    ///
+    /// Returns the `SyncJobId` for tracking progress
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`start_full_sync`](Self::start_full_sync),
+    /// or an error if the stored cursors can't be cleared.
+    #[instrument(skip(self), fields(profile_id = %profile_id))]
+    pub async fn force_full_rescan(&self, profile_id: ProfileId) -> Result<SyncJobId> {
+        self.start_sync_internal(profile_id, SyncType::Full, None, true)
             .await
     }
 
@@ -430,17 +611,121 @@ impl SyncCoordinator {
         profile_id: ProfileId,
         cursor: Option<String>,
     ) -> Result<SyncJobId> {
-        self.start_sync_internal(profile_id, SyncType::Incremental, cursor)
+        self.start_sync_internal(profile_id, SyncType::Incremental, cursor, false)
             .await
     }
 
+    /// Resume an interrupted sync job from its saved cursor
+    ///
+    /// A full sync saves the provider's listing cursor after every page it
+    /// processes, so a job that crashed or failed part way through continues
+    /// with the next page instead of listing the library again. Files from
+    /// the pages already processed aren't seen by the resumed job, so
+    /// deletion detection is skipped; the next full sync catches up.
+    ///
+    /// # Arguments
+    ///
+    /// * `profile_id` - User profile the job belongs to
+    /// * `job_id` - Interrupted job to resume
+    ///
+    /// # Returns
+    ///
+    /// Returns the `SyncJobId` of the resumed job
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`start_full_sync`](Self::start_full_sync),
+    /// or an error if the job doesn't exist, belongs to another provider or
+    /// already completed or was cancelled.
+    #[instrument(skip(self), fields(profile_id = %profile_id, job_id = %job_id))]
+    pub async fn resume_sync(&self, profile_id: ProfileId, job_id: SyncJobId) -> Result<SyncJobId> {
+        let provider = self.check_can_sync(profile_id).await?;
+
+        let job = self
+            .job_repository
+            .find_by_id(self.db.as_ref(), &job_id)
+            .await?
+            .ok_or_else(|| SyncError::JobNotFound {
+                job_id: job_id.to_string(),
+            })?;
+        if job.provider_id != provider {
+            return Err(SyncError::InvalidInput {
+                field: "job_id".to_string(),
+                message: format!("Job {} belongs to provider {}", job_id, job.provider_id),
+            });
+        }
+
+        let job = job.resume()?;
+        self.job_repository.update(self.db.as_ref(), &job).await?;
+
+        info!(
+            "Resuming {} sync job {} for profile {} (cursor: {:?})",
+            job.sync_type, job_id, profile_id, job.cursor
+        );
+        self.spawn_sync(profile_id, &job, false).await;
+
+        Ok(job_id)
+    }
+
     /// Internal method to start sync operation
+    ///
+    /// With `rescan` set, stored cursors are cleared before the job starts and
+    /// unchanged files are skipped during processing.
     async fn start_sync_internal(
         &self,
         profile_id: ProfileId,
         sync_type: SyncType,
         cursor: Option<String>,
+        rescan: bool,
     ) -> Result<SyncJobId> {
+        let provider = self.check_can_sync(profile_id).await?;
+
+        if rescan {
+            let cleared = self
+                .job_repository
+                .clear_cursors(self.db.as_ref(), profile_id, provider)
+                .await?;
+            info!("Cleared {} stored sync cursor(s) for rescan", cleared);
+        }
+
+        // Create sync job
+        let mut job = match sync_type {
+            SyncType::Full => SyncJob::new(provider, SyncType::Full),
+            SyncType::Incremental => SyncJob::new_incremental(
+                provider,
+                cursor.ok_or_else(|| SyncError::InvalidInput {
+                    field: "cursor".to_string(),
+                    message: "Cursor required for incremental sync".to_string(),
+                })?,
+            ),
+        }
+        .for_profile(profile_id);
+
+        // Start job
+        job = job.start()?;
+        let job_id = job.id;
+
+        // Persist job
+        self.job_repository.insert(self.db.as_ref(), &job).await?;
+
+        self.spawn_sync(profile_id, &job, rescan).await;
+
+        info!(
+            "Started {} sync{} for profile {} with job {}",
+            sync_type,
+            if rescan { " (rescan)" } else { "" },
+            profile_id,
+            job_id
+        );
+
+        Ok(job_id)
+    }
+
+    /// Check that a sync can start for the profile
+    ///
+    /// Returns the provider of the current session once no sync is in
+    /// progress, the network allows syncing and the provider is registered.
+    async fn check_can_sync(&self, profile_id: ProfileId) -> Result<ProviderKind> {
         // Check if sync already in progress
         {
             let active_syncs = self.active_syncs.lock().await;
@@ -452,29 +737,27 @@ impl SyncCoordinator {
         }
 
         // Check network constraints
-        if self.config.wifi_only {
-            if let Some(monitor) = &self.network_monitor {
-                let network_info = monitor
-                    .get_network_info()
-                    .await
-                    .map_err(|e| SyncError::Provider(format!("Failed to check network: {}", e)))?;
-
-                if network_info.status != NetworkStatus::Connected {
-                    return Err(SyncError::Provider("Network not available".to_string()));
-                }
+        if let Some(monitor) = &self.network_monitor {
+            match monitor.get_network_info().await {
+                Ok(network_info) => {
+                    // Requests would only reach the portal's login page
+                    if network_info.is_captive_portal == Some(true) {
+                        return Err(SyncError::CaptivePortal);
+                    }
 
-                // WiFi-only mode: require WiFi connection and non-metered
-                if !matches!(network_info.network_type, Some(NetworkType::WiFi)) {
-                    return Err(SyncError::Provider(
-                        "WiFi-only mode enabled but not connected to WiFi".to_string(),
-                    ));
+                    if self.config.wifi_only {
+                        if let Some(reason) = wifi_only_violation(&network_info) {
+                            return Err(SyncError::Provider(reason.to_string()));
+                        }
+                    }
                 }
-
-                if network_info.is_metered {
-                    return Err(SyncError::Provider(
-                        "WiFi-only mode enabled but network is metered".to_string(),
-                    ));
+                Err(e) if self.config.wifi_only => {
+                    return Err(SyncError::Provider(format!(
+                        "Failed to check network: {}",
+                        e
+                    )));
                 }
+                Err(e) => warn!("Failed to check network: {}", e),
             }
         }
 
@@ -483,7 +766,7 @@ impl SyncCoordinator {
             .auth_manager
             .current_session()
             .await
-            .ok_or_else(|| SyncError::Provider("No active session".to_string()))?;
+            .ok_or(SyncError::NotAuthenticated)?;
 
         // Verify provider is registered
         {
@@ -496,25 +779,13 @@ impl SyncCoordinator {
             }
         }
 
-        // Create sync job
-        let mut job = match sync_type {
-            SyncType::Full => SyncJob::new(session.provider, SyncType::Full),
-            SyncType::Incremental => SyncJob::new_incremental(
-                session.provider,
-                cursor.ok_or_else(|| SyncError::InvalidInput {
-                    field: "cursor".to_string(),
-                    message: "Cursor required for incremental sync".to_string(),
-                })?,
-            ),
-        };
+        Ok(session.provider)
+    }
 
-        // Start job
-        job = job.start()?;
+    /// Track the running job and run it in a background task
+    async fn spawn_sync(&self, profile_id: ProfileId, job: &SyncJob, rescan: bool) {
         let job_id = job.id;
 
-        // Persist job
-        self.job_repository.insert(self.db.as_ref(), &job).await?;
-
         // Create cancellation token
         let cancellation_token = CancellationToken::new();
 
@@ -536,8 +807,8 @@ impl SyncCoordinator {
             .emit(CoreEvent::Sync(SyncEvent::Started {
                 job_id: job_id.to_string(),
                 profile_id: profile_id.to_string(),
-                provider: session.provider.to_string(),
-                is_full_sync: matches!(sync_type, SyncType::Full),
+                provider: job.provider_id.to_string(),
+                is_full_sync: matches!(job.sync_type, SyncType::Full),
             }))
             .ok();
 
@@ -545,7 +816,7 @@ impl SyncCoordinator {
         let coordinator = Arc::new(self.clone_for_task());
         core_async::task::spawn(async move {
             let result = coordinator
-                .run_sync_task(job_id, profile_id, cancellation_token)
+                .run_sync_task(job_id, profile_id, rescan, cancellation_token)
                 .await;
 
             // Clean up active sync tracking
@@ -558,13 +829,37 @@ impl SyncCoordinator {
                 error!("Sync task failed: {}", e);
             }
         });
+    }
 
-        info!(
-            "Started {} sync for profile {} with job {}",
-            sync_type, profile_id, job_id
-        );
+    /// Number of downloads to run at once on the current link
+    ///
+    /// Returns `max_concurrent_downloads`, scaled down when the network
+    /// monitor's downlink estimate is below `low_bandwidth_threshold_mbps`.
+    /// Without a monitor, threshold or estimate, the configured maximum is used.
+    pub async fn download_concurrency(&self) -> usize {
+        let max = self.config.max_concurrent_downloads.max(1);
+        let (Some(threshold), Some(monitor)) = (
+            self.config.low_bandwidth_threshold_mbps,
+            &self.network_monitor,
+        ) else {
+            return max;
+        };
 
-        Ok(job_id)
+        let estimate = match monitor.estimated_downlink_mbps().await {
+            Ok(Some(mbps)) => mbps,
+            Ok(None) => return max,
+            Err(e) => {
+                warn!("Failed to read downlink estimate: {}", e);
+                return max;
+            }
+        };
+
+        if threshold <= 0.0 || estimate >= threshold {
+            return max;
+        }
+
+        let scaled = (max as f64 * estimate / threshold).floor() as usize;
+        scaled.clamp(1, max)
     }
 
     /// Clone for background task (avoids Arc<Arc<...>>)
@@ -592,10 +887,21 @@ impl SyncCoordinator {
         &self,
         job_id: SyncJobId,
         profile_id: ProfileId,
+        rescan: bool,
         cancellation_token: CancellationToken,
     ) -> Result<()> {
-        // Wrap in timeout
-        let sync_future = self.execute_sync(job_id, profile_id, cancellation_token);
+        // Wrap in timeout, stopping early if a WiFi-only sync loses its WiFi link
+        let sync_future = async {
+            let sync = self.execute_sync(job_id, profile_id, rescan, cancellation_token.clone());
+            match race2(sync, self.wait_for_wifi_only_violation()).await {
+                Either::Left(result) => result,
+                Either::Right(error) => {
+                    warn!("Stopping sync job {}: {}", job_id, error);
+                    cancellation_token.cancel();
+                    Err(error)
+                }
+            }
+        };
 
         match timeout(
             Duration::from_secs(self.config.sync_timeout_secs),
@@ -660,6 +966,33 @@ impl SyncCoordinator {
         }
     }
 
+    /// Resolve once the network no longer satisfies `wifi_only`
+    ///
+    /// Never resolves when `wifi_only` is off, there is no network monitor, or
+    /// the monitor can't stream connectivity changes.
+    async fn wait_for_wifi_only_violation(&self) -> SyncError {
+        let monitor = match &self.network_monitor {
+            Some(monitor) if self.config.wifi_only => monitor,
+            _ => return future::pending().await,
+        };
+
+        let mut transitions = match monitor.subscribe().await {
+            Ok(transitions) => transitions,
+            Err(e) => {
+                warn!("Failed to subscribe to network changes: {}", e);
+                return future::pending().await;
+            }
+        };
+
+        while let Some(info) = transitions.next().await {
+            if let Some(reason) = wifi_only_violation(&info) {
+                return SyncError::Provider(reason.to_string());
+            }
+        }
+
+        future::pending().await
+    }
+
     /// Execute the sync operation
     ///
     /// High-level orchestrator that coordinates the three main phases of sync:
@@ -671,6 +1004,7 @@ impl SyncCoordinator {
         &self,
         job_id: SyncJobId,
         profile_id: ProfileId,
+        rescan: bool,
         cancellation_token: CancellationToken,
     ) -> Result<()> {
         // Get current session
@@ -678,7 +1012,7 @@ impl SyncCoordinator {
             .auth_manager
             .current_session()
             .await
-            .ok_or_else(|| SyncError::Provider("No active session".to_string()))?;
+            .ok_or(SyncError::NotAuthenticated)?;
 
         // Get provider
         let provider = {
@@ -700,30 +1034,66 @@ impl SyncCoordinator {
                 job_id: job_id.to_string(),
             })?;
 
-        // Phase 1: Discovery
-        info!("Phase 1: Discovery - {} sync", job.sync_type);
-        let (audio_files, new_cursor, provider_file_ids) = self
-            .discovery_phase(&mut job, &provider, &cancellation_token)
-            .await?;
+        // A full sync that already has a cursor is picking up where it left off
+        let resumed = job.sync_type == SyncType::Full && job.cursor.is_some();
+        let provider_id = session.provider.to_string();
+
+        let (stats, provider_file_ids) =
+            if job.sync_type == SyncType::Incremental && job.cursor.is_some() {
+                // Phase 1: Discovery
+                info!("Phase 1: Discovery - incremental sync");
+                let (audio_files, new_cursor, provider_file_ids) = self
+                    .discovery_incremental_sync(&mut job, &provider, &cancellation_token)
+                    .await?;
+
+                // Update cursor if we got a new one
+                if let Some(cursor) = new_cursor {
+                    job.update_cursor(cursor)?;
+                    self.job_repository.update(self.db.as_ref(), &job).await?;
+                    info!("Updated sync cursor");
+                }
 
-        // Update cursor if we got a new one
-        if let Some(cursor) = new_cursor {
-            job.update_cursor(cursor)?;
-            self.job_repository.update(self.db.as_ref(), &job).await?;
-            info!("Updated sync cursor");
-        }
+                // Phase 2: Processing
+                info!("Phase 2: Processing {} audio files", audio_files.len());
+                let stats = self
+                    .processing_phase(
+                        &mut job,
+                        &provider,
+                        &provider_id,
+                        audio_files,
+                        rescan,
+                        &cancellation_token,
+                    )
+                    .await?;
+                (stats, provider_file_ids)
+            } else {
+                if job.sync_type == SyncType::Incremental {
+                    warn!("No cursor found for incremental sync, falling back to full sync");
+                }
 
-        // Phase 2: Processing
-        info!("Phase 2: Processing {} audio files", audio_files.len());
-        let stats = self
-            .processing_phase(&mut job, &provider, &session.provider.to_string(), audio_files, &cancellation_token)
-            .await?;
+                // Phases 1 and 2 run together, one page at a time
+                info!("Phase 1: Discovery - full sync, processing each page as it is listed");
+                self.discovery_full_sync(
+                    &mut job,
+                    &provider,
+                    &provider_id,
+                    rescan,
+                    &cancellation_token,
+                )
+                .await?
+            };
 
         // Phase 3: Conflict Resolution
-        info!("Phase 3: Resolving conflicts");
-        let conflict_stats = self
-            .conflict_resolution_phase(&job_id, &session.provider.to_string(), &provider_file_ids)
-            .await?;
+        let conflict_stats = if resumed {
+            // Files on the pages listed before the interruption aren't in
+            // provider_file_ids, so they would look deleted
+            info!("Phase 3: Skipping deletion detection for resumed job");
+            ConflictResolutionStats::default()
+        } else {
+            info!("Phase 3: Resolving conflicts");
+            self.conflict_resolution_phase(&job_id, &provider_id, &provider_file_ids)
+                .await?
+        };
 
         // Combine stats
         let final_stats = SyncJobStats {
@@ -761,48 +1131,36 @@ impl SyncCoordinator {
         Ok(())
     }
 
-    /// Phase 1: Discovery
+    /// Phases 1 and 2 for a full sync: list all media and process it page by page
     ///
-    /// Discovers files to sync based on sync type:
-    /// - Full sync: Lists all media from provider
-    /// - Incremental sync: Gets changes since last cursor
+    /// Each page's audio files are processed before the next page is fetched,
+    /// which bounds memory by the page size, and the cursor for the next page
+    /// is saved on the job once a page is done. A job that already has a
+    /// cursor continues from it. The checksum and size of every processed file
+    /// are kept across pages, so a duplicate of a file on an earlier page is
+    /// skipped too.
     ///
-    /// Returns: (audio_files, new_cursor, provider_file_ids)
+    /// Returns: (stats, provider_file_ids) for the pages listed by this run
     #[instrument(skip(self, job, provider, cancellation_token))]
-    async fn discovery_phase(
+    async fn discovery_full_sync(
         &self,
         job: &mut SyncJob,
         provider: &Arc<dyn StorageProvider>,
+        provider_id: &str,
+        skip_unchanged: bool,
         cancellation_token: &CancellationToken,
-    ) -> Result<(
-        Vec<RemoteFile>,
-        Option<String>,
-        std::collections::HashSet<String>,
-    )> {
-        match job.sync_type {
-            SyncType::Full => self.discovery_full_sync(job, provider, cancellation_token).await,
-            SyncType::Incremental => {
-                self.discovery_incremental_sync(job, provider, cancellation_token)
-                    .await
-            }
+    ) -> Result<(SyncJobStats, std::collections::HashSet<String>)> {
+        let mut cursor = job.cursor.clone();
+        match &cursor {
+            Some(cursor) => info!("Resuming full sync discovery from cursor: {}", cursor),
+            None => info!("Starting full sync discovery"),
         }
-    }
 
-    /// Full sync discovery: List all media from provider
-    #[instrument(skip(self, job, provider, cancellation_token))]
-    async fn discovery_full_sync(
-        &self,
-        job: &mut SyncJob,
-        provider: &Arc<dyn StorageProvider>,
-        cancellation_token: &CancellationToken,
-    ) -> Result<(
-        Vec<RemoteFile>,
-        Option<String>,
-        std::collections::HashSet<String>,
-    )> {
-        info!("Starting full sync discovery");
-        let mut all_files = Vec::new();
-        let mut cursor = None;
+        let mut stats = SyncJobStats::new();
+        let mut provider_file_ids = std::collections::HashSet::new();
+        let mut seen_content: std::collections::HashSet<(String, u64)> =
+            std::collections::HashSet::new();
+        let mut discovered = 0u64;
         let mut page_count = 0;
 
         loop {
@@ -813,18 +1171,20 @@ impl SyncCoordinator {
             page_count += 1;
             debug!("Fetching page {} (cursor: {:?})", page_count, cursor);
 
-            let (files, next_cursor) = provider
-                .list_media(cursor.clone())
-                .await
-                .map_err(|e| SyncError::Provider(format!("Failed to list media: {}", e)))?;
+            // A slow page fetch shouldn't delay cancellation until it returns
+            let (files, next_cursor) =
+                race_with_cancel(provider.list_media(cursor.clone()), cancellation_token)
+                    .await
+                    .map_err(|_| SyncError::Cancelled)?
+                    .map_err(|e| SyncError::Provider(format!("Failed to list media: {}", e)))?;
 
-            all_files.extend(files);
+            discovered += files.len() as u64;
 
             // Update progress
             job.update_progress(
-                all_files.len() as u64,
+                discovered,
                 0, // Total unknown during discovery
-                &format!("Discovered {} files", all_files.len()),
+                &format!("Discovered {} files", discovered),
             )?;
             self.job_repository.update(self.db.as_ref(), job).await?;
 
@@ -832,30 +1192,68 @@ impl SyncCoordinator {
             self.event_bus
                 .emit(CoreEvent::Sync(SyncEvent::Progress {
                     job_id: job.id.to_string(),
-                    items_processed: all_files.len() as u64,
+                    items_processed: discovered,
                     total_items: None,
                     percent: 0,
                     phase: "discovering".to_string(),
                 }))
                 .ok();
 
-            cursor = next_cursor;
-            if cursor.is_none() {
+            // Filter to audio files only
+            let audio_files = self.filter_audio_files(files);
+            debug!("Page {} has {} audio files", page_count, audio_files.len());
+            provider_file_ids.extend(audio_files.iter().map(|f| f.id.clone()));
+
+            // Content kept on an earlier page already became a track there
+            let audio_files: Vec<RemoteFile> = audio_files
+                .into_iter()
+                .filter(|file| match content_key(file) {
+                    Some(key) if seen_content.contains(&key) => {
+                        info!(
+                            "Skipping duplicate of a file on an earlier page (md5 {}): {}",
+                            key.0, file.name
+                        );
+                        false
+                    }
+                    _ => true,
+                })
+                .collect();
+            seen_content.extend(audio_files.iter().filter_map(content_key));
+
+            let page_stats = self
+                .processing_phase(
+                    job,
+                    provider,
+                    provider_id,
+                    audio_files,
+                    skip_unchanged,
+                    cancellation_token,
+                )
+                .await?;
+            stats.items_added += page_stats.items_added;
+            stats.items_updated += page_stats.items_updated;
+            stats.items_failed += page_stats.items_failed;
+
+            // An interrupted job resumes from the page after this one
+            let Some(next_cursor) = next_cursor else {
                 break;
-            }
+            };
+            job.update_cursor(next_cursor.clone())?;
+            self.job_repository.update(self.db.as_ref(), job).await?;
+            cursor = Some(next_cursor);
         }
 
-        info!("Discovered {} total files", all_files.len());
-
-        // Filter to audio files only
-        let audio_files = self.filter_audio_files(all_files);
-        info!("Filtered to {} audio files", audio_files.len());
+        // A listing cursor means nothing once the listing is done
+        if job.cursor.take().is_some() {
+            self.job_repository.update(self.db.as_ref(), job).await?;
+        }
 
-        // Build set of provider file IDs
-        let provider_file_ids: std::collections::HashSet<String> =
-            audio_files.iter().map(|f| f.id.clone()).collect();
+        info!(
+            "Discovered {} total files across {} page(s)",
+            discovered, page_count
+        );
 
-        Ok((audio_files, cursor, provider_file_ids))
+        Ok((stats, provider_file_ids))
     }
 
     /// Incremental sync discovery: Get changes since cursor
@@ -873,24 +1271,21 @@ impl SyncCoordinator {
         info!("Starting incremental sync discovery");
 
         // Get cursor from job
-        let cursor = job.cursor.clone();
-        
-        if cursor.is_none() {
-            warn!("No cursor found for incremental sync, falling back to full sync");
-            return self.discovery_full_sync(job, provider, cancellation_token).await;
-        }
-
-        let cursor = cursor.unwrap();
+        let cursor = job.cursor.clone().ok_or_else(|| SyncError::InvalidInput {
+            field: "cursor".to_string(),
+            message: "Cursor required for incremental sync".to_string(),
+        })?;
         info!("Fetching changes since cursor: {}", cursor);
 
         // Get changes from provider
-        let (changes, new_cursor) = provider
-            .get_changes(Some(cursor.clone()))
-            .await
-            .map_err(|e| {
-                warn!("Failed to get incremental changes: {}", e);
-                SyncError::Provider(format!("Failed to get changes: {}", e))
-            })?;
+        let (changes, new_cursor) =
+            race_with_cancel(provider.get_changes(Some(cursor.clone())), cancellation_token)
+                .await
+                .map_err(|_| SyncError::Cancelled)?
+                .map_err(|e| {
+                    warn!("Failed to get incremental changes: {}", e);
+                    SyncError::Provider(format!("Failed to get changes: {}", e))
+                })?;
 
         info!("Received {} changes from provider", changes.len());
 
@@ -973,11 +1368,14 @@ impl SyncCoordinator {
 
     /// Phase 2: Processing
     ///
-    /// Processes discovered audio files:
-    /// - Enqueues work items
-    /// - Downloads and extracts metadata
+    /// Processes discovered audio files, at most `max_in_memory_files` at a time:
+    /// - Enqueues work items as earlier ones finish
+    /// - Downloads and extracts metadata, up to `download_concurrency()` files at once
     /// - Updates library database
     ///
+    /// With `skip_unchanged` set (force rescan), files whose track is already
+    /// up to date are not downloaded again.
+    ///
     /// Returns: SyncJobStats with items added/updated/failed
     #[instrument(skip(self, job, provider, audio_files, cancellation_token))]
     async fn processing_phase(
@@ -986,6 +1384,7 @@ impl SyncCoordinator {
         provider: &Arc<dyn StorageProvider>,
         provider_id: &str,
         audio_files: Vec<RemoteFile>,
+        skip_unchanged: bool,
         cancellation_token: &CancellationToken,
     ) -> Result<SyncJobStats> {
         if audio_files.is_empty() {
@@ -998,29 +1397,51 @@ impl SyncCoordinator {
             });
         }
 
-        // Enqueue work items
-        info!("Enqueueing {} work items", audio_files.len());
-        let mut file_name_map: std::collections::HashMap<String, String> =
-            std::collections::HashMap::new();
+        // Only download one copy of files uploaded more than once
+        let (audio_files, duplicate_sets) = group_duplicates(audio_files);
+        for set in &duplicate_sets {
+            info!(
+                "Skipping {} duplicate(s) of {} (md5 {}): {}",
+                set.duplicates.len(),
+                set.primary.name,
+                set.md5_checksum,
+                set.duplicates
+                    .iter()
+                    .map(|file| file.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
 
-        for file in audio_files.iter() {
-            if cancellation_token.is_cancelled() {
-                return Err(SyncError::Cancelled);
+        // A rescan only re-processes files that changed since they were synced
+        let audio_files = if skip_unchanged {
+            let mut stale_files = Vec::with_capacity(audio_files.len());
+            for file in audio_files {
+                if self
+                    .metadata_processor
+                    .is_unchanged(provider_id, &file)
+                    .await?
+                {
+                    debug!("Skipping unchanged file: {}", file.name);
+                } else {
+                    stale_files.push(file);
+                }
             }
+            info!("{} file(s) changed since the last sync", stale_files.len());
+            stale_files
+        } else {
+            audio_files
+        };
 
-            let work_item = WorkItem::new(
-                file.id.clone(),
-                file.mime_type
-                    .clone()
-                    .unwrap_or_else(|| "application/octet-stream".to_string()),
-            )
-            .with_file_size(file.size.unwrap_or(0) as i64);
-
-            self.scan_queue.enqueue(work_item).await?;
-            file_name_map.insert(file.id.clone(), file.name.clone());
+        // Process queue, downloading up to `concurrency` files at once
+        let concurrency = self.download_concurrency().await;
+        if concurrency < self.config.max_concurrent_downloads {
+            info!(
+                "Slow network, limiting downloads to {} of {}",
+                concurrency, self.config.max_concurrent_downloads
+            );
         }
 
-        // Process queue
         let total_items = audio_files.len() as u64;
         let mut processed = 0u64;
         let mut added = 0u64;
@@ -1028,97 +1449,157 @@ impl SyncCoordinator {
         let mut failed = 0u64;
         let mut total_bytes_downloaded = 0u64;
 
+        // Files leave the list for the queue only as queued ones finish, so
+        // no more than `max_in_memory_files` are queued, and mapped by id, at once
+        let queue_limit = self.config.max_in_memory_files.max(1);
+        let mut unqueued_files = audio_files.into_iter();
+        let mut queued_files: HashMap<String, RemoteFile> = HashMap::new();
+
+        // Each item runs as its own task, so one holding a transaction
+        // keeps going while this loop waits on the database
+        let coordinator = Arc::new(self.clone_for_task());
+        let mut in_flight = FuturesUnordered::new();
         loop {
             if cancellation_token.is_cancelled() {
+                // Items already downloading still settle their transaction
+                while in_flight.next().await.is_some() {}
                 return Err(SyncError::Cancelled);
             }
 
-            match self.scan_queue.dequeue().await {
-                Ok(Some(item)) => {
-                    processed += 1;
-                    debug!(
-                        "Processing work item: {} ({}/{})",
-                        item.remote_file_id, processed, total_items
-                    );
-
-                    let file_name = file_name_map
-                        .get(&item.remote_file_id)
-                        .map(|s| s.as_str())
-                        .unwrap_or("unknown");
-
-                    match self
-                        .metadata_processor
-                        .process_work_item(&item, provider, provider_id, file_name)
-                        .await
-                    {
-                        Ok(result) => {
-                            if result.is_new {
-                                added += 1;
-                            } else {
-                                updated += 1;
-                            }
-                            total_bytes_downloaded += result.bytes_downloaded;
+            // Top the queue back up
+            while queued_files.len() < queue_limit {
+                let Some(file) = unqueued_files.next() else {
+                    break;
+                };
+
+                let work_item = WorkItem::new(
+                    file.id.clone(),
+                    file.mime_type
+                        .clone()
+                        .unwrap_or_else(|| "application/octet-stream".to_string()),
+                )
+                .with_file_size(file.size.unwrap_or(0) as i64);
+
+                self.scan_queue.enqueue(work_item).await?;
+                queued_files.insert(file.id.clone(), file);
+            }
 
-                            if let Err(e) = self.scan_queue.mark_complete(item.id).await {
-                                warn!("Failed to mark item complete: {}", e);
+            // Keep the download slots filled
+            while in_flight.len() < concurrency {
+                match self.scan_queue.dequeue().await {
+                    Ok(Some(item)) => {
+                        let file = queued_files.get(&item.remote_file_id);
+                        let file_name = file
+                            .map(|file| file.name.clone())
+                            .unwrap_or_else(|| "unknown".to_string());
+                        let modified_at = file.and_then(|file| file.modified_at);
+                        let md5_checksum = file.and_then(|file| file.md5_checksum.clone());
+                        let item_id = item.id;
+                        let remote_file_id = item.remote_file_id.clone();
+                        let task = core_async::task::spawn({
+                            let coordinator = Arc::clone(&coordinator);
+                            let job_id = job.id.to_string();
+                            let file_name = file_name.clone();
+                            let provider = Arc::clone(provider);
+                            let provider_id = provider_id.to_string();
+                            async move {
+                                coordinator
+                                    .process_queued_item(
+                                        job_id,
+                                        item,
+                                        &file_name,
+                                        modified_at,
+                                        md5_checksum,
+                                        &provider,
+                                        &provider_id,
+                                        skip_unchanged,
+                                    )
+                                    .await
                             }
-
-                            debug!(
-                                "Successfully processed {} in {}ms (new: {}, artwork: {}, {} bytes)",
-                                file_name,
-                                result.processing_time_ms,
-                                result.is_new,
-                                result.artwork_processed,
-                                result.bytes_downloaded
-                            );
-                        }
-                        Err(e) => {
-                            error!("Failed to process work item {}: {}", item.remote_file_id, e);
-                            failed += 1;
-                            let _ = self
-                                .scan_queue
-                                .mark_failed(item.id, Some(e.to_string()))
-                                .await;
-                        }
+                        });
+                        in_flight
+                            .push(async move { (item_id, remote_file_id, file_name, task.await) });
                     }
-
-                    // Update progress
-                    let percent = ((processed as f64 / total_items as f64) * 100.0) as u8;
-                    job.update_progress(
-                        processed,
-                        total_items,
-                        &format!(
-                            "Processed {}/{} files ({} MB downloaded)",
-                            processed,
-                            total_items,
-                            total_bytes_downloaded / (1024 * 1024)
-                        ),
-                    )?;
-                    self.job_repository.update(self.db.as_ref(), job).await?;
-
-                    if processed.is_multiple_of(10) || processed == total_items {
-                        self.event_bus
-                            .emit(CoreEvent::Sync(SyncEvent::Progress {
-                                job_id: job.id.to_string(),
-                                items_processed: processed,
-                                total_items: Some(total_items),
-                                percent,
-                                phase: "processing".to_string(),
-                            }))
-                            .ok();
+                    Ok(None) => break,
+                    Err(e) => {
+                        error!("Error dequeuing item: {}", e);
+                        break;
                     }
                 }
-                Ok(None) => {
-                    info!(
-                        "Queue processing complete: {} added, {} updated, {} failed",
-                        added, updated, failed
-                    );
-                    break;
-                }
-                Err(e) => {
-                    error!("Error dequeuing item: {}", e);
-                    break;
-                }
+            }
+
+            let Some((item_id, remote_file_id, file_name, outcome)) = in_flight.next().await else {
+                info!(
+                    "Queue processing complete: {} added, {} updated, {} failed",
+                    added, updated, failed
+                );
+                break;
+            };
+
+            let (status, bytes) = outcome.unwrap_or_else(|e| {
+                error!("Work item task for {} failed: {}", remote_file_id, e);
+                (ItemStatus::Failed, 0)
+            });
+
+            // A failed item that will be retried is pending again and keeps its entry
+            let requeued = status == ItemStatus::Failed
+                && matches!(
+                    self.scan_queue.get_status(item_id).await,
+                    Ok(Some(item)) if item.status == WorkItemStatus::Pending
+                );
+            if !requeued {
+                queued_files.remove(&remote_file_id);
+            }
+
+            processed += 1;
+            debug!(
+                "Processed work item: {} ({}/{})",
+                remote_file_id, processed, total_items
+            );
+            match status {
+                ItemStatus::Added => added += 1,
+                ItemStatus::Updated => updated += 1,
+                ItemStatus::Failed => failed += 1,
+                ItemStatus::Skipped => {}
+            }
+            total_bytes_downloaded += bytes;
+
+            if self.config.emit_per_item_events {
+                self.event_bus
+                    .emit(CoreEvent::Sync(SyncEvent::ItemFinished {
+                        job_id: job.id.to_string(),
+                        file_name,
+                        remote_file_id,
+                        added: status == ItemStatus::Added,
+                        bytes,
+                    }))
+                    .ok();
+            }
+
+            // Update progress
+            let percent = ((processed as f64 / total_items as f64) * 100.0) as u8;
+            job.update_progress(
+                processed,
+                total_items,
+                &format!(
+                    "Processed {}/{} files ({} MB downloaded)",
+                    processed,
+                    total_items,
+                    total_bytes_downloaded / (1024 * 1024)
+                ),
+            )?;
+            self.job_repository.update(self.db.as_ref(), job).await?;
+
+            if processed.is_multiple_of(10) || processed == total_items {
+                self.event_bus
+                    .emit(CoreEvent::Sync(SyncEvent::Progress {
+                        job_id: job.id.to_string(),
+                        items_processed: processed,
+                        total_items: Some(total_items),
+                        percent,
+                        phase: "processing".to_string(),
+                    }))
+                    .ok();
             }
         }
 
@@ -1130,6 +1611,94 @@ impl SyncCoordinator {
         })
     }
 
+    /// Process one dequeued work item and settle it in the scan queue
+    ///
+    /// Several items run at once, so the job itself is left to the caller,
+    /// which records the returned status and bytes downloaded.
+    #[allow(clippy::too_many_arguments)]
+    async fn process_queued_item(
+        &self,
+        job_id: String,
+        item: WorkItem,
+        file_name: &str,
+        modified_at: Option<i64>,
+        md5_checksum: Option<String>,
+        provider: &Arc<dyn StorageProvider>,
+        provider_id: &str,
+        skip_unchanged: bool,
+    ) -> (ItemStatus, u64) {
+        if self.config.emit_per_item_events {
+            self.event_bus
+                .emit(CoreEvent::Sync(SyncEvent::ItemStarted {
+                    job_id,
+                    file_name: file_name.to_string(),
+                    remote_file_id: item.remote_file_id.clone(),
+                }))
+                .ok();
+        }
+
+        match self
+            .metadata_processor
+            .process_work_item(&item, provider, provider_id, file_name, skip_unchanged)
+            .await
+        {
+            Ok(result) => {
+                // Lets a later rescan recognise the track as up to date.
+                // Existing tracks are only rewritten during a rescan.
+                let record = result.is_new || skip_unchanged;
+                let modified_at = modified_at.filter(|_| record);
+                let md5_checksum = md5_checksum.filter(|_| record);
+                if modified_at.is_some() || md5_checksum.is_some() {
+                    if let Err(e) = self
+                        .metadata_processor
+                        .record_provider_version(
+                            &result.track_id,
+                            modified_at,
+                            md5_checksum.as_deref(),
+                        )
+                        .await
+                    {
+                        warn!("Failed to record provider version: {}", e);
+                    }
+                }
+
+                if let Err(e) = self.scan_queue.mark_complete(item.id).await {
+                    warn!("Failed to mark item complete: {}", e);
+                }
+
+                debug!(
+                    "Successfully processed {} in {}ms (new: {}, artwork: {}, {} bytes)",
+                    file_name,
+                    result.processing_time_ms,
+                    result.is_new,
+                    result.artwork_processed,
+                    result.bytes_downloaded
+                );
+                let status = if result.is_new {
+                    ItemStatus::Added
+                } else {
+                    ItemStatus::Updated
+                };
+                (status, result.bytes_downloaded)
+            }
+            Err(SyncError::Metadata(MetadataError::Skipped(reason))) => {
+                info!("Skipping {}: {}", file_name, reason);
+                if let Err(e) = self.scan_queue.mark_complete(item.id).await {
+                    warn!("Failed to mark item complete: {}", e);
+                }
+                (ItemStatus::Skipped, 0)
+            }
+            Err(e) => {
+                error!("Failed to process work item {}: {}", item.remote_file_id, e);
+                let _ = self
+                    .scan_queue
+                    .mark_failed(item.id, Some(e.to_string()))
+                    .await;
+                (ItemStatus::Failed, 0)
+            }
+        }
+    }
+
     /// Phase 3: Conflict Resolution
     ///
     /// Resolves conflicts and handles cleanup:
@@ -1331,6 +1900,12 @@ impl SyncCoordinator {
         let active_syncs = self.active_syncs.lock().await;
         active_syncs.contains_key(&profile_id)
     }
+
+    /// Job id of the sync running for a profile, if any
+    pub async fn active_job_id(&self, profile_id: ProfileId) -> Option<SyncJobId> {
+        let active_syncs = self.active_syncs.lock().await;
+        active_syncs.get(&profile_id).map(|sync| sync.job_id)
+    }
 }
 
 #[cfg(test)]
@@ -1379,6 +1954,13 @@ mod tests {
     }
 
     async fn setup_test_coordinator(
+    ) -> (SyncCoordinator, Arc<AuthManager>, Arc<dyn DatabaseAdapter>) {
+        setup_test_coordinator_with(SyncConfig::default(), None).await
+    }
+
+    async fn setup_test_coordinator_with(
+        config: SyncConfig,
+        network_monitor: Option<Arc<dyn NetworkMonitor>>,
     ) -> (SyncCoordinator, Arc<AuthManager>, Arc<dyn DatabaseAdapter>) {
         let db_pool = create_test_pool().await.unwrap();
         let db: Arc<dyn DatabaseAdapter> = Arc::new(SqliteAdapter::from_pool(db_pool.clone()));
@@ -1560,10 +2142,10 @@ mod tests {
         ));
 
         let coordinator = SyncCoordinator::new(
-            SyncConfig::default(),
+            config,
             auth_manager.clone(),
             event_bus,
-            None,
+            network_monitor,
             file_system,
             db.clone(),
         )
@@ -1634,6 +2216,40 @@ mod tests {
         assert_eq!(audio_files[1].id, "3");
     }
 
+    #[test]
+    fn test_group_duplicates() {
+        let file = |id: &str, md5: Option<&str>, size: Option<u64>| RemoteFile {
+            id: id.to_string(),
+            name: format!("{}.mp3", id),
+            mime_type: Some("audio/mpeg".to_string()),
+            size,
+            created_at: None,
+            modified_at: None,
+            is_folder: false,
+            parent_ids: vec![],
+            md5_checksum: md5.map(|m| m.to_string()),
+            metadata: Default::default(),
+        };
+
+        let (unique, sets) = group_duplicates(vec![
+            file("a", Some("abc"), Some(100)),
+            file("b", Some("ABC"), Some(100)),
+            file("c", Some("abc"), Some(200)),
+            file("d", None, Some(100)),
+            file("e", None, Some(100)),
+            file("f", Some("abc"), Some(100)),
+        ]);
+
+        let unique_ids: Vec<_> = unique.iter().map(|f| f.id.as_str()).collect();
+        assert_eq!(unique_ids, vec!["a", "c", "d", "e"]);
+
+        assert_eq!(sets.len(), 1);
+        assert_eq!(sets[0].primary.id, "a");
+        assert_eq!(sets[0].size, 100);
+        let duplicate_ids: Vec<_> = sets[0].duplicates.iter().map(|f| f.id.as_str()).collect();
+        assert_eq!(duplicate_ids, vec!["b", "f"]);
+    }
+
     #[core_async::test]
     async fn test_register_provider() {
         let (coordinator, _, _) = setup_test_coordinator().await;
@@ -1646,4 +2262,150 @@ mod tests {
         let providers = coordinator.providers.read().await;
         assert!(providers.contains_key(&ProviderKind::GoogleDrive));
     }
+
+    struct SlowNetworkMonitor {
+        downlink_mbps: f64,
+    }
+
+    #[async_trait::async_trait]
+    impl NetworkMonitor for SlowNetworkMonitor {
+        async fn get_network_info(&self) -> bridge_traits::error::Result<NetworkInfo> {
+            Ok(NetworkInfo {
+                status: NetworkStatus::Connected,
+                network_type: Some(NetworkType::Cellular),
+                is_metered: true,
+                is_expensive: false,
+                is_captive_portal: None,
+                is_vpn: None,
+            })
+        }
+
+        async fn estimated_downlink_mbps(&self) -> bridge_traits::error::Result<Option<f64>> {
+            Ok(Some(self.downlink_mbps))
+        }
+
+        async fn subscribe_changes(
+            &self,
+        ) -> bridge_traits::error::Result<Box<dyn bridge_traits::network::NetworkChangeStream>>
+        {
+            Err(BridgeError::NotAvailable("subscribe_changes".to_string()))
+        }
+    }
+
+    #[core_async::test]
+    async fn test_low_downlink_estimate_reduces_concurrency() {
+        let config = SyncConfig {
+            max_concurrent_downloads: 8,
+            low_bandwidth_threshold_mbps: Some(10.0),
+            ..SyncConfig::default()
+        };
+        let monitor: Arc<dyn NetworkMonitor> = Arc::new(SlowNetworkMonitor { downlink_mbps: 2.5 });
+        let (coordinator, _, _) = setup_test_coordinator_with(config.clone(), Some(monitor)).await;
+        assert_eq!(coordinator.download_concurrency().await, 2);
+
+        // Very slow links still download one file at a time
+        let monitor: Arc<dyn NetworkMonitor> = Arc::new(SlowNetworkMonitor { downlink_mbps: 0.1 });
+        let (coordinator, _, _) = setup_test_coordinator_with(config.clone(), Some(monitor)).await;
+        assert_eq!(coordinator.download_concurrency().await, 1);
+
+        // Without a threshold the estimate is ignored
+        let monitor: Arc<dyn NetworkMonitor> = Arc::new(SlowNetworkMonitor { downlink_mbps: 0.1 });
+        let config = SyncConfig {
+            low_bandwidth_threshold_mbps: None,
+            ..config
+        };
+        let (coordinator, _, _) = setup_test_coordinator_with(config, Some(monitor)).await;
+        assert_eq!(coordinator.download_concurrency().await, 8);
+    }
+
+    /// Monitor that reports the first update, then streams the rest
+    struct ScriptedNetworkMonitor {
+        updates: Vec<NetworkInfo>,
+    }
+
+    struct ScriptedChanges(std::vec::IntoIter<NetworkInfo>);
+
+    #[async_trait::async_trait]
+    impl bridge_traits::network::NetworkChangeStream for ScriptedChanges {
+        async fn next(&mut self) -> Option<NetworkInfo> {
+            self.0.next()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl NetworkMonitor for ScriptedNetworkMonitor {
+        async fn get_network_info(&self) -> bridge_traits::error::Result<NetworkInfo> {
+            Ok(self.updates[0].clone())
+        }
+
+        async fn subscribe_changes(
+            &self,
+        ) -> bridge_traits::error::Result<Box<dyn bridge_traits::network::NetworkChangeStream>>
+        {
+            let mut changes = self.updates.clone().into_iter();
+            changes.next();
+            Ok(Box::new(ScriptedChanges(changes)))
+        }
+    }
+
+    #[core_async::test]
+    async fn test_wifi_only_sync_stops_when_link_becomes_metered() {
+        let wifi = NetworkInfo {
+            status: NetworkStatus::Connected,
+            network_type: Some(NetworkType::WiFi),
+            is_metered: false,
+            is_expensive: false,
+            is_captive_portal: None,
+            is_vpn: None,
+        };
+        let monitor: Arc<dyn NetworkMonitor> = Arc::new(ScriptedNetworkMonitor {
+            updates: vec![
+                wifi.clone(),
+                wifi.clone(),
+                // Joined a phone hotspot
+                NetworkInfo {
+                    is_metered: true,
+                    ..wifi.clone()
+                },
+            ],
+        });
+        let config = SyncConfig {
+            wifi_only: true,
+            ..SyncConfig::default()
+        };
+        let (coordinator, _, _) = setup_test_coordinator_with(config, Some(monitor)).await;
+
+        let error = timeout(
+            Duration::from_secs(5),
+            coordinator.wait_for_wifi_only_violation(),
+        )
+        .await
+        .expect("metered transition should stop the sync");
+        assert!(
+            matches!(&error, SyncError::Provider(message) if message.contains("metered")),
+            "unexpected error: {error}"
+        );
+    }
+
+    #[core_async::test]
+    async fn test_captive_portal_blocks_sync_with_distinct_error() {
+        let monitor: Arc<dyn NetworkMonitor> = Arc::new(ScriptedNetworkMonitor {
+            updates: vec![NetworkInfo {
+                status: NetworkStatus::Connected,
+                network_type: Some(NetworkType::WiFi),
+                is_metered: false,
+                is_expensive: false,
+                is_captive_portal: Some(true),
+                is_vpn: None,
+            }],
+        });
+        let (coordinator, _, _) =
+            setup_test_coordinator_with(SyncConfig::default(), Some(monitor)).await;
+
+        let result = coordinator.start_full_sync(ProfileId::new()).await;
+        assert!(
+            matches!(result, Err(SyncError::CaptivePortal)),
+            "unexpected result: {result:?}"
+        );
+    }
 }
@@ -11,6 +11,12 @@ pub enum SyncError {
     #[error("Provider error: {0}")]
     Provider(String),
 
+    #[error("No active session")]
+    NotAuthenticated,
+
+    #[error("Network is behind a captive portal; sign in to the network first")]
+    CaptivePortal,
+
     #[error("Sync timeout after {0} seconds")]
     Timeout(u64),
 
@@ -42,7 +42,7 @@
 //! ```
 
 use crate::{Result, SyncError};
-use core_auth::ProviderKind;
+use core_auth::{ProfileId, ProviderKind};
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -316,6 +316,8 @@ pub struct SyncJob {
     pub id: SyncJobId,
     /// The provider being synced
     pub provider_id: ProviderKind,
+    /// Profile the job syncs for, if recorded
+    pub profile_id: Option<ProfileId>,
     /// Current status
     pub status: SyncStatus,
     /// Type of sync
@@ -354,6 +356,7 @@ impl SyncJob {
         Self {
             id: SyncJobId::new(),
             provider_id,
+            profile_id: None,
             status: SyncStatus::Pending,
             sync_type,
             progress: SyncProgress::new(),
@@ -374,6 +377,12 @@ impl SyncJob {
         job
     }
 
+    /// Record the profile the job syncs for
+    pub fn for_profile(mut self, profile_id: ProfileId) -> Self {
+        self.profile_id = Some(profile_id);
+        self
+    }
+
     /// Start the sync job
     ///
     /// # Errors
@@ -387,6 +396,31 @@ impl SyncJob {
         Ok(self)
     }
 
+    /// Resume an interrupted job from its saved cursor
+    ///
+    /// A job left `Running` by a crash, or one that `Failed` part way
+    /// through, goes back to `Running` with its cursor and progress intact.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the job is pending, completed or cancelled
+    pub fn resume(mut self) -> Result<Self> {
+        if !matches!(self.status, SyncStatus::Running | SyncStatus::Failed) {
+            return Err(SyncError::InvalidStateTransition {
+                from: self.status.as_str().to_string(),
+                to: SyncStatus::Running.as_str().to_string(),
+                reason: "Only interrupted jobs can be resumed".to_string(),
+            });
+        }
+
+        self.status = SyncStatus::Running;
+        self.completed_at = None;
+        self.error_message = None;
+        self.error_details = None;
+        self.progress.phase = "Resuming sync".to_string();
+        Ok(self)
+    }
+
     /// Update progress information
     ///
     /// # Errors
@@ -755,6 +789,31 @@ mod tests {
         assert_eq!(job.progress.phase, "Failed");
     }
 
+    #[test]
+    fn test_sync_job_resume_after_failure() {
+        let mut job = SyncJob::new(ProviderKind::GoogleDrive, SyncType::Full)
+            .start()
+            .unwrap();
+        job.update_cursor("page-3".to_string()).unwrap();
+        let job = job.fail("Connection reset".to_string(), None).unwrap();
+
+        let job = job.resume().unwrap();
+
+        assert_eq!(job.status, SyncStatus::Running);
+        assert_eq!(job.cursor.as_deref(), Some("page-3"));
+        assert!(job.completed_at.is_none());
+        assert!(job.error_message.is_none());
+    }
+
+    #[test]
+    fn test_sync_job_resume_invalid_state() {
+        let job = SyncJob::new(ProviderKind::GoogleDrive, SyncType::Full);
+        assert!(job.clone().resume().is_err());
+
+        let completed = job.start().unwrap().complete(SyncJobStats::new()).unwrap();
+        assert!(completed.resume().is_err());
+    }
+
     #[test]
     fn test_sync_job_cancel() {
         let job = SyncJob::new(ProviderKind::GoogleDrive, SyncType::Full);
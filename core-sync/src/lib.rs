@@ -18,6 +18,9 @@
 //! - **Conflict Resolver** (`conflict_resolver`): Handles renames, duplicates, and deletions
 //! - **Repository** (`repository`): Database persistence for sync jobs and queue items
 //! - **Sync Coordinator** (`coordinator`): Orchestrates full and incremental synchronization
+//! - **Parallel Downloader** (`parallel_download`): Downloads large files as concurrent byte ranges
+//! - **Non-Music Classifier** (`non_music`): Flags podcasts and voice memos by tags, path and encoding
+//! - **WASM Bindings** (`wasm`): JavaScript surface for the sync coordinator
 
 pub mod conflict_resolution_orchestrator;
 pub mod conflict_resolver;
@@ -25,19 +28,29 @@ pub mod coordinator;
 pub mod error;
 pub mod job;
 pub mod metadata_processor;
+pub mod non_music;
+pub mod parallel_download;
 pub mod repository;
 pub mod scan_queue;
 
+#[cfg(target_arch = "wasm32")]
+pub mod wasm;
+
 pub use conflict_resolution_orchestrator::{
     ConflictResolutionOrchestrator, ConflictResolutionStats,
 };
 pub use conflict_resolver::{
     ConflictPolicy, ConflictResolver, DuplicateSet, MetadataConflict, ResolutionResult,
 };
-pub use coordinator::{SyncConfig, SyncCoordinator};
+pub use coordinator::{group_duplicates, DiscoveredDuplicateSet, SyncConfig, SyncCoordinator};
 pub use error::{Result, SyncError};
 pub use job::{SyncJob, SyncJobId, SyncJobStats, SyncProgress, SyncStatus, SyncType};
-pub use metadata_processor::{MetadataProcessor, ProcessingResult, ProcessorConfig};
+pub use metadata_processor::{
+    CompilationPolicy, IdStrategy, MetadataProcessor, ProcessingResult, ProcessorConfig,
+    TrackRelations,
+};
+pub use non_music::{AudioProfile, NonMusicAction, NonMusicFilter, NonMusicSignal};
+pub use parallel_download::{split_ranges, ByteRange, ParallelDownloadConfig, ParallelDownloader};
 pub use repository::{SqliteSyncJobRepository, SyncJobRepository};
 pub use scan_queue::{
     Priority, QueueStats, ScanQueue, ScanQueueRepository, SqliteScanQueueRepository, WorkItem,
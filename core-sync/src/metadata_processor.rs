@@ -19,8 +19,10 @@
 //! 2. Save to temporary location via `FileSystemAccess`
 //! 3. Extract metadata using `MetadataExtractor`
 //! 4. Resolve or create Artist and Album entities
+//!    (`resolve_relations` does this for a whole batch of tracks at once)
 //! 5. Create or update Track entity
 //! 6. Extract and store embedded artwork if present
+//!    (and measure loudness when `ProcessorConfig::analyze_loudness` is set)
 //! 7. Clean up temporary files
 //! 8. Return processing result with statistics
 //!
@@ -33,19 +35,26 @@
 //! - Corrupted files are logged but don't fail the entire sync
 
 use crate::error::{Result, SyncError};
+use crate::non_music::{AudioProfile, NonMusicAction, NonMusicFilter};
 use crate::scan_queue::WorkItem;
 use bridge_traits::database::DatabaseAdapter;
-use bridge_traits::storage::{FileSystemAccess, StorageProvider};
+use bridge_traits::storage::{FileSystemAccess, RemoteFile, StorageProvider};
 use bridge_traits::time::{Clock, SystemClock};
 use bytes::Bytes;
 use core_library::models::{Album, AlbumId, Artist, ArtistId, Track, TrackId};
 use core_library::repositories::{
-    AlbumRepository, ArtistRepository, ArtworkRepository, TrackRepository,
+    AlbumKey, AlbumKeyOptions, AlbumRepository, ArtistRepository, ArtworkRepository,
+    TrackRepository,
 };
+use core_library::text::normalize_search_text;
 use core_metadata::artwork::ArtworkService;
+use core_metadata::error::{MetadataError, SkipReason};
 use core_metadata::extractor::{ExtractedMetadata, MetadataExtractor};
+use core_playback::{AudioSource, Loudness, LoudnessAnalyzer, SymphoniaDecoder};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::{debug, error, info, warn};
 
 /// Result of processing a single work item
@@ -63,6 +72,82 @@ pub struct ProcessingResult {
     pub processing_time_ms: u64,
 }
 
+/// Artist and album a track is filed under
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrackRelations {
+    /// Primary artist, if the track names one
+    pub artist_id: Option<ArtistId>,
+    /// Album, if the track names one
+    pub album_id: Option<AlbumId>,
+}
+
+/// How ids are assigned to newly created tracks, albums and artists
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IdStrategy {
+    /// Random UUIDv4 ids
+    #[default]
+    Random,
+
+    /// UUIDv5 ids derived from stable content
+    ///
+    /// Tracks are keyed by provider and provider file id, artists by
+    /// normalized name and albums by normalized name and artist, so
+    /// re-importing a library reproduces the same ids on any device.
+    Deterministic,
+}
+
+/// How albums with tracks by several artists are credited
+///
+/// By default albums are credited to each track's artist, so a compilation
+/// splits into one album per performer. Tag-driven detection trusts the album
+/// artist tag; the distinct-artist heuristic groups untagged albums and
+/// credits those with enough performers to `va_artist_name`.
+///
+/// The heuristic needs every track of an album, so it only applies to
+/// [`MetadataProcessor::resolve_relations`]; tracks processed one at a time
+/// only follow the album artist tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompilationPolicy {
+    /// Credit albums to the album artist tag when a track has one
+    /// (default: false)
+    pub use_albumartist_tag: bool,
+
+    /// Distinct track artists at which an untagged album becomes a
+    /// compilation (default: `None`, never)
+    ///
+    /// Below the threshold the album stays whole under its most frequent
+    /// track artist.
+    pub va_threshold_distinct_artists: Option<usize>,
+
+    /// Artist compilations are credited to, also used for album artist tags
+    /// such as "Various" or "VA" (default: "Various Artists")
+    pub va_artist_name: String,
+}
+
+impl Default for CompilationPolicy {
+    fn default() -> Self {
+        Self {
+            use_albumartist_tag: false,
+            va_threshold_distinct_artists: None,
+            va_artist_name: "Various Artists".to_string(),
+        }
+    }
+}
+
+impl CompilationPolicy {
+    /// Album artist named by a track's tags, if the policy trusts them
+    fn tagged_album_artist<'a>(&'a self, metadata: &'a ExtractedMetadata) -> Option<&'a str> {
+        if !self.use_albumartist_tag {
+            return None;
+        }
+        let name = trimmed(&metadata.album_artist)?;
+        match normalize_name(name).as_str() {
+            "various artists" | "various" | "va" => Some(&self.va_artist_name),
+            _ => Some(name),
+        }
+    }
+}
+
 /// Configuration for metadata processing
 #[derive(Debug, Clone)]
 pub struct ProcessorConfig {
@@ -84,6 +169,27 @@ pub struct ProcessorConfig {
 
     /// Timeout for download operations (seconds)
     pub download_timeout_secs: u64,
+
+    /// How ids are assigned to new entities
+    pub id_strategy: IdStrategy,
+
+    /// Subdirectory of the cache directory that holds in-flight downloads
+    pub temp_subdirectory: String,
+
+    /// Which album name qualifiers to ignore when matching existing albums
+    pub album_key: AlbumKeyOptions,
+
+    /// When albums are credited to their album artist or to Various Artists
+    pub compilation: CompilationPolicy,
+
+    /// Heuristics for skipping or tagging podcasts and voice memos
+    /// (default: `None`, everything is imported as music)
+    pub non_music: Option<NonMusicFilter>,
+
+    /// Whether to measure EBU R128 loudness and store a ReplayGain value on
+    /// each track. Needs the whole file, so it is skipped in header-only mode
+    /// (default: false)
+    pub analyze_loudness: bool,
 }
 
 impl Default for ProcessorConfig {
@@ -95,6 +201,60 @@ impl Default for ProcessorConfig {
             update_existing: false,
             max_download_retries: 3,
             download_timeout_secs: 300, // 5 minutes
+            id_strategy: IdStrategy::Random,
+            temp_subdirectory: "sync_temp".to_string(),
+            album_key: AlbumKeyOptions::default(),
+            compilation: CompilationPolicy::default(),
+            non_music: None,
+            analyze_loudness: false,
+        }
+    }
+}
+
+/// Downloaded file awaiting processing
+///
+/// Call [`TempFile::remove`] once processing finishes, whether it succeeded or
+/// not. A guard dropped without removal (e.g. a cancelled sync) leaves the file
+/// for [`MetadataProcessor::cleanup_temp`] to sweep on the next startup.
+struct TempFile {
+    path: PathBuf,
+    file_system: Arc<dyn FileSystemAccess>,
+    removed: bool,
+}
+
+impl TempFile {
+    fn new(path: PathBuf, file_system: Arc<dyn FileSystemAccess>) -> Self {
+        Self {
+            path,
+            file_system,
+            removed: false,
+        }
+    }
+
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    async fn remove(mut self) {
+        self.removed = true;
+        match self.file_system.exists(&self.path).await {
+            Ok(false) => {}
+            _ => {
+                if let Err(e) = self.file_system.delete_file(&self.path).await {
+                    warn!("Failed to clean up temporary file {:?}: {}", self.path, e);
+                }
+            }
+        }
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        if !self.removed {
+            warn!(
+                "Temporary file {:?} abandoned; it will be removed by the next cleanup",
+                self.path
+            );
         }
     }
 }
@@ -105,9 +265,7 @@ pub struct MetadataProcessor {
     metadata_extractor: Arc<MetadataExtractor>,
     file_system: Arc<dyn FileSystemAccess>,
     track_repository: Arc<dyn TrackRepository>,
-    #[allow(dead_code)]
     artist_repository: Arc<dyn ArtistRepository>,
-    #[allow(dead_code)]
     album_repository: Arc<dyn AlbumRepository>,
     #[allow(dead_code)]
     artwork_repository: Arc<dyn ArtworkRepository>,
@@ -202,6 +360,7 @@ impl MetadataProcessor {
     /// * `provider` - Storage provider to download from
     /// * `provider_id` - The provider ID (profile ID) for this track
     /// * `remote_file` - The remote file metadata containing name and path
+    /// * `force_update` - Update an existing track even if `update_existing` is disabled
     ///
     /// # Returns
     ///
@@ -213,6 +372,9 @@ impl MetadataProcessor {
     /// - Download fails after retries
     /// - File system operations fail
     /// - Metadata extraction fails completely
+    /// - The file is empty or classified as non-music
+    ///   (`SyncError::Metadata(MetadataError::Skipped(_))`)
+    /// - The file is truncated (`SyncError::Metadata(MetadataError::Truncated(_))`)
     /// - Database operations fail
     pub async fn process_work_item(
         &self,
@@ -220,6 +382,7 @@ impl MetadataProcessor {
         provider: &Arc<dyn StorageProvider>,
         provider_id: &str,
         file_name: &str,
+        force_update: bool,
     ) -> Result<ProcessingResult> {
         let start_time = self.clock.unix_timestamp_millis();
 
@@ -229,7 +392,7 @@ impl MetadataProcessor {
         );
 
         // Step 1: Download file to temporary location
-        let (temp_path, bytes_downloaded) = self
+        let (temp_file, bytes_downloaded) = self
             .download_file(work_item, provider, file_name)
             .await
             .map_err(|e| {
@@ -240,15 +403,66 @@ impl MetadataProcessor {
                 e
             })?;
 
+        let result = self
+            .process_downloaded(
+                work_item,
+                provider_id,
+                file_name,
+                force_update,
+                temp_file.path(),
+                bytes_downloaded,
+                start_time,
+            )
+            .await;
+
+        // Clean up the temporary file however processing ended
+        temp_file.remove().await;
+
+        result
+    }
+
+    /// Persist a downloaded file (steps 2-9 of `process_work_item`)
+    #[allow(clippy::too_many_arguments)]
+    async fn process_downloaded(
+        &self,
+        work_item: &WorkItem,
+        provider_id: &str,
+        file_name: &str,
+        force_update: bool,
+        temp_path: &Path,
+        bytes_downloaded: u64,
+        start_time: i64,
+    ) -> Result<ProcessingResult> {
         // Step 2: Extract metadata
-        let metadata = match self.extract_metadata(&temp_path).await {
-            Ok(meta) => meta,
-            Err(e) => {
-                // Clean up temp file on error
-                self.cleanup_temp_file(&temp_path).await;
-                error!("Failed to extract metadata from {}: {}", file_name, e);
-                return Err(e);
+        let mut metadata = self.extract_metadata(temp_path).await.map_err(|e| {
+            error!("Failed to extract metadata from {}: {}", file_name, e);
+            e
+        })?;
+
+        // Podcasts, voice memos and the like are skipped or filed apart
+        if let Some(filter) = &self.config.non_music {
+            let profile = AudioProfile::from_metadata(&metadata, file_name);
+            if let Some(signals) = filter.classify(&profile) {
+                match filter.action {
+                    NonMusicAction::Skip => {
+                        debug!("Classified {} as non-music: {:?}", file_name, signals);
+                        return Err(SyncError::Metadata(MetadataError::Skipped(
+                            SkipReason::NonMusic,
+                        )));
+                    }
+                    NonMusicAction::Tag => {
+                        info!("Tagging {} as non-music: {:?}", file_name, signals);
+                        metadata.genre = Some(filter.tag_genre.clone());
+                    }
+                }
             }
+        }
+
+        // Loudness needs the decoded audio, so only full downloads are analyzed
+        let loudness = if self.config.analyze_loudness && !self.config.header_only {
+            self.analyze_loudness(temp_path, file_name).await
+        } else {
+            None
         };
 
         // Step 3: Check if track already exists
@@ -259,10 +473,21 @@ impl MetadataProcessor {
 
         let is_new = existing_track.is_none();
 
+        // A soft-deleted track keeps its derived id, so a file that comes back
+        // reclaims that track instead of colliding with it
+        let reclaimed_track = match (&existing_track, self.config.id_strategy) {
+            (None, IdStrategy::Deterministic) => {
+                let track_id = TrackId::from_provider_file(provider_id, &work_item.remote_file_id);
+                self.track_repository
+                    .find_by_id(&track_id.to_string())
+                    .await?
+            }
+            _ => None,
+        };
+
         // Skip if track exists and update_existing is false
-        if !is_new && !self.config.update_existing {
+        if !is_new && !self.config.update_existing && !force_update {
             debug!("Track already exists, skipping: {}", file_name);
-            self.cleanup_temp_file(&temp_path).await;
             return Ok(ProcessingResult {
                 is_new: false,
                 track_id: existing_track.unwrap().id,
@@ -279,65 +504,101 @@ impl MetadataProcessor {
             .await
             .map_err(|e| SyncError::Internal(format!("Failed to begin transaction: {}", e)))?;
 
-        // Step 5: Resolve or create artist
-        let artist_id = self
-            .resolve_or_create_artist(&metadata, tx_id)
-            .await
-            .map_err(|e| {
-                error!("Failed to resolve artist: {}", e);
-                e
-            })?;
+        // Steps 5-8 run inside the transaction, which is rolled back on any error
+        let written = async {
+            // Step 5: Resolve or create artist
+            let artist_id = self
+                .resolve_or_create_artist(&metadata, tx_id)
+                .await
+                .map_err(|e| {
+                    error!("Failed to resolve artist: {}", e);
+                    e
+                })?;
+
+            // Step 6: Resolve or create album, credited to the album artist
+            let album_artist_id = match self.config.compilation.tagged_album_artist(&metadata) {
+                Some(name) => Some(self.resolve_or_create_artist_named(name, tx_id).await?),
+                None => artist_id,
+            };
+            let album_id = self
+                .resolve_or_create_album(&metadata, album_artist_id.as_ref(), tx_id)
+                .await
+                .map_err(|e| {
+                    error!("Failed to resolve album: {}", e);
+                    e
+                })?;
+
+            // Step 7: Process embedded artwork if configured
+            let mut artwork_processed = false;
+            let artwork_id = if self.config.extract_artwork && !metadata.artwork.is_empty() {
+                match self.process_artwork(&metadata, tx_id).await {
+                    Ok(Some(id)) => {
+                        artwork_processed = true;
+                        Some(id)
+                    }
+                    Ok(None) => None,
+                    Err(e) => {
+                        warn!("Failed to process artwork for {}: {}", file_name, e);
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+
+            // Step 7: Create or update track
+            let track_id = if let Some(reclaimed_track) = reclaimed_track {
+                self.reclaim_track(
+                    &reclaimed_track,
+                    &work_item.remote_file_id,
+                    &metadata,
+                    artist_id,
+                    album_id,
+                    artwork_id,
+                    tx_id,
+                )
+                .await?
+            } else if is_new {
+                self.create_track(
+                    work_item,
+                    &metadata,
+                    provider_id,
+                    artist_id,
+                    album_id,
+                    artwork_id,
+                    tx_id,
+                    file_name,
+                )
+                .await?
+            } else {
+                self.update_track(
+                    &existing_track.unwrap(),
+                    &metadata,
+                    artist_id,
+                    album_id,
+                    artwork_id,
+                    tx_id,
+                )
+                .await?
+            };
 
-        // Step 6: Resolve or create album
-        let album_id = self
-            .resolve_or_create_album(&metadata, artist_id.as_ref(), tx_id)
-            .await
-            .map_err(|e| {
-                error!("Failed to resolve album: {}", e);
-                e
-            })?;
+            // Step 8: Store loudness analysis
+            if let Some(loudness) = loudness {
+                self.store_loudness(&track_id, &loudness, tx_id).await?;
+            }
 
-        // Step 7: Process embedded artwork if configured
-        let mut artwork_processed = false;
-        let artwork_id = if self.config.extract_artwork && !metadata.artwork.is_empty() {
-            match self.process_artwork(&metadata).await {
-                Ok(Some(id)) => {
-                    artwork_processed = true;
-                    Some(id)
-                }
-                Ok(None) => None,
-                Err(e) => {
-                    warn!("Failed to process artwork for {}: {}", file_name, e);
-                    None
+            Ok::<_, SyncError>((track_id, artwork_processed))
+        }
+        .await;
+
+        let (track_id, artwork_processed) = match written {
+            Ok(written) => written,
+            Err(e) => {
+                if let Err(rollback_error) = self.db.rollback_transaction(tx_id).await {
+                    warn!("Failed to roll back transaction: {}", rollback_error);
                 }
+                return Err(e);
             }
-        } else {
-            None
-        };
-
-        // Step 7: Create or update track
-        let track_id = if is_new {
-            self.create_track(
-                work_item,
-                &metadata,
-                provider_id,
-                artist_id,
-                album_id,
-                artwork_id,
-                tx_id,
-                file_name,
-            )
-            .await?
-        } else {
-            self.update_track(
-                &existing_track.unwrap(),
-                &metadata,
-                artist_id,
-                album_id,
-                artwork_id,
-                tx_id,
-            )
-            .await?
         };
 
         // Step 9: Commit transaction
@@ -346,9 +607,6 @@ impl MetadataProcessor {
             .await
             .map_err(|e| SyncError::Internal(format!("Failed to commit transaction: {}", e)))?;
 
-        // Step 10: Clean up temporary file
-        self.cleanup_temp_file(&temp_path).await;
-
         let processing_time_ms = self.elapsed_since(start_time);
 
         info!(
@@ -365,20 +623,161 @@ impl MetadataProcessor {
         })
     }
 
-    /// Download file from provider to temporary location
-    async fn download_file(
+    /// Check whether a remote file is already in the library and unchanged
+    ///
+    /// A file is unchanged when a track exists for it and the provider MD5
+    /// checksum recorded on that track matches the remote one. When either
+    /// side has no checksum, the provider modification time recorded on the
+    /// track must not be older than the remote one instead. Tracks with
+    /// neither recorded are treated as stale.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the track lookup fails
+    pub async fn is_unchanged(&self, provider_id: &str, remote_file: &RemoteFile) -> Result<bool> {
+        let row = self
+            .db
+            .query_one_optional(
+                "SELECT provider_md5, provider_modified_at FROM tracks \
+                 WHERE provider_id = ? AND provider_file_id = ?",
+                &[
+                    bridge_traits::database::QueryValue::Text(provider_id.to_string()),
+                    bridge_traits::database::QueryValue::Text(remote_file.id.clone()),
+                ],
+            )
+            .await
+            .map_err(|e| SyncError::Database(e.to_string()))?;
+        let Some(row) = row else {
+            return Ok(false);
+        };
+
+        let remote_md5 = remote_file
+            .md5_checksum
+            .as_deref()
+            .filter(|md5| !md5.is_empty());
+        let stored_md5 = row.get("provider_md5").and_then(|value| value.as_string());
+        if let (Some(remote_md5), Some(stored_md5)) = (remote_md5, stored_md5) {
+            return Ok(stored_md5.eq_ignore_ascii_case(remote_md5));
+        }
+
+        let stored_modified_at = row
+            .get("provider_modified_at")
+            .and_then(|value| value.as_i64());
+        Ok(match (stored_modified_at, remote_file.modified_at) {
+            (Some(stored), Some(remote)) => stored >= remote,
+            _ => false,
+        })
+    }
+
+    /// Record the provider version a track was synced from
+    ///
+    /// Stores the provider modification time and MD5 checksum so a later
+    /// rescan can recognise the track as up to date. Values that are `None`
+    /// leave the recorded ones in place.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database update fails
+    pub async fn record_provider_version(
         &self,
-        work_item: &WorkItem,
-        provider: &Arc<dyn StorageProvider>,
-        file_name: &str,
-    ) -> Result<(PathBuf, u64)> {
+        track_id: &str,
+        modified_at: Option<i64>,
+        md5_checksum: Option<&str>,
+    ) -> Result<()> {
+        use bridge_traits::database::QueryValue;
+
+        self.db
+            .execute(
+                "UPDATE tracks SET provider_modified_at = COALESCE(?, provider_modified_at), \
+                 provider_md5 = COALESCE(?, provider_md5) WHERE id = ?",
+                &[
+                    modified_at.map_or(QueryValue::Null, QueryValue::Integer),
+                    md5_checksum
+                        .filter(|md5| !md5.is_empty())
+                        .map_or(QueryValue::Null, |md5| QueryValue::Text(md5.to_lowercase())),
+                    QueryValue::Text(track_id.to_string()),
+                ],
+            )
+            .await
+            .map_err(|e| SyncError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Directory holding in-flight downloads
+    pub async fn temp_directory(&self) -> Result<PathBuf> {
         let cache_dir =
             self.file_system.get_cache_directory().await.map_err(|e| {
                 SyncError::Provider(format!("Failed to get cache directory: {}", e))
             })?;
+        Ok(cache_dir.join(&self.config.temp_subdirectory))
+    }
 
+    /// Remove temporary files older than `older_than`
+    ///
+    /// Processing removes its own temporary files, so anything left behind was
+    /// abandoned by a crash or a cancelled sync. Run this on startup, before
+    /// any processing begins. Returns the number of files removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the temp directory can't be listed. Files that
+    /// can't be inspected or deleted are logged and skipped.
+    pub async fn cleanup_temp(&self, older_than: Duration) -> Result<usize> {
+        let temp_dir = self.temp_directory().await?;
+        let exists =
+            self.file_system.exists(&temp_dir).await.map_err(|e| {
+                SyncError::Internal(format!("Failed to check temp directory: {}", e))
+            })?;
+        if !exists {
+            return Ok(0);
+        }
+
+        let entries = self
+            .file_system
+            .list_directory(&temp_dir)
+            .await
+            .map_err(|e| SyncError::Internal(format!("Failed to list temp directory: {}", e)))?;
+        let cutoff = self.clock.unix_timestamp() - older_than.as_secs() as i64;
+
+        let mut removed = 0;
+        for path in entries {
+            let stale = match self.file_system.metadata(&path).await {
+                Ok(metadata) => {
+                    !metadata.is_directory
+                        && metadata
+                            .modified_at
+                            .is_some_and(|modified| modified <= cutoff)
+                }
+                Err(e) => {
+                    warn!("Failed to inspect temporary file {:?}: {}", path, e);
+                    false
+                }
+            };
+            if !stale {
+                continue;
+            }
+
+            match self.file_system.delete_file(&path).await {
+                Ok(()) => removed += 1,
+                Err(e) => warn!("Failed to remove temporary file {:?}: {}", path, e),
+            }
+        }
+
+        if removed > 0 {
+            info!("Removed {} orphaned temporary files", removed);
+        }
+        Ok(removed)
+    }
+
+    /// Download file from provider to temporary location
+    async fn download_file(
+        &self,
+        work_item: &WorkItem,
+        provider: &Arc<dyn StorageProvider>,
+        file_name: &str,
+    ) -> Result<(TempFile, u64)> {
         // Create temp directory if it doesn't exist
-        let temp_dir = cache_dir.join("sync_temp");
+        let temp_dir = self.temp_directory().await?;
         self.file_system
             .create_dir_all(&temp_dir)
             .await
@@ -424,15 +823,23 @@ impl MetadataProcessor {
 
         let bytes_downloaded = data.len() as u64;
 
-        // Write to temporary file
-        self.file_system
-            .write_file(&temp_path, data)
-            .await
-            .map_err(|e| SyncError::Provider(format!("Failed to write temporary file: {}", e)))?;
+        // Write to temporary file; the guard also covers a partial write
+        let temp_file = TempFile::new(temp_path, self.file_system.clone());
+        if let Err(e) = self.file_system.write_file(temp_file.path(), data).await {
+            temp_file.remove().await;
+            return Err(SyncError::Provider(format!(
+                "Failed to write temporary file: {}",
+                e
+            )));
+        }
 
-        debug!("Downloaded {} bytes to {:?}", bytes_downloaded, temp_path);
+        debug!(
+            "Downloaded {} bytes to {:?}",
+            bytes_downloaded,
+            temp_file.path()
+        );
 
-        Ok((temp_path, bytes_downloaded))
+        Ok((temp_file, bytes_downloaded))
     }
 
     /// Download with timeout
@@ -462,7 +869,245 @@ impl MetadataProcessor {
         self.metadata_extractor
             .extract_from_bytes(file_data.as_ref(), path)
             .await
-            .map_err(|e| SyncError::Internal(format!("Metadata extraction failed: {}", e)))
+            .map_err(|e| match e {
+                // Keep per-file outcomes typed so the coordinator can tell them apart
+                MetadataError::Skipped(_) | MetadataError::Truncated(_) => SyncError::Metadata(e),
+                _ => SyncError::Internal(format!("Metadata extraction failed: {}", e)),
+            })
+    }
+
+    /// Measure the loudness of a fully downloaded file
+    ///
+    /// Analysis is best effort: files that fail to decode are imported
+    /// without a gain.
+    async fn analyze_loudness(&self, path: &Path, file_name: &str) -> Option<Loudness> {
+        let analysis = async {
+            let data = self
+                .file_system
+                .read_file(path)
+                .await
+                .map_err(|e| format!("failed to read temp file: {}", e))?;
+            let source = AudioSource::CachedChunk {
+                data,
+                codec_hint: None,
+            };
+            let mut decoder = SymphoniaDecoder::new(source)
+                .await
+                .map_err(|e| e.to_string())?;
+            let loudness = LoudnessAnalyzer::analyze(&mut decoder)
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok::<_, String>(loudness)
+        };
+
+        match analysis.await {
+            Ok(loudness) => {
+                debug!(
+                    "Measured {}: {:.1} LUFS, {:.1} dBTP",
+                    file_name, loudness.integrated_lufs, loudness.true_peak_dbtp
+                );
+                Some(loudness)
+            }
+            Err(e) => {
+                warn!("Failed to analyze loudness of {}: {}", file_name, e);
+                None
+            }
+        }
+    }
+
+    /// Store measured loudness on a track, in millibels
+    async fn store_loudness(
+        &self,
+        track_id: &str,
+        loudness: &Loudness,
+        tx_id: bridge_traits::database::TransactionId,
+    ) -> Result<()> {
+        let millibels = |db: f64| {
+            if db.is_finite() {
+                bridge_traits::database::QueryValue::Integer((db * 100.0).round() as i64)
+            } else {
+                bridge_traits::database::QueryValue::Null
+            }
+        };
+
+        self.db
+            .execute_in_transaction(
+                tx_id,
+                "UPDATE tracks SET replay_gain_millibels = ?, true_peak_millibels = ? WHERE id = ?",
+                &[
+                    millibels(loudness.gain_db()),
+                    millibels(loudness.true_peak_dbtp),
+                    bridge_traits::database::QueryValue::Text(track_id.to_string()),
+                ],
+            )
+            .await
+            .map_err(|e| SyncError::Internal(format!("Failed to store loudness: {}", e)))?;
+        Ok(())
+    }
+
+    /// Resolve or create the artists and albums for a batch of tracks
+    ///
+    /// Distinct artists and albums across the batch are looked up and created
+    /// in bulk, so the number of queries depends on the batch size rather
+    /// than growing by two per track. Names are matched the same way as in
+    /// `process_work_item`, and albums are credited following
+    /// [`ProcessorConfig::compilation`].
+    ///
+    /// # Returns
+    ///
+    /// The relations of each track, in the order of `metadata`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a lookup or insert fails
+    pub async fn resolve_relations(
+        &self,
+        metadata: &[ExtractedMetadata],
+    ) -> Result<Vec<TrackRelations>> {
+        let now = chrono::Utc::now().timestamp();
+        let album_artists = self.album_artists(metadata);
+
+        let artists: Vec<Artist> = metadata
+            .iter()
+            .filter_map(|metadata| trimmed(&metadata.artist))
+            .chain(album_artists.iter().flatten().copied())
+            .map(|name| {
+                let normalized_name = normalize_name(name);
+                let artist_id = match self.config.id_strategy {
+                    IdStrategy::Random => ArtistId::new(),
+                    IdStrategy::Deterministic => ArtistId::from_normalized_name(&normalized_name),
+                };
+                Artist {
+                    id: artist_id.to_string(),
+                    name: name.to_string(),
+                    normalized_name,
+                    sort_name: None,
+                    bio: None,
+                    country: None,
+                    created_at: now,
+                    updated_at: now,
+                }
+            })
+            .collect();
+        let artist_ids = self.artist_repository.get_or_create_many(&artists).await?;
+        let artist_ids = artist_ids
+            .into_iter()
+            .map(|(name, id)| Ok((name, parse_artist_id(&id)?)))
+            .collect::<Result<HashMap<_, _>>>()?;
+        let artist_named = |name: &str| artist_ids.get(&normalize_name(name));
+
+        let albums: Vec<Album> = metadata
+            .iter()
+            .zip(&album_artists)
+            .filter_map(|(metadata, album_artist)| {
+                Some((metadata, *album_artist, trimmed(&metadata.album)?))
+            })
+            .map(|(metadata, album_artist, name)| {
+                let normalized_name = self.album_key_name(name);
+                let artist_id = album_artist.and_then(artist_named);
+                let album_id = match self.config.id_strategy {
+                    IdStrategy::Random => AlbumId::new(),
+                    IdStrategy::Deterministic => {
+                        AlbumId::from_normalized_name(&normalized_name, artist_id)
+                    }
+                };
+                Album {
+                    id: album_id.to_string(),
+                    name: name.to_string(),
+                    normalized_name,
+                    artist_id: artist_id.map(ToString::to_string),
+                    year: metadata.year,
+                    genre: metadata.genre.clone(),
+                    artwork_id: None,
+                    track_count: 0,
+                    total_duration_ms: 0,
+                    created_at: now,
+                    updated_at: now,
+                }
+            })
+            .collect();
+        let album_ids = self.album_repository.get_or_create_many(&albums).await?;
+
+        metadata
+            .iter()
+            .zip(&album_artists)
+            .map(|(metadata, album_artist)| {
+                let artist_id = trimmed(&metadata.artist).and_then(artist_named).cloned();
+                let album_id = match trimmed(&metadata.album) {
+                    Some(name) => {
+                        let key = AlbumKey {
+                            normalized_name: self.album_key_name(name),
+                            artist_id: album_artist.and_then(artist_named).map(ToString::to_string),
+                        };
+                        album_ids
+                            .get(&key)
+                            .map(|id| parse_album_id(id))
+                            .transpose()?
+                    }
+                    None => None,
+                };
+                Ok(TrackRelations {
+                    artist_id,
+                    album_id,
+                })
+            })
+            .collect()
+    }
+
+    /// Album artist credited for each track of a batch
+    ///
+    /// Follows [`CompilationPolicy`]: the album artist tag when trusted, then
+    /// the distinct-artist heuristic when enabled, then the track artist.
+    fn album_artists<'a>(&'a self, metadata: &'a [ExtractedMetadata]) -> Vec<Option<&'a str>> {
+        let policy = &self.config.compilation;
+
+        // Track artists of each untagged album with their track counts, in
+        // the order they were first seen
+        let mut credits: HashMap<String, Vec<(String, &str, usize)>> = HashMap::new();
+        if policy.va_threshold_distinct_artists.is_some() {
+            for metadata in metadata {
+                if policy.tagged_album_artist(metadata).is_some() {
+                    continue;
+                }
+                let (Some(album), Some(artist)) =
+                    (trimmed(&metadata.album), trimmed(&metadata.artist))
+                else {
+                    continue;
+                };
+                let artists = credits.entry(self.album_key_name(album)).or_default();
+                let normalized_name = normalize_name(artist);
+                match artists
+                    .iter_mut()
+                    .find(|(name, _, _)| *name == normalized_name)
+                {
+                    Some((_, _, count)) => *count += 1,
+                    None => artists.push((normalized_name, artist, 1)),
+                }
+            }
+        }
+
+        metadata
+            .iter()
+            .map(|metadata| {
+                if let Some(name) = policy.tagged_album_artist(metadata) {
+                    return Some(name);
+                }
+                let artists = trimmed(&metadata.album)
+                    .and_then(|album| credits.get(&self.album_key_name(album)));
+                match (artists, policy.va_threshold_distinct_artists) {
+                    (Some(artists), Some(threshold)) if artists.len() >= threshold => {
+                        Some(policy.va_artist_name.as_str())
+                    }
+                    // Most frequent artist, the first seen on ties
+                    (Some(artists), _) => artists
+                        .iter()
+                        .rev()
+                        .max_by_key(|(_, _, count)| *count)
+                        .map(|(_, name, _)| *name),
+                    (None, _) => trimmed(&metadata.artist),
+                }
+            })
+            .collect()
     }
 
     /// Resolve or create artist entity
@@ -471,11 +1116,20 @@ impl MetadataProcessor {
         metadata: &ExtractedMetadata,
         tx_id: bridge_traits::database::TransactionId,
     ) -> Result<Option<ArtistId>> {
-        let artist_name = match &metadata.artist {
-            Some(name) if !name.trim().is_empty() => name.trim(),
-            _ => return Ok(None),
-        };
+        match trimmed(&metadata.artist) {
+            Some(name) => Ok(Some(
+                self.resolve_or_create_artist_named(name, tx_id).await?,
+            )),
+            None => Ok(None),
+        }
+    }
 
+    /// Resolve or create the artist called `artist_name`
+    async fn resolve_or_create_artist_named(
+        &self,
+        artist_name: &str,
+        tx_id: bridge_traits::database::TransactionId,
+    ) -> Result<ArtistId> {
         // Try to find existing artist by normalized name
         let normalized_name = normalize_name(artist_name);
 
@@ -499,12 +1153,16 @@ impl MetadataProcessor {
                 .ok_or_else(|| SyncError::Database("Missing id field".to_string()))?;
             let artist_id = ArtistId::from_string(&id)
                 .map_err(|e| SyncError::Internal(format!("Invalid artist ID: {}", e)))?;
-            return Ok(Some(artist_id));
+            return Ok(artist_id);
         }
 
         // Create new artist
+        let artist_id = match self.config.id_strategy {
+            IdStrategy::Random => ArtistId::new(),
+            IdStrategy::Deterministic => ArtistId::from_normalized_name(&normalized_name),
+        };
         let artist = Artist {
-            id: ArtistId::new().to_string(),
+            id: artist_id.to_string(),
             name: artist_name.to_string(),
             normalized_name: normalized_name.clone(),
             sort_name: None,
@@ -533,9 +1191,13 @@ impl MetadataProcessor {
 
         debug!("Created new artist: {} ({})", artist.name, artist.id);
 
-        Ok(Some(ArtistId::from_string(&artist.id).map_err(|e| {
-            SyncError::Internal(format!("Invalid artist ID: {}", e))
-        })?))
+        ArtistId::from_string(&artist.id)
+            .map_err(|e| SyncError::Internal(format!("Invalid artist ID: {}", e)))
+    }
+
+    /// Normalized name identifying the album called `name`
+    fn album_key_name(&self, name: &str) -> String {
+        normalize_name(self.config.album_key.matching_name(name))
     }
 
     /// Resolve or create album entity
@@ -550,7 +1212,7 @@ impl MetadataProcessor {
             _ => return Ok(None),
         };
 
-        let normalized_name = normalize_name(album_name);
+        let normalized_name = self.album_key_name(album_name);
         let artist_id_str = artist_id.map(|id| id.to_string());
 
         // Try to find existing album by normalized name and artist
@@ -589,8 +1251,12 @@ impl MetadataProcessor {
         }
 
         // Create new album
+        let album_id = match self.config.id_strategy {
+            IdStrategy::Random => AlbumId::new(),
+            IdStrategy::Deterministic => AlbumId::from_normalized_name(&normalized_name, artist_id),
+        };
         let album = Album {
-            id: AlbumId::new().to_string(),
+            id: album_id.to_string(),
             name: album_name.to_string(),
             normalized_name: normalized_name.clone(),
             artist_id: artist_id_str.clone(),
@@ -614,8 +1280,8 @@ impl MetadataProcessor {
                 album.year.map(|y| bridge_traits::database::QueryValue::Integer(y as i64)).unwrap_or(bridge_traits::database::QueryValue::Null),
                 album.genre.as_ref().map(|g| bridge_traits::database::QueryValue::Text(g.clone())).unwrap_or(bridge_traits::database::QueryValue::Null),
                 bridge_traits::database::QueryValue::Null,
-                bridge_traits::database::QueryValue::Integer(album.track_count as i64),
-                bridge_traits::database::QueryValue::Integer(album.total_duration_ms as i64),
+                bridge_traits::database::QueryValue::Integer(album.track_count),
+                bridge_traits::database::QueryValue::Integer(album.total_duration_ms),
                 bridge_traits::database::QueryValue::Integer(album.created_at),
                 bridge_traits::database::QueryValue::Integer(album.updated_at),
             ],
@@ -631,7 +1297,14 @@ impl MetadataProcessor {
     }
 
     /// Process embedded artwork
-    async fn process_artwork(&self, metadata: &ExtractedMetadata) -> Result<Option<String>> {
+    ///
+    /// Artwork is deduplicated by content hash and stored within the track's
+    /// transaction, so a failed track leaves no orphaned artwork behind.
+    async fn process_artwork(
+        &self,
+        metadata: &ExtractedMetadata,
+        tx_id: bridge_traits::database::TransactionId,
+    ) -> Result<Option<String>> {
         if metadata.artwork.is_empty() {
             return Ok(None);
         }
@@ -641,18 +1314,58 @@ impl MetadataProcessor {
             None => return Ok(None),
         };
 
-        // Extract embedded artwork
-        match artwork_service
-            .extract_embedded(metadata.artwork.clone())
-            .await
-        {
-            Ok(ids) if !ids.is_empty() => Ok(Some(ids[0].id.clone())),
-            Ok(_) => Ok(None),
-            Err(e) => Err(SyncError::Internal(format!(
-                "Failed to extract artwork: {}",
-                e
-            ))),
+        let mut first_id = None;
+        for extracted in metadata.artwork.iter().filter(|a| !a.data.is_empty()) {
+            let artwork = artwork_service
+                .prepare_embedded(extracted)
+                .map_err(|e| SyncError::Internal(format!("Failed to extract artwork: {}", e)))?;
+
+            let rows = self
+                .db
+                .query_in_transaction(
+                    tx_id,
+                    "SELECT id FROM artworks WHERE hash = ?",
+                    &[bridge_traits::database::QueryValue::Text(
+                        artwork.hash.clone(),
+                    )],
+                )
+                .await
+                .map_err(|e| SyncError::Internal(format!("Failed to query artwork: {}", e)))?;
+
+            let id = match rows.first().and_then(|row| row.get("id")) {
+                Some(id) => id
+                    .as_string()
+                    .ok_or_else(|| SyncError::Database("Missing id field".to_string()))?,
+                None => {
+                    self.db.execute_in_transaction(
+                        tx_id,
+                        "INSERT INTO artworks (id, hash, mime_type, binary_blob, width, height, file_size, dominant_color, source, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                        &[
+                            bridge_traits::database::QueryValue::Text(artwork.id.clone()),
+                            bridge_traits::database::QueryValue::Text(artwork.hash),
+                            bridge_traits::database::QueryValue::Text(artwork.mime_type),
+                            bridge_traits::database::QueryValue::Blob(artwork.binary_blob),
+                            bridge_traits::database::QueryValue::Integer(artwork.width),
+                            bridge_traits::database::QueryValue::Integer(artwork.height),
+                            bridge_traits::database::QueryValue::Integer(artwork.file_size),
+                            artwork
+                                .dominant_color
+                                .map(bridge_traits::database::QueryValue::Text)
+                                .unwrap_or(bridge_traits::database::QueryValue::Null),
+                            bridge_traits::database::QueryValue::Text(artwork.source),
+                            bridge_traits::database::QueryValue::Integer(artwork.created_at),
+                        ],
+                    )
+                    .await
+                    .map_err(|e| SyncError::Internal(format!("Failed to store artwork: {}", e)))?;
+                    debug!("Stored new artwork {}", artwork.id);
+                    artwork.id
+                }
+            };
+            first_id.get_or_insert(id);
         }
+
+        Ok(first_id)
     }
 
     /// Create new track entity
@@ -668,7 +1381,13 @@ impl MetadataProcessor {
         tx_id: bridge_traits::database::TransactionId,
         file_name: &str,
     ) -> Result<String> {
-        let track_id = TrackId::new().to_string();
+        let track_id = match self.config.id_strategy {
+            IdStrategy::Random => TrackId::new(),
+            IdStrategy::Deterministic => {
+                TrackId::from_provider_file(provider_id, &work_item.remote_file_id)
+            }
+        }
+        .to_string();
         let title = metadata.title.clone().unwrap_or_else(|| {
             Path::new(file_name)
                 .file_stem()
@@ -689,8 +1408,8 @@ impl MetadataProcessor {
                 title, normalized_title, album_id, artist_id, album_artist_id,
                 track_number, disc_number, duration_ms, bitrate, sample_rate,
                 channels, format, mime_type, file_size, artwork_id, lyrics_status,
-                year, genre, composer, comment, created_at, updated_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                year, genre, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
                 &[
                     bridge_traits::database::QueryValue::Text(track_id.clone()),
@@ -760,18 +1479,6 @@ impl MetadataProcessor {
                         .map_or(bridge_traits::database::QueryValue::Null, |g| {
                             bridge_traits::database::QueryValue::Text(g.clone())
                         }),
-                    metadata
-                        .composer
-                        .as_ref()
-                        .map_or(bridge_traits::database::QueryValue::Null, |c| {
-                            bridge_traits::database::QueryValue::Text(c.clone())
-                        }),
-                    metadata
-                        .comment
-                        .as_ref()
-                        .map_or(bridge_traits::database::QueryValue::Null, |c| {
-                            bridge_traits::database::QueryValue::Text(c.clone())
-                        }),
                     bridge_traits::database::QueryValue::Integer(now),
                     bridge_traits::database::QueryValue::Integer(now),
                 ],
@@ -784,6 +1491,43 @@ impl MetadataProcessor {
         Ok(track_id)
     }
 
+    /// Restore a soft-deleted track for a file that reappeared
+    #[allow(clippy::too_many_arguments)]
+    async fn reclaim_track(
+        &self,
+        deleted_track: &Track,
+        provider_file_id: &str,
+        metadata: &ExtractedMetadata,
+        artist_id: Option<ArtistId>,
+        album_id: Option<AlbumId>,
+        artwork_id: Option<String>,
+        tx_id: bridge_traits::database::TransactionId,
+    ) -> Result<String> {
+        self.db
+            .execute_in_transaction(
+                tx_id,
+                "UPDATE tracks SET provider_file_id = ? WHERE id = ?",
+                &[
+                    bridge_traits::database::QueryValue::Text(provider_file_id.to_string()),
+                    bridge_traits::database::QueryValue::Text(deleted_track.id.clone()),
+                ],
+            )
+            .await
+            .map_err(|e| SyncError::Internal(format!("Failed to restore track: {}", e)))?;
+
+        debug!("Reclaimed deleted track {}", deleted_track.id);
+
+        self.update_track(
+            deleted_track,
+            metadata,
+            artist_id,
+            album_id,
+            artwork_id,
+            tx_id,
+        )
+        .await
+    }
+
     /// Update existing track entity
     #[allow(clippy::too_many_arguments)]
     async fn update_track(
@@ -811,7 +1555,7 @@ impl MetadataProcessor {
                 track_number = ?, disc_number = ?, duration_ms = ?, bitrate = ?,
                 sample_rate = ?, channels = ?, format = ?, mime_type = ?, file_size = ?,
                 artwork_id = COALESCE(?, artwork_id), year = ?, genre = ?,
-                composer = ?, comment = ?, updated_at = ?
+                updated_at = ?
             WHERE id = ?
             "#,
                 &[
@@ -873,18 +1617,6 @@ impl MetadataProcessor {
                         .map_or(bridge_traits::database::QueryValue::Null, |g| {
                             bridge_traits::database::QueryValue::Text(g.clone())
                         }),
-                    metadata
-                        .composer
-                        .as_ref()
-                        .map_or(bridge_traits::database::QueryValue::Null, |c| {
-                            bridge_traits::database::QueryValue::Text(c.clone())
-                        }),
-                    metadata
-                        .comment
-                        .as_ref()
-                        .map_or(bridge_traits::database::QueryValue::Null, |c| {
-                            bridge_traits::database::QueryValue::Text(c.clone())
-                        }),
                     bridge_traits::database::QueryValue::Integer(chrono::Utc::now().timestamp()),
                     bridge_traits::database::QueryValue::Text(existing_track.id.clone()),
                 ],
@@ -896,19 +1628,27 @@ impl MetadataProcessor {
 
         Ok(existing_track.id.clone())
     }
+}
 
-    /// Clean up temporary file
-    async fn cleanup_temp_file(&self, path: &Path) {
-        if let Err(e) = self.file_system.delete_file(path).await {
-            warn!("Failed to clean up temporary file {:?}: {}", path, e);
-        }
-    }
+/// A tag value with surrounding whitespace removed, if not blank
+fn trimmed(value: &Option<String>) -> Option<&str> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+fn parse_artist_id(id: &str) -> Result<ArtistId> {
+    ArtistId::from_string(id).map_err(|e| SyncError::Internal(format!("Invalid artist ID: {}", e)))
+}
+
+fn parse_album_id(id: &str) -> Result<AlbumId> {
+    AlbumId::from_string(id).map_err(|e| SyncError::Internal(format!("Invalid album ID: {}", e)))
 }
 
 /// Normalize name for searching and matching
 fn normalize_name(name: &str) -> String {
-    name.trim()
-        .to_lowercase()
+    normalize_search_text(name)
         .chars()
         .filter(|c| c.is_alphanumeric() || c.is_whitespace())
         .collect::<String>()
@@ -927,6 +1667,7 @@ mod tests {
         assert_eq!(normalize_name("AC/DC"), "acdc");
         assert_eq!(normalize_name("  Pink Floyd  "), "pink floyd");
         assert_eq!(normalize_name("Guns N' Roses"), "guns n roses");
+        assert_eq!(normalize_name("ﾖﾙﾆｶｹﾙ"), "よるにかける");
     }
 
     #[test]
@@ -936,5 +1677,6 @@ mod tests {
         assert_eq!(config.header_size_bytes, 256 * 1024);
         assert!(config.extract_artwork);
         assert!(!config.update_existing);
+        assert_eq!(config.id_strategy, IdStrategy::Random);
     }
 }
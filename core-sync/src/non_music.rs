@@ -0,0 +1,276 @@
+//! Non-Music Classification
+//!
+//! Flags files that are likely podcasts, audiobooks or voice memos rather
+//! than music, so a sync can skip them or file them apart from the library.
+//!
+//! ## Signals
+//!
+//! - **Spoken genre**: the genre tag names spoken content ("Podcast", ...)
+//! - **Spoken folder**: a folder in the file's path is named like a podcast
+//!   or recordings folder
+//! - **Long duration**: longer than any ordinary track
+//! - **Voice encoding**: mono at a low bitrate, typical of speech recordings
+//!
+//! The heuristics are conservative. A genre or folder match is enough on its
+//! own, but duration and encoding are weak signals and only count when both
+//! are present: long DJ mixes and old mono recordings stay music.
+
+use core_metadata::extractor::ExtractedMetadata;
+use serde::{Deserialize, Serialize};
+
+/// Evidence that a file is not music
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum NonMusicSignal {
+    /// Genre tag names spoken content
+    SpokenGenre,
+    /// A folder in the path is named like a podcast or recordings folder
+    SpokenFolder,
+    /// Duration exceeds [`NonMusicFilter::long_duration_ms`]
+    LongDuration,
+    /// Mono audio at or below [`NonMusicFilter::voice_max_bitrate_kbps`]
+    VoiceEncoding,
+}
+
+impl NonMusicSignal {
+    /// Whether the signal classifies a file on its own
+    pub fn is_strong(&self) -> bool {
+        matches!(
+            self,
+            NonMusicSignal::SpokenGenre | NonMusicSignal::SpokenFolder
+        )
+    }
+}
+
+/// What happens to files classified as non-music
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum NonMusicAction {
+    /// Leave the file out of the library
+    #[default]
+    Skip,
+    /// Import the file with its genre set to [`NonMusicFilter::tag_genre`]
+    Tag,
+}
+
+/// Audio properties the classifier looks at
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AudioProfile<'a> {
+    /// Duration in milliseconds
+    pub duration_ms: u64,
+    /// Bitrate in kbps
+    pub bitrate_kbps: Option<u32>,
+    /// Number of audio channels
+    pub channels: Option<u8>,
+    /// Genre tag
+    pub genre: Option<&'a str>,
+    /// File name, including any folders the provider reports
+    pub path: &'a str,
+}
+
+impl<'a> AudioProfile<'a> {
+    /// Profile of an extracted file stored at `path`
+    pub fn from_metadata(metadata: &'a ExtractedMetadata, path: &'a str) -> Self {
+        Self {
+            duration_ms: metadata.duration_ms,
+            bitrate_kbps: metadata.bitrate,
+            channels: metadata.channels,
+            genre: metadata.genre.as_deref(),
+            path,
+        }
+    }
+}
+
+/// Configurable heuristics for detecting non-music audio
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NonMusicFilter {
+    /// What to do with files classified as non-music (default: skip)
+    pub action: NonMusicAction,
+
+    /// Duration above which a file counts as long (default: 60 minutes)
+    pub long_duration_ms: u64,
+
+    /// Highest bitrate at which mono audio counts as voice (default: 96 kbps)
+    pub voice_max_bitrate_kbps: u32,
+
+    /// Genre tags naming spoken content, matched case-insensitively
+    pub spoken_genres: Vec<String>,
+
+    /// Folder names holding spoken content, matched case-insensitively
+    pub spoken_folders: Vec<String>,
+
+    /// Genre given to tagged files (default: "Non-Music")
+    pub tag_genre: String,
+}
+
+impl Default for NonMusicFilter {
+    fn default() -> Self {
+        Self {
+            action: NonMusicAction::Skip,
+            long_duration_ms: 60 * 60 * 1000,
+            voice_max_bitrate_kbps: 96,
+            spoken_genres: [
+                "podcast",
+                "audiobook",
+                "speech",
+                "spoken word",
+                "voice memo",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            spoken_folders: [
+                "podcasts",
+                "audiobooks",
+                "voice memos",
+                "voice recordings",
+                "recordings",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            tag_genre: "Non-Music".to_string(),
+        }
+    }
+}
+
+impl NonMusicFilter {
+    /// Set what happens to files classified as non-music
+    pub fn with_action(mut self, action: NonMusicAction) -> Self {
+        self.action = action;
+        self
+    }
+
+    /// Every signal present in `profile`
+    pub fn signals(&self, profile: &AudioProfile<'_>) -> Vec<NonMusicSignal> {
+        let mut signals = Vec::new();
+
+        if let Some(genre) = profile.genre {
+            if contains_ignore_case(&self.spoken_genres, genre.trim()) {
+                signals.push(NonMusicSignal::SpokenGenre);
+            }
+        }
+
+        // The last component is the file itself
+        let mut components: Vec<&str> = profile.path.split(['/', '\\']).collect();
+        components.pop();
+        if components
+            .iter()
+            .any(|folder| contains_ignore_case(&self.spoken_folders, folder.trim()))
+        {
+            signals.push(NonMusicSignal::SpokenFolder);
+        }
+
+        if profile.duration_ms > self.long_duration_ms {
+            signals.push(NonMusicSignal::LongDuration);
+        }
+
+        let low_bitrate = profile
+            .bitrate_kbps
+            .is_some_and(|kbps| kbps <= self.voice_max_bitrate_kbps);
+        if profile.channels == Some(1) && low_bitrate {
+            signals.push(NonMusicSignal::VoiceEncoding);
+        }
+
+        signals
+    }
+
+    /// Signals that classify `profile` as non-music, or `None` for music
+    ///
+    /// One strong signal is enough; weak signals only count together.
+    pub fn classify(&self, profile: &AudioProfile<'_>) -> Option<Vec<NonMusicSignal>> {
+        let signals = self.signals(profile);
+        let strong = signals.iter().any(NonMusicSignal::is_strong);
+        let weak = signals.iter().filter(|s| !s.is_strong()).count();
+
+        (strong || weak >= 2).then_some(signals)
+    }
+}
+
+fn contains_ignore_case(names: &[String], value: &str) -> bool {
+    names.iter().any(|name| name.eq_ignore_ascii_case(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(duration_ms: u64, bitrate_kbps: u32, channels: u8) -> AudioProfile<'static> {
+        AudioProfile {
+            duration_ms,
+            bitrate_kbps: Some(bitrate_kbps),
+            channels: Some(channels),
+            genre: None,
+            path: "Music/track.mp3",
+        }
+    }
+
+    #[test]
+    fn test_long_mono_voice_file_is_non_music() {
+        let filter = NonMusicFilter::default();
+        let three_hours = 3 * 60 * 60 * 1000;
+
+        assert_eq!(
+            filter.classify(&profile(three_hours, 64, 1)),
+            Some(vec![
+                NonMusicSignal::LongDuration,
+                NonMusicSignal::VoiceEncoding
+            ])
+        );
+    }
+
+    #[test]
+    fn test_normal_song_is_music() {
+        let filter = NonMusicFilter::default();
+        assert_eq!(filter.classify(&profile(4 * 60 * 1000, 320, 2)), None);
+    }
+
+    #[test]
+    fn test_single_weak_signal_is_not_enough() {
+        let filter = NonMusicFilter::default();
+
+        // A two-hour DJ mix in stereo
+        assert_eq!(filter.classify(&profile(2 * 60 * 60 * 1000, 256, 2)), None);
+        // An old mono recording
+        assert_eq!(filter.classify(&profile(3 * 60 * 1000, 64, 1)), None);
+    }
+
+    #[test]
+    fn test_genre_and_folder_are_strong_signals() {
+        let filter = NonMusicFilter::default();
+
+        let tagged = AudioProfile {
+            genre: Some(" Podcast "),
+            ..profile(30 * 60 * 1000, 128, 2)
+        };
+        assert_eq!(
+            filter.classify(&tagged),
+            Some(vec![NonMusicSignal::SpokenGenre])
+        );
+
+        let filed = AudioProfile {
+            path: "Voice Memos/2024-01-01.m4a",
+            ..profile(60 * 1000, 128, 2)
+        };
+        assert_eq!(
+            filter.classify(&filed),
+            Some(vec![NonMusicSignal::SpokenFolder])
+        );
+
+        // Only folders count, not the file name itself
+        let named = AudioProfile {
+            path: "Music/Podcasts",
+            ..profile(60 * 1000, 128, 2)
+        };
+        assert_eq!(filter.classify(&named), None);
+    }
+
+    #[test]
+    fn test_thresholds_are_configurable() {
+        let filter = NonMusicFilter {
+            long_duration_ms: 10 * 60 * 60 * 1000,
+            ..NonMusicFilter::default()
+        };
+        let three_hours = 3 * 60 * 60 * 1000;
+
+        assert_eq!(filter.classify(&profile(three_hours, 64, 1)), None);
+    }
+}
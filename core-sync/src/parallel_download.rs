@@ -0,0 +1,314 @@
+//! Parallel Ranged Downloads
+//!
+//! Downloads a large remote file as several byte ranges at once and assembles
+//! them into a local file.
+//!
+//! ## Overview
+//!
+//! A single long-lived stream is slow on high-latency links and has to start
+//! over when it fails. `ParallelDownloader` instead:
+//! - Splits the file into contiguous ranges (`bytes=start-end`)
+//! - Downloads the ranges concurrently via `StorageProvider::download`,
+//!   retrying a failed range on its own
+//! - Writes each range at its offset with `FileSystemAccess::write_at`
+//! - Verifies the assembled content against the provider's MD5 checksum
+//!
+//! Providers that don't report `supports_range`, and files too small to be
+//! worth splitting, are downloaded with a single request.
+
+use crate::error::{Result, SyncError};
+use bridge_traits::storage::{FileSystemAccess, RemoteFile, StorageProvider};
+use bytes::Bytes;
+use md5::{Digest, Md5};
+use std::path::Path;
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+/// Configuration for parallel downloads
+#[derive(Debug, Clone)]
+pub struct ParallelDownloadConfig {
+    /// Maximum number of ranges downloaded concurrently
+    pub max_parts: usize,
+
+    /// Smallest range worth a separate request
+    ///
+    /// Files smaller than twice this size are downloaded in one request.
+    pub min_part_size: u64,
+
+    /// Retries for each range before the download fails
+    pub max_part_retries: u32,
+}
+
+impl Default for ParallelDownloadConfig {
+    fn default() -> Self {
+        Self {
+            max_parts: 4,
+            min_part_size: 8 * 1024 * 1024, // 8MB
+            max_part_retries: 2,
+        }
+    }
+}
+
+/// Inclusive byte range of a file part
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+    /// First byte offset
+    pub start: u64,
+    /// Last byte offset (inclusive)
+    pub end: u64,
+}
+
+impl ByteRange {
+    /// Number of bytes in the range
+    pub fn len(&self) -> u64 {
+        self.end - self.start + 1
+    }
+
+    /// Whether the range is empty (never true for ranges from `split_ranges`)
+    pub fn is_empty(&self) -> bool {
+        self.end < self.start
+    }
+
+    /// HTTP `Range` header value
+    pub fn header_value(&self) -> String {
+        format!("bytes={}-{}", self.start, self.end)
+    }
+}
+
+/// Split `size` bytes into at most `parts` contiguous ranges of near-equal size
+///
+/// Returns no ranges for an empty file.
+pub fn split_ranges(size: u64, parts: usize) -> Vec<ByteRange> {
+    if size == 0 {
+        return Vec::new();
+    }
+
+    let parts = (parts.max(1) as u64).min(size);
+    let base = size / parts;
+    let remainder = size % parts;
+
+    let mut ranges = Vec::with_capacity(parts as usize);
+    let mut start = 0;
+    for index in 0..parts {
+        // Spread the remainder over the first ranges
+        let len = base + u64::from(index < remainder);
+        ranges.push(ByteRange {
+            start,
+            end: start + len - 1,
+        });
+        start += len;
+    }
+    ranges
+}
+
+/// Downloads files as concurrent byte ranges
+pub struct ParallelDownloader {
+    provider: Arc<dyn StorageProvider>,
+    file_system: Arc<dyn FileSystemAccess>,
+    config: ParallelDownloadConfig,
+}
+
+impl ParallelDownloader {
+    /// Create a downloader with the default configuration
+    pub fn new(provider: Arc<dyn StorageProvider>, file_system: Arc<dyn FileSystemAccess>) -> Self {
+        Self::with_config(provider, file_system, ParallelDownloadConfig::default())
+    }
+
+    /// Create a downloader with a custom configuration
+    pub fn with_config(
+        provider: Arc<dyn StorageProvider>,
+        file_system: Arc<dyn FileSystemAccess>,
+        config: ParallelDownloadConfig,
+    ) -> Self {
+        Self {
+            provider,
+            file_system,
+            config,
+        }
+    }
+
+    /// Ranges `file` would be downloaded as, or `None` for a single request
+    pub fn plan(&self, file: &RemoteFile) -> Option<Vec<ByteRange>> {
+        if !self.provider.supports_range() {
+            return None;
+        }
+
+        let size = file.size?;
+        let min_part_size = self.config.min_part_size.max(1);
+        let parts = (size / min_part_size).min(self.config.max_parts as u64) as usize;
+        (parts >= 2).then(|| split_ranges(size, parts))
+    }
+
+    /// Download `file` to `destination`, returning the number of bytes written
+    ///
+    /// Any existing file at `destination` is replaced. When the provider
+    /// reports an MD5 checksum it is verified, and the destination is deleted
+    /// if it doesn't match.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - A range still fails after `max_part_retries` retries
+    /// - The provider returns a range of the wrong length
+    /// - Writing the destination fails
+    /// - The checksum doesn't match
+    pub async fn download(&self, file: &RemoteFile, destination: &Path) -> Result<u64> {
+        let Some(ranges) = self.plan(file) else {
+            debug!("Downloading {} in a single request", file.name);
+            let data = self
+                .provider
+                .download(&file.id, None)
+                .await
+                .map_err(|e| SyncError::Provider(format!("Download failed: {}", e)))?;
+            let len = data.len() as u64;
+            verify_checksum(file, Md5::new_with_prefix(&data))?;
+            self.write_file(destination, data).await?;
+            return Ok(len);
+        };
+
+        info!("Downloading {} as {} ranges", file.name, ranges.len());
+
+        // Start from an empty file so stale bytes can't survive
+        self.write_file(destination, Bytes::new()).await?;
+
+        let handles: Vec<_> = ranges
+            .iter()
+            .map(|&range| {
+                let provider = Arc::clone(&self.provider);
+                let file_id = file.id.clone();
+                let max_retries = self.config.max_part_retries;
+                core_async::task::spawn(async move {
+                    download_range(provider, file_id, range, max_retries).await
+                })
+            })
+            .collect();
+
+        // Ranges are written (and hashed) in file order as they arrive
+        let mut hasher = Md5::new();
+        let mut written = 0u64;
+        for (range, handle) in ranges.into_iter().zip(handles) {
+            let result = handle
+                .await
+                .map_err(|e| SyncError::Internal(format!("Range download task failed: {}", e)))
+                .and_then(|result| result);
+            let data = match result {
+                Ok(data) => data,
+                Err(e) => {
+                    self.discard(destination).await;
+                    return Err(e);
+                }
+            };
+
+            hasher.update(&data);
+            written += data.len() as u64;
+            if let Err(e) = self
+                .file_system
+                .write_at(destination, range.start, data)
+                .await
+            {
+                self.discard(destination).await;
+                return Err(SyncError::Internal(format!(
+                    "Failed to write range {}: {}",
+                    range.header_value(),
+                    e
+                )));
+            }
+        }
+
+        if let Err(e) = verify_checksum(file, hasher) {
+            self.discard(destination).await;
+            return Err(e);
+        }
+
+        info!("Assembled {} ({} bytes)", file.name, written);
+        Ok(written)
+    }
+
+    async fn write_file(&self, destination: &Path, data: Bytes) -> Result<()> {
+        self.file_system
+            .write_file(destination, data)
+            .await
+            .map_err(|e| SyncError::Internal(format!("Failed to write file: {}", e)))
+    }
+
+    /// Remove a partially assembled file
+    async fn discard(&self, destination: &Path) {
+        if let Err(e) = self.file_system.delete_file(destination).await {
+            warn!("Failed to remove partial download {:?}: {}", destination, e);
+        }
+    }
+}
+
+/// Download one range, retrying it on its own
+async fn download_range(
+    provider: Arc<dyn StorageProvider>,
+    file_id: String,
+    range: ByteRange,
+    max_retries: u32,
+) -> Result<Bytes> {
+    let header = range.header_value();
+    let mut attempt = 0;
+    loop {
+        let error = match provider.download(&file_id, Some(&header)).await {
+            Ok(data) if data.len() as u64 == range.len() => return Ok(data),
+            Ok(data) => SyncError::Provider(format!(
+                "Range {} returned {} bytes, expected {}",
+                header,
+                data.len(),
+                range.len()
+            )),
+            Err(e) => SyncError::Provider(format!("Range {} failed: {}", header, e)),
+        };
+
+        if attempt >= max_retries {
+            return Err(error);
+        }
+        attempt += 1;
+        warn!("{}; retrying ({}/{})", error, attempt, max_retries);
+    }
+}
+
+/// Compare the downloaded content's MD5 against the provider's checksum
+fn verify_checksum(file: &RemoteFile, hasher: Md5) -> Result<()> {
+    let Some(expected) = &file.md5_checksum else {
+        return Ok(());
+    };
+
+    let actual: String = hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    if actual.eq_ignore_ascii_case(expected) {
+        Ok(())
+    } else {
+        Err(SyncError::Provider(format!(
+            "Checksum mismatch for {}: expected {}, got {}",
+            file.name, expected, actual
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_ranges_covers_file() {
+        let ranges = split_ranges(10, 3);
+        assert_eq!(
+            ranges,
+            vec![
+                ByteRange { start: 0, end: 3 },
+                ByteRange { start: 4, end: 6 },
+                ByteRange { start: 7, end: 9 },
+            ]
+        );
+        assert_eq!(ranges.iter().map(ByteRange::len).sum::<u64>(), 10);
+        assert_eq!(ranges[0].header_value(), "bytes=0-3");
+
+        // Never more ranges than bytes
+        assert_eq!(split_ranges(2, 5).len(), 2);
+        assert!(split_ranges(0, 3).is_empty());
+    }
+}
@@ -18,7 +18,7 @@ use bridge_traits::{
     database::{DatabaseAdapter, QueryRow, QueryValue},
     platform::PlatformSendSync,
 };
-use core_auth::ProviderKind;
+use core_auth::{ProfileId, ProviderKind};
 
 // ============================================================================
 // Repository Trait
@@ -118,6 +118,20 @@ pub trait SyncJobRepository: PlatformSendSync {
         db: &dyn DatabaseAdapter,
         provider: ProviderKind,
     ) -> Result<bool>;
+
+    /// Discard the stored sync cursors of a profile's jobs for a provider
+    ///
+    /// Returns the number of jobs whose cursor was cleared.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    async fn clear_cursors(
+        &self,
+        db: &dyn DatabaseAdapter,
+        profile_id: ProfileId,
+        provider: ProviderKind,
+    ) -> Result<u64>;
 }
 
 // ============================================================================
@@ -191,6 +205,12 @@ fn row_to_sync_job(row: &QueryRow) -> Result<SyncJob> {
     Ok(SyncJob {
         id: SyncJobId::from_string(&get_string(row, "id")?)?,
         provider_id,
+        profile_id: get_optional_string(row, "profile_id")?
+            .map(|id| {
+                ProfileId::from_string(&id)
+                    .map_err(|e| SyncError::Database(format!("Invalid profile_id: {}", e)))
+            })
+            .transpose()?,
         status,
         sync_type,
         progress,
@@ -268,16 +288,17 @@ impl SyncJobRepository for SqliteSyncJobRepository {
         db.execute(
             r#"
             INSERT INTO sync_jobs (
-                id, provider_id, status, sync_type,
+                id, provider_id, profile_id, status, sync_type,
                 items_discovered, items_processed, items_failed,
                 items_added, items_updated, items_deleted,
                 error_message, error_details, cursor,
                 started_at, completed_at, created_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
             &[
                 QueryValue::Text(job.id.as_str().to_string()),
                 QueryValue::Text(job.provider_id.as_str().to_string()),
+                opt_text(&job.profile_id.map(|id| id.to_string())),
                 QueryValue::Text(job.status.as_str().to_string()),
                 QueryValue::Text(job.sync_type.as_str().to_string()),
                 QueryValue::Integer(job.progress.items_discovered as i64),
@@ -363,7 +384,7 @@ impl SyncJobRepository for SqliteSyncJobRepository {
         let row = db
             .query_one_optional(
                 r#"
-            SELECT id, provider_id, status, sync_type,
+            SELECT id, provider_id, profile_id, status, sync_type,
                    items_discovered, items_processed, items_failed,
                    items_added, items_updated, items_deleted,
                    error_message, error_details, cursor,
@@ -387,7 +408,7 @@ impl SyncJobRepository for SqliteSyncJobRepository {
         let rows = db
             .query(
                 r#"
-            SELECT id, provider_id, status, sync_type,
+            SELECT id, provider_id, profile_id, status, sync_type,
                    items_discovered, items_processed, items_failed,
                    items_added, items_updated, items_deleted,
                    error_message, error_details, cursor,
@@ -412,7 +433,7 @@ impl SyncJobRepository for SqliteSyncJobRepository {
         let rows = db
             .query(
                 r#"
-            SELECT id, provider_id, status, sync_type,
+            SELECT id, provider_id, profile_id, status, sync_type,
                    items_discovered, items_processed, items_failed,
                    items_added, items_updated, items_deleted,
                    error_message, error_details, cursor,
@@ -437,7 +458,7 @@ impl SyncJobRepository for SqliteSyncJobRepository {
         let row = db
             .query_one_optional(
                 r#"
-            SELECT id, provider_id, status, sync_type,
+            SELECT id, provider_id, profile_id, status, sync_type,
                    items_discovered, items_processed, items_failed,
                    items_added, items_updated, items_deleted,
                    error_message, error_details, cursor,
@@ -464,7 +485,7 @@ impl SyncJobRepository for SqliteSyncJobRepository {
         let rows = db
             .query(
                 r#"
-            SELECT id, provider_id, status, sync_type,
+            SELECT id, provider_id, profile_id, status, sync_type,
                    items_discovered, items_processed, items_failed,
                    items_added, items_updated, items_deleted,
                    error_message, error_details, cursor,
@@ -523,6 +544,24 @@ impl SyncJobRepository for SqliteSyncJobRepository {
         let count = get_i64(&row, "count")?;
         Ok(count > 0)
     }
+
+    async fn clear_cursors(
+        &self,
+        db: &dyn DatabaseAdapter,
+        profile_id: ProfileId,
+        provider: ProviderKind,
+    ) -> Result<u64> {
+        db.execute(
+            "UPDATE sync_jobs SET cursor = NULL \
+             WHERE profile_id = ? AND provider_id = ? AND cursor IS NOT NULL",
+            &[
+                QueryValue::Text(profile_id.to_string()),
+                QueryValue::Text(provider.as_str().to_string()),
+            ],
+        )
+        .await
+        .map_err(|e| SyncError::Database(e.to_string()))
+    }
 }
 
 // ============================================================================
@@ -545,6 +584,7 @@ mod tests {
             CREATE TABLE sync_jobs (
                 id TEXT PRIMARY KEY NOT NULL,
                 provider_id TEXT NOT NULL,
+                profile_id TEXT,
                 status TEXT NOT NULL DEFAULT 'pending',
                 sync_type TEXT NOT NULL,
                 items_discovered INTEGER DEFAULT 0,
@@ -784,4 +824,53 @@ mod tests {
         assert_eq!(found_stats.items_deleted, 5);
         assert_eq!(found_stats.items_failed, 2);
     }
+
+    #[core_async::test]
+    async fn test_clear_cursors() {
+        let db = create_test_adapter().await;
+        let repo = SqliteSyncJobRepository::new();
+        let profile_id = ProfileId::new();
+        let other_profile_id = ProfileId::new();
+
+        let google_job =
+            SyncJob::new_incremental(ProviderKind::GoogleDrive, "drive-cursor".to_string())
+                .for_profile(profile_id);
+        let onedrive_job =
+            SyncJob::new_incremental(ProviderKind::OneDrive, "onedrive-cursor".to_string())
+                .for_profile(profile_id);
+        let other_profile_job =
+            SyncJob::new_incremental(ProviderKind::GoogleDrive, "other-cursor".to_string())
+                .for_profile(other_profile_id);
+        repo.insert(db.as_ref(), &google_job).await.unwrap();
+        repo.insert(db.as_ref(), &onedrive_job).await.unwrap();
+        repo.insert(db.as_ref(), &other_profile_job).await.unwrap();
+
+        let cleared = repo
+            .clear_cursors(db.as_ref(), profile_id, ProviderKind::GoogleDrive)
+            .await
+            .unwrap();
+        assert_eq!(cleared, 1);
+
+        let found = repo
+            .find_by_id(db.as_ref(), &google_job.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(found.cursor.is_none());
+        assert_eq!(found.profile_id, Some(profile_id));
+
+        // Other providers and other profiles keep their cursors
+        let found = repo
+            .find_by_id(db.as_ref(), &onedrive_job.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.cursor.as_deref(), Some("onedrive-cursor"));
+        let found = repo
+            .find_by_id(db.as_ref(), &other_profile_job.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.cursor.as_deref(), Some("other-cursor"));
+    }
 }
@@ -301,6 +301,7 @@ pub trait ScanQueueRepository: Send + Sync {
 }
 
 /// SQLite implementation of scan queue repository
+#[derive(Default)]
 pub struct SqliteScanQueueRepository {}
 
 impl SqliteScanQueueRepository {
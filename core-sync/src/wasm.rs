@@ -0,0 +1,296 @@
+//! WebAssembly bindings for core-sync
+//!
+//! This module exposes the `SyncCoordinator` to JavaScript so web hosts can drive
+//! full and incremental synchronization the same way native hosts do.
+//!
+//! ## Philosophy
+//!
+//! If native has a feature, WASM must expose it. Authentication is provided by
+//! `JsAuthManager` from core-auth; this module only adds the sync surface.
+
+use crate::coordinator::{SyncConfig, SyncCoordinator};
+use crate::error::SyncError;
+use crate::job::SyncJobId;
+use bridge_traits::database::{DatabaseAdapter, DatabaseConfig};
+use bridge_traits::storage::{FileSystemAccess, StorageProvider};
+use bridge_wasm::{
+    run_cancellable, to_js_error, token_from_abort_signal, JsError, JsErrorKind, WasmDbAdapter,
+    WasmFileSystem,
+};
+use core_async::sync::CancellationToken;
+use core_auth::wasm::{JsAuthManager, JsProviderKind};
+use core_auth::{ProfileId, ProviderKind};
+use core_runtime::events::{CoreEvent, RecvError};
+use core_runtime::wasm::JsEventBus;
+use js_sys::{Function, Promise};
+use std::sync::Arc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::{future_to_promise, spawn_local};
+use web_sys::AbortSignal;
+
+// =============================================================================
+// Error Handling Utilities
+// =============================================================================
+
+impl From<SyncError> for JsError {
+    fn from(err: SyncError) -> Self {
+        let message = err.to_string();
+        let kind = match err {
+            SyncError::Library(library) => JsError::from(library).kind,
+            SyncError::NotAuthenticated => JsErrorKind::Auth,
+            SyncError::JobNotFound { .. } => JsErrorKind::NotFound,
+            SyncError::SyncInProgress { .. } | SyncError::InvalidStateTransition { .. } => {
+                JsErrorKind::Conflict
+            }
+            SyncError::Provider(_) | SyncError::CaptivePortal => JsErrorKind::Network,
+            SyncError::Timeout(_) => JsErrorKind::Timeout,
+            SyncError::Cancelled => JsErrorKind::Cancelled,
+            SyncError::InvalidJobId(_)
+            | SyncError::InvalidStatus(_)
+            | SyncError::InvalidSyncType(_)
+            | SyncError::InvalidInput { .. } => JsErrorKind::InvalidInput,
+            SyncError::Database(_) => JsErrorKind::Storage,
+            SyncError::Internal(_) | SyncError::Metadata(_) => JsErrorKind::Internal,
+        };
+        JsError::new(kind, message)
+    }
+}
+
+fn parse_profile_id(profile_id: &str) -> Result<ProfileId, JsValue> {
+    ProfileId::from_string(profile_id)
+        .map_err(|e| to_js_error(JsError::invalid_input(format!("Invalid profile ID: {}", e))))
+}
+
+fn parse_job_id(job_id: &str) -> Result<SyncJobId, JsValue> {
+    SyncJobId::from_string(job_id).map_err(to_js_error)
+}
+
+/// Cancel a running job once `token` fires
+///
+/// Sync jobs run in the background after the start call resolves, so the abort
+/// has to be forwarded to `cancel_sync` rather than dropping a future.
+fn cancel_job_on_abort(
+    coordinator: Arc<SyncCoordinator>,
+    job_id: SyncJobId,
+    token: Option<CancellationToken>,
+) {
+    let Some(token) = token else {
+        return;
+    };
+    spawn_local(async move {
+        token.cancelled().await;
+        if coordinator.get_status(job_id).await.is_ok_and(|job| !job.status.is_terminal()) {
+            let _ = coordinator.cancel_sync(job_id).await;
+        }
+    });
+}
+
+// =============================================================================
+// SyncCoordinator - Main API
+// =============================================================================
+
+/// JavaScript-accessible sync coordinator
+///
+/// # Example
+///
+/// ```javascript
+/// import { JsAuthManager, JsProviderKind } from './core_auth';
+/// import { JsEventBus } from './core_runtime';
+/// import { JsSyncCoordinator } from './core_sync';
+///
+/// const eventBus = new JsEventBus(100);
+/// const authManager = new JsAuthManager(eventBus, httpClient, secureStore);
+/// const sync = await JsSyncCoordinator.create(authManager, eventBus, 'music.db', 'music-app');
+///
+/// sync.onProgress((event) => console.log(event.event, event));
+/// const jobId = await sync.startFullSync(profileId);
+/// const job = await sync.getStatus(jobId);
+/// ```
+#[wasm_bindgen]
+pub struct JsSyncCoordinator {
+    inner: Arc<SyncCoordinator>,
+}
+
+impl JsSyncCoordinator {
+    /// Wrap an already constructed coordinator (for other crates and tests to use)
+    pub fn from_coordinator(inner: Arc<SyncCoordinator>) -> Self {
+        Self { inner }
+    }
+
+    /// Get the inner SyncCoordinator (for other crates to use)
+    pub fn inner(&self) -> &Arc<SyncCoordinator> {
+        &self.inner
+    }
+
+    /// Register a storage provider implementation
+    ///
+    /// Providers are Rust trait objects, so registration happens on the Rust side
+    /// (e.g. by core-service) rather than from JavaScript.
+    pub async fn register_provider(&self, kind: ProviderKind, provider: Arc<dyn StorageProvider>) {
+        self.inner.register_provider(kind, provider).await;
+    }
+}
+
+#[wasm_bindgen]
+impl JsSyncCoordinator {
+    /// Create a new sync coordinator
+    ///
+    /// Returns a Promise that resolves to a `JsSyncCoordinator`.
+    ///
+    /// # Arguments
+    ///
+    /// * `auth_manager` - Auth manager used to acquire provider tokens
+    /// * `event_bus` - Event bus that receives sync events
+    /// * `database_url` - Library database (same URL as `JsLibrary.create`)
+    /// * `app_name` - Namespace for the IndexedDB-backed file system
+    pub fn create(
+        auth_manager: &JsAuthManager,
+        event_bus: &JsEventBus,
+        database_url: String,
+        app_name: String,
+    ) -> Promise {
+        let auth_manager = auth_manager.inner().clone();
+        let event_bus = event_bus.inner().clone();
+        future_to_promise(async move {
+            let db = WasmDbAdapter::new(DatabaseConfig::new(&database_url))
+                .await
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to create database")))?;
+            let file_system = WasmFileSystem::new(&app_name)
+                .await
+                .map_err(|e| to_js_error(JsError::from(e).with_context("Failed to create file system")))?;
+
+            let coordinator = SyncCoordinator::new(
+                SyncConfig::default(),
+                auth_manager,
+                event_bus,
+                None,
+                Arc::new(file_system) as Arc<dyn FileSystemAccess>,
+                Arc::new(db) as Arc<dyn DatabaseAdapter>,
+            )
+            .await
+            .map_err(to_js_error)?;
+
+            Ok(JsValue::from(JsSyncCoordinator {
+                inner: Arc::new(coordinator),
+            }))
+        })
+    }
+
+    /// Start a full synchronization for a profile
+    ///
+    /// Resolves to the job ID. Aborting `signal` cancels the job; if it fires
+    /// before the job starts, the promise rejects with a `Cancelled` error.
+    #[wasm_bindgen(js_name = startFullSync)]
+    pub fn start_full_sync(&self, profile_id: String, signal: Option<AbortSignal>) -> Promise {
+        let inner = self.inner.clone();
+        let token = signal.as_ref().map(token_from_abort_signal);
+        future_to_promise(async move {
+            let profile_id = parse_profile_id(&profile_id)?;
+            let job_id = run_cancellable(token.clone(), async {
+                inner.start_full_sync(profile_id).await.map_err(to_js_error)
+            })
+            .await?;
+            cancel_job_on_abort(inner, job_id, token);
+            Ok(JsValue::from_str(&job_id.to_string()))
+        })
+    }
+
+    /// Start an incremental synchronization for a profile
+    ///
+    /// Resolves to the job ID. Aborting `signal` cancels the job; if it fires
+    /// before the job starts, the promise rejects with a `Cancelled` error.
+    #[wasm_bindgen(js_name = startIncrementalSync)]
+    pub fn start_incremental_sync(
+        &self,
+        profile_id: String,
+        cursor: Option<String>,
+        signal: Option<AbortSignal>,
+    ) -> Promise {
+        let inner = self.inner.clone();
+        let token = signal.as_ref().map(token_from_abort_signal);
+        future_to_promise(async move {
+            let profile_id = parse_profile_id(&profile_id)?;
+            let job_id = run_cancellable(token.clone(), async {
+                inner
+                    .start_incremental_sync(profile_id, cursor)
+                    .await
+                    .map_err(to_js_error)
+            })
+            .await?;
+            cancel_job_on_abort(inner, job_id, token);
+            Ok(JsValue::from_str(&job_id.to_string()))
+        })
+    }
+
+    /// Get the current state of a sync job
+    ///
+    /// Resolves to the serialized `SyncJob` object.
+    #[wasm_bindgen(js_name = getStatus)]
+    pub fn get_status(&self, job_id: String) -> Promise {
+        let inner = self.inner.clone();
+        future_to_promise(async move {
+            let job_id = parse_job_id(&job_id)?;
+            let job = inner.get_status(job_id).await.map_err(to_js_error)?;
+            serde_wasm_bindgen::to_value(&job).map_err(to_js_error)
+        })
+    }
+
+    /// List recent sync jobs for a provider
+    ///
+    /// Resolves to an array of serialized `SyncJob` objects.
+    #[wasm_bindgen(js_name = listHistory)]
+    pub fn list_history(&self, provider: JsProviderKind, limit: usize) -> Promise {
+        let inner = self.inner.clone();
+        future_to_promise(async move {
+            let jobs = inner
+                .list_history(provider.into(), limit)
+                .await
+                .map_err(to_js_error)?;
+            serde_wasm_bindgen::to_value(&jobs).map_err(to_js_error)
+        })
+    }
+
+    /// Cancel a running sync job
+    #[wasm_bindgen(js_name = cancelSync)]
+    pub fn cancel_sync(&self, job_id: String) -> Promise {
+        let inner = self.inner.clone();
+        future_to_promise(async move {
+            let job_id = parse_job_id(&job_id)?;
+            inner.cancel_sync(job_id).await.map_err(to_js_error)?;
+            Ok(JsValue::NULL)
+        })
+    }
+
+    /// Check whether a sync is running for a profile
+    #[wasm_bindgen(js_name = isSyncActive)]
+    pub fn is_sync_active(&self, profile_id: String) -> Promise {
+        let inner = self.inner.clone();
+        future_to_promise(async move {
+            let profile_id = parse_profile_id(&profile_id)?;
+            Ok(JsValue::from_bool(inner.is_sync_active(profile_id).await))
+        })
+    }
+
+    /// Register a progress callback
+    ///
+    /// The callback receives every sync event (`Started`, `Progress`, `Completed`,
+    /// `Failed`, `Cancelled`) as a plain object tagged by its `event` field.
+    /// Listening stops when the event bus is dropped.
+    #[wasm_bindgen(js_name = onProgress)]
+    pub fn on_progress(&self, event_bus: &JsEventBus, callback: Function) {
+        let mut receiver = event_bus.inner().subscribe();
+        spawn_local(async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(CoreEvent::Sync(sync_event)) => {
+                        if let Ok(value) = serde_wasm_bindgen::to_value(&sync_event) {
+                            let _ = callback.call1(&JsValue::NULL, &value);
+                        }
+                    }
+                    Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+}
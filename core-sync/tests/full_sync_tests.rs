@@ -0,0 +1,768 @@
+//! Integration tests for full syncs and forced rescans
+//!
+//! These tests run syncs against an in-memory provider and verify what gets
+//! downloaded and how the resulting tracks line up with the provider's files:
+//! duplicate content is only downloaded once, even across listing pages, a
+//! forced rescan reconciles a library that drifted from the provider and
+//! skips files whose checksum is unchanged, deterministic ids survive
+//! re-imports, an interrupted sync resumes from its saved cursor, a large
+//! library never has more than `max_in_memory_files` queued at once, files are
+//! downloaded up to the download concurrency at a time, and per-file events
+//! name the files being processed.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use bridge_desktop::TokioFileSystem;
+use bridge_traits::{
+    database::{DatabaseAdapter, QueryValue},
+    error::{BridgeError, Result as BridgeResult},
+    storage::{FileSystemAccess, RemoteFile, StorageProvider},
+    test_util::InMemorySecureStore,
+    HttpClient, HttpRequest, HttpResponse,
+};
+use bytes::Bytes;
+use core_auth::{AuthManager, ProfileId, ProviderKind};
+use core_library::models::TrackId;
+use core_library::{adapters::sqlite_native::SqliteAdapter, create_pool, DatabaseConfig};
+use core_runtime::events::{CoreEvent, EventBus, SyncEvent};
+use core_sync::{
+    IdStrategy, SqliteSyncJobRepository, SyncConfig, SyncCoordinator, SyncJob, SyncJobId,
+    SyncJobRepository, SyncStatus,
+};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const SAMPLE_MP3: &[u8] = include_bytes!("../../core-metadata/tests/fixtures/sample.mp3");
+
+// ============================================================================
+// Mock Implementations
+// ============================================================================
+
+/// Storage provider that serves files from memory and counts downloads
+struct InMemoryProvider {
+    files: Mutex<Vec<(RemoteFile, Bytes)>>,
+    downloads: AtomicUsize,
+    /// Files per listing page; everything fits on one page when unset
+    page_size: Option<usize>,
+    /// Cursors passed to `list_media`, in order
+    listed_cursors: Mutex<Vec<Option<String>>>,
+    /// Cursor whose listing fails once, interrupting the sync
+    fail_listing_at: Mutex<Option<String>>,
+    /// Database whose unfinished queue items are counted on every download
+    queue_db: Mutex<Option<Arc<dyn DatabaseAdapter>>>,
+    /// Most unfinished queue items seen during a download
+    peak_queued: AtomicU64,
+    /// How long each download takes
+    download_delay: Option<Duration>,
+    /// Downloads currently running
+    active_downloads: AtomicUsize,
+    /// Most downloads seen running at once
+    peak_downloads: AtomicUsize,
+}
+
+impl InMemoryProvider {
+    fn new() -> Self {
+        Self {
+            files: Mutex::new(Vec::new()),
+            downloads: AtomicUsize::new(0),
+            page_size: None,
+            listed_cursors: Mutex::new(Vec::new()),
+            fail_listing_at: Mutex::new(None),
+            queue_db: Mutex::new(None),
+            peak_queued: AtomicU64::new(0),
+            download_delay: None,
+            active_downloads: AtomicUsize::new(0),
+            peak_downloads: AtomicUsize::new(0),
+        }
+    }
+
+    fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    fn with_download_delay(mut self, delay: Duration) -> Self {
+        self.download_delay = Some(delay);
+        self
+    }
+
+    fn fail_listing_once_at(&self, cursor: &str) {
+        *self.fail_listing_at.lock().unwrap() = Some(cursor.to_string());
+    }
+
+    fn with_file(self, id: &str, name: &str, md5: &str, data: impl Into<Bytes>) -> Self {
+        self.add_file(id, name, md5, data);
+        self
+    }
+
+    fn add_file(&self, id: &str, name: &str, md5: &str, data: impl Into<Bytes>) {
+        let data = data.into();
+        let file = RemoteFile {
+            id: id.to_string(),
+            name: name.to_string(),
+            mime_type: Some("audio/mpeg".to_string()),
+            size: Some(data.len() as u64),
+            created_at: Some(1234567890),
+            modified_at: Some(1234567890),
+            is_folder: false,
+            parent_ids: vec![],
+            md5_checksum: Some(md5.to_string()),
+            metadata: HashMap::new(),
+        };
+        self.files.lock().unwrap().push((file, data));
+    }
+
+    fn remove_file(&self, id: &str) {
+        self.files.lock().unwrap().retain(|(file, _)| file.id != id);
+    }
+
+    fn list(&self) -> Vec<RemoteFile> {
+        let files = self.files.lock().unwrap();
+        files.iter().map(|(file, _)| file.clone()).collect()
+    }
+}
+
+#[async_trait::async_trait]
+impl StorageProvider for InMemoryProvider {
+    async fn list_media(
+        &self,
+        cursor: Option<String>,
+    ) -> BridgeResult<(Vec<RemoteFile>, Option<String>)> {
+        self.listed_cursors.lock().unwrap().push(cursor.clone());
+        {
+            let mut fail_listing_at = self.fail_listing_at.lock().unwrap();
+            if cursor.is_some() && *fail_listing_at == cursor {
+                fail_listing_at.take();
+                return Err(BridgeError::OperationFailed("Connection reset".to_string()));
+            }
+        }
+
+        let files = self.list();
+        let Some(page_size) = self.page_size else {
+            return Ok((files, None));
+        };
+
+        // Cursors are the offset of the page's first file
+        let offset = cursor
+            .and_then(|cursor| cursor.strip_prefix("offset-")?.parse::<usize>().ok())
+            .unwrap_or(0);
+        let end = (offset + page_size).min(files.len());
+        let next_cursor = (end < files.len()).then(|| format!("offset-{}", end));
+        Ok((files[offset..end].to_vec(), next_cursor))
+    }
+
+    async fn get_metadata(&self, file_id: &str) -> BridgeResult<RemoteFile> {
+        self.list()
+            .into_iter()
+            .find(|file| file.id == file_id)
+            .ok_or_else(|| BridgeError::OperationFailed(format!("File not found: {}", file_id)))
+    }
+
+    async fn download(&self, file_id: &str, _range: Option<&str>) -> BridgeResult<Bytes> {
+        self.downloads.fetch_add(1, Ordering::SeqCst);
+        let queue_db = self.queue_db.lock().unwrap().clone();
+        if let Some(db) = queue_db {
+            // One query, so an item moving between states isn't counted twice
+            let row = db
+                .query_one(
+                    "SELECT COUNT(*) AS count FROM scan_queue \
+                     WHERE status IN ('pending', 'processing')",
+                    &[],
+                )
+                .await
+                .unwrap();
+            let Some(QueryValue::Integer(queued)) = row.get("count") else {
+                panic!("unexpected count value: {:?}", row.get("count"));
+            };
+            self.peak_queued.fetch_max(*queued as u64, Ordering::SeqCst);
+        }
+
+        let active = self.active_downloads.fetch_add(1, Ordering::SeqCst) + 1;
+        self.peak_downloads.fetch_max(active, Ordering::SeqCst);
+        if let Some(delay) = self.download_delay {
+            core_async::time::sleep(delay).await;
+        }
+        self.active_downloads.fetch_sub(1, Ordering::SeqCst);
+
+        let files = self.files.lock().unwrap();
+        files
+            .iter()
+            .find(|(file, _)| file.id == file_id)
+            .map(|(_, data)| data.clone())
+            .ok_or_else(|| BridgeError::OperationFailed(format!("File not found: {}", file_id)))
+    }
+
+    async fn get_changes(
+        &self,
+        _cursor: Option<String>,
+    ) -> BridgeResult<(Vec<RemoteFile>, Option<String>)> {
+        Ok((self.list(), Some("in-memory-cursor".to_string())))
+    }
+}
+
+/// HTTP client that answers every token request with a valid token response
+struct MockTokenHttpClient;
+
+#[async_trait::async_trait]
+impl HttpClient for MockTokenHttpClient {
+    async fn execute(&self, _request: HttpRequest) -> BridgeResult<HttpResponse> {
+        Ok(HttpResponse {
+            status: 200,
+            headers: HashMap::new(),
+            body: Bytes::from_static(
+                br#"{"access_token":"test-access","refresh_token":"test-refresh","expires_in":3600}"#,
+            ),
+        })
+    }
+
+    async fn download_stream(
+        &self,
+        _url: String,
+    ) -> BridgeResult<Box<dyn core_async::io::AsyncRead + Send + Unpin>> {
+        Err(BridgeError::NotAvailable("download_stream".to_string()))
+    }
+}
+
+// ============================================================================
+// Test Utilities
+// ============================================================================
+
+/// Sign in against the mock token endpoint and return the new profile
+async fn sign_in(auth_manager: &AuthManager) -> ProfileId {
+    let auth_url = auth_manager
+        .sign_in(ProviderKind::GoogleDrive)
+        .await
+        .unwrap();
+    let state = auth_url
+        .split(['?', '&'])
+        .find_map(|pair| pair.strip_prefix("state="))
+        .expect("auth URL has a state parameter")
+        .to_string();
+
+    auth_manager
+        .complete_sign_in(ProviderKind::GoogleDrive, "test-code".to_string(), state)
+        .await
+        .unwrap()
+}
+
+async fn setup(
+    provider: Arc<InMemoryProvider>,
+) -> (SyncCoordinator, Arc<dyn DatabaseAdapter>, ProfileId) {
+    setup_with_config(provider, SyncConfig::default()).await
+}
+
+async fn setup_with_config(
+    provider: Arc<InMemoryProvider>,
+    config: SyncConfig,
+) -> (SyncCoordinator, Arc<dyn DatabaseAdapter>, ProfileId) {
+    setup_with_events(provider, config, EventBus::new(100)).await
+}
+
+async fn setup_with_events(
+    provider: Arc<InMemoryProvider>,
+    config: SyncConfig,
+    event_bus: EventBus,
+) -> (SyncCoordinator, Arc<dyn DatabaseAdapter>, ProfileId) {
+    let db_pool = create_pool(DatabaseConfig::in_memory()).await.unwrap();
+    let db: Arc<dyn DatabaseAdapter> = Arc::new(SqliteAdapter::from_pool(db_pool));
+
+    // Sync jobs reference the provider kind; tracks reference its display name
+    for (id, display_name) in [
+        (ProviderKind::GoogleDrive.as_str(), "Google Drive"),
+        (ProviderKind::GoogleDrive.display_name(), "Google Drive"),
+    ] {
+        db.execute(
+            "INSERT INTO providers (id, type, display_name, profile_id, created_at) \
+             VALUES (?, 'GoogleDrive', ?, 'test-profile', 1699200000)",
+            &[
+                QueryValue::Text(id.to_string()),
+                QueryValue::Text(display_name.to_string()),
+            ],
+        )
+        .await
+        .unwrap();
+    }
+
+    let temp_dir =
+        std::env::temp_dir().join(format!("mpc_full_sync_test_{}", uuid::Uuid::new_v4()));
+    let file_system: Arc<dyn FileSystemAccess> = Arc::new(TokioFileSystem::with_directories(
+        temp_dir.join("cache"),
+        temp_dir.join("data"),
+    ));
+
+    let auth_manager = Arc::new(AuthManager::new(
+        Arc::new(InMemorySecureStore::new()),
+        event_bus.clone(),
+        Arc::new(MockTokenHttpClient),
+    ));
+    let profile_id = sign_in(&auth_manager).await;
+
+    let config = SyncConfig {
+        header_only_download: false,
+        ..config
+    };
+    let coordinator = SyncCoordinator::new(
+        config,
+        auth_manager,
+        Arc::new(event_bus),
+        None,
+        file_system,
+        db.clone(),
+    )
+    .await
+    .unwrap();
+
+    coordinator
+        .register_provider(
+            ProviderKind::GoogleDrive,
+            provider as Arc<dyn StorageProvider>,
+        )
+        .await;
+
+    (coordinator, db, profile_id)
+}
+
+/// Poll a sync job until it reaches a terminal state
+async fn wait_for_job(coordinator: &SyncCoordinator, job_id: SyncJobId) -> SyncJob {
+    let mut job = coordinator.get_status(job_id).await.unwrap();
+    for _ in 0..200 {
+        if job.status.is_terminal() {
+            break;
+        }
+        core_async::time::sleep(Duration::from_millis(50)).await;
+        job = coordinator.get_status(job_id).await.unwrap();
+    }
+    job
+}
+
+/// Provider file IDs of all tracks, sorted
+async fn track_file_ids(db: &Arc<dyn DatabaseAdapter>) -> Vec<String> {
+    let rows = db
+        .query(
+            "SELECT provider_file_id FROM tracks ORDER BY provider_file_id",
+            &[],
+        )
+        .await
+        .unwrap();
+    rows.iter()
+        .map(|row| match row.get("provider_file_id") {
+            Some(QueryValue::Text(id)) => id.clone(),
+            other => panic!("unexpected provider_file_id value: {:?}", other),
+        })
+        .collect()
+}
+
+/// Track IDs keyed by provider file ID, sorted
+async fn track_ids(db: &Arc<dyn DatabaseAdapter>) -> Vec<(String, String)> {
+    let rows = db
+        .query(
+            "SELECT provider_file_id, id FROM tracks ORDER BY provider_file_id",
+            &[],
+        )
+        .await
+        .unwrap();
+    rows.iter()
+        .map(|row| match (row.get("provider_file_id"), row.get("id")) {
+            (Some(QueryValue::Text(file_id)), Some(QueryValue::Text(id))) => {
+                (file_id.clone(), id.clone())
+            }
+            other => panic!("unexpected track row: {:?}", other),
+        })
+        .collect()
+}
+
+/// Sample audio with a distinct suffix so each file gets its own content hash
+fn unique_sample(suffix: &[u8]) -> Vec<u8> {
+    [SAMPLE_MP3, suffix].concat()
+}
+
+async fn count_tracks(db: &Arc<dyn DatabaseAdapter>) -> i64 {
+    let row = db
+        .query_one("SELECT COUNT(*) AS count FROM tracks", &[])
+        .await
+        .unwrap();
+    match row.get("count") {
+        Some(QueryValue::Integer(count)) => *count,
+        other => panic!("unexpected count value: {:?}", other),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[core_async::test]
+async fn test_identical_files_are_downloaded_once() {
+    let provider = Arc::new(
+        InMemoryProvider::new()
+            .with_file("file-1", "song.mp3", "d41d8cd9", SAMPLE_MP3)
+            .with_file("file-2", "song (copy).mp3", "D41D8CD9", SAMPLE_MP3),
+    );
+    let (coordinator, db, profile_id) = setup(provider.clone()).await;
+
+    let job_id = coordinator.start_full_sync(profile_id).await.unwrap();
+    let job = wait_for_job(&coordinator, job_id).await;
+
+    assert_eq!(job.status, SyncStatus::Completed);
+    assert_eq!(provider.downloads.load(Ordering::SeqCst), 1);
+    assert_eq!(count_tracks(&db).await, 1);
+    let stats = job.stats.expect("completed job has stats");
+    assert_eq!(stats.items_added, 1);
+    assert_eq!(stats.items_failed, 0);
+}
+
+#[core_async::test]
+async fn test_identical_files_on_different_pages_are_downloaded_once() {
+    // The copy is listed on the second page
+    let provider = Arc::new(
+        InMemoryProvider::new()
+            .with_page_size(2)
+            .with_file("file-1", "song.mp3", "d41d8cd9", SAMPLE_MP3)
+            .with_file("file-2", "other.mp3", "aaaa", unique_sample(b"other"))
+            .with_file("file-3", "song (copy).mp3", "D41D8CD9", SAMPLE_MP3),
+    );
+    let (coordinator, db, profile_id) = setup(provider.clone()).await;
+
+    let job_id = coordinator.start_full_sync(profile_id).await.unwrap();
+    let job = wait_for_job(&coordinator, job_id).await;
+
+    assert_eq!(job.status, SyncStatus::Completed);
+    assert_eq!(provider.listed_cursors.lock().unwrap().len(), 2);
+    assert_eq!(provider.downloads.load(Ordering::SeqCst), 2);
+    assert_eq!(count_tracks(&db).await, 2);
+    let stats = job.stats.expect("completed job has stats");
+    assert_eq!(stats.items_added, 2);
+    assert_eq!(stats.items_failed, 0);
+}
+
+#[core_async::test]
+async fn test_force_full_rescan_reconciles_drifted_library() {
+    let provider = Arc::new(
+        InMemoryProvider::new()
+            .with_file("file-1", "kept.mp3", "aaaa", unique_sample(b"kept"))
+            .with_file("file-2", "removed.mp3", "bbbb", unique_sample(b"removed")),
+    );
+    let (coordinator, db, profile_id) = setup(provider.clone()).await;
+
+    let job_id = coordinator.start_full_sync(profile_id).await.unwrap();
+    let job = wait_for_job(&coordinator, job_id).await;
+    assert_eq!(job.status, SyncStatus::Completed);
+    assert_eq!(provider.downloads.load(Ordering::SeqCst), 2);
+    assert_eq!(track_file_ids(&db).await, vec!["file-1", "file-2"]);
+
+    // An earlier incremental sync left a cursor behind
+    let repository = SqliteSyncJobRepository::new();
+    let stale_job = SyncJob::new_incremental(ProviderKind::GoogleDrive, "stale-cursor".to_string())
+        .for_profile(profile_id);
+    repository.insert(db.as_ref(), &stale_job).await.unwrap();
+
+    // The provider drifted: file-2 is gone and file-3 was never synced
+    provider.remove_file("file-2");
+    provider.add_file("file-3", "added.mp3", "cccc", unique_sample(b"added"));
+    provider.downloads.store(0, Ordering::SeqCst);
+
+    let job_id = coordinator.force_full_rescan(profile_id).await.unwrap();
+    let job = wait_for_job(&coordinator, job_id).await;
+
+    assert_eq!(job.status, SyncStatus::Completed);
+    // Only the missing file is downloaded; the unchanged one is matched
+    assert_eq!(provider.downloads.load(Ordering::SeqCst), 1);
+    let stats = job.stats.expect("completed job has stats");
+    assert_eq!(stats.items_added, 1);
+    assert_eq!(stats.items_updated, 0);
+    assert_eq!(stats.items_deleted, 1);
+
+    // The stale track is soft deleted and nothing is duplicated
+    let file_ids = track_file_ids(&db).await;
+    assert_eq!(file_ids.len(), 3);
+    assert_eq!(file_ids[0], "DELETED_file-2");
+    assert_eq!(file_ids[1..], ["file-1", "file-3"]);
+
+    let stale_job = repository
+        .find_by_id(db.as_ref(), &stale_job.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(stale_job.cursor.is_none());
+}
+
+#[core_async::test]
+async fn test_force_full_rescan_matches_unchanged_content_by_md5() {
+    let provider = Arc::new(
+        InMemoryProvider::new()
+            .with_file("file-1", "kept.mp3", "aaaa", unique_sample(b"kept"))
+            .with_file("file-2", "edited.mp3", "bbbb", unique_sample(b"before")),
+    );
+    let (coordinator, db, profile_id) = setup(provider.clone()).await;
+
+    let job_id = coordinator.start_full_sync(profile_id).await.unwrap();
+    let job = wait_for_job(&coordinator, job_id).await;
+    assert_eq!(job.status, SyncStatus::Completed);
+    assert_eq!(provider.downloads.load(Ordering::SeqCst), 2);
+
+    // Tracks synced before modification times were recorded only match by checksum
+    db.execute("UPDATE tracks SET provider_modified_at = NULL", &[])
+        .await
+        .unwrap();
+
+    // file-2's content changed on the provider
+    provider.remove_file("file-2");
+    provider.add_file("file-2", "edited.mp3", "dddd", unique_sample(b"after"));
+    provider.downloads.store(0, Ordering::SeqCst);
+
+    let job_id = coordinator.force_full_rescan(profile_id).await.unwrap();
+    let job = wait_for_job(&coordinator, job_id).await;
+
+    assert_eq!(job.status, SyncStatus::Completed);
+    assert_eq!(provider.downloads.load(Ordering::SeqCst), 1);
+    let stats = job.stats.expect("completed job has stats");
+    assert_eq!(stats.items_added, 0);
+    assert_eq!(stats.items_updated, 1);
+    assert_eq!(track_file_ids(&db).await, vec!["file-1", "file-2"]);
+}
+
+#[core_async::test]
+async fn test_deterministic_ids_are_stable_across_imports() {
+    let config = SyncConfig {
+        id_strategy: IdStrategy::Deterministic,
+        ..Default::default()
+    };
+    let mut imports = Vec::new();
+
+    // Import the same library into two independent databases
+    for _ in 0..2 {
+        let provider = Arc::new(InMemoryProvider::new().with_file(
+            "file-1",
+            "song.mp3",
+            "aaaa",
+            unique_sample(b"one"),
+        ));
+        let (coordinator, db, profile_id) = setup_with_config(provider, config.clone()).await;
+
+        let job_id = coordinator.start_full_sync(profile_id).await.unwrap();
+        let job = wait_for_job(&coordinator, job_id).await;
+        assert_eq!(job.status, SyncStatus::Completed);
+
+        imports.push(track_ids(&db).await);
+    }
+
+    assert_eq!(imports[0].len(), 1);
+    assert_eq!(imports[0], imports[1]);
+    assert_eq!(
+        imports[0][0].1,
+        TrackId::from_provider_file(ProviderKind::GoogleDrive.display_name(), "file-1").to_string()
+    );
+}
+
+#[core_async::test]
+async fn test_deterministic_id_is_reclaimed_after_soft_delete() {
+    let config = SyncConfig {
+        id_strategy: IdStrategy::Deterministic,
+        ..Default::default()
+    };
+    let provider = Arc::new(InMemoryProvider::new().with_file(
+        "file-1",
+        "song.mp3",
+        "aaaa",
+        unique_sample(b"one"),
+    ));
+    let (coordinator, db, profile_id) = setup_with_config(provider.clone(), config).await;
+
+    let job_id = coordinator.start_full_sync(profile_id).await.unwrap();
+    assert_eq!(
+        wait_for_job(&coordinator, job_id).await.status,
+        SyncStatus::Completed
+    );
+    let original = track_ids(&db).await;
+
+    // The file disappears, then comes back
+    provider.remove_file("file-1");
+    let job_id = coordinator.force_full_rescan(profile_id).await.unwrap();
+    wait_for_job(&coordinator, job_id).await;
+    assert_eq!(track_file_ids(&db).await, vec!["DELETED_file-1"]);
+
+    provider.add_file("file-1", "song.mp3", "aaaa", unique_sample(b"one"));
+    let job_id = coordinator.force_full_rescan(profile_id).await.unwrap();
+    let job = wait_for_job(&coordinator, job_id).await;
+
+    assert_eq!(job.status, SyncStatus::Completed);
+    assert_eq!(track_ids(&db).await, original);
+}
+
+#[core_async::test]
+async fn test_interrupted_sync_resumes_from_saved_cursor() {
+    // Three pages of two files; listing the third page fails the first time
+    let provider = InMemoryProvider::new().with_page_size(2);
+    for i in 1..=6 {
+        provider.add_file(
+            &format!("file-{}", i),
+            &format!("song-{}.mp3", i),
+            &format!("md5-{}", i),
+            unique_sample(format!("song-{}", i).as_bytes()),
+        );
+    }
+    provider.fail_listing_once_at("offset-4");
+    let provider = Arc::new(provider);
+    let (coordinator, db, profile_id) = setup(provider.clone()).await;
+
+    let job_id = coordinator.start_full_sync(profile_id).await.unwrap();
+    let job = wait_for_job(&coordinator, job_id).await;
+
+    // Pages 1 and 2 were processed and the cursor for page 3 was saved
+    assert_eq!(job.status, SyncStatus::Failed);
+    assert_eq!(job.cursor.as_deref(), Some("offset-4"));
+    assert_eq!(
+        track_file_ids(&db).await,
+        ["file-1", "file-2", "file-3", "file-4"]
+    );
+
+    // The failed task stops tracking the job just after recording the failure
+    while coordinator.is_sync_active(profile_id).await {
+        core_async::time::sleep(Duration::from_millis(10)).await;
+    }
+    provider.listed_cursors.lock().unwrap().clear();
+
+    let resumed_id = coordinator.resume_sync(profile_id, job_id).await.unwrap();
+    assert_eq!(resumed_id, job_id);
+    let job = wait_for_job(&coordinator, job_id).await;
+
+    assert_eq!(job.status, SyncStatus::Completed);
+    assert_eq!(
+        *provider.listed_cursors.lock().unwrap(),
+        [Some("offset-4".to_string())]
+    );
+    assert_eq!(provider.downloads.load(Ordering::SeqCst), 6);
+    let stats = job.stats.expect("completed job has stats");
+    assert_eq!(stats.items_added, 2);
+    assert_eq!(stats.items_deleted, 0);
+    assert!(job.cursor.is_none());
+    assert_eq!(track_file_ids(&db).await.len(), 6);
+}
+
+#[core_async::test]
+async fn test_large_library_keeps_queued_files_bounded() {
+    // Three pages of twenty files, queued at most four at a time
+    let provider = InMemoryProvider::new()
+        .with_page_size(20)
+        .with_download_delay(Duration::from_millis(5));
+    for i in 0..60 {
+        provider.add_file(
+            &format!("file-{:02}", i),
+            &format!("song-{}.mp3", i),
+            &format!("md5-{}", i),
+            unique_sample(format!("song-{}", i).as_bytes()),
+        );
+    }
+    let provider = Arc::new(provider);
+    let config = SyncConfig {
+        max_in_memory_files: 4,
+        max_concurrent_downloads: 3,
+        ..Default::default()
+    };
+    let (coordinator, db, profile_id) = setup_with_config(provider.clone(), config).await;
+    *provider.queue_db.lock().unwrap() = Some(db.clone());
+
+    let job_id = coordinator.start_full_sync(profile_id).await.unwrap();
+    let job = wait_for_job(&coordinator, job_id).await;
+
+    assert_eq!(job.status, SyncStatus::Completed);
+    assert_eq!(provider.downloads.load(Ordering::SeqCst), 60);
+    assert_eq!(count_tracks(&db).await, 60);
+    // The queue is topped back up to the limit, never past it
+    assert_eq!(provider.peak_queued.load(Ordering::SeqCst), 4);
+    assert_eq!(provider.peak_downloads.load(Ordering::SeqCst), 3);
+}
+
+#[core_async::test]
+async fn test_files_download_up_to_the_concurrency_limit() {
+    let provider = InMemoryProvider::new().with_download_delay(Duration::from_millis(50));
+    for i in 0..12 {
+        provider.add_file(
+            &format!("file-{:02}", i),
+            &format!("song-{}.mp3", i),
+            &format!("md5-{}", i),
+            unique_sample(format!("song-{}", i).as_bytes()),
+        );
+    }
+    let provider = Arc::new(provider);
+    let config = SyncConfig {
+        max_concurrent_downloads: 3,
+        ..Default::default()
+    };
+    let (coordinator, db, profile_id) = setup_with_config(provider.clone(), config).await;
+
+    let job_id = coordinator.start_full_sync(profile_id).await.unwrap();
+    let job = wait_for_job(&coordinator, job_id).await;
+
+    assert_eq!(job.status, SyncStatus::Completed);
+    let stats = job.stats.unwrap();
+    assert_eq!(stats.items_added, 12);
+    assert_eq!(stats.items_failed, 0);
+    assert_eq!(count_tracks(&db).await, 12);
+    assert_eq!(provider.peak_downloads.load(Ordering::SeqCst), 3);
+}
+
+#[core_async::test]
+async fn test_per_item_events_name_each_file() {
+    let provider = Arc::new(
+        InMemoryProvider::new()
+            .with_file("file-1", "first.mp3", "aaaa", unique_sample(b"first"))
+            .with_file("file-2", "second.mp3", "bbbb", unique_sample(b"second")),
+    );
+    let config = SyncConfig {
+        emit_per_item_events: true,
+        max_concurrent_downloads: 1,
+        ..Default::default()
+    };
+    let event_bus = EventBus::new(100);
+    let mut events = event_bus.subscribe();
+    let (coordinator, _db, profile_id) = setup_with_events(provider, config, event_bus).await;
+
+    let job_id = coordinator.start_full_sync(profile_id).await.unwrap();
+    assert_eq!(
+        wait_for_job(&coordinator, job_id).await.status,
+        SyncStatus::Completed
+    );
+
+    let mut started = Vec::new();
+    let mut finished = Vec::new();
+    while let Ok(event) = events.try_recv() {
+        match event {
+            CoreEvent::Sync(SyncEvent::ItemStarted {
+                job_id: event_job_id,
+                file_name,
+                remote_file_id,
+            }) => {
+                assert_eq!(event_job_id, job_id.to_string());
+                // Files are processed one at a time
+                assert_eq!(started.len(), finished.len());
+                started.push((file_name, remote_file_id));
+            }
+            CoreEvent::Sync(SyncEvent::ItemFinished {
+                file_name,
+                added,
+                bytes,
+                ..
+            }) => {
+                assert_eq!(Some(&file_name), started.last().map(|(name, _)| name));
+                assert!(added);
+                assert!(bytes > 0);
+                finished.push(file_name);
+            }
+            _ => {}
+        }
+    }
+
+    started.sort();
+    finished.sort();
+    assert_eq!(
+        started,
+        [
+            ("first.mp3".to_string(), "file-1".to_string()),
+            ("second.mp3".to_string(), "file-2".to_string()),
+        ]
+    );
+    assert_eq!(finished, ["first.mp3", "second.mp3"]);
+}
@@ -10,7 +10,8 @@
 use bridge_traits::{
     database::DatabaseAdapter,
     error::BridgeError,
-    storage::{FileSystemAccess, RemoteFile, SecureStore, StorageProvider},
+    storage::{FileSystemAccess, RemoteFile, StorageProvider},
+    test_util::InMemorySecureStore,
     HttpClient, HttpRequest, HttpResponse,
 };
 use bytes::Bytes;
@@ -93,47 +94,6 @@ impl StorageProvider for MockIncrementalProvider {
     }
 }
 
-struct MockSecureStore {
-    data: Arc<AsyncMutex<HashMap<String, Vec<u8>>>>,
-}
-
-impl MockSecureStore {
-    fn new() -> Self {
-        Self {
-            data: Arc::new(AsyncMutex::new(HashMap::new())),
-        }
-    }
-}
-
-#[async_trait::async_trait]
-impl SecureStore for MockSecureStore {
-    async fn set_secret(&self, key: &str, value: &[u8]) -> bridge_traits::error::Result<()> {
-        self.data
-            .lock()
-            .await
-            .insert(key.to_string(), value.to_vec());
-        Ok(())
-    }
-
-    async fn get_secret(&self, key: &str) -> bridge_traits::error::Result<Option<Vec<u8>>> {
-        Ok(self.data.lock().await.get(key).cloned())
-    }
-
-    async fn delete_secret(&self, key: &str) -> bridge_traits::error::Result<()> {
-        self.data.lock().await.remove(key);
-        Ok(())
-    }
-
-    async fn list_keys(&self) -> bridge_traits::error::Result<Vec<String>> {
-        Ok(self.data.lock().await.keys().cloned().collect())
-    }
-
-    async fn clear_all(&self) -> bridge_traits::error::Result<()> {
-        self.data.lock().await.clear();
-        Ok(())
-    }
-}
-
 struct MockHttpClient;
 
 #[async_trait::async_trait]
@@ -244,7 +204,7 @@ async fn setup_test_coordinator(
     let db: Arc<dyn DatabaseAdapter> = Arc::new(SqliteAdapter::from_pool(db_pool.clone()));
 
     let event_bus = Arc::new(EventBus::new(100));
-    let secure_store = Arc::new(MockSecureStore::new());
+    let secure_store = Arc::new(InMemorySecureStore::new());
     let http_client = Arc::new(MockHttpClient);
 
     let temp_dir = std::env::temp_dir().join("mpc_incremental_test");
@@ -258,8 +218,10 @@ async fn setup_test_coordinator(
         http_client,
     ));
 
-    let mut config = SyncConfig::default();
-    config.max_concurrent_downloads = 1; // Sequential processing for tests
+    let config = SyncConfig {
+        max_concurrent_downloads: 1, // Sequential processing for tests
+        ..Default::default()
+    };
 
     let coordinator = SyncCoordinator::new(
         config,
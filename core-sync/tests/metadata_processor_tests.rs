@@ -0,0 +1,579 @@
+//! Integration tests for metadata processor temp file handling
+//!
+//! These tests verify that downloads staged in the temp directory never
+//! outlive processing, even when it fails, and that orphans left by a crash
+//! are swept by `cleanup_temp`. A failed item must roll back its transaction.
+//! They also check that relations for a batch of tracks are resolved with a
+//! bounded number of queries and credited as the compilation policy asks, and
+//! that loudness analysis stores a gain.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use bridge_desktop::TokioFileSystem;
+use bridge_traits::{
+    database::{DatabaseAdapter, DatabaseStatistics, QueryRow, QueryValue, TransactionId},
+    error::{BridgeError, Result as BridgeResult},
+    storage::{FileSystemAccess, RemoteFile, StorageProvider},
+    time::Clock,
+};
+use bytes::Bytes;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use core_library::{
+    adapters::sqlite_native::SqliteAdapter,
+    create_pool,
+    repositories::{
+        AlbumKeyOptions, SqliteAlbumRepository, SqliteArtistRepository, SqliteArtworkRepository,
+        SqliteTrackRepository, TrackRepository,
+    },
+    DatabaseConfig,
+};
+use core_metadata::extractor::ExtractedMetadata;
+use core_sync::{CompilationPolicy, MetadataProcessor, ProcessorConfig, WorkItem};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+const SAMPLE_MP3: &[u8] = include_bytes!("../../core-metadata/tests/fixtures/sample.mp3");
+
+/// Provider that serves the sample MP3 for every file
+struct SampleProvider;
+
+#[async_trait::async_trait]
+impl StorageProvider for SampleProvider {
+    async fn list_media(
+        &self,
+        _cursor: Option<String>,
+    ) -> BridgeResult<(Vec<RemoteFile>, Option<String>)> {
+        Ok((Vec::new(), None))
+    }
+
+    async fn get_metadata(&self, file_id: &str) -> BridgeResult<RemoteFile> {
+        Err(BridgeError::OperationFailed(format!(
+            "File not found: {}",
+            file_id
+        )))
+    }
+
+    async fn download(&self, _file_id: &str, _range: Option<&str>) -> BridgeResult<Bytes> {
+        Ok(Bytes::from_static(SAMPLE_MP3))
+    }
+
+    async fn get_changes(
+        &self,
+        _cursor: Option<String>,
+    ) -> BridgeResult<(Vec<RemoteFile>, Option<String>)> {
+        Ok((Vec::new(), None))
+    }
+}
+
+/// Clock reporting a fixed offset from the real time
+struct OffsetClock(ChronoDuration);
+
+impl Clock for OffsetClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now() + self.0
+    }
+}
+
+/// Adapter counting the statements that touch the `artists` table
+struct CountingAdapter {
+    inner: SqliteAdapter,
+    artist_statements: AtomicUsize,
+}
+
+impl CountingAdapter {
+    fn count(&self, sql: &str) {
+        if sql.contains(" artists") {
+            self.artist_statements.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl DatabaseAdapter for CountingAdapter {
+    async fn initialize(&mut self) -> BridgeResult<()> {
+        self.inner.initialize().await
+    }
+
+    async fn health_check(&self) -> BridgeResult<()> {
+        self.inner.health_check().await
+    }
+
+    async fn close(&mut self) -> BridgeResult<()> {
+        self.inner.close().await
+    }
+
+    async fn query(&self, query: &str, params: &[QueryValue]) -> BridgeResult<Vec<QueryRow>> {
+        self.count(query);
+        self.inner.query(query, params).await
+    }
+
+    async fn execute(&self, statement: &str, params: &[QueryValue]) -> BridgeResult<u64> {
+        self.count(statement);
+        self.inner.execute(statement, params).await
+    }
+
+    async fn query_one_optional(
+        &self,
+        query: &str,
+        params: &[QueryValue],
+    ) -> BridgeResult<Option<QueryRow>> {
+        self.count(query);
+        self.inner.query_one_optional(query, params).await
+    }
+
+    async fn query_one(&self, query: &str, params: &[QueryValue]) -> BridgeResult<QueryRow> {
+        self.count(query);
+        self.inner.query_one(query, params).await
+    }
+
+    async fn begin_transaction(&self) -> BridgeResult<TransactionId> {
+        self.inner.begin_transaction().await
+    }
+
+    async fn commit_transaction(&self, transaction_id: TransactionId) -> BridgeResult<()> {
+        self.inner.commit_transaction(transaction_id).await
+    }
+
+    async fn rollback_transaction(&self, transaction_id: TransactionId) -> BridgeResult<()> {
+        self.inner.rollback_transaction(transaction_id).await
+    }
+
+    async fn query_in_transaction(
+        &self,
+        transaction_id: TransactionId,
+        query: &str,
+        params: &[QueryValue],
+    ) -> BridgeResult<Vec<QueryRow>> {
+        self.count(query);
+        self.inner
+            .query_in_transaction(transaction_id, query, params)
+            .await
+    }
+
+    async fn execute_in_transaction(
+        &self,
+        transaction_id: TransactionId,
+        statement: &str,
+        params: &[QueryValue],
+    ) -> BridgeResult<u64> {
+        self.count(statement);
+        self.inner
+            .execute_in_transaction(transaction_id, statement, params)
+            .await
+    }
+
+    async fn execute_batch(&self, statements: &[(&str, &[QueryValue])]) -> BridgeResult<Vec<u64>> {
+        for (statement, _) in statements {
+            self.count(statement);
+        }
+        self.inner.execute_batch(statements).await
+    }
+
+    async fn get_schema_version(&self) -> BridgeResult<i64> {
+        self.inner.get_schema_version().await
+    }
+
+    async fn apply_migration(&self, version: i64, up_sql: &str) -> BridgeResult<()> {
+        self.inner.apply_migration(version, up_sql).await
+    }
+
+    async fn is_migration_applied(&self, version: i64) -> BridgeResult<bool> {
+        self.inner.is_migration_applied(version).await
+    }
+
+    async fn last_insert_rowid(&self) -> BridgeResult<i64> {
+        self.inner.last_insert_rowid().await
+    }
+
+    async fn get_statistics(&self) -> BridgeResult<DatabaseStatistics> {
+        self.inner.get_statistics().await
+    }
+}
+
+/// Tags of a track by `artist` on `album`
+fn tagged(artist: &str, album: &str) -> ExtractedMetadata {
+    ExtractedMetadata {
+        title: Some("Song".to_string()),
+        artist: Some(artist.to_string()),
+        album: Some(album.to_string()),
+        album_artist: None,
+        year: Some(2024),
+        track_number: None,
+        total_tracks: None,
+        disc_number: None,
+        total_discs: None,
+        genre: None,
+        composer: None,
+        comment: None,
+        duration_ms: 180_000,
+        bitrate: None,
+        sample_rate: None,
+        channels: None,
+        format: "MP3".to_string(),
+        file_size: 0,
+        mime_type: "audio/mpeg".to_string(),
+        content_hash: String::new(),
+        artwork: Vec::new(),
+        has_errors: false,
+        partial_metadata: false,
+        truncated_fields: Vec::new(),
+        original_title: None,
+        version: None,
+        featured_artists: Vec::new(),
+    }
+}
+
+struct Fixture {
+    db: Arc<dyn DatabaseAdapter>,
+    file_system: Arc<dyn FileSystemAccess>,
+}
+
+async fn in_memory_adapter() -> SqliteAdapter {
+    let pool = create_pool(DatabaseConfig::in_memory().max_connections(1))
+        .await
+        .unwrap();
+    SqliteAdapter::from_pool(pool)
+}
+
+impl Fixture {
+    async fn new() -> Self {
+        Self::with_db(Arc::new(in_memory_adapter().await))
+    }
+
+    fn with_db(db: Arc<dyn DatabaseAdapter>) -> Self {
+        let temp_dir =
+            std::env::temp_dir().join(format!("mpc_processor_test_{}", uuid::Uuid::new_v4()));
+
+        Self {
+            db,
+            file_system: Arc::new(TokioFileSystem::with_directories(
+                temp_dir.join("cache"),
+                temp_dir.join("data"),
+            )),
+        }
+    }
+
+    fn processor(&self, clock: OffsetClock) -> MetadataProcessor {
+        self.processor_with_config(ProcessorConfig::default(), clock)
+    }
+
+    fn processor_with_config(
+        &self,
+        config: ProcessorConfig,
+        clock: OffsetClock,
+    ) -> MetadataProcessor {
+        MetadataProcessor::with_clock(
+            ProcessorConfig {
+                header_only: false,
+                max_download_retries: 1,
+                ..config
+            },
+            self.file_system.clone(),
+            Arc::new(SqliteTrackRepository::new(self.db.clone())),
+            Arc::new(SqliteArtistRepository::new(self.db.clone())),
+            Arc::new(SqliteAlbumRepository::new(self.db.clone())),
+            Arc::new(SqliteArtworkRepository::new(self.db.clone())),
+            None,
+            self.db.clone(),
+            Arc::new(clock),
+        )
+    }
+}
+
+#[core_async::test]
+async fn test_processing_error_removes_temp_file() {
+    let fixture = Fixture::new().await;
+    let processor = fixture.processor(OffsetClock(ChronoDuration::zero()));
+    let provider: Arc<dyn StorageProvider> = Arc::new(SampleProvider);
+    let work_item = WorkItem::new("file-1".to_string(), "audio/mpeg".to_string());
+
+    // Metadata extraction succeeds, but inserting the track fails because
+    // the provider doesn't exist
+    let result = processor
+        .process_work_item(&work_item, &provider, "missing-provider", "song.mp3", false)
+        .await;
+    assert!(result.is_err());
+
+    let temp_dir = processor.temp_directory().await.unwrap();
+    let leftovers = fixture.file_system.list_directory(&temp_dir).await.unwrap();
+    assert!(
+        leftovers.is_empty(),
+        "temp files left behind: {:?}",
+        leftovers
+    );
+}
+
+#[core_async::test]
+async fn test_processing_error_rolls_back_transaction() {
+    // The adapter only has one connection, so a leaked transaction would
+    // starve every later query
+    let fixture = Fixture::new().await;
+    let processor = fixture.processor(OffsetClock(ChronoDuration::zero()));
+    let provider: Arc<dyn StorageProvider> = Arc::new(SampleProvider);
+    let work_item = WorkItem::new("file-1".to_string(), "audio/mpeg".to_string());
+
+    // The artist is created before inserting the track fails
+    let result = processor
+        .process_work_item(&work_item, &provider, "missing-provider", "song.mp3", false)
+        .await;
+    assert!(result.is_err());
+
+    let artists = fixture
+        .db
+        .query("SELECT COUNT(*) AS count FROM artists", &[])
+        .await
+        .unwrap();
+    assert_eq!(artists[0].get("count").and_then(|v| v.as_i64()), Some(0));
+}
+
+#[core_async::test]
+async fn test_cleanup_temp_removes_old_orphans() {
+    let fixture = Fixture::new().await;
+    let processor = fixture.processor(OffsetClock(ChronoDuration::zero()));
+
+    // A crash left a partial download behind
+    let temp_dir = processor.temp_directory().await.unwrap();
+    fixture.file_system.create_dir_all(&temp_dir).await.unwrap();
+    let orphan = temp_dir.join("orphan_song.mp3");
+    fixture
+        .file_system
+        .write_file(&orphan, Bytes::from_static(b"partial"))
+        .await
+        .unwrap();
+
+    // Recent files are left alone
+    let removed = processor
+        .cleanup_temp(Duration::from_secs(60 * 60))
+        .await
+        .unwrap();
+    assert_eq!(removed, 0);
+    assert!(fixture.file_system.exists(&orphan).await.unwrap());
+
+    // Two hours later the orphan is stale
+    let later = fixture.processor(OffsetClock(ChronoDuration::hours(2)));
+    let removed = later
+        .cleanup_temp(Duration::from_secs(60 * 60))
+        .await
+        .unwrap();
+    assert_eq!(removed, 1);
+    assert!(!fixture.file_system.exists(&orphan).await.unwrap());
+}
+
+#[core_async::test]
+async fn test_resolve_relations_batches_artist_queries() {
+    let db = Arc::new(CountingAdapter {
+        inner: in_memory_adapter().await,
+        artist_statements: AtomicUsize::new(0),
+    });
+    let fixture = Fixture::with_db(db.clone());
+    let processor = fixture.processor(OffsetClock(ChronoDuration::zero()));
+
+    // 100 tracks by 10 artists, one album each
+    let metadata: Vec<ExtractedMetadata> = (0..100)
+        .map(|i| tagged(&format!("Artist {}", i % 10), &format!("Album {}", i % 10)))
+        .collect();
+    let relations = processor.resolve_relations(&metadata).await.unwrap();
+
+    // One lookup and one bulk insert, rather than two per track
+    assert_eq!(db.artist_statements.load(Ordering::SeqCst), 2);
+    assert_eq!(relations.len(), 100);
+    assert_eq!(relations[3], relations[13]);
+    assert_ne!(relations[3], relations[4]);
+    assert!(relations
+        .iter()
+        .all(|r| r.artist_id.is_some() && r.album_id.is_some()));
+
+    // A second batch only looks the artists up
+    processor.resolve_relations(&metadata).await.unwrap();
+    assert_eq!(db.artist_statements.load(Ordering::SeqCst), 3);
+    let artists = db
+        .query("SELECT COUNT(*) AS count FROM artists", &[])
+        .await
+        .unwrap();
+    assert_eq!(artists[0].get("count").and_then(|v| v.as_i64()), Some(10));
+}
+
+#[core_async::test]
+async fn test_edition_suffixes_collapse_to_one_album() {
+    let fixture = Fixture::new().await;
+    let processor = fixture.processor_with_config(
+        ProcessorConfig {
+            album_key: AlbumKeyOptions {
+                strip_edition_suffixes: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        OffsetClock(ChronoDuration::zero()),
+    );
+
+    let relations = processor
+        .resolve_relations(&[tagged("Artist", "Album (Deluxe)")])
+        .await
+        .unwrap();
+    let later = processor
+        .resolve_relations(&[tagged("Artist", "Album (Deluxe Edition)")])
+        .await
+        .unwrap();
+    assert_eq!(relations[0].album_id, later[0].album_id);
+
+    // The first name seen is kept for display
+    let albums = fixture
+        .db
+        .query("SELECT name, normalized_name FROM albums", &[])
+        .await
+        .unwrap();
+    assert_eq!(albums.len(), 1);
+    assert_eq!(
+        albums[0].get("name").and_then(|v| v.as_string()),
+        Some("Album (Deluxe)".to_string())
+    );
+    assert_eq!(
+        albums[0].get("normalized_name").and_then(|v| v.as_string()),
+        Some("album".to_string())
+    );
+
+    // Without suffix stripping the editions stay apart
+    let processor = fixture.processor(OffsetClock(ChronoDuration::zero()));
+    let other = processor
+        .resolve_relations(&[tagged("Artist", "Album (Deluxe Edition)")])
+        .await
+        .unwrap();
+    assert_ne!(other[0].album_id, relations[0].album_id);
+}
+
+/// Name of the artist an album is credited to
+async fn album_artist_name(db: &Arc<dyn DatabaseAdapter>, album_id: &str) -> Option<String> {
+    let rows = db
+        .query(
+            "SELECT ar.name FROM albums al JOIN artists ar ON ar.id = al.artist_id \
+             WHERE al.id = ?",
+            &[QueryValue::Text(album_id.to_string())],
+        )
+        .await
+        .unwrap();
+    rows.first()
+        .and_then(|row| row.get("name"))
+        .and_then(|v| v.as_string())
+}
+
+#[core_async::test]
+async fn test_distinct_artist_threshold_credits_various_artists() {
+    let fixture = Fixture::new().await;
+    let processor = fixture.processor_with_config(
+        ProcessorConfig {
+            compilation: CompilationPolicy {
+                va_threshold_distinct_artists: Some(3),
+                va_artist_name: "Various".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        OffsetClock(ChronoDuration::zero()),
+    );
+
+    let metadata = vec![
+        tagged("A", "Hits"),
+        tagged("B", "Hits"),
+        tagged("C", "Hits"),
+        tagged("D", "Hits"),
+        tagged("Guest", "Duets"),
+        tagged("Singer", "Duets"),
+        tagged("Singer", "Duets"),
+    ];
+    let relations = processor.resolve_relations(&metadata).await.unwrap();
+
+    // Four artists make a compilation, each track keeping its own artist
+    let hits = relations[0].album_id.unwrap().to_string();
+    assert!(relations[..4]
+        .iter()
+        .all(|r| r.album_id.map(|id| id.to_string()) == Some(hits.clone())));
+    assert_ne!(relations[0].artist_id, relations[1].artist_id);
+    assert_eq!(
+        album_artist_name(&fixture.db, &hits).await.as_deref(),
+        Some("Various")
+    );
+
+    // Two stay one album, under the artist on most of its tracks
+    let duets = relations[4].album_id.unwrap().to_string();
+    assert_eq!(relations[5].album_id, relations[4].album_id);
+    assert_eq!(
+        album_artist_name(&fixture.db, &duets).await.as_deref(),
+        Some("Singer")
+    );
+}
+
+#[core_async::test]
+async fn test_album_artist_tag_credits_album() {
+    let fixture = Fixture::new().await;
+    let processor = fixture.processor_with_config(
+        ProcessorConfig {
+            compilation: CompilationPolicy {
+                use_albumartist_tag: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        OffsetClock(ChronoDuration::zero()),
+    );
+
+    let mut first = tagged("A", "Soundtrack");
+    first.album_artist = Some("VA".to_string());
+    let mut second = tagged("B", "Soundtrack");
+    second.album_artist = Some("Various Artists".to_string());
+    let mut solo = tagged("Featured", "Record");
+    solo.album_artist = Some("Band".to_string());
+
+    let relations = processor
+        .resolve_relations(&[first, second, solo])
+        .await
+        .unwrap();
+
+    assert_eq!(relations[0].album_id, relations[1].album_id);
+    let soundtrack = relations[0].album_id.unwrap().to_string();
+    assert_eq!(
+        album_artist_name(&fixture.db, &soundtrack).await.as_deref(),
+        Some("Various Artists")
+    );
+    let record = relations[2].album_id.unwrap().to_string();
+    assert_eq!(
+        album_artist_name(&fixture.db, &record).await.as_deref(),
+        Some("Band")
+    );
+}
+
+#[core_async::test]
+async fn test_loudness_analysis_stores_gain() {
+    let fixture = Fixture::new().await;
+    fixture
+        .db
+        .execute(
+            "INSERT INTO providers (id, type, display_name, profile_id, created_at) \
+             VALUES ('test-provider', 'GoogleDrive', 'Test', 'test-profile', 1699200000)",
+            &[],
+        )
+        .await
+        .unwrap();
+    let processor = fixture.processor_with_config(
+        ProcessorConfig {
+            analyze_loudness: true,
+            ..Default::default()
+        },
+        OffsetClock(ChronoDuration::zero()),
+    );
+    let provider: Arc<dyn StorageProvider> = Arc::new(SampleProvider);
+    let work_item = WorkItem::new("file-1".to_string(), "audio/mpeg".to_string());
+
+    let result = processor
+        .process_work_item(&work_item, &provider, "test-provider", "song.mp3", false)
+        .await
+        .unwrap();
+
+    let track = SqliteTrackRepository::new(fixture.db.clone())
+        .find_by_id(&result.track_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(track.replay_gain_millibels.is_some());
+}
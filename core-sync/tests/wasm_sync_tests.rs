@@ -0,0 +1,298 @@
+//! Integration tests for the core-sync WASM bindings.
+//!
+//! These tests drive a complete full sync through `JsSyncCoordinator` using an
+//! in-memory storage provider and verify that library rows are written and that
+//! aborting a sync cancels it.
+
+#![cfg(target_arch = "wasm32")]
+
+use bridge_traits::{
+    database::{DatabaseAdapter, DatabaseConfig, QueryValue},
+    error::{BridgeError, Result as BridgeResult},
+    storage::{FileSystemAccess, RemoteFile, StorageProvider},
+    test_util::InMemorySecureStore,
+    DynAsyncRead, HttpClient, HttpRequest, HttpResponse,
+};
+use bridge_wasm::{WasmDbAdapter, WasmFileSystem};
+use bytes::Bytes;
+use core_auth::{AuthManager, ProfileId, ProviderKind};
+use core_runtime::events::EventBus;
+use core_sync::wasm::JsSyncCoordinator;
+use core_sync::{SyncConfig, SyncCoordinator, SyncJobId, SyncStatus};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::*;
+use web_sys::AbortController;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const SAMPLE_MP3: &[u8] = include_bytes!("../../core-metadata/tests/fixtures/sample.mp3");
+
+// ============================================================================
+// Mock Implementations
+// ============================================================================
+
+/// Storage provider that serves a fixed set of files from memory
+struct InMemoryProvider {
+    files: HashMap<String, (RemoteFile, Bytes)>,
+}
+
+impl InMemoryProvider {
+    fn new() -> Self {
+        Self {
+            files: HashMap::new(),
+        }
+    }
+
+    fn with_file(mut self, id: &str, name: &str, data: &'static [u8]) -> Self {
+        let file = RemoteFile {
+            id: id.to_string(),
+            name: name.to_string(),
+            mime_type: Some("audio/mpeg".to_string()),
+            size: Some(data.len() as u64),
+            created_at: Some(1234567890),
+            modified_at: Some(1234567890),
+            is_folder: false,
+            parent_ids: vec![],
+            md5_checksum: Some(format!("hash-{}", id)),
+            metadata: HashMap::new(),
+        };
+        self.files
+            .insert(id.to_string(), (file, Bytes::from_static(data)));
+        self
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl StorageProvider for InMemoryProvider {
+    async fn list_media(
+        &self,
+        _cursor: Option<String>,
+    ) -> BridgeResult<(Vec<RemoteFile>, Option<String>)> {
+        let files = self.files.values().map(|(file, _)| file.clone()).collect();
+        Ok((files, None))
+    }
+
+    async fn get_metadata(&self, file_id: &str) -> BridgeResult<RemoteFile> {
+        self.files
+            .get(file_id)
+            .map(|(file, _)| file.clone())
+            .ok_or_else(|| BridgeError::OperationFailed(format!("File not found: {}", file_id)))
+    }
+
+    async fn download(&self, file_id: &str, _range: Option<&str>) -> BridgeResult<Bytes> {
+        self.files
+            .get(file_id)
+            .map(|(_, data)| data.clone())
+            .ok_or_else(|| BridgeError::OperationFailed(format!("File not found: {}", file_id)))
+    }
+
+    async fn get_changes(
+        &self,
+        cursor: Option<String>,
+    ) -> BridgeResult<(Vec<RemoteFile>, Option<String>)> {
+        self.list_media(cursor).await
+    }
+}
+
+/// HTTP client that answers every token request with a valid token response
+struct MockTokenHttpClient;
+
+#[async_trait::async_trait(?Send)]
+impl HttpClient for MockTokenHttpClient {
+    async fn execute(&self, _request: HttpRequest) -> BridgeResult<HttpResponse> {
+        Ok(HttpResponse {
+            status: 200,
+            headers: HashMap::new(),
+            body: Bytes::from_static(
+                br#"{"access_token":"test-access","refresh_token":"test-refresh","expires_in":3600}"#,
+            ),
+        })
+    }
+
+    async fn download_stream(&self, _url: String) -> BridgeResult<Box<DynAsyncRead>> {
+        Err(BridgeError::NotAvailable("download_stream".to_string()))
+    }
+}
+
+// ============================================================================
+// Test Utilities
+// ============================================================================
+
+/// Sign in against the mock token endpoint and return the new profile
+async fn sign_in(auth_manager: &AuthManager) -> ProfileId {
+    let auth_url = auth_manager.sign_in(ProviderKind::GoogleDrive).await.unwrap();
+    let state = auth_url
+        .split(['?', '&'])
+        .find_map(|pair| pair.strip_prefix("state="))
+        .expect("auth URL has a state parameter")
+        .to_string();
+
+    auth_manager
+        .complete_sign_in(ProviderKind::GoogleDrive, "test-code".to_string(), state)
+        .await
+        .unwrap()
+}
+
+async fn setup(
+    provider: InMemoryProvider,
+) -> (JsSyncCoordinator, Arc<dyn DatabaseAdapter>, ProfileId) {
+    let db: Arc<dyn DatabaseAdapter> = Arc::new(
+        WasmDbAdapter::new(DatabaseConfig::new("wasm_sync_tests.db"))
+            .await
+            .unwrap(),
+    );
+    let file_system: Arc<dyn FileSystemAccess> =
+        Arc::new(WasmFileSystem::new("wasm-sync-tests").await.unwrap());
+
+    let event_bus = EventBus::new(100);
+    let auth_manager = Arc::new(AuthManager::new(
+        Arc::new(InMemorySecureStore::new()),
+        event_bus.clone(),
+        Arc::new(MockTokenHttpClient),
+    ));
+    let profile_id = sign_in(&auth_manager).await;
+
+    let config = SyncConfig {
+        max_concurrent_downloads: 1,
+        ..Default::default()
+    };
+    let coordinator = SyncCoordinator::new(
+        config,
+        auth_manager,
+        Arc::new(event_bus),
+        None,
+        file_system,
+        db.clone(),
+    )
+    .await
+    .unwrap();
+
+    let sync = JsSyncCoordinator::from_coordinator(Arc::new(coordinator));
+    sync.register_provider(ProviderKind::GoogleDrive, Arc::new(provider))
+        .await;
+
+    (sync, db, profile_id)
+}
+
+/// Count tracks whose provider file ID matches a SQL `LIKE` pattern
+async fn count_tracks(db: &Arc<dyn DatabaseAdapter>, pattern: &str) -> i64 {
+    let row = db
+        .query_one(
+            "SELECT COUNT(*) AS count FROM tracks WHERE provider_file_id LIKE ?",
+            &[QueryValue::Text(pattern.to_string())],
+        )
+        .await
+        .unwrap();
+    match row.get("count") {
+        Some(QueryValue::Integer(count)) => *count,
+        other => panic!("unexpected count value: {:?}", other),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[wasm_bindgen_test]
+async fn test_full_sync_creates_library_rows() {
+    let provider = InMemoryProvider::new().with_file("wasm-file-1", "sample.mp3", SAMPLE_MP3);
+    let (sync, db, profile_id) = setup(provider).await;
+
+    let job_id = sync
+        .inner()
+        .start_full_sync(profile_id)
+        .await
+        .unwrap();
+
+    let mut job = sync.inner().get_status(job_id).await.unwrap();
+    for _ in 0..100 {
+        if job.status.is_terminal() {
+            break;
+        }
+        core_async::time::sleep(Duration::from_millis(50)).await;
+        job = sync.inner().get_status(job_id).await.unwrap();
+    }
+
+    assert_eq!(job.status, SyncStatus::Completed);
+    assert_eq!(count_tracks(&db, "wasm-file-1").await, 1);
+    assert!(!sync.inner().is_sync_active(profile_id).await);
+}
+
+#[wasm_bindgen_test]
+async fn test_sync_history_lists_completed_job() {
+    let provider = InMemoryProvider::new().with_file("wasm-file-2", "sample.mp3", SAMPLE_MP3);
+    let (sync, _db, profile_id) = setup(provider).await;
+
+    let job_id = sync
+        .inner()
+        .start_full_sync(profile_id)
+        .await
+        .unwrap();
+
+    for _ in 0..100 {
+        if !sync.inner().is_sync_active(profile_id).await {
+            break;
+        }
+        core_async::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    let history = sync
+        .inner()
+        .list_history(ProviderKind::GoogleDrive, 10)
+        .await
+        .unwrap();
+    assert!(history.iter().any(|job| job.id == job_id));
+}
+
+#[wasm_bindgen_test]
+async fn test_abort_signal_cancels_running_sync() {
+    let provider = (0..20).fold(InMemoryProvider::new(), |provider, i| {
+        provider.with_file(&format!("wasm-abort-{}", i), "sample.mp3", SAMPLE_MP3)
+    });
+    let (sync, db, profile_id) = setup(provider).await;
+
+    let controller = AbortController::new().unwrap();
+    let job_id = JsFuture::from(sync.start_full_sync(profile_id.to_string(), Some(controller.signal())))
+        .await
+        .unwrap()
+        .as_string()
+        .unwrap();
+    let job_id = SyncJobId::from_string(&job_id).unwrap();
+
+    controller.abort();
+
+    let mut job = sync.inner().get_status(job_id).await.unwrap();
+    for _ in 0..100 {
+        if job.status.is_terminal() {
+            break;
+        }
+        core_async::time::sleep(Duration::from_millis(50)).await;
+        job = sync.inner().get_status(job_id).await.unwrap();
+    }
+    assert_eq!(job.status, SyncStatus::Cancelled);
+
+    // Work stops once cancelled: the processed count no longer grows
+    let processed = count_tracks(&db, "wasm-abort-%").await;
+    assert!(processed < 20);
+    core_async::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(count_tracks(&db, "wasm-abort-%").await, processed);
+}
+
+#[wasm_bindgen_test]
+async fn test_aborted_signal_rejects_start() {
+    let provider = InMemoryProvider::new().with_file("wasm-file-3", "sample.mp3", SAMPLE_MP3);
+    let (sync, _db, profile_id) = setup(provider).await;
+
+    let controller = AbortController::new().unwrap();
+    controller.abort();
+    let err = JsFuture::from(sync.start_full_sync(profile_id.to_string(), Some(controller.signal())))
+        .await
+        .unwrap_err();
+
+    let kind = js_sys::Reflect::get(&err, &"kind".into()).unwrap();
+    assert_eq!(kind.as_string().as_deref(), Some("Cancelled"));
+    assert!(!sync.inner().is_sync_active(profile_id).await);
+}
@@ -4,8 +4,9 @@
 
 use async_trait::async_trait;
 use bridge_traits::error::Result;
-use bridge_traits::http::HttpClient;
+use bridge_traits::http::{HttpClient, HttpRequest, RequestSigner, RetryPolicy};
 use bridge_traits::storage::{RemoteFile, StorageProvider};
+use bridge_traits::time::{Clock, SystemClock};
 use bytes::Bytes;
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
@@ -19,7 +20,7 @@ use crate::types::{ChangesListResponse, DriveFile, FilesListResponse, StartPageT
 const DRIVE_API_BASE: &str = "https://www.googleapis.com/drive/v3";
 
 /// Maximum results per page (Google Drive API limit)
-const MAX_PAGE_SIZE: u32 = 1000;
+pub const MAX_PAGE_SIZE: u32 = 1000;
 
 /// Fields to request for file resources
 const FILE_FIELDS: &str =
@@ -31,11 +32,13 @@ const FILE_FIELDS: &str =
 ///
 /// # Features
 ///
-/// - Paginated file listing with audio MIME type filtering
+/// - Paginated file listing with audio MIME type filtering and a configurable
+///   page size (defaults to the API maximum to minimise round trips)
 /// - Streaming downloads with range request support
 /// - Incremental sync using change tokens (pageToken)
-/// - Exponential backoff for rate limiting
+/// - Exponential backoff with jitter for rate limiting (see [`RetryPolicy`])
 /// - OAuth 2.0 authentication via `HttpClient`
+/// - Optional [`RequestSigner`] hook for deployment-specific headers
 ///
 /// # Example
 ///
@@ -52,6 +55,18 @@ pub struct GoogleDriveConnector {
 
     /// OAuth 2.0 access token
     access_token: String,
+
+    /// Results requested per page when listing files or changes
+    page_size: u32,
+
+    /// Hook that adds custom headers before each request is sent
+    request_signer: Option<Arc<dyn RequestSigner>>,
+
+    /// Retry count and backoff for rate-limited or failed API requests
+    retry_policy: RetryPolicy,
+
+    /// Seeds the backoff jitter
+    clock: Arc<dyn Clock>,
 }
 
 impl GoogleDriveConnector {
@@ -65,14 +80,75 @@ impl GoogleDriveConnector {
         Self {
             http_client,
             access_token,
+            page_size: MAX_PAGE_SIZE,
+            request_signer: None,
+            retry_policy: RetryPolicy::default(),
+            clock: Arc::new(SystemClock),
         }
     }
 
+    /// Set the retry count, backoff delays and jitter mode for API requests
+    ///
+    /// Defaults to [`RetryPolicy::default`]: 3 attempts with full-jitter
+    /// exponential backoff from 100ms, capped at 30s.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Use a custom clock to seed the backoff jitter (for deterministic tests)
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Set a hook that adds or modifies headers on every outgoing request
+    ///
+    /// The signer runs after the OAuth headers are set, so it can add headers
+    /// such as API gateway keys without touching token handling.
+    pub fn with_request_signer(mut self, signer: Arc<dyn RequestSigner>) -> Self {
+        self.request_signer = Some(signer);
+        self
+    }
+
+    /// Set the number of results requested per page (`pageSize`)
+    ///
+    /// Defaults to [`MAX_PAGE_SIZE`]. Smaller pages mean more round trips on
+    /// large libraries.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GoogleDriveError::InvalidPageSize` if `page_size` is zero or
+    /// larger than [`MAX_PAGE_SIZE`].
+    pub fn with_page_size(mut self, page_size: u32) -> crate::error::Result<Self> {
+        if page_size == 0 || page_size > MAX_PAGE_SIZE {
+            return Err(GoogleDriveError::InvalidPageSize {
+                page_size,
+                max: MAX_PAGE_SIZE,
+            });
+        }
+        self.page_size = page_size;
+        Ok(self)
+    }
+
+    /// Results requested per page
+    pub fn page_size(&self) -> u32 {
+        self.page_size
+    }
+
     /// Build authorization header value
     fn auth_header(&self) -> String {
         format!("Bearer {}", self.access_token)
     }
 
+    /// Send a request, running the request signer first if one is set
+    async fn send(&self, mut request: HttpRequest) -> Result<bridge_traits::http::HttpResponse> {
+        if let Some(signer) = &self.request_signer {
+            signer.sign(&mut request)?;
+        }
+        self.http_client.execute(request).await
+    }
+
     /// Parse RFC 3339 timestamp to Unix timestamp
     fn parse_timestamp(rfc3339: &str) -> Option<i64> {
         DateTime::parse_from_rfc3339(rfc3339)
@@ -101,13 +177,12 @@ impl GoogleDriveConnector {
 
     /// Execute API request with retry logic
     ///
-    /// Implements exponential backoff for rate limiting and transient errors.
+    /// Retries rate limiting and transient errors with the backoff configured
+    /// by the connector's [`RetryPolicy`].
     #[instrument(skip(self), fields(url = %url))]
-    async fn execute_with_retry(
-        &self,
-        url: String,
-        max_retries: u32,
-    ) -> Result<bridge_traits::http::HttpResponse> {
+    async fn execute_with_retry(&self, url: String) -> Result<bridge_traits::http::HttpResponse> {
+        let max_retries = self.retry_policy.max_attempts.max(1);
+        let mut backoff = self.retry_policy.backoff_from_clock(self.clock.as_ref());
         let mut attempt = 0;
 
         loop {
@@ -115,15 +190,17 @@ impl GoogleDriveConnector {
             headers.insert("Authorization".to_string(), self.auth_header());
             headers.insert("Accept".to_string(), "application/json".to_string());
 
-            let request = bridge_traits::http::HttpRequest {
+            let request = HttpRequest {
                 method: bridge_traits::http::HttpMethod::Get,
                 url: url.clone(),
                 headers,
                 body: None,
                 timeout: Some(core_async::time::Duration::from_secs(30)),
+                max_retries: None,
+                content_length: None,
             };
 
-            match self.http_client.execute(request).await {
+            match self.send(request).await {
                 Ok(response) => {
                     let status = response.status;
 
@@ -145,15 +222,15 @@ impl GoogleDriveConnector {
                             .into());
                         }
 
-                        let backoff_ms = 100u64 * 2u64.pow(attempt);
+                        let delay = backoff.next_delay();
                         warn!(
                             "API request failed (attempt {}/{}): status={}, retrying in {}ms",
-                            attempt, max_retries, status, backoff_ms
+                            attempt,
+                            max_retries,
+                            status,
+                            delay.as_millis()
                         );
-                        core_async::time::sleep(core_async::time::Duration::from_millis(
-                            backoff_ms,
-                        ))
-                        .await;
+                        core_async::time::sleep(delay).await;
                     } else {
                         // Client error - don't retry
                         warn!("API request failed: status={}", status);
@@ -171,13 +248,15 @@ impl GoogleDriveConnector {
                         return Err(e);
                     }
 
-                    let backoff_ms = 100u64 * 2u64.pow(attempt);
+                    let delay = backoff.next_delay();
                     warn!(
                         "API request failed (attempt {}/{}): {}, retrying in {}ms",
-                        attempt, max_retries, e, backoff_ms
+                        attempt,
+                        max_retries,
+                        e,
+                        delay.as_millis()
                     );
-                    core_async::time::sleep(core_async::time::Duration::from_millis(backoff_ms))
-                        .await;
+                    core_async::time::sleep(delay).await;
                 }
             }
         }
@@ -202,7 +281,7 @@ impl StorageProvider for GoogleDriveConnector {
             "{}/files?q={}&pageSize={}&fields=nextPageToken,incompleteSearch,files({})",
             DRIVE_API_BASE,
             urlencoding::encode(query),
-            MAX_PAGE_SIZE,
+            self.page_size,
             FILE_FIELDS
         );
 
@@ -211,7 +290,7 @@ impl StorageProvider for GoogleDriveConnector {
         }
 
         // Execute request
-        let response = self.execute_with_retry(url, 3).await?;
+        let response = self.execute_with_retry(url).await?;
 
         // Parse response body directly (it's already Bytes)
         let list_response: FilesListResponse =
@@ -240,7 +319,7 @@ impl StorageProvider for GoogleDriveConnector {
             DRIVE_API_BASE, file_id, FILE_FIELDS
         );
 
-        let response = self.execute_with_retry(url, 3).await?;
+        let response = self.execute_with_retry(url).await?;
 
         // Parse response body directly
         let drive_file: DriveFile = serde_json::from_slice(&response.body).map_err(|e| {
@@ -250,6 +329,47 @@ impl StorageProvider for GoogleDriveConnector {
         Ok(self.convert_file(drive_file))
     }
 
+    #[instrument(skip(self), fields(folder_id = %folder_id))]
+    async fn list_folder(&self, folder_id: &str) -> Result<Vec<RemoteFile>> {
+        let query = format!("'{}' in parents and trashed=false", folder_id);
+        let mut files = Vec::new();
+        let mut page_token: Option<String> = None;
+
+        loop {
+            let mut url = format!(
+                "{}/files?q={}&pageSize={}&fields=nextPageToken,files({})",
+                DRIVE_API_BASE,
+                urlencoding::encode(&query),
+                self.page_size,
+                FILE_FIELDS
+            );
+            if let Some(token) = &page_token {
+                url.push_str(&format!("&pageToken={}", urlencoding::encode(token)));
+            }
+
+            let response = self.execute_with_retry(url).await?;
+            let list_response: FilesListResponse =
+                serde_json::from_slice(&response.body).map_err(|e| {
+                    GoogleDriveError::ParseError(format!("Failed to parse folder listing: {}", e))
+                })?;
+
+            files.extend(
+                list_response
+                    .files
+                    .into_iter()
+                    .map(|f| self.convert_file(f)),
+            );
+
+            match list_response.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+
+        debug!(count = files.len(), "Listed folder contents");
+        Ok(files)
+    }
+
     #[instrument(skip(self), fields(file_id = %file_id, range = ?range))]
     async fn download(&self, file_id: &str, range: Option<&str>) -> Result<Bytes> {
         info!("Downloading file: {}", file_id);
@@ -263,15 +383,17 @@ impl StorageProvider for GoogleDriveConnector {
             headers.insert("Range".to_string(), range_value.to_string());
         }
 
-        let request = bridge_traits::http::HttpRequest {
+        let request = HttpRequest {
             method: bridge_traits::http::HttpMethod::Get,
             url,
             headers,
             body: None,
             timeout: Some(core_async::time::Duration::from_secs(60)),
+            max_retries: None,
+            content_length: None,
         };
 
-        let response = self.http_client.execute(request).await?;
+        let response = self.send(request).await?;
 
         if response.status == 200 || response.status == 206 {
             info!("Downloaded {} bytes", response.body.len());
@@ -285,6 +407,11 @@ impl StorageProvider for GoogleDriveConnector {
         }
     }
 
+    fn supports_range(&self) -> bool {
+        // Media downloads honour the `Range` header (206 Partial Content)
+        true
+    }
+
     #[instrument(skip(self))]
     async fn get_changes(
         &self,
@@ -297,7 +424,7 @@ impl StorageProvider for GoogleDriveConnector {
             token
         } else {
             let url = format!("{}/changes/startPageToken", DRIVE_API_BASE);
-            let response = self.execute_with_retry(url, 3).await?;
+            let response = self.execute_with_retry(url).await?;
 
             // Parse start page token directly
             let start_token: StartPageTokenResponse = serde_json::from_slice(&response.body)
@@ -310,13 +437,14 @@ impl StorageProvider for GoogleDriveConnector {
 
         // Get changes
         let url = format!(
-            "{}/changes?pageToken={}&fields=nextPageToken,newStartPageToken,changes(type,time,removed,file({}),fileId)",
+            "{}/changes?pageToken={}&pageSize={}&fields=nextPageToken,newStartPageToken,changes(type,time,removed,file({}),fileId)",
             DRIVE_API_BASE,
             urlencoding::encode(&page_token),
+            self.page_size,
             FILE_FIELDS
         );
 
-        let response = self.execute_with_retry(url, 3).await?;
+        let response = self.execute_with_retry(url).await?;
 
         // Parse changes list directly
         let changes_list: ChangesListResponse =
@@ -374,7 +502,7 @@ mod tests {
 
         #[async_trait]
         impl HttpClient for HttpClient {
-            async fn execute(&self, request: bridge_traits::http::HttpRequest) -> Result<bridge_traits::http::HttpResponse>;
+            async fn execute(&self, request: HttpRequest) -> Result<bridge_traits::http::HttpResponse>;
             async fn download_stream(&self, url: String) -> Result<Box<dyn core_async::io::AsyncRead + Send + Unpin>>;
         }
     }
@@ -499,6 +627,39 @@ mod tests {
         assert_eq!(file.mime_type, Some("audio/mpeg".to_string()));
     }
 
+    #[core_async::test]
+    async fn test_list_folder_queries_parent() {
+        let mut mock_http = MockHttpClient::new();
+
+        mock_http
+            .expect_execute()
+            .times(1)
+            .withf(|req| {
+                query_param(&req.url, "q")
+                    == Some("%27folder1%27%20in%20parents%20and%20trashed%3Dfalse")
+            })
+            .returning(|_| {
+                let response_body = r#"{
+                    "files": [
+                        {"id": "file1", "name": "song.mp3", "mimeType": "audio/mpeg", "createdTime": "2024-01-01T00:00:00.000Z", "modifiedTime": "2024-01-01T00:00:00.000Z", "parents": ["folder1"]},
+                        {"id": "file2", "name": "cover.jpg", "mimeType": "image/jpeg", "createdTime": "2024-01-01T00:00:00.000Z", "modifiedTime": "2024-01-01T00:00:00.000Z", "parents": ["folder1"]}
+                    ]
+                }"#;
+
+                Ok(bridge_traits::http::HttpResponse {
+                    status: 200,
+                    headers: HashMap::new(),
+                    body: Bytes::from(response_body.as_bytes()),
+                })
+            });
+
+        let connector = GoogleDriveConnector::new(Arc::new(mock_http), "test_token".to_string());
+        let files = connector.list_folder("folder1").await.unwrap();
+
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[1].name, "cover.jpg");
+    }
+
     #[core_async::test]
     async fn test_download_success() {
         let mut mock_http = MockHttpClient::new();
@@ -626,6 +787,274 @@ mod tests {
         assert_eq!(files[0].metadata.get("removed"), Some(&"true".to_string()));
     }
 
+    /// Read a query parameter from a request URL
+    fn query_param<'a>(url: &'a str, name: &str) -> Option<&'a str> {
+        url.split(['?', '&'])
+            .find_map(|pair| pair.strip_prefix(name)?.strip_prefix('='))
+    }
+
+    /// Mock HTTP client that pages through `total_files` files using the
+    /// request's `pageSize`, recording the page size of every request
+    fn paged_http_client(
+        total_files: usize,
+        page_sizes: Arc<std::sync::Mutex<Vec<u32>>>,
+    ) -> MockHttpClient {
+        let mut mock_http = MockHttpClient::new();
+
+        mock_http.expect_execute().returning(move |req| {
+            let page_size: usize = query_param(&req.url, "pageSize").unwrap().parse().unwrap();
+            page_sizes.lock().unwrap().push(page_size as u32);
+
+            let start: usize = query_param(&req.url, "pageToken")
+                .map(|token| token.parse().unwrap())
+                .unwrap_or(0);
+            let end = (start + page_size).min(total_files);
+
+            let files: Vec<serde_json::Value> = (start..end)
+                .map(|i| {
+                    serde_json::json!({
+                        "id": format!("file{}", i),
+                        "name": format!("song{}.mp3", i),
+                        "mimeType": "audio/mpeg",
+                        "createdTime": "2024-01-01T00:00:00.000Z",
+                        "modifiedTime": "2024-01-01T00:00:00.000Z",
+                        "trashed": false
+                    })
+                })
+                .collect();
+            let mut body = serde_json::json!({ "files": files });
+            if end < total_files {
+                body["nextPageToken"] = serde_json::json!(end.to_string());
+            }
+
+            Ok(bridge_traits::http::HttpResponse {
+                status: 200,
+                headers: HashMap::new(),
+                body: Bytes::from(serde_json::to_vec(&body).unwrap()),
+            })
+        });
+
+        mock_http
+    }
+
+    /// List every page and return the number of files found
+    async fn list_all(connector: &GoogleDriveConnector) -> usize {
+        let mut total = 0;
+        let mut cursor = None;
+        loop {
+            let (files, next_cursor) = connector.list_media(cursor).await.unwrap();
+            total += files.len();
+            cursor = next_cursor;
+            if cursor.is_none() {
+                return total;
+            }
+        }
+    }
+
+    #[core_async::test]
+    async fn test_list_media_uses_max_page_size_by_default() {
+        let page_sizes = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mock_http = paged_http_client(3, page_sizes.clone());
+
+        let connector = GoogleDriveConnector::new(Arc::new(mock_http), "test_token".to_string());
+        assert_eq!(connector.page_size(), MAX_PAGE_SIZE);
+        assert_eq!(list_all(&connector).await, 3);
+
+        assert_eq!(*page_sizes.lock().unwrap(), vec![MAX_PAGE_SIZE]);
+    }
+
+    #[core_async::test]
+    async fn test_list_media_uses_configured_page_size() {
+        let small_pages = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let connector = GoogleDriveConnector::new(
+            Arc::new(paged_http_client(10, small_pages.clone())),
+            "test_token".to_string(),
+        )
+        .with_page_size(2)
+        .unwrap();
+        assert_eq!(list_all(&connector).await, 10);
+
+        let large_pages = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let connector = GoogleDriveConnector::new(
+            Arc::new(paged_http_client(10, large_pages.clone())),
+            "test_token".to_string(),
+        )
+        .with_page_size(5)
+        .unwrap();
+        assert_eq!(list_all(&connector).await, 10);
+
+        // Every request carries the configured size, and larger pages need fewer requests
+        assert_eq!(*small_pages.lock().unwrap(), vec![2; 5]);
+        assert_eq!(*large_pages.lock().unwrap(), vec![5; 2]);
+    }
+
+    #[test]
+    fn test_page_size_validation() {
+        let connector =
+            || GoogleDriveConnector::new(Arc::new(MockHttpClient::new()), "test_token".to_string());
+
+        assert!(matches!(
+            connector().with_page_size(0),
+            Err(GoogleDriveError::InvalidPageSize { page_size: 0, .. })
+        ));
+        assert!(matches!(
+            connector().with_page_size(MAX_PAGE_SIZE + 1),
+            Err(GoogleDriveError::InvalidPageSize {
+                max: MAX_PAGE_SIZE,
+                ..
+            })
+        ));
+        assert_eq!(
+            connector()
+                .with_page_size(MAX_PAGE_SIZE)
+                .unwrap()
+                .page_size(),
+            MAX_PAGE_SIZE
+        );
+    }
+
+    #[core_async::test]
+    async fn test_request_signer_adds_headers() {
+        let mut mock_http = MockHttpClient::new();
+
+        mock_http.expect_execute().times(2).returning(|req| {
+            assert_eq!(req.headers.get("X-Org-Id"), Some(&"acme".to_string()));
+            // OAuth is still handled by the connector
+            assert_eq!(
+                req.headers.get("Authorization"),
+                Some(&"Bearer test_token".to_string())
+            );
+
+            let body = if req.url.contains("alt=media") {
+                Bytes::from_static(b"audio")
+            } else {
+                Bytes::from_static(br#"{"files": []}"#)
+            };
+            Ok(bridge_traits::http::HttpResponse {
+                status: 200,
+                headers: HashMap::new(),
+                body,
+            })
+        });
+
+        let signer: Arc<dyn RequestSigner> = Arc::new(|request: &mut HttpRequest| {
+            request
+                .headers
+                .insert("X-Org-Id".to_string(), "acme".to_string());
+            Ok(())
+        });
+        let connector = GoogleDriveConnector::new(Arc::new(mock_http), "test_token".to_string())
+            .with_request_signer(signer);
+
+        connector.list_media(None).await.unwrap();
+        connector.download("file1", None).await.unwrap();
+    }
+
+    #[core_async::test]
+    async fn test_request_signer_error_aborts_request() {
+        let mut mock_http = MockHttpClient::new();
+        mock_http.expect_execute().never();
+
+        let signer: Arc<dyn RequestSigner> = Arc::new(|_: &mut HttpRequest| {
+            Err(bridge_traits::error::BridgeError::OperationFailed(
+                "gateway key unavailable".to_string(),
+            ))
+        });
+        let connector = GoogleDriveConnector::new(Arc::new(mock_http), "test_token".to_string())
+            .with_request_signer(signer);
+
+        assert!(connector.download("file1", None).await.is_err());
+    }
+
+    /// Clock frozen at a fixed instant, so backoff jitter is reproducible
+    struct FixedClock;
+
+    impl Clock for FixedClock {
+        fn now(&self) -> DateTime<Utc> {
+            DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc)
+        }
+    }
+
+    fn fast_retry_policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            base_delay: std::time::Duration::from_millis(1),
+            max_delay: std::time::Duration::from_millis(5),
+            ..Default::default()
+        }
+    }
+
+    #[core_async::test]
+    async fn test_retries_transient_errors_per_policy() {
+        let mut mock_http = MockHttpClient::new();
+        let mut sequence = mockall::Sequence::new();
+
+        mock_http
+            .expect_execute()
+            .times(2)
+            .in_sequence(&mut sequence)
+            .returning(|_| {
+                Ok(bridge_traits::http::HttpResponse {
+                    status: 503,
+                    headers: HashMap::new(),
+                    body: Bytes::new(),
+                })
+            });
+        mock_http
+            .expect_execute()
+            .times(1)
+            .in_sequence(&mut sequence)
+            .returning(|_| {
+                Ok(bridge_traits::http::HttpResponse {
+                    status: 200,
+                    headers: HashMap::new(),
+                    body: Bytes::from_static(br#"{"files": []}"#),
+                })
+            });
+
+        let connector = GoogleDriveConnector::new(Arc::new(mock_http), "test_token".to_string())
+            .with_retry_policy(fast_retry_policy(3))
+            .with_clock(Arc::new(FixedClock));
+
+        let (files, _) = connector.list_media(None).await.unwrap();
+        assert!(files.is_empty());
+    }
+
+    #[core_async::test]
+    async fn test_gives_up_after_max_attempts() {
+        let mut mock_http = MockHttpClient::new();
+
+        mock_http.expect_execute().times(2).returning(|_| {
+            Ok(bridge_traits::http::HttpResponse {
+                status: 429,
+                headers: HashMap::new(),
+                body: Bytes::new(),
+            })
+        });
+
+        let connector = GoogleDriveConnector::new(Arc::new(mock_http), "test_token".to_string())
+            .with_retry_policy(fast_retry_policy(2))
+            .with_clock(Arc::new(FixedClock));
+
+        assert!(connector.list_media(None).await.is_err());
+    }
+
+    #[test]
+    fn test_backoff_seeded_from_clock_is_reproducible() {
+        let policy = fast_retry_policy(10);
+        let schedule = || {
+            let mut backoff = policy.backoff_from_clock(&FixedClock);
+            (0..10).map(|_| backoff.next_delay()).collect::<Vec<_>>()
+        };
+
+        let delays = schedule();
+        assert_eq!(delays, schedule());
+        assert!(delays.iter().all(|delay| *delay <= policy.max_delay));
+        assert!(delays.windows(2).any(|pair| pair[0] != pair[1]));
+    }
+
     #[core_async::test]
     async fn test_api_error_handling() {
         let mut mock_http = MockHttpClient::new();
@@ -33,6 +33,10 @@ pub enum GoogleDriveError {
     #[error("Invalid or expired change token: {0}")]
     InvalidChangeToken(String),
 
+    /// Requested page size is outside the API limits
+    #[error("Invalid page size {page_size}: must be between 1 and {max}")]
+    InvalidPageSize { page_size: u32, max: u32 },
+
     /// Bridge error
     #[error(transparent)]
     BridgeError(#[from] bridge_traits::error::BridgeError),
@@ -84,6 +88,12 @@ impl From<GoogleDriveError> for bridge_traits::error::BridgeError {
                     msg
                 ))
             }
+            GoogleDriveError::InvalidPageSize { page_size, max } => {
+                bridge_traits::error::BridgeError::OperationFailed(format!(
+                    "Invalid page size {}: must be between 1 and {}",
+                    page_size, max
+                ))
+            }
             GoogleDriveError::BridgeError(e) => e,
         }
     }
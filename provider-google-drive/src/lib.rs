@@ -55,6 +55,6 @@ pub mod connector;
 pub mod error;
 pub mod types;
 
-pub use connector::GoogleDriveConnector;
+pub use connector::{GoogleDriveConnector, MAX_PAGE_SIZE};
 pub use error::{GoogleDriveError, Result};
 pub use types::{ChangesListResponse, DriveFile, FilesListResponse, StartPageTokenResponse};
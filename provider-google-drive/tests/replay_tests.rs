@@ -0,0 +1,55 @@
+//! Replay tests for the Google Drive connector
+//!
+//! These tests drive the connector against recorded API responses in
+//! `tests/fixtures`, so they exercise real response shapes without live
+//! credentials. Re-record fixtures with `RecordingHttpClient` when the API
+//! calls change.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use bridge_traits::storage::StorageProvider;
+use bridge_traits::test_util::ReplayHttpClient;
+use provider_google_drive::GoogleDriveConnector;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+fn connector(fixture: &str) -> GoogleDriveConnector {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(fixture);
+    let replay = ReplayHttpClient::from_file(path).expect("fixture loads");
+    GoogleDriveConnector::new(Arc::new(replay), "test_token".to_string())
+}
+
+#[core_async::test]
+async fn test_list_media_follows_recorded_pages() {
+    let connector = connector("drive_library.json");
+
+    let (first_page, cursor) = connector.list_media(None).await.unwrap();
+    assert_eq!(first_page.len(), 1);
+    assert_eq!(first_page[0].name, "first.mp3");
+    assert_eq!(first_page[0].parent_ids, vec!["music".to_string()]);
+    assert_eq!(cursor.as_deref(), Some("page2"));
+
+    let (second_page, cursor) = connector.list_media(cursor).await.unwrap();
+    assert_eq!(second_page.len(), 1);
+    assert_eq!(second_page[0].id, "file2");
+    assert_eq!(second_page[0].md5_checksum.as_deref(), Some("abcfile2"));
+    assert!(cursor.is_none());
+}
+
+#[core_async::test]
+async fn test_download_replays_binary_body() {
+    let connector = connector("drive_library.json");
+
+    let data = connector.download("file1", None).await.unwrap();
+    assert_eq!(&data[..], b"ID3\x04\x00");
+}
+
+#[core_async::test]
+async fn test_unrecorded_request_fails() {
+    let connector = connector("drive_library.json");
+
+    assert!(connector.get_metadata("file1").await.is_err());
+}